@@ -82,3 +82,16 @@ fn panic_reason_description() {
         }
     }
 }
+
+#[test]
+fn opcode_table_contains_each_valid_byte() {
+    let valid = (0..=u8::MAX)
+        .filter_map(|byte| Opcode::try_from(byte).ok())
+        .collect::<Vec<_>>();
+
+    assert_eq!(Opcode::ALL.len(), valid.len());
+    for opcode in valid {
+        assert!(Opcode::ALL.contains(&opcode));
+        assert_eq!(opcode.mnemonic(), alloc::format!("{opcode:?}"));
+    }
+}
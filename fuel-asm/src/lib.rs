@@ -21,6 +21,7 @@ pub mod macros;
 pub mod op;
 mod pack;
 mod panic_reason;
+mod parse;
 mod unpack;
 
 #[cfg(test)]
@@ -41,6 +42,10 @@ pub type Word = u64;
 
 pub use panic_instruction::PanicInstruction;
 pub use panic_reason::PanicReason;
+pub use parse::{
+    parse_lines,
+    ParseError,
+};
 
 /// Represents a 6-bit register ID, guaranteed to be masked by construction.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -200,9 +205,9 @@ impl_instructions! {
     0x3C TR tr [contract_id_addr: RegId amount: RegId asset_id_addr: RegId]
     "Transfer coins to a variable output."
     0x3D TRO tro [contract_id_addr: RegId output_index: RegId amount: RegId asset_id_addr: RegId]
-    "The 64-byte public key (x, y) recovered from 64-byte signature on 32-byte message."
+    "The 64-byte public key (x, y) recovered from 64-byte signature on 32-byte message. The high `s` form of the signature is accepted."
     0x3E ECK1 eck1 [dst_addr: RegId sig_addr: RegId msg_hash_addr: RegId]
-    "The 64-byte Secp256r1 public key (x, y) recovered from 64-byte signature on 32-byte message."
+    "The 64-byte Secp256r1 public key (x, y) recovered from 64-byte signature on 32-byte message. The high `s` form of the signature is accepted."
     0x3F ECR1 ecr1 [dst_addr: RegId sig_addr: RegId msg_hash_addr: RegId]
     "Verify ED25519 public key and signature match a 32-byte message."
     0x40 ED19 ed19 [pub_key_addr: RegId sig_addr: RegId msg_hash_addr: RegId]
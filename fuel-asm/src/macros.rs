@@ -58,6 +58,14 @@
 //! assert_eq!(Opcode::try_from(0), Err(InvalidOpcode));
 //! ```
 //!
+//! The table itself is available as `Opcode::ALL`, with the `mnemonic` of each opcode.
+//!
+//! ```rust
+//! # use fuel_asm::Opcode;
+//! assert_eq!(Opcode::ALL[0], Opcode::ADD);
+//! assert_eq!(Opcode::ADD.mnemonic(), "ADD");
+//! ```
+//!
 //! ## The `Instruction` enum
 //!
 //! Represents a single, full instruction, discriminated by its `Opcode`.
@@ -1117,6 +1125,18 @@ macro_rules! impl_instructions {
         }
 
         impl Opcode {
+            /// All the opcodes, in the order of the instruction table.
+            pub const ALL: &'static [Self] = &[$(Self::$Op,)*];
+
+            /// The name of the opcode in the assembly, in uppercase, like `ADD`.
+            pub const fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$Op => stringify!($Op),
+                    )*
+                }
+            }
+
             /// Construct the instruction from all possible raw fields, ignoring inapplicable ones.
             #[cfg(test)]
             pub fn test_construct(self, ra: RegId, rb: RegId, rc: RegId, rd: RegId, imm: u32) -> Instruction {
@@ -1190,6 +1210,28 @@ macro_rules! impl_instructions {
         }
     };
 
+    // Implement the construction of the instructions from their textual assembly.
+    (impl_parse $($doc:literal $ix:literal $Op:ident $op:ident [$($fname:ident: $field:ident)*])*) => {
+        impl Instruction {
+            /// Construct the instruction named by the `mnemonic` from the next `operands`.
+            fn from_mnemonic<'a, I>(mnemonic: &str, operands: &mut I) -> Result<Self, parse::ParseError>
+            where
+                I: Iterator<Item = &'a str>,
+            {
+                $(
+                    if mnemonic.eq_ignore_ascii_case(stringify!($Op)) {
+                        return Ok(Self::$Op(op::$Op::new($(
+                            <$field as parse::Operand>::parse(
+                                operands.next().ok_or(parse::ParseError::MissingOperand)?,
+                            )?,
+                        )*)))
+                    }
+                )*
+                Err(parse::ParseError::UnknownMnemonic)
+            }
+        }
+    };
+
     // Entrypoint to the macro, generates structs, methods, opcode enum and instruction enum
     // separately.
     ($($tts:tt)*) => {
@@ -1202,6 +1244,7 @@ macro_rules! impl_instructions {
         impl_instructions!(decl_instruction_enum $($tts)*);
         impl_instructions!(impl_opcode $($tts)*);
         impl_instructions!(impl_instruction $($tts)*);
+        impl_instructions!(impl_parse $($tts)*);
         impl_instructions!(impl_opcode_test_construct $($tts)*);
     };
 }
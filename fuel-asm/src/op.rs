@@ -316,3 +316,15 @@ const _: () = {
         crate::op::wqdv_args(ra, rb, rc, args).into()
     }
 };
+
+/// Construct the `gtf` and `addi` sequence loading into `ra` the VM address of the
+/// script data value at `offset`.
+///
+/// Panics if `offset` is out of range for a 12-bit immediate.
+pub fn script_data_address<A: CheckRegId>(ra: A, offset: u16) -> [Instruction; 2] {
+    let ra = ra.check();
+    [
+        gtf_args(ra, RegId::ZERO, GTFArgs::ScriptData),
+        addi(ra, ra, offset),
+    ]
+}
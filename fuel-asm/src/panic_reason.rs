@@ -3,12 +3,21 @@ use core::fmt;
 macro_rules! enum_from {
     (
         $(#[$meta:meta])* $vis:vis enum $name:ident {
-            $($(#[$vmeta:meta])* $vname:ident $(= $val:expr)?,)*
+            $($(#[doc = $doc:literal])* $vname:ident $(= $val:expr)?,)*
         }
     ) => {
         $(#[$meta])*
         $vis enum $name {
-            $($(#[$vmeta])* $vname $(= $val)?,)*
+            $($(#[doc = $doc])* $vname $(= $val)?,)*
+        }
+
+        impl $name {
+            /// The description of the reason, from its documentation.
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $($name::$vname => concat!($($doc),*).trim(),)*
+                }
+            }
         }
 
         impl From<u8> for $name {
@@ -147,6 +156,9 @@ enum_from! {
         BytecodeAlreadyUploaded = 0x34,
         /// The part of the bytecode is not sequentially connected to the previous parts.
         ThePartIsNotSequentiallyConnected = 0x35,
+        /// The contract is a read-only input of the transaction and its state or
+        /// balances can't be modified.
+        ContractNotWritable = 0x36,
     }
 }
 
@@ -167,6 +179,19 @@ mod tests {
     use super::*;
     use strum::IntoEnumIterator;
 
+    #[test]
+    fn description_joins_the_lines_of_the_documentation() {
+        assert_eq!(
+            PanicReason::OutOfGas.description(),
+            "Execution ran out of gas."
+        );
+        assert_eq!(
+            PanicReason::PredicateReturnedNonOne.description(),
+            "The predicate returned non `1`. The `1` means successful verification \
+             of the predicate, all other values means unsuccessful."
+        );
+    }
+
     #[test]
     fn test_u8_panic_reason_round_trip() {
         let last_known_panic_reason: u8 = PanicReason::iter().last().unwrap() as u8 + 1;
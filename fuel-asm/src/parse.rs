@@ -0,0 +1,258 @@
+//! Parsing of the textual assembly of the instructions.
+//!
+//! An instruction is written as its lowercase or uppercase mnemonic followed by its
+//! operands, separated by whitespace or commas, e.g. `addi $r16 $one 0x2a`.
+//!
+//! - A register is either one of the named registers, like `$zero` or `$hp`, a numbered
+//!   register like `$r16`, or the plain number of the register.
+//! - An immediate is a decimal, `0x` hexadecimal or `0b` binary number that must fit in
+//!   the width of the immediate.
+//!
+//! Everything after `//` on a line is a comment.
+
+use core::{
+    fmt,
+    str::FromStr,
+};
+
+use crate::{
+    Imm06,
+    Imm12,
+    Imm18,
+    Imm24,
+    Instruction,
+    RegId,
+};
+
+/// Failed to parse the textual assembly of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// The line contains no instruction.
+    Empty,
+    /// The mnemonic doesn't name an instruction.
+    UnknownMnemonic,
+    /// The instruction has fewer operands than its layout.
+    MissingOperand,
+    /// The instruction has more operands than its layout.
+    UnexpectedOperand,
+    /// The operand isn't a register.
+    InvalidRegister,
+    /// The operand isn't a number, or overflows the width of the immediate.
+    InvalidImmediate,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Empty => "no instruction",
+            Self::UnknownMnemonic => "unknown mnemonic",
+            Self::MissingOperand => "missing operand",
+            Self::UnexpectedOperand => "unexpected operand",
+            Self::InvalidRegister => "invalid register",
+            Self::InvalidImmediate => "invalid immediate",
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The names of the reserved registers, with their `$` prefix.
+const NAMED_REGISTERS: [(&str, RegId); 16] = [
+    ("$zero", RegId::ZERO),
+    ("$one", RegId::ONE),
+    ("$of", RegId::OF),
+    ("$pc", RegId::PC),
+    ("$ssp", RegId::SSP),
+    ("$sp", RegId::SP),
+    ("$fp", RegId::FP),
+    ("$hp", RegId::HP),
+    ("$err", RegId::ERR),
+    ("$ggas", RegId::GGAS),
+    ("$cgas", RegId::CGAS),
+    ("$bal", RegId::BAL),
+    ("$is", RegId::IS),
+    ("$ret", RegId::RET),
+    ("$retl", RegId::RETL),
+    ("$flag", RegId::FLAG),
+];
+
+/// Formats the register in its textual assembly: the name of the reserved registers,
+/// or `$r` followed by the number of the register.
+impl fmt::Display for RegId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match NAMED_REGISTERS
+            .iter()
+            .find(|(_, register)| register == self)
+        {
+            Some((name, _)) => f.write_str(name),
+            None => write!(f, "$r{}", self.to_u8()),
+        }
+    }
+}
+
+fn number(token: &str) -> Option<u32> {
+    let (digits, radix) = if let Some(hex) = token.strip_prefix("0x") {
+        (hex, 16)
+    } else if let Some(binary) = token.strip_prefix("0b") {
+        (binary, 2)
+    } else {
+        (token, 10)
+    };
+    let mut digits = digits.chars().filter(|c| *c != '_').peekable();
+    digits.peek()?;
+    digits.try_fold(0u32, |value, digit| {
+        value
+            .checked_mul(radix)?
+            .checked_add(digit.to_digit(radix)?)
+    })
+}
+
+/// An operand of the layout of an instruction.
+pub(crate) trait Operand: Sized {
+    fn parse(token: &str) -> Result<Self, ParseError>;
+}
+
+impl FromStr for RegId {
+    type Err = ParseError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        if let Some((_, register)) = NAMED_REGISTERS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(token))
+        {
+            return Ok(*register)
+        }
+        let index = token.strip_prefix("$r").unwrap_or(token);
+        number(index)
+            .and_then(|index| u8::try_from(index).ok())
+            .and_then(RegId::new_checked)
+            .ok_or(ParseError::InvalidRegister)
+    }
+}
+
+impl Operand for RegId {
+    fn parse(token: &str) -> Result<Self, ParseError> {
+        token.parse()
+    }
+}
+
+macro_rules! impl_operand_for_immediate {
+    ($($Imm:ident: $ty:ty),*) => {
+        $(
+            impl Operand for $Imm {
+                fn parse(token: &str) -> Result<Self, ParseError> {
+                    number(token)
+                        .and_then(|value| <$ty>::try_from(value).ok())
+                        .and_then($Imm::new_checked)
+                        .ok_or(ParseError::InvalidImmediate)
+                }
+            }
+        )*
+    };
+}
+
+impl_operand_for_immediate!(Imm06: u8, Imm12: u16, Imm18: u32, Imm24: u32);
+
+/// Removes the comment of the line.
+fn strip_comment(line: &str) -> &str {
+    line.split_once("//").map_or(line, |(code, _)| code)
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = strip_comment(line)
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty());
+        let mnemonic = tokens.next().ok_or(ParseError::Empty)?;
+        let instruction = Self::from_mnemonic(mnemonic, &mut tokens)?;
+        match tokens.next() {
+            Some(_) => Err(ParseError::UnexpectedOperand),
+            None => Ok(instruction),
+        }
+    }
+}
+
+/// Parses the instructions of a program, one per line, skipping the lines without
+/// instruction.
+///
+/// Each item is the zero-based index of the line with its parsed instruction.
+pub fn parse_lines(
+    program: &str,
+) -> impl Iterator<Item = (usize, Result<Instruction, ParseError>)> + '_ {
+    program
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.parse()))
+        .filter(|(_, instruction)| *instruction != Err(ParseError::Empty))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::op;
+
+    #[test]
+    fn from_str__parses_the_named_and_numbered_registers() {
+        assert_eq!("add $r16 $one $hp".parse(), Ok(op::add(0x10, 0x01, 0x07)));
+        assert_eq!("ADD 16, 1, 0x07".parse(), Ok(op::add(0x10, 0x01, 0x07)));
+        assert_eq!("move $r63 $RETL".parse(), Ok(op::move_(0x3f, 0x0e)));
+    }
+
+    #[test]
+    fn from_str__parses_the_immediates_of_each_width() {
+        assert_eq!(
+            "wdcm $r16 $r17 $r18 0b101".parse(),
+            Ok(op::wdcm(0x10, 0x11, 0x12, 0b101))
+        );
+        assert_eq!("addi $r16 $zero 4095".parse(), Ok(op::addi(0x10, 0, 4095)));
+        assert_eq!("movi $r16 0x3_ffff".parse(), Ok(op::movi(0x10, 0x3ffff)));
+        assert_eq!("ji 0xffffff".parse(), Ok(op::ji(0xffffff)));
+        assert_eq!("noop // nothing".parse(), Ok(op::noop()));
+    }
+
+    #[test]
+    fn from_str__rejects_the_invalid_instructions() {
+        let parse = |line: &str| line.parse::<Instruction>();
+        assert_eq!(parse("  // comment"), Err(ParseError::Empty));
+        assert_eq!(parse("mov $r16 $one"), Err(ParseError::UnknownMnemonic));
+        assert_eq!(parse("add $r16 $one"), Err(ParseError::MissingOperand));
+        assert_eq!(parse("ret $one $one"), Err(ParseError::UnexpectedOperand));
+        assert_eq!(parse("ret $r64"), Err(ParseError::InvalidRegister));
+        assert_eq!(parse("ret $sp2"), Err(ParseError::InvalidRegister));
+        assert_eq!(
+            parse("addi $r16 $zero 4096"),
+            Err(ParseError::InvalidImmediate)
+        );
+        assert_eq!(parse("movi $r16 -1"), Err(ParseError::InvalidImmediate));
+    }
+
+    #[test]
+    fn display__renders_the_registers_parsed_back() {
+        for register in (0..64).map(RegId::new) {
+            assert_eq!(register.to_string().parse::<RegId>(), Ok(register));
+        }
+        assert_eq!(RegId::HP.to_string(), "$hp");
+        assert_eq!(RegId::new(0x10).to_string(), "$r16");
+    }
+
+    #[test]
+    fn parse_lines__skips_the_lines_without_instruction() {
+        let program = "movi $r16 32\n\n// allocate\naloc $r16\nfoo";
+
+        let lines: Vec<_> = parse_lines(program).collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                (0, Ok(op::movi(0x10, 32))),
+                (3, Ok(op::aloc(0x10))),
+                (4, Err(ParseError::UnknownMnemonic)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use fuel_crypto::{
+    batch::verify_recover,
+    Message,
+    SecretKey,
+    Signature,
+};
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+
+fn batch(c: &mut Criterion) {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    // The signatures of 64 inputs of the same transaction
+    let message = Message::new(b"The id of a transaction with many signed inputs.");
+    let batch: Vec<_> = (0..64)
+        .map(|_| (Signature::sign(&SecretKey::random(rng), &message), message))
+        .collect();
+
+    let mut group = c.benchmark_group("recover-64");
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            for (signature, message) in black_box(&batch) {
+                signature.recover(message).expect("valid signature");
+            }
+        })
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| verify_recover(black_box(&batch)).expect("valid signatures"))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, batch);
+criterion_main!(benches);
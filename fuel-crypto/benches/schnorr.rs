@@ -0,0 +1,80 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use fuel_crypto::{
+    schnorr,
+    Message,
+    SecretKey,
+    Signature,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+fn schnorr(c: &mut Criterion) {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"A message signed in the Taproot style.");
+    let public = schnorr::public_key(&secret);
+    let signature = schnorr::sign(&secret, &*message, &rng.gen()).expect("signed");
+    let ecdsa = Signature::sign(&secret, &message);
+
+    let mut group = c.benchmark_group("schnorr");
+
+    group.bench_function("sign", |b| {
+        b.iter(|| schnorr::sign(black_box(&secret), black_box(&*message), &[0; 32]))
+    });
+
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            schnorr::verify(black_box(&public), black_box(&signature), &*message)
+                .expect("valid signature")
+        })
+    });
+
+    // The cost of `eck1` for comparison
+    group.bench_function("ecdsa-recover", |b| {
+        b.iter(|| {
+            black_box(&ecdsa)
+                .recover(&message)
+                .expect("valid signature")
+        })
+    });
+
+    group.finish();
+
+    let messages: Vec<[u8; 32]> = (0..64).map(|_| rng.gen()).collect();
+    let batch: Vec<_> = messages
+        .iter()
+        .map(|message| {
+            let secret = SecretKey::random(rng);
+            let signature = schnorr::sign(&secret, message, &rng.gen()).expect("signed");
+            (schnorr::public_key(&secret), signature, message.as_slice())
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("schnorr-verify-64");
+
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            for (public, signature, message) in black_box(&batch) {
+                schnorr::verify(public, signature, message).expect("valid signature");
+            }
+        })
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| schnorr::verify_batch(black_box(&batch)).expect("valid signatures"))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, schnorr);
+criterion_main!(benches);
@@ -140,7 +140,7 @@ fn signatures(c: &mut Criterion) {
 
     group_sign.bench_with_input(
         "fuel-crypto-sign",
-        &(fc_key, fc_message),
+        &(fc_key.clone(), fc_message),
         |b, (key, message)| {
             b.iter(|| fuel_crypto::Signature::sign(black_box(key), black_box(message)))
         },
@@ -237,6 +237,27 @@ fn signatures(c: &mut Criterion) {
         |b, (signature, message)| b.iter(|| signature.recover(black_box(message))),
     );
 
+    group_recover.bench_with_input(
+        "fuel-crypto-with-context",
+        &(fc_signature, fc_message),
+        |b, (signature, message)| {
+            fuel_crypto::with_context(|context| {
+                b.iter(|| context.recover(signature, black_box(message)))
+            })
+        },
+    );
+
+    group_recover.bench_with_input(
+        "secp256k1-context-per-call",
+        &(s2_recoverable, s2_message),
+        |b, (recoverable, message)| {
+            b.iter(|| {
+                secp256k1::Secp256k1::verification_only()
+                    .recover_ecdsa(black_box(message), black_box(recoverable))
+            })
+        },
+    );
+
     group_recover.bench_with_input(
         "secp256k1",
         &(s2_secp, s2_recoverable, s2_message),
@@ -0,0 +1,74 @@
+//! Verification of many signatures at once.
+
+use crate::Error;
+#[cfg(feature = "alloc")]
+use crate::{
+    Message,
+    PublicKey,
+    Signature,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The failure of a signature of the batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchError {
+    /// The index of the first failed signature of the batch.
+    pub index: usize,
+    /// The failure of the signature.
+    pub error: Error,
+}
+
+/// Recovers the public keys of all the signatures of the `batch`, in the order of the
+/// batch.
+///
+/// Fails with the index of the first signature that can't be recovered. With the
+/// `parallel` feature, the signatures are recovered on the rayon thread pool, and the
+/// reported failure is still the one of the lowest index. All the signatures share the
+/// secp256k1 context of the crate.
+#[cfg(feature = "alloc")]
+pub fn verify_recover(
+    batch: &[(Signature, Message)],
+) -> Result<Vec<PublicKey>, BatchError> {
+    #[cfg(feature = "parallel")]
+    let recovered: Vec<_> = {
+        use rayon::prelude::*;
+
+        batch
+            .par_iter()
+            .map(|(signature, message)| signature.recover(message))
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let recovered = batch
+        .iter()
+        .map(|(signature, message)| signature.recover(message));
+
+    recovered
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| result.map_err(|error| BatchError { index, error }))
+        .collect()
+}
+
+#[cfg(feature = "std")]
+mod use_std {
+    use super::BatchError;
+    use std::{
+        error,
+        fmt,
+    };
+
+    impl fmt::Display for BatchError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "signature {} failed: {}", self.index, self.error)
+        }
+    }
+
+    impl error::Error for BatchError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            Some(&self.error)
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! ED25519 signature verification
 
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use ed25519_dalek::Signature;
 use fuel_types::{
     Bytes32,
@@ -7,6 +8,7 @@ use fuel_types::{
 };
 
 use crate::{
+    batch::BatchError,
     message::Message,
     Error,
 };
@@ -28,3 +30,70 @@ pub fn verify(
         Err(Error::InvalidSignature)
     }
 }
+
+/// Verify the signatures of a batch of messages of any length, with the rules of
+/// [`verify`].
+///
+/// Fails with the index of the first invalid signature. The signatures are verified one
+/// by one: the randomized batch equation of `ed25519-dalek` accepts some signatures
+/// rejected by the strict verification, and can't tell which signature is invalid.
+pub fn verify_batch(batch: &[(Bytes32, Bytes64, &[u8])]) -> Result<(), BatchError> {
+    batch
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, (pub_key, signature, message))| {
+            let mut verifier = StreamVerifier::new(pub_key, signature)
+                .map_err(|error| BatchError { index, error })?;
+            verifier.update(message);
+            verifier
+                .finalize()
+                .map_err(|error| BatchError { index, error })
+        })
+}
+
+/// Verifier of a signature over a message fed in chunks, for the messages that don't
+/// fit in memory. Follows the rules of [`verify`].
+pub struct StreamVerifier {
+    verifier: ed25519_dalek::StreamVerifier,
+}
+
+impl StreamVerifier {
+    /// Starts the verification of the `signature` by the `pub_key`.
+    ///
+    /// Fails if the public key or the signature can't be valid, whatever the message.
+    pub fn new(pub_key: &Bytes32, signature: &Bytes64) -> Result<Self, Error> {
+        let signature = Signature::from_bytes(signature);
+
+        let pub_key = ed25519_dalek::VerifyingKey::from_bytes(pub_key)
+            .map_err(|_| Error::InvalidPublicKey)?;
+        if pub_key.is_weak() {
+            return Err(Error::InvalidSignature)
+        }
+
+        // The strict verification rejects the small order `R` as well
+        let r = CompressedEdwardsY(*signature.r_bytes())
+            .decompress()
+            .ok_or(Error::InvalidSignature)?;
+        if r.is_small_order() {
+            return Err(Error::InvalidSignature)
+        }
+
+        let verifier = pub_key
+            .verify_stream(&signature)
+            .map_err(|_| Error::InvalidSignature)?;
+
+        Ok(Self { verifier })
+    }
+
+    /// Appends the `chunk` to the verified message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.verifier.update(chunk)
+    }
+
+    /// Checks the signature against the whole message.
+    pub fn finalize(self) -> Result<(), Error> {
+        self.verifier
+            .finalize_and_verify()
+            .map_err(|_| Error::InvalidSignature)
+    }
+}
@@ -8,27 +8,110 @@ use sha2::{
 use core::iter;
 
 /// Standard hasher
+///
+/// # Keys, domains and chunks
+///
+/// - The keyed hasher of [`Hasher::new_keyed`] computes the HMAC-SHA256 of its input, as
+///   defined by RFC 2104.
+/// - A length-prefixed chunk, absorbed by [`Hasher::input_prefixed`], is absorbed as its
+///   length as a big-endian `u64` followed by its bytes. The chunks can't be split or
+///   merged without changing the digest, unlike the plain concatenation of
+///   [`Hasher::input`].
+/// - The domain separation tag of [`Hasher::with_domain`] is the first chunk.
 #[derive(Debug, Default, Clone)]
-pub struct Hasher(Sha256);
+pub struct Hasher {
+    hash: Sha256,
+    key: Option<HmacKey>,
+}
+
+/// The states of the keyed hasher after absorbing the padded key.
+#[derive(Debug, Clone)]
+struct HmacKey {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacKey {
+    /// The block size of SHA-256
+    const BLOCK_LEN: usize = 64;
+
+    fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; Self::BLOCK_LEN];
+        if key.len() > Self::BLOCK_LEN {
+            block[..Bytes32::LEN].copy_from_slice(&*Hasher::hash(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let pad = |byte: u8| {
+            let mut pad = block;
+            pad.iter_mut().for_each(|b| *b ^= byte);
+            Sha256::new().chain(pad)
+        };
+
+        Self {
+            inner: pad(0x36),
+            outer: pad(0x5c),
+        }
+    }
+}
 
 impl Hasher {
     /// Length of the output
     pub const OUTPUT_LEN: usize = Bytes32::LEN;
 
+    /// Create a hasher computing the HMAC-SHA256 of its input with the `key`
+    pub fn new_keyed(key: &[u8]) -> Self {
+        let key = HmacKey::new(key);
+
+        Self {
+            hash: key.inner.clone(),
+            key: Some(key),
+        }
+    }
+
+    /// Create a hasher that absorbed the domain separation `tag` as a length-prefixed
+    /// chunk
+    pub fn with_domain(tag: &str) -> Self {
+        Self::default().chain_prefixed(tag)
+    }
+
     /// Append data to the hasher
     pub fn input<B>(&mut self, data: B)
     where
         B: AsRef<[u8]>,
     {
-        sha2::Digest::update(&mut self.0, data)
+        sha2::Digest::update(&mut self.hash, data)
+    }
+
+    /// Append the data to the hasher as a length-prefixed chunk
+    pub fn input_prefixed<B>(&mut self, data: B)
+    where
+        B: AsRef<[u8]>,
+    {
+        let data = data.as_ref();
+        self.input((data.len() as u64).to_be_bytes());
+        self.input(data);
     }
 
     /// Consume, append data and return the hasher
-    pub fn chain<B>(self, data: B) -> Self
+    pub fn chain<B>(mut self, data: B) -> Self
     where
         B: AsRef<[u8]>,
     {
-        Self(self.0.chain(data))
+        self.input(data);
+
+        self
+    }
+
+    /// Consume, append data as a length-prefixed chunk and return the hasher
+    pub fn chain_prefixed<B>(mut self, data: B) -> Self
+    where
+        B: AsRef<[u8]>,
+    {
+        self.input_prefixed(data);
+
+        self
     }
 
     /// Consume, append the items of the iterator and return the hasher
@@ -42,9 +125,12 @@ impl Hasher {
         self
     }
 
-    /// Reset the hasher to the default state
+    /// Reset the hasher to the default state, keeping its key
     pub fn reset(&mut self) {
-        self.0.reset();
+        match &self.key {
+            Some(key) => self.hash = key.inner.clone(),
+            None => self.hash.reset(),
+        }
     }
 
     /// Hash the provided data, returning its digest
@@ -59,14 +145,50 @@ impl Hasher {
         <[u8; Bytes32::LEN]>::from(hasher.finalize()).into()
     }
 
+    /// Hash the items of the iterator as length-prefixed chunks, returning the digest
+    pub fn hash_many<B, I>(iter: I) -> Bytes32
+    where
+        B: AsRef<[u8]>,
+        I: IntoIterator<Item = B>,
+    {
+        iter.into_iter()
+            .fold(Self::default(), Self::chain_prefixed)
+            .finalize()
+    }
+
     /// Consume the hasher, returning the digest
     pub fn finalize(self) -> Bytes32 {
-        <[u8; Bytes32::LEN]>::from(self.0.finalize()).into()
+        let hash = self.hash.finalize();
+        let hash = match self.key {
+            Some(key) => key.outer.chain(hash).finalize(),
+            None => hash,
+        };
+
+        <[u8; Bytes32::LEN]>::from(hash).into()
     }
 
     /// Return the digest without consuming the hasher
     pub fn digest(&self) -> Bytes32 {
-        <[u8; Bytes32::LEN]>::from(self.0.clone().finalize()).into()
+        self.clone().finalize()
+    }
+}
+
+impl fuel_types::canonical::Output for Hasher {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), fuel_types::canonical::Error> {
+        self.input(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.input(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
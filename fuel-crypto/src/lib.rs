@@ -10,6 +10,9 @@
 #![deny(unused_crate_dependencies)]
 #![deny(clippy::cast_possible_truncation)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // Satisfy unused_crate_dependencies lint for self-dependency enabling test features
 #[cfg(test)]
 use fuel_crypto as _;
@@ -35,15 +38,36 @@ mod hasher;
 mod message;
 mod mnemonic;
 mod secp256;
+mod signer;
 
+pub mod batch;
 pub mod ed25519;
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_vectors;
+#[cfg(feature = "wallet")]
+pub mod wallet;
 
 pub use secp256::backend::r1 as secp256r1;
 
+#[cfg(feature = "std")]
+pub use secp256::backend::k1::secp256k1::{
+    with_context,
+    Context,
+};
+
+#[cfg(all(feature = "mlock", unix))]
+pub use secp256::LockedSecretKey;
+// Only used to lock memory on the unix targets
+#[cfg(all(feature = "mlock", not(unix)))]
+use libc as _;
+
 pub use secp256::{
     PublicKey,
     SecretKey,
     Signature,
+    SignatureMode,
 };
 
 #[cfg(test)]
@@ -52,6 +76,12 @@ mod tests;
 pub use error::Error;
 pub use hasher::Hasher;
 pub use message::Message;
+#[cfg(feature = "async")]
+pub use signer::AsyncSigner;
+pub use signer::{
+    Signer,
+    SignerError,
+};
 
 #[cfg(all(feature = "std", feature = "random"))]
 pub use mnemonic::generate_mnemonic_phrase;
@@ -6,6 +6,9 @@ use core::{
 pub use fuel_types::Bytes32;
 
 /// Normalized (hashed) message authenticated by a signature
+///
+/// `==` may return as soon as a byte differs, use [`Message::ct_eq`] to compare values
+/// guarding an authentication decision.
 #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
@@ -24,6 +27,11 @@ impl Message {
         Self(Hasher::hash(message))
     }
 
+    /// Equality in time independent of the bytes of the messages.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
+
     /// Construct a `Message` directly from its bytes.
     ///
     /// This constructor expects the given bytes to be a valid,
@@ -0,0 +1,98 @@
+//! BIP340 Schnorr signatures over secp256k1
+//!
+//! The public keys are the 32 bytes `x` coordinates of the points with an even `y`, and
+//! the signatures are the 64 bytes `(r, s)` pairs. The messages are signed as they are,
+//! without hashing them first. Signing with a [`SecretKey`] uses its point with an even
+//! `y`, negating the key if needed, so any secp256k1 secret key can sign.
+
+use fuel_types::{
+    Bytes32,
+    Bytes64,
+};
+use k256::schnorr::{
+    signature::hazmat::PrehashVerifier,
+    Signature,
+    SigningKey,
+    VerifyingKey,
+};
+
+use crate::{
+    batch::BatchError,
+    Error,
+    Hasher,
+    SecretKey,
+};
+
+/// Creates a hasher of the BIP340 tagged hash construction,
+/// `sha256(sha256(tag) || sha256(tag) || data)`, that absorbed the `tag`.
+pub fn tagged_hasher(tag: &str) -> Hasher {
+    let tag = Hasher::hash(tag);
+
+    Hasher::default().chain(tag).chain(tag)
+}
+
+impl From<&SecretKey> for SigningKey {
+    fn from(secret: &SecretKey) -> Self {
+        SigningKey::from_bytes(secret.as_ref())
+            .expect("SecretKey is guaranteed to be valid")
+    }
+}
+
+/// Derives the x-only public key of the `secret`.
+pub fn public_key(secret: &SecretKey) -> Bytes32 {
+    let signing_key = SigningKey::from(secret);
+    let public_key: [u8; Bytes32::LEN] = signing_key.verifying_key().to_bytes().into();
+
+    public_key.into()
+}
+
+/// Signs the `message` with the `secret`.
+///
+/// The auxiliary randomness `aux_rand` protects the nonce from side-channel attacks;
+/// the signature is deterministic for a given `aux_rand`, and stays secure with a fixed
+/// one.
+pub fn sign(
+    secret: &SecretKey,
+    message: &[u8],
+    aux_rand: &[u8; 32],
+) -> Result<Bytes64, Error> {
+    let signature = SigningKey::from(secret)
+        .sign_raw(message, aux_rand)
+        .map_err(|_| Error::FailedToSign)?;
+    let signature: [u8; Bytes64::LEN] = signature.to_bytes();
+
+    Ok(signature.into())
+}
+
+/// Verifies the `signature` of the `message` by the x-only `public_key`.
+///
+/// Fails with [`Error::InvalidPublicKey`] if the key isn't the `x` coordinate of a
+/// point, and with [`Error::InvalidSignature`] if `r` isn't a field element, `s` isn't a
+/// scalar, or the signature doesn't match.
+pub fn verify(
+    public_key: &Bytes32,
+    signature: &Bytes64,
+    message: &[u8],
+) -> Result<(), Error> {
+    let public_key =
+        VerifyingKey::from_bytes(&**public_key).map_err(|_| Error::InvalidPublicKey)?;
+    let signature =
+        Signature::try_from(&signature[..]).map_err(|_| Error::InvalidSignature)?;
+
+    public_key
+        .verify_prehash(message, &signature)
+        .map_err(|_| Error::InvalidSignature)
+}
+
+/// Verifies the signatures of a batch of messages, with the rules of [`verify`].
+///
+/// Fails with the index of the first invalid signature.
+pub fn verify_batch(batch: &[(Bytes32, Bytes64, &[u8])]) -> Result<(), BatchError> {
+    batch
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, (public_key, signature, message))| {
+            verify(public_key, signature, message)
+                .map_err(|error| BatchError { index, error })
+        })
+}
@@ -1,10 +1,17 @@
 pub(crate) mod backend;
 
+#[cfg(all(feature = "mlock", unix))]
+mod locked;
 mod public;
 mod secret;
 mod signature;
 mod signature_format;
 
+#[cfg(all(feature = "mlock", unix))]
+pub use locked::LockedSecretKey;
 pub use public::PublicKey;
 pub use secret::SecretKey;
-pub use signature::Signature;
+pub use signature::{
+    Signature,
+    SignatureMode,
+};
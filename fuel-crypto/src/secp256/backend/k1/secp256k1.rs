@@ -20,6 +20,10 @@ use secp256k1::{
 };
 
 use crate::SecretKey;
+use core::cell::{
+    Cell,
+    RefCell,
+};
 
 #[cfg(feature = "random")]
 use rand::{
@@ -27,8 +31,140 @@ use rand::{
     RngCore,
 };
 
-lazy_static::lazy_static! {
-    static ref CONTEXT: Secp256k1<secp256k1::All> = Secp256k1::new();
+/// The number of secret key operations after which the context of a thread is
+/// rerandomized.
+const RERANDOMIZATION_INTERVAL: u32 = 1024;
+
+std::thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::new());
+}
+
+/// The secp256k1 context of the current thread.
+///
+/// The context is allocated once per thread, on its first use, and rerandomized
+/// periodically to protect the secret keys from side-channel attacks.
+pub struct Context {
+    secp: Secp256k1<secp256k1::All>,
+    // The secret key operations since the last rerandomization
+    operations: Cell<u32>,
+}
+
+impl Context {
+    fn new() -> Self {
+        // The `rand-std` feature randomizes the new context
+        Self {
+            secp: Secp256k1::new(),
+            operations: Cell::new(0),
+        }
+    }
+
+    fn count_secret_operation(&self) {
+        self.operations.set(self.operations.get().saturating_add(1));
+    }
+
+    /// Derives the public key from a given secret key
+    pub fn public_key(&self, secret: &SecretKey) -> PublicKey {
+        self.count_secret_operation();
+        let sk: secp256k1::SecretKey = secret.into();
+        let vk = secp256k1::PublicKey::from_secret_key(&self.secp, &sk);
+        vk.into()
+    }
+
+    /// Sign a given message and compress the `v` to the signature
+    ///
+    /// The compression scheme is described in
+    /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/protocol/cryptographic-primitives.md>
+    pub fn sign(&self, secret: &SecretKey, message: &Message) -> crate::Signature {
+        crate::Signature::from_bytes(self.sign_raw(secret, message))
+    }
+
+    fn sign_raw(&self, secret: &SecretKey, message: &Message) -> [u8; 64] {
+        self.count_secret_operation();
+        let signature = self
+            .secp
+            .sign_ecdsa_recoverable(&message.into(), &secret.into());
+        let (recovery_id, signature) = signature.serialize_compact();
+
+        // encode_signature cannot panic as we don't generate reduced-x recovery ids.
+        let recovery_id = SecpRecoveryId::try_from(recovery_id)
+            .expect("reduced-x recovery ids are never generated");
+        encode_signature(signature, recovery_id)
+    }
+
+    /// Recover the public key from a signature.
+    pub fn recover(
+        &self,
+        signature: &crate::Signature,
+        message: &Message,
+    ) -> Result<PublicKey, Error> {
+        self.recover_raw(**signature, message)
+    }
+
+    fn recover_raw(
+        &self,
+        signature: [u8; 64],
+        message: &Message,
+    ) -> Result<PublicKey, Error> {
+        let (signature, recovery_id) = decode_signature(signature);
+        let recoverable =
+            RecoverableSignature::from_compact(&signature, recovery_id.into())
+                .map_err(|_| Error::InvalidSignature)?;
+        let vk = self
+            .secp
+            .recover_ecdsa(&message.into(), &recoverable)
+            .map_err(|_| Error::InvalidSignature)?;
+        Ok(PublicKey::from(vk))
+    }
+
+    /// Verify that a signature matches given public key
+    pub fn verify(
+        &self,
+        signature: &crate::Signature,
+        public_key: &PublicKey,
+        message: &Message,
+    ) -> Result<(), Error> {
+        self.verify_raw(**signature, **public_key, message)
+    }
+
+    fn verify_raw(
+        &self,
+        signature: [u8; 64],
+        public_key: [u8; 64],
+        message: &Message,
+    ) -> Result<(), Error> {
+        let (signature, _) = decode_signature(signature); // Trunactes recovery id
+        let signature =
+            Signature::from_compact(&signature).map_err(|_| Error::InvalidSignature)?;
+
+        let mut prefixed_public_key = [0u8; 65];
+        prefixed_public_key[0] = 0x04; // Uncompressed
+        prefixed_public_key[1..].copy_from_slice(&public_key);
+        let vk = secp256k1::PublicKey::from_slice(&prefixed_public_key)
+            .map_err(|_| Error::InvalidPublicKey)?;
+
+        self.secp
+            .verify_ecdsa(&message.into(), &signature, &vk)
+            .map_err(|_| Error::InvalidSignature)?;
+        Ok(())
+    }
+}
+
+/// Calls `f` with the secp256k1 context of the current thread.
+///
+/// The free functions of the crate, like [`crate::Signature::recover`], use the same
+/// context. Callers doing bulk work can borrow it once for all the operations.
+pub fn with_context<R>(f: impl FnOnce(&Context) -> R) -> R {
+    CONTEXT.with(|context| {
+        // The context can't be rerandomized while it's borrowed by an outer call
+        if let Ok(mut context) = context.try_borrow_mut() {
+            if context.operations.get() >= RERANDOMIZATION_INTERVAL {
+                context.secp.randomize(&mut secp256k1::rand::thread_rng());
+                context.operations.set(0);
+            }
+        }
+
+        f(&context.borrow())
+    })
 }
 
 /// Generates a random secret key
@@ -39,9 +175,7 @@ pub fn random_secret(rng: &mut (impl CryptoRng + RngCore)) -> SecretKey {
 
 /// Derives the public key from a given secret key
 pub fn public_key(secret: &SecretKey) -> PublicKey {
-    let sk: secp256k1::SecretKey = secret.into();
-    let vk = secp256k1::PublicKey::from_secret_key(&CONTEXT, &sk);
-    vk.into()
+    with_context(|context| context.public_key(secret))
 }
 
 /// Sign a given message and compress the `v` to the signature
@@ -49,13 +183,7 @@ pub fn public_key(secret: &SecretKey) -> PublicKey {
 /// The compression scheme is described in
 /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/protocol/cryptographic-primitives.md>
 pub fn sign(secret: &SecretKey, message: &Message) -> [u8; 64] {
-    let signature = CONTEXT.sign_ecdsa_recoverable(&message.into(), &secret.into());
-    let (recovery_id, signature) = signature.serialize_compact();
-
-    // encode_signature cannot panic as we don't generate reduced-x recovery ids.
-    let recovery_id = SecpRecoveryId::try_from(recovery_id)
-        .expect("reduced-x recovery ids are never generated");
-    encode_signature(signature, recovery_id)
+    with_context(|context| context.sign_raw(secret, message))
 }
 
 /// Recover the public key from a signature.
@@ -64,13 +192,7 @@ pub fn sign(secret: &SecretKey, message: &Message) -> [u8; 64] {
 /// taken signature will not be recoverable. Signatures are meant to be
 /// single use, so this avoids unnecessary copy.
 pub fn recover(signature: [u8; 64], message: &Message) -> Result<PublicKey, Error> {
-    let (signature, recovery_id) = decode_signature(signature);
-    let recoverable = RecoverableSignature::from_compact(&signature, recovery_id.into())
-        .map_err(|_| Error::InvalidSignature)?;
-    let vk = CONTEXT
-        .recover_ecdsa(&message.into(), &recoverable)
-        .map_err(|_| Error::InvalidSignature)?;
-    Ok(PublicKey::from(vk))
+    with_context(|context| context.recover_raw(signature, message))
 }
 
 /// Verify that a signature matches given public key
@@ -79,20 +201,7 @@ pub fn verify(
     public_key: [u8; 64],
     message: &Message,
 ) -> Result<(), Error> {
-    let (signature, _) = decode_signature(signature); // Trunactes recovery id
-    let signature =
-        Signature::from_compact(&signature).map_err(|_| Error::InvalidSignature)?;
-
-    let mut prefixed_public_key = [0u8; 65];
-    prefixed_public_key[0] = 0x04; // Uncompressed
-    prefixed_public_key[1..].copy_from_slice(&public_key);
-    let vk = secp256k1::PublicKey::from_slice(&prefixed_public_key)
-        .map_err(|_| Error::InvalidPublicKey)?;
-
-    CONTEXT
-        .verify_ecdsa(&message.into(), &signature, &vk)
-        .map_err(|_| Error::InvalidSignature)?;
-    Ok(())
+    with_context(|context| context.verify_raw(signature, public_key, message))
 }
 
 #[cfg(all(test, feature = "std"))]
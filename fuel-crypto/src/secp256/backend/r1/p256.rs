@@ -6,6 +6,7 @@ use crate::{
     message::Message,
     secp256::signature_format::decode_signature,
     Error,
+    SignatureMode,
 };
 #[cfg(feature = "test-helpers")]
 use ecdsa::RecoveryId;
@@ -66,8 +67,29 @@ pub fn encode_pubkey(key: VerifyingKey) -> [u8; 64] {
     result
 }
 
+/// Recover a public key from a signature and a message digest, handling the high `s`
+/// value according to the `mode`. It assumes a compacted signature
+pub fn recover_with_mode(
+    signature: &Bytes64,
+    message: &Message,
+    mode: SignatureMode,
+) -> Result<Bytes64, Error> {
+    if mode == SignatureMode::Strict {
+        let (sig, _) = decode_signature(**signature);
+        let sig = p256::ecdsa::Signature::from_slice(&sig)
+            .map_err(|_| Error::InvalidSignature)?;
+        if sig.normalize_s().is_some() {
+            return Err(Error::InvalidSignature)
+        }
+    }
+
+    recover(signature, message)
+}
+
 /// Recover a public key from a signature and a message digest. It assumes
 /// a compacted signature
+///
+/// Accepts the high `s` signatures, like [`SignatureMode::Lax`].
 pub fn recover(signature: &Bytes64, message: &Message) -> Result<Bytes64, Error> {
     let (sig, recid) = decode_signature(**signature);
     let sig =
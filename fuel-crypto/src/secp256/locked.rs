@@ -0,0 +1,72 @@
+use crate::{
+    Error,
+    SecretKey,
+};
+
+use alloc::boxed::Box;
+use core::{
+    fmt,
+    mem,
+    ops::Deref,
+};
+
+use zeroize::Zeroize;
+
+/// A [`SecretKey`] kept on the heap, in memory pages locked out of the swap
+///
+/// The key is zeroed before its pages are unlocked on drop.
+pub struct LockedSecretKey(Box<SecretKey>);
+
+impl LockedSecretKey {
+    /// Moves the `secret` to locked memory
+    ///
+    /// Fails with [`Error::NotEnoughMemory`] if the process can't lock more memory, for
+    /// example because of `RLIMIT_MEMLOCK`.
+    pub fn new(secret: SecretKey) -> Result<Self, Error> {
+        let locked = Box::new(secret);
+
+        // SAFETY: the range is the live allocation of the box
+        #[allow(unsafe_code)]
+        let result = unsafe {
+            libc::mlock(
+                (&*locked as *const SecretKey).cast(),
+                mem::size_of::<SecretKey>(),
+            )
+        };
+
+        if result == 0 {
+            Ok(Self(locked))
+        } else {
+            Err(Error::NotEnoughMemory)
+        }
+    }
+}
+
+impl Deref for LockedSecretKey {
+    type Target = SecretKey;
+
+    fn deref(&self) -> &SecretKey {
+        &self.0
+    }
+}
+
+impl fmt::Debug for LockedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LockedSecretKey").field(&*self.0).finish()
+    }
+}
+
+impl Drop for LockedSecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+
+        // SAFETY: the range was locked by `new` and the box is still allocated
+        #[allow(unsafe_code)]
+        unsafe {
+            libc::munlock(
+                (&*self.0 as *const SecretKey).cast(),
+                mem::size_of::<SecretKey>(),
+            );
+        }
+    }
+}
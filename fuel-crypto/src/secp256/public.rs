@@ -13,6 +13,7 @@ use k256::ecdsa::VerifyingKey;
 use core::str;
 
 use fuel_types::{
+    Address,
     Bytes32,
     Bytes64,
 };
@@ -20,19 +21,107 @@ use k256::elliptic_curve::sec1::ToEncodedPoint;
 
 /// Asymmetric secp256k1 public key, i.e. verifying key, in uncompressed form.
 /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/protocol/cryptographic-primitives.md#ecdsa-public-key-cryptography>
+///
+/// `==` may return as soon as a byte differs, use [`PublicKey::ct_eq`] to compare values
+/// guarding an authentication decision.
 #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct PublicKey(Bytes64);
 
 impl PublicKey {
+    /// Length of the SEC1 compressed encoding in bytes.
+    pub const COMPRESSED_LEN: usize = 33;
     /// Memory length of the type in bytes.
     pub const LEN: usize = Bytes64::LEN;
+    /// Length of the SEC1 uncompressed encoding in bytes.
+    pub const UNCOMPRESSED_LEN: usize = 65;
+    /// SEC1 tag of the uncompressed encoding.
+    const UNCOMPRESSED_TAG: u8 = 0x04;
+
+    /// Equality in time independent of the bytes of the keys.
+    ///
+    /// A verifier checking that a signature was produced by an expected signer compares
+    /// them in constant time:
+    ///
+    /// ```
+    /// use fuel_crypto::{
+    ///     Message,
+    ///     PublicKey,
+    ///     SecretKey,
+    ///     Signature,
+    /// };
+    /// use fuel_types::Bytes32;
+    ///
+    /// let secret = SecretKey::try_from(Bytes32::from([7; 32])).expect("Valid key");
+    /// let expected = secret.public_key();
+    ///
+    /// let message = Message::new(b"Spend the coin");
+    /// let signature = Signature::sign(&secret, &message);
+    ///
+    /// let signer: PublicKey = signature.recover(&message).expect("Valid signature");
+    /// assert!(signer.ct_eq(&expected));
+    /// assert!(signer.address().ct_eq(&expected.address()));
+    /// ```
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
 
     /// Cryptographic hash of the public key.
     pub fn hash(&self) -> Bytes32 {
         Hasher::hash(self.as_ref())
     }
+
+    /// Address owning the coins and messages signed by the key, i.e. the hash of the
+    /// public key.
+    pub fn address(&self) -> Address {
+        let address: [u8; Address::LEN] = self.hash().into();
+
+        address.into()
+    }
+
+    /// SEC1 compressed encoding, the `0x02` or `0x03` tag of the parity of `y`
+    /// followed by `x`.
+    pub fn to_compressed(&self) -> [u8; Self::COMPRESSED_LEN] {
+        let mut compressed = [0u8; Self::COMPRESSED_LEN];
+        compressed[0] = 0x02 | (self.0[Self::LEN - 1] & 1);
+        compressed[1..].copy_from_slice(&self.0[..Self::LEN / 2]);
+        compressed
+    }
+
+    /// SEC1 uncompressed encoding, the `0x04` tag followed by `x` and `y`.
+    pub fn to_uncompressed(&self) -> [u8; Self::UNCOMPRESSED_LEN] {
+        let mut uncompressed = [0u8; Self::UNCOMPRESSED_LEN];
+        uncompressed[0] = Self::UNCOMPRESSED_TAG;
+        uncompressed[1..].copy_from_slice(self.as_ref());
+        uncompressed
+    }
+
+    /// Decompresses a SEC1 compressed public key.
+    ///
+    /// Fails with [`Error::InvalidPublicKey`] if the tag isn't `0x02` or `0x03`, or if
+    /// `x` isn't the coordinate of a point of the curve.
+    pub fn from_compressed(bytes: &[u8; Self::COMPRESSED_LEN]) -> Result<Self, Error> {
+        k256::PublicKey::from_sec1_bytes(bytes)
+            .map(Into::into)
+            .map_err(|_| Error::InvalidPublicKey)
+    }
+
+    /// Reads a SEC1 uncompressed public key.
+    ///
+    /// Fails with [`Error::InvalidPublicKey`] if the tag isn't `0x04`, or if the point
+    /// isn't on the curve.
+    pub fn from_uncompressed(
+        bytes: &[u8; Self::UNCOMPRESSED_LEN],
+    ) -> Result<Self, Error> {
+        if bytes[0] != Self::UNCOMPRESSED_TAG {
+            return Err(Error::InvalidPublicKey)
+        }
+
+        k256::PublicKey::from_sec1_bytes(bytes)
+            .map(Into::into)
+            .map_err(|_| Error::InvalidPublicKey)
+    }
 }
 
 impl Deref for PublicKey {
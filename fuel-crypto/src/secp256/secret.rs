@@ -6,11 +6,15 @@ use core::{
     str,
 };
 
-use zeroize::Zeroize;
+use zeroize::{
+    Zeroize,
+    ZeroizeOnDrop,
+};
 
 use crate::{
     secp256::PublicKey,
     Error,
+    Hasher,
 };
 
 #[cfg(feature = "std")]
@@ -29,7 +33,14 @@ use rand::{
 };
 
 /// Asymmetric secret key, guaranteed to be valid by construction
-#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Zeroize)]
+///
+/// The key is zeroed when dropped, and its `Debug` and `Display` output is redacted. Use
+/// the hex formatting, like `{:x}`, to print the key itself.
+///
+/// `==` may return as soon as a byte differs, use [`SecretKey::ct_eq`] to compare keys.
+#[derive(
+    Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Zeroize, ZeroizeOnDrop,
+)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct SecretKey(Bytes32);
@@ -37,6 +48,45 @@ pub struct SecretKey(Bytes32);
 impl SecretKey {
     /// Memory length of the type
     pub const LEN: usize = Bytes32::LEN;
+    /// The `Debug` and `Display` output of every key
+    const REDACTED: &'static str = "SecretKey(<redacted>)";
+    /// The HKDF info of [`SecretKey::from_seed`], followed by the counter
+    pub const SEED_INFO: &'static [u8] = b"fuel-crypto/secret-key/v1";
+    /// The HKDF salt of [`SecretKey::from_seed`]
+    pub const SEED_SALT: &'static [u8] = b"fuel-crypto/secret-key/salt";
+
+    /// Derives a secret key from the `seed`, without randomness.
+    ///
+    /// The key is the 32 bytes HKDF-SHA256 (RFC 5869) of the `seed`, with the
+    /// [`SEED_SALT`](Self::SEED_SALT) salt and the [`SEED_INFO`](Self::SEED_INFO) info
+    /// followed by a big-endian `u32` counter. The counter starts at zero and is
+    /// incremented while the output is zero or not lower than the order of the curve,
+    /// which happens with a probability below `2^-127`.
+    ///
+    /// The key is as secret as the seed: the seed must be secret and hold at least 128
+    /// bits of entropy.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut prk = Hasher::new_keyed(Self::SEED_SALT).chain(seed).finalize();
+
+        let secret = (0u32..)
+            .find_map(|counter| {
+                let okm = Hasher::new_keyed(&*prk)
+                    .chain(Self::SEED_INFO)
+                    .chain(counter.to_be_bytes())
+                    .chain([1u8])
+                    .finalize();
+                Self::try_from(okm).ok()
+            })
+            .expect("A valid key is found with an overwhelming probability");
+
+        prk.zeroize();
+        secret
+    }
+
+    /// Equality in time independent of the bytes of the keys.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
 }
 
 impl Deref for SecretKey {
@@ -73,21 +123,25 @@ impl fmt::UpperHex for SecretKey {
 
 impl fmt::Debug for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        f.write_str(Self::REDACTED)
     }
 }
 
 impl fmt::Display for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        f.write_str(Self::REDACTED)
     }
 }
 
 impl From<::k256::SecretKey> for SecretKey {
     fn from(s: ::k256::SecretKey) -> Self {
+        let mut bytes = s.to_bytes();
         let mut raw_bytes = [0u8; Self::LEN];
-        raw_bytes.copy_from_slice(&s.to_bytes());
-        Self(Bytes32::from(raw_bytes))
+        raw_bytes.copy_from_slice(&bytes);
+        let secret = Self(Bytes32::from(raw_bytes));
+        bytes.zeroize();
+        raw_bytes.zeroize();
+        secret
     }
 }
 
@@ -96,20 +150,26 @@ impl From<::secp256k1::SecretKey> for SecretKey {
     fn from(s: ::secp256k1::SecretKey) -> Self {
         let mut raw_bytes = [0u8; Self::LEN];
         raw_bytes.copy_from_slice(s.as_ref());
-        Self(Bytes32::from(raw_bytes))
+        let secret = Self(Bytes32::from(raw_bytes));
+        raw_bytes.zeroize();
+        secret
     }
 }
 
 impl From<&SecretKey> for ::k256::SecretKey {
     fn from(sk: &SecretKey) -> Self {
-        ::k256::SecretKey::from_bytes(&(*sk.0).into())
-            .expect("SecretKey is guaranteed to be valid")
+        let mut bytes = (*sk.0).into();
+        let secret = ::k256::SecretKey::from_bytes(&bytes)
+            .expect("SecretKey is guaranteed to be valid");
+        bytes.zeroize();
+        secret
     }
 }
 
 #[cfg(feature = "std")]
 impl From<&SecretKey> for ::secp256k1::SecretKey {
     fn from(sk: &SecretKey) -> Self {
+        // `secp256k1` 0.26 can't erase its secret keys, keep the conversions short-lived
         ::secp256k1::SecretKey::from_slice(sk.as_ref())
             .expect("SecretKey is guaranteed to be valid")
     }
@@ -148,8 +208,19 @@ impl SecretKey {
     pub fn new_from_mnemonic(d: DerivationPath, m: Mnemonic<W>) -> Result<Self, Error> {
         let derived_priv_key = m.derive_key(d, None)?;
         let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-        let bytes: [u8; Self::LEN] = key.to_bytes().into();
-        Ok(SecretKey(Bytes32::from(bytes)))
+        let mut bytes: [u8; Self::LEN] = key.to_bytes().into();
+        let secret = SecretKey(Bytes32::from(bytes));
+        bytes.zeroize();
+        Ok(secret)
+    }
+
+    /// Derives the secret key of the English mnemonic `phrase` at the BIP32 `path`,
+    /// without passphrase. The Fuel wallets use
+    /// [`DEFAULT_DERIVATION_PATH`](crate::wallet::DEFAULT_DERIVATION_PATH) for their
+    /// first account.
+    #[cfg(feature = "wallet")]
+    pub fn derive_from_mnemonic(phrase: &str, path: &str) -> Result<Self, Error> {
+        crate::wallet::derive_from_mnemonic(phrase, "", path)
     }
 
     /// Return the curve representation of this secret.
@@ -161,10 +232,13 @@ impl SecretKey {
 impl TryFrom<Bytes32> for SecretKey {
     type Error = Error;
 
-    fn try_from(b: Bytes32) -> Result<Self, Self::Error> {
+    fn try_from(mut b: Bytes32) -> Result<Self, Self::Error> {
         match k256::SecretKey::from_bytes((&*b).into()) {
             Ok(_) => Ok(Self(b)),
-            Err(_) => Err(Error::InvalidSecretKey),
+            Err(_) => {
+                b.zeroize();
+                Err(Error::InvalidSecretKey)
+            }
         }
     }
 }
@@ -173,6 +247,7 @@ impl TryFrom<&[u8]> for SecretKey {
     type Error = Error;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        // The key is copied to the stack only, the invalid ones are zeroed
         Bytes32::try_from(slice)
             .map_err(|_| Error::InvalidSecretKey)
             .and_then(SecretKey::try_from)
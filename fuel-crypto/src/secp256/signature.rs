@@ -1,4 +1,10 @@
-use super::backend::k1;
+use super::{
+    backend::k1,
+    signature_format::{
+        decode_signature,
+        encode_signature,
+    },
+};
 use crate::{
     Error,
     Message,
@@ -14,7 +20,24 @@ use core::{
     str,
 };
 
+/// The handling of the signatures with a high `s` value.
+///
+/// Every signature has a malleated counterpart, with the `s` value of `n - s` and the
+/// other recovery id, that recovers the same public key. The signatures produced by this
+/// crate always have the low `s` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignatureMode {
+    /// Accepts both the low and the high `s` form of the signature.
+    Lax,
+    /// Rejects the signatures with a high `s` value.
+    Strict,
+}
+
 /// Compact-form Secp256k1 signature.
+///
+/// `==` may return as soon as a byte differs, use [`Signature::ct_eq`] to compare values
+/// guarding an authentication decision.
 #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
@@ -24,6 +47,11 @@ impl Signature {
     /// Memory length of the type in bytes.
     pub const LEN: usize = Bytes64::LEN;
 
+    /// Equality in time independent of the bytes of the signatures.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
+
     /// Construct a `Signature` directly from its bytes.
     ///
     /// This constructor expects the given bytes to be a valid signature. No signing is
@@ -128,12 +156,74 @@ impl Signature {
     }
 
     /// Recover secp256k1 public key from a signature performed with
+    ///
+    /// Accepts the high `s` signatures, like [`SignatureMode::Lax`].
     pub fn recover(&self, message: &Message) -> Result<PublicKey, Error> {
-        k1::recover(*self.0, message)
+        self.recover_with_mode(message, SignatureMode::Lax)
+    }
+
+    /// Recover secp256k1 public key from a signature, handling the high `s` value
+    /// according to the `mode`.
+    pub fn recover_with_mode(
+        &self,
+        message: &Message,
+        mode: SignatureMode,
+    ) -> Result<PublicKey, Error> {
+        k1::recover(*self.checked(mode)?.0, message)
     }
 
     /// Verify that a signature matches given public key
+    ///
+    /// Rejects the high `s` signatures, like [`SignatureMode::Strict`].
     pub fn verify(&self, public_key: &PublicKey, message: &Message) -> Result<(), Error> {
-        k1::verify(*self.0, **public_key, message)
+        self.verify_with_mode(public_key, message, SignatureMode::Strict)
+    }
+
+    /// Verify that a signature matches given public key, handling the high `s` value
+    /// according to the `mode`.
+    pub fn verify_with_mode(
+        &self,
+        public_key: &PublicKey,
+        message: &Message,
+        mode: SignatureMode,
+    ) -> Result<(), Error> {
+        k1::verify(*self.checked(mode)?.0, **public_key, message)
+    }
+
+    /// Returns `true` unless the `s` value of the signature is in the upper half of the
+    /// curve order.
+    ///
+    /// The signatures that can't be decoded are normalized, as there is nothing to
+    /// normalize.
+    pub fn is_normalized(&self) -> bool {
+        self.normalized_high_s().is_none()
+    }
+
+    /// Returns the low `s` form of the signature, that recovers the same public key.
+    /// Normalized signatures are returned as is.
+    pub fn normalize(&self) -> Self {
+        self.normalized_high_s().unwrap_or(*self)
+    }
+
+    /// Returns the low `s` form of the signature if its `s` value is high.
+    fn normalized_high_s(&self) -> Option<Self> {
+        let (bytes, recovery_id) = decode_signature(*self.0);
+        let normalized = k256::ecdsa::Signature::from_slice(&bytes)
+            .ok()?
+            .normalize_s()?;
+
+        Some(Self::from_bytes(encode_signature(
+            normalized.to_bytes().into(),
+            recovery_id.negated(),
+        )))
+    }
+
+    /// Returns the signature to pass to the backend according to the `mode`.
+    fn checked(&self, mode: SignatureMode) -> Result<Self, Error> {
+        match mode {
+            SignatureMode::Lax => Ok(self.normalize()),
+            SignatureMode::Strict if self.is_normalized() => Ok(*self),
+            SignatureMode::Strict => Err(Error::InvalidSignature),
+        }
     }
 }
@@ -10,6 +10,15 @@ pub struct RecoveryId {
     is_y_odd: bool,
 }
 
+impl RecoveryId {
+    /// The recovery id of the signature with the negated `s` value.
+    pub fn negated(self) -> Self {
+        Self {
+            is_y_odd: !self.is_y_odd,
+        }
+    }
+}
+
 impl From<RecoveryId> for k256::ecdsa::RecoveryId {
     fn from(recid: RecoveryId) -> Self {
         k256::ecdsa::RecoveryId::new(recid.is_y_odd, false)
@@ -0,0 +1,111 @@
+use crate::{
+    Error,
+    Message,
+    PublicKey,
+    SecretKey,
+    Signature,
+};
+
+/// Signer error variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignerError {
+    /// The signer doesn't hold the requested key
+    KeyNotFound,
+
+    /// The signer isn't reachable, e.g. a disconnected hardware wallet
+    Unavailable,
+
+    /// The signer refused to sign the message
+    Rejected,
+
+    /// The signature couldn't be produced
+    Crypto(Error),
+}
+
+impl From<Error> for SignerError {
+    fn from(e: Error) -> Self {
+        Self::Crypto(e)
+    }
+}
+
+/// Provider of secp256k1 signatures for a single key.
+///
+/// The key itself may live outside of the process, like in a hardware wallet or a
+/// remote key management service. [`SecretKey`] is the in-memory implementation.
+pub trait Signer {
+    /// The public key of the signatures produced by the signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs the `message`.
+    fn sign(&self, message: &Message) -> Result<Signature, SignerError>;
+}
+
+impl Signer for SecretKey {
+    fn public_key(&self) -> PublicKey {
+        SecretKey::public_key(self)
+    }
+
+    fn sign(&self, message: &Message) -> Result<Signature, SignerError> {
+        Ok(Signature::sign(self, message))
+    }
+}
+
+impl<S: Signer + ?Sized> Signer for &S {
+    fn public_key(&self) -> PublicKey {
+        (**self).public_key()
+    }
+
+    fn sign(&self, message: &Message) -> Result<Signature, SignerError> {
+        (**self).sign(message)
+    }
+}
+
+/// Asynchronous counterpart of [`Signer`], for the signers that wait for an external
+/// party to produce the signature.
+#[cfg(feature = "async")]
+pub trait AsyncSigner {
+    /// The public key of the signatures produced by the signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs the `message`.
+    fn sign(
+        &self,
+        message: &Message,
+    ) -> impl core::future::Future<Output = Result<Signature, SignerError>>;
+}
+
+#[cfg(feature = "async")]
+impl AsyncSigner for SecretKey {
+    fn public_key(&self) -> PublicKey {
+        SecretKey::public_key(self)
+    }
+
+    async fn sign(&self, message: &Message) -> Result<Signature, SignerError> {
+        Ok(Signature::sign(self, message))
+    }
+}
+
+#[cfg(feature = "std")]
+mod use_std {
+    use super::*;
+    use std::{
+        error,
+        fmt,
+    };
+
+    impl fmt::Display for SignerError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+
+    impl error::Error for SignerError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match self {
+                Self::Crypto(e) => Some(e),
+                _ => None,
+            }
+        }
+    }
+}
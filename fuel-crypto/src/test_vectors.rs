@@ -0,0 +1,26 @@
+//! Deterministic keys for the tests, derived with [`SecretKey::from_seed`].
+//!
+//! The key of index `i` is derived from the seed `b"fuel-crypto/test-vectors"`
+//! followed by `i` as a big-endian `u32`. The keys are public, never hold funds with
+//! them.
+
+use crate::SecretKey;
+
+/// The prefix of the seeds of the test keys
+pub const SEED_PREFIX: &[u8] = b"fuel-crypto/test-vectors";
+
+/// The test key of the `index`.
+pub fn secret_key(index: u32) -> SecretKey {
+    let mut seed = [0u8; SEED_PREFIX.len() + 4];
+    seed[..SEED_PREFIX.len()].copy_from_slice(SEED_PREFIX);
+    seed[SEED_PREFIX.len()..].copy_from_slice(&index.to_be_bytes());
+
+    SecretKey::from_seed(&seed)
+}
+
+/// The `N` first test keys.
+pub fn keyring<const N: usize>() -> [SecretKey; N] {
+    core::array::from_fn(|index| {
+        secret_key(u32::try_from(index).expect("The keyring is smaller than 2^32"))
+    })
+}
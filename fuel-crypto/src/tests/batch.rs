@@ -0,0 +1,83 @@
+use crate::{
+    batch::{
+        verify_recover,
+        BatchError,
+    },
+    Error,
+    Message,
+    SecretKey,
+    Signature,
+};
+
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+fn signed_batch(rng: &mut StdRng, len: usize) -> Vec<(SecretKey, Signature, Message)> {
+    (0..len)
+        .map(|_| {
+            let secret = SecretKey::random(rng);
+            let message = Message::new(rng.gen::<[u8; 32]>());
+            let signature = Signature::sign(&secret, &message);
+            (secret, signature, message)
+        })
+        .collect()
+}
+
+#[test]
+fn verify_recover_returns_keys_in_batch_order() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let signed = signed_batch(rng, 16);
+    let batch: Vec<_> = signed.iter().map(|(_, s, m)| (*s, *m)).collect();
+
+    let public_keys = verify_recover(&batch).expect("All signatures are valid");
+
+    let expected: Vec<_> = signed.iter().map(|(k, _, _)| k.public_key()).collect();
+    assert_eq!(public_keys, expected);
+}
+
+#[test]
+fn verify_recover_reports_first_failure() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for k in [0, 7, 15] {
+        let mut batch: Vec<_> = signed_batch(rng, 16)
+            .into_iter()
+            .map(|(_, s, m)| (s, m))
+            .collect();
+        batch[k].0 = Signature::default();
+        batch[15].0 = Signature::default();
+
+        let err = verify_recover(&batch).expect_err("The signature is invalid");
+
+        assert_eq!(
+            err,
+            BatchError {
+                index: k,
+                error: Error::InvalidSignature,
+            }
+        );
+    }
+}
+
+#[test]
+fn verify_recover_recovers_duplicated_signatures() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let signed = signed_batch(rng, 2);
+    let (first, second) = ((signed[0].1, signed[0].2), (signed[1].1, signed[1].2));
+    let batch = [first, second, first, first];
+
+    let public_keys = verify_recover(&batch).expect("All signatures are valid");
+
+    let (a, b) = (signed[0].0.public_key(), signed[1].0.public_key());
+    assert_eq!(public_keys, vec![a, b, a, a]);
+}
+
+#[test]
+fn verify_recover_accepts_empty_batch() {
+    assert_eq!(verify_recover(&[]), Ok(vec![]));
+}
@@ -0,0 +1,108 @@
+use crate::{
+    with_context,
+    Error,
+    Message,
+    SecretKey,
+    Signature,
+};
+
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use std::thread;
+
+#[test]
+fn with_context_matches_free_functions() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..16 {
+        let secret = SecretKey::random(rng);
+        let message = Message::new(rng.gen::<[u8; 32]>());
+        let signature = Signature::sign(&secret, &message);
+
+        with_context(|context| {
+            assert_eq!(context.public_key(&secret), secret.public_key());
+            assert_eq!(context.sign(&secret, &message), signature);
+            assert_eq!(
+                context.recover(&signature, &message),
+                signature.recover(&message)
+            );
+            assert_eq!(
+                context.verify(&signature, &secret.public_key(), &message),
+                Ok(())
+            );
+        });
+    }
+}
+
+#[test]
+fn with_context_allows_nested_operations() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"nested");
+
+    let (outer, inner) = with_context(|context| {
+        let inner = Signature::sign(&secret, &message);
+        (context.sign(&secret, &message), inner)
+    });
+
+    assert_eq!(outer, inner);
+}
+
+#[test]
+fn signatures_are_unchanged_by_rerandomization() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"rerandomized");
+    let expected = Signature::sign(&secret, &message);
+
+    // Spans several rerandomization intervals
+    for _ in 0..3000 {
+        assert_eq!(Signature::sign(&secret, &message), expected);
+    }
+    assert_eq!(expected.recover(&message), Ok(secret.public_key()));
+}
+
+#[test]
+fn recover_rejects_invalid_signature_with_context() {
+    let message = Message::new(b"invalid");
+    let signature = Signature::default();
+
+    let result = with_context(|context| context.recover(&signature, &message));
+
+    assert_eq!(result, Err(Error::InvalidSignature));
+    assert_eq!(result, signature.recover(&message));
+}
+
+#[test]
+fn recover_from_many_threads() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let cases: Vec<_> = (0..64)
+        .map(|_| {
+            let secret = SecretKey::random(rng);
+            let message = Message::new(rng.gen::<[u8; 32]>());
+            (
+                Signature::sign(&secret, &message),
+                message,
+                secret.public_key(),
+            )
+        })
+        .collect();
+
+    thread::scope(|scope| {
+        for thread in 0..8 {
+            let cases = &cases;
+            scope.spawn(move || {
+                for round in 0..50 {
+                    let (signature, message, public) =
+                        &cases[(thread * 7 + round) % cases.len()];
+                    assert_eq!(signature.recover(message).as_ref(), Ok(public));
+                    signature.verify(public, message).expect("Failed to verify");
+                }
+            });
+        }
+    });
+}
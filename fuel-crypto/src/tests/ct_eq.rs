@@ -0,0 +1,71 @@
+use crate::{
+    Message,
+    SecretKey,
+    Signature,
+};
+
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+#[test]
+fn ct_eq_agrees_with_eq() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..32 {
+        let secret = SecretKey::random(rng);
+        let other = SecretKey::random(rng);
+        let public = secret.public_key();
+        let message = Message::new(rng.gen::<[u8; 32]>());
+        let signature = Signature::sign(&secret, &message);
+
+        assert!(secret.ct_eq(&secret.clone()));
+        assert_eq!(secret.ct_eq(&other), secret == other);
+        assert!(public.ct_eq(&public));
+        assert_eq!(
+            public.ct_eq(&other.public_key()),
+            public == other.public_key()
+        );
+        assert!(message.ct_eq(&message));
+        assert!(signature.ct_eq(&signature));
+        assert_eq!(
+            signature.ct_eq(&Signature::sign(&other, &message)),
+            signature == Signature::sign(&other, &message)
+        );
+    }
+}
+
+#[test]
+fn ct_eq_rejects_single_bit_differences() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"Timing leaks the position of the first mismatch.");
+    let signature = Signature::sign(&secret, &message);
+    let public = secret.public_key();
+
+    for index in [0, 31, 32, 63] {
+        let mut altered = <[u8; Signature::LEN]>::from(signature);
+        altered[index] ^= 0x80;
+        let altered = Signature::from_bytes(altered);
+
+        assert!(!signature.ct_eq(&altered));
+        assert_ne!(signature, altered);
+
+        let mut altered = public;
+        altered.as_mut()[index] ^= 1;
+
+        assert!(!public.ct_eq(&altered));
+        assert_ne!(public, altered);
+    }
+
+    for index in [0, 31] {
+        let mut altered = *message;
+        altered[index] ^= 1;
+        let altered = Message::from_bytes(altered);
+
+        assert!(!message.ct_eq(&altered));
+        assert_ne!(message, altered);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    batch::BatchError,
+    ed25519::{
+        verify_batch,
+        StreamVerifier,
+    },
+    Error,
+};
+
+use core::str::FromStr;
+use ed25519_dalek::{
+    Signer,
+    SigningKey,
+};
+use fuel_types::{
+    Bytes32,
+    Bytes64,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+/// The secret key, public key, message and signature of the RFC 8032 section 7.1
+/// tests 1, 2 and 3.
+const RFC_8032_VECTORS: [(&str, &str, &[u8], &str); 3] = [
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        &[],
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        &[0x72],
+        "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        &[0xaf, 0x82],
+        "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    ),
+];
+
+fn rfc_8032_batch() -> Vec<(Bytes32, Bytes64, &'static [u8])> {
+    RFC_8032_VECTORS
+        .iter()
+        .map(|(secret, public, message, signature)| {
+            let secret = Bytes32::from_str(secret).unwrap();
+            let public = Bytes32::from_str(public).unwrap();
+            let signature = Bytes64::from_str(signature).unwrap();
+
+            // The vectors are consistent with the signing of ed25519-dalek
+            let signing_key = SigningKey::from_bytes(&secret);
+            assert_eq!(signing_key.verifying_key().to_bytes(), *public);
+            assert_eq!(signing_key.sign(message).to_bytes(), *signature);
+
+            (public, signature, *message)
+        })
+        .collect()
+}
+
+fn random_batch(rng: &mut StdRng, len: usize) -> Vec<(Bytes32, Bytes64, Vec<u8>)> {
+    (0..len)
+        .map(|i| {
+            let signing_key = SigningKey::from_bytes(&rng.gen());
+            let message: Vec<u8> = (0..i * 13).map(|_| rng.gen()).collect();
+            let signature = signing_key.sign(&message).to_bytes();
+            (
+                signing_key.verifying_key().to_bytes().into(),
+                signature.into(),
+                message,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn verify_batch_accepts_rfc_8032_vectors() {
+    let batch = rfc_8032_batch();
+
+    assert_eq!(verify_batch(&batch), Ok(()));
+}
+
+#[test]
+fn verify_batch_reports_corrupted_signature() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let signed = random_batch(rng, 32);
+    let mut batch: Vec<_> = signed
+        .iter()
+        .map(|(public, signature, message)| (*public, *signature, message.as_slice()))
+        .collect();
+    assert_eq!(verify_batch(&batch), Ok(()));
+
+    batch[17].1[40] ^= 1;
+
+    assert_eq!(
+        verify_batch(&batch),
+        Err(BatchError {
+            index: 17,
+            error: Error::InvalidSignature,
+        })
+    );
+}
+
+#[test]
+fn verify_batch_reports_signature_of_other_message() {
+    let mut batch = rfc_8032_batch();
+    batch[1].2 = RFC_8032_VECTORS[2].2;
+
+    assert_eq!(
+        verify_batch(&batch),
+        Err(BatchError {
+            index: 1,
+            error: Error::InvalidSignature,
+        })
+    );
+}
+
+#[test]
+fn stream_verifier_matches_one_shot_verification_of_large_message() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let signing_key = SigningKey::from_bytes(&rng.gen());
+    let public = Bytes32::from(signing_key.verifying_key().to_bytes());
+    let mut message = vec![0u8; 10 * 1024 * 1024];
+    rng.fill(message.as_mut_slice());
+    let signature = Bytes64::from(signing_key.sign(&message).to_bytes());
+
+    let stream = |message: &[u8]| {
+        let mut verifier = StreamVerifier::new(&public, &signature)?;
+        for chunk in message.chunks(64 * 1024 + 7) {
+            verifier.update(chunk);
+        }
+        verifier.finalize()
+    };
+
+    assert_eq!(stream(&message), Ok(()));
+    assert_eq!(verify_batch(&[(public, signature, &message)]), Ok(()));
+
+    message[5 * 1024 * 1024] ^= 1;
+
+    assert_eq!(stream(&message), Err(Error::InvalidSignature));
+    assert!(verify_batch(&[(public, signature, &message)]).is_err());
+}
+
+#[test]
+fn stream_verifier_rejects_small_order_components() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let (public, signature, _) = random_batch(rng, 1).remove(0);
+
+    // The identity point is of small order
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+
+    let mut small_order_r = signature;
+    small_order_r[..32].copy_from_slice(&identity);
+
+    assert_eq!(
+        StreamVerifier::new(&public, &small_order_r).err(),
+        Some(Error::InvalidSignature)
+    );
+    assert_eq!(
+        StreamVerifier::new(&identity.into(), &signature).err(),
+        Some(Error::InvalidSignature)
+    );
+}
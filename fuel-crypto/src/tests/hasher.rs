@@ -48,3 +48,114 @@ fn digest() {
 
     assert_eq!(digest, d);
 }
+
+fn hex_digest(digest: &str) -> fuel_types::Bytes32 {
+    core::str::FromStr::from_str(digest).expect("Invalid hex digest")
+}
+
+#[test]
+fn new_keyed_matches_rfc_4231_vectors() {
+    // Test cases 1, 2 and 6 of RFC 4231
+    let vectors: [(&[u8], &[u8], &str); 3] = [
+        (
+            &[0x0b; 20],
+            b"Hi There",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            b"Jefe",
+            b"what do ya want for nothing?",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+    ];
+
+    for (key, data, expected) in vectors {
+        let mut hasher = Hasher::new_keyed(key);
+        hasher.input(data);
+
+        assert_eq!(hasher.digest(), hex_digest(expected));
+        assert_eq!(hasher.finalize(), hex_digest(expected));
+    }
+}
+
+#[test]
+fn keyed_hasher_reset_keeps_the_key() {
+    let mut hasher = Hasher::new_keyed(b"Jefe");
+    hasher.input(b"something else");
+
+    hasher.reset();
+    hasher.input(b"what do ya want for nothing?");
+
+    assert_eq!(
+        hasher.finalize(),
+        hex_digest("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+    );
+}
+
+#[test]
+fn domain_and_chunks_match_vectors() {
+    // The tag is absorbed as `len(tag) as u64 big-endian ++ tag`
+    let digest = Hasher::with_domain("fuel/v1/bridge")
+        .chain(b"payload")
+        .finalize();
+    assert_eq!(
+        digest,
+        hex_digest("b0e4f5d2a8d7574e791f8039a0b281f27ed14395a8c2804a8b87a92f13c33a5d")
+    );
+
+    let digest = Hasher::hash_many([&b"fuel"[..], b"", b"vm"]);
+    assert_eq!(
+        digest,
+        hex_digest("d864810e13ce795223c89da3c969c3ab6288ec540219e8dbf6ac4c4a84e8d306")
+    );
+
+    let digest = Hasher::new_keyed(b"secret")
+        .chain_prefixed("fuel/v1/bridge")
+        .chain_prefixed(b"payload")
+        .finalize();
+    assert_eq!(
+        digest,
+        hex_digest("bd567b53eb8695b91976ea1f586933da3896dadfff39e1ffc777ec99043ca605")
+    );
+
+    assert_eq!(Hasher::hash_many::<&[u8], _>([]), Hasher::hash([]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_many_is_not_ambiguous() {
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..100 {
+        let len = rng.gen_range(1..64);
+        let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let split = rng.gen_range(0..len);
+        let other_split = (split + rng.gen_range(1..len.max(2))) % len;
+        let (a, b) = data.split_at(split);
+
+        assert_ne!(Hasher::hash_many([a, b]), Hasher::hash(&data));
+        assert_ne!(Hasher::hash_many([a, b]), Hasher::hash_many([&data[..]]));
+        if other_split != split {
+            let (c, d) = data.split_at(other_split);
+            assert_ne!(Hasher::hash_many([a, b]), Hasher::hash_many([c, d]));
+        }
+        assert_ne!(
+            Hasher::with_domain("fuel/v1/a").chain(&data).finalize(),
+            Hasher::with_domain("fuel/v1/")
+                .chain(b"a")
+                .chain(&data)
+                .finalize()
+        );
+    }
+}
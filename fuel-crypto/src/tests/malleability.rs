@@ -0,0 +1,177 @@
+use crate::{
+    secp256r1,
+    Error,
+    Message,
+    SecretKey,
+    Signature,
+    SignatureMode,
+};
+
+use fuel_types::Bytes64;
+use k256::elliptic_curve::{
+    ops::Reduce,
+    point::AffineCoordinates,
+    sec1::ToEncodedPoint,
+    PrimeField,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+/// The `s` value in the upper half of the curve order that still leaves the highest bit
+/// free for the recovery id.
+const HIGH_S: [u8; 32] = {
+    let mut s = [0xff; 32];
+    s[0] = 0x7f;
+    s
+};
+
+/// Returns the Fuel encoding of the `(r, s)` pair with the `R` point parity.
+fn encode(r: [u8; 32], s: [u8; 32], is_y_odd: bool) -> [u8; 64] {
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&s);
+    signature[32] |= (is_y_odd as u8) << 7;
+    signature
+}
+
+/// Signs with a nonce and a message chosen to produce the `HIGH_S` value, like a
+/// malleated low `s` signature.
+fn high_s_secp256k1(rng: &mut StdRng) -> (SecretKey, Message, Signature) {
+    use k256::{
+        ProjectivePoint,
+        Scalar,
+        U256,
+    };
+
+    let secret = SecretKey::random(rng);
+    let d = *k256::SecretKey::from(&secret).to_nonzero_scalar();
+    let k = <Scalar as Reduce<U256>>::reduce_bytes(&rng.gen::<[u8; 32]>().into());
+    let point = (ProjectivePoint::GENERATOR * k).to_affine();
+    let r = <Scalar as Reduce<U256>>::reduce_bytes(&point.x());
+    let s = Scalar::from_repr(HIGH_S.into()).unwrap();
+
+    // s = k⁻¹(z + rd)
+    let z = s * k - r * d;
+    let message = Message::from_bytes(z.to_bytes().into());
+    let signature = Signature::from_bytes(encode(
+        r.to_bytes().into(),
+        HIGH_S,
+        point.y_is_odd().into(),
+    ));
+
+    (secret, message, signature)
+}
+
+/// The secp256r1 counterpart of `high_s_secp256k1`, returning the public key.
+fn high_s_secp256r1(rng: &mut StdRng) -> (Bytes64, Message, Bytes64) {
+    use p256::{
+        ProjectivePoint,
+        Scalar,
+        U256,
+    };
+
+    let d = <Scalar as Reduce<U256>>::reduce_bytes(&rng.gen::<[u8; 32]>().into());
+    let k = <Scalar as Reduce<U256>>::reduce_bytes(&rng.gen::<[u8; 32]>().into());
+    let point = (ProjectivePoint::GENERATOR * k).to_affine();
+    let r = <Scalar as Reduce<U256>>::reduce_bytes(&point.x());
+    let s = Scalar::from_repr(HIGH_S.into()).unwrap();
+
+    let z = s * k - r * d;
+    let message = Message::from_bytes(z.to_bytes().into());
+    let signature = encode(r.to_bytes().into(), HIGH_S, point.y_is_odd().into());
+
+    let public = (ProjectivePoint::GENERATOR * d)
+        .to_affine()
+        .to_encoded_point(false);
+    let mut public_key = Bytes64::zeroed();
+    public_key[..32].copy_from_slice(public.x().unwrap());
+    public_key[32..].copy_from_slice(public.y().unwrap());
+
+    (public_key, message, signature.into())
+}
+
+#[test]
+fn malleated_signature_is_accepted_only_in_lax_mode() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..16 {
+        let (secret, message, malleated) = high_s_secp256k1(rng);
+        let public = secret.public_key();
+        let normalized = malleated.normalize();
+
+        assert!(!malleated.is_normalized());
+        assert!(normalized.is_normalized());
+        assert_ne!(normalized, malleated);
+
+        assert_eq!(
+            malleated.recover_with_mode(&message, SignatureMode::Lax),
+            Ok(public)
+        );
+        assert_eq!(
+            malleated.recover_with_mode(&message, SignatureMode::Strict),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            normalized.recover_with_mode(&message, SignatureMode::Strict),
+            Ok(public)
+        );
+        assert_eq!(malleated.recover(&message), Ok(public));
+
+        assert_eq!(
+            malleated.verify_with_mode(&public, &message, SignatureMode::Lax),
+            Ok(())
+        );
+        assert_eq!(
+            malleated.verify_with_mode(&public, &message, SignatureMode::Strict),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            malleated.verify(&public, &message),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(normalized.verify(&public, &message), Ok(()));
+    }
+}
+
+#[test]
+fn normalize_is_idempotent() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..16 {
+        let secret = SecretKey::random(rng);
+        let message = Message::new(rng.gen::<[u8; 32]>());
+        let signature = Signature::sign(&secret, &message);
+        assert!(signature.is_normalized());
+        assert_eq!(signature.normalize(), signature);
+
+        let (_, _, malleated) = high_s_secp256k1(rng);
+        let normalized = malleated.normalize();
+        assert_eq!(normalized.normalize(), normalized);
+    }
+
+    // Undecodable signatures are left as is
+    assert!(Signature::default().is_normalized());
+    assert_eq!(Signature::default().normalize(), Signature::default());
+}
+
+#[test]
+fn malleated_secp256r1_signature_is_accepted_only_in_lax_mode() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..16 {
+        let (public, message, malleated) = high_s_secp256r1(rng);
+
+        assert_eq!(
+            secp256r1::recover_with_mode(&malleated, &message, SignatureMode::Lax),
+            Ok(public)
+        );
+        assert_eq!(secp256r1::recover(&malleated, &message), Ok(public));
+        assert_eq!(
+            secp256r1::recover_with_mode(&malleated, &message, SignatureMode::Strict),
+            Err(Error::InvalidSignature)
+        );
+    }
+}
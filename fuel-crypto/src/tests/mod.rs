@@ -2,13 +2,44 @@
 use criterion as _;
 use k256 as _;
 
+#[cfg(feature = "std")]
+mod batch;
+
+#[cfg(feature = "std")]
+mod context;
+
+#[cfg(feature = "std")]
+mod ct_eq;
+
+#[cfg(feature = "std")]
+mod ed25519;
+
 mod hasher;
 
+#[cfg(feature = "std")]
+mod malleability;
+
 #[cfg(feature = "std")]
 mod mnemonic;
 
+#[cfg(feature = "std")]
+mod public;
+
+#[cfg(all(feature = "std", feature = "schnorr"))]
+mod schnorr;
+
+#[cfg(feature = "std")]
+mod secret;
+
+mod seed;
+
 mod signature;
 
+mod signer;
+
+#[cfg(feature = "wallet")]
+mod wallet;
+
 #[cfg(feature = "serde")]
 mod serde;
 #[cfg(not(feature = "serde"))]
@@ -0,0 +1,101 @@
+use crate::{
+    Error,
+    Hasher,
+    PublicKey,
+    SecretKey,
+};
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+
+#[test]
+fn public_key_encodings_round_trip() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..32 {
+        let public = SecretKey::random(rng).public_key();
+
+        let compressed = public.to_compressed();
+        let uncompressed = public.to_uncompressed();
+
+        assert_eq!(PublicKey::from_compressed(&compressed), Ok(public));
+        assert_eq!(PublicKey::from_uncompressed(&uncompressed), Ok(public));
+        assert_eq!(&uncompressed[1..], public.as_ref());
+    }
+}
+
+#[test]
+fn public_key_encodings_match_sec1() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let expected = k256::SecretKey::from(&secret).public_key();
+
+    let public = secret.public_key();
+
+    assert_eq!(
+        public.to_compressed().as_slice(),
+        expected.to_encoded_point(true).as_bytes()
+    );
+    assert_eq!(
+        public.to_uncompressed().as_slice(),
+        expected.to_encoded_point(false).as_bytes()
+    );
+}
+
+#[test]
+fn public_key_from_compressed_rejects_invalid_keys() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut compressed = SecretKey::random(rng).public_key().to_compressed();
+
+    compressed[0] = 0x04;
+    assert_eq!(
+        PublicKey::from_compressed(&compressed),
+        Err(Error::InvalidPublicKey)
+    );
+
+    // There is no point of the curve with `x = 5`
+    let mut off_curve = [0u8; PublicKey::COMPRESSED_LEN];
+    off_curve[0] = 0x02;
+    off_curve[PublicKey::COMPRESSED_LEN - 1] = 5;
+    assert_eq!(
+        PublicKey::from_compressed(&off_curve),
+        Err(Error::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn public_key_from_uncompressed_rejects_invalid_keys() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let uncompressed = SecretKey::random(rng).public_key().to_uncompressed();
+
+    for tag in [0x00, 0x02, 0x03, 0x06, 0x07] {
+        let mut tagged = uncompressed;
+        tagged[0] = tag;
+
+        assert_eq!(
+            PublicKey::from_uncompressed(&tagged),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    let mut off_curve = uncompressed;
+    off_curve[PublicKey::UNCOMPRESSED_LEN - 1] ^= 1;
+    assert_eq!(
+        PublicKey::from_uncompressed(&off_curve),
+        Err(Error::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn public_key_address_is_its_hash() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let public = SecretKey::random(rng).public_key();
+
+    let address = public.address();
+
+    assert_eq!(*address, *Hasher::hash(public.as_ref()));
+    assert_eq!(*address, *public.hash());
+}
@@ -0,0 +1,277 @@
+use crate::{
+    batch::BatchError,
+    schnorr,
+    Error,
+    SecretKey,
+};
+
+use fuel_types::{
+    Bytes32,
+    Bytes64,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+fn decode(hex: &str) -> Vec<u8> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|chunk| {
+            let chunk = core::str::from_utf8(chunk).expect("Valid hex");
+            u8::from_str_radix(chunk, 16).expect("Valid hex")
+        })
+        .collect()
+}
+
+fn bytes32(hex: &str) -> Bytes32 {
+    Bytes32::try_from(decode(hex).as_slice()).expect("32 bytes")
+}
+
+fn bytes64(hex: &str) -> Bytes64 {
+    Bytes64::try_from(decode(hex).as_slice()).expect("64 bytes")
+}
+
+/// (secret key, public key, aux rand, message, signature) of the BIP340 vectors 0 to 3
+const SIGN_VECTORS: [(&str, &str, &str, &str, &str); 4] = [
+    (
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+    ),
+    (
+        "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+    ),
+    (
+        "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+        "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+        "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+        "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+        "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+    ),
+    (
+        "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+        "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+    ),
+];
+
+/// (public key, message, signature, expected result) of the BIP340 vectors 4 to 14
+const VERIFY_VECTORS: [(&str, &str, &str, Result<(), Error>); 11] = [
+    (
+        "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+        "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+        "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4",
+        Ok(()),
+    ),
+    // The public key isn't on the curve
+    (
+        "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        Err(Error::InvalidPublicKey),
+    ),
+    // The `y` of `R` is odd
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+        Err(Error::InvalidSignature),
+    ),
+    // Negated message
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD",
+        Err(Error::InvalidSignature),
+    ),
+    // Negated `s`
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6",
+        Err(Error::InvalidSignature),
+    ),
+    // `sG - eP` is the point at infinity, with `x(inf) = 0`
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051",
+        Err(Error::InvalidSignature),
+    ),
+    // `sG - eP` is the point at infinity, with `x(inf) = 1`
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197",
+        Err(Error::InvalidSignature),
+    ),
+    // `r` isn't the `x` coordinate of a point
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        Err(Error::InvalidSignature),
+    ),
+    // `r` is the field size
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        Err(Error::InvalidSignature),
+    ),
+    // `s` is the curve order
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        Err(Error::InvalidSignature),
+    ),
+    // The public key exceeds the field size
+    (
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        Err(Error::InvalidPublicKey),
+    ),
+];
+
+/// (message, signature) of the BIP340 vectors 15 to 17, signed with the key
+/// `0340...0340` and the zero aux rand
+const MESSAGE_LENGTH_VECTORS: [(&str, &str); 3] = [
+    (
+        "",
+        "71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63",
+    ),
+    (
+        "11",
+        "08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF",
+    ),
+    (
+        "0102030405060708090A0B0C0D0E0F1011",
+        "5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5",
+    ),
+];
+
+/// Signature of the BIP340 vector 18
+const LONG_MESSAGE_SIGNATURE: &str = "403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367";
+
+#[test]
+fn bip340_sign_vectors() {
+    for (index, (secret, public, aux_rand, message, expected)) in
+        SIGN_VECTORS.into_iter().enumerate()
+    {
+        let secret = SecretKey::try_from(bytes32(secret)).expect("Valid key");
+        let public = bytes32(public);
+        let aux_rand = *bytes32(aux_rand);
+        let message = decode(message);
+
+        let signature = schnorr::sign(&secret, &message, &aux_rand).expect("Signed");
+
+        assert_eq!(schnorr::public_key(&secret), public, "vector {index}");
+        assert_eq!(signature, bytes64(expected), "vector {index}");
+        assert_eq!(schnorr::verify(&public, &signature, &message), Ok(()));
+    }
+}
+
+#[test]
+fn bip340_verify_vectors() {
+    for (index, (public, message, signature, expected)) in
+        VERIFY_VECTORS.into_iter().enumerate()
+    {
+        let result =
+            schnorr::verify(&bytes32(public), &bytes64(signature), &decode(message));
+
+        assert_eq!(result, expected, "vector {}", index + 4);
+    }
+}
+
+#[test]
+fn bip340_message_length_vectors() {
+    let secret = SecretKey::try_from(bytes32(&"0340".repeat(16))).expect("Valid key");
+    let public = schnorr::public_key(&secret);
+    let mut vectors: Vec<_> = MESSAGE_LENGTH_VECTORS
+        .into_iter()
+        .map(|(message, signature)| (decode(message), bytes64(signature)))
+        .collect();
+    // The vector 18 signs 100 bytes of `0x99`
+    vectors.push((vec![0x99; 100], bytes64(LONG_MESSAGE_SIGNATURE)));
+
+    for (message, expected) in vectors {
+        let signature = schnorr::sign(&secret, &message, &[0; 32]).expect("Signed");
+
+        assert_eq!(signature, expected, "message of {} bytes", message.len());
+        assert_eq!(schnorr::verify(&public, &signature, &message), Ok(()));
+    }
+}
+
+#[test]
+fn tagged_hasher_follows_bip340() {
+    let hash = schnorr::tagged_hasher("BIP0340/challenge")
+        .chain(b"fuel")
+        .finalize();
+
+    assert_eq!(
+        hash,
+        bytes32("c421d8e7d54203d545bd21518c43274cad8e1afbe382342bfbc496d5cbf115a7")
+    );
+}
+
+#[test]
+fn any_secret_key_signs() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    // About half of the keys have a point with an odd `y`
+    for _ in 0..16 {
+        let secret = SecretKey::random(rng);
+        let message: [u8; 32] = rng.gen();
+        let aux_rand = rng.gen();
+
+        let signature = schnorr::sign(&secret, &message, &aux_rand).expect("Signed");
+
+        assert_eq!(
+            schnorr::verify(&schnorr::public_key(&secret), &signature, &message),
+            Ok(())
+        );
+        assert_eq!(
+            &schnorr::public_key(&secret)[..],
+            &secret.public_key().as_ref()[..32]
+        );
+    }
+}
+
+#[test]
+fn verify_batch_reports_the_first_invalid_signature() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let messages: Vec<[u8; 32]> = (0..8).map(|_| rng.gen()).collect();
+    let mut batch: Vec<_> = messages
+        .iter()
+        .map(|message| {
+            let secret = SecretKey::random(rng);
+            let signature = schnorr::sign(&secret, message, &rng.gen()).expect("Signed");
+            (schnorr::public_key(&secret), signature, message.as_slice())
+        })
+        .collect();
+
+    assert_eq!(schnorr::verify_batch(&batch), Ok(()));
+
+    batch[5].1 = batch[4].1;
+    batch[6].0 = Bytes32::zeroed();
+
+    assert_eq!(
+        schnorr::verify_batch(&batch),
+        Err(BatchError {
+            index: 5,
+            error: Error::InvalidSignature
+        })
+    );
+}
@@ -0,0 +1,106 @@
+use crate::{
+    Message,
+    SecretKey,
+    Signature,
+};
+
+use core::mem::ManuallyDrop;
+use fuel_types::Bytes32;
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+
+/// Keeps the key allocated after its destructor ran, to inspect the freed bytes
+struct Inspected(Box<ManuallyDrop<SecretKey>>);
+
+impl Inspected {
+    fn new(secret: SecretKey) -> Self {
+        Self(Box::new(ManuallyDrop::new(secret)))
+    }
+
+    fn drop_key(&mut self) -> [u8; SecretKey::LEN] {
+        // SAFETY: the key is dropped once, and only its plain bytes are read afterwards
+        // from the allocation still owned by the box
+        #[allow(unsafe_code)]
+        unsafe {
+            ManuallyDrop::drop(&mut self.0);
+            core::ptr::read((&**self.0 as *const SecretKey).cast())
+        }
+    }
+}
+
+#[test]
+fn secret_key_formatting_is_redacted() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let hex = format!("{secret:x}");
+
+    assert_eq!(format!("{secret:?}"), "SecretKey(<redacted>)");
+    assert_eq!(format!("{secret}"), "SecretKey(<redacted>)");
+    assert_eq!(
+        format!("{:?}", Some(&secret)),
+        "Some(SecretKey(<redacted>))"
+    );
+    assert!(!format!("{secret:?}{secret}").contains(hex.trim_start_matches("0x")));
+}
+
+#[test]
+fn secret_key_is_zeroed_on_drop() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    assert_ne!(*secret, [0; SecretKey::LEN]);
+
+    let mut inspected = Inspected::new(secret);
+
+    assert_eq!(inspected.drop_key(), [0; SecretKey::LEN]);
+}
+
+#[test]
+fn secret_key_signatures_are_unchanged() {
+    let secret = SecretKey::try_from(Bytes32::from([
+        0x3b, 0x94, 0x0b, 0x55, 0x86, 0x82, 0x3d, 0xfd, 0x02, 0xae, 0x3b, 0x46, 0x1b,
+        0xb4, 0x33, 0x6b, 0x5e, 0xcb, 0xae, 0xfd, 0x66, 0x27, 0xaa, 0x92, 0x2e, 0xfc,
+        0x04, 0x8f, 0xec, 0x0c, 0x88, 0x1c,
+    ]))
+    .expect("Valid secret key");
+    let message = Message::new(b"Every secret creates a potential failure point.");
+
+    let signature = Signature::sign(&secret, &message);
+    let cloned = Signature::sign(&secret.clone(), &message);
+
+    assert_eq!(signature, cloned);
+    assert_eq!(
+        signature.recover(&message).expect("Failed to recover"),
+        secret.public_key()
+    );
+}
+
+#[test]
+fn secret_key_from_invalid_slice_fails() {
+    assert!(SecretKey::try_from([0u8; SecretKey::LEN].as_slice()).is_err());
+    assert!(SecretKey::try_from([1u8; SecretKey::LEN - 1].as_slice()).is_err());
+    assert!(SecretKey::try_from([0xffu8; SecretKey::LEN].as_slice()).is_err());
+}
+
+#[cfg(all(feature = "mlock", unix))]
+#[test]
+fn locked_secret_key_signs_like_the_secret_key() {
+    use crate::LockedSecretKey;
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"Locked keys never hit the swap.");
+    let expected = Signature::sign(&secret, &message);
+
+    // The memory lock limit of the sandboxes may be zero
+    let Ok(locked) = LockedSecretKey::new(secret) else {
+        return
+    };
+
+    assert_eq!(Signature::sign(&locked, &message), expected);
+    assert_eq!(
+        format!("{locked:?}"),
+        "LockedSecretKey(SecretKey(<redacted>))"
+    );
+}
@@ -0,0 +1,72 @@
+use crate::{
+    test_vectors,
+    Message,
+    SecretKey,
+    Signature,
+};
+
+use fuel_types::Bytes32;
+
+fn key(hex: &str) -> SecretKey {
+    hex.parse().expect("Valid secret key")
+}
+
+#[test]
+fn from_seed_matches_the_golden_keys() {
+    let seed: [u8; 32] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+
+    assert_eq!(
+        SecretKey::from_seed(&[]),
+        key("6ff2657afe493098ae85c968ee87ca70f5805c06939321c5c6fe6c4810a00821")
+    );
+    assert_eq!(
+        SecretKey::from_seed(b"fuel"),
+        key("ff1a774892dcdfbe1f7c9c11f029d72d2b2d61804902896eb4b9ed98701f00a4")
+    );
+    assert_eq!(
+        SecretKey::from_seed(&seed),
+        key("dffac25a0d8882114c98210c861217fc61b73dfec1b27b2e73dc464528e9d563")
+    );
+}
+
+#[test]
+fn from_seed_depends_on_every_byte_of_the_seed() {
+    let seed = [7u8; 32];
+    let secret = SecretKey::from_seed(&seed);
+
+    for index in 0..seed.len() {
+        let mut altered = seed;
+        altered[index] ^= 1;
+
+        assert_ne!(SecretKey::from_seed(&altered), secret);
+    }
+    assert_ne!(SecretKey::from_seed(&seed[..31]), secret);
+}
+
+#[test]
+fn test_vectors_match_the_golden_keys() {
+    let keyring = test_vectors::keyring::<4>();
+
+    assert_eq!(
+        keyring,
+        [
+            key("9b398680282559b99cf4d472f9c1d9aec4a0983da55f0f7c817d48524a7fdc93"),
+            key("5bb5cc26db1953c9c9b66761c0858de91f7e4071920e9d144fbfd8a850b7de14"),
+            key("e3ce700e65fb3f025ff64167a730bcb0e99d7c9cd6eefe1795abc0fcf512f289"),
+            key("b5f1a83fa99b7756f04206d205cae57559ce03e8d7d8275340feebcc79db58a7"),
+        ]
+    );
+    assert_eq!(test_vectors::secret_key(2), keyring[2]);
+}
+
+#[test]
+fn seeded_keys_sign() {
+    let message = Message::new(b"Deterministic keys for deterministic machines.");
+
+    for secret in test_vectors::keyring::<8>() {
+        let signature = Signature::sign(&secret, &message);
+
+        assert_eq!(signature.recover(&message), Ok(secret.public_key()));
+        assert_ne!(*secret, *Bytes32::zeroed());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    Message,
+    SecretKey,
+    Signature,
+    Signer,
+    SignerError,
+};
+
+#[cfg(feature = "std")]
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+
+#[cfg(feature = "std")]
+#[test]
+fn secret_key_signer_matches_signature_sign() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let message = Message::new(b"The signer is only as good as the key it holds.");
+    let secret = SecretKey::random(rng);
+
+    let signature = Signer::sign(&secret, &message).expect("Failed to sign");
+
+    assert_eq!(signature, Signature::sign(&secret, &message));
+    assert_eq!(Signer::public_key(&secret), secret.public_key());
+    signature
+        .verify(&Signer::public_key(&secret), &message)
+        .expect("Failed to verify");
+}
+
+#[test]
+fn crypto_errors_convert_into_signer_errors() {
+    let error = SignerError::from(crate::Error::FailedToSign);
+
+    assert_eq!(error, SignerError::Crypto(crate::Error::FailedToSign));
+}
+
+#[cfg(all(feature = "std", feature = "async"))]
+#[test]
+fn secret_key_async_signer_matches_signer() {
+    use crate::AsyncSigner;
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{
+            Context,
+            Poll,
+        },
+    };
+    use std::{
+        sync::Arc,
+        task::Wake,
+    };
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let message = Message::new(b"Some signatures are worth waiting for.");
+    let secret = SecretKey::random(rng);
+
+    let waker = Arc::new(NoopWaker).into();
+    let mut context = Context::from_waker(&waker);
+    let future = pin!(AsyncSigner::sign(&secret, &message));
+    let Poll::Ready(signature) = future.poll(&mut context) else {
+        panic!("The in-memory signer is always ready")
+    };
+
+    assert_eq!(signature, Signer::sign(&secret, &message));
+}
@@ -0,0 +1,146 @@
+use crate::{
+    wallet::{
+        self,
+        DEFAULT_DERIVATION_PATH,
+    },
+    Error,
+    SecretKey,
+};
+
+use fuel_types::Bytes32;
+
+const PHRASE: &str =
+    "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+fn hex(secret: &SecretKey) -> String {
+    format!("{secret:x}").trim_start_matches("0x").to_owned()
+}
+
+fn decode(hex: &str) -> Vec<u8> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|chunk| {
+            let chunk = core::str::from_utf8(chunk).expect("Valid hex");
+            u8::from_str_radix(chunk, 16).expect("Valid hex")
+        })
+        .collect()
+}
+
+#[test]
+fn bip39_seeds_match_the_test_vectors() {
+    // (phrase, passphrase, seed) of the trezor/python-mnemonic vectors
+    let vectors = [
+        (
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "TREZOR",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "TREZOR",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+    ];
+
+    for (phrase, passphrase, seed) in vectors {
+        let expected = decode(seed);
+
+        let seed = wallet::mnemonic_to_seed(phrase, passphrase).expect("Valid phrase");
+
+        assert_eq!(seed.as_slice(), expected.as_slice());
+    }
+}
+
+#[test]
+fn bip32_keys_match_the_test_vectors() {
+    // (seed, path, secret key) of the BIP32 test vectors 1 and 2
+    let vectors = [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "m",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "m/0'/1",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "m/0'/1/2'/2/1000000000",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ),
+        (
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            "m/0",
+            "abe74a98f6c7eabee0428f53798f0ab8aa1bd37873999041703c742f15ac7e1e",
+        ),
+        (
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            "m/0/2147483647'/1/2147483646'/2",
+            "bb7d39bdb83ecf58f2fd82b6d918341cbef428661ef01ab97c28a4842125ac23",
+        ),
+    ];
+
+    for (seed, path, expected) in vectors {
+        let secret =
+            wallet::derive_from_seed(&decode(seed), path).expect("Valid derivation");
+
+        assert_eq!(hex(&secret), expected, "{path}");
+    }
+}
+
+#[test]
+fn derive_from_mnemonic_matches_the_fuels_wallet_address() {
+    let secret = SecretKey::derive_from_mnemonic(PHRASE, "m/44'/60'/0'/0/0")
+        .expect("Valid derivation");
+
+    assert_eq!(
+        secret.public_key().address().to_string(),
+        "df9d0e6c6c5f5da6e82e5e1a77974af6642bdb450a10c43f0c6910a212600185"
+    );
+    assert_eq!(
+        Ok(secret),
+        SecretKey::new_from_mnemonic_phrase_with_path(PHRASE, "m/44'/60'/0'/0/0")
+    );
+}
+
+#[test]
+fn derive_from_mnemonic_uses_the_empty_passphrase() {
+    let secret = SecretKey::derive_from_mnemonic(PHRASE, DEFAULT_DERIVATION_PATH)
+        .expect("Valid derivation");
+    let with_passphrase =
+        wallet::derive_from_mnemonic(PHRASE, "passphrase", DEFAULT_DERIVATION_PATH)
+            .expect("Valid derivation");
+
+    assert_eq!(
+        Ok(secret.clone()),
+        wallet::derive_from_mnemonic(PHRASE, "", DEFAULT_DERIVATION_PATH)
+    );
+    assert_ne!(secret, with_passphrase);
+}
+
+#[test]
+fn account_derivation_paths_are_the_fuel_wallet_ones() {
+    assert_eq!(wallet::account_derivation_path(0), DEFAULT_DERIVATION_PATH);
+    assert_eq!(
+        wallet::account_derivation_path(7),
+        "m/44'/1179993420'/7'/0/0"
+    );
+}
+
+#[test]
+fn invalid_derivations_fail() {
+    assert_eq!(
+        SecretKey::derive_from_mnemonic("oblige salon price", DEFAULT_DERIVATION_PATH),
+        Err(Error::InvalidMnemonic)
+    );
+    assert_eq!(
+        SecretKey::derive_from_mnemonic(PHRASE, "m/44'/x"),
+        Err(Error::Bip32Error)
+    );
+    assert_eq!(
+        wallet::derive_from_seed(&Bytes32::zeroed()[..15], DEFAULT_DERIVATION_PATH),
+        Err(Error::Bip32Error)
+    );
+}
@@ -0,0 +1,72 @@
+//! Hierarchical deterministic keys of the Fuel wallets.
+//!
+//! The mnemonic phrases are turned into seeds as defined by BIP39, and the secp256k1
+//! keys are derived from the seeds as defined by BIP32. The Fuel wallets derive the key
+//! of the account `n` at the path `m/44'/1179993420'/n'/0/0`, where `1179993420` is the
+//! SLIP-44 coin type of Fuel.
+
+use crate::{
+    Error,
+    SecretKey,
+};
+
+use coins_bip32::{
+    path::DerivationPath,
+    xkeys::XPriv,
+};
+use coins_bip39::{
+    English,
+    Mnemonic,
+};
+use core::str::FromStr;
+use fuel_types::Bytes32;
+use zeroize::Zeroize;
+
+/// Derivation path of the first account of the Fuel wallets.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/1179993420'/0'/0/0";
+
+/// Length of the BIP39 seeds in bytes.
+pub const SEED_LEN: usize = 64;
+
+/// Derivation path of the `account` of the Fuel wallets.
+pub fn account_derivation_path(account: u32) -> String {
+    format!("m/44'/1179993420'/{account}'/0/0")
+}
+
+/// Computes the BIP39 seed of the English mnemonic `phrase`, salted with the
+/// `passphrase`.
+///
+/// The empty passphrase is the one used by the wallets that don't ask for it.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; SEED_LEN], Error> {
+    let mnemonic = Mnemonic::<English>::new_from_phrase(phrase)?;
+    Ok(mnemonic.to_seed(Some(passphrase))?)
+}
+
+/// Derives the BIP32 secp256k1 secret key of the `seed` at the `path`, like
+/// `m/44'/1179993420'/0'/0/0`. The indices suffixed with `'` are hardened.
+///
+/// Fails with [`Error::Bip32Error`] if the path is malformed or the seed is shorter
+/// than 16 bytes.
+pub fn derive_from_seed(seed: &[u8], path: &str) -> Result<SecretKey, Error> {
+    let path = DerivationPath::from_str(path)?;
+    let derived = XPriv::root_from_seed(seed, None)?.derive_path(path)?;
+    let key: &coins_bip32::ecdsa::SigningKey = derived.as_ref();
+
+    let mut bytes: [u8; SecretKey::LEN] = key.to_bytes().into();
+    let secret = SecretKey::try_from(Bytes32::from(bytes));
+    bytes.zeroize();
+    secret
+}
+
+/// Derives the secret key of the English mnemonic `phrase` salted with the
+/// `passphrase`, at the `path`.
+pub fn derive_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    path: &str,
+) -> Result<SecretKey, Error> {
+    let mut seed = mnemonic_to_seed(phrase, passphrase)?;
+    let secret = derive_from_seed(&seed, path);
+    seed.zeroize();
+    secret
+}
@@ -102,3 +102,45 @@ pub fn should_skip_field(attrs: &[Attribute]) -> bool {
     }
     false
 }
+
+/// Parse `#[canonical(unknown)]` attribute for an enum variant.
+///
+/// The variant has the `discriminant: u64` and `bytes: Vec<u8>` fields, and it is
+/// decoded from any discriminant that doesn't match the other variants. The `bytes`
+/// take the rest of the input, and they are encoded as is after the discriminant.
+pub fn is_unknown_variant(variant: &synstructure::VariantInfo<'_>) -> bool {
+    for attr in variant.ast().attrs {
+        if attr.style != AttrStyle::Outer {
+            continue
+        }
+        if let Meta::List(ml) = &attr.meta {
+            if ml.path.segments.len() == 1 && ml.path.segments[0].ident == "canonical" {
+                for token in ml.tokens.clone() {
+                    if let TokenTree::Ident(ident) = &token {
+                        if ident == "unknown" {
+                            return true
+                        } else {
+                            panic!("unknown canonical attribute: {}", ident)
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Returns the identifier of the `#[canonical(unknown)]` variant of the enum, if any.
+pub fn unknown_variant(s: &synstructure::Structure) -> Option<syn::Ident> {
+    let mut variants = s.variants().iter().filter(|v| is_unknown_variant(v));
+    let unknown = variants.next().map(|v| v.ast().ident.clone());
+    if variants.next().is_some() {
+        panic!("only one variant can be marked with `#[canonical(unknown)]`");
+    }
+    if let Some(last) = s.variants().last() {
+        if unknown.is_some() && !is_unknown_variant(last) {
+            panic!("the `#[canonical(unknown)]` variant must be the last one");
+        }
+    }
+    unknown
+}
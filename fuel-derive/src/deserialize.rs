@@ -5,8 +5,10 @@ use quote::{
 };
 
 use crate::attribute::{
+    is_unknown_variant,
     should_skip_field,
     should_skip_field_binding,
+    unknown_variant,
     StructAttrs,
 };
 
@@ -77,6 +79,32 @@ fn deserialize_enum(s: &synstructure::Structure) -> TokenStream2 {
 
     assert!(!s.variants().is_empty(), "got invalid empty enum");
 
+    let unknown = unknown_variant(s);
+    let mut s = s.clone();
+    s.filter_variants(|v| !is_unknown_variant(v));
+    let s = &s;
+
+    // The unknown variant takes the rest of the input, because its size is unknown.
+    let (decode_unknown, decode_dynamic_unknown) = match &unknown {
+        Some(ident) => (
+            quote! {
+                discriminant => {
+                    let mut bytes = ::alloc::vec::Vec::new();
+                    bytes.resize(buffer.remaining(), 0u8);
+                    buffer.read(bytes.as_mut_slice())?;
+                    ::core::result::Result::Ok(Self::#ident { discriminant, bytes })
+                }
+            },
+            quote! { Self::#ident { .. } => {} },
+        ),
+        None => (
+            quote! {
+                _ => ::core::result::Result::Err(::fuel_types::canonical::Error::UnknownDiscriminant),
+            },
+            quote! {},
+        ),
+    };
+
     let mut next_discriminant = quote! { { 0u64 } };
     let enum_ident = &s.ast().ident;
     let calculated_discriminants =
@@ -155,7 +183,7 @@ fn deserialize_enum(s: &synstructure::Structure) -> TokenStream2 {
 
                 match #discriminant {
                     #decode_static
-                    _ => ::core::result::Result::Err(::fuel_types::canonical::Error::UnknownDiscriminant),
+                    #decode_unknown
                 }
             }
 
@@ -164,6 +192,7 @@ fn deserialize_enum(s: &synstructure::Structure) -> TokenStream2 {
                     #(
                         #decode_dynamic
                     )*
+                    #decode_dynamic_unknown
                     _ => return ::core::result::Result::Err(::fuel_types::canonical::Error::UnknownDiscriminant),
                 };
 
@@ -2,7 +2,9 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 
 use crate::attribute::{
+    is_unknown_variant,
     should_skip_field_binding,
+    unknown_variant,
     StructAttrs,
 };
 
@@ -93,11 +95,33 @@ fn serialize_enum(s: &synstructure::Structure) -> TokenStream2 {
     assert!(!s.variants().is_empty(), "got invalid empty enum");
     let mut s = s.clone();
     let mut next_discriminant = quote! { { 0u64 } };
+    let unknown = unknown_variant(&s);
 
     s.variants_mut().iter_mut().for_each(|v| {
         v.filter(|binding| !should_skip_field_binding(binding));
     });
 
+    // The unknown variant is the last one, so it doesn't affect the discriminants of
+    // the other variants.
+    let (unknown_size_static, unknown_encode_static, unknown_empty) = match &unknown {
+        Some(ident) => (
+            quote! {
+                Self::#ident { bytes, .. } => {
+                    size = ::fuel_types::canonical::add_sizes(size, bytes.len());
+                }
+            },
+            quote! {
+                Self::#ident { discriminant, bytes } => {
+                    <::core::primitive::u64 as ::fuel_types::canonical::Serialize>::encode(discriminant, buffer)?;
+                    buffer.write(bytes.as_slice())?;
+                }
+            },
+            quote! { Self::#ident { .. } => {} },
+        ),
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+    s.filter_variants(|v| !is_unknown_variant(v));
+
     let encode_static = s.variants().iter().map(|v| {
         let pat = v.pat();
 
@@ -151,7 +175,7 @@ fn serialize_enum(s: &synstructure::Structure) -> TokenStream2 {
     let match_size_static = quote! {{
         // `repr(128)` is unstable, so because of that we can use 8 bytes.
         let mut size = 8;
-        match self { #match_size_static } size }
+        match self { #match_size_static #unknown_size_static } size }
     };
 
     let match_size_dynamic: TokenStream2 = s
@@ -165,8 +189,11 @@ fn serialize_enum(s: &synstructure::Structure) -> TokenStream2 {
             })
         })
         .collect();
-    let match_size_dynamic =
-        quote! {{ let mut size = 0; match self { #match_size_dynamic } size }};
+    let match_size_dynamic = quote! {{
+        let mut size = 0;
+        match self { #match_size_dynamic #unknown_empty }
+        size
+    }};
 
     let impl_code = s.gen_impl(quote! {
         gen impl ::fuel_types::canonical::Serialize for @Self {
@@ -185,7 +212,8 @@ fn serialize_enum(s: &synstructure::Structure) -> TokenStream2 {
                 match self {
                     #(
                         #encode_static
-                    )*,
+                    )*
+                    #unknown_encode_static
                     _ => return ::core::result::Result::Err(::fuel_types::canonical::Error::UnknownDiscriminant),
                 };
 
@@ -196,7 +224,8 @@ fn serialize_enum(s: &synstructure::Structure) -> TokenStream2 {
                 match self {
                     #(
                         #encode_dynamic
-                    )*,
+                    )*
+                    #unknown_empty
                     _ => return ::core::result::Result::Err(::fuel_types::canonical::Error::UnknownDiscriminant),
                 };
 
@@ -0,0 +1,80 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+};
+use fuel_merkle::binary::{
+    in_memory,
+    root_calculator::MerkleRootCalculator,
+};
+
+const LEAVES_COUNT: u64 = 100_000;
+
+fn binary_parallel(c: &mut Criterion) {
+    let leaves: Vec<_> = (0..LEAVES_COUNT).map(u64::to_le_bytes).collect();
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let threads = core::iter::successors(Some(1), |n| Some(n * 2))
+        .take_while(|n| *n <= max_threads)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("binary-parallel");
+    group.sample_size(10);
+
+    group.bench_function("tree-push-100k-leaves", |b| {
+        b.iter(|| {
+            let mut tree = in_memory::MerkleTree::new();
+            for leaf in black_box(&leaves) {
+                tree.push(leaf);
+            }
+            tree.root()
+        });
+    });
+    group.bench_function("calculator-push-all-100k-leaves", |b| {
+        b.iter(|| {
+            let mut calculator = MerkleRootCalculator::new();
+            calculator.push_all(black_box(&leaves));
+            calculator.root()
+        });
+    });
+
+    // The same work on thread pools of increasing sizes, up to the available cores.
+    for threads in threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build the thread pool");
+
+        group.bench_with_input(
+            BenchmarkId::new("tree-from-leaves-parallel-100k-leaves", threads),
+            &threads,
+            |b, _| {
+                b.iter(|| {
+                    pool.install(|| {
+                        in_memory::MerkleTree::from_leaves_parallel(black_box(&leaves))
+                            .root()
+                    })
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("calculator-push-all-parallel-100k-leaves", threads),
+            &threads,
+            |b, _| {
+                b.iter(|| {
+                    pool.install(|| {
+                        let mut calculator = MerkleRootCalculator::new();
+                        calculator.push_all_parallel(black_box(&leaves));
+                        calculator.root()
+                    })
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, binary_parallel);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+
+const LEAVES_COUNT: u64 = 1_000_000;
+
+// The leaves are generated on the fly, so the only memory used by the calculation is
+// the state of the calculator.
+fn root(leaves_count: u64) -> MerkleRootCalculator {
+    let mut calculator = MerkleRootCalculator::new();
+    calculator.push_all((0..leaves_count).map(u64::to_le_bytes));
+    calculator
+}
+
+fn binary_merkle_root_calculator(c: &mut Criterion) {
+    // The state holds one subtree per set bit of the leaves count: it stays within
+    // the 20 subtrees of the 20 bits of a million, whatever the number of leaves
+    // pushed before.
+    let mut calculator = MerkleRootCalculator::new();
+    let mut max_state_len = 0;
+    for value in 0..LEAVES_COUNT {
+        calculator.push(&value.to_le_bytes());
+        max_state_len = max_state_len.max(calculator.stack().len());
+    }
+    assert!(max_state_len <= 20);
+    assert_eq!(
+        calculator.to_bytes().len(),
+        2 + 33 * calculator.stack().len()
+    );
+
+    let mut group = c.benchmark_group("root-calculator");
+    group.sample_size(10);
+
+    group.bench_function("push-1m-leaves", |b| {
+        b.iter(|| root(black_box(LEAVES_COUNT)).root());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, binary_merkle_root_calculator);
+criterion_main!(benches);
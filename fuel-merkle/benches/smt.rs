@@ -2,6 +2,7 @@ use criterion::{
     black_box,
     criterion_group,
     criterion_main,
+    BatchSize,
     Criterion,
 };
 use fuel_merkle::{
@@ -102,5 +103,103 @@ fn sparse_merkle_tree(c: &mut Criterion) {
     group_update.finish();
 }
 
-criterion_group!(benches, sparse_merkle_tree);
+fn sparse_merkle_tree_update_batch(c: &mut Criterion) {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let gen = || Some((MerkleTreeKey::new(random_bytes32(rng)), random_bytes32(rng)));
+    let initial = core::iter::from_fn(gen).take(10_000).collect::<Vec<_>>();
+
+    // 10k updates: half of them overwrite or delete the existing keys, and the other
+    // half insert new keys.
+    let mut updates = initial
+        .iter()
+        .step_by(2)
+        .enumerate()
+        .map(|(i, (key, _))| (*key, (i % 4 != 0).then(|| random_bytes32(rng))))
+        .collect::<Vec<_>>();
+    for _ in 0..5_000 {
+        updates.push((
+            MerkleTreeKey::new(random_bytes32(rng)),
+            Some(random_bytes32(rng)),
+        ));
+    }
+    let mut sorted = updates.clone();
+    sorted.sort_by_key(|(key, _)| **key);
+
+    let tree = || in_memory::MerkleTree::from_set(initial.clone().into_iter());
+    let sequential = |mut tree: in_memory::MerkleTree| {
+        for (key, data) in sorted.iter() {
+            match data {
+                Some(data) => tree.update(*key, data),
+                None => tree.delete(*key),
+            }
+        }
+        tree.root()
+    };
+    assert_eq!(tree().update_batch(updates.clone()), sequential(tree()));
+
+    let mut group = c.benchmark_group("update-batch");
+
+    group.bench_function("update-batch-10k", |b| {
+        b.iter_batched(
+            tree,
+            |mut tree| tree.update_batch(black_box(updates.clone())),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("update-batch-10k-baseline", |b| {
+        b.iter_batched(
+            tree,
+            |tree| sequential(black_box(tree)),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+// Inserts and looks up 100k keys, whose paths are read with the fixed-width routines
+// of the 256 bits keys. Compare against the byte-by-byte key routines by saving a
+// baseline on the previous revision with `cargo bench --bench smt -- --save-baseline
+// generic` and running `cargo bench --bench smt -- --baseline generic 100k`.
+fn sparse_merkle_tree_100k_keys(c: &mut Criterion) {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let gen = || Some((MerkleTreeKey::new(random_bytes32(rng)), random_bytes32(rng)));
+    let data = core::iter::from_fn(gen).take(100_000).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("100k-keys");
+    group.sample_size(10);
+
+    group.bench_with_input("insert-100k", &data, |b, data| {
+        b.iter(|| baseline_root(black_box(data.clone().into_iter())));
+    });
+
+    let tree = in_memory::MerkleTree::from_set(data.clone().into_iter());
+    group.bench_with_input("get-100k", &data, |b, data| {
+        b.iter(|| {
+            for (key, _) in data.iter() {
+                black_box(tree.generate_proof(black_box(key)));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    sparse_merkle_tree,
+    sparse_merkle_tree_update_batch,
+    sparse_merkle_tree_100k_keys
+);
 criterion_main!(benches);
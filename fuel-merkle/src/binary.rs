@@ -1,13 +1,21 @@
 mod hash;
 mod merkle_tree;
+mod multi_proof;
 mod node;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod primitive;
+mod proof;
 mod verify;
 
+#[cfg(test)]
+pub(crate) use hash::node_sum;
 pub(crate) use hash::{
+    empty_hash,
     empty_sum,
+    leaf_hash,
     leaf_sum,
-    node_sum,
+    node_hash,
 };
 pub(crate) use node::Node;
 
@@ -15,8 +23,21 @@ pub use merkle_tree::{
     MerkleTree,
     MerkleTreeError,
 };
+pub use multi_proof::{
+    verify_multi,
+    verify_multi_with_hash,
+    MultiProof,
+    MultiProofDecodeError,
+};
 pub use primitive::Primitive;
+pub use proof::{
+    InclusionProof,
+    ProofDecodeError,
+};
 pub mod in_memory;
 pub mod root_calculator;
 
-pub use verify::verify;
+pub use verify::{
+    verify,
+    verify_with_hash,
+};
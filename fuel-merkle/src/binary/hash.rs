@@ -1,14 +1,10 @@
 use crate::common::{
     empty_sum_sha256,
     Bytes32,
-    Prefix,
+    MerkleHash,
+    Sha256,
 };
 
-use digest::Digest;
-use sha2::Sha256;
-
-type Hash = Sha256;
-
 // Merkle Tree hash of an empty list
 // MTH({}) = Hash()
 pub const fn empty_sum() -> &'static Bytes32 {
@@ -17,23 +13,26 @@ pub const fn empty_sum() -> &'static Bytes32 {
 
 // Merkle tree hash of an n-element list D[n]
 // MTH(D[n]) = Hash(0x01 || MTH(D[0:k]) || MTH(D[k:n])
+#[cfg(test)]
 pub fn node_sum(lhs_data: &Bytes32, rhs_data: &Bytes32) -> Bytes32 {
-    let mut hash = Hash::new();
-
-    hash.update(Prefix::Node);
-    hash.update(lhs_data);
-    hash.update(rhs_data);
-
-    hash.finalize().into()
+    node_hash::<Sha256>(lhs_data, rhs_data)
 }
 
 // Merkle tree hash of a list with one entry
 // MTH({d(0)}) = Hash(0x00 || d(0))
 pub fn leaf_sum(data: &[u8]) -> Bytes32 {
-    let mut hash = Hash::new();
+    leaf_hash::<Sha256>(data)
+}
+
+// The hashes of the tree with the hash function `H` and its prefixes.
+pub fn empty_hash<H: MerkleHash>() -> Bytes32 {
+    H::hash(&[])
+}
 
-    hash.update(Prefix::Leaf);
-    hash.update(data);
+pub fn node_hash<H: MerkleHash>(lhs_data: &Bytes32, rhs_data: &Bytes32) -> Bytes32 {
+    H::hash(&[&[H::NODE_PREFIX], lhs_data, rhs_data])
+}
 
-    hash.finalize().into()
+pub fn leaf_hash<H: MerkleHash>(data: &[u8]) -> Bytes32 {
+    H::hash(&[&[H::LEAF_PREFIX], data])
 }
@@ -1,6 +1,8 @@
 use crate::{
     binary::{
         self,
+        InclusionProof,
+        MultiProof,
         Primitive,
     },
     common::{
@@ -39,6 +41,15 @@ impl MerkleTree {
         }
     }
 
+    /// Builds the tree of the leaves with the `data`, hashing them on the rayon thread
+    /// pool, like [`binary::MerkleTree::from_leaves_parallel`].
+    #[cfg(feature = "rayon")]
+    pub fn from_leaves_parallel<T: AsRef<[u8]> + Sync>(data: &[T]) -> Self {
+        let tree = BinaryMerkleTree::from_leaves_parallel(Storage::new(), data)
+            .expect("`Storage` can't return error");
+        Self { tree }
+    }
+
     pub fn push(&mut self, data: &[u8]) {
         let _ = self.tree.push(data);
     }
@@ -51,6 +62,14 @@ impl MerkleTree {
         self.tree.prove(proof_index).ok()
     }
 
+    pub fn generate_inclusion_proof(&self, proof_index: u64) -> Option<InclusionProof> {
+        self.tree.generate_inclusion_proof(proof_index).ok()
+    }
+
+    pub fn generate_multi_proof(&self, indices: &[u64]) -> Option<MultiProof> {
+        self.tree.generate_multi_proof(indices).ok()
+    }
+
     pub fn reset(&mut self) {
         self.tree.reset();
     }
@@ -1,14 +1,20 @@
 use crate::{
     binary::{
+        empty_hash,
         empty_sum,
         in_memory::NodesTable,
+        InclusionProof,
+        MultiProof,
         Node,
         Primitive,
     },
     common::{
+        left_subtree_leaves_count,
         Bytes32,
+        MerkleHash,
         Position,
         ProofSet,
+        Sha256,
         StorageMap,
         Subtree,
     },
@@ -42,24 +48,32 @@ impl<StorageError> From<StorageError> for MerkleTreeError<StorageError> {
     }
 }
 
+/// The binary Merkle tree of the leaves pushed into the `StorageType`, hashed with the
+/// `HashType`, SHA-256 by default.
 #[derive(Debug, Clone)]
-pub struct MerkleTree<TableType, StorageType> {
+pub struct MerkleTree<TableType, StorageType, HashType = Sha256> {
     storage: StorageType,
     head: Option<Subtree<Node>>,
     leaves_count: u64,
     phantom_table: PhantomData<TableType>,
+    phantom_hash: PhantomData<HashType>,
 }
 
 impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
     pub const fn empty_root() -> &'static Bytes32 {
         empty_sum()
     }
+}
 
+impl<TableType, StorageType, HashType> MerkleTree<TableType, StorageType, HashType>
+where
+    HashType: MerkleHash,
+{
     pub fn root(&self) -> Bytes32 {
         let mut scratch_storage = StorageMap::<NodesTable>::new();
         let root_node = self.root_node(&mut scratch_storage);
         match root_node {
-            None => *Self::empty_root(),
+            None => empty_hash::<HashType>(),
             Some(ref node) => *node.hash(),
         }
     }
@@ -93,7 +107,7 @@ impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
     /// callee.
     fn root_node(&self, scratch_storage: &mut StorageMap<NodesTable>) -> Option<Node> {
         self.head()
-            .map(|head| build_root_node(head, scratch_storage))
+            .map(|head| build_root_node::<HashType, _, _>(head, scratch_storage))
     }
 
     fn peak_positions(&self) -> Vec<Position> {
@@ -132,15 +146,38 @@ where
     StorageType: StorageInspect<TableType, Error = StorageError>,
 {
     pub fn new(storage: StorageType) -> Self {
+        Self::new_with_hash(storage)
+    }
+
+    pub fn load(
+        storage: StorageType,
+        leaves_count: u64,
+    ) -> Result<Self, MerkleTreeError<StorageError>> {
+        Self::load_with_hash(storage, leaves_count)
+    }
+}
+
+impl<TableType, StorageType, StorageError, HashType>
+    MerkleTree<TableType, StorageType, HashType>
+where
+    TableType: Mappable<Key = u64, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+    HashType: MerkleHash,
+{
+    /// Creates the empty tree hashed with the `HashType`, like [`MerkleTree::new`].
+    pub fn new_with_hash(storage: StorageType) -> Self {
         Self {
             storage,
             head: None,
             leaves_count: 0,
             phantom_table: Default::default(),
+            phantom_hash: Default::default(),
         }
     }
 
-    pub fn load(
+    /// Loads the tree of `leaves_count` leaves hashed with the `HashType`, like
+    /// [`MerkleTree::load`].
+    pub fn load_with_hash(
         storage: StorageType,
         leaves_count: u64,
     ) -> Result<Self, MerkleTreeError<StorageError>> {
@@ -149,6 +186,7 @@ where
             head: None,
             leaves_count,
             phantom_table: Default::default(),
+            phantom_hash: Default::default(),
         };
 
         tree.build()?;
@@ -200,6 +238,60 @@ where
         Ok((root, proof_set))
     }
 
+    /// Generates the [`InclusionProof`] of the leaf of the `proof_index`.
+    pub fn generate_inclusion_proof(
+        &self,
+        proof_index: u64,
+    ) -> Result<InclusionProof, MerkleTreeError<StorageError>> {
+        let (_, proof_set) = self.prove(proof_index)?;
+        Ok(InclusionProof {
+            leaf_index: proof_index,
+            leaves_count: self.leaves_count,
+            proof_set,
+        })
+    }
+
+    /// Generates the [`MultiProof`] of the leaves of the `indices`.
+    ///
+    /// The indices may be given in any order, and the duplicate indices are
+    /// ignored: the proof of `[5, 2, 5]` is the proof of `[2, 5]`.
+    pub fn generate_multi_proof(
+        &self,
+        indices: &[u64],
+    ) -> Result<MultiProof, MerkleTreeError<StorageError>> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if let Some(index) = indices.last() {
+            if *index >= self.leaves_count {
+                return Err(MerkleTreeError::InvalidProofIndex(*index))
+            }
+        }
+
+        let mut proof = MultiProof {
+            leaves_count: self.leaves_count,
+            structure: Vec::new(),
+            proof_set: ProofSet::new(),
+        };
+        if self.leaves_count == 0 {
+            return Ok(proof)
+        }
+
+        // Allocate scratch storage to store temporary nodes when building the
+        // root.
+        let mut scratch_storage = StorageMap::<NodesTable>::new();
+        self.root_node(&mut scratch_storage);
+        self.walk_multi_proof(
+            &scratch_storage,
+            0,
+            self.leaves_count,
+            &indices,
+            &mut proof,
+        )?;
+
+        Ok(proof)
+    }
+
     pub fn reset(&mut self) {
         self.leaves_count = 0;
         self.head = None;
@@ -208,6 +300,49 @@ where
     // PRIVATE
     //
 
+    /// Records the walk of the subtree of the leaves `start..start + leaves_count`
+    /// in the `proof`, where `indices` are the proven leaves of the subtree.
+    fn walk_multi_proof(
+        &self,
+        scratch_storage: &StorageMap<NodesTable>,
+        start: u64,
+        leaves_count: u64,
+        indices: &[u64],
+        proof: &mut MultiProof,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        if indices.is_empty() {
+            // The subtree of the leaves `start..start + leaves_count` is rooted
+            // at the height `ceil(log2(leaves_count))` above the first leaf.
+            let height = 64 - (leaves_count - 1).leading_zeros();
+            let key = start * 2 + (1 << height) - 1;
+            let primitive = StorageInspectInfallible::get(scratch_storage, &key)
+                .or(StorageInspect::get(&self.storage, &key)?)
+                .ok_or(MerkleTreeError::LoadError(key))?
+                .into_owned();
+            proof.structure.push(false);
+            proof.proof_set.push(*Node::from(primitive).hash());
+            return Ok(())
+        }
+
+        proof.structure.push(true);
+        if leaves_count > 1 {
+            let left_count = left_subtree_leaves_count(leaves_count);
+            let middle = start + left_count;
+            let (left, right) =
+                indices.split_at(indices.partition_point(|index| *index < middle));
+            self.walk_multi_proof(scratch_storage, start, left_count, left, proof)?;
+            self.walk_multi_proof(
+                scratch_storage,
+                middle,
+                leaves_count - left_count,
+                right,
+                proof,
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// A binary Merkle tree can be built from a collection of Merkle Mountain
     /// Range (MMR) peaks. The MMR structure can be accurately defined by the
     /// number of leaves in the leaf row.
@@ -308,13 +443,33 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
 impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
 where
     TableType: Mappable<Key = u64, Value = Primitive, OwnedValue = Primitive>,
     StorageType: StorageMutate<TableType, Error = StorageError>,
+{
+    /// Builds the tree of the leaves with the `data`, hashing the leaves and the nodes of
+    /// each level on the rayon thread pool. The tree, its root and the nodes written to
+    /// the `storage` are the same as after pushing the leaves one by one into an empty
+    /// tree, and more leaves can be pushed after.
+    pub fn from_leaves_parallel<T: AsRef<[u8]> + Sync>(
+        storage: StorageType,
+        data: &[T],
+    ) -> Result<Self, StorageError> {
+        Self::from_leaves_parallel_with_hash(storage, data)
+    }
+}
+
+impl<TableType, StorageType, StorageError, HashType>
+    MerkleTree<TableType, StorageType, HashType>
+where
+    TableType: Mappable<Key = u64, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageMutate<TableType, Error = StorageError>,
+    HashType: MerkleHash,
 {
     pub fn push(&mut self, data: &[u8]) -> Result<(), StorageError> {
-        let node = Node::create_leaf(self.leaves_count, data);
+        let node = Node::create_leaf::<HashType>(self.leaves_count, data);
         self.storage.insert(&node.key(), &node.as_ref().into())?;
         let next = self.head.take();
         let head = Subtree::new(node, next);
@@ -326,6 +481,38 @@ where
         Ok(())
     }
 
+    /// Builds the tree of the leaves with the `data` hashed with the `HashType`, like
+    /// [`MerkleTree::from_leaves_parallel`].
+    #[cfg(feature = "rayon")]
+    pub fn from_leaves_parallel_with_hash<T: AsRef<[u8]> + Sync>(
+        storage: StorageType,
+        data: &[T],
+    ) -> Result<Self, StorageError> {
+        let mut tree = Self::new_with_hash(storage);
+
+        // Each level pairs its nodes from the left. The last node of a level of odd
+        // length is the head of a balanced subtree, from the lowest to the highest.
+        let mut peaks = Vec::new();
+        let mut level = super::parallel::leaves::<HashType, _>(data);
+        while !level.is_empty() {
+            for node in level.iter() {
+                tree.storage.insert(&node.key(), &node.into())?;
+            }
+            if level.len() % 2 == 1 {
+                peaks.extend(level.last().cloned());
+            }
+            level = super::parallel::parents::<HashType>(&level);
+        }
+
+        tree.head = peaks
+            .into_iter()
+            .rev()
+            .fold(None, |next, peak| Some(Subtree::new(peak, next)));
+        tree.leaves_count = data.len() as u64;
+
+        Ok(tree)
+    }
+
     // PRIVATE
     //
 
@@ -348,7 +535,7 @@ where
             // Merge the two front heads of the list into a single head
             let mut head = self.head.take().expect("Expected head to be present");
             let mut head_next = head.take_next().expect("Expected next to be present");
-            let joined_head = join_subtrees(&mut head_next, &mut head);
+            let joined_head = join_subtrees::<HashType>(&mut head_next, &mut head);
             self.storage
                 .insert(&joined_head.node().key(), &joined_head.node().into())?;
             self.head = Some(joined_head);
@@ -358,19 +545,26 @@ where
     }
 }
 
-fn join_subtrees(lhs: &mut Subtree<Node>, rhs: &mut Subtree<Node>) -> Subtree<Node> {
-    let joined_node = Node::create_node(lhs.node(), rhs.node());
+fn join_subtrees<H: MerkleHash>(
+    lhs: &mut Subtree<Node>,
+    rhs: &mut Subtree<Node>,
+) -> Subtree<Node> {
+    let joined_node = Node::create_node::<H>(lhs.node(), rhs.node());
     Subtree::new(joined_node, lhs.take_next())
 }
 
-fn build_root_node<Table, Storage>(subtree: &Subtree<Node>, storage: &mut Storage) -> Node
+fn build_root_node<H, Table, Storage>(
+    subtree: &Subtree<Node>,
+    storage: &mut Storage,
+) -> Node
 where
+    H: MerkleHash,
     Table: Mappable<Key = u64, OwnedValue = Primitive, Value = Primitive>,
     Storage: StorageMutateInfallible<Table>,
 {
     let mut head = subtree.clone();
     while let Some(mut head_next) = head.take_next() {
-        head = join_subtrees(&mut head_next, &mut head);
+        head = join_subtrees::<H>(&mut head_next, &mut head);
         storage.insert(&head.node().key(), &head.node().into());
     }
     head.node().clone()
@@ -387,10 +581,15 @@ mod test {
             empty_sum,
             leaf_sum,
             node_sum,
+            verify,
+            verify_with_hash,
             Node,
             Primitive,
         },
-        common::StorageMap,
+        common::{
+            Sha512Trunc256,
+            StorageMap,
+        },
     };
     use fuel_merkle_test_helpers::TEST_DATA;
     use fuel_storage::{
@@ -891,4 +1090,64 @@ mod test {
         let expected_root = node_3;
         assert_eq!(root, expected_root);
     }
+
+    #[test]
+    fn root_returns_the_pinned_sha256_root_for_5_leaves() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        for datum in [b"a", b"b", b"c", b"d", b"e"] {
+            tree.push(datum).unwrap();
+        }
+
+        let root = tree.root();
+        let expected_root =
+            "fe14a5426fbd70c0fa73f52342afed0da0bd23c4838662ccf6b88a3070ead97b";
+        assert_eq!(hex::encode(root), expected_root);
+    }
+
+    #[test]
+    fn new_with_hash_builds_the_tree_with_the_given_hash() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<_, _, Sha512Trunc256>::new_with_hash(&mut storage_map);
+
+        let empty_root =
+            "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a";
+        assert_eq!(hex::encode(tree.root()), empty_root);
+
+        let data = [b"a", b"b", b"c", b"d", b"e"];
+        for datum in data {
+            tree.push(datum).unwrap();
+        }
+
+        let root = tree.root();
+        let expected_root =
+            "0b77c29ff92a7252c7985eff3c732a1b886945f4c1ac0bdda9cf9a354fbf939c";
+        assert_eq!(hex::encode(root), expected_root);
+
+        let (proof_root, proof_set) = tree.prove(2).unwrap();
+        assert_eq!(proof_root, root);
+        assert!(verify_with_hash::<Sha512Trunc256, _>(
+            &root, &data[2], &proof_set, 2, 5
+        ));
+        assert!(!verify(&root, &data[2], &proof_set, 2, 5));
+    }
+
+    #[test]
+    fn load_with_hash_restores_the_tree_with_the_given_hash() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<_, _, Sha512Trunc256>::new_with_hash(&mut storage_map);
+        for datum in TEST_DATA[0..7].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+
+        let tree =
+            MerkleTree::<_, _, Sha512Trunc256>::load_with_hash(&mut storage_map, 7)
+                .unwrap();
+
+        assert_eq!(tree.root(), root);
+    }
 }
@@ -0,0 +1,603 @@
+use crate::{
+    binary::{
+        empty_hash,
+        leaf_hash,
+        node_hash,
+    },
+    common::{
+        left_subtree_leaves_count,
+        Bytes32,
+        MerkleHash,
+        ProofSet,
+        Sha256,
+    },
+};
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    fmt::Debug,
+};
+
+/// The proof that several leaves are in a binary Merkle tree.
+///
+/// The proof describes the walk of the tree from the root that reaches all the proven
+/// leaves, and only them. The walk visits the nodes in pre-order, left before right,
+/// and records one structure bit per visited node:
+///
+/// - `true`: the subtree of the node contains a proven leaf. The walk descends into the
+///   children of the node, or, if the node is a leaf, takes the proven leaf data;
+/// - `false`: the subtree of the node contains no proven leaf. The walk takes the next
+///   node of the proof set as the hash of the subtree.
+///
+/// Every side node shared by the paths of several leaves is in the proof set only once,
+/// and adjacent leaves share their side nodes, so the proof of a cluster of leaves is
+/// close to the proof of a single leaf.
+///
+/// The proof of a set of leaves is unique: [`verify_multi`] rejects any other structure
+/// bits or proof set.
+///
+/// # Encoding
+///
+/// [`MultiProof::to_bytes`] encodes the proof as:
+///
+/// - the version of the encoding, `0x01`;
+/// - the number of leaves of the tree, as a big-endian `u64`;
+/// - the number of structure bits, as a big-endian `u64`;
+/// - the structure bits, packed most significant bit first, with the unused bits of the
+///   last byte set to zero;
+/// - the 32 bytes nodes of the proof set, one per unset structure bit, in the order of
+///   the walk.
+///
+/// The encoding is canonical: [`MultiProof::from_bytes`] rejects any other byte string,
+/// including the valid encodings followed by more bytes.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiProof {
+    /// The number of leaves of the tree.
+    pub leaves_count: u64,
+    /// The structure bits of the walk, in pre-order.
+    pub structure: Vec<bool>,
+    /// The hashes of the subtrees without proven leaves, in the order of the walk.
+    pub proof_set: ProofSet,
+}
+
+impl Debug for MultiProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let structure = self
+            .structure
+            .iter()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect::<alloc::string::String>();
+        let proof_set = self.proof_set.iter().map(hex::encode).collect::<Vec<_>>();
+        f.debug_struct("MultiProof")
+            .field("Leaves count", &self.leaves_count)
+            .field("Structure", &structure)
+            .field("Proof set", &proof_set)
+            .finish()
+    }
+}
+
+/// The failure to decode a [`MultiProof`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum MultiProofDecodeError {
+    #[display(fmt = "the encoded proof ends unexpectedly")]
+    UnexpectedEnd,
+    #[display(fmt = "{:#04x} is not a supported encoding version", _0)]
+    UnsupportedVersion(u8),
+    #[display(fmt = "the unused bits of the structure are not zero")]
+    NonZeroPadding,
+    #[display(fmt = "the encoded proof is followed by more bytes")]
+    TrailingBytes,
+}
+
+impl MultiProof {
+    const VERSION: u8 = 0x01;
+
+    /// Encodes the proof, as documented by [`MultiProof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let structure_len = self.structure.len().div_ceil(8);
+        let mut bytes =
+            Vec::with_capacity(1 + 8 + 8 + structure_len + 32 * self.proof_set.len());
+
+        bytes.push(Self::VERSION);
+        bytes.extend_from_slice(&self.leaves_count.to_be_bytes());
+        bytes.extend_from_slice(&(self.structure.len() as u64).to_be_bytes());
+        for chunk in self.structure.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, bit)| byte | (u8::from(*bit) << (7 - i)));
+            bytes.push(byte);
+        }
+        for node in self.proof_set.iter() {
+            bytes.extend_from_slice(node);
+        }
+
+        bytes
+    }
+
+    /// Decodes a proof encoded by [`MultiProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiProofDecodeError> {
+        fn take<'a>(
+            bytes: &mut &'a [u8],
+            n: usize,
+        ) -> Result<&'a [u8], MultiProofDecodeError> {
+            if bytes.len() < n {
+                return Err(MultiProofDecodeError::UnexpectedEnd)
+            }
+            let (head, tail) = bytes.split_at(n);
+            *bytes = tail;
+            Ok(head)
+        }
+
+        fn take_u64(bytes: &mut &[u8]) -> Result<u64, MultiProofDecodeError> {
+            let head = take(bytes, 8)?;
+            Ok(u64::from_be_bytes(
+                head.try_into().expect("The length was checked above"),
+            ))
+        }
+
+        let mut bytes = bytes;
+
+        let version = take(&mut bytes, 1)?[0];
+        if version != Self::VERSION {
+            return Err(MultiProofDecodeError::UnsupportedVersion(version))
+        }
+
+        let leaves_count = take_u64(&mut bytes)?;
+
+        // The structure can't be longer than the encoding, which bounds the allocation.
+        let structure_bits = usize::try_from(take_u64(&mut bytes)?)
+            .map_err(|_| MultiProofDecodeError::UnexpectedEnd)?;
+        let packed = take(&mut bytes, structure_bits.div_ceil(8))?;
+        let structure: Vec<bool> = (0..structure_bits)
+            .map(|i| packed[i / 8] & (0x80 >> (i % 8)) != 0)
+            .collect();
+        if let Some(last) = packed.last() {
+            let unused = packed.len() * 8 - structure_bits;
+            if last & ((1 << unused) - 1) != 0 {
+                return Err(MultiProofDecodeError::NonZeroPadding)
+            }
+        }
+
+        let proof_set_len = structure.iter().filter(|bit| !**bit).count();
+        let proof_set = (0..proof_set_len)
+            .map(|_| {
+                take(&mut bytes, 32)
+                    .map(|node| node.try_into().expect("The length was checked above"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        if !bytes.is_empty() {
+            return Err(MultiProofDecodeError::TrailingBytes)
+        }
+
+        Ok(Self {
+            leaves_count,
+            structure,
+            proof_set,
+        })
+    }
+}
+
+/// Verifies that the `leaves`, given as pairs of leaf index and leaf data, are in the
+/// tree of the `root`.
+///
+/// The leaves may be given in any order, but each index only once: the leaves with a
+/// duplicate index are rejected, even with the same data. The `proof` must prove
+/// exactly the given leaves.
+pub fn verify_multi<T: AsRef<[u8]>>(
+    root: &Bytes32,
+    leaves: &[(u64, T)],
+    proof: &MultiProof,
+) -> bool {
+    verify_multi_with_hash::<Sha256, T>(root, leaves, proof)
+}
+
+/// Verifies that the `leaves` are in the tree of the `root`, like [`verify_multi`],
+/// for a tree hashed with `H`.
+pub fn verify_multi_with_hash<H: MerkleHash, T: AsRef<[u8]>>(
+    root: &Bytes32,
+    leaves: &[(u64, T)],
+    proof: &MultiProof,
+) -> bool {
+    let mut leaves: Vec<(u64, &[u8])> = leaves
+        .iter()
+        .map(|(index, data)| (*index, data.as_ref()))
+        .collect();
+    leaves.sort_unstable_by_key(|(index, _)| *index);
+
+    let sorted_unique = leaves.windows(2).all(|pair| pair[0].0 < pair[1].0);
+    if !sorted_unique {
+        return false
+    }
+    if let Some((index, _)) = leaves.last() {
+        if *index >= proof.leaves_count {
+            return false
+        }
+    }
+
+    if proof.leaves_count == 0 {
+        return proof.structure.is_empty()
+            && proof.proof_set.is_empty()
+            && *root == empty_hash::<H>()
+    }
+
+    let mut structure = proof.structure.iter();
+    let mut proof_set = proof.proof_set.iter();
+    let sum = subtree_sum::<H>(
+        0,
+        proof.leaves_count,
+        &leaves,
+        &mut structure,
+        &mut proof_set,
+    );
+
+    match sum {
+        Some(sum) => {
+            structure.next().is_none() && proof_set.next().is_none() && sum == *root
+        }
+        None => false,
+    }
+}
+
+/// Computes the hash of the subtree of the leaves `start..start + leaves_count`, where
+/// `leaves` are the proven leaves of the subtree.
+fn subtree_sum<'a, H: MerkleHash>(
+    start: u64,
+    leaves_count: u64,
+    leaves: &[(u64, &[u8])],
+    structure: &mut impl Iterator<Item = &'a bool>,
+    proof_set: &mut impl Iterator<Item = &'a Bytes32>,
+) -> Option<Bytes32> {
+    if !*structure.next()? {
+        // A subtree with proven leaves must be walked to reach them.
+        return if leaves.is_empty() {
+            proof_set.next().copied()
+        } else {
+            None
+        }
+    }
+
+    match leaves {
+        // Walking a subtree without proven leaves is not canonical.
+        [] => None,
+        [(_, data)] if leaves_count == 1 => Some(leaf_hash::<H>(data)),
+        _ if leaves_count == 1 => None,
+        _ => {
+            let left_count = left_subtree_leaves_count(leaves_count);
+            let middle = start + left_count;
+            let (left, right) =
+                leaves.split_at(leaves.partition_point(|(index, _)| *index < middle));
+            let left_sum =
+                subtree_sum::<H>(start, left_count, left, structure, proof_set)?;
+            let right_sum = subtree_sum::<H>(
+                middle,
+                leaves_count - left_count,
+                right,
+                structure,
+                proof_set,
+            )?;
+            Some(node_hash::<H>(&left_sum, &right_sum))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::{
+        verify_multi,
+        MultiProof,
+        MultiProofDecodeError,
+    };
+    use crate::{
+        binary::{
+            MerkleTree,
+            MerkleTreeError,
+            Primitive,
+        },
+        common::StorageMap,
+    };
+    use alloc::{
+        vec,
+        vec::Vec,
+    };
+    use fuel_merkle_test_helpers::TEST_DATA;
+    use fuel_storage::Mappable;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = u64;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    fn tree(
+        storage: &mut StorageMap<TestTable>,
+        leaves_count: usize,
+    ) -> MerkleTree<TestTable, &mut StorageMap<TestTable>> {
+        let mut tree = MerkleTree::new(storage);
+        for datum in TEST_DATA[0..leaves_count].iter() {
+            tree.push(datum).unwrap();
+        }
+        tree
+    }
+
+    fn leaves(indices: &[u64]) -> Vec<(u64, &'static [u8])> {
+        indices
+            .iter()
+            .map(|index| (*index, TEST_DATA[usize::try_from(*index).unwrap()]))
+            .collect()
+    }
+
+    #[test]
+    fn generate_multi_proof__single_index__matches_the_individual_proof() {
+        for leaves_count in 1..=TEST_DATA.len() {
+            // Given
+            let mut storage = StorageMap::<TestTable>::new();
+            let tree = tree(&mut storage, leaves_count);
+
+            for index in 0..leaves_count as u64 {
+                // When
+                let proof = tree.generate_multi_proof(&[index]).unwrap();
+
+                // Then
+                let (root, mut individual_set) = tree.prove(index).unwrap();
+                let mut proof_set = proof.proof_set.clone();
+                individual_set.sort();
+                proof_set.sort();
+                assert_eq!(proof_set, individual_set);
+                assert!(verify_multi(&root, &leaves(&[index]), &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn generate_multi_proof__adjacent_indices__shares_the_side_nodes() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 10);
+        let indices = [4, 5, 6, 7];
+
+        // When
+        let proof = tree.generate_multi_proof(&indices).unwrap();
+
+        // Then
+        let root = tree.root();
+        assert!(verify_multi(&root, &leaves(&indices), &proof));
+        // The leaves 4..8 form a balanced subtree, proven by the side nodes of its
+        // root only.
+        assert_eq!(proof.proof_set.len(), 2);
+        let individual_nodes: usize = indices
+            .iter()
+            .map(|index| tree.prove(*index).unwrap().1.len())
+            .sum();
+        assert!(proof.proof_set.len() < individual_nodes);
+    }
+
+    #[test]
+    fn generate_multi_proof__scattered_indices__verifies_with_the_union_of_individual_proofs(
+    ) {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, TEST_DATA.len());
+        let indices = [0, 3, 6, 9];
+
+        // When
+        let proof = tree.generate_multi_proof(&indices).unwrap();
+
+        // Then
+        let root = tree.root();
+        assert!(verify_multi(&root, &leaves(&indices), &proof));
+        let mut union: Vec<_> = indices
+            .iter()
+            .flat_map(|index| tree.prove(*index).unwrap().1)
+            .collect();
+        union.sort();
+        union.dedup();
+        assert!(proof.proof_set.iter().all(|node| union.contains(node)));
+        assert!(proof.proof_set.len() <= union.len());
+    }
+
+    #[test]
+    fn generate_multi_proof__all_indices__has_an_empty_proof_set() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+        let indices: Vec<u64> = (0..7).collect();
+
+        // When
+        let proof = tree.generate_multi_proof(&indices).unwrap();
+
+        // Then
+        assert!(proof.proof_set.is_empty());
+        assert!(proof.structure.iter().all(|bit| *bit));
+        assert!(verify_multi(&tree.root(), &leaves(&indices), &proof));
+    }
+
+    #[test]
+    fn generate_multi_proof__duplicate_indices__dedupes_them() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+
+        // When
+        let proof = tree.generate_multi_proof(&[5, 2, 5, 2]).unwrap();
+
+        // Then
+        assert_eq!(proof, tree.generate_multi_proof(&[2, 5]).unwrap());
+        assert!(verify_multi(&tree.root(), &leaves(&[5, 2]), &proof));
+    }
+
+    #[test]
+    fn generate_multi_proof__index_out_of_range__returns_an_error() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+
+        // When
+        let result = tree.generate_multi_proof(&[1, 7]);
+
+        // Then
+        assert!(matches!(result, Err(MerkleTreeError::InvalidProofIndex(7))));
+    }
+
+    #[test]
+    fn verify_multi__duplicate_leaves__returns_false() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+        let proof = tree.generate_multi_proof(&[2, 5]).unwrap();
+
+        // When
+        let verification = verify_multi(&tree.root(), &leaves(&[2, 5, 5]), &proof);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_multi__subset_or_superset_of_the_proven_leaves__returns_false() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+        let root = tree.root();
+        let proof = tree.generate_multi_proof(&[2, 5]).unwrap();
+
+        // Then
+        assert!(!verify_multi(&root, &leaves(&[2]), &proof));
+        assert!(!verify_multi(&root, &leaves(&[2, 4, 5]), &proof));
+        assert!(!verify_multi(&root, &leaves(&[2, 6]), &proof));
+    }
+
+    #[test]
+    fn verify_multi__wrong_leaf_data__returns_false() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+        let proof = tree.generate_multi_proof(&[2, 5]).unwrap();
+
+        // When
+        let leaves = vec![(2, TEST_DATA[2]), (5, TEST_DATA[6])];
+        let verification = verify_multi(&tree.root(), &leaves, &proof);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_multi__empty_tree__accepts_the_empty_proof() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 0);
+
+        // When
+        let proof = tree.generate_multi_proof(&[]).unwrap();
+
+        // Then
+        assert_eq!(proof.leaves_count, 0);
+        assert!(verify_multi(&tree.root(), &leaves(&[]), &proof));
+    }
+
+    #[test]
+    fn multi_proof__encoding__round_trips() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, TEST_DATA.len());
+        let proof = tree.generate_multi_proof(&[1, 2, 8]).unwrap();
+
+        // When
+        let bytes = proof.to_bytes();
+
+        // Then
+        assert_eq!(bytes[0], 0x01);
+        assert_eq!(
+            bytes.len(),
+            1 + 8 + 8 + proof.structure.len().div_ceil(8) + 32 * proof.proof_set.len()
+        );
+        assert_eq!(MultiProof::from_bytes(&bytes), Ok(proof));
+    }
+
+    #[test]
+    fn multi_proof__non_canonical_encodings__are_rejected() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, 7);
+        let bytes = tree.generate_multi_proof(&[3]).unwrap().to_bytes();
+
+        // Then
+        let mut unsupported = bytes.clone();
+        unsupported[0] = 0x02;
+        assert_eq!(
+            MultiProof::from_bytes(&unsupported),
+            Err(MultiProofDecodeError::UnsupportedVersion(0x02))
+        );
+
+        let mut padded = bytes.clone();
+        padded[17] |= 0x01;
+        assert_eq!(
+            MultiProof::from_bytes(&padded),
+            Err(MultiProofDecodeError::NonZeroPadding)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            MultiProof::from_bytes(&trailing),
+            Err(MultiProofDecodeError::TrailingBytes)
+        );
+
+        assert_eq!(
+            MultiProof::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(MultiProofDecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn verify_multi__random_bit_flips__always_fail() {
+        // Given
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage, TEST_DATA.len());
+        let root = tree.root();
+
+        for _ in 0..200 {
+            let count = rng.gen_range(1..=4);
+            let indices: Vec<u64> = (0..count)
+                .map(|_| rng.gen_range(0..TEST_DATA.len() as u64))
+                .collect();
+            let proof = tree.generate_multi_proof(&indices).unwrap();
+            let leaves = leaves(&indices);
+            let leaves: Vec<_> = leaves
+                .iter()
+                .enumerate()
+                .filter(|(i, (index, _))| {
+                    leaves[..*i].iter().all(|(other, _)| other != index)
+                })
+                .map(|(_, leaf)| *leaf)
+                .collect();
+            assert!(verify_multi(&root, &leaves, &proof));
+
+            // When
+            let mut bytes = proof.to_bytes();
+            let bit = rng.gen_range(0..bytes.len() * 8);
+            bytes[bit / 8] ^= 0x80 >> (bit % 8);
+
+            // Then
+            let verification = MultiProof::from_bytes(&bytes)
+                .map(|flipped| verify_multi(&root, &leaves, &flipped))
+                .unwrap_or(false);
+            assert!(!verification, "flipping the bit {bit} of {proof:?}");
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    binary::{
-        leaf_sum,
-        node_sum,
+    binary::hash::{
+        leaf_hash,
+        node_hash,
     },
     common::{
         Bytes32,
+        MerkleHash,
         Position,
     },
 };
@@ -22,15 +23,15 @@ impl Node {
         Self { position, hash }
     }
 
-    pub fn create_leaf(index: u64, data: &[u8]) -> Self {
+    pub fn create_leaf<H: MerkleHash>(index: u64, data: &[u8]) -> Self {
         let position = Position::from_leaf_index(index);
-        let hash = leaf_sum(data);
+        let hash = leaf_hash::<H>(data);
         Self { position, hash }
     }
 
-    pub fn create_node(left_child: &Self, right_child: &Self) -> Self {
+    pub fn create_node<H: MerkleHash>(left_child: &Self, right_child: &Self) -> Self {
         let position = left_child.position().parent();
-        let hash = node_sum(left_child.hash(), right_child.hash());
+        let hash = node_hash::<H>(left_child.hash(), right_child.hash());
         Self { position, hash }
     }
 
@@ -0,0 +1,46 @@
+//! The construction of the binary Merkle tree on the rayon thread pool.
+
+use crate::{
+    binary::Node,
+    common::{
+        Bytes32,
+        MerkleHash,
+    },
+};
+
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// The minimum number of nodes hashed by each parallel task, so the small levels of the
+/// tree aren't split into tasks more expensive than their hashing.
+const MIN_TASK_LEN: usize = 256;
+
+/// The leaves of the `data`, hashed in parallel.
+pub(crate) fn leaves<H: MerkleHash, T: AsRef<[u8]> + Sync>(data: &[T]) -> Vec<Node> {
+    data.par_iter()
+        .enumerate()
+        .with_min_len(MIN_TASK_LEN)
+        .map(|(index, datum)| Node::create_leaf::<H>(index as u64, datum.as_ref()))
+        .collect()
+}
+
+/// The parents of the consecutive pairs of the `level`, hashed in parallel. The last
+/// node of a level of odd length has no parent.
+pub(crate) fn parents<H: MerkleHash>(level: &[Node]) -> Vec<Node> {
+    level
+        .par_chunks_exact(2)
+        .with_min_len(MIN_TASK_LEN)
+        .map(|pair| Node::create_node::<H>(&pair[0], &pair[1]))
+        .collect()
+}
+
+/// The root of the balanced tree of the leaves of the `data`, whose length is a power
+/// of two.
+pub(crate) fn balanced_root<H: MerkleHash, T: AsRef<[u8]> + Sync>(data: &[T]) -> Bytes32 {
+    debug_assert!(data.len().is_power_of_two());
+    let mut level = leaves::<H, _>(data);
+    while level.len() > 1 {
+        level = parents::<H>(&level);
+    }
+    *level[0].hash()
+}
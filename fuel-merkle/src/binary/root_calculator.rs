@@ -1,21 +1,63 @@
 use crate::{
     binary::{
         empty_sum,
+        leaf_sum,
         Node,
     },
-    common::Bytes32,
+    common::{
+        Bytes32,
+        Position,
+        Sha256,
+    },
 };
 
 use crate::alloc::borrow::ToOwned;
 use alloc::vec::Vec;
 
+/// Calculates the root of a binary Merkle tree from its leaves, pushed one at a time,
+/// without building the tree.
+///
+/// The calculator only keeps the roots of the balanced subtrees of the leaves pushed so
+/// far, at most one per height, so the memory is logarithmic in the number of leaves.
+/// The root matches the root of the [`MerkleTree`](crate::binary::MerkleTree) of the
+/// same leaves.
+///
+/// # Encoding
+///
+/// [`MerkleRootCalculator::to_bytes`] encodes the state of the calculator, allowing to
+/// resume the calculation later, as:
+///
+/// - the version of the encoding, `0x01`;
+/// - the number of subtrees, as a `u8` of at most 64;
+/// - for each subtree, from the highest to the lowest, its height as a `u8` followed by
+///   its 32 bytes root.
+///
+/// The heights of the subtrees must strictly decrease, as they do after pushing leaves.
+/// The encoding is canonical: [`MerkleRootCalculator::from_bytes`] rejects any other
+/// byte string, including the valid encodings followed by more bytes.
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MerkleRootCalculator {
     stack: Vec<Node>,
 }
 
+/// The failure to decode a [`MerkleRootCalculator`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum StateDecodeError {
+    #[display(fmt = "the encoded state ends unexpectedly")]
+    UnexpectedEnd,
+    #[display(fmt = "{:#04x} is not a supported encoding version", _0)]
+    UnsupportedVersion(u8),
+    #[display(fmt = "the heights of the subtrees don't strictly decrease")]
+    InvalidHeights,
+    #[display(fmt = "the encoded state is followed by more bytes")]
+    TrailingBytes,
+}
+
 impl MerkleRootCalculator {
+    const MAX_HEIGHT: u8 = 63;
+    const VERSION: u8 = 0x01;
+
     pub fn new() -> Self {
         Self { stack: Vec::new() }
     }
@@ -24,15 +66,52 @@ impl MerkleRootCalculator {
         Self { stack }
     }
 
+    /// Pushes the next leaf of the tree, with the leaf `data`.
     pub fn push(&mut self, data: &[u8]) {
-        let node = Node::create_leaf(0, data);
+        self.push_leaf(&leaf_sum(data));
+    }
+
+    /// Pushes the next leaf of the tree, with the already computed `leaf_hash`
+    /// (`sha256(0x00 || data)`).
+    pub fn push_leaf(&mut self, leaf_hash: &Bytes32) {
+        let node = Node::new(Position::from_leaf_index(0), *leaf_hash);
+        self.push_node(node);
+    }
+
+    /// Pushes the leaves with the data of the `leaves`, in order, like
+    /// [`MerkleRootCalculator::push_all`]. The leaves are split into the largest
+    /// balanced subtrees that continue the leaves pushed before, and the roots of the
+    /// subtrees are computed on the rayon thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn push_all_parallel<T: AsRef<[u8]> + Sync>(&mut self, leaves: &[T]) {
+        let mut leaves_count: u64 =
+            self.stack.iter().map(|node| 1 << node.height()).sum();
+        let mut leaves = leaves;
+        while !leaves.is_empty() {
+            // The subtree must fit in the remaining leaves, and must start at a multiple
+            // of its size to be a subtree of the tree.
+            let max_height = usize::BITS - 1 - leaves.len().leading_zeros();
+            let height = max_height.min(leaves_count.trailing_zeros());
+            let (subtree, rest) = leaves.split_at(1 << height);
+
+            let root = super::parallel::balanced_root::<Sha256, _>(subtree);
+            // The leftmost position of the height.
+            let position = Position::from_in_order_index((1 << height) - 1);
+            self.push_node(Node::new(position, root));
+
+            leaves_count += 1 << height;
+            leaves = rest;
+        }
+    }
+
+    fn push_node(&mut self, node: Node) {
         self.stack.push(node);
 
         while self.stack.len() > 1 {
             let right_node = &self.stack[self.stack.len() - 1];
             let left_node = &self.stack[self.stack.len() - 2];
             if right_node.height() == left_node.height() {
-                let merged_node = Node::create_node(left_node, right_node);
+                let merged_node = Node::create_node::<Sha256>(left_node, right_node);
                 self.stack.pop();
                 self.stack.pop();
                 self.stack.push(merged_node);
@@ -42,17 +121,25 @@ impl MerkleRootCalculator {
         }
     }
 
-    pub fn root(mut self) -> Bytes32 {
-        if self.stack.is_empty() {
-            return empty_sum().to_owned()
+    /// Pushes the leaves with the data of the `iterator`, in order.
+    pub fn push_all<I: IntoIterator<Item = T>, T: AsRef<[u8]>>(&mut self, iterator: I) {
+        for data in iterator {
+            self.push(data.as_ref());
         }
-        while self.stack.len() > 1 {
-            let right_child = self.stack.pop().expect("Unable to pop element from stack");
-            let left_child = self.stack.pop().expect("Unable to pop element from stack");
-            let merged_node = Node::create_node(&left_child, &right_child);
-            self.stack.push(merged_node);
+    }
+
+    /// The root of the tree of the leaves pushed so far. More leaves can be pushed
+    /// after.
+    pub fn root(&self) -> Bytes32 {
+        let mut nodes = self.stack.iter().rev();
+        let Some(last) = nodes.next() else {
+            return empty_sum().to_owned()
+        };
+        let mut root = last.clone();
+        for left_child in nodes {
+            root = Node::create_node::<Sha256>(left_child, &root);
         }
-        self.stack.pop().unwrap().hash().to_owned()
+        root.hash().to_owned()
     }
 
     pub fn root_from_iterator<I: Iterator<Item = T>, T: AsRef<[u8]>>(
@@ -60,23 +147,84 @@ impl MerkleRootCalculator {
         iterator: I,
     ) -> Bytes32 {
         let mut calculator = MerkleRootCalculator::new();
-
-        for data in iterator {
-            calculator.push(data.as_ref());
-        }
-
+        calculator.push_all(iterator);
         calculator.root()
     }
 
     pub fn stack(&self) -> &Vec<Node> {
         &self.stack
     }
+
+    /// Encodes the state of the calculator, as documented by
+    /// [`MerkleRootCalculator`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + 33 * self.stack.len());
+
+        bytes.push(Self::VERSION);
+        bytes.push(u8::try_from(self.stack.len()).unwrap_or(u8::MAX));
+        for node in self.stack.iter() {
+            bytes.push(u8::try_from(node.height()).unwrap_or(u8::MAX));
+            bytes.extend_from_slice(node.hash());
+        }
+
+        bytes
+    }
+
+    /// Decodes the state of a calculator encoded by
+    /// [`MerkleRootCalculator::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StateDecodeError> {
+        fn take<'a>(
+            bytes: &mut &'a [u8],
+            n: usize,
+        ) -> Result<&'a [u8], StateDecodeError> {
+            if bytes.len() < n {
+                return Err(StateDecodeError::UnexpectedEnd)
+            }
+            let (head, tail) = bytes.split_at(n);
+            *bytes = tail;
+            Ok(head)
+        }
+
+        let mut bytes = bytes;
+
+        let version = take(&mut bytes, 1)?[0];
+        if version != Self::VERSION {
+            return Err(StateDecodeError::UnsupportedVersion(version))
+        }
+
+        let len = take(&mut bytes, 1)?[0];
+        let mut stack = Vec::with_capacity(len.into());
+        let mut previous_height = None;
+        for _ in 0..len {
+            let height = take(&mut bytes, 1)?[0];
+            if height > Self::MAX_HEIGHT
+                || previous_height.is_some_and(|previous| height >= previous)
+            {
+                return Err(StateDecodeError::InvalidHeights)
+            }
+            previous_height = Some(height);
+
+            let hash = take(&mut bytes, 32)?
+                .try_into()
+                .expect("The length was checked above");
+            // The leftmost position of the height.
+            let position = Position::from_in_order_index((1 << height) - 1);
+            stack.push(Node::new(position, hash));
+        }
+
+        if !bytes.is_empty() {
+            return Err(StateDecodeError::TrailingBytes)
+        }
+
+        Ok(Self { stack })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::binary::in_memory::MerkleTree;
+    use alloc::vec::Vec;
     use fuel_merkle_test_helpers::TEST_DATA;
     #[cfg(test)]
     use serde_json as _;
@@ -145,6 +293,104 @@ mod test {
         assert_eq!(tree.root(), root);
     }
 
+    #[test]
+    fn root_matches_the_merkle_root_for_0_to_1025_leaves() {
+        let mut tree = MerkleTree::new();
+        let mut calculate_root = MerkleRootCalculator::new();
+
+        for value in 0..=1025u64 {
+            assert_eq!(tree.root(), calculate_root.root(), "{value} leaves");
+            assert_eq!(
+                calculate_root.stack().len(),
+                usize::try_from(value.count_ones()).unwrap()
+            );
+
+            let data = value.to_le_bytes();
+            tree.push(&data);
+            calculate_root.push(&data);
+        }
+    }
+
+    #[test]
+    fn push_leaf_matches_push_with_the_leaf_data() {
+        let mut calculate_root = MerkleRootCalculator::new();
+        let mut calculate_root_from_hashes = MerkleRootCalculator::new();
+
+        let data = &TEST_DATA[0..7];
+        for datum in data.iter() {
+            calculate_root.push(datum);
+            calculate_root_from_hashes.push_leaf(&leaf_sum(datum));
+        }
+
+        assert_eq!(calculate_root, calculate_root_from_hashes);
+    }
+
+    #[test]
+    fn push_all_matches_push() {
+        let mut calculate_root = MerkleRootCalculator::new();
+        let mut calculate_root_all = MerkleRootCalculator::new();
+
+        let data = &TEST_DATA[0..7];
+        for datum in data.iter() {
+            calculate_root.push(datum);
+        }
+        calculate_root_all.push_all(data.iter());
+
+        assert_eq!(calculate_root.root(), calculate_root_all.root());
+    }
+
+    #[test]
+    fn from_bytes_resumes_the_calculation_from_a_checkpoint() {
+        let data: Vec<_> = (0..100u64).map(u64::to_le_bytes).collect();
+        let expected_root = MerkleRootCalculator::new().root_from_iterator(data.iter());
+
+        for checkpoint in 0..=data.len() {
+            let mut calculate_root = MerkleRootCalculator::new();
+            calculate_root.push_all(data[..checkpoint].iter());
+
+            let bytes = calculate_root.to_bytes();
+            let mut resumed = MerkleRootCalculator::from_bytes(&bytes).unwrap();
+            assert_eq!(resumed.root(), calculate_root.root());
+
+            resumed.push_all(data[checkpoint..].iter());
+            assert_eq!(resumed.root(), expected_root, "checkpoint at {checkpoint}");
+        }
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_encodings() {
+        let mut calculate_root = MerkleRootCalculator::new();
+        calculate_root.push_all(TEST_DATA[0..7].iter());
+        let bytes = calculate_root.to_bytes();
+        assert_eq!(bytes.len(), 2 + 3 * 33);
+
+        let mut unsupported = bytes.clone();
+        unsupported[0] = 0x02;
+        assert_eq!(
+            MerkleRootCalculator::from_bytes(&unsupported),
+            Err(StateDecodeError::UnsupportedVersion(0x02))
+        );
+
+        let mut increasing = bytes.clone();
+        increasing[2 + 33] = 2;
+        assert_eq!(
+            MerkleRootCalculator::from_bytes(&increasing),
+            Err(StateDecodeError::InvalidHeights)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            MerkleRootCalculator::from_bytes(&trailing),
+            Err(StateDecodeError::TrailingBytes)
+        );
+
+        assert_eq!(
+            MerkleRootCalculator::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(StateDecodeError::UnexpectedEnd)
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_serialize_deserialize() {
@@ -1,11 +1,13 @@
 use crate::{
     binary::{
-        leaf_sum,
-        node_sum,
+        leaf_hash,
+        node_hash,
     },
     common::{
         Bytes32,
+        MerkleHash,
         ProofSet,
+        Sha256,
     },
 };
 
@@ -43,6 +45,18 @@ pub fn verify<T: AsRef<[u8]>>(
     proof_set: &ProofSet,
     proof_index: u64,
     num_leaves: u64,
+) -> bool {
+    verify_with_hash::<Sha256, T>(root, data, proof_set, proof_index, num_leaves)
+}
+
+/// Verifies the proof of the leaf at `proof_index`, like [`verify`], for a tree hashed
+/// with `H`.
+pub fn verify_with_hash<H: MerkleHash, T: AsRef<[u8]>>(
+    root: &Bytes32,
+    data: &T,
+    proof_set: &ProofSet,
+    proof_index: u64,
+    num_leaves: u64,
 ) -> bool {
     if num_leaves <= 1 {
         if !proof_set.is_empty() {
@@ -56,7 +70,7 @@ pub fn verify<T: AsRef<[u8]>>(
         return false
     }
 
-    let mut sum = leaf_sum(data.as_ref());
+    let mut sum = leaf_hash::<H>(data.as_ref());
     if proof_set.is_empty() {
         return if num_leaves == 1 { *root == sum } else { false }
     }
@@ -80,9 +94,9 @@ pub fn verify<T: AsRef<[u8]>>(
 
         let proof_data = proof_set[height - 1];
         if proof_index - subtree_start_index < 1 << (height - 1) {
-            sum = node_sum(&sum, &proof_data);
+            sum = node_hash::<H>(&sum, &proof_data);
         } else {
-            sum = node_sum(&proof_data, &sum);
+            sum = node_hash::<H>(&proof_data, &sum);
         }
 
         height += 1;
@@ -93,13 +107,13 @@ pub fn verify<T: AsRef<[u8]>>(
             return false
         }
         let proof_data = proof_set[height - 1];
-        sum = node_sum(&sum, &proof_data);
+        sum = node_hash::<H>(&sum, &proof_data);
         height += 1;
     }
 
     while height - 1 < proof_set.len() {
         let proof_data = proof_set[height - 1];
-        sum = node_sum(&proof_data, &sum);
+        sum = node_hash::<H>(&proof_data, &sum);
         height += 1;
     }
 
@@ -1,3 +1,4 @@
+mod cow_storage_map;
 mod hash;
 mod msb;
 mod path_iterator;
@@ -11,12 +12,15 @@ pub(crate) mod error;
 pub(crate) mod node;
 pub(crate) mod path;
 
+pub use cow_storage_map::CowStorageMap;
 pub use path_iterator::AsPathIterator;
 pub use position::Position;
 pub use storage_map::StorageMap;
 pub use subtree::Subtree;
 
 pub(crate) use msb::{
+    bytes32_bit,
+    bytes32_common_prefix_count,
     Bit,
     Msb,
 };
@@ -39,6 +43,9 @@ pub type ProofSet = Vec<Bytes32>;
 pub use hash::{
     sum,
     sum_iter,
+    MerkleHash,
+    Sha256,
+    Sha512Trunc256,
 };
 
 // Merkle Tree hash of an empty list
@@ -53,6 +60,13 @@ pub const fn empty_sum_sha256() -> &'static Bytes32 {
     &EMPTY_SUM
 }
 
+/// The number of leaves in the left subtree of a binary tree of `leaves_count` leaves:
+/// the largest power of two less than `leaves_count`.
+pub(crate) fn left_subtree_leaves_count(leaves_count: u64) -> u64 {
+    debug_assert!(leaves_count > 1);
+    1 << (63 - (leaves_count - 1).leading_zeros())
+}
+
 #[test]
 fn empty_sum_sha256_is_empty_hash() {
     use digest::Digest;
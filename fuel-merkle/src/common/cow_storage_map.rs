@@ -0,0 +1,388 @@
+use crate::{
+    alloc::borrow::ToOwned,
+    storage::{
+        Mappable,
+        StorageInspect,
+        StorageMutate,
+    },
+};
+
+use alloc::{
+    borrow::Cow,
+    sync::Arc,
+};
+use core::{
+    borrow::Borrow,
+    hash::Hash,
+    mem,
+};
+use hashbrown::{
+    HashMap,
+    HashSet,
+};
+
+/// The in-memory storage that can be forked cheaply, like a copy-on-write
+/// [`StorageMap`](crate::common::StorageMap).
+///
+/// [`CowStorageMap::fork`] freezes the entries of the map into a layer shared by the
+/// map and the fork behind an [`Arc`], without copying them. Afterwards, the map and
+/// the fork each record only their own inserts, and their removals of the shared
+/// entries as tombstones, so the memory of a fork grows with the entries it touches.
+/// Dropping a fork frees its entries, and the shared layers once no map refers to
+/// them.
+///
+/// Reads look up the layers from the newest to the oldest, so each fork of a fork adds
+/// a layer to the lookups.
+#[derive(Debug, Clone)]
+pub struct CowStorageMap<Type>
+where
+    Type: Mappable,
+{
+    map: HashMap<Type::OwnedKey, Type::OwnedValue>,
+    removed: HashSet<Type::OwnedKey>,
+    parent: Option<Arc<CowStorageMap<Type>>>,
+}
+
+impl<Type> Default for CowStorageMap<Type>
+where
+    Type: Mappable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Type> CowStorageMap<Type>
+where
+    Type: Mappable,
+{
+    pub fn new() -> Self {
+        Self {
+            map: Default::default(),
+            removed: Default::default(),
+            parent: None,
+        }
+    }
+
+    /// Returns a fork of the map that shares all the current entries with it. The
+    /// subsequent changes of the map and of the fork are independent.
+    pub fn fork(&mut self) -> Self {
+        if !self.map.is_empty() || !self.removed.is_empty() {
+            let frozen = Self {
+                map: mem::take(&mut self.map),
+                removed: mem::take(&mut self.removed),
+                parent: self.parent.take(),
+            };
+            self.parent = Some(Arc::new(frozen));
+        }
+
+        Self {
+            map: Default::default(),
+            removed: Default::default(),
+            parent: self.parent.clone(),
+        }
+    }
+
+    /// The number of entries inserted into the map since it was created or forked.
+    pub fn local_len(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<Type> CowStorageMap<Type>
+where
+    Type: Mappable,
+    Type::Key: Eq + Hash,
+    Type::OwnedKey: Eq + Hash + Borrow<Type::Key>,
+{
+    fn lookup(&self, key: &Type::Key) -> Option<&Type::OwnedValue> {
+        let mut layer = self;
+        loop {
+            if let Some(value) = layer.map.get(key) {
+                return Some(value)
+            }
+            if layer.removed.contains(key) {
+                return None
+            }
+            layer = layer.parent.as_deref()?;
+        }
+    }
+
+    fn lookup_parent(&self, key: &Type::Key) -> Option<&Type::OwnedValue> {
+        self.parent.as_deref().and_then(|parent| parent.lookup(key))
+    }
+}
+
+impl<Type> StorageInspect<Type> for CowStorageMap<Type>
+where
+    Type: Mappable,
+    Type::Key: Eq + Hash,
+    Type::OwnedKey: Eq + Hash + Borrow<Type::Key>,
+{
+    type Error = core::convert::Infallible;
+
+    fn get(&self, key: &Type::Key) -> Result<Option<Cow<Type::OwnedValue>>, Self::Error> {
+        let value = self.lookup(key).map(Cow::Borrowed);
+        Ok(value)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        let contains = self.lookup(key).is_some();
+        Ok(contains)
+    }
+}
+
+impl<Type> StorageMutate<Type> for CowStorageMap<Type>
+where
+    Type: Mappable,
+    Type::Key: Eq + Hash,
+    Type::OwnedKey: Eq + Hash + Borrow<Type::Key>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = match self.map.remove(key) {
+            Some(previous) => Some(previous),
+            None if self.removed.remove(key) => None,
+            None => self.lookup_parent(key).cloned(),
+        };
+
+        self.map
+            .insert(key.to_owned().into(), value.to_owned().into());
+        Ok(previous)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let local = self.map.remove(key);
+        if self.removed.contains(key) {
+            return Ok(local)
+        }
+        let shadowed = self.lookup_parent(key).cloned();
+        if shadowed.is_some() {
+            self.removed.insert(key.to_owned().into());
+        }
+        Ok(local.or(shadowed))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::*;
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    struct TestKey(u32);
+
+    /// The value that counts its live instances.
+    #[derive(Debug)]
+    struct TestValue(u32, Rc<Cell<usize>>);
+
+    impl TestValue {
+        fn new(value: u32, live: &Rc<Cell<usize>>) -> Self {
+            live.set(live.get() + 1);
+            Self(value, live.clone())
+        }
+    }
+
+    impl Clone for TestValue {
+        fn clone(&self) -> Self {
+            Self::new(self.0, &self.1)
+        }
+    }
+
+    impl Drop for TestValue {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() - 1);
+        }
+    }
+
+    impl PartialEq for TestValue {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = TestKey;
+        type OwnedValue = TestValue;
+        type Value = Self::OwnedValue;
+    }
+
+    fn value(store: &CowStorageMap<TestTable>, key: u32) -> Option<u32> {
+        store
+            .get(&TestKey(key))
+            .unwrap()
+            .map(|value| value.into_owned().0)
+    }
+
+    fn store_of(
+        live: &Rc<Cell<usize>>,
+        keys: core::ops::Range<u32>,
+    ) -> CowStorageMap<TestTable> {
+        let mut store = CowStorageMap::<TestTable>::new();
+        for key in keys {
+            let _ = store.insert(&TestKey(key), &TestValue::new(key, live));
+        }
+        store
+    }
+
+    #[test]
+    fn fork__shares_the_entries_of_the_map() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..100);
+
+        // When
+        let fork = store.fork();
+
+        // Then
+        assert_eq!(live.get(), 100);
+        assert_eq!(fork.local_len(), 0);
+        for key in 0..100 {
+            assert_eq!(value(&fork, key), Some(key));
+            assert_eq!(value(&store, key), Some(key));
+        }
+    }
+
+    #[test]
+    fn fork__changes_of_the_map_and_of_the_fork_are_independent() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..10);
+        let mut fork = store.fork();
+
+        // When
+        let _ = store.insert(&TestKey(0), &TestValue::new(100, &live));
+        let _ = store.remove(&TestKey(1));
+        let _ = store.insert(&TestKey(10), &TestValue::new(10, &live));
+        let _ = fork.insert(&TestKey(2), &TestValue::new(200, &live));
+        let _ = fork.remove(&TestKey(3));
+        let _ = fork.insert(&TestKey(11), &TestValue::new(11, &live));
+
+        // Then
+        let store_values = (0..12).map(|key| value(&store, key)).collect::<Vec<_>>();
+        let fork_values = (0..12).map(|key| value(&fork, key)).collect::<Vec<_>>();
+        let expected_store_values = [
+            Some(100),
+            None,
+            Some(2),
+            Some(3),
+            Some(4),
+            Some(5),
+            Some(6),
+            Some(7),
+            Some(8),
+            Some(9),
+            Some(10),
+            None,
+        ];
+        let expected_fork_values = [
+            Some(0),
+            Some(1),
+            Some(200),
+            None,
+            Some(4),
+            Some(5),
+            Some(6),
+            Some(7),
+            Some(8),
+            Some(9),
+            None,
+            Some(11),
+        ];
+        assert_eq!(store_values, expected_store_values);
+        assert_eq!(fork_values, expected_fork_values);
+    }
+
+    #[test]
+    fn insert_and_remove__return_the_shadowed_entries_of_the_parent() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..2);
+        let mut fork = store.fork();
+
+        // When
+        let replaced = fork
+            .insert(&TestKey(0), &TestValue::new(100, &live))
+            .unwrap();
+        let removed = fork.remove(&TestKey(1)).unwrap();
+        let removed_twice = fork.remove(&TestKey(1)).unwrap();
+        let reinserted = fork
+            .insert(&TestKey(1), &TestValue::new(101, &live))
+            .unwrap();
+
+        // Then
+        assert_eq!(replaced.map(|value| value.0), Some(0));
+        assert_eq!(removed.map(|value| value.0), Some(1));
+        assert_eq!(removed_twice, None);
+        assert_eq!(reinserted, None);
+        assert_eq!(value(&fork, 1), Some(101));
+        assert_eq!(value(&store, 1), Some(1));
+    }
+
+    #[test]
+    fn fork__of_a_fork_sees_the_entries_of_all_the_layers() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..2);
+        let mut fork = store.fork();
+        let _ = fork.insert(&TestKey(2), &TestValue::new(2, &live));
+        let _ = fork.remove(&TestKey(0));
+
+        // When
+        let fork_of_fork = fork.fork();
+
+        // Then
+        assert_eq!(value(&fork_of_fork, 0), None);
+        assert_eq!(value(&fork_of_fork, 1), Some(1));
+        assert_eq!(value(&fork_of_fork, 2), Some(2));
+        assert_eq!(value(&store, 0), Some(0));
+        assert_eq!(value(&store, 2), None);
+    }
+
+    #[test]
+    fn drop__of_the_fork_frees_its_entries() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..100);
+        let mut fork = store.fork();
+        for key in 100..110 {
+            let _ = fork.insert(&TestKey(key), &TestValue::new(key, &live));
+        }
+        let _ = fork.remove(&TestKey(0));
+        assert_eq!(live.get(), 110);
+
+        // When
+        drop(fork);
+
+        // Then
+        assert_eq!(live.get(), 100);
+        assert_eq!(value(&store, 0), Some(0));
+    }
+
+    #[test]
+    fn drop__of_the_map_and_its_forks_frees_the_shared_entries() {
+        // Given
+        let live = Rc::new(Cell::new(0));
+        let mut store = store_of(&live, 0..100);
+        let fork = store.fork();
+
+        // When
+        drop(store);
+
+        // Then
+        assert_eq!(live.get(), 100);
+        drop(fork);
+        assert_eq!(live.get(), 0);
+    }
+}
@@ -15,3 +15,51 @@ pub fn sum_iter<I: IntoIterator<Item = T>, T: AsRef<[u8]>>(iterator: I) -> Bytes
     }
     hash.finalize().into()
 }
+
+/// The hash function of the binary and sparse Merkle trees.
+///
+/// The trees hash the leaves and the internal nodes with the prefixes of the
+/// implementation prepended to their preimages, to separate the leaves from the nodes.
+/// [`Sha256`] is the hash function of the specification, and the default of the trees.
+pub trait MerkleHash {
+    /// The prefix of the preimages of the leaves.
+    const LEAF_PREFIX: u8;
+    /// The prefix of the preimages of the internal nodes.
+    const NODE_PREFIX: u8;
+
+    /// Hashes the concatenation of the `chunks` into a 32 bytes digest.
+    fn hash(chunks: &[&[u8]]) -> Bytes32;
+}
+
+/// The SHA-256 hash function, with the `0x00` prefix of the leaves and the `0x01`
+/// prefix of the internal nodes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Sha256;
+
+impl MerkleHash for Sha256 {
+    const LEAF_PREFIX: u8 = 0x00;
+    const NODE_PREFIX: u8 = 0x01;
+
+    fn hash(chunks: &[&[u8]]) -> Bytes32 {
+        sum_iter(chunks)
+    }
+}
+
+/// The SHA-512/256 hash function, the SHA-512 truncated to 256 bits with its own
+/// initial values, with the same prefixes as [`Sha256`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Sha512Trunc256;
+
+impl MerkleHash for Sha512Trunc256 {
+    const LEAF_PREFIX: u8 = 0x00;
+    const NODE_PREFIX: u8 = 0x01;
+
+    fn hash(chunks: &[&[u8]]) -> Bytes32 {
+        use digest::Digest;
+        let mut hash = sha2::Sha512_256::new();
+        for chunk in chunks {
+            hash.update(chunk);
+        }
+        hash.finalize().into()
+    }
+}
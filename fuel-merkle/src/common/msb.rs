@@ -1,3 +1,5 @@
+use crate::common::Bytes32;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum Bit {
     _0 = 0,
@@ -53,6 +55,45 @@ impl<const N: usize> Msb for [u8; N] {
     }
 }
 
+/// The bit at the `index` from the most significant bit of a 256 bits key, like
+/// [`Msb::get_bit_at_index_from_msb`] for a `Bytes32`, for the fixed-depth paths of
+/// the sparse Merkle tree.
+///
+/// The key width is known at compile time, so a single bound check on the `index`
+/// replaces the lookups of the byte and of the bit in the byte.
+pub(crate) fn bytes32_bit(key: &Bytes32, index: u32) -> Option<Bit> {
+    if index >= 256 {
+        return None
+    }
+    let byte = key[(index / 8) as usize];
+    match (byte >> (7 - index % 8)) & 1 {
+        0 => Some(Bit::_0),
+        _ => Some(Bit::_1),
+    }
+}
+
+/// The number of leading bits shared by two 256 bits keys, like
+/// [`Msb::common_prefix_count`] for two `Bytes32`, compared as two 128 bits words
+/// instead of byte by byte.
+pub(crate) fn bytes32_common_prefix_count(lhs: &Bytes32, rhs: &Bytes32) -> u32 {
+    fn words(bytes: &Bytes32) -> (u128, u128) {
+        let (hi, lo) = bytes.split_at(16);
+        (
+            u128::from_be_bytes(hi.try_into().expect("The key is 32 bytes")),
+            u128::from_be_bytes(lo.try_into().expect("The key is 32 bytes")),
+        )
+    }
+
+    let (lhs_hi, lhs_lo) = words(lhs);
+    let (rhs_hi, rhs_lo) = words(rhs);
+    let hi = lhs_hi ^ rhs_hi;
+    if hi != 0 {
+        hi.leading_zeros()
+    } else {
+        128 + (lhs_lo ^ rhs_lo).leading_zeros()
+    }
+}
+
 #[allow(clippy::cast_possible_truncation)]
 #[cfg(test)]
 mod test {
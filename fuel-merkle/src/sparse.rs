@@ -1,18 +1,40 @@
 mod hash;
 mod merkle_tree;
 mod primitive;
+mod storage_adapter;
+mod update_proof;
 
 pub(crate) use hash::zero_sum;
 
 pub use merkle_tree::{
+    Iter,
     MerkleTree,
     MerkleTreeError,
     MerkleTreeKey,
 };
 pub use primitive::Primitive;
+pub use storage_adapter::{
+    decode_node,
+    encode_node,
+    MerkleStorageAdapter,
+    MerkleStorageAdapterError,
+    ENCODED_NODE_LEN,
+};
+pub use update_proof::{
+    verify_and_update,
+    verify_and_update_with_hash,
+    ProofError,
+    SiblingPreimage,
+    UpdateProof,
+};
 pub mod in_memory;
 pub mod proof;
 
+pub use proof::{
+    verify_exclusion,
+    verify_exclusion_with_hash,
+};
+
 use crate::common::Bytes32;
 
 pub const fn empty_sum() -> &'static Bytes32 {
@@ -1,7 +1,6 @@
 use crate::common::{
-    sum_iter,
     Bytes32,
-    Prefix,
+    MerkleHash,
 };
 
 pub const fn zero_sum() -> &'static Bytes32 {
@@ -10,19 +9,16 @@ pub const fn zero_sum() -> &'static Bytes32 {
     &ZERO_SUM
 }
 
-pub fn calculate_hash(
-    prefix: &Prefix,
-    bytes_lo: &Bytes32,
-    bytes_hi: &Bytes32,
+pub fn calculate_leaf_hash<H: MerkleHash>(
+    leaf_key: &Bytes32,
+    leaf_value: &Bytes32,
 ) -> Bytes32 {
-    let input = [prefix.as_ref(), bytes_lo.as_ref(), bytes_hi.as_ref()];
-    sum_iter(input)
+    H::hash(&[&[H::LEAF_PREFIX], leaf_key, leaf_value])
 }
 
-pub fn calculate_leaf_hash(leaf_key: &Bytes32, leaf_value: &Bytes32) -> Bytes32 {
-    calculate_hash(&Prefix::Leaf, leaf_key, leaf_value)
-}
-
-pub fn calculate_node_hash(left_child: &Bytes32, right_child: &Bytes32) -> Bytes32 {
-    calculate_hash(&Prefix::Node, left_child, right_child)
+pub fn calculate_node_hash<H: MerkleHash>(
+    left_child: &Bytes32,
+    right_child: &Bytes32,
+) -> Bytes32 {
+    H::hash(&[&[H::NODE_PREFIX], left_child, right_child])
 }
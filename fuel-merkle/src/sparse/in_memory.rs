@@ -1,12 +1,15 @@
 use crate::{
     common::{
         Bytes32,
-        StorageMap,
+        CowStorageMap,
     },
     sparse::{
         self,
         merkle_tree::MerkleTreeKey,
-        proof::Proof,
+        proof::{
+            ExclusionProof,
+            Proof,
+        },
         Primitive,
     },
     storage::{
@@ -34,7 +37,7 @@ impl Mappable for NodesTable {
     type Value = Self::OwnedValue;
 }
 
-type Storage = StorageMap<NodesTable>;
+type Storage = CowStorageMap<NodesTable>;
 type SparseMerkleTree = sparse::MerkleTree<NodesTable, Storage>;
 
 #[derive(Debug)]
@@ -167,6 +170,18 @@ impl MerkleTree {
         (root, nodes)
     }
 
+    /// Returns a fork of the tree, with the same root, that shares the nodes of the tree
+    /// instead of copying them. The fork and the tree can then be updated independently,
+    /// and each records only the nodes of the paths it updates, so a speculative root
+    /// can be computed on the fork and discarded by dropping it.
+    pub fn fork(&mut self) -> Self {
+        let root = self.tree.root();
+        let storage = self.tree.storage_mut().fork();
+        let tree = SparseMerkleTree::load(storage, &root)
+            .expect("The fork has the nodes of the tree");
+        Self { tree }
+    }
+
     pub fn update(&mut self, key: MerkleTreeKey, data: &[u8]) {
         let _ = self.tree.update(key, data);
     }
@@ -175,13 +190,47 @@ impl MerkleTree {
         let _ = self.tree.delete(key);
     }
 
+    pub fn update_batch<I, D>(&mut self, updates: I) -> Bytes32
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        let _ = self.tree.update_batch(updates);
+        self.tree.root()
+    }
+
     pub fn root(&self) -> Bytes32 {
         self.tree.root()
     }
 
+    /// Returns the keys of the leaves and the digests of their values, in the order of
+    /// the keys.
+    pub fn iter(&self) -> impl Iterator<Item = (MerkleTreeKey, Bytes32)> + '_ {
+        self.tree.iter().filter_map(Result::ok)
+    }
+
+    /// Returns the leaves whose keys start with the first `prefix_bits` bits of the
+    /// `prefix`, in the order of the keys.
+    pub fn iter_prefix(
+        &self,
+        prefix: &Bytes32,
+        prefix_bits: u32,
+    ) -> impl Iterator<Item = (MerkleTreeKey, Bytes32)> + '_ {
+        self.tree
+            .iter_prefix(prefix, prefix_bits)
+            .filter_map(Result::ok)
+    }
+
     pub fn generate_proof(&self, key: &MerkleTreeKey) -> Option<Proof> {
         self.tree.generate_proof(key).ok()
     }
+
+    pub fn generate_exclusion_proof(
+        &self,
+        key: &MerkleTreeKey,
+    ) -> Option<ExclusionProof> {
+        self.tree.generate_exclusion_proof(key).ok().flatten()
+    }
 }
 
 impl Default for MerkleTree {
@@ -273,4 +322,74 @@ mod test {
             "39f36a7cb4dfb1b46f03d044265df6a491dffc1034121bc1071a34ddce9bb14b";
         assert_eq!(hex::encode(root), expected_root);
     }
+
+    #[test]
+    fn test_fork_and_parent_updates_are_independent() {
+        let mut tree = MerkleTree::new();
+        for i in 0..100u32 {
+            tree.update(key(&i.to_be_bytes()), b"DATA");
+        }
+
+        let mut fork = tree.fork();
+        assert_eq!(fork.root(), tree.root());
+
+        for i in 0..10u32 {
+            tree.update(key(&i.to_be_bytes()), b"PARENT DATA");
+            tree.delete(key(&(50 + i).to_be_bytes()));
+            fork.update(key(&(100 + i).to_be_bytes()), b"FORK DATA");
+            fork.delete(key(&(20 + i).to_be_bytes()));
+        }
+
+        let expected_tree = MerkleTree::from_set((0..100u32).filter_map(|i| {
+            let data: &[u8] = match i {
+                0..=9 => b"PARENT DATA",
+                50..=59 => return None,
+                _ => b"DATA",
+            };
+            Some((key(&i.to_be_bytes()), data))
+        }));
+        let expected_fork = MerkleTree::from_set((0..110u32).filter_map(|i| {
+            let data: &[u8] = match i {
+                20..=29 => return None,
+                100..=109 => b"FORK DATA",
+                _ => b"DATA",
+            };
+            Some((key(&i.to_be_bytes()), data))
+        }));
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_eq!(fork.root(), expected_fork.root());
+    }
+
+    #[test]
+    fn test_fork_of_a_fork_is_independent_of_both() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        let mut fork = tree.fork();
+        fork.update(key(b"\x00\x00\x00\x01"), b"DATA");
+        let root_of_fork = fork.root();
+
+        let mut fork_of_fork = fork.fork();
+        fork_of_fork.delete(key(b"\x00\x00\x00\x00"));
+
+        let expected =
+            MerkleTree::from_set([(key(b"\x00\x00\x00\x01"), b"DATA")].into_iter());
+        assert_eq!(fork_of_fork.root(), expected.root());
+        assert_eq!(fork.root(), root_of_fork);
+        let expected =
+            MerkleTree::from_set([(key(b"\x00\x00\x00\x00"), b"DATA")].into_iter());
+        assert_eq!(tree.root(), expected.root());
+    }
+
+    #[test]
+    fn test_fork_stores_only_the_nodes_of_the_updated_paths() {
+        let mut tree =
+            MerkleTree::from_set((0..1_000u32).map(|i| (key(&i.to_be_bytes()), b"DATA")));
+
+        let mut fork = tree.fork();
+        fork.update(key(b"NEW KEY"), b"DATA");
+
+        // The leaf and the internal nodes of its path; the tree has about 10 levels.
+        assert!(fork.tree.storage().local_len() < 32);
+        assert_eq!(tree.tree.storage().local_len(), 0);
+    }
 }
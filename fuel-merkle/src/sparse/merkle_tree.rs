@@ -1,6 +1,9 @@
 mod branch;
+mod iterator;
 mod node;
 
+pub use iterator::Iter;
+
 use branch::{
     merge_branches,
     Branch,
@@ -13,12 +16,23 @@ use node::{
 
 use crate::{
     common::{
+        bytes32_bit,
+        bytes32_common_prefix_count,
         error::DeserializeError,
-        node::ChildError,
+        node::{
+            ChildError,
+            ParentNode,
+        },
+        path::{
+            Instruction,
+            Path,
+        },
         AsPathIterator,
         Bit,
         Bytes32,
+        MerkleHash,
         Msb,
+        Sha256,
     },
     sparse::{
         empty_sum,
@@ -30,6 +44,8 @@ use crate::{
             Proof,
         },
         Primitive,
+        SiblingPreimage,
+        UpdateProof,
     },
     storage::{
         Mappable,
@@ -38,6 +54,7 @@ use crate::{
     },
 };
 use alloc::{
+    collections::BTreeMap,
     format,
     vec::Vec,
 };
@@ -85,6 +102,9 @@ pub struct MerkleTreeKey(Bytes32);
 impl MerkleTreeKey {
     /// The safe way to create a `Self`. It hashes the `storage_key`, making
     /// it entirely random and preventing SMT structure manipulation.
+    ///
+    /// The key is always hashed with SHA-256, whatever the hash function of the
+    /// tree, so the keys of a storage key are the same in all the trees.
     pub fn new<B>(storage_key: B) -> Self
     where
         B: AsRef<[u8]>,
@@ -152,13 +172,18 @@ impl Deref for MerkleTreeKey {
     }
 }
 
+/// The keys are exactly 256 bits, the depth of the tree, so their bits are read with
+/// the fixed-width [`bytes32_bit`] and [`bytes32_common_prefix_count`].
 impl Msb for MerkleTreeKey {
     fn get_bit_at_index_from_msb(&self, index: u32) -> Option<Bit> {
-        self.0.get_bit_at_index_from_msb(index)
+        bytes32_bit(&self.0, index)
     }
 
     fn common_prefix_count(&self, other: &[u8]) -> u32 {
-        self.0.common_prefix_count(other.as_ref())
+        match <&Bytes32>::try_from(other) {
+            Ok(other) => bytes32_common_prefix_count(&self.0, other),
+            Err(_) => self.0.common_prefix_count(other),
+        }
     }
 }
 
@@ -169,14 +194,18 @@ impl From<Bytes32> for MerkleTreeKey {
     }
 }
 
+/// The sparse Merkle tree of the leaves stored in the `StorageType`, hashed with the
+/// `HashType`, SHA-256 by default. The empty root and the placeholders are all zeros
+/// whatever the hash function.
 #[derive(Debug)]
-pub struct MerkleTree<TableType, StorageType> {
+pub struct MerkleTree<TableType, StorageType, HashType = Sha256> {
     root_node: Node,
     storage: StorageType,
     phantom_table: PhantomData<TableType>,
+    phantom_hash: PhantomData<HashType>,
 }
 
-impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
+impl<TableType, StorageType, HashType> MerkleTree<TableType, StorageType, HashType> {
     pub const fn empty_root() -> &'static Bytes32 {
         empty_sum()
     }
@@ -193,6 +222,10 @@ impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
         &self.storage
     }
 
+    pub(crate) fn storage_mut(&mut self) -> &mut StorageType {
+        &mut self.storage
+    }
+
     // PRIVATE
 
     fn root_node(&self) -> &Node {
@@ -211,19 +244,42 @@ where
     StorageType: StorageInspect<TableType, Error = StorageError>,
 {
     pub fn new(storage: StorageType) -> Self {
+        Self::new_with_hash(storage)
+    }
+
+    pub fn load(
+        storage: StorageType,
+        root: &Bytes32,
+    ) -> Result<Self, MerkleTreeError<StorageError>> {
+        Self::load_with_hash(storage, root)
+    }
+}
+
+impl<TableType, StorageType, StorageError, HashType>
+    MerkleTree<TableType, StorageType, HashType>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+    HashType: MerkleHash,
+{
+    /// Creates the empty tree hashed with the `HashType`, like [`MerkleTree::new`].
+    pub fn new_with_hash(storage: StorageType) -> Self {
         Self {
             root_node: Node::create_placeholder(),
             storage,
             phantom_table: Default::default(),
+            phantom_hash: Default::default(),
         }
     }
 
-    pub fn load(
+    /// Loads the tree of the `root` hashed with the `HashType`, like
+    /// [`MerkleTree::load`]. The `HashType` must be the one the tree was built with.
+    pub fn load_with_hash(
         storage: StorageType,
         root: &Bytes32,
     ) -> Result<Self, MerkleTreeError<StorageError>> {
         if root == Self::empty_root() {
-            let tree = Self::new(storage);
+            let tree = Self::new_with_hash(storage);
             Ok(tree)
         } else {
             let primitive = storage
@@ -231,16 +287,38 @@ where
                 .ok_or_else(|| MerkleTreeError::LoadError(*root))?
                 .into_owned();
             let tree = Self {
-                root_node: primitive
-                    .try_into()
+                root_node: Node::from_primitive(*root, primitive)
                     .map_err(MerkleTreeError::DeserializeError)?,
                 storage,
                 phantom_table: Default::default(),
+                phantom_hash: Default::default(),
             };
             Ok(tree)
         }
     }
 
+    /// Returns the iterator over the keys of the leaves of the tree and the digests of
+    /// their values, in the order of the keys. See [`Iter`] for the details.
+    pub fn iter(&self) -> Iter<'_, TableType, StorageType> {
+        self.iter_prefix(&[0; 32], 0)
+    }
+
+    /// Returns the iterator over the leaves of the tree whose keys start with the first
+    /// `prefix_bits` bits of the `prefix`, in the order of the keys. A `prefix_bits`
+    /// above 256 is the same as 256. See [`Iter`] for the details.
+    pub fn iter_prefix(
+        &self,
+        prefix: &Bytes32,
+        prefix_bits: u32,
+    ) -> Iter<'_, TableType, StorageType> {
+        Iter::new(
+            &self.storage,
+            self.root_node().clone(),
+            *prefix,
+            prefix_bits,
+        )
+    }
+
     // PRIVATE
 
     fn path_set(
@@ -281,7 +359,26 @@ where
     /// preferred approach when the key-values are known upfront. Leaves can be
     /// appended to the returned tree using `update` to further accumulate leaf
     /// data.
-    pub fn from_set<B, I, D>(
+    pub fn from_set<B, I, D>(storage: StorageType, set: I) -> Result<Self, StorageError>
+    where
+        I: Iterator<Item = (B, D)>,
+        B: Into<Bytes32>,
+        D: AsRef<[u8]>,
+    {
+        Self::from_set_with_hash(storage, set)
+    }
+}
+
+impl<TableType, StorageType, StorageError, HashType>
+    MerkleTree<TableType, StorageType, HashType>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageMutate<TableType, Error = StorageError>,
+    HashType: MerkleHash,
+{
+    /// Build a sparse Merkle tree hashed with the `HashType` from a set of key-value
+    /// pairs, like [`MerkleTree::from_set`].
+    pub fn from_set_with_hash<B, I, D>(
         mut storage: StorageType,
         set: I,
     ) -> Result<Self, StorageError>
@@ -297,7 +394,7 @@ where
         let mut branches = sorted
             .iter()
             .filter(|(_, value)| !value.as_ref().is_empty())
-            .map(|(key, data)| Node::create_leaf(key, data))
+            .map(|(key, data)| Node::create_leaf::<HashType, _>(key, data))
             .map(Into::<Branch>::into)
             .collect::<Vec<_>>();
 
@@ -307,13 +404,13 @@ where
         }
 
         if branches.is_empty() {
-            let tree = Self::new(storage);
+            let tree = Self::new_with_hash(storage);
             return Ok(tree)
         }
 
         if branches.len() == 1 {
             let leaf = branches.pop().expect("Expected at least 1 leaf").node;
-            let mut tree = Self::new(storage);
+            let mut tree = Self::new_with_hash(storage);
             tree.set_root_node(leaf);
             return Ok(tree)
         }
@@ -369,7 +466,8 @@ where
                     let current =
                         nodes.pop().expect("Expected current node to be present");
                     let right = nodes.pop().expect("Expected right node to be present");
-                    let merged = merge_branches(&mut storage, current, right)?;
+                    let merged =
+                        merge_branches::<HashType, _, _>(&mut storage, current, right)?;
                     nodes.push(merged);
 
                     // Now that the current node and its right neighbour are
@@ -392,7 +490,7 @@ where
                 .pop()
                 .expect("Nodes stack must have at least 1 element");
             while let Some(next) = nodes.pop() {
-                node = merge_branches(&mut storage, node, next)?;
+                node = merge_branches::<HashType, _, _>(&mut storage, node, next)?;
             }
             node
         };
@@ -407,7 +505,7 @@ where
         let depth = Node::max_height() - height;
         let placeholders = iter::repeat(Node::create_placeholder()).take(depth as usize);
         for placeholder in placeholders {
-            node = Node::create_node_on_path(&path, &node, &placeholder);
+            node = Node::create_node_on_path::<HashType>(&path, &node, &placeholder);
             storage.insert(node.hash(), &node.as_ref().into())?;
         }
 
@@ -415,6 +513,7 @@ where
             root_node: node,
             storage,
             phantom_table: Default::default(),
+            phantom_hash: Default::default(),
         };
         Ok(tree)
     }
@@ -431,7 +530,7 @@ where
             return Ok(())
         }
 
-        let leaf_node = Node::create_leaf(key.as_ref(), data);
+        let leaf_node = Node::create_leaf::<HashType, _>(key.as_ref(), data);
         self.storage
             .insert(leaf_node.hash(), &leaf_node.as_ref().into())?;
 
@@ -476,8 +575,151 @@ where
         Ok(())
     }
 
+    /// Applies the `updates` to the tree and returns the new root. A `None` or
+    /// empty value deletes the key.
+    ///
+    /// The result is the same as calling [update](Self::update) or
+    /// [delete](Self::delete) for each update in key order, with the last
+    /// update of a key overriding the previous ones. The nodes shared by the
+    /// paths of several updated keys are recomputed only once.
+    pub fn update_batch<I, D>(
+        &mut self,
+        updates: I,
+    ) -> Result<Bytes32, MerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        let updates = updates
+            .into_iter()
+            .map(|(key, data)| {
+                let leaf = data
+                    .filter(|data| !data.as_ref().is_empty())
+                    .map(|data| Node::create_leaf::<HashType, _>(key.as_ref(), data));
+                (*key, leaf)
+            })
+            .collect::<BTreeMap<Bytes32, Option<Node>>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let root_node = self.root_node().clone();
+        let root_node = self.update_subtree(root_node, 0, &updates)?;
+        self.set_root_node(root_node);
+
+        Ok(self.root())
+    }
+
     // PRIVATE
 
+    /// Applies the sorted `updates` to the subtree of the `node` at the `depth`,
+    /// and returns the new root of the subtree.
+    fn update_subtree(
+        &mut self,
+        node: Node,
+        depth: u32,
+        updates: &[(Bytes32, Option<Node>)],
+    ) -> Result<Node, MerkleTreeError<StorageError>> {
+        if updates.is_empty() {
+            return Ok(node)
+        }
+
+        if node.is_node() {
+            let storage_node = StorageNode::new(&self.storage, node);
+            let left_child = storage_node
+                .left_child()
+                .map_err(MerkleTreeError::ChildError)?
+                .into_node();
+            let right_child = storage_node
+                .right_child()
+                .map_err(MerkleTreeError::ChildError)?
+                .into_node();
+            let node = storage_node.into_node();
+            self.storage.remove(node.hash())?;
+
+            let (left_updates, right_updates) =
+                updates.split_at(updates.partition_point(|(key, _)| {
+                    matches!(key.get_instruction(depth), Some(Instruction::Left))
+                }));
+            let left_child = self.update_subtree(left_child, depth + 1, left_updates)?;
+            let right_child =
+                self.update_subtree(right_child, depth + 1, right_updates)?;
+
+            return Ok(self.join_subtrees(left_child, right_child, depth)?)
+        }
+
+        // The subtree holds at most one leaf: it is rebuilt from its leaves
+        // after the updates.
+        let mut leaves = updates
+            .iter()
+            .filter_map(|(_, leaf)| leaf.clone())
+            .collect::<Vec<_>>();
+        if !node.is_placeholder() {
+            let leaf_key = node.leaf_key();
+            if updates
+                .binary_search_by(|(key, _)| key.cmp(leaf_key))
+                .is_ok()
+            {
+                self.storage.remove(node.hash())?;
+            } else {
+                let position = leaves.partition_point(|leaf| leaf.leaf_key() < leaf_key);
+                leaves.insert(position, node);
+            }
+        }
+        for leaf in updates.iter().filter_map(|(_, leaf)| leaf.as_ref()) {
+            self.storage.insert(leaf.hash(), &leaf.into())?;
+        }
+
+        Ok(self.build_subtree(&leaves, depth)?)
+    }
+
+    /// Builds the subtree at the `depth` of the sorted `leaves`.
+    fn build_subtree(
+        &mut self,
+        leaves: &[Node],
+        depth: u32,
+    ) -> Result<Node, StorageError> {
+        match leaves {
+            [] => Ok(Node::create_placeholder()),
+            [leaf] => Ok(leaf.clone()),
+            _ => {
+                let (left_leaves, right_leaves) =
+                    leaves.split_at(leaves.partition_point(|leaf| {
+                        matches!(
+                            leaf.leaf_key().get_instruction(depth),
+                            Some(Instruction::Left)
+                        )
+                    }));
+                let left_child = self.build_subtree(left_leaves, depth + 1)?;
+                let right_child = self.build_subtree(right_leaves, depth + 1)?;
+                self.join_subtrees(left_child, right_child, depth)
+            }
+        }
+    }
+
+    /// Joins the subtrees of the children of the node at the `depth`. A subtree
+    /// with a single leaf is the leaf itself.
+    fn join_subtrees(
+        &mut self,
+        left_child: Node,
+        right_child: Node,
+        depth: u32,
+    ) -> Result<Node, StorageError> {
+        if left_child.is_placeholder() && right_child.is_leaf() {
+            return Ok(right_child)
+        }
+        if right_child.is_placeholder() && left_child.is_leaf() {
+            return Ok(left_child)
+        }
+
+        let node = Node::create_node::<HashType>(
+            &left_child,
+            &right_child,
+            Node::max_height() - depth,
+        );
+        self.storage.insert(node.hash(), &node.as_ref().into())?;
+        Ok(node)
+    }
+
     fn update_with_path_set(
         &mut self,
         requested_leaf_node: &Node,
@@ -517,8 +759,11 @@ where
         if requested_leaf_node.leaf_key() != actual_leaf_node.leaf_key() {
             // Merge leaves
             if !actual_leaf_node.is_placeholder() {
-                current_node =
-                    Node::create_node_on_path(path, &current_node, actual_leaf_node);
+                current_node = Node::create_node_on_path::<HashType>(
+                    path,
+                    &current_node,
+                    actual_leaf_node,
+                );
                 self.storage
                     .insert(current_node.hash(), &current_node.as_ref().into())?;
             }
@@ -530,8 +775,11 @@ where
             let placeholders =
                 iter::repeat(Node::create_placeholder()).take(placeholders_count);
             for placeholder in placeholders {
-                current_node =
-                    Node::create_node_on_path(path, &current_node, &placeholder);
+                current_node = Node::create_node_on_path::<HashType>(
+                    path,
+                    &current_node,
+                    &placeholder,
+                );
                 self.storage
                     .insert(current_node.hash(), &current_node.as_ref().into())?;
             }
@@ -541,7 +789,8 @@ where
 
         // Merge side nodes
         for side_node in side_nodes {
-            current_node = Node::create_node_on_path(path, &current_node, side_node);
+            current_node =
+                Node::create_node_on_path::<HashType>(path, &current_node, side_node);
             self.storage
                 .insert(current_node.hash(), &current_node.as_ref().into())?;
         }
@@ -601,8 +850,11 @@ where
                 if let Some(side_node) =
                     side_nodes_iter.find(|side_node| !side_node.is_placeholder())
                 {
-                    current_node =
-                        Node::create_node_on_path(path, &current_node, side_node);
+                    current_node = Node::create_node_on_path::<HashType>(
+                        path,
+                        &current_node,
+                        side_node,
+                    );
                     self.storage
                         .insert(current_node.hash(), &current_node.as_ref().into())?;
                 }
@@ -611,7 +863,8 @@ where
 
         // Merge side nodes
         for side_node in side_nodes_iter {
-            current_node = Node::create_node_on_path(path, &current_node, side_node);
+            current_node =
+                Node::create_node_on_path::<HashType>(path, &current_node, side_node);
             self.storage
                 .insert(current_node.hash(), &current_node.as_ref().into())?;
         }
@@ -622,10 +875,12 @@ where
     }
 }
 
-impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
+impl<TableType, StorageType, StorageError, HashType>
+    MerkleTree<TableType, StorageType, HashType>
 where
     TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
     StorageType: StorageInspect<TableType, Error = StorageError>,
+    HashType: MerkleHash,
 {
     pub fn generate_proof(
         &self,
@@ -633,6 +888,44 @@ where
     ) -> Result<Proof, MerkleTreeError<StorageError>> {
         let path = key.as_ref();
         let (path_nodes, side_nodes) = self.path_set(path)?;
+        Ok(Self::proof_with_path_set(path, &path_nodes[0], &side_nodes))
+    }
+
+    /// Generates the proof of the value of the `key`, verified by
+    /// [`verify_and_update`](crate::sparse::verify_and_update) to compute the root after
+    /// an update of the key.
+    pub fn generate_update_proof(
+        &self,
+        key: &MerkleTreeKey,
+    ) -> Result<UpdateProof, MerkleTreeError<StorageError>> {
+        let path = key.as_ref();
+        let (path_nodes, side_nodes) = self.path_set(path)?;
+        let proof = Self::proof_with_path_set(path, &path_nodes[0], &side_nodes);
+        let sibling = match side_nodes.first() {
+            Some(side_node) if proof.is_inclusion() && !side_node.is_placeholder() => {
+                let sibling = if side_node.is_leaf() {
+                    SiblingPreimage::Leaf {
+                        leaf_key: *side_node.leaf_key(),
+                        leaf_value: *side_node.leaf_data(),
+                    }
+                } else {
+                    SiblingPreimage::Node {
+                        left_child: *side_node.left_child_key(),
+                        right_child: *side_node.right_child_key(),
+                    }
+                };
+                Some(sibling)
+            }
+            _ => None,
+        };
+        Ok(UpdateProof { proof, sibling })
+    }
+
+    fn proof_with_path_set(
+        path: &Bytes32,
+        actual_leaf: &Node,
+        side_nodes: &[Node],
+    ) -> Proof {
         // Identify the closest leaf that is included in the tree to the
         // requested leaf. The closest leaf, as returned by the path set
         // corresponding to the requested leaf, will be the requested leaf
@@ -644,12 +937,11 @@ where
         // placeholder), the requested leaf is not in the tree, and we are
         // requesting an exclusion proof.
         //
-        let actual_leaf = &path_nodes[0];
         let proof_set = side_nodes
-            .into_iter()
+            .iter()
             .map(|side_node| *side_node.hash())
             .collect::<Vec<_>>();
-        let proof = if !actual_leaf.is_placeholder() && actual_leaf.leaf_key() == path {
+        if !actual_leaf.is_placeholder() && actual_leaf.leaf_key() == path {
             // If the requested key is part of the tree, build an inclusion
             // proof.
             let inclusion_proof = InclusionProof { proof_set };
@@ -688,8 +980,20 @@ where
 
             let exclusion_proof = ExclusionProof { proof_set, leaf };
             Proof::Exclusion(exclusion_proof)
-        };
-        Ok(proof)
+        }
+    }
+
+    /// Generates the proof that the `key` is not in the tree, verified by
+    /// [`verify_exclusion`](crate::sparse::verify_exclusion). Returns `None` if the key
+    /// is in the tree.
+    pub fn generate_exclusion_proof(
+        &self,
+        key: &MerkleTreeKey,
+    ) -> Result<Option<ExclusionProof>, MerkleTreeError<StorageError>> {
+        match self.generate_proof(key)? {
+            Proof::Exclusion(proof) => Ok(Some(proof)),
+            Proof::Inclusion(_) => Ok(None),
+        }
     }
 }
 
@@ -701,10 +1005,15 @@ mod test {
         common::{
             sum,
             Bytes32,
+            Sha256,
+            Sha512Trunc256,
             StorageMap,
         },
         sparse::{
             empty_sum,
+            proof::Proof,
+            verify_exclusion,
+            verify_exclusion_with_hash,
             MerkleTree,
             MerkleTreeError,
             MerkleTreeKey,
@@ -1176,11 +1485,11 @@ mod test {
 
         let leaf_1_key = key(b"\x00\x00\x00\x00");
         let leaf_1_data = b"DATA_1";
-        let leaf_1 = Node::create_leaf(&leaf_1_key.0, leaf_1_data);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&leaf_1_key.0, leaf_1_data);
 
         let leaf_2_key = MerkleTreeKey::new_without_hash(*leaf_1.hash());
         let leaf_2_data = b"DATA_2";
-        let leaf_2 = Node::create_leaf(&leaf_2_key.0, leaf_2_data);
+        let leaf_2 = Node::create_leaf::<Sha256, _>(&leaf_2_key.0, leaf_2_data);
 
         tree.update(leaf_2_key, leaf_2_data).unwrap();
         tree.update(leaf_1_key, leaf_1_data).unwrap();
@@ -1515,14 +1824,14 @@ mod test {
         tree.update(MerkleTreeKey::new_without_hash(k3), &v3)
             .expect("Expected successful update");
 
-        let l0 = Node::create_leaf(&k0, v0);
-        let l1 = Node::create_leaf(&k1, v1);
-        let l2 = Node::create_leaf(&k2, v2);
-        let l3 = Node::create_leaf(&k3, v3);
-        let n0 = Node::create_node(&l1, &l3, 252);
-        let n1 = Node::create_node(&n0, &Node::create_placeholder(), 253);
-        let n2 = Node::create_node(&n1, &l2, 254);
-        let n3 = Node::create_node(&l0, &n2, 255);
+        let l0 = Node::create_leaf::<Sha256, _>(&k0, v0);
+        let l1 = Node::create_leaf::<Sha256, _>(&k1, v1);
+        let l2 = Node::create_leaf::<Sha256, _>(&k2, v2);
+        let l3 = Node::create_leaf::<Sha256, _>(&k3, v3);
+        let n0 = Node::create_node::<Sha256>(&l1, &l3, 252);
+        let n1 = Node::create_node::<Sha256>(&n0, &Node::create_placeholder(), 253);
+        let n2 = Node::create_node::<Sha256>(&n1, &l2, 254);
+        let n3 = Node::create_node::<Sha256>(&l0, &n2, 255);
 
         {
             // When
@@ -1685,4 +1994,367 @@ mod test {
         // Then
         assert!(proof.is_exclusion());
     }
+
+    /// Random keys that share long prefixes: the first 31 bytes are taken from a
+    /// small set, and the last byte is random.
+    fn clustered_key<R: rand::Rng>(rng: &mut R) -> MerkleTreeKey {
+        let mut key = [0u8; 32];
+        key[0] = rng.gen_range(0..4) << 6;
+        key[30] = rng.gen_range(0..2);
+        key[31] = rng.gen();
+        MerkleTreeKey::new_without_hash(key)
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__matches_sequential_updates_in_key_order() {
+        use rand::{
+            rngs::StdRng,
+            Rng,
+            SeedableRng,
+        };
+
+        let rng = &mut StdRng::seed_from_u64(8586);
+
+        for _ in 0..16 {
+            // Given
+            let initial: Vec<_> = (0..rng.gen_range(0..40))
+                .map(|_| (clustered_key(rng), random_bytes32(rng)))
+                .collect();
+            let mut updates: Vec<_> = (0..rng.gen_range(0..40))
+                .map(|_| {
+                    let key = if !initial.is_empty() && rng.gen_bool(0.5) {
+                        initial[rng.gen_range(0..initial.len())].0
+                    } else {
+                        clustered_key(rng)
+                    };
+                    let data = rng.gen_bool(0.7).then(|| random_bytes32(rng));
+                    (key, data)
+                })
+                .collect();
+
+            let mut sequential_storage = StorageMap::<TestTable>::new();
+            let mut sequential = MerkleTree::new(&mut sequential_storage);
+            let mut batch_storage = StorageMap::<TestTable>::new();
+            let mut batch = MerkleTree::new(&mut batch_storage);
+            for (key, data) in initial.iter() {
+                sequential.update(*key, data).unwrap();
+                batch.update(*key, data).unwrap();
+            }
+
+            // When
+            let root = batch.update_batch(updates.clone()).unwrap();
+
+            // Then
+            updates.sort_by_key(|(key, _)| **key);
+            for (key, data) in updates.iter() {
+                match data {
+                    Some(data) => sequential.update(*key, data).unwrap(),
+                    None => sequential.delete(*key).unwrap(),
+                }
+            }
+            assert_eq!(root, sequential.root());
+            assert_eq!(batch.root(), sequential.root());
+            let keys = initial.iter().map(|(key, _)| key);
+            for key in keys.chain(updates.iter().map(|(key, _)| key)) {
+                assert_eq!(
+                    batch.generate_proof(key).unwrap(),
+                    sequential.generate_proof(key).unwrap()
+                );
+            }
+            assert_eq!(batch_storage.len(), sequential_storage.len());
+        }
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__the_last_update_of_a_key_wins() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA").unwrap();
+        let updates = vec![
+            (key(b"\x00\x00\x00\x00"), None),
+            (key(b"\x00\x00\x00\x01"), Some(b"DATA".as_slice())),
+            (key(b"\x00\x00\x00\x00"), Some(b"NEW DATA".as_slice())),
+        ];
+
+        // When
+        let root = tree.update_batch(updates).unwrap();
+
+        // Then
+        let mut expected_storage = StorageMap::<TestTable>::new();
+        let mut expected = MerkleTree::new(&mut expected_storage);
+        expected
+            .update(key(b"\x00\x00\x00\x00"), b"NEW DATA")
+            .unwrap();
+        expected.update(key(b"\x00\x00\x00\x01"), b"DATA").unwrap();
+        assert_eq!(root, expected.root());
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__deletes_of_absent_keys_keep_the_root() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA").unwrap();
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA").unwrap();
+        let expected_root = tree.root();
+
+        // When
+        let root = tree
+            .update_batch([
+                (key(b"\x00\x00\x00\x02"), None::<&[u8]>),
+                (key(b"\x00\x00\x00\x03"), Some(&[])),
+            ])
+            .unwrap();
+
+        // Then
+        assert_eq!(root, expected_root);
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__deleting_every_key_yields_the_empty_root() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let keys: Vec<_> = (0u32..10).map(|i| key(i.to_be_bytes())).collect();
+        tree.update_batch(keys.iter().map(|key| (*key, Some(b"DATA"))))
+            .unwrap();
+
+        // When
+        let root = tree
+            .update_batch(keys.iter().map(|key| (*key, None::<&[u8]>)))
+            .unwrap();
+
+        // Then
+        assert_eq!(root, *empty_sum());
+        assert!(tree.into_storage().is_empty());
+    }
+
+    #[test]
+    fn merkle_tree__iter__returns_nothing_for_an_empty_tree() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = MerkleTree::new(&mut storage);
+
+        // When
+        let leaves = tree.iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+        // Then
+        assert!(leaves.is_empty());
+    }
+
+    #[test]
+    fn merkle_tree__iter__returns_the_leaves_in_key_order() {
+        use rand::{
+            rngs::StdRng,
+            SeedableRng,
+        };
+        use std::collections::BTreeMap;
+
+        let rng = &mut StdRng::seed_from_u64(8586);
+
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let mut expected = BTreeMap::new();
+        for i in 0..200 {
+            let key = if i % 2 == 0 {
+                clustered_key(rng)
+            } else {
+                MerkleTreeKey::new_without_hash(random_bytes32(rng))
+            };
+            let value = random_bytes32(rng);
+            tree.update(key, &value).unwrap();
+            expected.insert(*key, sum(value));
+        }
+        for key in expected.keys().take(20).copied().collect::<Vec<_>>() {
+            tree.delete(MerkleTreeKey::new_without_hash(key)).unwrap();
+            expected.remove(&key);
+        }
+
+        // When
+        let leaves = tree.iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+        // Then
+        let leaves = leaves
+            .into_iter()
+            .map(|(key, value)| (*key, value))
+            .collect::<Vec<_>>();
+        assert_eq!(leaves, expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn merkle_tree__iter_prefix__returns_exactly_the_keys_under_an_8_bit_prefix() {
+        use rand::{
+            rngs::StdRng,
+            Rng,
+            SeedableRng,
+        };
+
+        let rng = &mut StdRng::seed_from_u64(8586);
+
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let mut keys = Vec::new();
+        for _ in 0..300 {
+            let mut key = random_bytes32(rng);
+            // The neighbours of the prefix share up to 7 bits with it.
+            key[0] = [0xAB, 0xAA, 0xAC, 0x2B, 0xFF][rng.gen_range(0..5)];
+            tree.update(MerkleTreeKey::new_without_hash(key), b"DATA")
+                .unwrap();
+            keys.push(key);
+        }
+        let mut prefix = [0u8; 32];
+        prefix[0] = 0xAB;
+        prefix[1] = 0xFF; // Bits past the prefix are ignored.
+
+        // When
+        let leaves = tree
+            .iter_prefix(&prefix, 8)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // Then
+        let mut expected = keys
+            .into_iter()
+            .filter(|key| key[0] == 0xAB)
+            .collect::<Vec<_>>();
+        expected.sort();
+        expected.dedup();
+        assert!(!expected.is_empty());
+        let leaves = leaves.into_iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        assert_eq!(leaves, expected);
+    }
+
+    #[test]
+    fn merkle_tree__iter_prefix__with_256_bits_returns_only_the_key_of_the_prefix() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for i in 0..10u8 {
+            tree.update(MerkleTreeKey::new_without_hash([i; 32]), b"DATA")
+                .unwrap();
+        }
+
+        // When
+        let present = tree
+            .iter_prefix(&[3; 32], 300)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let absent = tree
+            .iter_prefix(&[11; 32], 256)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            present,
+            vec![(MerkleTreeKey::new_without_hash([3; 32]), sum(b"DATA"))]
+        );
+        assert!(absent.is_empty());
+    }
+
+    #[test]
+    fn merkle_tree__iter__returns_an_error_for_a_missing_node() {
+        use fuel_storage::StorageMutate;
+
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for i in 0..10u8 {
+            tree.update(MerkleTreeKey::new_without_hash([i; 32]), b"DATA")
+                .unwrap();
+        }
+        let root = tree.root();
+        let missing_leaf = Node::create_leaf::<Sha256, _>(&[5; 32], b"DATA");
+        StorageMutate::<TestTable>::remove(&mut storage, missing_leaf.hash()).unwrap();
+        let tree = MerkleTree::load(&mut storage, &root).unwrap();
+
+        // When
+        let leaves = tree.iter().collect::<Vec<_>>();
+
+        // Then
+        let (error, leaves) = leaves.split_last().unwrap();
+        assert!(matches!(error, Err(MerkleTreeError::ChildError(_))));
+        assert!(leaves.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn merkle_tree__from_set_with_hash__hashes_the_leaves_and_nodes_with_the_hash() {
+        use digest::Digest;
+        fn sha512_256(chunks: &[&[u8]]) -> Bytes32 {
+            let mut hash = sha2::Sha512_256::new();
+            for chunk in chunks {
+                hash.update(chunk);
+            }
+            hash.finalize().into()
+        }
+
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let set = [([0x00; 32], b"A"), ([0xff; 32], b"B")];
+
+        // When
+        let tree = MerkleTree::<_, _, Sha512Trunc256>::from_set_with_hash(
+            &mut storage,
+            set.into_iter(),
+        )
+        .unwrap();
+
+        // Then
+        let leaf_a = sha512_256(&[&[0x00], &[0x00; 32], &sha512_256(&[b"A"])]);
+        let leaf_b = sha512_256(&[&[0x00], &[0xff; 32], &sha512_256(&[b"B"])]);
+        let expected_root = sha512_256(&[&[0x01], &leaf_a, &leaf_b]);
+        assert_eq!(tree.root(), expected_root);
+    }
+
+    #[test]
+    fn merkle_tree__with_hash__updates_loads_and_proves_like_from_set() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::<_, _, Sha512Trunc256>::new_with_hash(&mut storage);
+        for i in 0..10u8 {
+            tree.update(MerkleTreeKey::new_without_hash([i; 32]), b"DATA")
+                .unwrap();
+        }
+        tree.delete(MerkleTreeKey::new_without_hash([3; 32]))
+            .unwrap();
+        let root = tree.root();
+        let set = || {
+            (0..10u8)
+                .filter(|i| *i != 3)
+                .map(|i| (MerkleTreeKey::new_without_hash([i; 32]), b"DATA"))
+        };
+        let mut set_storage = StorageMap::<TestTable>::new();
+        let set_tree = MerkleTree::<_, _, Sha512Trunc256>::from_set_with_hash(
+            &mut set_storage,
+            set(),
+        )
+        .unwrap();
+        let mut sha256_storage = StorageMap::<TestTable>::new();
+        let sha256_tree = MerkleTree::from_set(&mut sha256_storage, set()).unwrap();
+
+        // When
+        let tree =
+            MerkleTree::<_, _, Sha512Trunc256>::load_with_hash(&mut storage, &root)
+                .unwrap();
+        let included = MerkleTreeKey::new_without_hash([5; 32]);
+        let excluded = MerkleTreeKey::new_without_hash([3; 32]);
+        let inclusion = tree.generate_proof(&included).unwrap();
+        let exclusion = tree.generate_exclusion_proof(&excluded).unwrap().unwrap();
+
+        // Then
+        assert_eq!(set_tree.root(), root);
+        assert_ne!(sha256_tree.root(), root);
+        let Proof::Inclusion(inclusion) = inclusion else {
+            panic!("Expected InclusionProof")
+        };
+        assert!(inclusion.verify_with_hash::<Sha512Trunc256>(&root, &included, b"DATA"));
+        assert!(!inclusion.verify(&root, &included, b"DATA"));
+        assert!(verify_exclusion_with_hash::<Sha512Trunc256>(
+            &root, &excluded, &exclusion
+        ));
+        assert!(!verify_exclusion(&root, &excluded, &exclusion));
+    }
 }
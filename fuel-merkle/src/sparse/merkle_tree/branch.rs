@@ -3,6 +3,7 @@ use crate::{
     common::{
         path::ComparablePath,
         Bytes32,
+        MerkleHash,
     },
     sparse::Primitive,
 };
@@ -27,12 +28,13 @@ impl From<Node> for Branch {
     }
 }
 
-pub(super) fn merge_branches<Storage, Table>(
+pub(super) fn merge_branches<H, Storage, Table>(
     storage: &mut Storage,
     mut left_branch: Branch,
     mut right_branch: Branch,
 ) -> Result<Branch, Storage::Error>
 where
+    H: MerkleHash,
     Storage: StorageMutate<Table>,
     Table: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
 {
@@ -40,7 +42,7 @@ where
         let parent_depth = left_branch.node.common_path_length(&right_branch.node);
         let parent_height = Node::max_height() - parent_depth;
         let node =
-            Node::create_node(&left_branch.node, &right_branch.node, parent_height);
+            Node::create_node::<H>(&left_branch.node, &right_branch.node, parent_height);
         Branch {
             bits: left_branch.bits,
             node,
@@ -57,7 +59,7 @@ where
                 iter::repeat(Node::create_placeholder()).take(stale_depth as usize);
             for placeholder in placeholders {
                 current_node =
-                    Node::create_node_on_path(&path, &current_node, &placeholder);
+                    Node::create_node_on_path::<H>(&path, &current_node, &placeholder);
                 storage.insert(current_node.hash(), &current_node.as_ref().into())?;
             }
             right_branch.node = current_node;
@@ -71,13 +73,16 @@ where
                 iter::repeat(Node::create_placeholder()).take(stale_depth as usize);
             for placeholder in placeholders {
                 current_node =
-                    Node::create_node_on_path(&path, &current_node, &placeholder);
+                    Node::create_node_on_path::<H>(&path, &current_node, &placeholder);
                 storage.insert(current_node.hash(), &current_node.as_ref().into())?;
             }
             left_branch.node = current_node;
         }
-        let node =
-            Node::create_node(&left_branch.node, &right_branch.node, ancestor_height);
+        let node = Node::create_node::<H>(
+            &left_branch.node,
+            &right_branch.node,
+            ancestor_height,
+        );
         Branch {
             bits: left_branch.bits,
             node,
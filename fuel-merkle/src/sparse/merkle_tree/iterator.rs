@@ -0,0 +1,119 @@
+use super::{
+    MerkleTreeError,
+    MerkleTreeKey,
+    Node,
+    StorageNode,
+};
+use crate::{
+    common::{
+        node::ParentNode,
+        Bit,
+        Bytes32,
+        Msb,
+    },
+    sparse::Primitive,
+    storage::{
+        Mappable,
+        StorageInspect,
+    },
+};
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::marker::PhantomData;
+
+/// The iterator over the leaves of a sparse Merkle tree, in the order of their keys,
+/// created by [`MerkleTree::iter`](super::MerkleTree::iter) and
+/// [`MerkleTree::iter_prefix`](super::MerkleTree::iter_prefix).
+///
+/// The iterator yields the key of each leaf with the SHA-256 digest of its value: the
+/// tree only stores the digests, the values themselves are kept by the owner of the
+/// tree. It descends the nodes depth first, loading only the nodes of the current path
+/// and their siblings from the storage, so it works for trees that don't fit in memory.
+///
+/// The iterator borrows the tree, so the tree can't be updated during the iteration:
+/// it yields the leaves of the root of the tree at the time the iterator was created.
+/// After the first error, when a node can't be loaded from the storage, the iterator
+/// yields no more items.
+pub struct Iter<'a, TableType, StorageType> {
+    storage: &'a StorageType,
+    stack: Vec<Node>,
+    prefix: Bytes32,
+    prefix_bits: u32,
+    phantom_table: PhantomData<TableType>,
+}
+
+impl<'a, TableType, StorageType> Iter<'a, TableType, StorageType> {
+    pub(super) fn new(
+        storage: &'a StorageType,
+        root_node: Node,
+        prefix: Bytes32,
+        prefix_bits: u32,
+    ) -> Self {
+        Self {
+            storage,
+            stack: vec![root_node],
+            prefix,
+            prefix_bits: prefix_bits.min(Node::max_height()),
+            phantom_table: Default::default(),
+        }
+    }
+}
+
+impl<TableType, StorageType, StorageError> Iterator for Iter<'_, TableType, StorageType>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+{
+    type Item = Result<(MerkleTreeKey, Bytes32), MerkleTreeError<StorageError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if node.is_placeholder() {
+                continue
+            }
+
+            if node.is_leaf() {
+                // A leaf can be stored above the depth of the prefix, so its key has to
+                // be checked.
+                if node.leaf_key().common_prefix_count(&self.prefix) >= self.prefix_bits {
+                    let key = MerkleTreeKey(*node.leaf_key());
+                    return Some(Ok((key, *node.leaf_data())))
+                }
+                continue
+            }
+
+            // The internal node at the height `h` has the path of the first `max - h`
+            // bits of the keys of its leaves, and the next bit selects its child.
+            let bit_index = Node::max_height() - node.height();
+            let storage_node = StorageNode::new(self.storage, node);
+            let children = storage_node
+                .left_child()
+                .and_then(|left| Ok((left, storage_node.right_child()?)));
+            let (left_child, right_child) = match children {
+                Ok(children) => children,
+                Err(error) => {
+                    self.stack.clear();
+                    return Some(Err(MerkleTreeError::ChildError(error)))
+                }
+            };
+
+            if bit_index < self.prefix_bits {
+                let child = match self.prefix.get_bit_at_index_from_msb(bit_index) {
+                    Some(Bit::_1) => right_child,
+                    _ => left_child,
+                };
+                self.stack.push(child.into_node());
+            } else {
+                // The right child is pushed first, so the left child, with the lower
+                // keys, is visited first.
+                self.stack.push(right_child.into_node());
+                self.stack.push(left_child.into_node());
+            }
+        }
+
+        None
+    }
+}
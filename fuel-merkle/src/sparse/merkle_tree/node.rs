@@ -1,5 +1,6 @@
 use crate::{
     common::{
+        bytes32_common_prefix_count,
         error::DeserializeError,
         node::{
             ChildError,
@@ -8,17 +9,15 @@ use crate::{
             ParentNode as ParentNodeTrait,
         },
         path::{
-            ComparablePath,
             Instruction,
             Path,
         },
-        sum,
         Bytes32,
+        MerkleHash,
         Prefix,
     },
     sparse::{
         hash::{
-            calculate_hash,
             calculate_leaf_hash,
             calculate_node_hash,
         },
@@ -57,25 +56,26 @@ impl Node {
         Node::key_size_in_bits()
     }
 
-    pub fn new(
-        height: u32,
-        prefix: Prefix,
-        bytes_lo: Bytes32,
-        bytes_hi: Bytes32,
-    ) -> Self {
-        Self::Node {
-            hash: calculate_hash(&prefix, &bytes_lo, &bytes_hi),
-            height,
-            prefix,
-            bytes_lo,
-            bytes_hi,
-        }
-    }
-
-    pub fn create_leaf<D: AsRef<[u8]>>(key: &Bytes32, data: D) -> Self {
-        let bytes_hi = sum(data);
+    /// Restores the node of the `primitive` stored under its `hash`. The tree stores
+    /// each node under its hash, so the hash isn't computed again, and the node doesn't
+    /// depend on the hash function of the tree.
+    pub fn from_primitive(
+        hash: Bytes32,
+        primitive: Primitive,
+    ) -> Result<Self, DeserializeError> {
+        Ok(Self::Node {
+            hash,
+            height: primitive.height(),
+            prefix: primitive.prefix()?,
+            bytes_lo: *primitive.bytes_lo(),
+            bytes_hi: *primitive.bytes_hi(),
+        })
+    }
+
+    pub fn create_leaf<H: MerkleHash, D: AsRef<[u8]>>(key: &Bytes32, data: D) -> Self {
+        let bytes_hi = H::hash(&[data.as_ref()]);
         Self::Node {
-            hash: calculate_leaf_hash(key, &bytes_hi),
+            hash: calculate_leaf_hash::<H>(key, &bytes_hi),
             height: 0u32,
             prefix: Prefix::Leaf,
             bytes_lo: *key,
@@ -83,11 +83,15 @@ impl Node {
         }
     }
 
-    pub fn create_node(left_child: &Node, right_child: &Node, height: u32) -> Self {
+    pub fn create_node<H: MerkleHash>(
+        left_child: &Node,
+        right_child: &Node,
+        height: u32,
+    ) -> Self {
         let bytes_lo = *left_child.hash();
         let bytes_hi = *right_child.hash();
         Self::Node {
-            hash: calculate_node_hash(&bytes_lo, &bytes_hi),
+            hash: calculate_node_hash::<H>(&bytes_lo, &bytes_hi),
             height,
             prefix: Prefix::Node,
             bytes_lo,
@@ -95,7 +99,7 @@ impl Node {
         }
     }
 
-    pub fn create_node_on_path(
+    pub fn create_node_on_path<H: MerkleHash>(
         path: &dyn Path,
         path_node: &Node,
         side_node: &Node,
@@ -110,10 +114,10 @@ impl Node {
             let parent_height = Node::max_height() - parent_depth;
             match path.get_instruction(parent_depth).unwrap() {
                 Instruction::Left => {
-                    Node::create_node(path_node, side_node, parent_height)
+                    Node::create_node::<H>(path_node, side_node, parent_height)
                 }
                 Instruction::Right => {
-                    Node::create_node(side_node, path_node, parent_height)
+                    Node::create_node::<H>(side_node, path_node, parent_height)
                 }
             }
         } else {
@@ -125,10 +129,10 @@ impl Node {
             let parent_depth = Node::max_height() - parent_height;
             match path.get_instruction(parent_depth).unwrap() {
                 Instruction::Left => {
-                    Node::create_node(path_node, side_node, parent_height)
+                    Node::create_node::<H>(path_node, side_node, parent_height)
                 }
                 Instruction::Right => {
-                    Node::create_node(side_node, path_node, parent_height)
+                    Node::create_node::<H>(side_node, path_node, parent_height)
                 }
             }
         }
@@ -149,7 +153,7 @@ impl Node {
         if self.is_placeholder() || other.is_placeholder() {
             0
         } else {
-            self.leaf_key().common_path_length(other.leaf_key())
+            bytes32_common_prefix_count(self.leaf_key(), other.leaf_key())
         }
     }
 
@@ -302,19 +306,6 @@ impl From<&Node> for Primitive {
     }
 }
 
-impl TryFrom<Primitive> for Node {
-    type Error = DeserializeError;
-
-    fn try_from(primitive: Primitive) -> Result<Self, Self::Error> {
-        let height = primitive.height();
-        let prefix = primitive.prefix()?;
-        let bytes_lo = *primitive.bytes_lo();
-        let bytes_hi = *primitive.bytes_hi();
-        let node = Self::new(height, prefix, bytes_lo, bytes_hi);
-        Ok(node)
-    }
-}
-
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_node() {
@@ -419,9 +410,7 @@ where
             .get(key)
             .map_err(StorageNodeError::StorageError)?
             .ok_or(ChildError::ChildNotFound(*key))?;
-        Ok(primitive
-            .into_owned()
-            .try_into()
+        Ok(Node::from_primitive(*key, primitive.into_owned())
             .map(|node| Self::new(self.storage, node))
             .map_err(StorageNodeError::DeserializeError)?)
     }
@@ -439,9 +428,7 @@ where
             .get(key)
             .map_err(StorageNodeError::StorageError)?
             .ok_or(ChildError::ChildNotFound(*key))?;
-        Ok(primitive
-            .into_owned()
-            .try_into()
+        Ok(Node::from_primitive(*key, primitive.into_owned())
             .map(|node| Self::new(self.storage, node))
             .map_err(StorageNodeError::DeserializeError)?)
     }
@@ -481,6 +468,7 @@ mod test_node {
             Bytes32,
             Prefix,
             PrefixError,
+            Sha256,
         },
         sparse::{
             zero_sum,
@@ -498,7 +486,7 @@ mod test_node {
 
     #[test]
     fn test_create_leaf_returns_a_valid_leaf() {
-        let leaf = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256, _>(&sum(b"LEAF"), [1u8; 32]);
         assert_eq!(leaf.is_leaf(), true);
         assert_eq!(leaf.is_node(), false);
         assert_eq!(leaf.height(), 0);
@@ -509,9 +497,9 @@ mod test_node {
 
     #[test]
     fn test_create_node_returns_a_valid_node() {
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256, _>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256, _>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         assert_eq!(node.is_leaf(), false);
         assert_eq!(node.is_node(), true);
         assert_eq!(node.height(), 1);
@@ -537,7 +525,8 @@ mod test_node {
     fn test_create_leaf_from_primitive_returns_a_valid_leaf() {
         let primitive = (0, Prefix::Leaf as u8, [0xff; 32], [0xff; 32]);
 
-        let node: Node = primitive.try_into().unwrap();
+        let node = Node::from_primitive([0xee; 32], primitive).unwrap();
+        assert_eq!(*node.hash(), [0xee; 32]);
         assert_eq!(node.is_leaf(), true);
         assert_eq!(node.is_node(), false);
         assert_eq!(node.height(), 0);
@@ -550,7 +539,8 @@ mod test_node {
     fn test_create_node_from_primitive_returns_a_valid_node() {
         let primitive = (255, Prefix::Node as u8, [0xff; 32], [0xff; 32]);
 
-        let node: Node = primitive.try_into().unwrap();
+        let node = Node::from_primitive([0xee; 32], primitive).unwrap();
+        assert_eq!(*node.hash(), [0xee; 32]);
         assert_eq!(node.is_leaf(), false);
         assert_eq!(node.is_node(), true);
         assert_eq!(node.height(), 255);
@@ -564,8 +554,8 @@ mod test_node {
         let primitive = (0xff, 0xff, [0xff; 32], [0xff; 32]);
 
         // Should return Error; prefix 0xff is does not represent a node or leaf
-        let err = Node::try_from(primitive)
-            .expect_err("Expected try_from() to be Error; got OK");
+        let err = Node::from_primitive([0xee; 32], primitive)
+            .expect_err("Expected from_primitive() to be Error; got OK");
         assert!(matches!(
             err,
             DeserializeError::PrefixError(PrefixError::InvalidPrefix(0xff))
@@ -579,7 +569,7 @@ mod test_node {
         let expected_primitive =
             (0_u32, Prefix::Leaf as u8, sum(b"LEAF"), sum([1u8; 32]));
 
-        let leaf = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256, _>(&sum(b"LEAF"), [1u8; 32]);
         let primitive = Primitive::from(&leaf);
 
         assert_eq!(primitive, expected_primitive);
@@ -596,9 +586,9 @@ mod test_node {
             leaf_hash(&sum(b"RIGHT CHILD"), &[1u8; 32]),
         );
 
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256, _>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256, _>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         let primitive = Primitive::from(&node);
 
         assert_eq!(primitive, expected_primitive);
@@ -614,7 +604,7 @@ mod test_node {
         expected_buffer[33..65].clone_from_slice(&sum([1u8; 32]));
         let expected_value = sum(expected_buffer);
 
-        let node = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let node = Node::create_leaf::<Sha256, _>(&sum(b"LEAF"), [1u8; 32]);
         let value = *node.hash();
 
         assert_eq!(value, expected_value);
@@ -632,9 +622,9 @@ mod test_node {
             .clone_from_slice(&leaf_hash(&sum(b"RIGHT CHILD"), &[1u8; 32]));
         let expected_value = sum(expected_buffer);
 
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256, _>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256, _>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         let value = *node.hash();
 
         assert_eq!(value, expected_value);
@@ -658,6 +648,7 @@ mod test_storage_node {
             sum,
             Bytes32,
             PrefixError,
+            Sha256,
             StorageMap,
         },
         sparse::Primitive,
@@ -680,13 +671,13 @@ mod test_storage_node {
     fn test_node_left_child_returns_the_left_child() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &leaf_0.as_ref().into());
 
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &leaf_1.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
         let storage_node = StorageNode::new(&s, node_0);
@@ -699,13 +690,13 @@ mod test_storage_node {
     fn test_node_right_child_returns_the_right_child() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &leaf_0.as_ref().into());
 
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &leaf_1.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
         let storage_node = StorageNode::new(&s, node_0);
@@ -718,10 +709,10 @@ mod test_storage_node {
     fn test_node_left_child_returns_placeholder_when_key_is_zero_sum() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf.hash(), &leaf.as_ref().into());
 
-        let node_0 = Node::create_node(&Node::create_placeholder(), &leaf, 1);
+        let node_0 = Node::create_node::<Sha256>(&Node::create_placeholder(), &leaf, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
         let storage_node = StorageNode::new(&s, node_0);
@@ -734,10 +725,10 @@ mod test_storage_node {
     fn test_node_right_child_returns_placeholder_when_key_is_zero_sum() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf.hash(), &leaf.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf, &Node::create_placeholder(), 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf, &Node::create_placeholder(), 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
         let storage_node = StorageNode::new(&s, node_0);
@@ -750,7 +741,7 @@ mod test_storage_node {
     fn test_node_left_child_returns_error_when_node_is_leaf() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
         let storage_node = StorageNode::new(&s, leaf_0);
         let err = storage_node
             .left_child()
@@ -763,7 +754,7 @@ mod test_storage_node {
     fn test_node_right_child_returns_error_when_node_is_leaf() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
         let storage_node = StorageNode::new(&s, leaf_0);
         let err = storage_node
             .right_child()
@@ -776,9 +767,9 @@ mod test_storage_node {
     fn test_node_left_child_returns_error_when_key_is_not_found() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [0u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [0u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
         let storage_node = StorageNode::new(&s, node_0);
         let err = storage_node
@@ -796,9 +787,9 @@ mod test_storage_node {
     fn test_node_right_child_returns_error_when_key_is_not_found() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
         let storage_node = StorageNode::new(&s, node_0);
         let err = storage_node
@@ -816,10 +807,10 @@ mod test_storage_node {
     fn test_node_left_child_returns_deserialize_error_when_primitive_is_invalid() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &(0xff, 0xff, [0xff; 32], [0xff; 32]));
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
         let storage_node = StorageNode::new(&s, node_0);
         let err = storage_node
@@ -838,10 +829,10 @@ mod test_storage_node {
     fn test_node_right_child_returns_deserialize_error_when_primitive_is_invalid() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256, _>(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256, _>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &(0xff, 0xff, [0xff; 32], [0xff; 32]));
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
         let storage_node = StorageNode::new(&s, node_0);
         let err = storage_node
@@ -4,9 +4,10 @@ use crate::{
             Instruction,
             Path,
         },
-        sum,
         Bytes32,
+        MerkleHash,
         ProofSet,
+        Sha256,
     },
     sparse::{
         hash::{
@@ -25,6 +26,7 @@ use core::{
 };
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Proof {
     Inclusion(InclusionProof),
     Exclusion(ExclusionProof),
@@ -50,26 +52,50 @@ impl Proof {
     }
 }
 
+/// The proof that a key is in a sparse Merkle tree.
+///
+/// # Encoding
+///
+/// [`InclusionProof::to_bytes`] encodes the proof as:
+///
+/// - the number of side nodes, as a big-endian `u16` of at most 256;
+/// - the 32 bytes side nodes, in the order of the proof set, from the leaf to the root.
+///
+/// The side of each side node is given by the bit of the key at its depth, so the
+/// encoding doesn't repeat it. The encoding is canonical:
+/// [`InclusionProof::from_bytes`] rejects any other byte string, including the valid
+/// encodings followed by more bytes.
 #[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InclusionProof {
     pub proof_set: ProofSet,
 }
 
 impl InclusionProof {
     pub fn verify(&self, root: &Bytes32, key: &MerkleTreeKey, value: &[u8]) -> bool {
+        self.verify_with_hash::<Sha256>(root, key, value)
+    }
+
+    /// Verifies the proof, like [`InclusionProof::verify`], for a tree hashed with `H`.
+    pub fn verify_with_hash<H: MerkleHash>(
+        &self,
+        root: &Bytes32,
+        key: &MerkleTreeKey,
+        value: &[u8],
+    ) -> bool {
         let Self { proof_set } = self;
 
         if proof_set.len() > 256usize {
             return false;
         }
 
-        let mut current = calculate_leaf_hash(key, &sum(value));
+        let mut current = calculate_leaf_hash::<H>(key, &H::hash(&[value]));
         for (i, side_hash) in proof_set.iter().enumerate() {
             let index =
                 u32::try_from(proof_set.len() - 1 - i).expect("We've checked it above");
             current = match key.get_instruction(index).expect("Infallible") {
-                Instruction::Left => calculate_node_hash(&current, side_hash),
-                Instruction::Right => calculate_node_hash(side_hash, &current),
+                Instruction::Left => calculate_node_hash::<H>(&current, side_hash),
+                Instruction::Right => calculate_node_hash::<H>(side_hash, &current),
             };
         }
         current == *root
@@ -86,12 +112,14 @@ impl Debug for InclusionProof {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExclusionLeaf {
     Leaf(ExclusionLeafData),
     Placeholder,
 }
 
 #[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExclusionLeafData {
     /// The leaf key.
     pub leaf_key: Bytes32,
@@ -109,24 +137,198 @@ impl Debug for ExclusionLeafData {
 }
 
 impl ExclusionLeaf {
-    fn hash(&self) -> Bytes32 {
+    fn hash<H: MerkleHash>(&self) -> Bytes32 {
         match self {
             ExclusionLeaf::Leaf(data) => {
-                calculate_leaf_hash(&data.leaf_key, &data.leaf_value)
+                calculate_leaf_hash::<H>(&data.leaf_key, &data.leaf_value)
             }
             ExclusionLeaf::Placeholder => *zero_sum(),
         }
     }
 }
 
+/// The proof that a key is not in a sparse Merkle tree.
+///
+/// # Encoding
+///
+/// [`ExclusionProof::to_bytes`] encodes the proof as:
+///
+/// - the tag of the leaf, `0x00` for a placeholder, or `0x01` for a leaf followed by the
+///   32 bytes key and the 32 bytes value hash of the leaf;
+/// - the number of side nodes, as a big-endian `u16` of at most 256;
+/// - the 32 bytes side nodes, in the order of the proof set, from the leaf to the root.
+///
+/// The encoding is canonical: [`ExclusionProof::from_bytes`] rejects any other byte
+/// string, including the valid encodings followed by more bytes.
 #[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExclusionProof {
     pub proof_set: ProofSet,
     pub leaf: ExclusionLeaf,
 }
 
+/// The failure to decode a [`Proof`], an [`InclusionProof`] or an [`ExclusionProof`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum ProofDecodeError {
+    #[display(fmt = "the encoded proof ends unexpectedly")]
+    UnexpectedEnd,
+    #[display(fmt = "{:#04x} is not a proof tag", _0)]
+    InvalidProofTag(u8),
+    #[display(fmt = "{:#04x} is not a leaf tag", _0)]
+    InvalidLeafTag(u8),
+    #[display(fmt = "the proof set has {} side nodes, more than 256", _0)]
+    ProofSetTooLong(u16),
+    #[display(fmt = "the encoded proof is followed by more bytes")]
+    TrailingBytes,
+}
+
+const MAX_PROOF_SET_LEN: u16 = 256;
+
+fn take<'a, const N: usize>(
+    bytes: &mut &'a [u8],
+) -> Result<&'a [u8; N], ProofDecodeError> {
+    if bytes.len() < N {
+        return Err(ProofDecodeError::UnexpectedEnd)
+    }
+    let (head, tail) = bytes.split_at(N);
+    *bytes = tail;
+    Ok(head.try_into().expect("The length was checked above"))
+}
+
+fn encode_proof_set(bytes: &mut Vec<u8>, proof_set: &ProofSet) {
+    let len = u16::try_from(proof_set.len()).unwrap_or(u16::MAX);
+    bytes.extend_from_slice(&len.to_be_bytes());
+    proof_set
+        .iter()
+        .for_each(|side_node| bytes.extend_from_slice(side_node));
+}
+
+fn decode_proof_set(bytes: &mut &[u8]) -> Result<ProofSet, ProofDecodeError> {
+    let len = u16::from_be_bytes(*take::<2>(bytes)?);
+    if len > MAX_PROOF_SET_LEN {
+        return Err(ProofDecodeError::ProofSetTooLong(len))
+    }
+    (0..len).map(|_| take::<32>(bytes).copied()).collect()
+}
+
+fn decode_all<T>(
+    bytes: &[u8],
+    decode: impl FnOnce(&mut &[u8]) -> Result<T, ProofDecodeError>,
+) -> Result<T, ProofDecodeError> {
+    let mut bytes = bytes;
+    let decoded = decode(&mut bytes)?;
+    if !bytes.is_empty() {
+        return Err(ProofDecodeError::TrailingBytes)
+    }
+    Ok(decoded)
+}
+
+impl Proof {
+    const EXCLUSION_TAG: u8 = 0x01;
+    const INCLUSION_TAG: u8 = 0x00;
+
+    /// Encodes the proof as the tag of its kind, `0x00` for an inclusion proof or
+    /// `0x01` for an exclusion proof, followed by the encoding of the
+    /// [`InclusionProof`] or the [`ExclusionProof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + 1 + 64 + 2 + 32 * self.proof_set().len());
+        match self {
+            Proof::Inclusion(proof) => {
+                bytes.push(Self::INCLUSION_TAG);
+                proof.encode(&mut bytes);
+            }
+            Proof::Exclusion(proof) => {
+                bytes.push(Self::EXCLUSION_TAG);
+                proof.encode(&mut bytes);
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a proof encoded by [`Proof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        decode_all(bytes, |bytes| match take::<1>(bytes)?[0] {
+            Self::INCLUSION_TAG => InclusionProof::decode(bytes).map(Proof::Inclusion),
+            Self::EXCLUSION_TAG => ExclusionProof::decode(bytes).map(Proof::Exclusion),
+            tag => Err(ProofDecodeError::InvalidProofTag(tag)),
+        })
+    }
+}
+
+impl InclusionProof {
+    /// Encodes the proof, as documented by [`InclusionProof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 + 32 * self.proof_set.len());
+        self.encode(&mut bytes);
+        bytes
+    }
+
+    /// Decodes a proof encoded by [`InclusionProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        decode_all(bytes, Self::decode)
+    }
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_proof_set(bytes, &self.proof_set);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, ProofDecodeError> {
+        let proof_set = decode_proof_set(bytes)?;
+        Ok(Self { proof_set })
+    }
+}
+
 impl ExclusionProof {
+    const LEAF_TAG: u8 = 0x01;
+    const PLACEHOLDER_TAG: u8 = 0x00;
+
+    /// Encodes the proof, as documented by [`ExclusionProof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + 64 + 2 + 32 * self.proof_set.len());
+        self.encode(&mut bytes);
+        bytes
+    }
+
+    /// Decodes a proof encoded by [`ExclusionProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        decode_all(bytes, Self::decode)
+    }
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match &self.leaf {
+            ExclusionLeaf::Leaf(data) => {
+                bytes.push(Self::LEAF_TAG);
+                bytes.extend_from_slice(&data.leaf_key);
+                bytes.extend_from_slice(&data.leaf_value);
+            }
+            ExclusionLeaf::Placeholder => bytes.push(Self::PLACEHOLDER_TAG),
+        }
+        encode_proof_set(bytes, &self.proof_set);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, ProofDecodeError> {
+        let leaf = match take::<1>(bytes)?[0] {
+            Self::PLACEHOLDER_TAG => ExclusionLeaf::Placeholder,
+            Self::LEAF_TAG => ExclusionLeaf::Leaf(ExclusionLeafData {
+                leaf_key: *take::<32>(bytes)?,
+                leaf_value: *take::<32>(bytes)?,
+            }),
+            tag => return Err(ProofDecodeError::InvalidLeafTag(tag)),
+        };
+        let proof_set = decode_proof_set(bytes)?;
+        Ok(Self { proof_set, leaf })
+    }
+
     pub fn verify(&self, root: &Bytes32, key: &MerkleTreeKey) -> bool {
+        self.verify_with_hash::<Sha256>(root, key)
+    }
+
+    /// Verifies the proof, like [`ExclusionProof::verify`], for a tree hashed with `H`.
+    pub fn verify_with_hash<H: MerkleHash>(
+        &self,
+        root: &Bytes32,
+        key: &MerkleTreeKey,
+    ) -> bool {
         let Self { proof_set, leaf } = self;
 
         if let ExclusionLeaf::Leaf(data) = leaf {
@@ -139,19 +341,43 @@ impl ExclusionProof {
             return false;
         }
 
-        let mut current = leaf.hash();
+        let mut current = leaf.hash::<H>();
         for (i, side_hash) in proof_set.iter().enumerate() {
             let index =
                 u32::try_from(proof_set.len() - 1 - i).expect("We've checked it above");
             current = match key.get_instruction(index).expect("Infallible") {
-                Instruction::Left => calculate_node_hash(&current, side_hash),
-                Instruction::Right => calculate_node_hash(side_hash, &current),
+                Instruction::Left => calculate_node_hash::<H>(&current, side_hash),
+                Instruction::Right => calculate_node_hash::<H>(side_hash, &current),
             };
         }
         current == *root
     }
 }
 
+/// Verifies that the `key` is not in the sparse Merkle tree of the `root`, with the
+/// `proof` generated by [`MerkleTree::generate_exclusion_proof`].
+///
+/// Fails if the key is in the tree, even with a proof of a different tree.
+///
+/// [`MerkleTree::generate_exclusion_proof`]: crate::sparse::MerkleTree::generate_exclusion_proof
+pub fn verify_exclusion(
+    root: &Bytes32,
+    key: &MerkleTreeKey,
+    proof: &ExclusionProof,
+) -> bool {
+    proof.verify(root, key)
+}
+
+/// Verifies that the `key` is not in the sparse Merkle tree of the `root`, like
+/// [`verify_exclusion`], for a tree hashed with `H`.
+pub fn verify_exclusion_with_hash<H: MerkleHash>(
+    root: &Bytes32,
+    key: &MerkleTreeKey,
+    proof: &ExclusionProof,
+) -> bool {
+    proof.verify_with_hash::<H>(root, key)
+}
+
 impl Debug for ExclusionProof {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let proof_set = self.proof_set.iter().map(hex::encode).collect::<Vec<_>>();
@@ -167,15 +393,24 @@ impl Debug for ExclusionProof {
 mod test {
     use crate::{
         common::{
+            sum,
             Bytes32,
             StorageMap,
         },
         sparse::{
-            proof::Proof,
+            proof::{
+                ExclusionLeaf,
+                ExclusionLeafData,
+                ExclusionProof,
+                Proof,
+                ProofDecodeError,
+            },
+            verify_exclusion,
             MerkleTree,
             Primitive,
         },
     };
+    use alloc::vec;
     use fuel_storage::Mappable;
 
     #[derive(Debug)]
@@ -605,6 +840,188 @@ mod test {
         // Then
         assert!(exclusion);
     }
+
+    #[test]
+    fn generate_exclusion_proof__verify_exclusion__returns_true_in_empty_tree() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = MerkleTree::new(&mut storage);
+        let root = tree.root();
+
+        // Given
+        let key = [0x42u8; 32].into();
+        let proof = tree
+            .generate_exclusion_proof(&key)
+            .unwrap()
+            .expect("Expected ExclusionProof");
+
+        // When
+        let exclusion = verify_exclusion(&root, &key, &proof);
+
+        // Then
+        assert!(exclusion);
+        assert_eq!(proof.leaf, ExclusionLeaf::Placeholder);
+        assert!(proof.proof_set.is_empty());
+    }
+
+    #[test]
+    fn generate_exclusion_proof__verify_exclusion__returns_true_next_to_key_with_shared_prefix(
+    ) {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+
+        let k0 = [0u8; 32];
+        tree.update(k0.into(), b"DATA_0")
+            .expect("Expected successful update");
+
+        let mut k1 = [0xffu8; 32];
+        k1[31] = 0b11111110;
+        tree.update(k1.into(), b"DATA_1")
+            .expect("Expected successful update");
+
+        let root = tree.root();
+
+        // Given
+        // - The key shares the first 255 bits of `k1`
+        let key = [0xffu8; 32].into();
+        let proof = tree
+            .generate_exclusion_proof(&key)
+            .unwrap()
+            .expect("Expected ExclusionProof");
+
+        // When
+        let exclusion = verify_exclusion(&root, &key, &proof);
+
+        // Then
+        assert!(exclusion);
+        assert!(matches!(
+            &proof.leaf,
+            ExclusionLeaf::Leaf(data) if data.leaf_key == k1
+        ));
+    }
+
+    #[test]
+    fn generate_exclusion_proof__returns_none_for_included_key() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+
+        let key = [0x42u8; 32];
+        tree.update(key.into(), b"DATA")
+            .expect("Expected successful update");
+
+        // When
+        let proof = tree.generate_exclusion_proof(&key.into()).unwrap();
+
+        // Then
+        assert_eq!(proof, None);
+    }
+
+    #[test]
+    fn verify_exclusion__returns_false_for_included_key() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+
+        let k0 = [0u8; 32];
+        tree.update(k0.into(), b"DATA_0")
+            .expect("Expected successful update");
+
+        // Given
+        // - An exclusion proof generated before inserting the key
+        let key = [0xffu8; 32];
+        let stale_proof = tree
+            .generate_exclusion_proof(&key.into())
+            .unwrap()
+            .expect("Expected ExclusionProof");
+        tree.update(key.into(), b"DATA_1")
+            .expect("Expected successful update");
+        let root = tree.root();
+        // - A proof claiming the key is its own closest leaf
+        let forged_proof = ExclusionProof {
+            proof_set: stale_proof.proof_set.clone(),
+            leaf: ExclusionLeaf::Leaf(ExclusionLeafData {
+                leaf_key: key,
+                leaf_value: sum(b"DATA_1"),
+            }),
+        };
+
+        // When
+        let stale = verify_exclusion(&root, &key.into(), &stale_proof);
+        let forged = verify_exclusion(&root, &key.into(), &forged_proof);
+
+        // Then
+        assert!(!stale);
+        assert!(!forged);
+    }
+
+    #[test]
+    fn exclusion_proof__from_bytes__decodes_the_encoded_proof() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for byte in 0..16u8 {
+            tree.update([byte; 32].into(), &[byte])
+                .expect("Expected successful update");
+        }
+        let root = tree.root();
+
+        for key in [[0x80u8; 32], [0x10u8; 32], [0xffu8; 32]] {
+            // Given
+            let proof = tree
+                .generate_exclusion_proof(&key.into())
+                .unwrap()
+                .expect("Expected ExclusionProof");
+
+            // When
+            let bytes = proof.to_bytes();
+            let decoded = ExclusionProof::from_bytes(&bytes);
+
+            // Then
+            assert_eq!(decoded, Ok(proof.clone()));
+            let leaf_len = match proof.leaf {
+                ExclusionLeaf::Leaf(_) => 1 + 64,
+                ExclusionLeaf::Placeholder => 1,
+            };
+            assert_eq!(bytes.len(), leaf_len + 2 + 32 * proof.proof_set.len());
+            assert!(verify_exclusion(&root, &key.into(), &decoded.unwrap()));
+        }
+    }
+
+    #[test]
+    fn exclusion_proof__from_bytes__rejects_non_canonical_encodings() {
+        let proof = ExclusionProof {
+            proof_set: vec![[1u8; 32], [2u8; 32]],
+            leaf: ExclusionLeaf::Placeholder,
+        };
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes[..3], [0x00, 0x00, 0x02]);
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            ExclusionProof::from_bytes(&trailing),
+            Err(ProofDecodeError::TrailingBytes)
+        );
+
+        assert_eq!(
+            ExclusionProof::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ProofDecodeError::UnexpectedEnd)
+        );
+        assert_eq!(
+            ExclusionProof::from_bytes(&[]),
+            Err(ProofDecodeError::UnexpectedEnd)
+        );
+
+        let mut tag = bytes.clone();
+        tag[0] = 0x02;
+        assert_eq!(
+            ExclusionProof::from_bytes(&tag),
+            Err(ProofDecodeError::InvalidLeafTag(0x02))
+        );
+
+        let too_long = [0x00, 0x01, 0x01];
+        assert_eq!(
+            ExclusionProof::from_bytes(&too_long),
+            Err(ProofDecodeError::ProofSetTooLong(257))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,417 @@
+use crate::{
+    common::Bytes32,
+    sparse::Primitive,
+    storage::{
+        Mappable,
+        StorageInspect,
+        StorageMutate,
+    },
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::marker::PhantomData;
+
+/// The length of the encoding of a node by [`encode_node`].
+pub const ENCODED_NODE_LEN: usize = 69;
+
+/// Encodes the node, as the buffer documented by [`Primitive`], with the height as a
+/// big-endian `u32`.
+pub fn encode_node(primitive: &Primitive) -> [u8; ENCODED_NODE_LEN] {
+    let (height, prefix, bytes_lo, bytes_hi) = primitive;
+    let mut bytes = [0; ENCODED_NODE_LEN];
+    bytes[0..4].copy_from_slice(&height.to_be_bytes());
+    bytes[4] = *prefix;
+    bytes[5..37].copy_from_slice(bytes_lo);
+    bytes[37..69].copy_from_slice(bytes_hi);
+    bytes
+}
+
+/// Decodes a node encoded by [`encode_node`]. Returns `None` if the `bytes` aren't
+/// [`ENCODED_NODE_LEN`] bytes long. The prefix is checked when the tree reads the node.
+pub fn decode_node(bytes: &[u8]) -> Option<Primitive> {
+    let bytes: &[u8; ENCODED_NODE_LEN] = bytes.try_into().ok()?;
+    let height = u32::from_be_bytes(bytes[0..4].try_into().expect("4 bytes"));
+    let bytes_lo = bytes[5..37].try_into().expect("32 bytes");
+    let bytes_hi = bytes[37..69].try_into().expect("32 bytes");
+    Some((height, bytes[4], bytes_lo, bytes_hi))
+}
+
+#[derive(Debug, Clone, derive_more::Display)]
+pub enum MerkleStorageAdapterError<StorageError> {
+    #[display(fmt = "{}", _0)]
+    StorageError(StorageError),
+
+    #[display(
+        fmt = "the value of the node with key {} is not an encoded node",
+        "hex::encode(_0)"
+    )]
+    InvalidNode(Bytes32),
+}
+
+impl<StorageError> From<StorageError> for MerkleStorageAdapterError<StorageError> {
+    fn from(err: StorageError) -> MerkleStorageAdapterError<StorageError> {
+        MerkleStorageAdapterError::StorageError(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingWrite {
+    Insert(Primitive),
+    Remove,
+}
+
+/// The storage of the nodes of a sparse Merkle tree on top of any `StorageType` of the
+/// `TableType`, which maps the hash of each node to its encoding by [`encode_node`].
+///
+/// The adapter implements [`StorageInspect`] and [`StorageMutate`] for any table of
+/// nodes, like [`NodesTable`](crate::sparse::in_memory::NodesTable), so a
+/// [`MerkleTree`](crate::sparse::MerkleTree) can be built on it, usually with
+/// `&mut MerkleStorageAdapter` to keep the adapter after the tree.
+///
+/// The writes of the tree are batched: they are kept in the adapter, which reads them
+/// back before the `StorageType`, until [`MerkleStorageAdapter::commit`] writes them
+/// in key order, or [`MerkleStorageAdapter::discard`] drops them. The nodes that the
+/// tree removes when its root changes are the orphans of the previous roots.
+/// [`MerkleStorageAdapter::commit_with`] lets a hook decide which ones are deleted
+/// from the `StorageType`, for example to keep the nodes of the previous roots.
+#[derive(Debug, Clone)]
+pub struct MerkleStorageAdapter<StorageType, TableType> {
+    storage: StorageType,
+    pending: BTreeMap<Bytes32, PendingWrite>,
+    phantom_table: PhantomData<TableType>,
+}
+
+impl<StorageType, TableType> MerkleStorageAdapter<StorageType, TableType> {
+    pub fn new(storage: StorageType) -> Self {
+        Self {
+            storage,
+            pending: Default::default(),
+            phantom_table: Default::default(),
+        }
+    }
+
+    pub fn storage(&self) -> &StorageType {
+        &self.storage
+    }
+
+    /// Returns the `StorageType`, dropping the writes that aren't committed.
+    pub fn into_storage(self) -> StorageType {
+        self.storage
+    }
+
+    /// Returns the number of the nodes written or removed since the last commit.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Drops the writes since the last commit. The trees built on the adapter since
+    /// then must be loaded again from a committed root.
+    pub fn discard(&mut self) {
+        self.pending.clear();
+    }
+}
+
+impl<StorageType, TableType> MerkleStorageAdapter<StorageType, TableType>
+where
+    TableType:
+        Mappable<Key = Bytes32, OwnedKey = Bytes32, Value = [u8], OwnedValue = Vec<u8>>,
+    StorageType: StorageMutate<TableType>,
+{
+    /// Writes the batch to the `StorageType` and deletes all its orphans.
+    pub fn commit(&mut self) -> Result<(), StorageType::Error> {
+        self.commit_with(|_| true)
+    }
+
+    /// Writes the batch to the `StorageType`, in key order, and deletes the orphans for
+    /// which `prune` returns `true`. The orphans are the keys of the nodes removed by the
+    /// tree since the last commit. Deleting one that the `StorageType` doesn't hold, as
+    /// a node inserted and removed in the same batch, does nothing.
+    ///
+    /// If a write fails, the writes from the failed one on stay in the batch, so
+    /// committing again resumes it.
+    pub fn commit_with<F>(&mut self, mut prune: F) -> Result<(), StorageType::Error>
+    where
+        F: FnMut(&Bytes32) -> bool,
+    {
+        while let Some((key, write)) = self.pending.pop_first() {
+            let result = match write {
+                PendingWrite::Insert(primitive) => self
+                    .storage
+                    .insert(&key, &encode_node(&primitive))
+                    .map(|_| ()),
+                PendingWrite::Remove if prune(&key) => {
+                    self.storage.remove(&key).map(|_| ())
+                }
+                PendingWrite::Remove => Ok(()),
+            };
+            if let Err(err) = result {
+                self.pending.insert(key, write);
+                return Err(err)
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<StorageType, TableType, NodesTableType> StorageInspect<NodesTableType>
+    for MerkleStorageAdapter<StorageType, TableType>
+where
+    NodesTableType: Mappable<
+        Key = Bytes32,
+        OwnedKey = Bytes32,
+        Value = Primitive,
+        OwnedValue = Primitive,
+    >,
+    TableType:
+        Mappable<Key = Bytes32, OwnedKey = Bytes32, Value = [u8], OwnedValue = Vec<u8>>,
+    StorageType: StorageInspect<TableType>,
+{
+    type Error = MerkleStorageAdapterError<StorageType::Error>;
+
+    fn get(&self, key: &Bytes32) -> Result<Option<Cow<Primitive>>, Self::Error> {
+        match self.pending.get(key) {
+            Some(PendingWrite::Insert(primitive)) => Ok(Some(Cow::Borrowed(primitive))),
+            Some(PendingWrite::Remove) => Ok(None),
+            None => self
+                .storage
+                .get(key)?
+                .map(|bytes| {
+                    decode_node(&bytes)
+                        .map(Cow::Owned)
+                        .ok_or(MerkleStorageAdapterError::InvalidNode(*key))
+                })
+                .transpose(),
+        }
+    }
+
+    fn contains_key(&self, key: &Bytes32) -> Result<bool, Self::Error> {
+        match self.pending.get(key) {
+            Some(PendingWrite::Insert(_)) => Ok(true),
+            Some(PendingWrite::Remove) => Ok(false),
+            None => Ok(self.storage.contains_key(key)?),
+        }
+    }
+}
+
+impl<StorageType, TableType, NodesTableType> StorageMutate<NodesTableType>
+    for MerkleStorageAdapter<StorageType, TableType>
+where
+    NodesTableType: Mappable<
+        Key = Bytes32,
+        OwnedKey = Bytes32,
+        Value = Primitive,
+        OwnedValue = Primitive,
+    >,
+    TableType:
+        Mappable<Key = Bytes32, OwnedKey = Bytes32, Value = [u8], OwnedValue = Vec<u8>>,
+    StorageType: StorageInspect<TableType>,
+{
+    fn insert(
+        &mut self,
+        key: &Bytes32,
+        value: &Primitive,
+    ) -> Result<Option<Primitive>, Self::Error> {
+        let previous =
+            StorageInspect::<NodesTableType>::get(self, key)?.map(Cow::into_owned);
+        self.pending.insert(*key, PendingWrite::Insert(*value));
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &Bytes32) -> Result<Option<Primitive>, Self::Error> {
+        let previous =
+            StorageInspect::<NodesTableType>::get(self, key)?.map(Cow::into_owned);
+        self.pending.insert(*key, PendingWrite::Remove);
+        Ok(previous)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::{
+        decode_node,
+        encode_node,
+        MerkleStorageAdapter,
+        MerkleStorageAdapterError,
+    };
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sparse::{
+            in_memory::NodesTable,
+            MerkleTree,
+            MerkleTreeError,
+            MerkleTreeKey,
+        },
+        storage::{
+            Mappable,
+            StorageInspect,
+            StorageMutate,
+        },
+    };
+    use alloc::vec::Vec;
+
+    struct EncodedNodesTable;
+
+    impl Mappable for EncodedNodesTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Vec<u8>;
+        type Value = [u8];
+    }
+
+    type Adapter = MerkleStorageAdapter<StorageMap<EncodedNodesTable>, EncodedNodesTable>;
+
+    fn key(i: u8) -> MerkleTreeKey {
+        MerkleTreeKey::new_without_hash([i; 32])
+    }
+
+    #[test]
+    fn decode_node__returns_the_encoded_node() {
+        // Given
+        let primitive = (0x01020304, 0x01, [0xaa; 32], [0xbb; 32]);
+
+        // When
+        let encoded = encode_node(&primitive);
+
+        // Then
+        assert_eq!(&encoded[0..5], &[0x01, 0x02, 0x03, 0x04, 0x01]);
+        assert_eq!(decode_node(&encoded), Some(primitive));
+        assert_eq!(decode_node(&encoded[..68]), None);
+        assert_eq!(decode_node(&[encoded.as_slice(), &[0]].concat()), None);
+    }
+
+    #[test]
+    fn merkle_storage_adapter__commit__writes_the_nodes_of_the_in_memory_tree() {
+        // Given
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut expected = MerkleTree::<NodesTable, _>::new(StorageMap::new());
+
+        // When
+        let mut tree = MerkleTree::<NodesTable, _>::new(&mut adapter);
+        for i in 0..20 {
+            tree.update(key(i), &[i; 8]).unwrap();
+            expected.update(key(i), &[i; 8]).unwrap();
+        }
+        let root = tree.root();
+        let stored_before_commit = adapter.storage().len();
+        adapter.commit().unwrap();
+
+        // Then
+        assert_eq!(root, expected.root());
+        assert_eq!(stored_before_commit, 0);
+        assert_eq!(adapter.pending_len(), 0);
+        assert_eq!(adapter.storage().len(), expected.storage().len());
+        let tree = MerkleTree::<NodesTable, _>::load(
+            Adapter::new(adapter.into_storage()),
+            &root,
+        )
+        .unwrap();
+        assert_eq!(tree.root(), root);
+    }
+
+    #[test]
+    fn merkle_storage_adapter__commit__deletes_the_orphans_of_the_previous_root() {
+        // Given
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut tree = MerkleTree::<NodesTable, _>::new(&mut adapter);
+        for i in 0..4 {
+            tree.update(key(i), b"DATA").unwrap();
+        }
+        let old_root = tree.root();
+        adapter.commit().unwrap();
+        let stored_len = adapter.storage().len();
+
+        // When
+        let mut tree =
+            MerkleTree::<NodesTable, _>::load(&mut adapter, &old_root).unwrap();
+        tree.update(key(0), b"CHANGED").unwrap();
+        let new_root = tree.root();
+        adapter.commit().unwrap();
+
+        // Then
+        assert_ne!(new_root, old_root);
+        assert_eq!(adapter.storage().len(), stored_len);
+        let old_tree = MerkleTree::<NodesTable, _>::load(&mut adapter, &old_root);
+        assert!(matches!(old_tree, Err(MerkleTreeError::LoadError(_))));
+    }
+
+    #[test]
+    fn merkle_storage_adapter__commit_with__keeps_the_orphans_kept_by_the_hook() {
+        // Given
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut tree = MerkleTree::<NodesTable, _>::new(&mut adapter);
+        for i in 0..4 {
+            tree.update(key(i), b"DATA").unwrap();
+        }
+        let old_root = tree.root();
+        adapter.commit().unwrap();
+
+        // When
+        let mut tree =
+            MerkleTree::<NodesTable, _>::load(&mut adapter, &old_root).unwrap();
+        tree.update(key(0), b"CHANGED").unwrap();
+        let new_root = tree.root();
+        let mut orphans = Vec::new();
+        adapter
+            .commit_with(|key| {
+                orphans.push(*key);
+                false
+            })
+            .unwrap();
+
+        // Then
+        assert!(orphans.contains(&old_root));
+        let old_tree =
+            MerkleTree::<NodesTable, _>::load(&mut adapter, &old_root).unwrap();
+        assert_eq!(old_tree.root(), old_root);
+        let new_tree =
+            MerkleTree::<NodesTable, _>::load(&mut adapter, &new_root).unwrap();
+        assert_eq!(new_tree.root(), new_root);
+    }
+
+    #[test]
+    fn merkle_storage_adapter__discard__drops_the_writes_since_the_last_commit() {
+        // Given
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut tree = MerkleTree::<NodesTable, _>::new(&mut adapter);
+        tree.update(key(0), b"DATA").unwrap();
+        let root = tree.root();
+        adapter.commit().unwrap();
+
+        // When
+        let mut tree = MerkleTree::<NodesTable, _>::load(&mut adapter, &root).unwrap();
+        tree.update(key(1), b"DATA").unwrap();
+        adapter.discard();
+
+        // Then
+        assert_eq!(adapter.pending_len(), 0);
+        let tree = MerkleTree::<NodesTable, _>::load(&mut adapter, &root).unwrap();
+        assert_eq!(tree.root(), root);
+    }
+
+    #[test]
+    fn merkle_storage_adapter__get__returns_an_error_for_an_invalid_encoding() {
+        // Given
+        let mut storage = StorageMap::<EncodedNodesTable>::new();
+        StorageMutate::<EncodedNodesTable>::insert(&mut storage, &[1; 32], &[0; 68])
+            .unwrap();
+        let adapter = Adapter::new(storage);
+
+        // When
+        let result = StorageInspect::<NodesTable>::get(&adapter, &[1; 32]);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(MerkleStorageAdapterError::InvalidNode(key)) if key == [1; 32]
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    common::{
+        path::{
+            ComparablePath,
+            Instruction,
+            Path,
+        },
+        Bytes32,
+        MerkleHash,
+        Sha256,
+    },
+    sparse::{
+        hash::{
+            calculate_leaf_hash,
+            calculate_node_hash,
+        },
+        proof::{
+            ExclusionLeaf,
+            Proof,
+        },
+        zero_sum,
+        MerkleTreeKey,
+    },
+};
+
+use core::{
+    fmt,
+    fmt::Debug,
+};
+
+/// The preimage of the first side node of a proof, the sibling of the leaf.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SiblingPreimage {
+    /// The sibling is a leaf.
+    Leaf {
+        /// The leaf key.
+        leaf_key: Bytes32,
+        /// Hash of the value of the leaf.
+        leaf_value: Bytes32,
+    },
+    /// The sibling is an internal node.
+    Node {
+        /// The hash of the left child.
+        left_child: Bytes32,
+        /// The hash of the right child.
+        right_child: Bytes32,
+    },
+}
+
+impl SiblingPreimage {
+    fn hash<H: MerkleHash>(&self) -> Bytes32 {
+        match self {
+            SiblingPreimage::Leaf {
+                leaf_key,
+                leaf_value,
+            } => calculate_leaf_hash::<H>(leaf_key, leaf_value),
+            SiblingPreimage::Node {
+                left_child,
+                right_child,
+            } => calculate_node_hash::<H>(left_child, right_child),
+        }
+    }
+}
+
+impl Debug for SiblingPreimage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiblingPreimage::Leaf {
+                leaf_key,
+                leaf_value,
+            } => f
+                .debug_struct("Leaf")
+                .field("Leaf key", &hex::encode(leaf_key))
+                .field("Leaf value", &hex::encode(leaf_value))
+                .finish(),
+            SiblingPreimage::Node {
+                left_child,
+                right_child,
+            } => f
+                .debug_struct("Node")
+                .field("Left child", &hex::encode(left_child))
+                .field("Right child", &hex::encode(right_child))
+                .finish(),
+        }
+    }
+}
+
+/// The proof of the value of a key, with what [`verify_and_update`] needs to compute
+/// the root of the tree after an update of the key.
+///
+/// Deleting a leaf moves its sibling up when the sibling is a leaf, and the side node
+/// alone doesn't tell if it is a leaf. The `sibling` is the preimage of the first side
+/// node of the inclusion proof, set by [`MerkleTree::generate_update_proof`] when it
+/// isn't a placeholder. It is `None` for an exclusion proof, and updates other than
+/// deletions don't need it.
+///
+/// [`MerkleTree::generate_update_proof`]: crate::sparse::MerkleTree::generate_update_proof
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateProof {
+    pub proof: Proof,
+    pub sibling: Option<SiblingPreimage>,
+}
+
+impl From<Proof> for UpdateProof {
+    fn from(proof: Proof) -> Self {
+        Self {
+            proof,
+            sibling: None,
+        }
+    }
+}
+
+/// The failure of [`verify_and_update`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum ProofError {
+    #[display(fmt = "the proof doesn't prove the old value of the key in the root")]
+    InvalidProof,
+    #[display(fmt = "the deletion needs the preimage of the sibling of the leaf")]
+    MissingSibling,
+    #[display(fmt = "the preimage doesn't hash to the sibling of the leaf")]
+    InvalidSibling,
+}
+
+/// Verifies that the `key` has the `old_value` in the sparse Merkle tree of the
+/// `root`, and returns the root of the tree after the update of the key to the
+/// `new_value`, without the nodes of the tree.
+///
+/// A `None` or empty value is the absence of the key: the `old_value` is checked with
+/// an inclusion proof if it is set and with an exclusion proof otherwise, and a `None`
+/// or empty `new_value` deletes the key, like [`MerkleTree::update`]. The new root is
+/// the root of the tree performing the same update.
+///
+/// [`MerkleTree::update`]: crate::sparse::MerkleTree::update
+pub fn verify_and_update(
+    root: &Bytes32,
+    key: &MerkleTreeKey,
+    old_value: Option<&[u8]>,
+    new_value: Option<&[u8]>,
+    proof: &UpdateProof,
+) -> Result<Bytes32, ProofError> {
+    verify_and_update_with_hash::<Sha256>(root, key, old_value, new_value, proof)
+}
+
+/// Verifies the old value of the key and computes the new root, like
+/// [`verify_and_update`], for a tree hashed with `H`.
+pub fn verify_and_update_with_hash<H: MerkleHash>(
+    root: &Bytes32,
+    key: &MerkleTreeKey,
+    old_value: Option<&[u8]>,
+    new_value: Option<&[u8]>,
+    proof: &UpdateProof,
+) -> Result<Bytes32, ProofError> {
+    let old_value = old_value.filter(|value| !value.is_empty());
+    let new_value = new_value.filter(|value| !value.is_empty());
+    let UpdateProof { proof, sibling } = proof;
+
+    let verified = match (proof, old_value) {
+        (Proof::Inclusion(proof), Some(value)) => {
+            proof.verify_with_hash::<H>(root, key, value)
+        }
+        (Proof::Exclusion(proof), None) => proof.verify_with_hash::<H>(root, key),
+        _ => false,
+    };
+    if !verified {
+        return Err(ProofError::InvalidProof)
+    }
+    let proof_set = proof.proof_set();
+
+    let new_root = match (proof, new_value) {
+        // Overwrite the leaf in place.
+        (Proof::Inclusion(_), Some(value)) => {
+            let leaf = calculate_leaf_hash::<H>(key, &H::hash(&[value]));
+            hash_up::<H>(key, leaf, proof_set.iter().enumerate(), proof_set.len())
+        }
+        (Proof::Inclusion(_), None) => {
+            let Some(first_side_node) = proof_set.first() else {
+                return Ok(*zero_sum())
+            };
+            let sibling_is_leaf = if first_side_node == zero_sum() {
+                true
+            } else {
+                let sibling = sibling.as_ref().ok_or(ProofError::MissingSibling)?;
+                if sibling.hash::<H>() != *first_side_node {
+                    return Err(ProofError::InvalidSibling)
+                }
+                matches!(sibling, SiblingPreimage::Leaf { .. })
+            };
+
+            if sibling_is_leaf {
+                // A leaf sibling, or a placeholder, replaces the parent of the
+                // deleted leaf and each following ancestor with a placeholder side
+                // node, up to the first side node that isn't a placeholder.
+                let mut side_nodes = proof_set.iter().enumerate().skip(1);
+                match side_nodes.find(|(_, side_node)| *side_node != zero_sum()) {
+                    Some((i, side_node)) => {
+                        let current = hash_step::<H>(
+                            key,
+                            *first_side_node,
+                            depth(proof_set.len(), i),
+                            side_node,
+                        );
+                        hash_up::<H>(key, current, side_nodes, proof_set.len())
+                    }
+                    None => *first_side_node,
+                }
+            } else {
+                hash_up::<H>(
+                    key,
+                    *zero_sum(),
+                    proof_set.iter().enumerate(),
+                    proof_set.len(),
+                )
+            }
+        }
+        (Proof::Exclusion(proof), Some(value)) => {
+            let leaf = calculate_leaf_hash::<H>(key, &H::hash(&[value]));
+            let current = match &proof.leaf {
+                // The new leaf takes the place of the placeholder.
+                ExclusionLeaf::Placeholder => leaf,
+                // The new leaf and the closest leaf are joined where their keys
+                // diverge, below placeholder side nodes up to the closest leaf's
+                // depth.
+                ExclusionLeaf::Leaf(data) => {
+                    let actual_leaf =
+                        calculate_leaf_hash::<H>(&data.leaf_key, &data.leaf_value);
+                    let ancestor_depth = key.common_path_length(&data.leaf_key);
+                    let leaf_depth = u32::try_from(proof_set.len())
+                        .expect("The proof has been verified above");
+                    if ancestor_depth < leaf_depth {
+                        return Err(ProofError::InvalidProof)
+                    }
+                    let current = hash_step::<H>(key, leaf, ancestor_depth, &actual_leaf);
+                    (leaf_depth..ancestor_depth)
+                        .rev()
+                        .fold(current, |current, depth| {
+                            hash_step::<H>(key, current, depth, zero_sum())
+                        })
+                }
+            };
+            hash_up::<H>(key, current, proof_set.iter().enumerate(), proof_set.len())
+        }
+        // Deleting a key that is not in the tree doesn't change the root.
+        (Proof::Exclusion(_), None) => *root,
+    };
+
+    Ok(new_root)
+}
+
+/// The depth of the side node `i` of a proof set of `len` side nodes.
+fn depth(len: usize, i: usize) -> u32 {
+    u32::try_from(len - 1 - i).expect("The proof set has at most 256 side nodes")
+}
+
+/// The parent of the `current` node and the `side_node` at the `depth`.
+fn hash_step<H: MerkleHash>(
+    key: &MerkleTreeKey,
+    current: Bytes32,
+    depth: u32,
+    side_node: &Bytes32,
+) -> Bytes32 {
+    match key.get_instruction(depth).expect("Infallible") {
+        Instruction::Left => calculate_node_hash::<H>(&current, side_node),
+        Instruction::Right => calculate_node_hash::<H>(side_node, &current),
+    }
+}
+
+/// The root of the `current` node and the side nodes of a proof set of `len` side
+/// nodes, with their index in the proof set.
+fn hash_up<'a, H: MerkleHash>(
+    key: &MerkleTreeKey,
+    current: Bytes32,
+    side_nodes: impl Iterator<Item = (usize, &'a Bytes32)>,
+    len: usize,
+) -> Bytes32 {
+    side_nodes.fold(current, |current, (i, side_node)| {
+        hash_step::<H>(key, current, depth(len, i), side_node)
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sparse::{
+            empty_sum,
+            verify_and_update,
+            MerkleTree,
+            MerkleTreeKey,
+            Primitive,
+            ProofError,
+        },
+    };
+    use fuel_storage::Mappable;
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    fn key(i: u8) -> MerkleTreeKey {
+        MerkleTreeKey::new([i])
+    }
+
+    #[test]
+    fn verify_and_update__inserts_into_the_empty_tree() {
+        // Given
+        let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+        let proof = tree.generate_update_proof(&key(0)).unwrap();
+
+        // When
+        let new_root =
+            verify_and_update(empty_sum(), &key(0), None, Some(b"DATA"), &proof);
+
+        // Then
+        tree.update(key(0), b"DATA").unwrap();
+        assert_eq!(new_root, Ok(tree.root()));
+    }
+
+    #[test]
+    fn verify_and_update__deletes_the_only_leaf() {
+        // Given
+        let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+        tree.update(key(0), b"DATA").unwrap();
+        let proof = tree.generate_update_proof(&key(0)).unwrap();
+
+        // When
+        let new_root =
+            verify_and_update(&tree.root(), &key(0), Some(b"DATA"), None, &proof);
+
+        // Then
+        assert_eq!(new_root, Ok(*empty_sum()));
+    }
+
+    #[test]
+    fn verify_and_update__fails_with_the_wrong_old_value() {
+        // Given
+        let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+        tree.update(key(0), b"DATA").unwrap();
+        tree.update(key(1), b"DATA").unwrap();
+        let root = tree.root();
+        let proof = tree.generate_update_proof(&key(0)).unwrap();
+
+        // When
+        let wrong_value = verify_and_update(&root, &key(0), Some(b"OTHER"), None, &proof);
+        let absent = verify_and_update(&root, &key(0), None, Some(b"OTHER"), &proof);
+
+        // Then
+        assert_eq!(wrong_value, Err(ProofError::InvalidProof));
+        assert_eq!(absent, Err(ProofError::InvalidProof));
+    }
+
+    #[test]
+    fn verify_and_update__fails_to_delete_without_the_sibling() {
+        // Given
+        let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+        tree.update(key(0), b"DATA").unwrap();
+        tree.update(key(1), b"DATA").unwrap();
+        let root = tree.root();
+        let mut proof = tree.generate_update_proof(&key(0)).unwrap();
+        proof.sibling = None;
+
+        // When
+        let deleted = verify_and_update(&root, &key(0), Some(b"DATA"), None, &proof);
+        let overwritten =
+            verify_and_update(&root, &key(0), Some(b"DATA"), Some(b"OTHER"), &proof);
+
+        // Then
+        assert_eq!(deleted, Err(ProofError::MissingSibling));
+        tree.update(key(0), b"OTHER").unwrap();
+        assert_eq!(overwritten, Ok(tree.root()));
+    }
+}
@@ -1,6 +1,8 @@
 mod hash;
 mod merkle_tree;
 mod node;
+mod proof;
+mod verify;
 
 pub(crate) use hash::{
     empty_sum,
@@ -12,3 +14,8 @@ pub use merkle_tree::{
     MerkleTreeError,
 };
 pub(crate) use node::Node;
+pub use proof::{
+    InclusionProof,
+    ProofDecodeError,
+};
+pub use verify::verify;
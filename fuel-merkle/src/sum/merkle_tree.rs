@@ -1,10 +1,12 @@
 use crate::{
     common::{
+        left_subtree_leaves_count,
         Bytes32,
         Subtree,
     },
     sum::{
         empty_sum,
+        InclusionProof,
         Node,
     },
 };
@@ -14,12 +16,28 @@ use fuel_storage::{
     StorageMutate,
 };
 
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 #[derive(Debug, Clone, derive_more::Display)]
-pub enum MerkleTreeError {
+pub enum MerkleTreeError<StorageError> {
     #[display(fmt = "proof index {_0} is not valid")]
     InvalidProofIndex(u64),
+
+    #[display(
+        fmt = "cannot load node with key {}; the key is not found in storage",
+        "hex::encode(_0)"
+    )]
+    LoadError(Bytes32),
+
+    #[display(fmt = "{}", _0)]
+    StorageError(StorageError),
+}
+
+impl<StorageError> From<StorageError> for MerkleTreeError<StorageError> {
+    fn from(err: StorageError) -> MerkleTreeError<StorageError> {
+        MerkleTreeError::StorageError(err)
+    }
 }
 
 /// The Binary Merkle Sum Tree is an extension to the existing Binary
@@ -29,11 +47,17 @@ pub enum MerkleTreeError {
 /// - a digest (array of bytes)
 ///
 /// Therefore, a node's data is now a data pair formed by `(fee, digest)`. The data pair
-/// of a node with two or more leaves is defined as:
+/// of a leaf with the `fee` and the `data` is defined as:
+///
+/// (fee, hash(0x00 ++ fee ++ data))
+///
+/// The data pair of a node with two or more leaves is defined as:
 ///
 /// (left.fee + right.fee, hash(0x01 ++ left.fee ++ left.digest ++ right.fee ++
 /// right.digest))
 ///
+/// where the fees are encoded as big-endian `u64`s, and `hash` is SHA-256.
+///
 /// This is in contrast to the Binary Merkle Tree node, where a node has only a digest.
 ///
 /// See the [specification](https://github.com/FuelLabs/fuel-specs/blob/master/src/protocol/cryptographic-primitives.md#merkle-trees)
@@ -95,9 +119,82 @@ where
         Ok(())
     }
 
+    pub fn leaves_count(&self) -> u64 {
+        let mut leaves_count = 0;
+        let mut subtree = self.head.as_ref();
+        while let Some(current) = subtree {
+            leaves_count += 1 << current.node().height();
+            subtree = current.next();
+        }
+        leaves_count
+    }
+
+    /// Generates the [`InclusionProof`] of the leaf of the `proof_index`, and returns
+    /// it with the root of the tree.
+    pub fn prove(
+        &mut self,
+        proof_index: u64,
+    ) -> Result<((u64, Bytes32), InclusionProof), MerkleTreeError<StorageError>> {
+        let leaves_count = self.leaves_count();
+        if proof_index >= leaves_count {
+            return Err(MerkleTreeError::InvalidProofIndex(proof_index))
+        }
+
+        let root_node = self.root_node()?.expect("Root node must be present");
+        let root = (root_node.fee(), *root_node.hash());
+
+        // Descend from the root to the leaf, collecting the side nodes.
+        let mut proof_set = Vec::new();
+        let mut node = root_node;
+        let (mut index, mut count) = (proof_index, leaves_count);
+        while count > 1 {
+            let left_child = self.load(node.left_child_key())?;
+            let right_child = self.load(node.right_child_key())?;
+            let left_count = left_subtree_leaves_count(count);
+            if index < left_count {
+                proof_set.push((right_child.fee(), *right_child.hash()));
+                node = left_child;
+                count = left_count;
+            } else {
+                proof_set.push((left_child.fee(), *left_child.hash()));
+                node = right_child;
+                index -= left_count;
+                count -= left_count;
+            }
+        }
+        proof_set.reverse(); // Reorder side nodes from leaf to root.
+
+        let proof = InclusionProof {
+            leaf_index: proof_index,
+            leaves_count,
+            proof_set,
+        };
+        Ok((root, proof))
+    }
+
+    /// Generates the [`InclusionProof`] of the leaf of the `proof_index`: the fees and
+    /// digests of the side nodes of its path, verified by [`verify`](crate::sum::verify).
+    pub fn generate_proof(
+        &mut self,
+        proof_index: u64,
+    ) -> Result<InclusionProof, MerkleTreeError<StorageError>> {
+        let (_, proof) = self.prove(proof_index)?;
+        Ok(proof)
+    }
+
     // PRIVATE
     //
 
+    fn load(&self, key: Option<Bytes32>) -> Result<Node, MerkleTreeError<StorageError>> {
+        let key = key.expect("Internal nodes have children");
+        let node = self
+            .storage
+            .get(&key)?
+            .ok_or(MerkleTreeError::LoadError(key))?
+            .into_owned();
+        Ok(node)
+    }
+
     fn root_node(&mut self) -> Result<Option<Node>, StorageError> {
         let root_node = match self.head {
             None => None,
@@ -168,6 +265,7 @@ mod test {
             leaf_sum,
             node_sum,
             MerkleTree,
+            MerkleTreeError,
             Node,
         },
     };
@@ -238,6 +336,65 @@ mod test {
         assert_eq!(root, expected);
     }
 
+    #[test]
+    fn generate_proof_returns_the_side_nodes_of_a_4_leaves_tree() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let fees = [10, 20, 30, 40];
+        let data = &TEST_DATA[0..4]; // 4 leaves
+        for (fee, datum) in fees.iter().zip(data) {
+            let _ = tree.push(*fee, datum);
+        }
+
+        //        N2
+        //       /  \
+        //      /    \
+        //    N0      N1
+        //   /  \    /  \
+        //  L0  L1  L2  L3
+
+        let leaf_0 = leaf_sum(10, data[0]);
+        let leaf_1 = leaf_sum(20, data[1]);
+        let leaf_2 = leaf_sum(30, data[2]);
+        let leaf_3 = leaf_sum(40, data[3]);
+
+        let node_0 = node_sum(10, &leaf_0, 20, &leaf_1);
+        let node_1 = node_sum(30, &leaf_2, 40, &leaf_3);
+
+        let expected_proof_sets = [
+            vec![(20, leaf_1), (70, node_1)],
+            vec![(10, leaf_0), (70, node_1)],
+            vec![(40, leaf_3), (30, node_0)],
+            vec![(30, leaf_2), (30, node_0)],
+        ];
+        for (index, expected_proof_set) in expected_proof_sets.into_iter().enumerate() {
+            let proof = tree.generate_proof(index as u64).unwrap();
+            assert_eq!(proof.leaf_index, index as u64);
+            assert_eq!(proof.leaves_count, 4);
+            assert_eq!(proof.proof_set, expected_proof_set);
+        }
+
+        let root = tree.root().unwrap();
+        let expected_root = (100, node_sum(30, &node_0, 70, &node_1));
+        assert_eq!(root, expected_root);
+    }
+
+    #[test]
+    fn generate_proof_returns_an_error_for_an_invalid_index() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        for datum in TEST_DATA[0..4].iter() {
+            let _ = tree.push(FEE, datum);
+        }
+
+        let err = tree
+            .generate_proof(4)
+            .expect_err("Expected generate_proof() to fail");
+        assert!(matches!(err, MerkleTreeError::InvalidProofIndex(4)));
+    }
+
     #[test]
     fn root_returns_the_hash_of_the_head_when_5_leaves_are_pushed() {
         let mut storage_map = StorageMap::<TestTable>::new();
@@ -0,0 +1,136 @@
+use crate::{
+    common::Bytes32,
+    sum::verify,
+};
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    fmt::Debug,
+};
+
+/// The proof that a leaf is in a binary Merkle sum tree.
+///
+/// # Encoding
+///
+/// [`InclusionProof::to_bytes`] encodes the proof as:
+///
+/// - the index of the leaf, as a big-endian `u64`;
+/// - the number of leaves of the tree, as a big-endian `u64`;
+/// - the number of side nodes, as a big-endian `u16` of at most 64;
+/// - the side nodes, in the order of the proof set, from the leaf to the root, each as
+///   its fee, as a big-endian `u64`, followed by its 32 bytes digest.
+///
+/// The side of each side node follows from the index of the leaf and the number of
+/// leaves, so the encoding doesn't repeat it. The encoding is canonical:
+/// [`InclusionProof::from_bytes`] rejects any other byte string, including the valid
+/// encodings followed by more bytes.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InclusionProof {
+    /// The index of the leaf.
+    pub leaf_index: u64,
+    /// The number of leaves of the tree.
+    pub leaves_count: u64,
+    /// The fee and digest of the side nodes of the path of the leaf, from the leaf to
+    /// the root.
+    pub proof_set: Vec<(u64, Bytes32)>,
+}
+
+impl Debug for InclusionProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct SideNode<'a>(&'a (u64, Bytes32));
+
+        impl Debug for SideNode<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (fee, hash) = self.0;
+                write!(f, "({fee}, {})", hex::encode(hash))
+            }
+        }
+
+        let proof_set = self.proof_set.iter().map(SideNode).collect::<Vec<_>>();
+        f.debug_struct("InclusionProof")
+            .field("Leaf index", &self.leaf_index)
+            .field("Leaves count", &self.leaves_count)
+            .field("Proof set", &proof_set)
+            .finish()
+    }
+}
+
+/// The failure to decode an [`InclusionProof`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
+pub enum ProofDecodeError {
+    #[display(fmt = "the encoded proof ends unexpectedly")]
+    UnexpectedEnd,
+    #[display(fmt = "the proof set has {} side nodes, more than 64", _0)]
+    ProofSetTooLong(u16),
+    #[display(fmt = "the encoded proof is followed by more bytes")]
+    TrailingBytes,
+}
+
+impl InclusionProof {
+    const MAX_PROOF_SET_LEN: u16 = 64;
+
+    /// Encodes the proof, as documented by [`InclusionProof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + 8 + 2 + 40 * self.proof_set.len());
+
+        bytes.extend_from_slice(&self.leaf_index.to_be_bytes());
+        bytes.extend_from_slice(&self.leaves_count.to_be_bytes());
+        let len = u16::try_from(self.proof_set.len()).unwrap_or(u16::MAX);
+        bytes.extend_from_slice(&len.to_be_bytes());
+        for (fee, hash) in self.proof_set.iter() {
+            bytes.extend_from_slice(&fee.to_be_bytes());
+            bytes.extend_from_slice(hash);
+        }
+
+        bytes
+    }
+
+    /// Decodes a proof encoded by [`InclusionProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        fn take<'a, const N: usize>(
+            bytes: &mut &'a [u8],
+        ) -> Result<&'a [u8; N], ProofDecodeError> {
+            if bytes.len() < N {
+                return Err(ProofDecodeError::UnexpectedEnd)
+            }
+            let (head, tail) = bytes.split_at(N);
+            *bytes = tail;
+            Ok(head.try_into().expect("The length was checked above"))
+        }
+
+        let mut bytes = bytes;
+
+        let leaf_index = u64::from_be_bytes(*take::<8>(&mut bytes)?);
+        let leaves_count = u64::from_be_bytes(*take::<8>(&mut bytes)?);
+        let len = u16::from_be_bytes(*take::<2>(&mut bytes)?);
+        if len > Self::MAX_PROOF_SET_LEN {
+            return Err(ProofDecodeError::ProofSetTooLong(len))
+        }
+        let proof_set = (0..len)
+            .map(|_| {
+                let fee = u64::from_be_bytes(*take::<8>(&mut bytes)?);
+                let hash = *take::<32>(&mut bytes)?;
+                Ok((fee, hash))
+            })
+            .collect::<Result<_, _>>()?;
+
+        if !bytes.is_empty() {
+            return Err(ProofDecodeError::TrailingBytes)
+        }
+
+        Ok(Self {
+            leaf_index,
+            leaves_count,
+            proof_set,
+        })
+    }
+
+    /// Verifies that the leaf with the `fee` and the `data` is in the tree of the
+    /// `root` fee and digest, like [`verify`](crate::sum::verify).
+    pub fn verify(&self, root: &(u64, Bytes32), fee: u64, data: &[u8]) -> bool {
+        let (root_sum, root_hash) = root;
+        verify(root_hash, *root_sum, (fee, &data), self)
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    common::{
+        left_subtree_leaves_count,
+        Bytes32,
+    },
+    sum::{
+        leaf_sum,
+        node_sum,
+        InclusionProof,
+    },
+};
+
+/// Verifies that the `leaf`, given as its fee and data, is in the sum tree with the
+/// `root_hash` and the `root_sum`.
+///
+/// The verification recomputes the path from the leaf to the root: each step joins the
+/// current node with the next side node of the `proof`, hashing both fees and digests
+/// with [`node_sum`] and adding the fees. A proof whose fees overflow a `u64` on the way
+/// to the root is rejected, so the verified `root_sum` is always the exact sum of the
+/// fees of the leaves.
+pub fn verify<T: AsRef<[u8]>>(
+    root_hash: &Bytes32,
+    root_sum: u64,
+    leaf: (u64, &T),
+    proof: &InclusionProof,
+) -> bool {
+    let InclusionProof {
+        leaf_index,
+        leaves_count,
+        proof_set,
+    } = proof;
+
+    if leaf_index >= leaves_count {
+        return false
+    }
+
+    // The path from the root to the leaf: the bit of each depth is set when the path
+    // goes to the left child.
+    let mut path = 0u64;
+    let mut depth = 0;
+    let (mut index, mut count) = (*leaf_index, *leaves_count);
+    while count > 1 {
+        let left_count = left_subtree_leaves_count(count);
+        if index < left_count {
+            path |= 1 << depth;
+            count = left_count;
+        } else {
+            index -= left_count;
+            count -= left_count;
+        }
+        depth += 1;
+    }
+
+    if proof_set.len() != depth {
+        return false
+    }
+
+    let (fee, data) = leaf;
+    let mut current_fee = fee;
+    let mut current_hash = leaf_sum(fee, data.as_ref());
+    for (height, (side_fee, side_hash)) in proof_set.iter().enumerate() {
+        let Some(joined_fee) = current_fee.checked_add(*side_fee) else {
+            return false
+        };
+        current_hash = if path & (1 << (depth - 1 - height)) != 0 {
+            node_sum(current_fee, &current_hash, *side_fee, side_hash)
+        } else {
+            node_sum(*side_fee, side_hash, current_fee, &current_hash)
+        };
+        current_fee = joined_fee;
+    }
+
+    current_fee == root_sum && current_hash == *root_hash
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::verify;
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sum::{
+            leaf_sum,
+            node_sum,
+            InclusionProof,
+            MerkleTree,
+            Node,
+        },
+    };
+    use fuel_merkle_test_helpers::TEST_DATA;
+    use fuel_storage::Mappable;
+
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Node;
+        type Value = Self::OwnedValue;
+    }
+
+    const FEES: [u64; 4] = [10, 20, 30, 40];
+
+    fn proof_of_4_leaves(index: u64) -> ((u64, Bytes32), InclusionProof) {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+        for (fee, datum) in FEES.iter().zip(TEST_DATA) {
+            tree.push(*fee, datum).unwrap();
+        }
+        let root = tree.root().unwrap();
+        let proof = tree.generate_proof(index).unwrap();
+        (root, proof)
+    }
+
+    #[test]
+    fn verify__returns_true_for_every_leaf_of_a_4_leaves_tree() {
+        for (index, (fee, datum)) in FEES.iter().zip(TEST_DATA).enumerate() {
+            // Given
+            let ((root_sum, root_hash), proof) = proof_of_4_leaves(index as u64);
+
+            // When
+            let verification = verify(&root_hash, root_sum, (*fee, &datum), &proof);
+
+            // Then
+            assert!(verification, "leaf {index}");
+        }
+    }
+
+    #[test]
+    fn verify__returns_false_for_a_tampered_root_sum() {
+        // Given
+        let ((root_sum, root_hash), proof) = proof_of_4_leaves(1);
+
+        // When
+        let verification =
+            verify(&root_hash, root_sum + 1, (FEES[1], &TEST_DATA[1]), &proof);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify__returns_false_for_a_tampered_leaf_fee() {
+        // Given
+        let ((root_sum, root_hash), proof) = proof_of_4_leaves(1);
+
+        // When
+        let verification =
+            verify(&root_hash, root_sum, (FEES[1] + 1, &TEST_DATA[1]), &proof);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify__returns_false_for_a_tampered_side_node_fee() {
+        for side in 0..2 {
+            // Given
+            let ((root_sum, root_hash), mut proof) = proof_of_4_leaves(2);
+            proof.proof_set[side].0 += 1;
+
+            // When
+            let verification =
+                verify(&root_hash, root_sum, (FEES[2], &TEST_DATA[2]), &proof);
+
+            // Then
+            assert!(!verification, "side node {side}");
+        }
+    }
+
+    #[test]
+    fn verify__returns_false_when_the_fees_are_moved_between_the_leaf_and_a_side_node() {
+        // Given
+        let ((root_sum, root_hash), mut proof) = proof_of_4_leaves(3);
+        proof.proof_set[0].0 += 5;
+
+        // When
+        let verification =
+            verify(&root_hash, root_sum, (FEES[3] - 5, &TEST_DATA[3]), &proof);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify__returns_false_when_the_fees_overflow() {
+        // Given
+        let leaf = leaf_sum(u64::MAX, TEST_DATA[0]);
+        let side = leaf_sum(1, TEST_DATA[1]);
+        let root_hash = node_sum(u64::MAX, &leaf, 1, &side);
+        let proof = InclusionProof {
+            leaf_index: 0,
+            leaves_count: 2,
+            proof_set: vec![(1, side)],
+        };
+
+        // When
+        let verification = verify(
+            &root_hash,
+            u64::MAX.wrapping_add(1),
+            (u64::MAX, &TEST_DATA[0]),
+            &proof,
+        );
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify__returns_false_for_an_out_of_bounds_leaf_index() {
+        // Given
+        let ((root_sum, root_hash), mut proof) = proof_of_4_leaves(3);
+        proof.leaf_index = 4;
+
+        // When
+        let verification = verify(&root_hash, root_sum, (FEES[3], &TEST_DATA[3]), &proof);
+
+        // Then
+        assert!(!verification);
+    }
+}
@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+
+use proptest::{
+    prop_assert_eq,
+    prop_compose,
+    proptest,
+};
+
+use crate::{
+    binary::{
+        root_calculator::MerkleRootCalculator,
+        MerkleTree,
+        Primitive,
+    },
+    common::StorageMap,
+};
+use fuel_storage::Mappable;
+
+#[derive(Debug)]
+struct TestTable;
+
+impl Mappable for TestTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = u64;
+    type OwnedValue = Primitive;
+    type Value = Self::OwnedValue;
+}
+
+prop_compose! {
+    fn leaves(max: usize)(seed: u64, n in 0..max) -> Vec<[u8; 8]> {
+        (0..n as u64).map(|i| seed.wrapping_add(i).to_le_bytes()).collect()
+    }
+}
+
+fn serial_tree(leaves: &[[u8; 8]]) -> MerkleTree<TestTable, StorageMap<TestTable>> {
+    let mut tree = MerkleTree::new(StorageMap::new());
+    for leaf in leaves {
+        tree.push(leaf).unwrap();
+    }
+    tree
+}
+
+proptest! {
+    #[test]
+    fn from_leaves_parallel__matches_the_pushed_leaves(leaves in leaves(3_000), index: u64) {
+        // Given
+        let mut serial = serial_tree(&leaves);
+
+        // When
+        let mut parallel =
+            MerkleTree::from_leaves_parallel(StorageMap::<TestTable>::new(), &leaves).unwrap();
+
+        // Then
+        prop_assert_eq!(parallel.root(), serial.root());
+        prop_assert_eq!(parallel.leaves_count(), serial.leaves_count());
+        if !leaves.is_empty() {
+            let index = index % leaves.len() as u64;
+            prop_assert_eq!(parallel.prove(index).unwrap(), serial.prove(index).unwrap());
+        }
+        serial.push(b"NEXT").unwrap();
+        parallel.push(b"NEXT").unwrap();
+        prop_assert_eq!(parallel.root(), serial.root());
+    }
+
+    #[test]
+    fn push_all_parallel__matches_push_all(pushed in leaves(300), leaves in leaves(3_000)) {
+        // Given
+        let mut serial = MerkleRootCalculator::new();
+        serial.push_all(pushed.iter());
+        let mut parallel = serial.clone();
+
+        // When
+        serial.push_all(leaves.iter());
+        parallel.push_all_parallel(&leaves);
+
+        // Then
+        prop_assert_eq!(&parallel, &serial);
+        prop_assert_eq!(parallel.root(), serial.root());
+    }
+}
@@ -14,9 +14,10 @@ use proptest::{
     strategy::Strategy,
 };
 
+use super::TestHash;
 use crate::{
     binary::{
-        verify,
+        verify_with_hash,
         MerkleTree,
         Primitive,
     },
@@ -70,9 +71,9 @@ prop_compose! {
 }
 
 prop_compose! {
-    fn random_tree(min: usize, max: usize)(values in values(min, max)) -> (Vec<Value>, MerkleTree<TestTable, StorageMap<TestTable>>) {
+    fn random_tree(min: usize, max: usize)(values in values(min, max)) -> (Vec<Value>, MerkleTree<TestTable, StorageMap<TestTable>, TestHash>) {
         let storage = StorageMap::<TestTable>::new();
-        let mut tree = MerkleTree::new(storage);
+        let mut tree = MerkleTree::new_with_hash(storage);
         for datum in values.iter() {
             tree.push(datum.as_ref()).unwrap();
         }
@@ -91,7 +92,7 @@ proptest! {
         let (root, proof_set) = tree.prove(index  as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, index as u64, num_leaves  as u64);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, index as u64, num_leaves  as u64);
 
         // Then
         prop_assert!(verification)
@@ -107,7 +108,7 @@ proptest! {
         let (_, proof_set) = tree.prove(index  as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, index as u64, num_leaves  as u64);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, index as u64, num_leaves  as u64);
 
         // Then
         prop_assert!(!verification)
@@ -123,7 +124,7 @@ proptest! {
         let (root, _) = tree.prove(index  as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, index as u64, num_leaves  as u64);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, index as u64, num_leaves  as u64);
 
         // Then
         prop_assert!(!verification)
@@ -139,7 +140,7 @@ proptest! {
         let (root, proof_set) = tree.prove(index as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, index as u64, num_leaves as u64);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, index as u64, num_leaves as u64);
 
         // Then
         prop_assert!(verification)
@@ -157,7 +158,7 @@ proptest! {
         let (root, proof_set) = tree.prove(index as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, index as u64, incorrect_num_leaves);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, index as u64, incorrect_num_leaves);
 
         // Then
         prop_assert!(!verification)
@@ -174,7 +175,7 @@ proptest! {
         let (root, proof_set) = tree.prove(valid_index as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, invalid_index, num_leaves as u64);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, invalid_index, num_leaves as u64);
 
         // Then
         prop_assert!(!verification)
@@ -191,7 +192,7 @@ proptest! {
         let (root, proof_set) = tree.prove(valid_index as u64).expect("Unable to generate proof");
 
         // When
-        let verification = verify(&root, &data, &proof_set, invalid_index, incorrect_num_leaves);
+        let verification = verify_with_hash::<TestHash, _>(&root, &data, &proof_set, invalid_index, incorrect_num_leaves);
 
         // Then
         prop_assert!(!verification)
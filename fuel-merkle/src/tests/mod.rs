@@ -3,6 +3,28 @@ use criterion as _;
 use datatest_stable as _;
 use serde_yaml as _;
 
+/// The hash function of the property tests of the trees, rerun with another hash
+/// function in [`sha512_256`].
+type TestHash = crate::common::Sha256;
+
 mod binary;
+#[cfg(feature = "rayon")]
+mod binary_parallel;
 mod binary_verify;
+mod proofs;
 mod sparse;
+mod sparse_keys;
+mod sparse_storage_adapter;
+mod sparse_update;
+mod sum_verify;
+
+/// The property tests of the trees, with SHA-512/256 instead of SHA-256.
+#[path = "."]
+#[allow(clippy::duplicate_mod)]
+mod sha512_256 {
+    type TestHash = crate::common::Sha512Trunc256;
+
+    mod binary_verify;
+    mod sparse;
+    mod sparse_update;
+}
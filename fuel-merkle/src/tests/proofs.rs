@@ -0,0 +1,362 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    binary,
+    common::{
+        Bytes32,
+        StorageMap,
+    },
+    sparse::{
+        self,
+        proof::{
+            ExclusionProof,
+            InclusionProof,
+            Proof,
+        },
+        MerkleTreeKey,
+    },
+    sum,
+};
+
+use fuel_merkle_test_helpers::TEST_DATA;
+use fuel_storage::Mappable;
+use proptest::{
+    arbitrary::any,
+    collection::vec,
+    prop_assert,
+    prop_assert_eq,
+    proptest,
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+/// The golden vectors of the proof encodings, generated by [`golden_vectors`].
+const GOLDEN_VECTORS: &str = include_str!("proofs.json");
+
+struct BinaryTable;
+
+impl Mappable for BinaryTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = u64;
+    type OwnedValue = binary::Primitive;
+    type Value = Self::OwnedValue;
+}
+
+struct SparseTable;
+
+impl Mappable for SparseTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = Bytes32;
+    type OwnedValue = sparse::Primitive;
+    type Value = Self::OwnedValue;
+}
+
+struct SumTable;
+
+impl Mappable for SumTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = Bytes32;
+    type OwnedValue = sum::Node;
+    type Value = Self::OwnedValue;
+}
+
+fn binary_tree(
+    leaves_count: usize,
+) -> binary::MerkleTree<BinaryTable, StorageMap<BinaryTable>> {
+    let mut tree = binary::MerkleTree::new(StorageMap::new());
+    for datum in TEST_DATA.iter().cycle().take(leaves_count) {
+        tree.push(datum).unwrap();
+    }
+    tree
+}
+
+fn sparse_key(i: u8) -> MerkleTreeKey {
+    MerkleTreeKey::new([i; 32])
+}
+
+fn sparse_tree(
+    leaves_count: usize,
+) -> sparse::MerkleTree<SparseTable, StorageMap<SparseTable>> {
+    let set = TEST_DATA
+        .iter()
+        .take(leaves_count)
+        .zip(0u8..)
+        .map(|(datum, i)| (sparse_key(i), datum));
+    sparse::MerkleTree::from_set(StorageMap::new(), set).unwrap()
+}
+
+fn sum_fee(index: usize) -> u64 {
+    100 + u64::try_from(index).unwrap()
+}
+
+fn sum_tree(leaves_count: usize) -> sum::MerkleTree<SumTable, StorageMap<SumTable>> {
+    let mut tree = sum::MerkleTree::new(StorageMap::new());
+    for (i, datum) in TEST_DATA.iter().cycle().take(leaves_count).enumerate() {
+        tree.push(sum_fee(i), datum).unwrap();
+    }
+    tree
+}
+
+fn text(data: &[u8]) -> &str {
+    core::str::from_utf8(data).unwrap()
+}
+
+/// Generates the golden vectors of the proof encodings.
+fn golden_vectors() -> Value {
+    let binary_tree = binary_tree(7);
+    let binary_inclusion = binary_tree.generate_inclusion_proof(4).unwrap();
+    let binary_multi = binary_tree.generate_multi_proof(&[1, 4, 5]).unwrap();
+
+    let sparse_tree = sparse_tree(5);
+    let sparse_inclusion = sparse_tree.generate_proof(&sparse_key(2)).unwrap();
+    let sparse_exclusion = sparse_tree.generate_proof(&sparse_key(7)).unwrap();
+
+    let mut sum_tree = sum_tree(5);
+    let (sum_root, sum_inclusion) = sum_tree.prove(4).unwrap();
+
+    json!([
+        {
+            "name": "binary-inclusion",
+            "root": hex::encode(binary_tree.root()),
+            "data": text(TEST_DATA[4]),
+            "encoded": hex::encode(binary_inclusion.to_bytes()),
+        },
+        {
+            "name": "binary-multi",
+            "root": hex::encode(binary_tree.root()),
+            "leaves": [
+                [1, text(TEST_DATA[1])],
+                [4, text(TEST_DATA[4])],
+                [5, text(TEST_DATA[5])],
+            ],
+            "encoded": hex::encode(binary_multi.to_bytes()),
+        },
+        {
+            "name": "sparse-inclusion",
+            "root": hex::encode(sparse_tree.root()),
+            "key": hex::encode(*sparse_key(2)),
+            "data": text(TEST_DATA[2]),
+            "encoded": hex::encode(sparse_inclusion.to_bytes()),
+        },
+        {
+            "name": "sparse-exclusion",
+            "root": hex::encode(sparse_tree.root()),
+            "key": hex::encode(*sparse_key(7)),
+            "encoded": hex::encode(sparse_exclusion.to_bytes()),
+        },
+        {
+            "name": "sum-inclusion",
+            "root_fee": sum_root.0,
+            "root": hex::encode(sum_root.1),
+            "fee": sum_fee(4),
+            "data": text(TEST_DATA[4]),
+            "encoded": hex::encode(sum_inclusion.to_bytes()),
+        },
+    ])
+}
+
+fn find_vector<'a>(vectors: &'a Value, name: &str) -> &'a Value {
+    vectors
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|vector| vector["name"] == name)
+        .unwrap()
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    hex::decode(value.as_str().unwrap()).unwrap()
+}
+
+fn bytes32(value: &Value) -> Bytes32 {
+    bytes(value).try_into().unwrap()
+}
+
+#[test]
+fn golden_vectors__match_the_generated_proofs() {
+    let vectors: Value = serde_json::from_str(GOLDEN_VECTORS).unwrap();
+
+    assert_eq!(vectors, golden_vectors());
+}
+
+#[test]
+fn golden_vectors__verify_from_the_decoded_proofs() {
+    let vectors: Value = serde_json::from_str(GOLDEN_VECTORS).unwrap();
+
+    let vector = find_vector(&vectors, "binary-inclusion");
+    let proof = binary::InclusionProof::from_bytes(&bytes(&vector["encoded"])).unwrap();
+    let data = vector["data"].as_str().unwrap();
+    assert!(proof.verify(&bytes32(&vector["root"]), &data));
+
+    let vector = find_vector(&vectors, "binary-multi");
+    let proof = binary::MultiProof::from_bytes(&bytes(&vector["encoded"])).unwrap();
+    let leaves = vector["leaves"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|leaf| (leaf[0].as_u64().unwrap(), leaf[1].as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert!(binary::verify_multi(
+        &bytes32(&vector["root"]),
+        &leaves,
+        &proof
+    ));
+
+    let vector = find_vector(&vectors, "sparse-inclusion");
+    let Proof::Inclusion(proof) = Proof::from_bytes(&bytes(&vector["encoded"])).unwrap()
+    else {
+        panic!("Expected an inclusion proof")
+    };
+    let key = MerkleTreeKey::new_without_hash(bytes32(&vector["key"]));
+    let data = vector["data"].as_str().unwrap();
+    assert!(proof.verify(&bytes32(&vector["root"]), &key, data.as_bytes()));
+
+    let vector = find_vector(&vectors, "sparse-exclusion");
+    let Proof::Exclusion(proof) = Proof::from_bytes(&bytes(&vector["encoded"])).unwrap()
+    else {
+        panic!("Expected an exclusion proof")
+    };
+    let key = MerkleTreeKey::new_without_hash(bytes32(&vector["key"]));
+    assert!(sparse::verify_exclusion(
+        &bytes32(&vector["root"]),
+        &key,
+        &proof
+    ));
+
+    let vector = find_vector(&vectors, "sum-inclusion");
+    let proof = sum::InclusionProof::from_bytes(&bytes(&vector["encoded"])).unwrap();
+    let root = (
+        vector["root_fee"].as_u64().unwrap(),
+        bytes32(&vector["root"]),
+    );
+    let fee = vector["fee"].as_u64().unwrap();
+    let data = vector["data"].as_str().unwrap();
+    assert!(proof.verify(&root, fee, data.as_bytes()));
+}
+
+#[test]
+fn sum_inclusion_proof__verify__returns_true_for_every_leaf() {
+    for leaves_count in 1..=TEST_DATA.len() {
+        let mut tree = sum_tree(leaves_count);
+
+        for (index, datum) in TEST_DATA.iter().take(leaves_count).enumerate() {
+            let (root, proof) = tree.prove(index.try_into().unwrap()).unwrap();
+
+            assert_eq!(root, tree.root().unwrap());
+            assert!(proof.verify(&root, sum_fee(index), datum));
+            assert!(!proof.verify(&root, sum_fee(index) + 1, datum));
+        }
+    }
+}
+
+#[test]
+fn sum_merkle_tree__prove__returns_an_error_for_an_invalid_index() {
+    let mut tree = sum_tree(5);
+
+    let result = tree.prove(5);
+
+    assert!(matches!(
+        result,
+        Err(sum::MerkleTreeError::InvalidProofIndex(5))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn proofs__serde__round_trip() {
+    let binary_proof = binary_tree(7).generate_inclusion_proof(4).unwrap();
+    let json = serde_json::to_string(&binary_proof).unwrap();
+    assert_eq!(
+        serde_json::from_str::<binary::InclusionProof>(&json).unwrap(),
+        binary_proof
+    );
+
+    let sparse_proof = sparse_tree(5).generate_proof(&sparse_key(7)).unwrap();
+    let json = serde_json::to_string(&sparse_proof).unwrap();
+    assert_eq!(serde_json::from_str::<Proof>(&json).unwrap(), sparse_proof);
+
+    let (_, sum_proof) = sum_tree(5).prove(4).unwrap();
+    let json = serde_json::to_string(&sum_proof).unwrap();
+    assert_eq!(
+        serde_json::from_str::<sum::InclusionProof>(&json).unwrap(),
+        sum_proof
+    );
+}
+
+proptest! {
+    #[test]
+    fn from_bytes__never_panics_on_arbitrary_bytes(bytes in vec(any::<u8>(), 0..1024)) {
+        let _ = binary::InclusionProof::from_bytes(&bytes);
+        let _ = binary::MultiProof::from_bytes(&bytes);
+        let _ = Proof::from_bytes(&bytes);
+        let _ = InclusionProof::from_bytes(&bytes);
+        let _ = ExclusionProof::from_bytes(&bytes);
+        let _ = sum::InclusionProof::from_bytes(&bytes);
+    }
+
+    #[test]
+    fn from_bytes__never_panics_on_arbitrary_proof_sets(
+        header in vec(any::<u8>(), 0..20),
+        proof_set in vec(any::<[u8; 32]>(), 0..70),
+    ) {
+        // The arbitrary headers followed by whole nodes reach deeper into the decoding
+        // than arbitrary bytes.
+        let bytes = header
+            .into_iter()
+            .chain(proof_set.into_iter().flatten())
+            .collect::<Vec<_>>();
+        let _ = binary::InclusionProof::from_bytes(&bytes);
+        let _ = binary::MultiProof::from_bytes(&bytes);
+        let _ = Proof::from_bytes(&bytes);
+        let _ = sum::InclusionProof::from_bytes(&bytes);
+    }
+
+    #[test]
+    fn binary_inclusion_proof__round_trips(leaves_count in 1..200usize, arb_num: usize) {
+        let tree = binary_tree(leaves_count);
+        let index = u64::try_from(arb_num % leaves_count).unwrap();
+        let proof = tree.generate_inclusion_proof(index).unwrap();
+
+        let decoded = binary::InclusionProof::from_bytes(&proof.to_bytes()).unwrap();
+
+        prop_assert_eq!(&decoded, &proof);
+        let datum = TEST_DATA[usize::try_from(index).unwrap() % TEST_DATA.len()];
+        prop_assert!(decoded.verify(&tree.root(), &datum));
+    }
+
+    #[test]
+    fn sparse_proof__round_trips(leaves_count in 1..TEST_DATA.len(), key: u8) {
+        let tree = sparse_tree(leaves_count);
+        let proof = tree.generate_proof(&sparse_key(key)).unwrap();
+
+        let decoded = Proof::from_bytes(&proof.to_bytes()).unwrap();
+
+        prop_assert_eq!(&decoded, &proof);
+        match decoded {
+            Proof::Inclusion(decoded) => {
+                let datum = TEST_DATA[usize::from(key)];
+                prop_assert!(decoded.verify(&tree.root(), &sparse_key(key), datum));
+                prop_assert_eq!(InclusionProof::from_bytes(&decoded.to_bytes()).unwrap(), decoded);
+            }
+            Proof::Exclusion(decoded) => {
+                prop_assert!(decoded.verify(&tree.root(), &sparse_key(key)));
+                prop_assert_eq!(ExclusionProof::from_bytes(&decoded.to_bytes()).unwrap(), decoded);
+            }
+        }
+    }
+
+    #[test]
+    fn sum_inclusion_proof__round_trips(leaves_count in 1..200usize, arb_num: usize) {
+        let mut tree = sum_tree(leaves_count);
+        let index = arb_num % leaves_count;
+        let (root, proof) = tree.prove(u64::try_from(index).unwrap()).unwrap();
+
+        let decoded = sum::InclusionProof::from_bytes(&proof.to_bytes()).unwrap();
+
+        prop_assert_eq!(&decoded, &proof);
+        let datum = TEST_DATA[index % TEST_DATA.len()];
+        prop_assert!(decoded.verify(&root, sum_fee(index), datum));
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(non_snake_case)]
 
+use super::TestHash;
 use crate::{
     common::{
         Bytes32,
@@ -105,10 +106,10 @@ prop_compose! {
 }
 
 prop_compose! {
-    fn random_tree(min: usize, max: usize)(kv in key_values(min, max)) -> (Vec<(Key, Value)>, MerkleTree<TestTable, StorageMap<TestTable>>) {
+    fn random_tree(min: usize, max: usize)(kv in key_values(min, max)) -> (Vec<(Key, Value)>, MerkleTree<TestTable, StorageMap<TestTable>, TestHash>) {
         let storage = StorageMap::<TestTable>::new();
         let iter = kv.clone().into_iter().map(|(key, value)| (MerkleTreeKey::new(key), value));
-        let tree = MerkleTree::from_set(storage, iter).expect("Unable to create Merkle tree");
+        let tree = MerkleTree::from_set_with_hash(storage, iter).expect("Unable to create Merkle tree");
         (kv, tree)
     }
 }
@@ -126,7 +127,7 @@ proptest! {
 
         // When
         let inclusion = match proof {
-            Proof::Inclusion(proof) => proof.verify(&root, &key, value.as_ref()),
+            Proof::Inclusion(proof) => proof.verify_with_hash::<TestHash>(&root, &key, value.as_ref()),
             Proof::Exclusion(_) => panic!("Expected InclusionProof"),
         };
 
@@ -146,7 +147,7 @@ proptest! {
 
         // When
         let inclusion = match proof {
-            Proof::Inclusion(proof) => proof.verify(&root, &key, value.as_ref()),
+            Proof::Inclusion(proof) => proof.verify_with_hash::<TestHash>(&root, &key, value.as_ref()),
             Proof::Exclusion(_) => panic!("Expected InclusionProof"),
         };
 
@@ -166,7 +167,7 @@ proptest! {
         // When
         let exclusion = match proof {
             Proof::Inclusion(_) => panic!("Expected ExclusionProof"),
-            Proof::Exclusion(proof) => proof.verify(&root, &key),
+            Proof::Exclusion(proof) => proof.verify_with_hash::<TestHash>(&root, &key),
         };
 
         // Then
@@ -190,8 +191,8 @@ proptest! {
         };
 
         // When
-        let inclusion_result = inclusion_proof.verify(&root, &included_key, included_value.as_ref());
-        let exclusion_result = exlucion_proof.verify(&root, &included_key);
+        let inclusion_result = inclusion_proof.verify_with_hash::<TestHash>(&root, &included_key, included_value.as_ref());
+        let exclusion_result = exlucion_proof.verify_with_hash::<TestHash>(&root, &included_key);
 
         // Then
         prop_assert!(inclusion_result);
@@ -0,0 +1,47 @@
+#![allow(non_snake_case)]
+
+use crate::common::{
+    bytes32_bit,
+    bytes32_common_prefix_count,
+    Bytes32,
+    Msb,
+};
+use proptest::{
+    prop_assert_eq,
+    proptest,
+};
+
+proptest! {
+    #[test]
+    fn bytes32_bit__matches_the_generic_msb(key: Bytes32, index in 0..300u32) {
+        prop_assert_eq!(bytes32_bit(&key, index), key.get_bit_at_index_from_msb(index));
+    }
+
+    #[test]
+    fn bytes32_common_prefix_count__matches_the_generic_msb(
+        lhs: Bytes32,
+        rhs: Bytes32,
+        common_bits in 0..=256u32,
+    ) {
+        // Given
+        let mut rhs = rhs;
+        for index in 0..common_bits {
+            let byte = (index / 8) as usize;
+            let mask = 1 << (7 - index % 8);
+            rhs[byte] = (rhs[byte] & !mask) | (lhs[byte] & mask);
+        }
+
+        // When
+        let count = bytes32_common_prefix_count(&lhs, &rhs);
+
+        // Then
+        prop_assert_eq!(count, lhs.common_prefix_count(&rhs));
+        prop_assert_eq!(count, Msb::common_prefix_count(&rhs, &lhs));
+    }
+}
+
+#[test]
+fn bytes32_common_prefix_count__returns_256_for_equal_keys() {
+    let key = [0xa5; 32];
+    assert_eq!(bytes32_common_prefix_count(&key, &key), 256);
+}
@@ -0,0 +1,138 @@
+#![allow(non_snake_case)]
+
+use proptest::{
+    arbitrary::any,
+    collection::vec,
+    option,
+    prop_assert,
+    prop_assert_eq,
+    proptest,
+    strategy::Strategy,
+};
+
+use crate::{
+    common::{
+        Bytes32,
+        StorageMap,
+    },
+    sparse::{
+        in_memory::{
+            self,
+            NodesTable,
+        },
+        proof::Proof,
+        MerkleStorageAdapter,
+        MerkleTree,
+        MerkleTreeKey,
+    },
+};
+use fuel_storage::Mappable;
+use std::collections::BTreeMap;
+
+struct EncodedNodesTable;
+
+impl Mappable for EncodedNodesTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = Bytes32;
+    type OwnedValue = Vec<u8>;
+    type Value = [u8];
+}
+
+type Adapter = MerkleStorageAdapter<StorageMap<EncodedNodesTable>, EncodedNodesTable>;
+
+/// The updates of the trees, with keys from a small set so that some updates overwrite
+/// or delete the leaves of the previous ones. `None` deletes the key, and the `bool`
+/// commits the adapter after the update.
+fn updates() -> impl Strategy<Value = Vec<(u8, Option<Bytes32>, bool)>> {
+    vec(
+        (0..32u8, option::of(any::<Bytes32>()), any::<bool>()),
+        1..100,
+    )
+}
+
+fn key(i: u8) -> MerkleTreeKey {
+    MerkleTreeKey::new([i])
+}
+
+proptest! {
+    #[test]
+    fn merkle_storage_adapter__updates__match_the_in_memory_tree(updates in updates()) {
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut expected = in_memory::MerkleTree::new();
+        let mut leaves = BTreeMap::new();
+        let mut root = *MerkleTree::<NodesTable, Adapter>::empty_root();
+
+        for (i, value, commit) in updates {
+            // Given
+            let mut tree = MerkleTree::<NodesTable, _>::load(&mut adapter, &root).unwrap();
+
+            // When
+            match value {
+                Some(value) => {
+                    tree.update(key(i), &value).unwrap();
+                    expected.update(key(i), &value);
+                    leaves.insert(i, value);
+                }
+                None => {
+                    tree.delete(key(i)).unwrap();
+                    expected.delete(key(i));
+                    leaves.remove(&i);
+                }
+            }
+            root = tree.root();
+            if commit {
+                adapter.commit().unwrap();
+            }
+
+            // Then
+            prop_assert_eq!(root, expected.root());
+        }
+
+        adapter.commit().unwrap();
+        let tree = MerkleTree::<NodesTable, _>::load(Adapter::new(adapter.into_storage()), &root).unwrap();
+        prop_assert_eq!(tree.root(), root);
+        for i in 0..32u8 {
+            let proof = tree.generate_proof(&key(i)).unwrap();
+            match (leaves.get(&i), proof) {
+                (Some(value), Proof::Inclusion(proof)) => {
+                    prop_assert!(proof.verify(&root, &key(i), value))
+                }
+                (None, Proof::Exclusion(proof)) => prop_assert!(proof.verify(&root, &key(i))),
+                (_, proof) => prop_assert!(false, "Unexpected proof {:?}", proof),
+            }
+        }
+    }
+
+    #[test]
+    fn merkle_storage_adapter__commit__stores_only_the_nodes_of_the_last_root(updates in updates()) {
+        let mut adapter = Adapter::new(StorageMap::new());
+        let mut expected = MerkleTree::<NodesTable, _>::new(StorageMap::new());
+        let mut root = *MerkleTree::<NodesTable, Adapter>::empty_root();
+
+        for (i, value, commit) in updates {
+            // Given
+            let mut tree = MerkleTree::<NodesTable, _>::load(&mut adapter, &root).unwrap();
+
+            // When
+            match value {
+                Some(value) => {
+                    tree.update(key(i), &value).unwrap();
+                    expected.update(key(i), &value).unwrap();
+                }
+                None => {
+                    tree.delete(key(i)).unwrap();
+                    expected.delete(key(i)).unwrap();
+                }
+            }
+            root = tree.root();
+            if commit {
+                adapter.commit().unwrap();
+            }
+        }
+        adapter.commit().unwrap();
+
+        // Then
+        prop_assert_eq!(root, expected.root());
+        prop_assert_eq!(adapter.storage().len(), expected.storage().len());
+    }
+}
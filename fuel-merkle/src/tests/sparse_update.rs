@@ -0,0 +1,174 @@
+#![allow(non_snake_case)]
+
+use super::TestHash;
+use crate::{
+    common::{
+        Bytes32,
+        StorageMap,
+    },
+    sparse::{
+        proof::{
+            ExclusionLeaf,
+            ExclusionLeafData,
+            ExclusionProof,
+            Proof,
+        },
+        verify_and_update_with_hash,
+        MerkleTree,
+        MerkleTreeKey,
+        Primitive,
+        ProofError,
+        SiblingPreimage,
+    },
+};
+use fuel_storage::Mappable;
+use proptest::{
+    arbitrary::any,
+    collection::vec,
+    option,
+    prop_assert,
+    prop_assert_eq,
+    proptest,
+    strategy::Strategy,
+};
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct TestTable;
+
+impl Mappable for TestTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = Bytes32;
+    type OwnedValue = Primitive;
+    type Value = Self::OwnedValue;
+}
+
+/// The updates of the tree, with keys from a small set so that some updates insert
+/// new leaves and others overwrite or delete the leaves of the previous ones. `None`
+/// deletes the key.
+fn updates() -> impl Strategy<Value = Vec<(u8, Option<Bytes32>)>> {
+    vec((0..32u8, option::of(any::<Bytes32>())), 1..100)
+}
+
+fn key(i: u8) -> MerkleTreeKey {
+    MerkleTreeKey::new([i])
+}
+
+proptest! {
+    #[test]
+    fn verify_and_update__returns_the_root_of_the_updated_tree(updates in updates()) {
+        let mut tree = MerkleTree::<TestTable, _, TestHash>::new_with_hash(StorageMap::new());
+        let mut leaves = BTreeMap::new();
+
+        for (i, value) in updates {
+            // Given
+            let root = tree.root();
+            let proof = tree.generate_update_proof(&key(i)).unwrap();
+            let old_value = leaves.get(&i).map(|value: &Bytes32| value.as_slice());
+
+            // When
+            let new_root = verify_and_update_with_hash::<TestHash>(
+                &root,
+                &key(i),
+                old_value,
+                value.as_ref().map(|value| value.as_slice()),
+                &proof,
+            );
+            match value {
+                Some(value) => {
+                    tree.update(key(i), &value).unwrap();
+                    leaves.insert(i, value);
+                }
+                None => {
+                    tree.delete(key(i)).unwrap();
+                    leaves.remove(&i);
+                }
+            }
+
+            // Then
+            prop_assert_eq!(new_root, Ok(tree.root()));
+        }
+    }
+
+    #[test]
+    fn verify_and_update__fails_with_a_tampered_proof(
+        updates in updates(),
+        i: u8,
+        value in option::of(any::<Bytes32>()),
+        tampered_byte: usize,
+    ) {
+        let mut tree = MerkleTree::<TestTable, _, TestHash>::new_with_hash(StorageMap::new());
+        let mut leaves = BTreeMap::new();
+        for (i, value) in updates {
+            match value {
+                Some(value) => {
+                    tree.update(key(i), &value).unwrap();
+                    leaves.insert(i, value);
+                }
+                None => {
+                    tree.delete(key(i)).unwrap();
+                    leaves.remove(&i);
+                }
+            }
+        }
+        let i = i % 32;
+        let root = tree.root();
+        let old_value = leaves.get(&i).map(|value: &Bytes32| value.as_slice());
+        let new_value = value.as_ref().map(|value| value.as_slice());
+        let proof = tree.generate_update_proof(&key(i)).unwrap();
+
+        // Given
+        let mut tampered = proof.clone();
+        let byte = tampered_byte % 32;
+        let expected = match (&mut tampered.proof, &mut tampered.sibling) {
+            (_, Some(SiblingPreimage::Leaf { leaf_value: hash, .. }))
+            | (_, Some(SiblingPreimage::Node { left_child: hash, .. })) if new_value.is_none() => {
+                hash[byte] ^= 1;
+                ProofError::InvalidSibling
+            }
+            (Proof::Exclusion(proof), _) if proof.proof_set.is_empty() => {
+                match &mut proof.leaf {
+                    ExclusionLeaf::Leaf(data) => data.leaf_value[byte] ^= 1,
+                    ExclusionLeaf::Placeholder => {
+                        proof.leaf = ExclusionLeaf::Leaf(ExclusionLeafData {
+                            leaf_key: [0; 32],
+                            leaf_value: [0; 32],
+                        })
+                    }
+                }
+                ProofError::InvalidProof
+            }
+            (Proof::Inclusion(proof), _) if proof.proof_set.is_empty() => {
+                tampered.proof = Proof::Exclusion(ExclusionProof {
+                    proof_set: vec![],
+                    leaf: ExclusionLeaf::Placeholder,
+                });
+                ProofError::InvalidProof
+            }
+            (proof, _) => {
+                let proof_set = match proof {
+                    Proof::Inclusion(proof) => &mut proof.proof_set,
+                    Proof::Exclusion(proof) => &mut proof.proof_set,
+                };
+                let len = proof_set.len();
+                proof_set[tampered_byte % len][byte] ^= 1;
+                ProofError::InvalidProof
+            }
+        };
+
+        // When
+        let result = verify_and_update_with_hash::<TestHash>(
+            &root,
+            &key(i),
+            old_value,
+            new_value,
+            &tampered,
+        );
+
+        // Then
+        prop_assert_eq!(result, Err(expected));
+        prop_assert!(
+            verify_and_update_with_hash::<TestHash>(&root, &key(i), old_value, new_value, &proof).is_ok()
+        );
+    }
+}
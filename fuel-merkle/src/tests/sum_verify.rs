@@ -0,0 +1,123 @@
+#![allow(non_snake_case)]
+
+use proptest::{
+    arbitrary::any,
+    collection::vec,
+    prop_assert,
+    prop_assert_eq,
+    prop_assume,
+    proptest,
+    strategy::Strategy,
+};
+
+use crate::{
+    common::{
+        left_subtree_leaves_count,
+        Bytes32,
+        StorageMap,
+    },
+    sum::{
+        leaf_sum,
+        node_sum,
+        verify,
+        MerkleTree,
+        Node,
+    },
+};
+use fuel_storage::Mappable;
+
+struct TestTable;
+
+impl Mappable for TestTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = Bytes32;
+    type OwnedValue = Node;
+    type Value = Self::OwnedValue;
+}
+
+/// The leaves of a tree, with fees small enough for the sum of up to 1000 of them to fit
+/// in a `u64`.
+fn leaves() -> impl Strategy<Value = Vec<(u64, Bytes32)>> {
+    vec((0..u64::from(u32::MAX), any::<Bytes32>()), 1..1_000)
+}
+
+/// Computes the root of the leaves from scratch, independently of the tree.
+fn recompute_root(leaves: &[(u64, Bytes32)]) -> (u64, Bytes32) {
+    match leaves {
+        [(fee, data)] => (*fee, leaf_sum(*fee, data)),
+        _ => {
+            let count = u64::try_from(leaves.len()).unwrap();
+            let left_count = usize::try_from(left_subtree_leaves_count(count)).unwrap();
+            let (left_fee, left_hash) = recompute_root(&leaves[..left_count]);
+            let (right_fee, right_hash) = recompute_root(&leaves[left_count..]);
+            let hash = node_sum(left_fee, &left_hash, right_fee, &right_hash);
+            (left_fee + right_fee, hash)
+        }
+    }
+}
+
+fn tree(leaves: &[(u64, Bytes32)]) -> MerkleTree<TestTable, StorageMap<TestTable>> {
+    let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+    for (fee, data) in leaves.iter() {
+        tree.push(*fee, data).unwrap();
+    }
+    tree
+}
+
+proptest! {
+    #[test]
+    fn verify__returns_true_for_the_root_recomputed_from_all_leaves(leaves in leaves(), arb_num: usize) {
+        let index = arb_num % leaves.len();
+        let (fee, data) = leaves[index];
+
+        // Given
+        let mut tree = tree(&leaves);
+        let proof = tree.generate_proof(u64::try_from(index).unwrap()).unwrap();
+        let (root_sum, root_hash) = recompute_root(&leaves);
+
+        // When
+        let verification = verify(&root_hash, root_sum, (fee, &data), &proof);
+
+        // Then
+        prop_assert_eq!(tree.root().unwrap(), (root_sum, root_hash));
+        prop_assert!(verification);
+    }
+
+    #[test]
+    fn verify__returns_false_for_a_tampered_side_node_fee(leaves in leaves(), arb_num: usize, side: usize, delta in 1..u64::MAX) {
+        prop_assume!(leaves.len() > 1);
+        let index = arb_num % leaves.len();
+        let (fee, data) = leaves[index];
+
+        // Given
+        let mut tree = tree(&leaves);
+        let mut proof = tree.generate_proof(u64::try_from(index).unwrap()).unwrap();
+        let side = side % proof.proof_set.len();
+        proof.proof_set[side].0 = proof.proof_set[side].0.wrapping_add(delta);
+        let (root_sum, root_hash) = recompute_root(&leaves);
+
+        // When
+        let verification = verify(&root_hash, root_sum, (fee, &data), &proof);
+
+        // Then
+        prop_assert!(!verification);
+    }
+
+    #[test]
+    fn verify__returns_false_for_a_tampered_root_sum(leaves in leaves(), arb_num: usize, root_sum: u64) {
+        let index = arb_num % leaves.len();
+        let (fee, data) = leaves[index];
+
+        // Given
+        let mut tree = tree(&leaves);
+        let proof = tree.generate_proof(u64::try_from(index).unwrap()).unwrap();
+        let (expected_root_sum, root_hash) = recompute_root(&leaves);
+        prop_assume!(root_sum != expected_root_sum);
+
+        // When
+        let verification = verify(&root_hash, root_sum, (fee, &data), &proof);
+
+        // Then
+        prop_assert!(!verification);
+    }
+}
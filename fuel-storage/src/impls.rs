@@ -1,8 +1,12 @@
 use crate::{
+    BatchValues,
+    BoxedIter,
     Mappable,
     MerkleRoot,
     MerkleRootStorage,
+    StorageBatchInspect,
     StorageInspect,
+    StorageIter,
     StorageMut,
     StorageMutate,
     StorageRead,
@@ -49,6 +53,42 @@ impl<'a, T: StorageInspect<Type> + ?Sized, Type: Mappable> StorageInspect<Type>
     }
 }
 
+impl<'a, T: StorageBatchInspect<Type> + ?Sized, Type: Mappable> StorageBatchInspect<Type>
+    for &'a T
+{
+    fn get_batch<'b, 'k, Iter>(&'b self, keys: Iter) -> BatchValues<'b, Type, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        <T as StorageBatchInspect<Type>>::get_batch(self, keys)
+    }
+}
+
+impl<'a, T: StorageBatchInspect<Type> + ?Sized, Type: Mappable> StorageBatchInspect<Type>
+    for &'a mut T
+{
+    fn get_batch<'b, 'k, Iter>(&'b self, keys: Iter) -> BatchValues<'b, Type, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        <T as StorageBatchInspect<Type>>::get_batch(self, keys)
+    }
+}
+
+impl<'a, T: StorageIter<Type> + ?Sized, Type: Mappable> StorageIter<Type> for &'a T {
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, Type, Self::Error> {
+        <T as StorageIter<Type>>::iter_prefix(self, prefix)
+    }
+}
+
+impl<'a, T: StorageIter<Type> + ?Sized, Type: Mappable> StorageIter<Type> for &'a mut T {
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, Type, Self::Error> {
+        <T as StorageIter<Type>>::iter_prefix(self, prefix)
+    }
+}
+
 impl<'a, T: StorageMutate<Type> + ?Sized, Type: Mappable> StorageMutate<Type>
     for &'a mut T
 {
@@ -103,6 +143,15 @@ impl<'a, T: StorageRead<Type> + StorageSize<Type> + ?Sized, Type: Mappable>
     ) -> Result<Option<alloc::vec::Vec<u8>>, Self::Error> {
         <T as StorageRead<Type>>::read_alloc(self, key)
     }
+
+    fn read_range(
+        &self,
+        key: &<Type as Mappable>::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        <T as StorageRead<Type>>::read_range(self, key, offset, buf)
+    }
 }
 
 impl<'a, T: StorageRead<Type> + StorageSize<Type> + ?Sized, Type: Mappable>
@@ -122,6 +171,15 @@ impl<'a, T: StorageRead<Type> + StorageSize<Type> + ?Sized, Type: Mappable>
     ) -> Result<Option<alloc::vec::Vec<u8>>, Self::Error> {
         <T as StorageRead<Type>>::read_alloc(self, key)
     }
+
+    fn read_range(
+        &self,
+        key: &<Type as Mappable>::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        <T as StorageRead<Type>>::read_range(self, key, offset, buf)
+    }
 }
 
 impl<'a, T: StorageWrite<Type> + ?Sized, Type: Mappable> StorageWrite<Type>
@@ -142,6 +200,15 @@ impl<'a, T: StorageWrite<Type> + ?Sized, Type: Mappable> StorageWrite<Type>
     fn take(&mut self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
         <T as StorageWrite<Type>>::take(self, key)
     }
+
+    fn write_range(
+        &mut self,
+        key: &Type::Key,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, Self::Error> {
+        <T as StorageWrite<Type>>::write_range(self, key, offset, buf)
+    }
 }
 
 impl<'a, T: MerkleRootStorage<Key, Type> + ?Sized, Key, Type: Mappable>
@@ -167,6 +234,24 @@ impl<'a, T: StorageInspect<Type>, Type: Mappable> StorageRef<'a, T, Type> {
     }
 }
 
+impl<'a, T: StorageBatchInspect<Type>, Type: Mappable> StorageRef<'a, T, Type> {
+    #[inline(always)]
+    pub fn get_batch<'k, Iter>(self, keys: Iter) -> BatchValues<'a, Type, T::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        self.0.get_batch(keys)
+    }
+}
+
+impl<'a, T: StorageIter<Type>, Type: Mappable> StorageRef<'a, T, Type> {
+    #[inline(always)]
+    pub fn iter_prefix(self, prefix: &[u8]) -> BoxedIter<'a, Type, T::Error> {
+        self.0.iter_prefix(prefix)
+    }
+}
+
 impl<'a, T, Type: Mappable> StorageRef<'a, T, Type> {
     #[inline(always)]
     pub fn root<Key>(self, key: &Key) -> Result<MerkleRoot, T::Error>
@@ -194,6 +279,16 @@ impl<'a, T: StorageRead<Type>, Type: Mappable> StorageRef<'a, T, Type> {
     ) -> Result<Option<alloc::vec::Vec<u8>>, T::Error> {
         self.0.read_alloc(key)
     }
+
+    #[inline(always)]
+    pub fn read_range(
+        &self,
+        key: &<Type as Mappable>::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, T::Error> {
+        self.0.read_range(key, offset, buf)
+    }
 }
 
 impl<'a, T: StorageInspect<Type>, Type: Mappable> StorageMut<'a, T, Type> {
@@ -213,6 +308,28 @@ impl<'a, T: StorageInspect<Type>, Type: Mappable> StorageMut<'a, T, Type> {
     }
 }
 
+impl<'a, T: StorageBatchInspect<Type>, Type: Mappable> StorageMut<'a, T, Type> {
+    #[inline(always)]
+    pub fn get_batch<'k, Iter>(self, keys: Iter) -> BatchValues<'a, Type, T::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        // Workaround, because compiler doesn't convert the lifetime to `'a` by default.
+        let self_: &'a T = self.0;
+        self_.get_batch(keys)
+    }
+}
+
+impl<'a, T: StorageIter<Type>, Type: Mappable> StorageMut<'a, T, Type> {
+    #[inline(always)]
+    pub fn iter_prefix(self, prefix: &[u8]) -> BoxedIter<'a, Type, T::Error> {
+        // Workaround, because compiler doesn't convert the lifetime to `'a` by default.
+        let self_: &'a T = self.0;
+        self_.iter_prefix(prefix)
+    }
+}
+
 impl<'a, T: StorageMutate<Type>, Type: Mappable> StorageMut<'a, T, Type> {
     #[inline(always)]
     pub fn insert(
@@ -261,4 +378,14 @@ impl<'a, T: StorageWrite<Type>, Type: Mappable> StorageMut<'a, T, Type> {
     pub fn take(&mut self, key: &Type::Key) -> Result<Option<Vec<u8>>, T::Error> {
         self.0.take(key)
     }
+
+    #[inline(always)]
+    pub fn write_range(
+        &mut self,
+        key: &Type::Key,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, T::Error> {
+        self.0.write_range(key, offset, buf)
+    }
 }
@@ -12,12 +12,30 @@ use alloc::{
         Cow,
         ToOwned,
     },
+    boxed::Box,
     vec::Vec,
 };
 
 /// Merkle root alias type
 pub type MerkleRoot = [u8; 32];
 
+/// The iterator over the `Key->Value` mappings of [`StorageIter::iter_prefix`].
+pub type BoxedIter<'a, Type, Error> = Box<
+    dyn Iterator<
+            Item = Result<
+                (
+                    <Type as Mappable>::OwnedKey,
+                    Cow<'a, <Type as Mappable>::OwnedValue>,
+                ),
+                Error,
+            >,
+        > + 'a,
+>;
+
+/// The values of the keys retrieved by [`StorageBatchInspect::get_batch`].
+pub type BatchValues<'a, Type, Error> =
+    Vec<Result<Option<Cow<'a, <Type as Mappable>::OwnedValue>>, Error>>;
+
 /// Mappable type with `Key` and `Value`.
 ///
 /// # Example
@@ -49,6 +67,28 @@ pub trait Mappable {
     type OwnedValue: From<<Self::Value as ToOwned>::Owned> + Clone;
 }
 
+/// The columns of a key-value store holding several tables, like the column families
+/// of RocksDB.
+pub trait StorageColumns: Copy + Eq + 'static {
+    /// All the columns, in the order of their ids.
+    const ALL: &'static [Self];
+
+    /// The id of the column, unique among the columns and stable across the versions.
+    fn id(&self) -> u32;
+
+    /// The name of the column.
+    fn name(&self) -> &'static str;
+}
+
+/// [`Mappable`] table stored in a column of a key-value store.
+pub trait TableColumn: Mappable {
+    /// The columns of the key-value store.
+    type Column: StorageColumns;
+
+    /// The column holding the `Key->Value` mappings of the table.
+    const COLUMN: Self::Column;
+}
+
 /// Base read storage trait for Fuel infrastructure.
 ///
 /// Generic should implement [`Mappable`] trait with all storage type information.
@@ -62,6 +102,33 @@ pub trait StorageInspect<Type: Mappable> {
     fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error>;
 }
 
+/// Base storage trait for Fuel infrastructure.
+///
+/// Allows retrieving the values of many keys with one call, for the backends that
+/// read several keys at once faster than one by one, like the multi-get of RocksDB.
+/// The default implementation retrieves the keys one by one.
+pub trait StorageBatchInspect<Type: Mappable>: StorageInspect<Type> {
+    /// Retrieve `Cow<Value>` of each of the `keys`, in the order of the keys.
+    fn get_batch<'a, 'k, Iter>(&'a self, keys: Iter) -> BatchValues<'a, Type, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        keys.into_iter().map(|key| self.get(key)).collect()
+    }
+}
+
+/// Base storage trait for Fuel infrastructure.
+///
+/// Allows iterating over the `Key->Value` mappings with the keys starting with a
+/// prefix, like all the entries of one contract in a table keyed by the contract.
+pub trait StorageIter<Type: Mappable>: StorageInspect<Type> {
+    /// Iterate over the `Key->Value` mappings with the bytes of the key starting with
+    /// the `prefix`, in the lexicographic order of the bytes of the keys. The empty
+    /// prefix iterates over all the mappings.
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, Type, Self::Error>;
+}
+
 /// Base storage trait for Fuel infrastructure.
 ///
 /// Generic should implement [`Mappable`] trait with all storage type information.
@@ -117,6 +184,31 @@ pub trait StorageRead<Type: Mappable>: StorageInspect<Type> + StorageSize<Type>
     ///
     /// Checks the size of the value and allocates a buffer of that size.
     fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Read the bytes of the value stored at the given key from the `offset` into the
+    /// provided buffer if the value exists.
+    ///
+    /// Does not perform any deserialization.
+    ///
+    /// Returns None if the value does not exist.
+    /// Otherwise, returns the number of bytes read, which is less than the length of the
+    /// buffer if the value ends before filling it.
+    ///
+    /// The default implementation reads the whole value with `read_alloc`, so the
+    /// storages should override it to read only the requested bytes.
+    fn read_range(
+        &self,
+        key: &Type::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        Ok(self.read_alloc(key)?.map(|value| {
+            let value = value.get(offset..).unwrap_or_default();
+            let len = buf.len().min(value.len());
+            buf[..len].copy_from_slice(&value[..len]);
+            len
+        }))
+    }
 }
 
 /// Base storage trait for Fuel infrastructure.
@@ -148,6 +240,32 @@ pub trait StorageWrite<Type: Mappable>: StorageMutate<Type> {
 
     /// Removes a value from the storage and returning it without deserializing it.
     fn take(&mut self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Write the provided buffer into the value stored at the given key from the
+    /// `offset`. The value is created if it doesn't exist, and extended with zeros if it
+    /// ends before the `offset`.
+    ///
+    /// Does not perform any serialization.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// The default implementation takes and writes back the whole value, so the storages
+    /// should override it to write only the provided bytes.
+    fn write_range(
+        &mut self,
+        key: &Type::Key,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, Self::Error> {
+        let mut value = self.take(key)?.unwrap_or_default();
+        let end = offset.saturating_add(buf.len());
+        if value.len() < end {
+            value.resize(end, 0);
+        }
+        value[offset..end].copy_from_slice(buf);
+        self.write(key, &value)?;
+        Ok(buf.len())
+    }
 }
 
 /// Returns the merkle root for the `StorageType` per merkle `Key`. Per one storage, it is
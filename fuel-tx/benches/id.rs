@@ -0,0 +1,74 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use fuel_crypto::Hasher;
+use fuel_tx::{
+    field::Witnesses,
+    policies::Policies,
+    Input,
+    Transaction,
+    UniqueIdentifier,
+    Witness,
+    WitnessIndex,
+};
+use fuel_types::{
+    canonical::Serialize,
+    ChainId,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+fn id(c: &mut Criterion) {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let chain_id = ChainId::default();
+
+    let witness: Witness = (0..1024 * 1024)
+        .map(|_| rng.gen::<u8>())
+        .collect::<Vec<_>>()
+        .into();
+    let tx = Transaction::script(
+        1_000_000,
+        vec![],
+        vec![],
+        Policies::new().with_max_fee(0),
+        vec![Input::coin_signed(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            WitnessIndex::new(0),
+        )],
+        vec![],
+        vec![witness],
+    );
+
+    c.bench_function("id_1mib_witness", |b| {
+        b.iter(|| black_box(tx.id(&chain_id)))
+    });
+
+    // The previous implementation: the whole transaction is cloned and serialized into
+    // the buffer before hashing.
+    c.bench_function("buffered_id_1mib_witness", |b| {
+        b.iter(|| {
+            let mut tx = tx.clone();
+            tx.witnesses_mut().clear();
+            let bytes = tx.to_bytes();
+            black_box(
+                Hasher::default()
+                    .chain(chain_id.to_be_bytes())
+                    .chain(bytes)
+                    .finalize(),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, id);
+criterion_main!(benches);
@@ -0,0 +1,636 @@
+//! Implementations of [`Arbitrary`] for transactions and receipts used by fuzzing.
+//!
+//! The generated values are structurally valid: they serialize and deserialize back
+//! into themselves, enum discriminants are valid, and the sizes of dynamic fields are
+//! bounded by the default consensus parameters. The semantic rules, like unique inputs,
+//! matching witness indices or balances, are not enforced, so most of the values fail
+//! the checks. Use [`arbitrary_checkable`] to get transactions that are likely to pass
+//! them and reach the execution.
+
+use crate::{
+    field,
+    input::{
+        coin::CoinSigned,
+        message::{
+            MessageCoinSigned,
+            MessageDataSigned,
+        },
+    },
+    policies::Policies,
+    ContractParameters,
+    Create,
+    Input,
+    Mint,
+    Output,
+    PredicateParameters,
+    Receipt,
+    Script,
+    ScriptExecutionResult,
+    ScriptParameters,
+    Signable,
+    StorageSlot,
+    Transaction,
+    TxParameters,
+    TxPointer,
+    UtxoId,
+    Witness,
+    WitnessIndex,
+};
+use ::arbitrary::{
+    Arbitrary,
+    Error,
+    Result,
+    Unstructured,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_crypto::{
+    Hasher,
+    SecretKey,
+};
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    ChainId,
+    Word,
+};
+
+/// The maximum number of instructions in the script of [`arbitrary_checkable`].
+const CHECKABLE_MAX_INSTRUCTIONS: usize = 64;
+
+/// The maximum size of the script data in [`arbitrary_checkable`].
+const CHECKABLE_MAX_SCRIPT_DATA_LENGTH: u64 = 256;
+
+fn array<T, const N: usize>(u: &mut Unstructured) -> Result<T>
+where
+    T: From<[u8; N]>,
+{
+    Ok(u.arbitrary::<[u8; N]>()?.into())
+}
+
+fn bytes(u: &mut Unstructured, max_len: u64) -> Result<Vec<u8>> {
+    let max_len = usize::try_from(max_len).unwrap_or(usize::MAX);
+    let len = u.arbitrary_len::<u8>()?.min(max_len);
+
+    Ok(u.bytes(len)?.to_vec())
+}
+
+/// Returns bytes with at least one element. The canonical decoding relies on empty
+/// predicates and message data to select the variant of the input.
+fn non_empty_bytes(u: &mut Unstructured, max_len: u64) -> Result<Vec<u8>> {
+    let mut bytes = bytes(u, max_len)?;
+    if bytes.is_empty() {
+        bytes.push(u.arbitrary()?);
+    }
+
+    Ok(bytes)
+}
+
+fn items<'a, T, F>(u: &mut Unstructured<'a>, max_len: u64, mut item: F) -> Result<Vec<T>>
+where
+    F: FnMut(&mut Unstructured<'a>) -> Result<T>,
+{
+    let max_len = usize::try_from(max_len).unwrap_or(usize::MAX);
+    let mut items = Vec::new();
+    while items.len() < max_len && !u.is_empty() && u.arbitrary()? {
+        items.push(item(u)?);
+    }
+
+    Ok(items)
+}
+
+fn utxo_id(u: &mut Unstructured) -> Result<UtxoId> {
+    Ok(UtxoId::new(array(u)?, u.arbitrary()?))
+}
+
+fn tx_pointer(u: &mut Unstructured) -> Result<TxPointer> {
+    Ok(TxPointer::new(block_height(u)?, u.arbitrary()?))
+}
+
+fn block_height(u: &mut Unstructured) -> Result<BlockHeight> {
+    Ok(u.arbitrary::<u32>()?.into())
+}
+
+fn witness_index(u: &mut Unstructured) -> Result<WitnessIndex> {
+    let max_witnesses =
+        u16::try_from(TxParameters::DEFAULT.max_witnesses()).unwrap_or(u16::MAX);
+
+    u.int_in_range(0..=max_witnesses).map(WitnessIndex::new)
+}
+
+fn storage_slot(u: &mut Unstructured) -> Result<StorageSlot> {
+    Ok(StorageSlot::new(array(u)?, array(u)?))
+}
+
+/// Points most of the witness indices of the signed inputs to existing witnesses,
+/// leaving the rest inconsistent.
+fn align_witness_indices<Tx>(u: &mut Unstructured, tx: &mut Tx) -> Result<()>
+where
+    Tx: field::Inputs + field::Witnesses,
+{
+    let witnesses = u16::try_from(tx.witnesses().len()).unwrap_or(u16::MAX);
+    if witnesses == 0 {
+        return Ok(());
+    }
+
+    for input in tx.inputs_mut() {
+        match input {
+            Input::CoinSigned(CoinSigned { witness_index, .. })
+            | Input::MessageCoinSigned(MessageCoinSigned { witness_index, .. })
+            | Input::MessageDataSigned(MessageDataSigned { witness_index, .. })
+                if u.ratio(7, 8)? =>
+            {
+                *witness_index %= witnesses;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+impl<'a> Arbitrary<'a> for Witness {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(bytes(u, TxParameters::DEFAULT.max_size())?.into())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Policies {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut policies = Policies::new();
+
+        if u.arbitrary()? {
+            policies = policies.with_tip(u.arbitrary()?);
+        }
+        if u.arbitrary()? {
+            policies = policies.with_witness_limit(u.arbitrary()?);
+        }
+        if u.arbitrary()? {
+            policies = policies.with_max_fee(u.arbitrary()?);
+        }
+
+        let maturity: u32 = if u.arbitrary()? {
+            let maturity: u32 = u.arbitrary()?;
+            policies = policies.with_maturity(maturity.into());
+            maturity
+        } else {
+            0
+        };
+        if u.arbitrary()? {
+            let expiration = u.int_in_range(maturity..=u32::MAX)?;
+            policies = policies.with_expiration(expiration.into());
+        }
+
+        Ok(policies)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let params = PredicateParameters::DEFAULT;
+        let max_predicate_length = params.max_predicate_length();
+        let max_predicate_data_length = params.max_predicate_data_length();
+        let max_message_data_length = params.max_message_data_length();
+
+        let input = match u.int_in_range(0..=6u8)? {
+            0 => Input::coin_signed(
+                utxo_id(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                tx_pointer(u)?,
+                witness_index(u)?,
+            ),
+            1 => Input::coin_predicate(
+                utxo_id(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                tx_pointer(u)?,
+                u.arbitrary()?,
+                non_empty_bytes(u, max_predicate_length)?,
+                bytes(u, max_predicate_data_length)?,
+            ),
+            2 => Input::contract(
+                utxo_id(u)?,
+                array(u)?,
+                array(u)?,
+                tx_pointer(u)?,
+                array(u)?,
+            ),
+            3 => Input::message_coin_signed(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                witness_index(u)?,
+            ),
+            4 => Input::message_coin_predicate(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                non_empty_bytes(u, max_predicate_length)?,
+                bytes(u, max_predicate_data_length)?,
+            ),
+            5 => Input::message_data_signed(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                witness_index(u)?,
+                non_empty_bytes(u, max_message_data_length)?,
+            ),
+            _ => Input::message_data_predicate(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                non_empty_bytes(u, max_message_data_length)?,
+                non_empty_bytes(u, max_predicate_length)?,
+                bytes(u, max_predicate_data_length)?,
+            ),
+        };
+
+        Ok(input)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Output {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let output = match u.int_in_range(0..=4u8)? {
+            0 => Output::coin(array(u)?, u.arbitrary()?, array(u)?),
+            1 => Output::contract(
+                u.int_in_range(0..=TxParameters::DEFAULT.max_inputs())?,
+                array(u)?,
+                array(u)?,
+            ),
+            2 => Output::change(array(u)?, u.arbitrary()?, array(u)?),
+            3 => Output::variable(array(u)?, u.arbitrary()?, array(u)?),
+            _ => Output::contract_created(array(u)?, array(u)?),
+        };
+
+        Ok(output)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Script {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tx_params = TxParameters::DEFAULT;
+        let script_params = ScriptParameters::DEFAULT;
+
+        let mut tx = Transaction::script(
+            u.arbitrary()?,
+            bytes(u, script_params.max_script_length())?,
+            bytes(u, script_params.max_script_data_length())?,
+            u.arbitrary()?,
+            items(u, tx_params.max_inputs().into(), Input::arbitrary)?,
+            items(u, tx_params.max_outputs().into(), Output::arbitrary)?,
+            items(u, tx_params.max_witnesses().into(), Witness::arbitrary)?,
+        );
+        align_witness_indices(u, &mut tx)?;
+
+        Ok(tx)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Create {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tx_params = TxParameters::DEFAULT;
+        let contract_params = ContractParameters::DEFAULT;
+
+        let witnesses = items(u, tx_params.max_witnesses().into(), Witness::arbitrary)?;
+        let bytecode_witness_index = if !witnesses.is_empty() && u.ratio(7, 8)? {
+            u.choose_index(witnesses.len())?
+                .try_into()
+                .unwrap_or(u16::MAX)
+        } else {
+            u.arbitrary()?
+        };
+
+        let mut tx = Transaction::create(
+            bytecode_witness_index,
+            u.arbitrary()?,
+            array(u)?,
+            items(u, contract_params.max_storage_slots(), storage_slot)?,
+            items(u, tx_params.max_inputs().into(), Input::arbitrary)?,
+            items(u, tx_params.max_outputs().into(), Output::arbitrary)?,
+            witnesses,
+        );
+        align_witness_indices(u, &mut tx)?;
+
+        Ok(tx)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Mint {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let input_contract = crate::input::contract::Contract {
+            utxo_id: utxo_id(u)?,
+            balance_root: array(u)?,
+            state_root: array(u)?,
+            tx_pointer: tx_pointer(u)?,
+            contract_id: array(u)?,
+        };
+        let output_contract = crate::output::contract::Contract {
+            input_index: u.arbitrary()?,
+            balance_root: array(u)?,
+            state_root: array(u)?,
+        };
+
+        Ok(Transaction::mint(
+            tx_pointer(u)?,
+            input_contract,
+            output_contract,
+            u.arbitrary()?,
+            array(u)?,
+            u.arbitrary()?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transaction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tx = match u.int_in_range(0..=2u8)? {
+            0 => Script::arbitrary(u)?.into(),
+            1 => Create::arbitrary(u)?.into(),
+            _ => Mint::arbitrary(u)?.into(),
+        };
+
+        Ok(tx)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ScriptExecutionResult {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let result = match u.int_in_range(0..=3u8)? {
+            0 => ScriptExecutionResult::Success,
+            1 => ScriptExecutionResult::Revert,
+            2 => ScriptExecutionResult::Panic,
+            _ => ScriptExecutionResult::GenericFailure(u.arbitrary()?),
+        };
+
+        Ok(result)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Receipt {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let receipt = match u.int_in_range(0..=13u8)? {
+            0 => Receipt::call(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            1 => Receipt::ret(array(u)?, u.arbitrary()?, u.arbitrary()?, u.arbitrary()?),
+            2 => Receipt::return_data_with_len(
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                None,
+            ),
+            3 => {
+                Receipt::panic(array(u)?, u.arbitrary()?, u.arbitrary()?, u.arbitrary()?)
+            }
+            4 => {
+                Receipt::revert(array(u)?, u.arbitrary()?, u.arbitrary()?, u.arbitrary()?)
+            }
+            5 => Receipt::log(
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            6 => Receipt::log_data_with_len(
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                None,
+            ),
+            7 => Receipt::transfer(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            8 => Receipt::transfer_out(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            9 => Receipt::script_result(u.arbitrary()?, u.arbitrary()?),
+            10 => Receipt::message_out_with_len(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                u.arbitrary()?,
+                array(u)?,
+                None,
+            ),
+            11 => Receipt::mint(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            _ => Receipt::burn(
+                array(u)?,
+                array(u)?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+        };
+
+        Ok(receipt)
+    }
+}
+
+fn secret_key(u: &mut Unstructured) -> Result<SecretKey> {
+    let seed: [u8; 32] = u.arbitrary()?;
+
+    SecretKey::try_from(Hasher::hash(seed)).map_err(|_| Error::IncorrectFormat)
+}
+
+/// Returns the script transaction that is likely to pass all checks with the
+/// standard consensus parameters at the genesis block height, so fuzzing reaches the
+/// execution.
+///
+/// The transaction spends coins signed by the keys derived from `u`, its outputs don't
+/// exceed the inputs, and its script is made of instructions that can be decoded.
+pub fn arbitrary_checkable(u: &mut Unstructured) -> Result<Script> {
+    let chain_id = ChainId::default();
+    let assets = [AssetId::BASE, array(u)?];
+
+    let secrets = (0..u.int_in_range(1..=3)?)
+        .map(|_| secret_key(u))
+        .collect::<Result<Vec<_>>>()?;
+    let witnesses = secrets.iter().map(|_| Witness::default()).collect();
+
+    let mut balances = BTreeMap::<AssetId, Word>::new();
+    let mut inputs = Vec::new();
+    for output_index in 0..u.int_in_range(1..=4u16)? {
+        let signer = u.choose_index(secrets.len())?;
+        let asset_id = *u.choose(&assets)?;
+        let amount = u.int_in_range(1..=1_000_000_000)?;
+        let balance = balances.entry(asset_id).or_default();
+        *balance = balance.saturating_add(amount);
+
+        inputs.push(Input::coin_signed(
+            UtxoId::new(array(u)?, output_index),
+            Input::owner(&secrets[signer].public_key()),
+            amount,
+            asset_id,
+            tx_pointer(u)?,
+            WitnessIndex::new(signer.try_into().unwrap_or(u16::MAX)),
+        ));
+    }
+
+    let mut outputs = Vec::new();
+    for (asset_id, balance) in balances {
+        if u.arbitrary()? {
+            outputs.push(Output::coin(
+                array(u)?,
+                u.int_in_range(0..=balance)?,
+                asset_id,
+            ));
+        }
+        outputs.push(Output::change(array(u)?, 0, asset_id));
+    }
+
+    let mut script = Vec::new();
+    for _ in 0..u.int_in_range(0..=CHECKABLE_MAX_INSTRUCTIONS)? {
+        let raw: u32 = u.arbitrary()?;
+        let instruction = Instruction::try_from(raw).unwrap_or(op::noop());
+        script.extend(instruction.to_bytes());
+    }
+    script.extend(op::ret(RegId::ONE).to_bytes());
+
+    let mut tx = Transaction::script(
+        u.int_in_range(0..=1_000_000)?,
+        script,
+        bytes(u, CHECKABLE_MAX_SCRIPT_DATA_LENGTH)?,
+        Policies::new().with_max_fee(0),
+        inputs,
+        outputs,
+        witnesses,
+    );
+    for secret in &secrets {
+        tx.sign_inputs(secret, &chain_id);
+    }
+
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_types::canonical::{
+        Deserialize,
+        Serialize,
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    const SAMPLES: usize = 1_000;
+
+    fn assert_round_trip<T>()
+    where
+        T: for<'a> Arbitrary<'a> + Serialize + Deserialize + PartialEq + core::fmt::Debug,
+    {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+
+        for _ in 0..SAMPLES {
+            let len = rng.gen_range(0..4096);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen::<u8>()).collect();
+            let Ok(value) = T::arbitrary(&mut Unstructured::new(&data)) else {
+                continue
+            };
+
+            let bytes = value.to_bytes();
+            let decoded = T::from_bytes(&bytes).expect("Should decode the value");
+            assert_eq!(value, decoded);
+        }
+    }
+
+    #[test]
+    fn transaction_round_trip() {
+        assert_round_trip::<Transaction>();
+    }
+
+    #[test]
+    fn input_round_trip() {
+        assert_round_trip::<Input>();
+    }
+
+    #[test]
+    fn output_round_trip() {
+        assert_round_trip::<Output>();
+    }
+
+    #[test]
+    fn witness_round_trip() {
+        assert_round_trip::<Witness>();
+    }
+
+    #[test]
+    fn policies_round_trip() {
+        assert_round_trip::<Policies>();
+    }
+
+    #[test]
+    fn receipt_round_trip() {
+        assert_round_trip::<Receipt>();
+    }
+
+    #[test]
+    fn arbitrary_checkable_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+
+        for _ in 0..SAMPLES {
+            let data: Vec<u8> = (0..512).map(|_| rng.gen::<u8>()).collect();
+            let tx = arbitrary_checkable(&mut Unstructured::new(&data))
+                .expect("Should produce a transaction");
+
+            let decoded =
+                Script::from_bytes(&tx.to_bytes()).expect("Should decode the tx");
+            assert_eq!(tx, decoded);
+        }
+    }
+}
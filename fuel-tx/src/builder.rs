@@ -1,10 +1,12 @@
 use crate::{
     input,
+    multisig,
     output,
     transaction::{
         field::{
             self,
             BytecodeWitnessIndex,
+            Expiration,
             Maturity,
             Tip,
             Witnesses,
@@ -31,7 +33,10 @@ use crate::{
     UpgradePurpose,
     Upload,
     UploadBody,
+    UtxoId,
+    ValidityError,
     Witness,
+    WitnessIndex,
 };
 
 use crate::{
@@ -52,11 +57,27 @@ use crate::{
     },
 };
 use alloc::{
-    collections::BTreeMap,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     vec::Vec,
 };
-use fuel_crypto::SecretKey;
+use core::{
+    cmp::Reverse,
+    iter,
+};
+#[cfg(feature = "async")]
+use fuel_crypto::AsyncSigner;
+use fuel_crypto::{
+    SecretKey,
+    Signature,
+    Signer,
+    SignerError,
+};
 use fuel_types::{
+    canonical::Serialize,
+    Address,
     AssetId,
     BlockHeight,
     ChainId,
@@ -109,6 +130,84 @@ impl<T> BuildableSet for T where T: BuildableAloc + BuildableStd {}
 
 impl<T> Buildable for T where T: BuildableSet {}
 
+/// A spendable resource that can be selected by
+/// [`TransactionBuilder::add_inputs_for_amount`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AvailableCoin {
+    /// The coin UTXO owned by the `secret`.
+    Coin {
+        secret: SecretKey,
+        utxo_id: UtxoId,
+        amount: Word,
+        asset_id: AssetId,
+        tx_pointer: TxPointer,
+    },
+    /// The message without data sent to the owner of the `secret`. It is always spent
+    /// as the base asset.
+    Message {
+        secret: SecretKey,
+        sender: Address,
+        nonce: Nonce,
+        amount: Word,
+    },
+}
+
+impl AvailableCoin {
+    pub const fn amount(&self) -> Word {
+        match self {
+            Self::Coin { amount, .. } | Self::Message { amount, .. } => *amount,
+        }
+    }
+
+    pub const fn asset_id<'a>(&'a self, base_asset_id: &'a AssetId) -> &'a AssetId {
+        match self {
+            Self::Coin { asset_id, .. } => asset_id,
+            Self::Message { .. } => base_asset_id,
+        }
+    }
+
+    /// Returns `true` if the `input` spends this coin.
+    fn is_spent_by(&self, input: &Input) -> bool {
+        match self {
+            Self::Coin { utxo_id, .. } => input.utxo_id() == Some(utxo_id),
+            Self::Message { nonce, .. } => input.nonce() == Some(nonce),
+        }
+    }
+}
+
+/// The totals of one asset of the transaction, returned by
+/// [`TransactionBuilder::balance_sheet`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AssetBalance {
+    /// The sum of the spendable inputs.
+    pub inputs: Word,
+    /// The sum of the coin outputs.
+    pub coin_outputs: Word,
+    /// The max fee of the transaction. It is zero for all assets except the base asset.
+    pub max_fee: Word,
+    /// The number of change outputs.
+    pub change_outputs: usize,
+}
+
+impl AssetBalance {
+    /// Returns the amount left to the change output after the coin outputs and the
+    /// max fee, or `None` if the inputs don't cover them.
+    pub const fn change(&self) -> Option<Word> {
+        match self.inputs.checked_sub(self.coin_outputs) {
+            Some(remaining) => remaining.checked_sub(self.max_fee),
+            None => None,
+        }
+    }
+
+    /// Returns the amount missing from the inputs to cover the coin outputs and the
+    /// max fee.
+    pub const fn deficit(&self) -> Word {
+        self.coin_outputs
+            .saturating_add(self.max_fee)
+            .saturating_sub(self.inputs)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TransactionBuilder<Tx> {
     tx: Tx,
@@ -118,7 +217,7 @@ pub struct TransactionBuilder<Tx> {
     // We take the key by reference so this lib won't have the responsibility to properly
     // zeroize the keys
     // Maps signing keys -> witness indexes
-    sign_keys: BTreeMap<SecretKey, u16>,
+    sign_keys: BTreeMap<SecretKey, WitnessIndex>,
 }
 
 impl TransactionBuilder<Script> {
@@ -342,6 +441,12 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    pub fn expiration(&mut self, expiration: BlockHeight) -> &mut Self {
+        self.tx.set_expiration(expiration);
+
+        self
+    }
+
     pub fn witness_limit(&mut self, witness_limit: Word) -> &mut Self {
         self.tx.set_witness_limit(witness_limit);
 
@@ -419,6 +524,59 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    /// Adds a coin input spendable by the `predicate`. The owner of the coin is derived
+    /// from the predicate code with [`Input::predicate_owner`], so it always matches.
+    ///
+    /// The `predicate_gas_used` is zero until the predicates are estimated.
+    pub fn add_predicate_coin_input(
+        &mut self,
+        utxo_id: crate::UtxoId,
+        amount: Word,
+        asset_id: fuel_types::AssetId,
+        tx_pointer: TxPointer,
+        predicate: Vec<u8>,
+        predicate_data: Vec<u8>,
+    ) -> &mut Self {
+        let owner = Input::predicate_owner(&predicate);
+
+        self.add_predicate_coin_input_with_owner(
+            owner,
+            utxo_id,
+            amount,
+            asset_id,
+            tx_pointer,
+            predicate,
+            predicate_data,
+        )
+    }
+
+    /// Same as [`Self::add_predicate_coin_input`], but uses the given `owner` without
+    /// comparing it to the owner of the predicate. Allows building transactions that
+    /// intentionally fail the checks.
+    pub fn add_predicate_coin_input_with_owner(
+        &mut self,
+        owner: fuel_types::Address,
+        utxo_id: crate::UtxoId,
+        amount: Word,
+        asset_id: fuel_types::AssetId,
+        tx_pointer: TxPointer,
+        predicate: Vec<u8>,
+        predicate_data: Vec<u8>,
+    ) -> &mut Self {
+        self.tx.add_input(Input::coin_predicate(
+            utxo_id,
+            owner,
+            amount,
+            asset_id,
+            tx_pointer,
+            0,
+            predicate,
+            predicate_data,
+        ));
+
+        self
+    }
+
     pub fn inputs(&self) -> &[Input] {
         self.tx.inputs()
     }
@@ -437,14 +595,394 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
-    pub fn add_witness(&mut self, witness: Witness) -> &mut Self {
+    /// Appends the `witness` and returns its index, to be referenced by the inputs.
+    pub fn add_witness(&mut self, witness: Witness) -> WitnessIndex {
+        let index = u16::try_from(self.witnesses().len())
+            .expect("The number of witnesses can't exceed `u16::MAX`");
         self.tx.add_witness(witness);
 
+        WitnessIndex::new(index)
+    }
+
+    /// Sets the `predicate_gas_used` of the input at `index`. Does nothing if the input
+    /// doesn't exist or doesn't have a predicate.
+    pub fn set_predicate_gas_used(
+        &mut self,
+        index: usize,
+        predicate_gas_used: Word,
+    ) -> &mut Self {
+        match self.tx.inputs_mut().get_mut(index) {
+            Some(Input::CoinPredicate(input::coin::CoinPredicate {
+                predicate_gas_used: gas_used,
+                ..
+            }))
+            | Some(Input::MessageCoinPredicate(input::message::MessageCoinPredicate {
+                predicate_gas_used: gas_used,
+                ..
+            }))
+            | Some(Input::MessageDataPredicate(input::message::MessageDataPredicate {
+                predicate_gas_used: gas_used,
+                ..
+            })) => *gas_used = predicate_gas_used,
+            _ => {}
+        }
+
         self
     }
 
+    /// Appends a witness with the `signatures` encoded by [`multisig::encode`].
+    ///
+    /// The witness is added at the index `self.witnesses().len()`, so the witness
+    /// indices of the already added inputs don't change. The signatures should be made
+    /// over the id of the transaction with all inputs and outputs set, because the id
+    /// doesn't depend on the witnesses.
+    pub fn add_multisig_witness(&mut self, signatures: &[Signature]) -> WitnessIndex {
+        self.add_witness(multisig::encode(signatures).into())
+    }
+
+    /// Selects coins from `available` until the spendable inputs of the `asset_id` cover
+    /// the `amount`. Inputs already present in the transaction count towards the
+    /// `amount`, and coins they spend are never selected twice.
+    ///
+    /// The selection is deterministic: the largest coins are picked first, and coins of
+    /// equal amount are picked in the order of `available`.
+    pub fn add_inputs_for_amount(
+        &mut self,
+        asset_id: AssetId,
+        amount: Word,
+        available: &[AvailableCoin],
+    ) -> Result<&mut Self, ValidityError> {
+        let base_asset_id = *self.params.base_asset_id();
+        let mut provided = self
+            .spendable_balances()
+            .get(&asset_id)
+            .copied()
+            .unwrap_or_default();
+
+        let mut candidates: Vec<_> = available
+            .iter()
+            .filter(|coin| coin.asset_id(&base_asset_id) == &asset_id)
+            .filter(|coin| !self.inputs().iter().any(|input| coin.is_spent_by(input)))
+            .collect();
+        candidates.sort_by_key(|coin| Reverse(coin.amount()));
+
+        let selectable = candidates
+            .iter()
+            .fold(provided, |acc, coin| acc.saturating_add(coin.amount()));
+        if selectable < amount {
+            return Err(ValidityError::InsufficientInputAmount {
+                asset: asset_id,
+                expected: amount,
+                provided: selectable,
+            });
+        }
+
+        for coin in candidates {
+            if provided >= amount {
+                break;
+            }
+            provided = provided.saturating_add(coin.amount());
+
+            match coin.clone() {
+                AvailableCoin::Coin {
+                    secret,
+                    utxo_id,
+                    amount,
+                    asset_id,
+                    tx_pointer,
+                } => {
+                    self.add_unsigned_coin_input(
+                        secret, utxo_id, amount, asset_id, tx_pointer,
+                    );
+                }
+                AvailableCoin::Message {
+                    secret,
+                    sender,
+                    nonce,
+                    amount,
+                } => {
+                    self.add_unsigned_message_input(
+                        secret,
+                        sender,
+                        nonce,
+                        amount,
+                        Vec::new(),
+                    );
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Appends an `Output::Change` to the `owner` for each asset that has spendable
+    /// inputs but no change output yet, and finalizes the transaction. Assets fully
+    /// spent by the coin outputs don't get a change output, except the base asset when
+    /// the unused part of the max fee can be refunded.
+    ///
+    /// Fails if the spendable inputs don't cover the coin outputs, or if the base
+    /// asset doesn't additionally cover the max fee of the transaction.
+    pub fn finalize_with_change(&mut self, owner: Address) -> Result<Tx, ValidityError> {
+        for asset_id in self.missing_change_assets()? {
+            self.tx
+                .outputs_mut()
+                .push(Output::change(owner, 0, asset_id));
+        }
+
+        self.try_finalize()
+    }
+
+    /// Returns the totals of each asset used by the spendable inputs, the coin outputs
+    /// or the change outputs of the transaction. The base asset is also listed if the
+    /// max fee is not zero.
+    pub fn balance_sheet(&self) -> BTreeMap<AssetId, AssetBalance> {
+        let base_asset_id = *self.params.base_asset_id();
+        let mut sheet: BTreeMap<_, _> = self
+            .spendable_balances()
+            .into_iter()
+            .map(|(asset_id, inputs)| {
+                let balance = AssetBalance {
+                    inputs,
+                    ..Default::default()
+                };
+                (asset_id, balance)
+            })
+            .collect();
+
+        let max_fee = self.tx.max_fee_limit();
+        if max_fee > 0 {
+            sheet.entry(base_asset_id).or_default().max_fee = max_fee;
+        }
+
+        for output in self.outputs() {
+            match output {
+                Output::Coin {
+                    asset_id, amount, ..
+                } => {
+                    let balance = sheet.entry(*asset_id).or_default();
+                    balance.coin_outputs = balance.coin_outputs.saturating_add(*amount);
+                }
+                Output::Change { asset_id, .. } => {
+                    sheet.entry(*asset_id).or_default().change_outputs += 1;
+                }
+                _ => {}
+            }
+        }
+
+        sheet
+    }
+
+    /// Balances the transaction using [`Self::balance_sheet`] and finalizes it. An
+    /// `Output::Change` to the `owner` is appended for each asset that has something
+    /// left after the coin outputs and the max fee, but no change output yet.
+    ///
+    /// Unlike [`Self::finalize_with_change`], the inputs of all assets are checked
+    /// before failing, and [`ValidityError::UnbalancedAssets`] lists every asset not
+    /// covered by the inputs. The change outputs already present must be unique per
+    /// asset and refer to an asset of the inputs.
+    pub fn finalize_balanced(&mut self, owner: Address) -> Result<Tx, ValidityError> {
+        self.check_change_outputs()?;
+
+        let base_asset_id = *self.params.base_asset_id();
+        let sheet = self.balance_sheet();
+
+        let deficits: Vec<_> = sheet
+            .iter()
+            .filter(|(_, balance)| balance.change().is_none())
+            .map(|(asset_id, balance)| (*asset_id, balance.deficit()))
+            .collect();
+        if !deficits.is_empty() {
+            return Err(ValidityError::UnbalancedAssets { deficits });
+        }
+
+        for (asset_id, balance) in sheet {
+            let refundable = asset_id == base_asset_id && balance.max_fee > 0;
+            let has_change = balance.change().is_some_and(|change| change > 0);
+            if balance.change_outputs == 0 && (has_change || refundable) {
+                self.tx
+                    .outputs_mut()
+                    .push(Output::change(owner, 0, asset_id));
+            }
+        }
+
+        self.try_finalize()
+    }
+
+    /// Finalizes the transaction like [`Finalizable::finalize`], but fails if any
+    /// signed input refers to a witness that the builder doesn't have.
+    pub fn try_finalize(&self) -> Result<Tx, ValidityError> {
+        self.check_witness_indexes()?;
+
+        Ok(self.finalize_inner())
+    }
+
+    /// Finalizes the transaction like [`Finalizable::finalize`], and additionally signs
+    /// the inputs owned by the key of the `signer` with the id of the transaction for
+    /// the chain id of the builder.
+    ///
+    /// The inputs of the `signer` are added with the witness index of a witness already
+    /// present in the builder, e.g. one returned by [`Self::add_witness`].
+    ///
+    /// Fails if the `signer` fails. The builder is never modified, so it can be
+    /// finalized again.
+    pub fn finalize_signed_with<S: Signer>(&self, signer: &S) -> Result<Tx, SignerError> {
+        let mut tx = self.signed_tx();
+        tx.sign_inputs_with(signer, &self.get_chain_id())?;
+
+        tx.precompute(&self.get_chain_id())
+            .expect("Should be able to calculate cache");
+
+        Ok(tx)
+    }
+
+    /// Asynchronous counterpart of [`Self::finalize_signed_with`].
+    #[cfg(feature = "async")]
+    pub async fn finalize_signed_with_async<S: AsyncSigner>(
+        &self,
+        signer: &S,
+    ) -> Result<Tx, SignerError> {
+        let mut tx = self.signed_tx();
+        let id = tx.id(&self.get_chain_id());
+        let signature = signer
+            .sign(fuel_crypto::Message::from_bytes_ref(&id))
+            .await?;
+        crate::transaction::attach_signature(&mut tx, &signer.public_key(), &signature);
+
+        tx.precompute(&self.get_chain_id())
+            .expect("Should be able to calculate cache");
+
+        Ok(tx)
+    }
+
+    /// Returns the exact canonical size of the transaction produced by
+    /// [`Finalizable::finalize`] after `num_signature_witnesses` signatures are
+    /// appended to it as separate witnesses.
+    ///
+    /// The witnesses of the signing keys added by the builder are accounted with the
+    /// size of the signature set during the finalization.
+    pub fn predicted_size(&self, num_signature_witnesses: usize) -> usize
+    where
+        Tx: Serialize,
+    {
+        self.predicted_tx(num_signature_witnesses).size()
+    }
+
+    /// Returns the exact canonical size of the transaction produced by
+    /// [`Self::finalize_with_change`] after `num_signature_witnesses` signatures are
+    /// appended to it as separate witnesses. It includes the change outputs that are
+    /// not added yet.
+    ///
+    /// Fails for the same reasons as [`Self::finalize_with_change`].
+    pub fn predicted_size_with_change(
+        &self,
+        num_signature_witnesses: usize,
+    ) -> Result<usize, ValidityError>
+    where
+        Tx: Serialize,
+    {
+        let mut tx = self.predicted_tx(num_signature_witnesses);
+        for asset_id in self.missing_change_assets()? {
+            tx.outputs_mut()
+                .push(Output::change(Default::default(), 0, asset_id));
+        }
+
+        Ok(tx.size())
+    }
+
+    /// Returns the transaction with the witnesses of the signing keys and
+    /// `num_signature_witnesses` additional witnesses filled with empty signatures.
+    fn predicted_tx(&self, num_signature_witnesses: usize) -> Tx {
+        let mut tx = self.tx.clone();
+        let signature = Witness::from(Signature::default().as_ref());
+
+        let witnesses = tx.witnesses_mut();
+        for witness_index in self.sign_keys.values() {
+            witnesses[witness_index.as_usize()] = signature.clone();
+        }
+        witnesses.extend(iter::repeat(signature).take(num_signature_witnesses));
+
+        tx
+    }
+
+    /// Returns the assets that need a change output in [`Self::finalize_with_change`],
+    /// after validating that the spendable inputs cover the coin outputs and the max
+    /// fee.
+    fn missing_change_assets(&self) -> Result<Vec<AssetId>, ValidityError> {
+        let base_asset_id = *self.params.base_asset_id();
+        let mut balances = self.spendable_balances();
+
+        let max_fee = self.tx.max_fee_limit();
+        let base_balance = balances.entry(base_asset_id).or_default();
+        *base_balance = base_balance.checked_sub(max_fee).ok_or(
+            ValidityError::InsufficientFeeAmount {
+                expected: max_fee,
+                provided: *base_balance,
+            },
+        )?;
+
+        for (index, output) in self.outputs().iter().enumerate() {
+            if let Output::Coin {
+                asset_id, amount, ..
+            } = output
+            {
+                let balance = balances.get_mut(asset_id).ok_or(
+                    ValidityError::TransactionOutputCoinAssetIdNotFound {
+                        index,
+                        asset_id: *asset_id,
+                    },
+                )?;
+                *balance = balance.checked_sub(*amount).ok_or(
+                    ValidityError::InsufficientInputAmount {
+                        asset: *asset_id,
+                        expected: *amount,
+                        provided: *balance,
+                    },
+                )?;
+            }
+        }
+
+        let assets = balances
+            .into_iter()
+            .filter(|(asset_id, remaining)| {
+                let refundable = asset_id == &base_asset_id && max_fee > 0;
+                *remaining > 0 || refundable
+            })
+            .filter(|(asset_id, _)| {
+                !self.outputs().iter().any(|output| {
+                    matches!(output, Output::Change { asset_id: change, .. } if change == asset_id)
+                })
+            })
+            .map(|(asset_id, _)| asset_id)
+            .collect();
+
+        Ok(assets)
+    }
+
+    /// Returns the sum of the inputs that can be spent, per asset. Messages with data
+    /// are excluded because they are only used to pay for the retryable execution.
+    fn spendable_balances(&self) -> BTreeMap<AssetId, Word> {
+        let base_asset_id = *self.params.base_asset_id();
+        let mut balances = BTreeMap::<AssetId, Word>::new();
+
+        for input in self.inputs() {
+            let spendable = input.is_coin()
+                || input.is_message_coin_signed()
+                || input.is_message_coin_predicate();
+            if spendable {
+                if let (Some(asset_id), Some(amount)) =
+                    (input.asset_id(&base_asset_id), input.amount())
+                {
+                    let balance = balances.entry(*asset_id).or_default();
+                    *balance = balance.saturating_add(amount);
+                }
+            }
+        }
+
+        balances
+    }
+
     /// Adds a secret to the builder, and adds a corresponding witness if it's a new entry
-    fn upsert_secret(&mut self, secret_key: SecretKey) -> u16 {
+    fn upsert_secret(&mut self, secret_key: SecretKey) -> WitnessIndex {
         let witness_len = u16::try_from(self.witnesses().len())
             .expect("The number of witnesses can't exceed `u16::MAX`");
 
@@ -456,21 +994,76 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
             // if this private key hasn't been used before,
             // add a new witness entry and return its index
             self.tx.witnesses_mut().push(Witness::default());
-            witness_len
+            WitnessIndex::new(witness_len)
         });
         *witness_index
     }
 
+    /// Checks that the change outputs are unique per asset and refer to an asset of
+    /// the inputs.
+    fn check_change_outputs(&self) -> Result<(), ValidityError> {
+        let base_asset_id = *self.params.base_asset_id();
+        let mut seen = BTreeSet::new();
+
+        self.outputs()
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, output)| match output {
+                Output::Change { asset_id, .. } if !seen.insert(*asset_id) => {
+                    Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+                        index,
+                        asset_id: *asset_id,
+                    })
+                }
+                Output::Change { asset_id, .. }
+                    if !self.inputs().iter().any(|input| {
+                        input.asset_id(&base_asset_id) == Some(asset_id)
+                    }) =>
+                {
+                    Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+                        index,
+                        asset_id: *asset_id,
+                    })
+                }
+                _ => Ok(()),
+            })
+    }
+
+    /// Checks that every signed input refers to an existing witness.
+    fn check_witness_indexes(&self) -> Result<(), ValidityError> {
+        let witnesses = self.witnesses().len();
+
+        self.inputs()
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, input)| match input.witness_index() {
+                Some(witness_index) if usize::from(witness_index) >= witnesses => {
+                    Err(ValidityError::InputWitnessIndexBounds {
+                        index,
+                        witness_index,
+                    })
+                }
+                _ => Ok(()),
+            })
+    }
+
     fn finalize_inner(&self) -> Tx {
+        let mut tx = self.signed_tx();
+
+        tx.precompute(&self.get_chain_id())
+            .expect("Should be able to calculate cache");
+
+        tx
+    }
+
+    /// Returns the transaction signed by the signing keys added to the builder.
+    fn signed_tx(&self) -> Tx {
         let mut tx = self.tx.clone();
 
         self.sign_keys
             .iter()
             .for_each(|(k, _)| tx.sign_inputs(k, &self.get_chain_id()));
 
-        tx.precompute(&self.get_chain_id())
-            .expect("Should be able to calculate cache");
-
         tx
     }
 
@@ -536,3 +1129,943 @@ where
         self.finalize_without_signature().into()
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::{
+        field::{
+            Inputs,
+            Outputs,
+        },
+        FormatValidityChecks,
+        UniqueIdentifier,
+    };
+    use fuel_types::canonical::Deserialize;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    fn coin(rng: &mut StdRng, amount: Word, asset_id: AssetId) -> AvailableCoin {
+        AvailableCoin::Coin {
+            secret: SecretKey::random(rng),
+            utxo_id: rng.gen(),
+            amount,
+            asset_id,
+            tx_pointer: Default::default(),
+        }
+    }
+
+    fn change_outputs(tx: &Script) -> Vec<(AssetId, Address)> {
+        tx.outputs()
+            .iter()
+            .filter_map(|output| match output {
+                Output::Change { asset_id, to, .. } => Some((*asset_id, *to)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_inputs_for_amount__selects_largest_coins_per_asset() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![
+            coin(rng, 50, asset),
+            coin(rng, 100, asset),
+            coin(rng, 60, asset),
+            coin(rng, 30, AssetId::BASE),
+            coin(rng, 40, AssetId::BASE),
+        ];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .max_fee_limit(10)
+            .add_output(Output::coin(owner, 150, asset))
+            .add_output(Output::coin(owner, 20, AssetId::BASE));
+
+        // When
+        builder
+            .add_inputs_for_amount(asset, 150, &available)
+            .expect("Enough asset coins")
+            .add_inputs_for_amount(AssetId::BASE, 30, &available)
+            .expect("Enough base coins");
+        let tx = builder
+            .finalize_with_change(owner)
+            .expect("Inputs cover outputs");
+
+        // Then
+        let selected: Vec<_> = tx.inputs().iter().filter_map(Input::amount).collect();
+        assert_eq!(selected, vec![100, 60, 40]);
+        assert_eq!(
+            change_outputs(&tx),
+            vec![(AssetId::BASE, owner), (asset, owner)]
+        );
+        tx.check(Default::default(), builder.get_params())
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn add_inputs_for_amount__counts_present_inputs_and_messages() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let owner: Address = rng.gen();
+        let message = AvailableCoin::Message {
+            secret: SecretKey::random(rng),
+            sender: rng.gen(),
+            nonce: rng.gen(),
+            amount: 70,
+        };
+        let present = coin(rng, 50, AssetId::BASE);
+        let available = vec![present.clone(), message, coin(rng, 10, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.max_fee_limit(100);
+        builder
+            .add_inputs_for_amount(AssetId::BASE, 50, &[present])
+            .expect("Enough base coins");
+
+        // When
+        builder
+            .add_inputs_for_amount(AssetId::BASE, 100, &available)
+            .expect("Enough base coins");
+        let tx = builder
+            .finalize_with_change(owner)
+            .expect("Inputs cover fee");
+
+        // Then
+        assert_eq!(tx.inputs().len(), 2);
+        assert!(tx.inputs()[1].is_message_coin_signed());
+        assert_eq!(change_outputs(&tx), vec![(AssetId::BASE, owner)]);
+        tx.check(Default::default(), builder.get_params())
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn finalize_with_change__exact_amount_needs_no_change() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 100, asset), coin(rng, 1, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_output(Output::coin(owner, 100, asset));
+
+        // When
+        builder
+            .add_inputs_for_amount(asset, 100, &available)
+            .unwrap()
+            .add_inputs_for_amount(AssetId::BASE, 1, &available)
+            .unwrap();
+        let tx = builder
+            .finalize_with_change(owner)
+            .expect("Inputs cover outputs");
+
+        // Then
+        assert_eq!(change_outputs(&tx), vec![(AssetId::BASE, owner)]);
+    }
+
+    #[test]
+    fn finalize_with_change__keeps_present_change_outputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let owner: Address = rng.gen();
+        let other: Address = rng.gen();
+        let available = vec![coin(rng, 100, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .max_fee_limit(1)
+            .add_output(Output::change(other, 0, AssetId::BASE));
+        builder
+            .add_inputs_for_amount(AssetId::BASE, 100, &available)
+            .unwrap();
+
+        // When
+        let tx = builder
+            .finalize_with_change(owner)
+            .expect("Inputs cover fee");
+
+        // Then
+        assert_eq!(change_outputs(&tx), vec![(AssetId::BASE, other)]);
+    }
+
+    #[test]
+    fn add_inputs_for_amount__fails_with_shortfall_of_missing_asset() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let available = vec![coin(rng, 40, asset), coin(rng, 1000, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+
+        // When
+        let err = builder
+            .add_inputs_for_amount(asset, 100, &available)
+            .expect_err("Not enough coins of the asset");
+
+        // Then
+        assert_eq!(
+            err,
+            ValidityError::InsufficientInputAmount {
+                asset,
+                expected: 100,
+                provided: 40,
+            }
+        );
+        assert!(builder.inputs().is_empty());
+    }
+
+    #[test]
+    fn finalize_with_change__fails_if_base_asset_does_not_cover_max_fee() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 40, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.max_fee_limit(100);
+        builder
+            .add_inputs_for_amount(AssetId::BASE, 40, &available)
+            .unwrap();
+
+        // When
+        let err = builder
+            .finalize_with_change(owner)
+            .expect_err("Max fee is not covered");
+
+        // Then
+        assert_eq!(
+            err,
+            ValidityError::InsufficientFeeAmount {
+                expected: 100,
+                provided: 40,
+            }
+        );
+    }
+
+    #[test]
+    fn balance_sheet__returns_totals_per_asset() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 100, asset), coin(rng, 50, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .max_fee_limit(10)
+            .add_output(Output::coin(owner, 30, asset))
+            .add_output(Output::coin(owner, 40, asset))
+            .add_output(Output::change(owner, 0, asset));
+        builder
+            .add_inputs_for_amount(asset, 100, &available)
+            .unwrap()
+            .add_inputs_for_amount(AssetId::BASE, 50, &available)
+            .unwrap();
+
+        // When
+        let sheet = builder.balance_sheet();
+
+        // Then
+        let base = sheet[&AssetId::BASE];
+        assert_eq!(
+            base,
+            AssetBalance {
+                inputs: 50,
+                coin_outputs: 0,
+                max_fee: 10,
+                change_outputs: 0,
+            }
+        );
+        assert_eq!(base.change(), Some(40));
+        let other = sheet[&asset];
+        assert_eq!(
+            other,
+            AssetBalance {
+                inputs: 100,
+                coin_outputs: 70,
+                max_fee: 0,
+                change_outputs: 1,
+            }
+        );
+        assert_eq!(other.change(), Some(30));
+    }
+
+    #[test]
+    fn finalize_balanced__adds_missing_change_outputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let spent: AssetId = rng.gen();
+        let kept: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![
+            coin(rng, 100, spent),
+            coin(rng, 100, kept),
+            coin(rng, 100, AssetId::BASE),
+        ];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .max_fee_limit(10)
+            .add_output(Output::coin(owner, 100, spent))
+            .add_output(Output::coin(owner, 60, kept));
+        for coin in &available {
+            builder
+                .add_inputs_for_amount(*coin.asset_id(&AssetId::BASE), 100, &available)
+                .unwrap();
+        }
+
+        // When
+        let tx = builder
+            .finalize_balanced(owner)
+            .expect("Inputs cover outputs");
+
+        // Then
+        let mut expected = vec![(AssetId::BASE, owner), (kept, owner)];
+        expected.sort();
+        assert_eq!(change_outputs(&tx), expected);
+        tx.check(Default::default(), builder.get_params())
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn finalize_balanced__exact_spend_needs_no_change() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 100, asset), coin(rng, 20, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .add_output(Output::coin(owner, 100, asset))
+            .add_output(Output::coin(owner, 20, AssetId::BASE));
+        builder
+            .add_inputs_for_amount(asset, 100, &available)
+            .unwrap()
+            .add_inputs_for_amount(AssetId::BASE, 20, &available)
+            .unwrap();
+
+        // When
+        let tx = builder
+            .finalize_balanced(owner)
+            .expect("Inputs cover outputs");
+
+        // Then
+        assert_eq!(change_outputs(&tx), vec![]);
+        assert!(builder
+            .balance_sheet()
+            .values()
+            .all(|balance| balance.change() == Some(0)));
+    }
+
+    #[test]
+    fn finalize_balanced__fails_for_conflicting_change_outputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let unknown: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 100, asset)];
+
+        // Given
+        let mut duplicated = TransactionBuilder::script(vec![], vec![]);
+        duplicated
+            .add_output(Output::change(owner, 0, asset))
+            .add_output(Output::change(rng.gen(), 0, asset));
+        duplicated
+            .add_inputs_for_amount(asset, 100, &available)
+            .unwrap();
+        let mut not_found = TransactionBuilder::script(vec![], vec![]);
+        not_found.add_output(Output::change(owner, 0, unknown));
+        not_found
+            .add_inputs_for_amount(asset, 100, &available)
+            .unwrap();
+
+        // When
+        let duplicated_err = duplicated
+            .finalize_balanced(owner)
+            .expect_err("Change output is duplicated");
+        let not_found_err = not_found
+            .finalize_balanced(owner)
+            .expect_err("Change output has no inputs");
+
+        // Then
+        assert_eq!(
+            duplicated_err,
+            ValidityError::TransactionOutputChangeAssetIdDuplicated {
+                index: 1,
+                asset_id: asset,
+            }
+        );
+        assert_eq!(
+            not_found_err,
+            ValidityError::TransactionOutputChangeAssetIdNotFound {
+                index: 0,
+                asset_id: unknown,
+            }
+        );
+        assert_eq!(duplicated.outputs().len(), 2);
+        assert_eq!(not_found.outputs().len(), 1);
+    }
+
+    #[test]
+    fn finalize_balanced__lists_all_unbalanced_assets() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset: AssetId = rng.gen();
+        let missing: AssetId = rng.gen();
+        let owner: Address = rng.gen();
+        let available = vec![coin(rng, 40, asset), coin(rng, 5, AssetId::BASE)];
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .max_fee_limit(10)
+            .add_output(Output::coin(owner, 100, asset))
+            .add_output(Output::coin(owner, 7, missing));
+        builder
+            .add_inputs_for_amount(asset, 40, &available)
+            .unwrap()
+            .add_inputs_for_amount(AssetId::BASE, 5, &available)
+            .unwrap();
+
+        // When
+        let err = builder
+            .finalize_balanced(owner)
+            .expect_err("Inputs don't cover outputs");
+
+        // Then
+        let mut deficits = vec![(AssetId::BASE, 5), (asset, 60), (missing, 7)];
+        deficits.sort();
+        assert_eq!(err, ValidityError::UnbalancedAssets { deficits });
+        assert_eq!(builder.outputs().len(), 2);
+    }
+
+    #[test]
+    fn add_multisig_witness__keeps_witness_indices_of_signed_inputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let first = SecretKey::random(rng);
+        let second = SecretKey::random(rng);
+        let message = fuel_crypto::Message::new(b"multisig");
+        let signatures: Vec<_> = (0..2)
+            .map(|_| Signature::sign(&SecretKey::random(rng), &message))
+            .collect();
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_unsigned_coin_input(
+            first.clone(),
+            rng.gen(),
+            10,
+            AssetId::BASE,
+            Default::default(),
+        );
+
+        // When
+        let multisig_index = builder.add_multisig_witness(&signatures);
+        builder
+            .add_unsigned_coin_input(
+                second,
+                rng.gen(),
+                10,
+                AssetId::BASE,
+                Default::default(),
+            )
+            .add_unsigned_coin_input(
+                first,
+                rng.gen(),
+                10,
+                AssetId::BASE,
+                Default::default(),
+            );
+        let tx = builder.finalize();
+
+        // Then
+        let witness_indices: Vec<_> = tx
+            .inputs()
+            .iter()
+            .filter_map(Input::witness_index)
+            .collect();
+        assert_eq!(witness_indices, vec![0, 2, 0]);
+        assert_eq!(multisig_index, WitnessIndex::new(1));
+        assert_eq!(tx.witnesses().len(), 3);
+        assert_eq!(
+            multisig::decode(tx.witnesses()[multisig_index.as_usize()].as_ref()),
+            Some(signatures)
+        );
+        tx.check_signatures(&builder.get_chain_id())
+            .expect("Signed inputs should be valid");
+    }
+
+    #[test]
+    fn add_witness__returns_index_referenced_by_inputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_random_fee_input();
+
+        // When
+        let witness_index = builder.add_witness(rng.gen());
+        builder.add_input(Input::coin_signed(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            witness_index,
+        ));
+
+        // Then
+        assert_eq!(witness_index, WitnessIndex::new(1));
+        let tx = builder.try_finalize().expect("The witness exists");
+        assert_eq!(tx.inputs()[1].witness_index(), Some(1));
+    }
+
+    #[test]
+    fn try_finalize__fails_for_witness_index_out_of_bounds() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_random_fee_input().add_input(Input::coin_signed(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            WitnessIndex::new(1),
+        ));
+
+        // When
+        let err = builder
+            .try_finalize()
+            .expect_err("The witness 1 doesn't exist");
+
+        // Then
+        assert_eq!(
+            err,
+            ValidityError::InputWitnessIndexBounds {
+                index: 1,
+                witness_index: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn finalize_with_change__fails_for_witness_index_out_of_bounds() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let owner: Address = rng.gen();
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_input(Input::coin_signed(
+            rng.gen(),
+            rng.gen(),
+            100,
+            AssetId::BASE,
+            rng.gen(),
+            WitnessIndex::new(0),
+        ));
+
+        // When
+        let err = builder
+            .finalize_with_change(owner)
+            .expect_err("The witness 0 doesn't exist");
+
+        // Then
+        assert_eq!(
+            err,
+            ValidityError::InputWitnessIndexBounds {
+                index: 0,
+                witness_index: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn witness_index__is_encoded_like_u16() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let index: u16 = rng.gen();
+
+        // When
+        let bytes = WitnessIndex::new(index).to_bytes();
+
+        // Then
+        assert_eq!(bytes, index.to_bytes());
+        assert_eq!(
+            WitnessIndex::from_bytes(&bytes),
+            Ok(WitnessIndex::new(index))
+        );
+    }
+
+    /// Adds signed coin and message inputs, a predicate input and policies to the
+    /// `builder`.
+    fn add_predicted_size_inputs<Tx: Buildable>(
+        rng: &mut StdRng,
+        builder: &mut TransactionBuilder<Tx>,
+    ) {
+        let secret = SecretKey::random(rng);
+        let predicate = vec![0u8; 24];
+        builder
+            .tip(5)
+            .maturity(10.into())
+            .expiration(100.into())
+            .witness_limit(10_000)
+            .max_fee_limit(1_000)
+            .add_unsigned_coin_input(
+                secret.clone(),
+                rng.gen(),
+                10_000,
+                AssetId::BASE,
+                Default::default(),
+            )
+            .add_unsigned_coin_input(
+                secret,
+                rng.gen(),
+                10_000,
+                rng.gen(),
+                Default::default(),
+            )
+            .add_unsigned_message_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                rng.gen(),
+                10_000,
+                vec![1, 2, 3],
+            )
+            .add_input(Input::coin_predicate(
+                rng.gen(),
+                Input::predicate_owner(&predicate),
+                10_000,
+                AssetId::BASE,
+                Default::default(),
+                1_000,
+                predicate,
+                vec![4, 5, 6, 7, 8],
+            ));
+    }
+
+    #[test]
+    fn predicted_size__matches_finalized_script() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let mut builder = TransactionBuilder::script(vec![1, 2, 3, 4], vec![5, 6]);
+        add_predicted_size_inputs(rng, &mut builder);
+        builder.add_output(Output::coin(rng.gen(), 100, AssetId::BASE));
+
+        for num_signature_witnesses in 0..3 {
+            // Given
+            let mut builder = builder.clone();
+
+            // When
+            let predicted = builder.predicted_size(num_signature_witnesses);
+
+            // Then
+            (0..num_signature_witnesses).for_each(|_| {
+                builder.add_witness(Signature::default().as_ref().into());
+            });
+            assert_eq!(predicted, builder.finalize().size());
+        }
+    }
+
+    #[test]
+    fn predicted_size__matches_finalized_create() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let mut builder = TransactionBuilder::create(
+            vec![0u8; 64].into(),
+            rng.gen(),
+            vec![StorageSlot::new(rng.gen(), rng.gen())],
+        );
+        add_predicted_size_inputs(rng, &mut builder);
+
+        // When
+        let predicted = builder.predicted_size(1);
+
+        // Then
+        builder.add_witness(Signature::default().as_ref().into());
+        assert_eq!(predicted, builder.finalize().size());
+    }
+
+    fn predicate_coin_tx(
+        rng: &mut StdRng,
+        predicate: Vec<u8>,
+        owner: Option<Address>,
+    ) -> Script {
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.max_fee_limit(0);
+        match owner {
+            Some(owner) => builder.add_predicate_coin_input_with_owner(
+                owner,
+                rng.gen(),
+                100,
+                AssetId::BASE,
+                Default::default(),
+                predicate,
+                vec![],
+            ),
+            None => builder.add_predicate_coin_input(
+                rng.gen(),
+                100,
+                AssetId::BASE,
+                Default::default(),
+                predicate,
+                vec![],
+            ),
+        };
+        builder.finalize()
+    }
+
+    #[test]
+    fn add_predicate_coin_input__sets_owner_of_predicate() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let predicate: Vec<u8> = (0..64).map(|_| rng.gen::<u8>()).collect();
+
+        // When
+        let tx = predicate_coin_tx(rng, predicate.clone(), None);
+
+        // Then
+        assert_eq!(
+            tx.inputs()[0].input_owner(),
+            Some(&Input::predicate_owner(&predicate))
+        );
+        tx.check(Default::default(), &ConsensusParameters::standard())
+            .expect("The owner should match the predicate");
+    }
+
+    #[test]
+    fn add_predicate_coin_input_with_owner__mismatch_reports_both_owners() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let predicate: Vec<u8> = (0..64).map(|_| rng.gen::<u8>()).collect();
+        let actual: Address = rng.gen();
+
+        // When
+        let tx = predicate_coin_tx(rng, predicate.clone(), Some(actual));
+
+        // Then
+        assert_eq!(
+            tx.check(Default::default(), &ConsensusParameters::standard()),
+            Err(ValidityError::InputPredicateOwner {
+                index: 0,
+                expected: Input::predicate_owner(&predicate),
+                actual,
+            })
+        );
+    }
+
+    #[test]
+    fn add_predicate_coin_input__empty_predicate_is_rejected() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // When
+        let tx = predicate_coin_tx(rng, vec![], None);
+
+        // Then
+        assert_eq!(
+            tx.inputs()[0].input_owner(),
+            Some(&Input::predicate_owner([]))
+        );
+        assert_eq!(
+            tx.check(Default::default(), &ConsensusParameters::standard()),
+            Err(ValidityError::InputPredicateEmpty { index: 0 })
+        );
+    }
+
+    #[test]
+    fn predicted_size_with_change__includes_missing_change_outputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let owner: Address = rng.gen();
+
+        // Given
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        add_predicted_size_inputs(rng, &mut builder);
+
+        let without_change = builder.predicted_size(0);
+
+        // When
+        let predicted = builder.predicted_size_with_change(0).unwrap();
+
+        // Then
+        let tx = builder.finalize_with_change(owner).unwrap();
+        assert_eq!(change_outputs(&tx).len(), 2);
+        assert_eq!(predicted, tx.size());
+        assert_eq!(
+            predicted,
+            without_change + 2 * Output::change(owner, 0, AssetId::BASE).size()
+        );
+    }
+
+    /// Signs with the `secret` and records the signed messages.
+    struct RecordingSigner {
+        secret: SecretKey,
+        messages: core::cell::RefCell<Vec<fuel_crypto::Message>>,
+    }
+
+    impl Signer for RecordingSigner {
+        fn public_key(&self) -> fuel_crypto::PublicKey {
+            self.secret.public_key()
+        }
+
+        fn sign(&self, message: &fuel_crypto::Message) -> Result<Signature, SignerError> {
+            self.messages.borrow_mut().push(*message);
+            Ok(Signature::sign(&self.secret, message))
+        }
+    }
+
+    /// Always fails to sign for the key of the `secret`.
+    struct FailingSigner {
+        secret: SecretKey,
+    }
+
+    impl Signer for FailingSigner {
+        fn public_key(&self) -> fuel_crypto::PublicKey {
+            self.secret.public_key()
+        }
+
+        fn sign(&self, _: &fuel_crypto::Message) -> Result<Signature, SignerError> {
+            Err(SignerError::Unavailable)
+        }
+    }
+
+    /// Returns the builder with an input owned by the `secret` and its witness index.
+    fn builder_with_input_of(
+        rng: &mut StdRng,
+        secret: &SecretKey,
+    ) -> (TransactionBuilder<Script>, WitnessIndex) {
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_random_fee_input();
+        let witness_index = builder.add_witness(Witness::default());
+        builder.add_input(Input::coin_signed(
+            rng.gen(),
+            Input::owner(&secret.public_key()),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            witness_index,
+        ));
+
+        (builder, witness_index)
+    }
+
+    #[test]
+    fn finalize_signed_with__signs_tx_id_and_attaches_witness() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let signer = RecordingSigner {
+            secret: SecretKey::random(rng),
+            messages: Default::default(),
+        };
+        let (builder, witness_index) = builder_with_input_of(rng, &signer.secret);
+
+        // When
+        let tx = builder
+            .finalize_signed_with(&signer)
+            .expect("The signer doesn't fail");
+
+        // Then
+        let chain_id = builder.get_chain_id();
+        let id = tx.id(&chain_id);
+        assert_eq!(
+            signer.messages.into_inner(),
+            vec![*fuel_crypto::Message::from_bytes_ref(&id)]
+        );
+        assert_eq!(
+            tx.witnesses()[witness_index.as_usize()].as_vec().len(),
+            Signature::LEN
+        );
+        tx.check_signatures(&chain_id)
+            .expect("All inputs are signed");
+    }
+
+    #[test]
+    fn finalize_signed_with__secret_key_matches_add_unsigned_coin_input() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let secret = SecretKey::random(rng);
+        let (builder, witness_index) = builder_with_input_of(rng, &secret);
+
+        // When
+        let tx = builder
+            .finalize_signed_with(&secret)
+            .expect("The in-memory signer can't fail");
+
+        // Then
+        let id = tx.id(&builder.get_chain_id());
+        let signature =
+            Signature::sign(&secret, fuel_crypto::Message::from_bytes_ref(&id));
+        assert_eq!(
+            tx.witnesses()[witness_index.as_usize()],
+            Witness::from(signature.as_ref())
+        );
+    }
+
+    #[test]
+    fn finalize_signed_with__failing_signer_leaves_builder_usable() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let signer = FailingSigner {
+            secret: SecretKey::random(rng),
+        };
+        let (builder, witness_index) = builder_with_input_of(rng, &signer.secret);
+        let unsigned = builder.finalize_without_signature_inner();
+
+        // When
+        let result = builder.finalize_signed_with(&signer);
+
+        // Then
+        assert_eq!(result, Err(SignerError::Unavailable));
+        assert_eq!(builder.finalize_without_signature_inner(), unsigned);
+        let tx = builder
+            .finalize_signed_with(&signer.secret)
+            .expect("The in-memory signer can't fail");
+        assert_eq!(
+            tx.witnesses()[witness_index.as_usize()].as_vec().len(),
+            Signature::LEN
+        );
+    }
+
+    #[test]
+    fn finalize_signed_with_async__matches_finalize_signed_with() {
+        use core::{
+            future::Future,
+            pin::pin,
+            task::{
+                Context,
+                Poll,
+            },
+        };
+        use std::{
+            sync::Arc,
+            task::Wake,
+        };
+
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let secret = SecretKey::random(rng);
+        let (builder, _) = builder_with_input_of(rng, &secret);
+
+        // When
+        let waker = Arc::new(NoopWaker).into();
+        let mut context = Context::from_waker(&waker);
+        let future = pin!(builder.finalize_signed_with_async(&secret));
+        let Poll::Ready(result) = future.poll(&mut context) else {
+            panic!("The in-memory signer is always ready")
+        };
+
+        // Then
+        assert_eq!(result, builder.finalize_signed_with(&secret));
+    }
+}
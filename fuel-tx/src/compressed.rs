@@ -0,0 +1,665 @@
+//! Compact encoding of transactions for posting to the DA layer.
+//!
+//! The 32-byte values repeated across transactions, like owners, asset ids and contract
+//! ids of inputs and outputs, are replaced by indices into a dictionary shared by the
+//! encoder and the decoder, see [`CompressionContext`]. The transaction id of the UTXO
+//! id of an input is elided when the context can recover it from the `tx_pointer` of
+//! the input.
+//!
+//! The compression is lossless: [`decompress`] restores the exact canonical bytes of
+//! the original transaction, so the transaction id is preserved. The `Mint` transaction
+//! is stored as is.
+
+use crate::{
+    field,
+    Transaction,
+    TxId,
+    TxPointer,
+};
+use alloc::vec::Vec;
+use fuel_types::{
+    canonical::{
+        self,
+        Deserialize,
+        Serialize,
+    },
+    BlockHeight,
+    Bytes32,
+};
+
+/// The index of a value in the dictionary of the [`CompressionContext`].
+pub type Index = u32;
+
+/// The dictionary shared by the encoder and the decoder of the transactions.
+pub trait CompressionContext {
+    /// Returns the index of the `value` in the dictionary. The context may register
+    /// the missing value and return its new index. `None` means the value is missing
+    /// and can't be registered.
+    fn index_of(&mut self, value: &Bytes32) -> Option<Index>;
+
+    /// Returns the value stored at the `index` in the dictionary.
+    fn value_at(&self, index: Index) -> Option<Bytes32>;
+
+    /// Returns the id of the transaction at the `tx_pointer`, if it is known.
+    ///
+    /// The transaction id of the UTXO id is elided if it matches the id returned for
+    /// the `tx_pointer` of the input, so the decoder must resolve the same pointer to
+    /// the same id. By default, no transaction ids are elided.
+    fn tx_id_at(&self, tx_pointer: &TxPointer) -> Option<TxId> {
+        let _ = tx_pointer;
+        None
+    }
+}
+
+/// The in-memory [`CompressionContext`] that registers every missing value.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    values: Vec<Bytes32>,
+    indices: alloc::collections::BTreeMap<Bytes32, Index>,
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of registered values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no values are registered.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl CompressionContext for Dictionary {
+    fn index_of(&mut self, value: &Bytes32) -> Option<Index> {
+        if let Some(index) = self.indices.get(value) {
+            return Some(*index);
+        }
+
+        let index = Index::try_from(self.values.len()).ok()?;
+        self.values.push(*value);
+        self.indices.insert(*value, index);
+        Some(index)
+    }
+
+    fn value_at(&self, index: Index) -> Option<Bytes32> {
+        self.values.get(usize::try_from(index).ok()?).copied()
+    }
+}
+
+/// The errors of the compression and the decompression.
+#[derive(Debug, PartialEq, Eq, derive_more::Display)]
+#[non_exhaustive]
+pub enum Error {
+    /// The value is missing in the dictionary and can't be registered.
+    #[display(fmt = "The value {value} is missing in the dictionary")]
+    DictionaryMiss {
+        /// The missing value.
+        value: Bytes32,
+    },
+    /// The index is missing in the dictionary.
+    #[display(fmt = "The index {index} is missing in the dictionary")]
+    UnknownIndex {
+        /// The missing index.
+        index: Index,
+    },
+    /// The transaction id at the pointer is unknown.
+    #[display(fmt = "The transaction id at {tx_pointer} is unknown")]
+    UnknownTxPointer {
+        /// The pointer of the elided transaction id.
+        tx_pointer: TxPointer,
+    },
+    /// The bytes are not a valid encoding.
+    #[display(fmt = "Invalid encoding: {_0}")]
+    Codec(canonical::Error),
+}
+
+impl From<canonical::Error> for Error {
+    fn from(error: canonical::Error) -> Self {
+        Self::Codec(error)
+    }
+}
+
+/// The replacement of a 32-byte value removed from the canonical bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressedValue {
+    /// The value is stored in the dictionary at the index.
+    Index(Index),
+    /// The value is the id of the transaction at the pointer.
+    TxIdAt(TxPointer),
+}
+
+/// The removed value and where to insert it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slot {
+    /// The offset in the remaining bytes where the value is inserted.
+    pub position: usize,
+    /// The replacement of the value.
+    pub value: CompressedValue,
+}
+
+/// The compressed transaction.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CompressedTransaction {
+    remaining: Vec<u8>,
+    slots: Vec<Slot>,
+}
+
+impl CompressedTransaction {
+    /// The canonical bytes of the transaction without the removed values.
+    pub fn remaining(&self) -> &[u8] {
+        &self.remaining
+    }
+
+    /// The removed values ordered by position.
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// Encodes the compressed transaction.
+    ///
+    /// Numbers are encoded as LEB128, and positions of the slots are encoded as the
+    /// difference from the previous slot.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.remaining.len() + 4 * self.slots.len());
+        write_varint(&mut bytes, self.remaining.len() as u64);
+        bytes.extend_from_slice(&self.remaining);
+        write_varint(&mut bytes, self.slots.len() as u64);
+
+        let mut previous = 0;
+        for slot in &self.slots {
+            write_varint(&mut bytes, (slot.position - previous) as u64);
+            previous = slot.position;
+            match slot.value {
+                CompressedValue::Index(index) => {
+                    bytes.push(0);
+                    write_varint(&mut bytes, index.into());
+                }
+                CompressedValue::TxIdAt(tx_pointer) => {
+                    bytes.push(1);
+                    write_varint(&mut bytes, (*tx_pointer.block_height()).into());
+                    write_varint(&mut bytes, tx_pointer.tx_index().into());
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes the compressed transaction encoded with [`Self::to_bytes`].
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, canonical::Error> {
+        let bytes = &mut bytes;
+        let len = read_usize(bytes)?;
+        let remaining = take(bytes, len)?.to_vec();
+
+        let count = read_usize(bytes)?;
+        let mut slots = Vec::new();
+        let mut position = 0usize;
+        for _ in 0..count {
+            position = position
+                .checked_add(read_usize(bytes)?)
+                .filter(|position| *position <= remaining.len())
+                .ok_or(canonical::Error::Unknown("The slot is out of bounds"))?;
+            let value = match take(bytes, 1)?[0] {
+                0 => CompressedValue::Index(read_int(bytes)?),
+                1 => {
+                    let block_height: u32 = read_int(bytes)?;
+                    let tx_index = read_int(bytes)?;
+                    CompressedValue::TxIdAt(TxPointer::new(
+                        BlockHeight::new(block_height),
+                        tx_index,
+                    ))
+                }
+                _ => return Err(canonical::Error::UnknownDiscriminant),
+            };
+            slots.push(Slot { position, value });
+        }
+
+        if !bytes.is_empty() {
+            return Err(canonical::Error::Unknown("Unexpected trailing bytes"));
+        }
+
+        Ok(Self { remaining, slots })
+    }
+}
+
+/// Compresses the `tx` with the dictionary of the `context`.
+pub fn compress<C>(
+    tx: &Transaction,
+    context: &mut C,
+) -> Result<CompressedTransaction, Error>
+where
+    C: CompressionContext + ?Sized,
+{
+    let bytes = tx.to_bytes();
+    let values = match tx {
+        Transaction::Script(tx) => compressible_values(tx),
+        Transaction::Create(tx) => compressible_values(tx),
+        Transaction::Upgrade(tx) => compressible_values(tx),
+        Transaction::Upload(tx) => compressible_values(tx),
+        Transaction::Mint(_) => Vec::new(),
+    };
+
+    let mut remaining = Vec::with_capacity(bytes.len());
+    let mut slots = Vec::with_capacity(values.len());
+    let mut copied = 0;
+    for (offset, tx_pointer) in values {
+        let range = offset..offset + Bytes32::LEN;
+        let value = Bytes32::try_from(&bytes[range.clone()])
+            .expect("The range has the length of `Bytes32`");
+
+        let value = match tx_pointer {
+            Some(tx_pointer) if context.tx_id_at(&tx_pointer) == Some(value) => {
+                CompressedValue::TxIdAt(tx_pointer)
+            }
+            _ => CompressedValue::Index(
+                context
+                    .index_of(&value)
+                    .ok_or(Error::DictionaryMiss { value })?,
+            ),
+        };
+
+        remaining.extend_from_slice(&bytes[copied..range.start]);
+        copied = range.end;
+        slots.push(Slot {
+            position: remaining.len(),
+            value,
+        });
+    }
+    remaining.extend_from_slice(&bytes[copied..]);
+
+    Ok(CompressedTransaction { remaining, slots })
+}
+
+/// Restores the transaction compressed with the dictionary of the `context`.
+pub fn decompress<C>(
+    compressed: &CompressedTransaction,
+    context: &C,
+) -> Result<Transaction, Error>
+where
+    C: CompressionContext + ?Sized,
+{
+    let remaining = &compressed.remaining;
+    let mut bytes =
+        Vec::with_capacity(remaining.len() + compressed.slots.len() * Bytes32::LEN);
+    let mut copied = 0;
+    for slot in &compressed.slots {
+        let value = match slot.value {
+            CompressedValue::Index(index) => context
+                .value_at(index)
+                .ok_or(Error::UnknownIndex { index })?,
+            CompressedValue::TxIdAt(tx_pointer) => context
+                .tx_id_at(&tx_pointer)
+                .ok_or(Error::UnknownTxPointer { tx_pointer })?,
+        };
+
+        let gap = remaining
+            .get(copied..slot.position)
+            .ok_or(canonical::Error::Unknown("The slots are not ordered"))?;
+        bytes.extend_from_slice(gap);
+        bytes.extend_from_slice(value.as_ref());
+        copied = slot.position;
+    }
+    bytes.extend_from_slice(&remaining[copied..]);
+
+    Ok(Transaction::from_bytes(&bytes)?)
+}
+
+/// Returns the offsets of the compressible values in the canonical bytes of the `tx`,
+/// along with the `tx_pointer` of the input for the transaction ids of UTXO ids.
+fn compressible_values<Tx>(tx: &Tx) -> Vec<(usize, Option<TxPointer>)>
+where
+    Tx: field::Inputs + field::Outputs,
+{
+    let mut values = Vec::new();
+
+    for (index, input) in tx.inputs().iter().enumerate() {
+        let Some(input_offset) = tx.inputs_offset_at(index) else {
+            continue
+        };
+        let repr = input.repr();
+        let tx_pointer = input.tx_pointer().copied();
+        let offsets = [
+            repr.utxo_id_offset().map(|offset| (offset, tx_pointer)),
+            repr.owner_offset().map(|offset| (offset, None)),
+            repr.asset_id_offset().map(|offset| (offset, None)),
+            repr.contract_id_offset().map(|offset| (offset, None)),
+            repr.message_sender_offset().map(|offset| (offset, None)),
+            repr.message_recipient_offset().map(|offset| (offset, None)),
+        ];
+        values.extend(
+            offsets
+                .into_iter()
+                .flatten()
+                .map(|(offset, tx_pointer)| (input_offset + offset, tx_pointer)),
+        );
+    }
+
+    for (index, output) in tx.outputs().iter().enumerate() {
+        let Some(output_offset) = tx.outputs_offset_at(index) else {
+            continue
+        };
+        let repr = output.repr();
+        let offsets = [
+            repr.to_offset(),
+            repr.asset_id_offset(),
+            repr.contract_id_offset(),
+        ];
+        values.extend(
+            offsets
+                .into_iter()
+                .flatten()
+                .map(|offset| (output_offset + offset, None)),
+        );
+    }
+
+    values.sort_by_key(|(offset, _)| *offset);
+    values
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, canonical::Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        value |= u64::from(byte & 0x7f)
+            .checked_shl(shift)
+            .ok_or(canonical::Error::Unknown("The number is too large"))?;
+        if byte & 0x80 == 0 {
+            return Ok(value)
+        }
+    }
+    Err(canonical::Error::Unknown("The number is too large"))
+}
+
+fn read_int<T: TryFrom<u64>>(bytes: &mut &[u8]) -> Result<T, canonical::Error> {
+    T::try_from(read_varint(bytes)?)
+        .map_err(|_| canonical::Error::Unknown("The number is too large"))
+}
+
+fn read_usize(bytes: &mut &[u8]) -> Result<usize, canonical::Error> {
+    read_int(bytes)
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], canonical::Error> {
+    if bytes.len() < len {
+        return Err(canonical::Error::BufferIsTooShort)
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::{
+        field::Inputs,
+        Contract,
+        Input,
+        Output,
+        TransactionBuilder,
+        UniqueIdentifier,
+        UtxoId,
+    };
+    use alloc::{
+        collections::BTreeMap,
+        vec,
+    };
+    use fuel_crypto::SecretKey;
+    use fuel_types::{
+        AssetId,
+        ChainId,
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    /// The script spending `count` coins of the same asset owned by the same key.
+    fn script_with_coins(rng: &mut StdRng, count: usize) -> Transaction {
+        let secret = SecretKey::random(rng);
+        let asset_id: AssetId = rng.gen();
+        let mut builder = TransactionBuilder::script(vec![0xaa; 16], vec![0xbb; 16]);
+        for _ in 0..count {
+            builder.add_unsigned_coin_input(
+                secret.clone(),
+                rng.gen(),
+                rng.gen_range(1..1_000),
+                asset_id,
+                rng.gen(),
+            );
+        }
+        builder
+            .add_input(Input::contract(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ))
+            .add_output(Output::coin(rng.gen(), 10, asset_id))
+            .add_output(Output::contract(
+                u16::try_from(count).unwrap(),
+                rng.gen(),
+                rng.gen(),
+            ))
+            .add_output(Output::change(rng.gen(), 0, asset_id))
+            .finalize_as_transaction()
+    }
+
+    fn assert_round_trip(tx: &Transaction, context: &mut impl CompressionContext) {
+        let compressed = compress(tx, context).expect("The compression should succeed");
+        let encoded = compressed.to_bytes();
+        let decoded = CompressedTransaction::from_bytes(&encoded)
+            .expect("The encoding should be valid");
+        let decompressed =
+            decompress(&decoded, context).expect("The decompression should succeed");
+
+        assert_eq!(decoded, compressed);
+        assert_eq!(decompressed.to_bytes(), tx.to_bytes());
+        assert_eq!(
+            decompressed.id(&ChainId::default()),
+            tx.id(&ChainId::default())
+        );
+    }
+
+    #[test]
+    fn decompress__restores_script_with_shared_and_unique_ids() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let mut dictionary = Dictionary::new();
+
+        for count in [0, 1, 3] {
+            assert_round_trip(&script_with_coins(rng, count), &mut dictionary);
+        }
+    }
+
+    #[test]
+    fn decompress__restores_create() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let mut dictionary = Dictionary::new();
+        let bytecode = vec![0xcc; 64];
+        let salt = rng.gen();
+        let state_root = Contract::default_state_root();
+        let contract_id =
+            Contract::id(&salt, &Contract::root_from_code(&bytecode), &state_root);
+
+        let tx = TransactionBuilder::create(bytecode.into(), salt, vec![])
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                1_000,
+                AssetId::BASE,
+                rng.gen(),
+            )
+            .add_unsigned_message_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                rng.gen(),
+                1_000,
+                vec![0xdd; 8],
+            )
+            .add_output(Output::contract_created(contract_id, state_root))
+            .add_output(Output::change(rng.gen(), 0, AssetId::BASE))
+            .finalize_as_transaction();
+
+        assert_round_trip(&tx, &mut dictionary);
+        assert!(dictionary
+            .indices
+            .contains_key(&Bytes32::from(*contract_id)));
+    }
+
+    #[test]
+    fn compress__shrinks_script_with_inputs_of_the_same_owner() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let mut dictionary = Dictionary::new();
+        let tx = script_with_coins(rng, 10);
+
+        // When
+        let compressed = compress(&tx, &mut dictionary).unwrap();
+
+        // Then
+        let original_len = tx.to_bytes().len();
+        let compressed_len = compressed.to_bytes().len();
+        assert!(
+            compressed_len * 100 < original_len * 70,
+            "{compressed_len} is not smaller than 70% of {original_len}"
+        );
+        // 10 UTXO ids, the owner and the asset id of the coins, the contract and UTXO
+        // id of the contract input, and recipients of the coin and change outputs.
+        assert_eq!(dictionary.len(), 10 + 2 + 2 + 2);
+        assert_round_trip(&tx, &mut dictionary);
+    }
+
+    /// The dictionary that knows the transactions at some pointers.
+    #[derive(Default)]
+    struct Chain {
+        dictionary: Dictionary,
+        tx_ids: BTreeMap<TxPointer, TxId>,
+    }
+
+    impl CompressionContext for Chain {
+        fn index_of(&mut self, value: &Bytes32) -> Option<Index> {
+            self.dictionary.index_of(value)
+        }
+
+        fn value_at(&self, index: Index) -> Option<Bytes32> {
+            self.dictionary.value_at(index)
+        }
+
+        fn tx_id_at(&self, tx_pointer: &TxPointer) -> Option<TxId> {
+            self.tx_ids.get(tx_pointer).copied()
+        }
+    }
+
+    #[test]
+    fn compress__elides_utxo_tx_id_recoverable_from_tx_pointer() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx_id: TxId = rng.gen();
+        let tx_pointer = TxPointer::new(10.into(), 3);
+        let mut chain = Chain::default();
+        chain.tx_ids.insert(tx_pointer, tx_id);
+
+        let tx = TransactionBuilder::script(vec![], vec![])
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                UtxoId::new(tx_id, 1),
+                1_000,
+                AssetId::BASE,
+                tx_pointer,
+            )
+            .finalize_as_transaction();
+
+        // When
+        let compressed = compress(&tx, &mut chain).unwrap();
+
+        // Then
+        assert_eq!(
+            compressed.slots()[0].value,
+            CompressedValue::TxIdAt(tx_pointer)
+        );
+        assert!(!chain.dictionary.indices.contains_key(&tx_id));
+        assert_round_trip(&tx, &mut chain);
+        assert_eq!(
+            decompress(&compressed, &chain.dictionary),
+            Err(Error::UnknownTxPointer { tx_pointer })
+        );
+    }
+
+    #[test]
+    fn decompress__fails_on_unknown_index() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx = script_with_coins(rng, 1);
+        let compressed = compress(&tx, &mut Dictionary::new()).unwrap();
+
+        let result = decompress(&compressed, &Dictionary::new());
+
+        assert_eq!(result, Err(Error::UnknownIndex { index: 0 }));
+    }
+
+    /// The dictionary that doesn't register new values.
+    struct Frozen(Dictionary);
+
+    impl CompressionContext for Frozen {
+        fn index_of(&mut self, value: &Bytes32) -> Option<Index> {
+            self.0.indices.get(value).copied()
+        }
+
+        fn value_at(&self, index: Index) -> Option<Bytes32> {
+            self.0.value_at(index)
+        }
+    }
+
+    #[test]
+    fn compress__fails_on_dictionary_miss() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx = script_with_coins(rng, 1);
+        let mut dictionary = Dictionary::new();
+        compress(&tx, &mut dictionary).unwrap();
+        let mut frozen = Frozen(dictionary);
+        assert_round_trip(&tx, &mut frozen);
+
+        // When
+        let other = script_with_coins(rng, 1);
+        let result = compress(&other, &mut frozen);
+
+        // Then
+        let Transaction::Script(script) = &other else {
+            unreachable!()
+        };
+        let value = *script.inputs()[0].utxo_id().unwrap().tx_id();
+        assert_eq!(result, Err(Error::DictionaryMiss { value }));
+    }
+
+    #[test]
+    fn from_bytes__rejects_truncated_encoding() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx = script_with_coins(rng, 2);
+        let encoded = compress(&tx, &mut Dictionary::new()).unwrap().to_bytes();
+
+        for len in [0, 1, encoded.len() / 2, encoded.len() - 1] {
+            assert!(CompressedTransaction::from_bytes(&encoded[..len]).is_err());
+        }
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert!(CompressedTransaction::from_bytes(&trailing).is_err());
+    }
+}
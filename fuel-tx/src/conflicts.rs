@@ -0,0 +1,330 @@
+//! Detection of conflicts between the transactions of a batch.
+//!
+//! A block can't include two transactions that spend the same coin, consume the same
+//! message or create the same contract. [`check_batch`] finds such pairs before the
+//! execution, so the block builder can drop one of the transactions. Whether the
+//! transactions would revert is irrelevant: the inputs of a reverted transaction are
+//! consumed anyway.
+//!
+//! The conflicts inside one transaction are reported by the validity checks of the
+//! transaction and are ignored here.
+
+use crate::{
+    field::{
+        Inputs,
+        Outputs,
+    },
+    Input,
+    Output,
+    Transaction,
+    UtxoId,
+};
+use alloc::vec::Vec;
+use fuel_types::{
+    ContractId,
+    Nonce,
+};
+use hashbrown::HashMap;
+
+/// The resource claimed by more than one transaction of the batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContestedResource {
+    /// The coin spent by a coin input.
+    Coin(UtxoId),
+    /// The nonce of the message consumed by a message input.
+    Message(Nonce),
+    /// The id of the contract created by an `Output::ContractCreated`.
+    ContractCreation(ContractId),
+}
+
+/// Two transactions of the batch that claim the same resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The index of the first transaction that claims the resource.
+    pub first: usize,
+    /// The index of the later transaction that claims the resource.
+    pub second: usize,
+    /// The contested resource.
+    pub resource: ContestedResource,
+}
+
+/// Returns the conflicts between the transactions of the batch, in the order of the
+/// later transactions. Each later transaction claiming a resource is paired with the
+/// first transaction that claimed it, so a resource claimed by `n` transactions
+/// produces `n - 1` conflicts.
+///
+/// The batch is processed in one pass, with a hash map of the claimed resources.
+pub fn check_batch<'a>(txs: impl Iterator<Item = &'a Transaction>) -> Vec<Conflict> {
+    let mut claimed = HashMap::<ContestedResource, usize>::new();
+    let mut conflicts = Vec::new();
+
+    for (index, tx) in txs.enumerate() {
+        let (inputs, outputs) = inputs_and_outputs(tx);
+        let resources = inputs
+            .iter()
+            .filter_map(input_resource)
+            .chain(outputs.iter().filter_map(output_resource));
+
+        for resource in resources {
+            let first = *claimed.entry(resource).or_insert(index);
+
+            if first != index {
+                conflicts.push(Conflict {
+                    first,
+                    second: index,
+                    resource,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+fn inputs_and_outputs(tx: &Transaction) -> (&[Input], &[Output]) {
+    match tx {
+        Transaction::Script(tx) => (tx.inputs(), tx.outputs()),
+        Transaction::Create(tx) => (tx.inputs(), tx.outputs()),
+        Transaction::Upgrade(tx) => (tx.inputs(), tx.outputs()),
+        Transaction::Upload(tx) => (tx.inputs(), tx.outputs()),
+        // The input contract of the `Mint` is not consumed.
+        Transaction::Mint(_) => (&[], &[]),
+    }
+}
+
+fn input_resource(input: &Input) -> Option<ContestedResource> {
+    match input {
+        Input::CoinSigned(_) | Input::CoinPredicate(_) => {
+            input.utxo_id().copied().map(ContestedResource::Coin)
+        }
+        Input::MessageCoinSigned(_)
+        | Input::MessageCoinPredicate(_)
+        | Input::MessageDataSigned(_)
+        | Input::MessageDataPredicate(_) => {
+            input.nonce().copied().map(ContestedResource::Message)
+        }
+        // The contracts can be used by any number of transactions.
+        Input::Contract(_) | Input::ContractReadOnly(_) => None,
+    }
+}
+
+fn output_resource(output: &Output) -> Option<ContestedResource> {
+    output
+        .contract_id()
+        .copied()
+        .map(ContestedResource::ContractCreation)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::{
+        Contract,
+        TransactionBuilder,
+        WitnessIndex,
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    fn coin(utxo_id: UtxoId, rng: &mut StdRng) -> Input {
+        Input::coin_signed(
+            utxo_id,
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            WitnessIndex::new(0),
+        )
+    }
+
+    fn message(nonce: Nonce, rng: &mut StdRng) -> Input {
+        Input::message_coin_signed(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            nonce,
+            WitnessIndex::new(0),
+        )
+    }
+
+    fn script(inputs: Vec<Input>) -> Transaction {
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        for input in inputs {
+            builder.add_input(input);
+        }
+        builder.finalize_without_signature_as_transaction()
+    }
+
+    fn create(contract_id: ContractId, rng: &mut StdRng) -> Transaction {
+        let mut builder = TransactionBuilder::create(
+            rng.gen::<[u8; 32]>().to_vec().into(),
+            rng.gen(),
+            vec![],
+        );
+        builder
+            .add_input(coin(rng.gen(), rng))
+            .add_output(Output::contract_created(
+                contract_id,
+                Contract::default_state_root(),
+            ));
+        builder.finalize_without_signature_as_transaction()
+    }
+
+    #[test]
+    fn check_batch__returns_nothing_for_independent_transactions() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let contract_input =
+            Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen());
+        let txs = [
+            script(vec![coin(rng.gen(), rng), contract_input.clone()]),
+            script(vec![message(rng.gen(), rng), contract_input]),
+            create(rng.gen(), rng),
+            TransactionBuilder::mint(
+                Default::default(),
+                0,
+                Default::default(),
+                Default::default(),
+                rng.gen(),
+                rng.gen(),
+                0,
+            )
+            .finalize_as_transaction(),
+        ];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        assert_eq!(conflicts, vec![]);
+    }
+
+    #[test]
+    fn check_batch__detects_coin_spent_twice() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let utxo_id = rng.gen();
+
+        // Given
+        let txs = [
+            script(vec![coin(utxo_id, rng)]),
+            script(vec![coin(rng.gen(), rng)]),
+            script(vec![coin(rng.gen(), rng), coin(utxo_id, rng)]),
+        ];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                first: 0,
+                second: 2,
+                resource: ContestedResource::Coin(utxo_id),
+            }]
+        );
+    }
+
+    #[test]
+    fn check_batch__detects_message_consumed_twice() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let nonce = rng.gen();
+
+        // Given
+        let txs = [
+            script(vec![coin(rng.gen(), rng), message(nonce, rng)]),
+            script(vec![message(nonce, rng)]),
+        ];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                first: 0,
+                second: 1,
+                resource: ContestedResource::Message(nonce),
+            }]
+        );
+    }
+
+    #[test]
+    fn check_batch__detects_contract_created_twice() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let contract_id = rng.gen();
+
+        // Given
+        let txs = [create(contract_id, rng), create(contract_id, rng)];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                first: 0,
+                second: 1,
+                resource: ContestedResource::ContractCreation(contract_id),
+            }]
+        );
+    }
+
+    #[test]
+    fn check_batch__pairs_each_repeated_claim_with_the_first_one() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let utxo_id = rng.gen();
+
+        // Given
+        let txs = [
+            script(vec![coin(utxo_id, rng)]),
+            script(vec![coin(utxo_id, rng)]),
+            script(vec![coin(utxo_id, rng)]),
+        ];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        let pairs: Vec<_> = conflicts.iter().map(|c| (c.first, c.second)).collect();
+        assert_eq!(pairs, vec![(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn check_batch__detects_same_transaction_included_twice() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let utxo_id = rng.gen();
+        let nonce = rng.gen();
+
+        // Given
+        let tx = script(vec![coin(utxo_id, rng), message(nonce, rng)]);
+        let txs = [tx.clone(), create(rng.gen(), rng), tx];
+
+        // When
+        let conflicts = check_batch(txs.iter());
+
+        // Then
+        assert_eq!(
+            conflicts,
+            vec![
+                Conflict {
+                    first: 0,
+                    second: 2,
+                    resource: ContestedResource::Coin(utxo_id),
+                },
+                Conflict {
+                    first: 0,
+                    second: 2,
+                    resource: ContestedResource::Message(nonce),
+                },
+            ]
+        );
+    }
+}
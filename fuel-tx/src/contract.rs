@@ -97,7 +97,11 @@ impl Contract {
         tree.root().into()
     }
 
-    /// Calculate the root of the initial storage slots for this contract
+    /// Calculate the root of the initial storage slots for this contract.
+    ///
+    /// The order of the slots doesn't affect the root, and the value of the last
+    /// duplicated key is used. Use [`Self::checked_initial_state_root`] to reject the
+    /// slots that are invalid for a `Create` transaction.
     pub fn initial_state_root<'a, I>(storage_slots: I) -> Bytes32
     where
         I: Iterator<Item = &'a StorageSlot>,
@@ -109,6 +113,24 @@ impl Contract {
         root.into()
     }
 
+    /// Calculate the root of the initial storage slots for this contract, verifying that
+    /// the slots are sorted by key without duplicates as required for a `Create`
+    /// transaction.
+    pub fn checked_initial_state_root(
+        storage_slots: &[StorageSlot],
+    ) -> Result<Bytes32, ValidityError> {
+        if !Self::storage_slots_are_sorted(storage_slots) {
+            return Err(ValidityError::TransactionCreateStorageSlotOrder);
+        }
+
+        Ok(Self::initial_state_root(storage_slots.iter()))
+    }
+
+    /// Returns `true` if the slots are strictly ascending by key.
+    pub(crate) fn storage_slots_are_sorted(storage_slots: &[StorageSlot]) -> bool {
+        storage_slots.windows(2).all(|s| s[0] < s[1])
+    }
+
     /// The default state root value without any entries
     pub fn default_state_root() -> Bytes32 {
         Self::initial_state_root(iter::empty())
@@ -117,7 +139,7 @@ impl Contract {
     /// Calculate and return the contract id, provided a salt, code root and state root.
     ///
     /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/identifiers/contract-id.md>
-    pub fn id(&self, salt: &Salt, root: &Bytes32, state_root: &Bytes32) -> ContractId {
+    pub fn id(salt: &Salt, root: &Bytes32, state_root: &Bytes32) -> ContractId {
         let mut hasher = Hasher::default();
 
         hasher.input(ContractId::SEED);
@@ -365,7 +387,42 @@ mod tests {
         let root = contract.root();
         let state_root = Contract::default_state_root();
 
-        let calculated_id = contract.id(&salt, &root, &state_root);
+        let calculated_id = Contract::id(&salt, &root, &state_root);
         assert_eq!(calculated_id, Contract::EMPTY_CONTRACT_ID)
     }
+
+    #[test]
+    fn checked_initial_state_root_rejects_unsorted_slots() {
+        let rng = &mut StdRng::seed_from_u64(100);
+        let mut slots: Vec<StorageSlot> = (0..3)
+            .map(|_| {
+                let mut key = Bytes32::zeroed();
+                let mut value = Bytes32::zeroed();
+                rng.fill_bytes(key.as_mut());
+                rng.fill_bytes(value.as_mut());
+                StorageSlot::new(key, value)
+            })
+            .collect();
+        slots.sort();
+        let root = Contract::initial_state_root(slots.iter());
+
+        assert_eq!(Contract::checked_initial_state_root(&slots), Ok(root));
+        assert_eq!(
+            Contract::checked_initial_state_root(&[]),
+            Ok(Contract::default_state_root())
+        );
+
+        slots.swap(0, 1);
+        assert_eq!(Contract::initial_state_root(slots.iter()), root);
+        assert_eq!(
+            Contract::checked_initial_state_root(&slots),
+            Err(ValidityError::TransactionCreateStorageSlotOrder)
+        );
+
+        slots[0] = slots[1].clone();
+        assert_eq!(
+            Contract::checked_initial_state_root(&slots),
+            Err(ValidityError::TransactionCreateStorageSlotOrder)
+        );
+    }
 }
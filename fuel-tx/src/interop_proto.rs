@@ -0,0 +1,617 @@
+//! Protobuf interoperability for transactions and receipts.
+//!
+//! The messages mirror the schema of `proto/fuel_tx.proto`, so the tools written in
+//! other languages can generate the decoders of the same bytes. The conversions into
+//! the messages are infallible: `proto::Transaction::from(&tx)`. The conversions back
+//! are `TryFrom` and fail with [`Error`] if a fixed-size byte field has the wrong
+//! length, an integer doesn't fit the type of the crate, or a required field is missing.
+//!
+//! The protobuf bytes are not canonical: the same message has several valid encodings,
+//! and the unknown fields are skipped. The transaction id and the signatures are always
+//! computed from the canonical bytes of the [`Transaction`](crate::Transaction) that the
+//! message is converted into, never from the protobuf bytes.
+//!
+//! The conversions are lossless, so a transaction converted into a message and back has
+//! the same canonical bytes and the same id.
+
+mod convert;
+mod messages;
+mod wire;
+
+pub use messages::*;
+
+/// The errors of the decoding of the messages and of their conversion.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[non_exhaustive]
+pub enum Error {
+    /// The bytes are not a valid protobuf encoding of the message.
+    #[display(fmt = "Malformed message: {_0}")]
+    Malformed(&'static str),
+    /// The fixed-size byte field has the wrong length.
+    #[display(fmt = "The field `{field}` has {actual} bytes instead of {expected}")]
+    InvalidLength {
+        /// The name of the field in the schema.
+        field: &'static str,
+        /// The length of the type of the crate.
+        expected: usize,
+        /// The length of the received field.
+        actual: usize,
+    },
+    /// The integer field doesn't fit the type of the crate.
+    #[display(fmt = "The value {value} of the field `{field}` is out of range")]
+    OutOfRange {
+        /// The name of the field in the schema.
+        field: &'static str,
+        /// The received value.
+        value: u64,
+    },
+    /// The required message field, or the `oneof` field, is not set.
+    #[display(fmt = "The required field `{field}` is missing")]
+    MissingField {
+        /// The name of the field in the schema.
+        field: &'static str,
+    },
+    /// The number of the policy values doesn't match the bits, or a block height
+    /// policy doesn't fit `u32`.
+    #[display(fmt = "The policies are invalid")]
+    InvalidPolicies,
+    /// The `UnknownReceipt` requires the `unknown-receipts` feature.
+    #[display(fmt = "The unknown receipts are not supported")]
+    UnknownReceipt,
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{
+        wire,
+        Error,
+    };
+    use crate::{
+        field::ReceiptsRoot,
+        input::contract::Contract as ContractInput,
+        interop_proto as proto,
+        output::contract::Contract as ContractOutput,
+        policies::Policies,
+        Input,
+        Output,
+        Receipt,
+        ScriptExecutionResult,
+        StorageSlot,
+        Transaction,
+        UniqueIdentifier,
+        UpgradePurpose,
+        UploadBody,
+        WitnessIndex,
+    };
+    use alloc::vec::Vec;
+    use arbitrary::{
+        Arbitrary,
+        Unstructured,
+    };
+    use fuel_asm::{
+        PanicInstruction,
+        PanicReason,
+    };
+    use fuel_types::{
+        canonical::Serialize,
+        ChainId,
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    const SAMPLES: usize = 1_000;
+
+    /// The encodings of [`fixture_transactions`] and [`fixture_receipts`]. A change of
+    /// the fixtures means the schema changed: the new fields and variants must use new
+    /// tags, and the old tags must keep their meaning.
+    const TRANSACTIONS_FIXTURE: &[u8] =
+        include_bytes!("../proto/fixtures/transactions.bin");
+    const RECEIPTS_FIXTURE: &[u8] = include_bytes!("../proto/fixtures/receipts.bin");
+
+    fn inputs(rng: &mut StdRng) -> Vec<Input> {
+        vec![
+            Input::coin_signed(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                WitnessIndex::new(rng.gen()),
+            ),
+            Input::coin_predicate(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 3],
+                vec![rng.gen(); 4],
+            ),
+            Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+            Input::contract_read_only(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ),
+            Input::message_coin_signed(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                WitnessIndex::new(rng.gen()),
+            ),
+            Input::message_coin_predicate(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 5],
+                vec![],
+            ),
+            Input::message_data_signed(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                WitnessIndex::new(rng.gen()),
+                vec![rng.gen(); 6],
+            ),
+            Input::message_data_predicate(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 7],
+                vec![rng.gen(); 8],
+                vec![rng.gen(); 9],
+            ),
+        ]
+    }
+
+    fn outputs(rng: &mut StdRng) -> Vec<Output> {
+        vec![
+            Output::coin(rng.gen(), rng.gen(), rng.gen()),
+            Output::contract(rng.gen(), rng.gen(), rng.gen()),
+            Output::change(rng.gen(), rng.gen(), rng.gen()),
+            Output::variable(rng.gen(), rng.gen(), rng.gen()),
+            Output::contract_created(rng.gen(), rng.gen()),
+        ]
+    }
+
+    fn policies() -> Policies {
+        Policies::new()
+            .with_tip(1)
+            .with_witness_limit(2)
+            .with_maturity(3.into())
+            .with_max_fee(4)
+            .with_expiration(5.into())
+    }
+
+    /// One transaction of each kind, with all kinds of inputs and outputs.
+    fn fixture_transactions() -> Vec<Transaction> {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let witnesses = vec![vec![rng.gen(); 10].into(), vec![].into()];
+
+        let mut script = Transaction::script(
+            rng.gen(),
+            vec![rng.gen(); 4],
+            vec![rng.gen(); 2],
+            policies(),
+            inputs(rng),
+            outputs(rng),
+            witnesses.clone(),
+        );
+        *script.receipts_root_mut() = rng.gen();
+        let create = Transaction::create(
+            rng.gen(),
+            Policies::new().with_max_fee(rng.gen()),
+            rng.gen(),
+            vec![StorageSlot::new(rng.gen(), rng.gen())],
+            inputs(rng),
+            outputs(rng),
+            witnesses.clone(),
+        );
+        let mint = Transaction::mint(
+            rng.gen(),
+            ContractInput {
+                utxo_id: rng.gen(),
+                balance_root: rng.gen(),
+                state_root: rng.gen(),
+                tx_pointer: rng.gen(),
+                contract_id: rng.gen(),
+            },
+            ContractOutput {
+                input_index: rng.gen(),
+                balance_root: rng.gen(),
+                state_root: rng.gen(),
+            },
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+        let upgrade = Transaction::upgrade(
+            UpgradePurpose::ConsensusParameters {
+                witness_index: rng.gen(),
+                checksum: rng.gen(),
+            },
+            Policies::new(),
+            inputs(rng),
+            outputs(rng),
+            witnesses.clone(),
+        );
+        let upload = Transaction::upload(
+            UploadBody {
+                root: rng.gen(),
+                witness_index: rng.gen(),
+                subsection_index: rng.gen(),
+                subsections_number: rng.gen(),
+                proof_set: vec![rng.gen(), rng.gen()],
+            },
+            policies(),
+            inputs(rng),
+            outputs(rng),
+            witnesses,
+        );
+
+        vec![
+            script.into(),
+            create.into(),
+            mint.into(),
+            upgrade.into(),
+            upload.into(),
+        ]
+    }
+
+    /// One receipt of each kind.
+    fn fixture_receipts() -> Vec<Receipt> {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        vec![
+            Receipt::call(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ),
+            Receipt::ret(rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+            Receipt::return_data(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 3],
+            ),
+            Receipt::panic(
+                rng.gen(),
+                PanicInstruction::error(PanicReason::ContractNotFound, rng.gen()),
+                rng.gen(),
+                rng.gen(),
+            )
+            .with_panic_contract_id(Some(rng.gen())),
+            Receipt::revert(rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+            Receipt::log(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ),
+            Receipt::log_data(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 4],
+            ),
+            Receipt::transfer(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ),
+            Receipt::transfer_out(
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            ),
+            Receipt::script_result(
+                ScriptExecutionResult::GenericFailure(rng.gen()),
+                rng.gen(),
+            ),
+            Receipt::message_out(
+                &rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                vec![rng.gen(); 5],
+            ),
+            Receipt::mint(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+            Receipt::burn(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+            Receipt::Unknown {
+                discriminant: rng.gen(),
+                bytes: vec![rng.gen(); 6],
+            },
+        ]
+    }
+
+    /// Writes the messages as a stream of length-delimited messages.
+    fn delimited(messages: impl Iterator<Item = Vec<u8>>) -> Vec<u8> {
+        let mut stream = Vec::new();
+        for message in messages {
+            wire::write_varint(&mut stream, message.len() as u64);
+            stream.extend(message);
+        }
+        stream
+    }
+
+    fn undelimited(mut stream: &[u8]) -> Vec<&[u8]> {
+        let mut messages = Vec::new();
+        while !stream.is_empty() {
+            let len = usize::try_from(wire::read_varint(&mut stream).unwrap()).unwrap();
+            let (message, rest) = stream.split_at(len);
+            messages.push(message);
+            stream = rest;
+        }
+        messages
+    }
+
+    fn transaction_round_trip(tx: &Transaction) -> Transaction {
+        let bytes = proto::Transaction::from(tx).encode_to_vec();
+        let message = proto::Transaction::decode(&bytes).expect("Should decode");
+        Transaction::try_from(message).expect("Should convert")
+    }
+
+    fn receipt_round_trip(receipt: &Receipt) -> Receipt {
+        let bytes = proto::Receipt::from(receipt).encode_to_vec();
+        let message = proto::Receipt::decode(&bytes).expect("Should decode");
+        Receipt::try_from(message).expect("Should convert")
+    }
+
+    #[test]
+    fn transaction__round_trips_arbitrary_transactions() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let chain_id = ChainId::default();
+
+        for _ in 0..SAMPLES {
+            let len = rng.gen_range(0..4096);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen::<u8>()).collect();
+            let Ok(tx) = Transaction::arbitrary(&mut Unstructured::new(&data)) else {
+                continue
+            };
+
+            let decoded = transaction_round_trip(&tx);
+
+            assert_eq!(decoded, tx);
+            assert_eq!(decoded.to_bytes(), tx.to_bytes());
+            assert_eq!(decoded.id(&chain_id), tx.id(&chain_id));
+        }
+    }
+
+    #[test]
+    fn receipt__round_trips_arbitrary_receipts() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        for _ in 0..SAMPLES {
+            let len = rng.gen_range(0..512);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen::<u8>()).collect();
+            let Ok(receipt) = Receipt::arbitrary(&mut Unstructured::new(&data)) else {
+                continue
+            };
+
+            let decoded = receipt_round_trip(&receipt);
+
+            assert_eq!(decoded, receipt);
+            // The equality of the receipts ignores the data.
+            assert_eq!(decoded.data(), receipt.data());
+        }
+    }
+
+    #[test]
+    fn transaction__round_trips_all_kinds() {
+        for tx in fixture_transactions() {
+            let decoded = transaction_round_trip(&tx);
+
+            assert_eq!(decoded.to_bytes(), tx.to_bytes());
+        }
+    }
+
+    #[test]
+    fn receipt__round_trips_all_kinds() {
+        for receipt in fixture_receipts() {
+            let decoded = receipt_round_trip(&receipt);
+
+            assert_eq!(decoded, receipt);
+            assert_eq!(decoded.data(), receipt.data());
+        }
+    }
+
+    #[test]
+    fn transaction__encoding_matches_fixture() {
+        // Given
+        let txs = fixture_transactions();
+
+        // When
+        let stream = delimited(
+            txs.iter()
+                .map(|tx| proto::Transaction::from(tx).encode_to_vec()),
+        );
+
+        // Then
+        assert_eq!(stream, TRANSACTIONS_FIXTURE);
+        let decoded: Vec<Transaction> = undelimited(TRANSACTIONS_FIXTURE)
+            .into_iter()
+            .map(|bytes| {
+                proto::Transaction::decode(bytes)
+                    .unwrap()
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(decoded, txs);
+    }
+
+    #[test]
+    fn receipt__encoding_matches_fixture() {
+        // Given
+        let receipts = fixture_receipts();
+
+        // When
+        let stream = delimited(
+            receipts
+                .iter()
+                .map(|receipt| proto::Receipt::from(receipt).encode_to_vec()),
+        );
+
+        // Then
+        assert_eq!(stream, RECEIPTS_FIXTURE);
+        let decoded: Vec<Receipt> = undelimited(RECEIPTS_FIXTURE)
+            .into_iter()
+            .map(|bytes| proto::Receipt::decode(bytes).unwrap().try_into().unwrap())
+            .collect();
+        assert_eq!(decoded, receipts);
+    }
+
+    #[test]
+    fn try_from__fails_for_wrong_length_of_fixed_size_field() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        // Given
+        let mut message = proto::Output::from(&Output::coin(rng.gen(), 1, rng.gen()));
+        let Some(proto::OutputVariant::Coin(coin)) = &mut message.variant else {
+            panic!("The output is a coin")
+        };
+        coin.asset_id.pop();
+
+        // When
+        let err = Output::try_from(message).expect_err("The asset id is too short");
+
+        // Then
+        assert_eq!(
+            err,
+            Error::InvalidLength {
+                field: "asset_id",
+                expected: 32,
+                actual: 31,
+            }
+        );
+    }
+
+    #[test]
+    fn try_from__fails_for_out_of_range_and_missing_fields() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let tx = fixture_transactions().remove(0);
+
+        // Given
+        let mut out_of_range = proto::Transaction::from(&tx);
+        let mut missing = out_of_range.clone();
+        let Some(proto::TransactionVariant::Script(script)) = &mut out_of_range.variant
+        else {
+            panic!("The transaction is a script")
+        };
+        let Some(proto::InputVariant::CoinSigned(coin)) = &mut script.inputs[0].variant
+        else {
+            panic!("The first input is a signed coin")
+        };
+        coin.witness_index = u32::from(u16::MAX) + rng.gen_range(1..100);
+        let witness_index = coin.witness_index;
+        let Some(proto::TransactionVariant::Script(script)) = &mut missing.variant else {
+            panic!("The transaction is a script")
+        };
+        script.policies = None;
+
+        // When
+        let out_of_range_err =
+            Transaction::try_from(out_of_range).expect_err("The index is too large");
+        let missing_err =
+            Transaction::try_from(missing).expect_err("The policies are missing");
+
+        // Then
+        assert_eq!(
+            out_of_range_err,
+            Error::OutOfRange {
+                field: "witness_index",
+                value: u64::from(witness_index),
+            }
+        );
+        assert_eq!(missing_err, Error::MissingField { field: "policies" });
+    }
+
+    #[test]
+    fn decode__skips_unknown_fields() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let output = Output::change(rng.gen(), rng.gen(), rng.gen());
+
+        // Given
+        let Some(proto::OutputVariant::Change(coin)) =
+            proto::Output::from(&output).variant
+        else {
+            panic!("The output is a change")
+        };
+        let mut bytes = coin.encode_to_vec();
+        // The fields 15 of each wire type used by the newer schemas.
+        bytes.extend([15 << 3, 1]);
+        bytes.extend([15 << 3 | 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend([15 << 3 | 2, 2, 0xaa, 0xbb]);
+        bytes.extend([15 << 3 | 5, 0, 0, 0, 0]);
+        let message = proto::Output {
+            variant: Some(proto::OutputVariant::Change(
+                proto::CoinOutput::decode(&bytes).expect("Should skip unknown fields"),
+            )),
+        };
+
+        // When
+        let decoded = Output::try_from(message).expect("Should convert");
+
+        // Then
+        assert_eq!(decoded, output);
+    }
+
+    #[test]
+    fn decode__fails_for_truncated_message() {
+        let tx = fixture_transactions().remove(0);
+        let bytes = proto::Transaction::from(&tx).encode_to_vec();
+
+        let err = proto::Transaction::decode(&bytes[..bytes.len() - 1])
+            .expect_err("The message is truncated");
+
+        assert!(matches!(err, Error::Malformed(_)));
+    }
+
+    #[test]
+    fn try_from__keeps_unknown_policies() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let message = proto::Policies {
+            bits: 1 << 3 | 1 << 7,
+            values: vec![rng.gen(), rng.gen()],
+        };
+
+        let policies = Policies::try_from(message.clone()).expect("Should convert");
+
+        assert!(policies.has_unknown());
+        assert_eq!(proto::Policies::from(&policies), message);
+    }
+}
@@ -0,0 +1,1188 @@
+//! The conversions between the types of the crate and the protobuf messages.
+//!
+//! The conversions into the messages are infallible. The conversions back validate the
+//! length of the fixed-size byte fields, the range of the integers narrower than in the
+//! schema, and the presence of the required message fields.
+
+use super::{
+    messages as proto,
+    Error,
+};
+use crate::{
+    input::{
+        coin::{
+            CoinPredicate,
+            CoinSigned,
+        },
+        contract::Contract as ContractInput,
+        message::{
+            MessageCoinPredicate,
+            MessageCoinSigned,
+            MessageDataPredicate,
+            MessageDataSigned,
+        },
+    },
+    output::contract::Contract as ContractOutput,
+    policies::Policies,
+    transaction::{
+        CreateBody,
+        ScriptBody,
+        UpgradeBody,
+    },
+    Create,
+    Input,
+    Mint,
+    Output,
+    Receipt,
+    Script,
+    ScriptExecutionResult,
+    StorageSlot,
+    Transaction,
+    TxPointer,
+    Upgrade,
+    UpgradePurpose,
+    Upload,
+    UploadBody,
+    UtxoId,
+    Witness,
+    WitnessIndex,
+};
+use alloc::vec::Vec;
+use fuel_asm::PanicInstruction;
+use fuel_types::BlockHeight;
+
+/// The `ScriptResultReceipt.Kind` values of the schema.
+const SUCCESS: u32 = 0;
+const REVERT: u32 = 1;
+const PANIC: u32 = 2;
+const GENERIC_FAILURE: u32 = 3;
+
+fn fixed<T: From<[u8; N]>, const N: usize>(
+    bytes: &[u8],
+    field: &'static str,
+) -> Result<T, Error> {
+    <[u8; N]>::try_from(bytes)
+        .map(T::from)
+        .map_err(|_| Error::InvalidLength {
+            field,
+            expected: N,
+            actual: bytes.len(),
+        })
+}
+
+fn narrow(value: u32, field: &'static str) -> Result<u16, Error> {
+    u16::try_from(value).map_err(|_| Error::OutOfRange {
+        field,
+        value: u64::from(value),
+    })
+}
+
+fn required<T>(value: Option<T>, field: &'static str) -> Result<T, Error> {
+    value.ok_or(Error::MissingField { field })
+}
+
+impl From<&UtxoId> for proto::UtxoId {
+    fn from(utxo_id: &UtxoId) -> Self {
+        Self {
+            tx_id: utxo_id.tx_id().to_vec(),
+            output_index: u32::from(utxo_id.output_index()),
+        }
+    }
+}
+
+impl TryFrom<proto::UtxoId> for UtxoId {
+    type Error = Error;
+
+    fn try_from(utxo_id: proto::UtxoId) -> Result<Self, Error> {
+        Ok(UtxoId::new(
+            fixed(&utxo_id.tx_id, "tx_id")?,
+            narrow(utxo_id.output_index, "output_index")?,
+        ))
+    }
+}
+
+impl From<&TxPointer> for proto::TxPointer {
+    fn from(tx_pointer: &TxPointer) -> Self {
+        Self {
+            block_height: *tx_pointer.block_height(),
+            tx_index: u32::from(tx_pointer.tx_index()),
+        }
+    }
+}
+
+impl TryFrom<proto::TxPointer> for TxPointer {
+    type Error = Error;
+
+    fn try_from(tx_pointer: proto::TxPointer) -> Result<Self, Error> {
+        Ok(TxPointer::new(
+            BlockHeight::new(tx_pointer.block_height),
+            narrow(tx_pointer.tx_index, "tx_index")?,
+        ))
+    }
+}
+
+fn utxo_id(utxo_id: Option<proto::UtxoId>) -> Result<UtxoId, Error> {
+    required(utxo_id, "utxo_id")?.try_into()
+}
+
+fn tx_pointer(tx_pointer: Option<proto::TxPointer>) -> Result<TxPointer, Error> {
+    required(tx_pointer, "tx_pointer")?.try_into()
+}
+
+impl From<&Policies> for proto::Policies {
+    fn from(policies: &Policies) -> Self {
+        Self {
+            bits: policies.bits(),
+            values: policies.encoded_values().collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::Policies> for Policies {
+    type Error = Error;
+
+    fn try_from(policies: proto::Policies) -> Result<Self, Error> {
+        Policies::from_encoded_values(policies.bits, &policies.values)
+            .ok_or(Error::InvalidPolicies)
+    }
+}
+
+impl From<&Witness> for proto::Witness {
+    fn from(witness: &Witness) -> Self {
+        Self {
+            data: witness.as_vec().clone(),
+        }
+    }
+}
+
+impl From<proto::Witness> for Witness {
+    fn from(witness: proto::Witness) -> Self {
+        witness.data.into()
+    }
+}
+
+impl From<&StorageSlot> for proto::StorageSlot {
+    fn from(slot: &StorageSlot) -> Self {
+        Self {
+            key: slot.key().to_vec(),
+            value: slot.value().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::StorageSlot> for StorageSlot {
+    type Error = Error;
+
+    fn try_from(slot: proto::StorageSlot) -> Result<Self, Error> {
+        Ok(StorageSlot::new(
+            fixed(&slot.key, "key")?,
+            fixed(&slot.value, "value")?,
+        ))
+    }
+}
+
+/// The fields shared by the chargeable transactions.
+struct Chargeable {
+    policies: Policies,
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    witnesses: Vec<Witness>,
+}
+
+fn to_proto<'a, T, P>(values: impl IntoIterator<Item = &'a T>) -> Vec<P>
+where
+    T: 'a,
+    P: From<&'a T>,
+{
+    values.into_iter().map(P::from).collect()
+}
+
+fn from_proto<P, T>(values: Vec<P>) -> Result<Vec<T>, Error>
+where
+    T: TryFrom<P, Error = Error>,
+{
+    values.into_iter().map(T::try_from).collect()
+}
+
+fn chargeable(
+    policies: Option<proto::Policies>,
+    inputs: Vec<proto::Input>,
+    outputs: Vec<proto::Output>,
+    witnesses: Vec<proto::Witness>,
+) -> Result<Chargeable, Error> {
+    Ok(Chargeable {
+        policies: required(policies, "policies")?.try_into()?,
+        inputs: from_proto(inputs)?,
+        outputs: from_proto(outputs)?,
+        witnesses: witnesses.into_iter().map(Witness::from).collect(),
+    })
+}
+
+impl From<&Script> for proto::Script {
+    fn from(tx: &Script) -> Self {
+        Self {
+            script_gas_limit: tx.body.script_gas_limit,
+            receipts_root: tx.body.receipts_root.to_vec(),
+            script: tx.body.script.clone(),
+            script_data: tx.body.script_data.clone(),
+            policies: Some((&tx.policies).into()),
+            inputs: to_proto(&tx.inputs),
+            outputs: to_proto(&tx.outputs),
+            witnesses: to_proto(&tx.witnesses),
+        }
+    }
+}
+
+impl TryFrom<proto::Script> for Script {
+    type Error = Error;
+
+    fn try_from(tx: proto::Script) -> Result<Self, Error> {
+        let Chargeable {
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+        } = chargeable(tx.policies, tx.inputs, tx.outputs, tx.witnesses)?;
+
+        Ok(Script {
+            body: ScriptBody {
+                script_gas_limit: tx.script_gas_limit,
+                receipts_root: fixed(&tx.receipts_root, "receipts_root")?,
+                script: tx.script,
+                script_data: tx.script_data,
+            },
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+            metadata: None,
+        })
+    }
+}
+
+impl From<&Create> for proto::Create {
+    fn from(tx: &Create) -> Self {
+        Self {
+            bytecode_witness_index: u32::from(tx.body.bytecode_witness_index),
+            salt: tx.body.salt.to_vec(),
+            storage_slots: to_proto(&tx.body.storage_slots),
+            policies: Some((&tx.policies).into()),
+            inputs: to_proto(&tx.inputs),
+            outputs: to_proto(&tx.outputs),
+            witnesses: to_proto(&tx.witnesses),
+        }
+    }
+}
+
+impl TryFrom<proto::Create> for Create {
+    type Error = Error;
+
+    fn try_from(tx: proto::Create) -> Result<Self, Error> {
+        let Chargeable {
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+        } = chargeable(tx.policies, tx.inputs, tx.outputs, tx.witnesses)?;
+
+        // The storage slots are kept in the received order, even if they aren't sorted,
+        // so the canonical bytes are preserved.
+        Ok(Create {
+            body: CreateBody {
+                bytecode_witness_index: narrow(
+                    tx.bytecode_witness_index,
+                    "bytecode_witness_index",
+                )?,
+                salt: fixed(&tx.salt, "salt")?,
+                storage_slots: from_proto(tx.storage_slots)?,
+            },
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+            metadata: None,
+        })
+    }
+}
+
+impl From<&Mint> for proto::Mint {
+    fn from(tx: &Mint) -> Self {
+        Self {
+            tx_pointer: Some((&tx.tx_pointer).into()),
+            input_contract: Some((&tx.input_contract).into()),
+            output_contract: Some((&tx.output_contract).into()),
+            mint_amount: tx.mint_amount,
+            mint_asset_id: tx.mint_asset_id.to_vec(),
+            gas_price: tx.gas_price,
+        }
+    }
+}
+
+impl TryFrom<proto::Mint> for Mint {
+    type Error = Error;
+
+    fn try_from(tx: proto::Mint) -> Result<Self, Error> {
+        Ok(Mint {
+            tx_pointer: tx_pointer(tx.tx_pointer)?,
+            input_contract: required(tx.input_contract, "input_contract")?.try_into()?,
+            output_contract: required(tx.output_contract, "output_contract")?
+                .try_into()?,
+            mint_amount: tx.mint_amount,
+            mint_asset_id: fixed(&tx.mint_asset_id, "mint_asset_id")?,
+            gas_price: tx.gas_price,
+            metadata: None,
+        })
+    }
+}
+
+impl From<&UpgradePurpose> for proto::UpgradePurpose {
+    fn from(purpose: &UpgradePurpose) -> Self {
+        let variant = match purpose {
+            UpgradePurpose::ConsensusParameters {
+                witness_index,
+                checksum,
+            } => proto::UpgradePurposeVariant::ConsensusParameters(
+                proto::ConsensusParametersUpgrade {
+                    witness_index: u32::from(*witness_index),
+                    checksum: checksum.to_vec(),
+                },
+            ),
+            UpgradePurpose::StateTransition { root } => {
+                proto::UpgradePurposeVariant::StateTransition(
+                    proto::StateTransitionUpgrade {
+                        root: root.to_vec(),
+                    },
+                )
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<proto::UpgradePurpose> for UpgradePurpose {
+    type Error = Error;
+
+    fn try_from(purpose: proto::UpgradePurpose) -> Result<Self, Error> {
+        let purpose = match required(purpose.variant, "variant")? {
+            proto::UpgradePurposeVariant::ConsensusParameters(purpose) => {
+                UpgradePurpose::ConsensusParameters {
+                    witness_index: narrow(purpose.witness_index, "witness_index")?,
+                    checksum: fixed(&purpose.checksum, "checksum")?,
+                }
+            }
+            proto::UpgradePurposeVariant::StateTransition(purpose) => {
+                UpgradePurpose::StateTransition {
+                    root: fixed(&purpose.root, "root")?,
+                }
+            }
+        };
+
+        Ok(purpose)
+    }
+}
+
+impl From<&Upgrade> for proto::Upgrade {
+    fn from(tx: &Upgrade) -> Self {
+        Self {
+            purpose: Some((&tx.body.purpose).into()),
+            policies: Some((&tx.policies).into()),
+            inputs: to_proto(&tx.inputs),
+            outputs: to_proto(&tx.outputs),
+            witnesses: to_proto(&tx.witnesses),
+        }
+    }
+}
+
+impl TryFrom<proto::Upgrade> for Upgrade {
+    type Error = Error;
+
+    fn try_from(tx: proto::Upgrade) -> Result<Self, Error> {
+        let Chargeable {
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+        } = chargeable(tx.policies, tx.inputs, tx.outputs, tx.witnesses)?;
+
+        Ok(Upgrade {
+            body: UpgradeBody {
+                purpose: required(tx.purpose, "purpose")?.try_into()?,
+            },
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+            metadata: None,
+        })
+    }
+}
+
+impl From<&Upload> for proto::Upload {
+    fn from(tx: &Upload) -> Self {
+        Self {
+            root: tx.body.root.to_vec(),
+            witness_index: u32::from(tx.body.witness_index),
+            subsection_index: u32::from(tx.body.subsection_index),
+            subsections_number: u32::from(tx.body.subsections_number),
+            proof_set: tx.body.proof_set.iter().map(|node| node.to_vec()).collect(),
+            policies: Some((&tx.policies).into()),
+            inputs: to_proto(&tx.inputs),
+            outputs: to_proto(&tx.outputs),
+            witnesses: to_proto(&tx.witnesses),
+        }
+    }
+}
+
+impl TryFrom<proto::Upload> for Upload {
+    type Error = Error;
+
+    fn try_from(tx: proto::Upload) -> Result<Self, Error> {
+        let Chargeable {
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+        } = chargeable(tx.policies, tx.inputs, tx.outputs, tx.witnesses)?;
+
+        Ok(Upload {
+            body: UploadBody {
+                root: fixed(&tx.root, "root")?,
+                witness_index: narrow(tx.witness_index, "witness_index")?,
+                subsection_index: narrow(tx.subsection_index, "subsection_index")?,
+                subsections_number: narrow(tx.subsections_number, "subsections_number")?,
+                proof_set: tx
+                    .proof_set
+                    .iter()
+                    .map(|node| fixed(node, "proof_set"))
+                    .collect::<Result<_, _>>()?,
+            },
+            policies,
+            inputs,
+            outputs,
+            witnesses,
+            metadata: None,
+        })
+    }
+}
+
+impl From<&Transaction> for proto::Transaction {
+    fn from(tx: &Transaction) -> Self {
+        let variant = match tx {
+            Transaction::Script(tx) => proto::TransactionVariant::Script(tx.into()),
+            Transaction::Create(tx) => proto::TransactionVariant::Create(tx.into()),
+            Transaction::Mint(tx) => proto::TransactionVariant::Mint(tx.into()),
+            Transaction::Upgrade(tx) => proto::TransactionVariant::Upgrade(tx.into()),
+            Transaction::Upload(tx) => proto::TransactionVariant::Upload(tx.into()),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl From<Transaction> for proto::Transaction {
+    fn from(tx: Transaction) -> Self {
+        (&tx).into()
+    }
+}
+
+impl TryFrom<proto::Transaction> for Transaction {
+    type Error = Error;
+
+    fn try_from(tx: proto::Transaction) -> Result<Self, Error> {
+        let tx = match required(tx.variant, "variant")? {
+            proto::TransactionVariant::Script(tx) => Script::try_from(tx)?.into(),
+            proto::TransactionVariant::Create(tx) => Create::try_from(tx)?.into(),
+            proto::TransactionVariant::Mint(tx) => Mint::try_from(tx)?.into(),
+            proto::TransactionVariant::Upgrade(tx) => Upgrade::try_from(tx)?.into(),
+            proto::TransactionVariant::Upload(tx) => Upload::try_from(tx)?.into(),
+        };
+
+        Ok(tx)
+    }
+}
+
+impl From<&ContractInput> for proto::ContractInput {
+    fn from(contract: &ContractInput) -> Self {
+        Self {
+            utxo_id: Some((&contract.utxo_id).into()),
+            balance_root: contract.balance_root.to_vec(),
+            state_root: contract.state_root.to_vec(),
+            tx_pointer: Some((&contract.tx_pointer).into()),
+            contract_id: contract.contract_id.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::ContractInput> for ContractInput {
+    type Error = Error;
+
+    fn try_from(contract: proto::ContractInput) -> Result<Self, Error> {
+        Ok(ContractInput {
+            utxo_id: utxo_id(contract.utxo_id)?,
+            balance_root: fixed(&contract.balance_root, "balance_root")?,
+            state_root: fixed(&contract.state_root, "state_root")?,
+            tx_pointer: tx_pointer(contract.tx_pointer)?,
+            contract_id: fixed(&contract.contract_id, "contract_id")?,
+        })
+    }
+}
+
+impl From<&Input> for proto::Input {
+    fn from(input: &Input) -> Self {
+        use proto::InputVariant as Variant;
+
+        let variant = match input {
+            Input::CoinSigned(CoinSigned {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                tx_pointer,
+                witness_index,
+                ..
+            }) => Variant::CoinSigned(proto::CoinInput {
+                utxo_id: Some(utxo_id.into()),
+                owner: owner.to_vec(),
+                amount: *amount,
+                asset_id: asset_id.to_vec(),
+                tx_pointer: Some(tx_pointer.into()),
+                witness_index: u32::from(*witness_index),
+                ..Default::default()
+            }),
+            Input::CoinPredicate(CoinPredicate {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                tx_pointer,
+                predicate_gas_used,
+                predicate,
+                predicate_data,
+                ..
+            }) => Variant::CoinPredicate(proto::CoinInput {
+                utxo_id: Some(utxo_id.into()),
+                owner: owner.to_vec(),
+                amount: *amount,
+                asset_id: asset_id.to_vec(),
+                tx_pointer: Some(tx_pointer.into()),
+                predicate_gas_used: *predicate_gas_used,
+                predicate: predicate.clone(),
+                predicate_data: predicate_data.clone(),
+                ..Default::default()
+            }),
+            Input::Contract(contract) => Variant::Contract(contract.into()),
+            Input::ContractReadOnly(contract) => {
+                Variant::ContractReadOnly(contract.into())
+            }
+            Input::MessageCoinSigned(MessageCoinSigned {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                witness_index,
+                ..
+            }) => Variant::MessageCoinSigned(proto::MessageInput {
+                sender: sender.to_vec(),
+                recipient: recipient.to_vec(),
+                amount: *amount,
+                nonce: nonce.to_vec(),
+                witness_index: u32::from(*witness_index),
+                ..Default::default()
+            }),
+            Input::MessageCoinPredicate(MessageCoinPredicate {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                predicate_gas_used,
+                predicate,
+                predicate_data,
+                ..
+            }) => Variant::MessageCoinPredicate(proto::MessageInput {
+                sender: sender.to_vec(),
+                recipient: recipient.to_vec(),
+                amount: *amount,
+                nonce: nonce.to_vec(),
+                predicate_gas_used: *predicate_gas_used,
+                predicate: predicate.clone(),
+                predicate_data: predicate_data.clone(),
+                ..Default::default()
+            }),
+            Input::MessageDataSigned(MessageDataSigned {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                witness_index,
+                data,
+                ..
+            }) => Variant::MessageDataSigned(proto::MessageInput {
+                sender: sender.to_vec(),
+                recipient: recipient.to_vec(),
+                amount: *amount,
+                nonce: nonce.to_vec(),
+                witness_index: u32::from(*witness_index),
+                data: data.clone(),
+                ..Default::default()
+            }),
+            Input::MessageDataPredicate(MessageDataPredicate {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                predicate_gas_used,
+                data,
+                predicate,
+                predicate_data,
+                ..
+            }) => Variant::MessageDataPredicate(proto::MessageInput {
+                sender: sender.to_vec(),
+                recipient: recipient.to_vec(),
+                amount: *amount,
+                nonce: nonce.to_vec(),
+                predicate_gas_used: *predicate_gas_used,
+                data: data.clone(),
+                predicate: predicate.clone(),
+                predicate_data: predicate_data.clone(),
+                ..Default::default()
+            }),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<proto::Input> for Input {
+    type Error = Error;
+
+    fn try_from(input: proto::Input) -> Result<Self, Error> {
+        use proto::InputVariant as Variant;
+
+        let witness_index = |index| narrow(index, "witness_index").map(WitnessIndex::new);
+
+        let input = match required(input.variant, "variant")? {
+            Variant::CoinSigned(coin) => Input::coin_signed(
+                utxo_id(coin.utxo_id)?,
+                fixed(&coin.owner, "owner")?,
+                coin.amount,
+                fixed(&coin.asset_id, "asset_id")?,
+                tx_pointer(coin.tx_pointer)?,
+                witness_index(coin.witness_index)?,
+            ),
+            Variant::CoinPredicate(coin) => Input::coin_predicate(
+                utxo_id(coin.utxo_id)?,
+                fixed(&coin.owner, "owner")?,
+                coin.amount,
+                fixed(&coin.asset_id, "asset_id")?,
+                tx_pointer(coin.tx_pointer)?,
+                coin.predicate_gas_used,
+                coin.predicate,
+                coin.predicate_data,
+            ),
+            Variant::Contract(contract) => Input::Contract(contract.try_into()?),
+            Variant::ContractReadOnly(contract) => {
+                Input::ContractReadOnly(contract.try_into()?)
+            }
+            Variant::MessageCoinSigned(message) => Input::message_coin_signed(
+                fixed(&message.sender, "sender")?,
+                fixed(&message.recipient, "recipient")?,
+                message.amount,
+                fixed(&message.nonce, "nonce")?,
+                witness_index(message.witness_index)?,
+            ),
+            Variant::MessageCoinPredicate(message) => Input::message_coin_predicate(
+                fixed(&message.sender, "sender")?,
+                fixed(&message.recipient, "recipient")?,
+                message.amount,
+                fixed(&message.nonce, "nonce")?,
+                message.predicate_gas_used,
+                message.predicate,
+                message.predicate_data,
+            ),
+            Variant::MessageDataSigned(message) => Input::message_data_signed(
+                fixed(&message.sender, "sender")?,
+                fixed(&message.recipient, "recipient")?,
+                message.amount,
+                fixed(&message.nonce, "nonce")?,
+                witness_index(message.witness_index)?,
+                message.data,
+            ),
+            Variant::MessageDataPredicate(message) => Input::message_data_predicate(
+                fixed(&message.sender, "sender")?,
+                fixed(&message.recipient, "recipient")?,
+                message.amount,
+                fixed(&message.nonce, "nonce")?,
+                message.predicate_gas_used,
+                message.data,
+                message.predicate,
+                message.predicate_data,
+            ),
+        };
+
+        Ok(input)
+    }
+}
+
+impl From<&ContractOutput> for proto::ContractOutput {
+    fn from(contract: &ContractOutput) -> Self {
+        Self {
+            input_index: u32::from(contract.input_index),
+            balance_root: contract.balance_root.to_vec(),
+            state_root: contract.state_root.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<proto::ContractOutput> for ContractOutput {
+    type Error = Error;
+
+    fn try_from(contract: proto::ContractOutput) -> Result<Self, Error> {
+        Ok(ContractOutput {
+            input_index: narrow(contract.input_index, "input_index")?,
+            balance_root: fixed(&contract.balance_root, "balance_root")?,
+            state_root: fixed(&contract.state_root, "state_root")?,
+        })
+    }
+}
+
+impl From<&Output> for proto::Output {
+    fn from(output: &Output) -> Self {
+        use proto::OutputVariant as Variant;
+
+        let coin = |to: &[u8], amount: u64, asset_id: &[u8]| proto::CoinOutput {
+            to: to.to_vec(),
+            amount,
+            asset_id: asset_id.to_vec(),
+        };
+
+        let variant = match output {
+            Output::Coin {
+                to,
+                amount,
+                asset_id,
+            } => Variant::Coin(coin(to.as_ref(), *amount, asset_id.as_ref())),
+            Output::Contract(contract) => Variant::Contract(contract.into()),
+            Output::Change {
+                to,
+                amount,
+                asset_id,
+            } => Variant::Change(coin(to.as_ref(), *amount, asset_id.as_ref())),
+            Output::Variable {
+                to,
+                amount,
+                asset_id,
+            } => Variant::Variable(coin(to.as_ref(), *amount, asset_id.as_ref())),
+            Output::ContractCreated {
+                contract_id,
+                state_root,
+            } => Variant::ContractCreated(proto::ContractCreatedOutput {
+                contract_id: contract_id.to_vec(),
+                state_root: state_root.to_vec(),
+            }),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<proto::Output> for Output {
+    type Error = Error;
+
+    fn try_from(output: proto::Output) -> Result<Self, Error> {
+        use proto::OutputVariant as Variant;
+
+        let output = match required(output.variant, "variant")? {
+            Variant::Coin(coin) => Output::coin(
+                fixed(&coin.to, "to")?,
+                coin.amount,
+                fixed(&coin.asset_id, "asset_id")?,
+            ),
+            Variant::Contract(contract) => Output::Contract(contract.try_into()?),
+            Variant::Change(coin) => Output::change(
+                fixed(&coin.to, "to")?,
+                coin.amount,
+                fixed(&coin.asset_id, "asset_id")?,
+            ),
+            Variant::Variable(coin) => Output::variable(
+                fixed(&coin.to, "to")?,
+                coin.amount,
+                fixed(&coin.asset_id, "asset_id")?,
+            ),
+            Variant::ContractCreated(contract) => Output::contract_created(
+                fixed(&contract.contract_id, "contract_id")?,
+                fixed(&contract.state_root, "state_root")?,
+            ),
+        };
+
+        Ok(output)
+    }
+}
+
+impl From<&Receipt> for proto::Receipt {
+    fn from(receipt: &Receipt) -> Self {
+        use proto::ReceiptVariant as Variant;
+
+        let variant = match receipt {
+            Receipt::Call {
+                id,
+                to,
+                amount,
+                asset_id,
+                gas,
+                param1,
+                param2,
+                pc,
+                is,
+            } => Variant::Call(proto::CallReceipt {
+                id: id.to_vec(),
+                to: to.to_vec(),
+                amount: *amount,
+                asset_id: asset_id.to_vec(),
+                gas: *gas,
+                param1: *param1,
+                param2: *param2,
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::Return { id, val, pc, is } => {
+                Variant::Return(proto::ReturnReceipt {
+                    id: id.to_vec(),
+                    val: *val,
+                    pc: *pc,
+                    is: *is,
+                })
+            }
+            Receipt::ReturnData {
+                id,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data,
+            } => Variant::ReturnData(proto::ReturnDataReceipt {
+                id: id.to_vec(),
+                ptr: *ptr,
+                len: *len,
+                digest: digest.to_vec(),
+                pc: *pc,
+                is: *is,
+                data: data.clone(),
+            }),
+            Receipt::Panic {
+                id,
+                reason,
+                pc,
+                is,
+                contract_id,
+            } => Variant::Panic(proto::PanicReceipt {
+                id: id.to_vec(),
+                reason: (*reason).into(),
+                pc: *pc,
+                is: *is,
+                contract_id: contract_id.map(|contract_id| contract_id.to_vec()),
+            }),
+            Receipt::Revert { id, ra, pc, is } => Variant::Revert(proto::RevertReceipt {
+                id: id.to_vec(),
+                ra: *ra,
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::Log {
+                id,
+                ra,
+                rb,
+                rc,
+                rd,
+                pc,
+                is,
+            } => Variant::Log(proto::LogReceipt {
+                id: id.to_vec(),
+                ra: *ra,
+                rb: *rb,
+                rc: *rc,
+                rd: *rd,
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::LogData {
+                id,
+                ra,
+                rb,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data,
+            } => Variant::LogData(proto::LogDataReceipt {
+                id: id.to_vec(),
+                ra: *ra,
+                rb: *rb,
+                ptr: *ptr,
+                len: *len,
+                digest: digest.to_vec(),
+                pc: *pc,
+                is: *is,
+                data: data.clone(),
+            }),
+            Receipt::Transfer {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Variant::Transfer(proto::TransferReceipt {
+                id: id.to_vec(),
+                to: to.to_vec(),
+                amount: *amount,
+                asset_id: asset_id.to_vec(),
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::TransferOut {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Variant::TransferOut(proto::TransferOutReceipt {
+                id: id.to_vec(),
+                to: to.to_vec(),
+                amount: *amount,
+                asset_id: asset_id.to_vec(),
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::ScriptResult { result, gas_used } => {
+                let (result, generic_failure) = match result {
+                    ScriptExecutionResult::Success => (SUCCESS, 0),
+                    ScriptExecutionResult::Revert => (REVERT, 0),
+                    ScriptExecutionResult::Panic => (PANIC, 0),
+                    ScriptExecutionResult::GenericFailure(code) => {
+                        (GENERIC_FAILURE, *code)
+                    }
+                };
+                Variant::ScriptResult(proto::ScriptResultReceipt {
+                    result,
+                    generic_failure,
+                    gas_used: *gas_used,
+                })
+            }
+            Receipt::MessageOut {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                len,
+                digest,
+                data,
+            } => Variant::MessageOut(proto::MessageOutReceipt {
+                sender: sender.to_vec(),
+                recipient: recipient.to_vec(),
+                amount: *amount,
+                nonce: nonce.to_vec(),
+                len: *len,
+                digest: digest.to_vec(),
+                data: data.clone(),
+            }),
+            Receipt::Mint {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => Variant::Mint(proto::MintReceipt {
+                sub_id: sub_id.to_vec(),
+                contract_id: contract_id.to_vec(),
+                val: *val,
+                pc: *pc,
+                is: *is,
+            }),
+            Receipt::Burn {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => Variant::Burn(proto::BurnReceipt {
+                sub_id: sub_id.to_vec(),
+                contract_id: contract_id.to_vec(),
+                val: *val,
+                pc: *pc,
+                is: *is,
+            }),
+            #[cfg(feature = "unknown-receipts")]
+            Receipt::Unknown {
+                discriminant,
+                bytes,
+            } => Variant::Unknown(proto::UnknownReceipt {
+                discriminant: *discriminant,
+                bytes: bytes.clone(),
+            }),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl From<Receipt> for proto::Receipt {
+    fn from(receipt: Receipt) -> Self {
+        (&receipt).into()
+    }
+}
+
+impl TryFrom<proto::Receipt> for Receipt {
+    type Error = Error;
+
+    fn try_from(receipt: proto::Receipt) -> Result<Self, Error> {
+        use proto::ReceiptVariant as Variant;
+
+        let optional_fixed = |bytes: Option<Vec<u8>>, field| {
+            bytes.map(|bytes| fixed(&bytes, field)).transpose()
+        };
+
+        let receipt = match required(receipt.variant, "variant")? {
+            Variant::Call(receipt) => Receipt::Call {
+                id: fixed(&receipt.id, "id")?,
+                to: fixed(&receipt.to, "to")?,
+                amount: receipt.amount,
+                asset_id: fixed(&receipt.asset_id, "asset_id")?,
+                gas: receipt.gas,
+                param1: receipt.param1,
+                param2: receipt.param2,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::Return(receipt) => Receipt::Return {
+                id: fixed(&receipt.id, "id")?,
+                val: receipt.val,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::ReturnData(receipt) => Receipt::ReturnData {
+                id: fixed(&receipt.id, "id")?,
+                ptr: receipt.ptr,
+                len: receipt.len,
+                digest: fixed(&receipt.digest, "digest")?,
+                pc: receipt.pc,
+                is: receipt.is,
+                data: receipt.data,
+            },
+            Variant::Panic(receipt) => Receipt::Panic {
+                id: fixed(&receipt.id, "id")?,
+                reason: PanicInstruction::from(receipt.reason),
+                pc: receipt.pc,
+                is: receipt.is,
+                contract_id: optional_fixed(receipt.contract_id, "contract_id")?,
+            },
+            Variant::Revert(receipt) => Receipt::Revert {
+                id: fixed(&receipt.id, "id")?,
+                ra: receipt.ra,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::Log(receipt) => Receipt::Log {
+                id: fixed(&receipt.id, "id")?,
+                ra: receipt.ra,
+                rb: receipt.rb,
+                rc: receipt.rc,
+                rd: receipt.rd,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::LogData(receipt) => Receipt::LogData {
+                id: fixed(&receipt.id, "id")?,
+                ra: receipt.ra,
+                rb: receipt.rb,
+                ptr: receipt.ptr,
+                len: receipt.len,
+                digest: fixed(&receipt.digest, "digest")?,
+                pc: receipt.pc,
+                is: receipt.is,
+                data: receipt.data,
+            },
+            Variant::Transfer(receipt) => Receipt::Transfer {
+                id: fixed(&receipt.id, "id")?,
+                to: fixed(&receipt.to, "to")?,
+                amount: receipt.amount,
+                asset_id: fixed(&receipt.asset_id, "asset_id")?,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::TransferOut(receipt) => Receipt::TransferOut {
+                id: fixed(&receipt.id, "id")?,
+                to: fixed(&receipt.to, "to")?,
+                amount: receipt.amount,
+                asset_id: fixed(&receipt.asset_id, "asset_id")?,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::ScriptResult(receipt) => {
+                let result = match receipt.result {
+                    SUCCESS => ScriptExecutionResult::Success,
+                    REVERT => ScriptExecutionResult::Revert,
+                    PANIC => ScriptExecutionResult::Panic,
+                    GENERIC_FAILURE => {
+                        ScriptExecutionResult::GenericFailure(receipt.generic_failure)
+                    }
+                    result => {
+                        return Err(Error::OutOfRange {
+                            field: "result",
+                            value: u64::from(result),
+                        })
+                    }
+                };
+                Receipt::ScriptResult {
+                    result,
+                    gas_used: receipt.gas_used,
+                }
+            }
+            Variant::MessageOut(receipt) => Receipt::MessageOut {
+                sender: fixed(&receipt.sender, "sender")?,
+                recipient: fixed(&receipt.recipient, "recipient")?,
+                amount: receipt.amount,
+                nonce: fixed(&receipt.nonce, "nonce")?,
+                len: receipt.len,
+                digest: fixed(&receipt.digest, "digest")?,
+                data: receipt.data,
+            },
+            Variant::Mint(receipt) => Receipt::Mint {
+                sub_id: fixed(&receipt.sub_id, "sub_id")?,
+                contract_id: fixed(&receipt.contract_id, "contract_id")?,
+                val: receipt.val,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            Variant::Burn(receipt) => Receipt::Burn {
+                sub_id: fixed(&receipt.sub_id, "sub_id")?,
+                contract_id: fixed(&receipt.contract_id, "contract_id")?,
+                val: receipt.val,
+                pc: receipt.pc,
+                is: receipt.is,
+            },
+            #[cfg(feature = "unknown-receipts")]
+            Variant::Unknown(receipt) => Receipt::Unknown {
+                discriminant: receipt.discriminant,
+                bytes: receipt.bytes,
+            },
+            #[cfg(not(feature = "unknown-receipts"))]
+            Variant::Unknown(_) => return Err(Error::UnknownReceipt),
+        };
+
+        Ok(receipt)
+    }
+}
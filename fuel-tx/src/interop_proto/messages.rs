@@ -0,0 +1,545 @@
+//! The Rust types of the messages of `proto/fuel_tx.proto`.
+//!
+//! The types follow the conventions of the protobuf code generators: the `bytes` fields
+//! are `Vec<u8>`, the message fields are `Option`, and a `oneof` is an `Option` of an
+//! enum. The tags and the field names must match the schema.
+
+use super::{
+    wire::{
+        self,
+        Field,
+        Message,
+        Value,
+    },
+    Error,
+};
+use alloc::vec::Vec;
+
+macro_rules! codec {
+    ($name:ident) => {
+        impl $name {
+            /// Encodes the message into the protobuf wire format.
+            pub fn encode_to_vec(&self) -> Vec<u8> {
+                let mut buf = Vec::new();
+                Message::encode_raw(self, &mut buf);
+                buf
+            }
+
+            /// Decodes the message from the protobuf wire format. Unknown fields are
+            /// skipped.
+            pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+                let mut message = Self::default();
+                Message::merge(&mut message, bytes)?;
+                Ok(message)
+            }
+        }
+    };
+}
+
+macro_rules! message {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $ty:ty = $tag:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: $ty,
+            )*
+        }
+
+        codec!($name);
+
+        impl Message for $name {
+            fn encode_raw(&self, buf: &mut Vec<u8>) {
+                $(Field::encode_field(&self.$field, $tag, buf);)*
+            }
+
+            fn merge_field(&mut self, tag: u32, value: Value<'_>) -> Result<(), Error> {
+                match tag {
+                    $($tag => Field::merge_field(&mut self.$field, value),)*
+                    _ => Ok(()),
+                }
+            }
+        }
+    };
+}
+
+/// The message with a single `oneof variant`. The last variant read from the wire wins,
+/// and the repeated occurrences of the same variant are merged.
+macro_rules! oneof {
+    (
+        $(#[$meta:meta])*
+        $name:ident($variant_name:ident) {
+            $(
+                $variant:ident($ty:ty) = $tag:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, PartialEq, Eq)]
+        pub struct $name {
+            pub variant: Option<$variant_name>,
+        }
+
+        #[doc = concat!("The `oneof variant` of [`", stringify!($name), "`].")]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $variant_name {
+            $($variant($ty),)*
+        }
+
+        codec!($name);
+
+        impl Message for $name {
+            fn encode_raw(&self, buf: &mut Vec<u8>) {
+                match &self.variant {
+                    $(Some($variant_name::$variant(message)) => {
+                        wire::write_message(buf, $tag, message)
+                    })*
+                    None => {}
+                }
+            }
+
+            fn merge_field(&mut self, tag: u32, value: Value<'_>) -> Result<(), Error> {
+                match tag {
+                    $($tag => {
+                        let mut message = match self.variant.take() {
+                            Some($variant_name::$variant(message)) => message,
+                            _ => <$ty>::default(),
+                        };
+                        Message::merge(&mut message, wire::len(value)?)?;
+                        self.variant = Some($variant_name::$variant(message));
+                        Ok(())
+                    })*
+                    _ => Ok(()),
+                }
+            }
+        }
+    };
+}
+
+message! {
+    /// The `UtxoId` message.
+    UtxoId {
+        tx_id: Vec<u8> = 1,
+        output_index: u32 = 2,
+    }
+}
+
+message! {
+    /// The `TxPointer` message.
+    TxPointer {
+        block_height: u32 = 1,
+        tx_index: u32 = 2,
+    }
+}
+
+message! {
+    /// The `Policies` message.
+    Policies {
+        /// The bitmask of the set policies, including the ones unknown to this version.
+        bits: u32 = 1,
+        /// The values of the set policies, in the order of the bits.
+        values: Vec<u64> = 2,
+    }
+}
+
+message! {
+    /// The `Witness` message.
+    Witness {
+        data: Vec<u8> = 1,
+    }
+}
+
+message! {
+    /// The `StorageSlot` message.
+    StorageSlot {
+        key: Vec<u8> = 1,
+        value: Vec<u8> = 2,
+    }
+}
+
+oneof! {
+    /// The `Transaction` message.
+    Transaction(TransactionVariant) {
+        Script(Script) = 1,
+        Create(Create) = 2,
+        Mint(Mint) = 3,
+        Upgrade(Upgrade) = 4,
+        Upload(Upload) = 5,
+    }
+}
+
+message! {
+    /// The `Script` message.
+    Script {
+        script_gas_limit: u64 = 1,
+        receipts_root: Vec<u8> = 2,
+        script: Vec<u8> = 3,
+        script_data: Vec<u8> = 4,
+        policies: Option<Policies> = 5,
+        inputs: Vec<Input> = 6,
+        outputs: Vec<Output> = 7,
+        witnesses: Vec<Witness> = 8,
+    }
+}
+
+message! {
+    /// The `Create` message.
+    Create {
+        bytecode_witness_index: u32 = 1,
+        salt: Vec<u8> = 2,
+        storage_slots: Vec<StorageSlot> = 3,
+        policies: Option<Policies> = 4,
+        inputs: Vec<Input> = 5,
+        outputs: Vec<Output> = 6,
+        witnesses: Vec<Witness> = 7,
+    }
+}
+
+message! {
+    /// The `Mint` message.
+    Mint {
+        tx_pointer: Option<TxPointer> = 1,
+        input_contract: Option<ContractInput> = 2,
+        output_contract: Option<ContractOutput> = 3,
+        mint_amount: u64 = 4,
+        mint_asset_id: Vec<u8> = 5,
+        gas_price: u64 = 6,
+    }
+}
+
+message! {
+    /// The `Upgrade` message.
+    Upgrade {
+        purpose: Option<UpgradePurpose> = 1,
+        policies: Option<Policies> = 2,
+        inputs: Vec<Input> = 3,
+        outputs: Vec<Output> = 4,
+        witnesses: Vec<Witness> = 5,
+    }
+}
+
+oneof! {
+    /// The `UpgradePurpose` message.
+    UpgradePurpose(UpgradePurposeVariant) {
+        ConsensusParameters(ConsensusParametersUpgrade) = 1,
+        StateTransition(StateTransitionUpgrade) = 2,
+    }
+}
+
+message! {
+    /// The `ConsensusParametersUpgrade` message.
+    ConsensusParametersUpgrade {
+        witness_index: u32 = 1,
+        checksum: Vec<u8> = 2,
+    }
+}
+
+message! {
+    /// The `StateTransitionUpgrade` message.
+    StateTransitionUpgrade {
+        root: Vec<u8> = 1,
+    }
+}
+
+message! {
+    /// The `Upload` message.
+    Upload {
+        root: Vec<u8> = 1,
+        witness_index: u32 = 2,
+        subsection_index: u32 = 3,
+        subsections_number: u32 = 4,
+        proof_set: Vec<Vec<u8>> = 5,
+        policies: Option<Policies> = 6,
+        inputs: Vec<Input> = 7,
+        outputs: Vec<Output> = 8,
+        witnesses: Vec<Witness> = 9,
+    }
+}
+
+oneof! {
+    /// The `Input` message.
+    Input(InputVariant) {
+        CoinSigned(CoinInput) = 1,
+        CoinPredicate(CoinInput) = 2,
+        Contract(ContractInput) = 3,
+        ContractReadOnly(ContractInput) = 4,
+        MessageCoinSigned(MessageInput) = 5,
+        MessageCoinPredicate(MessageInput) = 6,
+        MessageDataSigned(MessageInput) = 7,
+        MessageDataPredicate(MessageInput) = 8,
+    }
+}
+
+message! {
+    /// The `CoinInput` message. The fields not used by the variant are left empty.
+    CoinInput {
+        utxo_id: Option<UtxoId> = 1,
+        owner: Vec<u8> = 2,
+        amount: u64 = 3,
+        asset_id: Vec<u8> = 4,
+        tx_pointer: Option<TxPointer> = 5,
+        witness_index: u32 = 6,
+        predicate_gas_used: u64 = 7,
+        predicate: Vec<u8> = 8,
+        predicate_data: Vec<u8> = 9,
+    }
+}
+
+message! {
+    /// The `ContractInput` message.
+    ContractInput {
+        utxo_id: Option<UtxoId> = 1,
+        balance_root: Vec<u8> = 2,
+        state_root: Vec<u8> = 3,
+        tx_pointer: Option<TxPointer> = 4,
+        contract_id: Vec<u8> = 5,
+    }
+}
+
+message! {
+    /// The `MessageInput` message. The fields not used by the variant are left empty.
+    MessageInput {
+        sender: Vec<u8> = 1,
+        recipient: Vec<u8> = 2,
+        amount: u64 = 3,
+        nonce: Vec<u8> = 4,
+        witness_index: u32 = 5,
+        predicate_gas_used: u64 = 6,
+        data: Vec<u8> = 7,
+        predicate: Vec<u8> = 8,
+        predicate_data: Vec<u8> = 9,
+    }
+}
+
+oneof! {
+    /// The `Output` message.
+    Output(OutputVariant) {
+        Coin(CoinOutput) = 1,
+        Contract(ContractOutput) = 2,
+        Change(CoinOutput) = 3,
+        Variable(CoinOutput) = 4,
+        ContractCreated(ContractCreatedOutput) = 5,
+    }
+}
+
+message! {
+    /// The `CoinOutput` message.
+    CoinOutput {
+        to: Vec<u8> = 1,
+        amount: u64 = 2,
+        asset_id: Vec<u8> = 3,
+    }
+}
+
+message! {
+    /// The `ContractOutput` message.
+    ContractOutput {
+        input_index: u32 = 1,
+        balance_root: Vec<u8> = 2,
+        state_root: Vec<u8> = 3,
+    }
+}
+
+message! {
+    /// The `ContractCreatedOutput` message.
+    ContractCreatedOutput {
+        contract_id: Vec<u8> = 1,
+        state_root: Vec<u8> = 2,
+    }
+}
+
+oneof! {
+    /// The `Receipt` message.
+    Receipt(ReceiptVariant) {
+        Call(CallReceipt) = 1,
+        Return(ReturnReceipt) = 2,
+        ReturnData(ReturnDataReceipt) = 3,
+        Panic(PanicReceipt) = 4,
+        Revert(RevertReceipt) = 5,
+        Log(LogReceipt) = 6,
+        LogData(LogDataReceipt) = 7,
+        Transfer(TransferReceipt) = 8,
+        TransferOut(TransferOutReceipt) = 9,
+        ScriptResult(ScriptResultReceipt) = 10,
+        MessageOut(MessageOutReceipt) = 11,
+        Mint(MintReceipt) = 12,
+        Burn(BurnReceipt) = 13,
+        Unknown(UnknownReceipt) = 14,
+    }
+}
+
+message! {
+    /// The `CallReceipt` message.
+    CallReceipt {
+        id: Vec<u8> = 1,
+        to: Vec<u8> = 2,
+        amount: u64 = 3,
+        asset_id: Vec<u8> = 4,
+        gas: u64 = 5,
+        param1: u64 = 6,
+        param2: u64 = 7,
+        pc: u64 = 8,
+        is: u64 = 9,
+    }
+}
+
+message! {
+    /// The `ReturnReceipt` message.
+    ReturnReceipt {
+        id: Vec<u8> = 1,
+        val: u64 = 2,
+        pc: u64 = 3,
+        is: u64 = 4,
+    }
+}
+
+message! {
+    /// The `ReturnDataReceipt` message.
+    ReturnDataReceipt {
+        id: Vec<u8> = 1,
+        ptr: u64 = 2,
+        len: u64 = 3,
+        digest: Vec<u8> = 4,
+        pc: u64 = 5,
+        is: u64 = 6,
+        data: Option<Vec<u8>> = 7,
+    }
+}
+
+message! {
+    /// The `PanicReceipt` message.
+    PanicReceipt {
+        id: Vec<u8> = 1,
+        /// The `PanicInstruction` packed into a word.
+        reason: u64 = 2,
+        pc: u64 = 3,
+        is: u64 = 4,
+        contract_id: Option<Vec<u8>> = 5,
+    }
+}
+
+message! {
+    /// The `RevertReceipt` message.
+    RevertReceipt {
+        id: Vec<u8> = 1,
+        ra: u64 = 2,
+        pc: u64 = 3,
+        is: u64 = 4,
+    }
+}
+
+message! {
+    /// The `LogReceipt` message.
+    LogReceipt {
+        id: Vec<u8> = 1,
+        ra: u64 = 2,
+        rb: u64 = 3,
+        rc: u64 = 4,
+        rd: u64 = 5,
+        pc: u64 = 6,
+        is: u64 = 7,
+    }
+}
+
+message! {
+    /// The `LogDataReceipt` message.
+    LogDataReceipt {
+        id: Vec<u8> = 1,
+        ra: u64 = 2,
+        rb: u64 = 3,
+        ptr: u64 = 4,
+        len: u64 = 5,
+        digest: Vec<u8> = 6,
+        pc: u64 = 7,
+        is: u64 = 8,
+        data: Option<Vec<u8>> = 9,
+    }
+}
+
+message! {
+    /// The `TransferReceipt` message.
+    TransferReceipt {
+        id: Vec<u8> = 1,
+        to: Vec<u8> = 2,
+        amount: u64 = 3,
+        asset_id: Vec<u8> = 4,
+        pc: u64 = 5,
+        is: u64 = 6,
+    }
+}
+
+message! {
+    /// The `TransferOutReceipt` message.
+    TransferOutReceipt {
+        id: Vec<u8> = 1,
+        to: Vec<u8> = 2,
+        amount: u64 = 3,
+        asset_id: Vec<u8> = 4,
+        pc: u64 = 5,
+        is: u64 = 6,
+    }
+}
+
+message! {
+    /// The `ScriptResultReceipt` message.
+    ScriptResultReceipt {
+        /// The `ScriptResultReceipt.Kind` enum.
+        result: u32 = 1,
+        /// The code of the `GENERIC_FAILURE` result.
+        generic_failure: u64 = 2,
+        gas_used: u64 = 3,
+    }
+}
+
+message! {
+    /// The `MessageOutReceipt` message.
+    MessageOutReceipt {
+        sender: Vec<u8> = 1,
+        recipient: Vec<u8> = 2,
+        amount: u64 = 3,
+        nonce: Vec<u8> = 4,
+        len: u64 = 5,
+        digest: Vec<u8> = 6,
+        data: Option<Vec<u8>> = 7,
+    }
+}
+
+message! {
+    /// The `MintReceipt` message.
+    MintReceipt {
+        sub_id: Vec<u8> = 1,
+        contract_id: Vec<u8> = 2,
+        val: u64 = 3,
+        pc: u64 = 4,
+        is: u64 = 5,
+    }
+}
+
+message! {
+    /// The `BurnReceipt` message.
+    BurnReceipt {
+        sub_id: Vec<u8> = 1,
+        contract_id: Vec<u8> = 2,
+        val: u64 = 3,
+        pc: u64 = 4,
+        is: u64 = 5,
+    }
+}
+
+message! {
+    /// The `UnknownReceipt` message, holding a receipt introduced by a newer version
+    /// as its canonical discriminant and the rest of its canonical bytes.
+    UnknownReceipt {
+        discriminant: u64 = 1,
+        bytes: Vec<u8> = 2,
+    }
+}
@@ -0,0 +1,259 @@
+//! The subset of the protobuf wire format used by the messages of the schema.
+//!
+//! Only the `VARINT` and `LEN` wire types are produced. The fields with the `I64` and
+//! `I32` wire types are skipped like any unknown field, so the messages written by a
+//! newer schema can be decoded.
+
+use super::Error;
+use alloc::vec::Vec;
+
+const VARINT: u8 = 0;
+const I64: u8 = 1;
+const LEN: u8 = 2;
+const I32: u8 = 5;
+
+/// The value of a field read from the wire.
+pub(crate) enum Value<'a> {
+    Varint(u64),
+    Len(&'a [u8]),
+    Fixed,
+}
+
+/// The protobuf message.
+pub(crate) trait Message: Default {
+    /// Appends the fields of the message to the `buf`.
+    fn encode_raw(&self, buf: &mut Vec<u8>);
+
+    /// Merges the field with the `tag` into the message.
+    fn merge_field(&mut self, tag: u32, value: Value<'_>) -> Result<(), Error>;
+
+    /// Merges the fields encoded in the `bytes` into the message.
+    fn merge(&mut self, mut bytes: &[u8]) -> Result<(), Error> {
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes)?;
+            let tag = u32::try_from(key >> 3)
+                .map_err(|_| Error::Malformed("The field tag is too large"))?;
+            if tag == 0 {
+                return Err(Error::Malformed("The field tag is zero"))
+            }
+            let value = match (key & 0x7) as u8 {
+                VARINT => Value::Varint(read_varint(&mut bytes)?),
+                LEN => {
+                    let len = usize::try_from(read_varint(&mut bytes)?)
+                        .map_err(|_| Error::Malformed("The length is too large"))?;
+                    Value::Len(take(&mut bytes, len)?)
+                }
+                I64 => {
+                    take(&mut bytes, 8)?;
+                    Value::Fixed
+                }
+                I32 => {
+                    take(&mut bytes, 4)?;
+                    Value::Fixed
+                }
+                _ => return Err(Error::Malformed("Unsupported wire type")),
+            };
+            self.merge_field(tag, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The type of a field of a message.
+pub(crate) trait Field: Default {
+    /// Appends the field to the `buf`, unless it has the default value.
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>);
+
+    /// Merges the `value` read from the wire into the field.
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error>;
+}
+
+impl Field for u64 {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        if *self != 0 {
+            write_key(buf, tag, VARINT);
+            write_varint(buf, *self);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        *self = varint(value)?;
+        Ok(())
+    }
+}
+
+impl Field for u32 {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        u64::from(*self).encode_field(tag, buf)
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        *self = u32::try_from(varint(value)?)
+            .map_err(|_| Error::Malformed("The `uint32` value is too large"))?;
+        Ok(())
+    }
+}
+
+impl Field for Vec<u8> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        if !self.is_empty() {
+            write_bytes(buf, tag, self);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        *self = len(value)?.to_vec();
+        Ok(())
+    }
+}
+
+/// The `optional bytes` field, encoded even if it is empty.
+impl Field for Option<Vec<u8>> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        if let Some(bytes) = self {
+            write_bytes(buf, tag, bytes);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        *self = Some(len(value)?.to_vec());
+        Ok(())
+    }
+}
+
+/// The `repeated bytes` field.
+impl Field for Vec<Vec<u8>> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        for bytes in self {
+            write_bytes(buf, tag, bytes);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        self.push(len(value)?.to_vec());
+        Ok(())
+    }
+}
+
+/// The `repeated uint64` field, packed by default in proto3. The unpacked encoding is
+/// accepted as well.
+impl Field for Vec<u64> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        if !self.is_empty() {
+            let mut packed = Vec::new();
+            for value in self {
+                write_varint(&mut packed, *value);
+            }
+            write_bytes(buf, tag, &packed);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        match value {
+            Value::Varint(value) => self.push(value),
+            Value::Len(mut packed) => {
+                while !packed.is_empty() {
+                    self.push(read_varint(&mut packed)?);
+                }
+            }
+            Value::Fixed => return Err(Error::Malformed("Unexpected wire type")),
+        }
+        Ok(())
+    }
+}
+
+impl<M: Message> Field for Option<M> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        if let Some(message) = self {
+            write_message(buf, tag, message);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        self.get_or_insert_with(M::default).merge(len(value)?)
+    }
+}
+
+impl<M: Message> Field for Vec<M> {
+    fn encode_field(&self, tag: u32, buf: &mut Vec<u8>) {
+        for message in self {
+            write_message(buf, tag, message);
+        }
+    }
+
+    fn merge_field(&mut self, value: Value<'_>) -> Result<(), Error> {
+        let mut message = M::default();
+        message.merge(len(value)?)?;
+        self.push(message);
+        Ok(())
+    }
+}
+
+/// Appends the `message` as the field with the `tag`, even if it is empty.
+pub(crate) fn write_message<M: Message>(buf: &mut Vec<u8>, tag: u32, message: &M) {
+    let mut bytes = Vec::new();
+    message.encode_raw(&mut bytes);
+    write_bytes(buf, tag, &bytes);
+}
+
+/// Returns the bytes of the `LEN` value.
+pub(crate) fn len<'a>(value: Value<'a>) -> Result<&'a [u8], Error> {
+    match value {
+        Value::Len(bytes) => Ok(bytes),
+        _ => Err(Error::Malformed("Unexpected wire type")),
+    }
+}
+
+fn varint(value: Value<'_>) -> Result<u64, Error> {
+    match value {
+        Value::Varint(value) => Ok(value),
+        _ => Err(Error::Malformed("Unexpected wire type")),
+    }
+}
+
+fn write_bytes(buf: &mut Vec<u8>, tag: u32, bytes: &[u8]) {
+    write_key(buf, tag, LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_key(buf: &mut Vec<u8>, tag: u32, wire_type: u8) {
+    write_varint(buf, u64::from(tag) << 3 | u64::from(wire_type));
+}
+
+pub(crate) fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+pub(crate) fn read_varint(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        value |= u64::from(byte & 0x7f)
+            .checked_shl(shift)
+            .filter(|part| part >> shift == u64::from(byte & 0x7f))
+            .ok_or(Error::Malformed("The varint is too large"))?;
+        if byte & 0x80 == 0 {
+            return Ok(value)
+        }
+    }
+    Err(Error::Malformed("The varint is too large"))
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if bytes.len() < len {
+        return Err(Error::Malformed("The buffer is too short"))
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
@@ -18,6 +18,9 @@ extern crate core;
 pub mod consts;
 mod tx_pointer;
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+
 pub use fuel_asm::{
     PanicInstruction,
     PanicReason,
@@ -36,15 +39,30 @@ pub use fuel_types::{
 };
 pub use tx_pointer::TxPointer;
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 mod builder;
 
+#[cfg(feature = "alloc")]
+pub mod compressed;
+
+#[cfg(feature = "alloc")]
+pub mod conflicts;
+
 #[cfg(feature = "alloc")]
 mod contract;
 
+#[cfg(feature = "interop-proto")]
+pub mod interop_proto;
+
+#[cfg(feature = "alloc")]
+pub mod multisig;
+
 #[cfg(feature = "alloc")]
 mod receipt;
 
+#[cfg(feature = "alloc")]
+pub mod script_data;
+
 #[cfg(feature = "alloc")]
 mod transaction;
 
@@ -54,8 +72,10 @@ mod tests;
 #[cfg(feature = "test-helpers")]
 pub mod test_helper;
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 pub use builder::{
+    AssetBalance,
+    AvailableCoin,
     Buildable,
     Finalizable,
     TransactionBuilder,
@@ -63,13 +83,18 @@ pub use builder::{
 
 #[cfg(feature = "alloc")]
 pub use receipt::{
+    receipt_formatter,
+    receipts_ext,
     Receipt,
+    ReceiptFormatter,
+    ReceiptsExt,
     ScriptExecutionResult,
 };
 
 #[cfg(feature = "alloc")]
 pub use transaction::{
     consensus_parameters,
+    fee,
     field,
     input,
     input::Input,
@@ -89,10 +114,12 @@ pub use transaction::{
     Executable,
     FeeParameters,
     FormatValidityChecks,
+    GasCostValue,
     GasCosts,
     GasCostsValues,
     GasUnit,
     Mint,
+    NewStorageCost,
     PredicateParameters,
     Script,
     ScriptParameters,
@@ -113,6 +140,7 @@ pub use transaction::{
     UtxoId,
     ValidityError,
     Witness,
+    WitnessIndex,
 };
 
 pub use transaction::{
@@ -124,6 +152,9 @@ pub use transaction::{
 #[cfg(feature = "alloc")]
 pub use contract::Contract;
 
+#[cfg(feature = "arbitrary")]
+pub use arbitrary::arbitrary_checkable;
+
 /// Trait extends the functionality of the `ContractId` type.
 pub trait ContractIdExt {
     /// Creates an `AssetId` from the `ContractId` and `sub_id`.
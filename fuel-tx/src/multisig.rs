@@ -0,0 +1,76 @@
+//! Encoding of the witness used by multisignature predicates.
+//!
+//! All parties sign the same transaction id, and their signatures are concatenated
+//! into one witness. The signature at the position `i` starts at the offset
+//! `i * Signature::LEN` of the witness data, so a predicate can iterate over them
+//! without any additional metadata.
+
+use alloc::vec::Vec;
+use fuel_crypto::Signature;
+
+/// Encodes the `signatures` into the payload of the multisignature witness.
+pub fn encode(signatures: &[Signature]) -> Vec<u8> {
+    signatures
+        .iter()
+        .flat_map(|signature| signature.as_ref().iter().copied())
+        .collect()
+}
+
+/// Decodes the signatures from the payload of the multisignature witness.
+///
+/// Returns `None` if the length of the `data` is not a multiple of `Signature::LEN`.
+pub fn decode(data: &[u8]) -> Option<Vec<Signature>> {
+    if data.len() % Signature::LEN != 0 {
+        return None;
+    }
+
+    let signatures = data
+        .chunks_exact(Signature::LEN)
+        .map(|chunk| {
+            let bytes = <[u8; Signature::LEN]>::try_from(chunk)
+                .expect("The chunk has exactly `Signature::LEN` bytes");
+            Signature::from_bytes(bytes)
+        })
+        .collect();
+
+    Some(signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_crypto::{
+        Message,
+        SecretKey,
+    };
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    #[test]
+    fn encode_decode() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let message = Message::new(b"multisig");
+
+        let signatures: Vec<_> = (0..3)
+            .map(|_| Signature::sign(&SecretKey::random(rng), &message))
+            .collect();
+
+        let data = encode(&signatures);
+        assert_eq!(data.len(), 3 * Signature::LEN);
+        assert_eq!(
+            &data[Signature::LEN..2 * Signature::LEN],
+            signatures[1].as_ref()
+        );
+        assert_eq!(decode(&data), Some(signatures));
+        assert_eq!(decode(&[]), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_rejects_truncated_signature() {
+        let data = [0u8; Signature::LEN + 1];
+
+        assert_eq!(decode(&data), None);
+    }
+}
@@ -3,6 +3,7 @@ use alloc::vec::Vec;
 use derivative::Derivative;
 use fuel_asm::PanicInstruction;
 use fuel_crypto::Hasher;
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
 use fuel_types::{
     canonical::{
         Deserialize,
@@ -18,15 +19,19 @@ use fuel_types::{
     Word,
 };
 
+pub mod receipt_formatter;
 mod receipt_repr;
+#[cfg(feature = "serde")]
+mod receipt_serde;
+pub mod receipts_ext;
 mod script_result;
 
 use crate::input::message::compute_message_id;
+pub use receipt_formatter::ReceiptFormatter;
+pub use receipts_ext::ReceiptsExt;
 pub use script_result::ScriptExecutionResult;
 
-#[derive(Clone, Derivative)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Derivative, Deserialize, Serialize)]
 #[derivative(Eq, PartialEq, Hash, Debug)]
 pub enum Receipt {
     Call {
@@ -152,6 +157,20 @@ pub enum Receipt {
         pc: Word,
         is: Word,
     },
+    /// The receipt with the `discriminant` introduced by a newer version, with the
+    /// rest of its canonical encoding kept as opaque `bytes`. It is re-encoded into the
+    /// same bytes, so the receipts root doesn't change.
+    ///
+    /// The size of the unknown receipt is unknown, so it takes the rest of the input:
+    /// only the last receipt of the buffer can be unknown, like with
+    /// [`Deserialize::from_bytes`]. The VM never produces it.
+    #[cfg(feature = "unknown-receipts")]
+    #[canonical(unknown)]
+    Unknown {
+        discriminant: Word,
+        #[derivative(Debug(format_with = "fuel_types::fmt_truncated_hex::<16>"))]
+        bytes: Vec<u8>,
+    },
 }
 
 impl Receipt {
@@ -450,6 +469,8 @@ impl Receipt {
             Self::MessageOut { .. } => None,
             Self::Mint { contract_id, .. } => Some(contract_id),
             Self::Burn { contract_id, .. } => Some(contract_id),
+            #[cfg(feature = "unknown-receipts")]
+            Self::Unknown { .. } => None,
         })
     }
 
@@ -476,6 +497,8 @@ impl Receipt {
             Self::MessageOut { .. } => None,
             Self::Mint { pc, .. } => Some(*pc),
             Self::Burn { pc, .. } => Some(*pc),
+            #[cfg(feature = "unknown-receipts")]
+            Self::Unknown { .. } => None,
         }
     }
 
@@ -494,6 +517,8 @@ impl Receipt {
             Self::MessageOut { .. } => None,
             Self::Mint { is, .. } => Some(*is),
             Self::Burn { is, .. } => Some(*is),
+            #[cfg(feature = "unknown-receipts")]
+            Self::Unknown { .. } => None,
         }
     }
 
@@ -700,6 +725,15 @@ impl Receipt {
             _ => None,
         }
     }
+
+    /// Returns the receipts root committed by the block header: the root of the binary
+    /// Merkle tree with the canonical serialization of each receipt as a leaf. The root
+    /// of the empty `receipts` is the hash of the empty input.
+    pub fn root(receipts: &[Receipt]) -> Bytes32 {
+        MerkleRootCalculator::new()
+            .root_from_iterator(receipts.iter().map(|receipt| receipt.to_bytes()))
+            .into()
+    }
 }
 
 fn trim_contract_id(id: Option<&ContractId>) -> Option<&ContractId> {
@@ -713,9 +747,28 @@ fn trim_contract_id(id: Option<&ContractId>) -> Option<&ContractId> {
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod tests {
-    use crate::Receipt;
-    use fuel_types::ContractId;
+    use crate::{
+        Receipt,
+        ScriptExecutionResult,
+    };
+    use core::str::FromStr;
+    use fuel_crypto::Hasher;
+    use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
+    use fuel_types::{
+        canonical::{
+            Deserialize,
+            Serialize,
+        },
+        Bytes32,
+        ContractId,
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
 
     // TODO: Rewrite the test cases when `Receipt` will have its struct for
     //  each variant. It will allow to use `Default` trait.
@@ -813,4 +866,158 @@ mod tests {
     fn receipt_to(#[case] receipt: Receipt, #[case] expected_to: Option<ContractId>) {
         assert_eq!(receipt.to(), expected_to.as_ref());
     }
+
+    fn root_test_receipts() -> Vec<Receipt> {
+        vec![
+            Receipt::call(
+                ContractId::from([1; 32]),
+                ContractId::from([2; 32]),
+                3,
+                [4; 32].into(),
+                5,
+                6,
+                7,
+                8,
+                9,
+            ),
+            Receipt::log_data(ContractId::from([1; 32]), 2, 3, 4, 5, 6, vec![7, 8, 9]),
+            Receipt::script_result(ScriptExecutionResult::Success, 10),
+        ]
+    }
+
+    #[test]
+    fn root__empty_receipts_have_empty_tree_root() {
+        let root = Receipt::root(&[]);
+
+        assert_eq!(
+            root,
+            Bytes32::from_str(
+                "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn root__matches_fixed_vectors() {
+        let receipts = root_test_receipts();
+
+        assert_eq!(
+            Receipt::root(&receipts[..1]),
+            Bytes32::from_str(
+                "0x3401365e023aff7a842a45fdabbf3fa1090aa9242c9a45650b92f31084f4a61b"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Receipt::root(&receipts),
+            Bytes32::from_str(
+                "0xe09d916bf1ae8a92b310645f6bdc86c85517989413c3711b326d819c450ced1d"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn root__single_receipt_is_leaf_hash_of_canonical_bytes() {
+        let receipt = root_test_receipts().remove(0);
+
+        let mut leaf = vec![0x00];
+        leaf.extend(receipt.to_bytes());
+
+        assert_eq!(Receipt::root(&[receipt]), Hasher::hash(leaf));
+    }
+
+    #[test]
+    fn root__reordering_receipts_changes_root() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+
+        for _ in 0..100 {
+            let len = rng.gen_range(2..16);
+            let receipts: Vec<_> = (0..len)
+                .map(|_| {
+                    Receipt::log(
+                        rng.gen(),
+                        rng.gen(),
+                        rng.gen(),
+                        rng.gen(),
+                        rng.gen(),
+                        rng.gen(),
+                        rng.gen(),
+                    )
+                })
+                .collect();
+
+            let mut reordered = receipts.clone();
+            let i = rng.gen_range(0..len);
+            let j = (i + rng.gen_range(1..len)) % len;
+            reordered.swap(i, j);
+
+            assert_ne!(Receipt::root(&receipts), Receipt::root(&reordered));
+        }
+    }
+
+    /// Returns the encoding of the receipt with the discriminant following the
+    /// discriminants known to this version.
+    fn future_receipt_bytes() -> Vec<u8> {
+        let mut bytes = 0x0Du64.to_bytes();
+        bytes.extend([0x11; 32]);
+        bytes.extend(7u64.to_bytes());
+        bytes
+    }
+
+    #[test]
+    fn decode__unknown_discriminant_is_kept_as_opaque_bytes() {
+        // Given
+        let bytes = future_receipt_bytes();
+
+        // When
+        let receipt = Receipt::from_bytes(&bytes).expect("Unknown receipt is decoded");
+
+        // Then
+        assert_eq!(
+            receipt,
+            Receipt::Unknown {
+                discriminant: 0x0D,
+                bytes: bytes[8..].to_vec(),
+            }
+        );
+        assert_eq!(receipt.size(), bytes.len());
+        assert_eq!(receipt.to_bytes(), bytes);
+        assert_eq!(receipt.id(), None);
+    }
+
+    #[test]
+    fn root__includes_unknown_receipts_as_encoded() {
+        // Given
+        let bytes = future_receipt_bytes();
+        let unknown = Receipt::from_bytes(&bytes).expect("Unknown receipt is decoded");
+        let mut receipts = root_test_receipts();
+        receipts.push(unknown);
+
+        // When
+        let root = Receipt::root(&receipts);
+
+        // Then
+        let leaves: Vec<_> = receipts.iter().map(Receipt::to_bytes).collect();
+        assert_eq!(leaves.last(), Some(&bytes));
+        assert_eq!(
+            root,
+            MerkleRootCalculator::new()
+                .root_from_iterator(leaves.into_iter())
+                .into()
+        );
+    }
+
+    #[test]
+    fn decode__known_discriminant_is_not_unknown() {
+        // Given
+        let receipt = root_test_receipts().remove(0);
+
+        // When
+        let decoded = Receipt::from_bytes(&receipt.to_bytes()).expect("Known receipt");
+
+        // Then
+        assert_eq!(decoded, receipt);
+    }
 }
@@ -0,0 +1,622 @@
+//! Human-readable rendering of the receipts, for the explorers and the CLI tools.
+
+use super::{
+    receipts_ext::LogDataView,
+    Receipt,
+};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use core::fmt::{
+    self,
+    Write,
+};
+use fuel_asm::{
+    Instruction,
+    PanicInstruction,
+};
+use fuel_types::Word;
+
+/// The number of bytes of the data rendered when the data is truncated.
+pub const TRUNCATED_DATA_LEN: usize = 32;
+
+/// Decodes the payload of a `LogData` receipt, e.g. with the ABI of the contract.
+///
+/// It returns `None` when it doesn't know the payload, and the receipt is rendered
+/// without the decoded value.
+pub type LogDataDecoder<'a> = dyn Fn(&LogDataView<'_>) -> Option<String> + 'a;
+
+/// Renders the receipts with one aligned field per line.
+///
+/// The panic reasons are rendered with their name and description, the data is
+/// truncated to [`TRUNCATED_DATA_LEN`] bytes unless [`Self::full_data`] is set, and the
+/// gas of each `Call` and `ScriptResult` receipt is followed by its difference with
+/// the previous receipt of the same kind. The output only depends on the receipts and
+/// the options, so it can be compared with snapshots.
+#[derive(Default)]
+pub struct ReceiptFormatter<'a> {
+    full_data: bool,
+    log_data_decoder: Option<Box<LogDataDecoder<'a>>>,
+}
+
+impl<'a> ReceiptFormatter<'a> {
+    /// New formatter truncating the data, without decoder of the `LogData` payloads.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the data of the receipts in full instead of truncating it.
+    pub fn full_data(mut self, full_data: bool) -> Self {
+        self.full_data = full_data;
+        self
+    }
+
+    /// Renders the payloads of the `LogData` receipts decoded by the `decoder`.
+    pub fn log_data_decoder<F>(mut self, decoder: F) -> Self
+    where
+        F: Fn(&LogDataView<'_>) -> Option<String> + 'a,
+    {
+        self.log_data_decoder = Some(Box::new(decoder));
+        self
+    }
+
+    /// The rendering of the `receipts`, numbered in their order.
+    pub fn display<'r>(&'r self, receipts: &'r [Receipt]) -> FormattedReceipts<'r, 'a> {
+        FormattedReceipts {
+            formatter: self,
+            receipts,
+        }
+    }
+
+    /// Writes the rendering of the `receipts` to `f`.
+    pub fn write<W>(&self, f: &mut W, receipts: &[Receipt]) -> fmt::Result
+    where
+        W: Write + ?Sized,
+    {
+        let width = receipts.len().saturating_sub(1).to_string().len();
+        let mut previous_call_gas = None;
+        let mut previous_gas_used = None;
+        for (index, receipt) in receipts.iter().enumerate() {
+            let delta = match receipt {
+                Receipt::Call { gas, .. } => previous_call_gas.replace(*gas),
+                Receipt::ScriptResult { gas_used, .. } => {
+                    previous_gas_used.replace(*gas_used)
+                }
+                _ => None,
+            };
+            write!(f, "[{index:>width$}] ")?;
+            self.write_receipt(f, receipt, delta)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the rendering of the `receipt`, with the difference of its gas with the
+    /// `previous` gas of the same kind of receipt.
+    fn write_receipt<W>(
+        &self,
+        f: &mut W,
+        receipt: &Receipt,
+        previous: Option<Word>,
+    ) -> fmt::Result
+    where
+        W: Write + ?Sized,
+    {
+        let gas = |gas: Word| match previous {
+            Some(previous) => {
+                format!("{gas} ({:+})", i128::from(gas) - i128::from(previous))
+            }
+            None => gas.to_string(),
+        };
+        let (name, fields): (&str, Vec<(&str, String)>) = match receipt {
+            Receipt::Call {
+                id,
+                to,
+                amount,
+                asset_id,
+                gas: forwarded,
+                param1,
+                param2,
+                pc,
+                is,
+            } => (
+                "Call",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("to", to.to_string()),
+                    ("amount", amount.to_string()),
+                    ("asset_id", asset_id.to_string()),
+                    ("gas", gas(*forwarded)),
+                    ("param1", param1.to_string()),
+                    ("param2", param2.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::Return { id, val, pc, is } => (
+                "Return",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("val", val.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::ReturnData {
+                id,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data,
+            } => (
+                "ReturnData",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("ptr", ptr.to_string()),
+                    ("len", len.to_string()),
+                    ("digest", digest.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                    ("data", self.data(data.as_deref())),
+                ],
+            ),
+            Receipt::Panic {
+                id,
+                reason,
+                pc,
+                is,
+                contract_id,
+            } => (
+                "Panic",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    (
+                        "reason",
+                        format!("{}: {}", reason.reason(), reason.reason().description()),
+                    ),
+                    ("instruction", instruction(reason)),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                    (
+                        "contract_id",
+                        contract_id
+                            .map_or_else(|| "None".to_string(), |id| id.to_string()),
+                    ),
+                ],
+            ),
+            Receipt::Revert { id, ra, pc, is } => (
+                "Revert",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("ra", ra.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::Log {
+                id,
+                ra,
+                rb,
+                rc,
+                rd,
+                pc,
+                is,
+            } => (
+                "Log",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("ra", ra.to_string()),
+                    ("rb", rb.to_string()),
+                    ("rc", rc.to_string()),
+                    ("rd", rd.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::LogData {
+                id,
+                ra,
+                rb,
+                ptr,
+                len,
+                digest,
+                pc,
+                is,
+                data,
+            } => {
+                let mut fields = alloc::vec![
+                    ("id", id.to_string()),
+                    ("ra", ra.to_string()),
+                    ("rb", rb.to_string()),
+                    ("ptr", ptr.to_string()),
+                    ("len", len.to_string()),
+                    ("digest", digest.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                    ("data", self.data(data.as_deref())),
+                ];
+                let view = LogDataView {
+                    id: *id,
+                    ra: *ra,
+                    rb: *rb,
+                    data: data.as_deref(),
+                };
+                if let Some(decoded) = self
+                    .log_data_decoder
+                    .as_ref()
+                    .and_then(|decoder| decoder(&view))
+                {
+                    fields.push(("decoded", decoded));
+                }
+                ("LogData", fields)
+            }
+            Receipt::Transfer {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => (
+                "Transfer",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("to", to.to_string()),
+                    ("amount", amount.to_string()),
+                    ("asset_id", asset_id.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::TransferOut {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => (
+                "TransferOut",
+                alloc::vec![
+                    ("id", id.to_string()),
+                    ("to", to.to_string()),
+                    ("amount", amount.to_string()),
+                    ("asset_id", asset_id.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::ScriptResult { result, gas_used } => (
+                "ScriptResult",
+                alloc::vec![
+                    ("result", format!("{result:?}")),
+                    ("gas_used", gas(*gas_used)),
+                ],
+            ),
+            Receipt::MessageOut {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                len,
+                digest,
+                data,
+            } => (
+                "MessageOut",
+                alloc::vec![
+                    ("sender", sender.to_string()),
+                    ("recipient", recipient.to_string()),
+                    ("amount", amount.to_string()),
+                    ("nonce", nonce.to_string()),
+                    ("len", len.to_string()),
+                    ("digest", digest.to_string()),
+                    ("data", self.data(data.as_deref())),
+                ],
+            ),
+            Receipt::Mint {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => (
+                "Mint",
+                alloc::vec![
+                    ("sub_id", sub_id.to_string()),
+                    ("contract_id", contract_id.to_string()),
+                    ("val", val.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            Receipt::Burn {
+                sub_id,
+                contract_id,
+                val,
+                pc,
+                is,
+            } => (
+                "Burn",
+                alloc::vec![
+                    ("sub_id", sub_id.to_string()),
+                    ("contract_id", contract_id.to_string()),
+                    ("val", val.to_string()),
+                    ("pc", pc.to_string()),
+                    ("is", is.to_string()),
+                ],
+            ),
+            #[cfg(feature = "unknown-receipts")]
+            Receipt::Unknown {
+                discriminant,
+                bytes,
+            } => (
+                "Unknown",
+                alloc::vec![
+                    ("discriminant", discriminant.to_string()),
+                    ("bytes", self.data(Some(bytes))),
+                ],
+            ),
+        };
+        writeln!(f, "{name}")?;
+        let width = fields
+            .iter()
+            .map(|(field, _)| field.len())
+            .max()
+            .unwrap_or(0);
+        for (field, value) in fields {
+            writeln!(f, "    {field:<width$} {value}")?;
+        }
+        Ok(())
+    }
+
+    /// The hex of the `data`, truncated unless rendering the full data.
+    fn data(&self, data: Option<&[u8]>) -> String {
+        let Some(data) = data else {
+            return "None".to_string()
+        };
+        let shown = if self.full_data {
+            data
+        } else {
+            &data[..data.len().min(TRUNCATED_DATA_LEN)]
+        };
+        let mut hex = String::with_capacity(shown.len() * 2);
+        for byte in shown {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        if shown.len() < data.len() {
+            let _ = write!(hex, "... ({} bytes)", data.len());
+        }
+        hex
+    }
+}
+
+/// The raw panicking instruction, followed by its opcode when it is valid.
+fn instruction(reason: &PanicInstruction) -> String {
+    let raw = *reason.instruction();
+    match Instruction::try_from(raw) {
+        Ok(instruction) => format!("{raw:#010x} ({:?})", instruction.opcode()),
+        Err(_) => format!("{raw:#010x}"),
+    }
+}
+
+impl Receipt {
+    /// Writes the rendering of the receipt with the options of the `formatter`.
+    ///
+    /// A single receipt has no previous receipt, so its gas is rendered without
+    /// difference. Use [`ReceiptFormatter::display`] to render a sequence.
+    pub fn pretty<W>(&self, f: &mut W, formatter: &ReceiptFormatter<'_>) -> fmt::Result
+    where
+        W: Write + ?Sized,
+    {
+        formatter.write_receipt(f, self, None)
+    }
+}
+
+/// The rendering of the receipts, by [`ReceiptFormatter::display`].
+pub struct FormattedReceipts<'r, 'a> {
+    formatter: &'r ReceiptFormatter<'a>,
+    receipts: &'r [Receipt],
+}
+
+impl fmt::Display for FormattedReceipts<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter.write(f, self.receipts)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::ScriptExecutionResult;
+    use alloc::vec;
+    use fuel_asm::{
+        op,
+        PanicReason,
+    };
+    use fuel_types::{
+        AssetId,
+        ContractId,
+    };
+
+    /// The receipts of a script calling a contract that logs twice and panics.
+    fn panicking_run() -> Vec<Receipt> {
+        let script = ContractId::zeroed();
+        let contract = ContractId::from([0x11; 32]);
+        let reason = PanicInstruction::error(
+            PanicReason::ArithmeticError,
+            op::div(0x10, 0x10, 0).into(),
+        );
+        vec![
+            Receipt::call(
+                script,
+                contract,
+                0,
+                AssetId::zeroed(),
+                90_000,
+                1,
+                2,
+                10360,
+                10336,
+            ),
+            Receipt::call(
+                contract,
+                contract,
+                0,
+                AssetId::zeroed(),
+                85_500,
+                0,
+                0,
+                40,
+                11000,
+            ),
+            Receipt::log(contract, 1, 2, 3, 4, 12, 12000),
+            Receipt::log_data(contract, 7, 0, 0, 16, 12000, (0u8..40).collect()),
+            Receipt::panic(contract, reason, 20, 12000),
+            Receipt::script_result(ScriptExecutionResult::Panic, 9_823),
+        ]
+    }
+
+    const ZEROS: &str =
+        "0000000000000000000000000000000000000000000000000000000000000000";
+    const ELEVENS: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+
+    #[test]
+    fn display__renders_the_receipts_of_a_run() {
+        // Given
+        let receipts = panicking_run();
+
+        // When
+        let rendered = ReceiptFormatter::new().display(&receipts).to_string();
+
+        // Then
+        let digest = fuel_crypto::Hasher::hash((0u8..40).collect::<Vec<_>>());
+        let data = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+        let expected = format!(
+            "[0] Call
+    id       {ZEROS}
+    to       {ELEVENS}
+    amount   0
+    asset_id {ZEROS}
+    gas      90000
+    param1   1
+    param2   2
+    pc       10360
+    is       10336
+[1] Call
+    id       {ELEVENS}
+    to       {ELEVENS}
+    amount   0
+    asset_id {ZEROS}
+    gas      85500 (-4500)
+    param1   0
+    param2   0
+    pc       40
+    is       11000
+[2] Log
+    id {ELEVENS}
+    ra 1
+    rb 2
+    rc 3
+    rd 4
+    pc 12
+    is 12000
+[3] LogData
+    id     {ELEVENS}
+    ra     7
+    rb     0
+    ptr    0
+    len    40
+    digest {digest}
+    pc     16
+    is     12000
+    data   {data}... (40 bytes)
+[4] Panic
+    id          {ELEVENS}
+    reason      ArithmeticError: Mathematically invalid arguments where given to an \
+             arithmetic instruction. For instance, division by zero produces this. These \
+             errors are ignored using the UNSAFEMATH flag.
+    instruction 0x12410000 (DIV)
+    pc          20
+    is          12000
+    contract_id None
+[5] ScriptResult
+    result   Panic
+    gas_used 9823
+"
+        );
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn display__renders_the_full_data_and_the_decoded_logs() {
+        // Given
+        let receipts = panicking_run();
+        let formatter = ReceiptFormatter::new()
+            .full_data(true)
+            .log_data_decoder(|log| {
+                (log.rb == 0).then(|| format!("Bytes({})", log.data.unwrap().len()))
+            });
+
+        // When
+        let rendered = formatter.display(&receipts).to_string();
+
+        // Then
+        let data = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+                    2021222324252627";
+        let log_data = rendered
+            .split("[3] LogData\n")
+            .nth(1)
+            .and_then(|rest| rest.split("[4]").next())
+            .unwrap();
+        assert!(
+            log_data.ends_with(&format!("    data    {data}\n    decoded Bytes(40)\n")),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn display__renders_the_gas_delta_between_the_script_results() {
+        // Given
+        let receipts = vec![
+            Receipt::script_result(ScriptExecutionResult::Success, 100),
+            Receipt::script_result(ScriptExecutionResult::Revert, 40),
+        ];
+
+        // When
+        let rendered = ReceiptFormatter::new().display(&receipts).to_string();
+
+        // Then
+        assert_eq!(
+            rendered,
+            "[0] ScriptResult\n    result   Success\n    gas_used 100\n\
+             [1] ScriptResult\n    result   Revert\n    gas_used 40 (-60)\n"
+        );
+    }
+
+    #[test]
+    fn pretty__renders_a_single_receipt_without_gas_delta() {
+        // Given
+        let receipt = Receipt::script_result(ScriptExecutionResult::Success, 100);
+        let mut rendered = String::new();
+
+        // When
+        receipt
+            .pretty(&mut rendered, &ReceiptFormatter::new())
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            rendered,
+            "ScriptResult\n    result   Success\n    gas_used 100\n"
+        );
+    }
+}
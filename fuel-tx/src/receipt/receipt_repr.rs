@@ -11,10 +11,15 @@ macro_rules! enum_from {
             $($(#[$vmeta])* $vname $(= $val)?,)*
         }
 
-        impl From<&Receipt> for $name {
-            fn from(receipt: &Receipt) -> Self {
+        impl TryFrom<&Receipt> for $name {
+            /// The discriminant of the unknown receipt.
+            type Error = fuel_types::Word;
+
+            fn try_from(receipt: &Receipt) -> Result<Self, Self::Error> {
                 match receipt {
-                    $(Receipt::$vname { .. } => Self::$vname,)*
+                    $(Receipt::$vname { .. } => Ok(Self::$vname),)*
+                    #[cfg(feature = "unknown-receipts")]
+                    Receipt::Unknown { discriminant, .. } => Err(*discriminant),
                 }
             }
         }
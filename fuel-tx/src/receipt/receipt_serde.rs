@@ -0,0 +1,811 @@
+//! Serde representation of the [`Receipt`].
+//!
+//! Human-readable formats, like JSON, use the stable representation intended for
+//! indexers and other external consumers:
+//!
+//! - Each receipt is an object with the `"type"` field set to the name of the variant,
+//!   like `"Call"` or `"LogData"`, and the fields of the variant next to it.
+//! - Bytes, hashes, addresses and ids are lowercase hex strings with the `0x` prefix.
+//! - `Word` values are decimal strings, so they don't lose precision in JavaScript.
+//! - The optional `data` and `contract_id` fields are `null` if they are not set.
+//! - The `ScriptResult` receipt encodes its `result` as `"Success"`, `"Revert"`,
+//!   `"Panic"` or `{ "GenericFailure": "<code>" }`.
+//!
+//! Other formats keep the representation derived from the definition of the
+//! [`Receipt`].
+
+use super::{
+    Receipt,
+    ScriptExecutionResult,
+};
+use alloc::{
+    format,
+    string::String,
+    vec::Vec,
+};
+use fuel_asm::PanicInstruction;
+use fuel_types::{
+    Address,
+    AssetId,
+    Bytes32,
+    ContractId,
+    Nonce,
+    Word,
+};
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+impl Serialize for Receipt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            ReadableReceipt::serialize(self, serializer)
+        } else {
+            BinaryReceipt::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Receipt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            ReadableReceipt::deserialize(deserializer)
+        } else {
+            BinaryReceipt::deserialize(deserializer)
+        }
+    }
+}
+
+/// Lowercase hex string with the `0x` prefix.
+mod hex_bytes {
+    use super::*;
+    use serde::de::Error;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(value)))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: for<'a> TryFrom<&'a [u8]>,
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let value = value
+            .strip_prefix("0x")
+            .ok_or_else(|| D::Error::custom("expected a hex string with `0x` prefix"))?;
+        let bytes = hex::decode(value).map_err(D::Error::custom)?;
+
+        T::try_from(&bytes).map_err(|_| {
+            D::Error::invalid_length(bytes.len(), &"the length of the encoded type")
+        })
+    }
+}
+
+/// Optional [`hex_bytes`], `null` if not set.
+mod option_hex_bytes {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => hex_bytes::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: for<'a> TryFrom<&'a [u8]>,
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(bound = "T: for<'a> TryFrom<&'a [u8]>")]
+        struct Wrapper<T>(#[serde(with = "hex_bytes")] T);
+
+        let value = Option::<Wrapper<T>>::deserialize(deserializer)?;
+
+        Ok(value.map(|Wrapper(value)| value))
+    }
+}
+
+/// Decimal string of the `Word`.
+mod word {
+    use super::*;
+    use serde::de::Error;
+
+    pub fn serialize<S>(value: &Word, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Word, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ScriptExecutionResult")]
+enum ReadableScriptExecutionResult {
+    Success,
+    Revert,
+    Panic,
+    GenericFailure(#[serde(with = "word")] u64),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Receipt", tag = "type")]
+enum ReadableReceipt {
+    Call {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "hex_bytes")]
+        to: ContractId,
+        #[serde(with = "word")]
+        amount: Word,
+        #[serde(with = "hex_bytes")]
+        asset_id: AssetId,
+        #[serde(with = "word")]
+        gas: Word,
+        #[serde(with = "word")]
+        param1: Word,
+        #[serde(with = "word")]
+        param2: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    Return {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "word")]
+        val: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    ReturnData {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "word")]
+        ptr: Word,
+        #[serde(with = "word")]
+        len: Word,
+        #[serde(with = "hex_bytes")]
+        digest: Bytes32,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+        #[serde(with = "option_hex_bytes")]
+        data: Option<Vec<u8>>,
+    },
+
+    Panic {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        reason: PanicInstruction,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+        #[serde(with = "option_hex_bytes")]
+        contract_id: Option<ContractId>,
+    },
+
+    Revert {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "word")]
+        ra: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    Log {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "word")]
+        ra: Word,
+        #[serde(with = "word")]
+        rb: Word,
+        #[serde(with = "word")]
+        rc: Word,
+        #[serde(with = "word")]
+        rd: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    LogData {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "word")]
+        ra: Word,
+        #[serde(with = "word")]
+        rb: Word,
+        #[serde(with = "word")]
+        ptr: Word,
+        #[serde(with = "word")]
+        len: Word,
+        #[serde(with = "hex_bytes")]
+        digest: Bytes32,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+        #[serde(with = "option_hex_bytes")]
+        data: Option<Vec<u8>>,
+    },
+
+    Transfer {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "hex_bytes")]
+        to: ContractId,
+        #[serde(with = "word")]
+        amount: Word,
+        #[serde(with = "hex_bytes")]
+        asset_id: AssetId,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    TransferOut {
+        #[serde(with = "hex_bytes")]
+        id: ContractId,
+        #[serde(with = "hex_bytes")]
+        to: Address,
+        #[serde(with = "word")]
+        amount: Word,
+        #[serde(with = "hex_bytes")]
+        asset_id: AssetId,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    ScriptResult {
+        #[serde(with = "ReadableScriptExecutionResult")]
+        result: ScriptExecutionResult,
+        #[serde(with = "word")]
+        gas_used: Word,
+    },
+
+    MessageOut {
+        #[serde(with = "hex_bytes")]
+        sender: Address,
+        #[serde(with = "hex_bytes")]
+        recipient: Address,
+        #[serde(with = "word")]
+        amount: Word,
+        #[serde(with = "hex_bytes")]
+        nonce: Nonce,
+        #[serde(with = "word")]
+        len: Word,
+        #[serde(with = "hex_bytes")]
+        digest: Bytes32,
+        #[serde(with = "option_hex_bytes")]
+        data: Option<Vec<u8>>,
+    },
+
+    Mint {
+        #[serde(with = "hex_bytes")]
+        sub_id: Bytes32,
+        #[serde(with = "hex_bytes")]
+        contract_id: ContractId,
+        #[serde(with = "word")]
+        val: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+
+    Burn {
+        #[serde(with = "hex_bytes")]
+        sub_id: Bytes32,
+        #[serde(with = "hex_bytes")]
+        contract_id: ContractId,
+        #[serde(with = "word")]
+        val: Word,
+        #[serde(with = "word")]
+        pc: Word,
+        #[serde(with = "word")]
+        is: Word,
+    },
+    #[cfg(feature = "unknown-receipts")]
+    Unknown {
+        #[serde(with = "word")]
+        discriminant: Word,
+        #[serde(with = "hex_bytes")]
+        bytes: Vec<u8>,
+    },
+}
+
+/// Mirrors the definition of the [`Receipt`] for non-human-readable formats.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Receipt", rename = "Receipt")]
+enum BinaryReceipt {
+    Call {
+        id: ContractId,
+        to: ContractId,
+        amount: Word,
+        asset_id: AssetId,
+        gas: Word,
+        param1: Word,
+        param2: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    Return {
+        id: ContractId,
+        val: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    ReturnData {
+        id: ContractId,
+        ptr: Word,
+        len: Word,
+        digest: Bytes32,
+        pc: Word,
+        is: Word,
+        data: Option<Vec<u8>>,
+    },
+
+    Panic {
+        id: ContractId,
+        reason: PanicInstruction,
+        pc: Word,
+        is: Word,
+        contract_id: Option<ContractId>,
+    },
+
+    Revert {
+        id: ContractId,
+        ra: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    Log {
+        id: ContractId,
+        ra: Word,
+        rb: Word,
+        rc: Word,
+        rd: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    LogData {
+        id: ContractId,
+        ra: Word,
+        rb: Word,
+        ptr: Word,
+        len: Word,
+        digest: Bytes32,
+        pc: Word,
+        is: Word,
+        data: Option<Vec<u8>>,
+    },
+
+    Transfer {
+        id: ContractId,
+        to: ContractId,
+        amount: Word,
+        asset_id: AssetId,
+        pc: Word,
+        is: Word,
+    },
+
+    TransferOut {
+        id: ContractId,
+        to: Address,
+        amount: Word,
+        asset_id: AssetId,
+        pc: Word,
+        is: Word,
+    },
+
+    ScriptResult {
+        result: ScriptExecutionResult,
+        gas_used: Word,
+    },
+
+    MessageOut {
+        sender: Address,
+        recipient: Address,
+        amount: Word,
+        nonce: Nonce,
+        len: Word,
+        digest: Bytes32,
+        data: Option<Vec<u8>>,
+    },
+
+    Mint {
+        sub_id: Bytes32,
+        contract_id: ContractId,
+        val: Word,
+        pc: Word,
+        is: Word,
+    },
+
+    Burn {
+        sub_id: Bytes32,
+        contract_id: ContractId,
+        val: Word,
+        pc: Word,
+        is: Word,
+    },
+    #[cfg(feature = "unknown-receipts")]
+    Unknown { discriminant: Word, bytes: Vec<u8> },
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use fuel_asm::PanicReason;
+    use serde_json::{
+        json,
+        Value,
+    };
+
+    const ID: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const TO: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+    const ASSET_ID: &str =
+        "0x3333333333333333333333333333333333333333333333333333333333333333";
+    const DIGEST: &str =
+        "0x4444444444444444444444444444444444444444444444444444444444444444";
+
+    fn id() -> ContractId {
+        ContractId::from([0x11; 32])
+    }
+
+    fn to() -> [u8; 32] {
+        [0x22; 32]
+    }
+
+    fn asset_id() -> AssetId {
+        AssetId::from([0x33; 32])
+    }
+
+    fn digest() -> Bytes32 {
+        Bytes32::from([0x44; 32])
+    }
+
+    #[rstest::rstest]
+    #[case::call(
+        Receipt::call(id(), to().into(), 1, asset_id(), 2, 3, 4, 5, 6),
+        json!({
+            "type": "Call",
+            "id": ID,
+            "to": TO,
+            "amount": "1",
+            "asset_id": ASSET_ID,
+            "gas": "2",
+            "param1": "3",
+            "param2": "4",
+            "pc": "5",
+            "is": "6",
+        })
+    )]
+    #[case::ret(
+        Receipt::ret(id(), u64::MAX, 1, 2),
+        json!({
+            "type": "Return",
+            "id": ID,
+            "val": "18446744073709551615",
+            "pc": "1",
+            "is": "2",
+        })
+    )]
+    #[case::return_data(
+        Receipt::return_data_with_len(id(), 1, 3, digest(), 2, 3, Some(vec![0xab, 0xcd, 0xef])),
+        json!({
+            "type": "ReturnData",
+            "id": ID,
+            "ptr": "1",
+            "len": "3",
+            "digest": DIGEST,
+            "pc": "2",
+            "is": "3",
+            "data": "0xabcdef",
+        })
+    )]
+    #[case::return_data_without_data(
+        Receipt::return_data_with_len(id(), 1, 3, digest(), 2, 3, None),
+        json!({
+            "type": "ReturnData",
+            "id": ID,
+            "ptr": "1",
+            "len": "3",
+            "digest": DIGEST,
+            "pc": "2",
+            "is": "3",
+            "data": null,
+        })
+    )]
+    #[case::panic(
+        Receipt::panic(
+            id(),
+            PanicInstruction::error(PanicReason::ContractNotInInputs, 0x2d000000),
+            1,
+            2,
+        ),
+        json!({
+            "type": "Panic",
+            "id": ID,
+            "reason": {
+                "reason": "ContractNotInInputs",
+                "instruction": 0x2d000000,
+            },
+            "pc": "1",
+            "is": "2",
+            "contract_id": null,
+        })
+    )]
+    #[case::panic_with_contract_id(
+        Receipt::panic(
+            id(),
+            PanicInstruction::error(PanicReason::ContractNotInInputs, 0x2d000000),
+            1,
+            2,
+        )
+        .with_panic_contract_id(Some(to().into())),
+        json!({
+            "type": "Panic",
+            "id": ID,
+            "reason": {
+                "reason": "ContractNotInInputs",
+                "instruction": 0x2d000000,
+            },
+            "pc": "1",
+            "is": "2",
+            "contract_id": TO,
+        })
+    )]
+    #[case::revert(
+        Receipt::revert(id(), 1, 2, 3),
+        json!({
+            "type": "Revert",
+            "id": ID,
+            "ra": "1",
+            "pc": "2",
+            "is": "3",
+        })
+    )]
+    #[case::log(
+        Receipt::log(id(), 1, 2, 3, 4, 5, 6),
+        json!({
+            "type": "Log",
+            "id": ID,
+            "ra": "1",
+            "rb": "2",
+            "rc": "3",
+            "rd": "4",
+            "pc": "5",
+            "is": "6",
+        })
+    )]
+    #[case::log_data(
+        Receipt::log_data_with_len(id(), 1, 2, 3, 2, digest(), 4, 5, Some(vec![0x01, 0x02])),
+        json!({
+            "type": "LogData",
+            "id": ID,
+            "ra": "1",
+            "rb": "2",
+            "ptr": "3",
+            "len": "2",
+            "digest": DIGEST,
+            "pc": "4",
+            "is": "5",
+            "data": "0x0102",
+        })
+    )]
+    #[case::log_data_with_empty_data(
+        Receipt::log_data_with_len(id(), 1, 2, 3, 0, digest(), 4, 5, Some(vec![])),
+        json!({
+            "type": "LogData",
+            "id": ID,
+            "ra": "1",
+            "rb": "2",
+            "ptr": "3",
+            "len": "0",
+            "digest": DIGEST,
+            "pc": "4",
+            "is": "5",
+            "data": "0x",
+        })
+    )]
+    #[case::transfer(
+        Receipt::transfer(id(), to().into(), 1, asset_id(), 2, 3),
+        json!({
+            "type": "Transfer",
+            "id": ID,
+            "to": TO,
+            "amount": "1",
+            "asset_id": ASSET_ID,
+            "pc": "2",
+            "is": "3",
+        })
+    )]
+    #[case::transfer_out(
+        Receipt::transfer_out(id(), to().into(), 1, asset_id(), 2, 3),
+        json!({
+            "type": "TransferOut",
+            "id": ID,
+            "to": TO,
+            "amount": "1",
+            "asset_id": ASSET_ID,
+            "pc": "2",
+            "is": "3",
+        })
+    )]
+    #[case::script_result(
+        Receipt::script_result(ScriptExecutionResult::Success, 1),
+        json!({
+            "type": "ScriptResult",
+            "result": "Success",
+            "gas_used": "1",
+        })
+    )]
+    #[case::script_result_generic_failure(
+        Receipt::script_result(ScriptExecutionResult::GenericFailure(u64::MAX), 1),
+        json!({
+            "type": "ScriptResult",
+            "result": { "GenericFailure": "18446744073709551615" },
+            "gas_used": "1",
+        })
+    )]
+    #[case::message_out(
+        Receipt::message_out_with_len(
+            Address::from([0x11; 32]),
+            to().into(),
+            1,
+            Nonce::from([0x33; 32]),
+            1,
+            digest(),
+            Some(vec![0xff]),
+        ),
+        json!({
+            "type": "MessageOut",
+            "sender": ID,
+            "recipient": TO,
+            "amount": "1",
+            "nonce": ASSET_ID,
+            "len": "1",
+            "digest": DIGEST,
+            "data": "0xff",
+        })
+    )]
+    #[case::mint(
+        Receipt::mint(digest(), id(), 1, 2, 3),
+        json!({
+            "type": "Mint",
+            "sub_id": DIGEST,
+            "contract_id": ID,
+            "val": "1",
+            "pc": "2",
+            "is": "3",
+        })
+    )]
+    #[case::burn(
+        Receipt::burn(digest(), id(), 1, 2, 3),
+        json!({
+            "type": "Burn",
+            "sub_id": DIGEST,
+            "contract_id": ID,
+            "val": "1",
+            "pc": "2",
+            "is": "3",
+        })
+    )]
+    fn receipt_serde__json_matches_golden_vector(
+        #[case] receipt: Receipt,
+        #[case] expected: Value,
+    ) {
+        // When
+        let json = serde_json::to_value(&receipt).expect("Should serialize the receipt");
+        let decoded: Receipt =
+            serde_json::from_value(json.clone()).expect("Should deserialize the receipt");
+
+        // Then
+        assert_eq!(json, expected);
+        assert_eq!(decoded, receipt);
+        assert_eq!(decoded.data(), receipt.data());
+        assert_eq!(
+            serde_json::to_value(&decoded).expect("Should serialize the receipt"),
+            expected
+        );
+    }
+
+    #[test]
+    fn receipt_serde__json_rejects_numeric_words() {
+        // Given
+        let json = json!({
+            "type": "Return",
+            "id": ID,
+            "val": 1,
+            "pc": "1",
+            "is": "2",
+        });
+
+        // When
+        let result = serde_json::from_value::<Receipt>(json);
+
+        // Then
+        result.expect_err("Words should be encoded as strings");
+    }
+
+    #[test]
+    fn receipt_serde__json_rejects_hex_without_prefix() {
+        // Given
+        let json = json!({
+            "type": "Return",
+            "id": ID.trim_start_matches("0x"),
+            "val": "1",
+            "pc": "1",
+            "is": "2",
+        });
+
+        // When
+        let result = serde_json::from_value::<Receipt>(json);
+
+        // Then
+        result.expect_err("Hex strings should have the `0x` prefix");
+    }
+
+    #[test]
+    fn receipt_serde__binary_format_round_trip() {
+        // Given
+        let receipt = Receipt::log_data(id(), 1, 2, 3, 4, 5, vec![0x01, 0x02]);
+
+        // When
+        let bytes = bincode::serialize(&receipt).expect("Should serialize the receipt");
+        let decoded: Receipt =
+            bincode::deserialize(&bytes).expect("Should deserialize the receipt");
+
+        // Then
+        assert_eq!(decoded, receipt);
+        assert_eq!(decoded.data(), receipt.data());
+    }
+}
@@ -0,0 +1,466 @@
+//! Typed queries over the receipts of an executed transaction.
+
+use super::{
+    Receipt,
+    ScriptExecutionResult,
+};
+use alloc::vec::Vec;
+use fuel_asm::PanicReason;
+use fuel_types::{
+    Address,
+    AssetId,
+    ContractId,
+    Nonce,
+    Word,
+};
+
+/// The `Call` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallView {
+    /// The caller, zeroed if the call is made by the script.
+    pub id: ContractId,
+    /// The called contract.
+    pub to: ContractId,
+    /// The amount of coins forwarded to the contract.
+    pub amount: Word,
+    /// The asset of the forwarded coins.
+    pub asset_id: AssetId,
+    /// The gas forwarded to the contract.
+    pub gas: Word,
+    /// The first parameter of the call.
+    pub param1: Word,
+    /// The second parameter of the call.
+    pub param2: Word,
+}
+
+/// The `Log` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogView {
+    /// The contract emitting the log, zeroed for the script.
+    pub id: ContractId,
+    /// The value of the `$rA` register.
+    pub ra: Word,
+    /// The value of the `$rB` register.
+    pub rb: Word,
+    /// The value of the `$rC` register.
+    pub rc: Word,
+    /// The value of the `$rD` register.
+    pub rd: Word,
+}
+
+/// The `LogData` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogDataView<'a> {
+    /// The contract emitting the log, zeroed for the script.
+    pub id: ContractId,
+    /// The value of the `$rA` register.
+    pub ra: Word,
+    /// The value of the `$rB` register.
+    pub rb: Word,
+    /// The logged data, `None` if the receipt was decoded without it.
+    pub data: Option<&'a [u8]>,
+}
+
+/// The receiver of the coins in the [`TransferView`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferRecipient {
+    /// The coins are moved to the balance of the contract by the `Transfer` receipt.
+    Contract(ContractId),
+    /// The coins are moved to the variable output by the `TransferOut` receipt.
+    Address(Address),
+}
+
+/// The `Transfer` or `TransferOut` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransferView {
+    /// The contract sending the coins, zeroed for the script.
+    pub id: ContractId,
+    /// The receiver of the coins.
+    pub to: TransferRecipient,
+    /// The amount of coins.
+    pub amount: Word,
+    /// The asset of the coins.
+    pub asset_id: AssetId,
+}
+
+/// The `MessageOut` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageOutView<'a> {
+    /// The sender of the message.
+    pub sender: Address,
+    /// The recipient of the message.
+    pub recipient: Address,
+    /// The amount of base asset coins sent with the message.
+    pub amount: Word,
+    /// The nonce of the message.
+    pub nonce: Nonce,
+    /// The data of the message, `None` if the receipt was decoded without it.
+    pub data: Option<&'a [u8]>,
+}
+
+/// Queries over the receipts of an executed transaction.
+///
+/// The receipts of nested calls are reported along with the receipts of the script.
+/// The queries about the result of the execution only look at the receipts produced by
+/// the script itself, i.e. with the zeroed contract id.
+pub trait ReceiptsExt {
+    /// The result of the script execution.
+    fn script_result(&self) -> Option<ScriptExecutionResult>;
+
+    /// The reason of the panic that stopped the execution.
+    fn panic_reason(&self) -> Option<PanicReason>;
+
+    /// The value returned by the script with `RET`.
+    fn returned_value(&self) -> Option<Word>;
+
+    /// The data returned by the script with `RETD`.
+    fn return_data(&self) -> Option<&[u8]>;
+
+    /// All `Log` receipts in the order of emission.
+    fn logs(&self) -> Vec<LogView>;
+
+    /// All `LogData` receipts in the order of emission.
+    fn logs_data(&self) -> Vec<LogDataView<'_>>;
+
+    /// All `Transfer` and `TransferOut` receipts in the order of emission.
+    fn transfers(&self) -> Vec<TransferView>;
+
+    /// All `MessageOut` receipts in the order of emission.
+    fn messages_out(&self) -> Vec<MessageOutView<'_>>;
+
+    /// All `Call` receipts in the order of emission.
+    fn calls(&self) -> Vec<CallView>;
+
+    /// Panics if the script didn't succeed.
+    #[cfg(feature = "test-helpers")]
+    fn assert_success(&self);
+
+    /// Panics if the script didn't panic with the `reason`.
+    #[cfg(feature = "test-helpers")]
+    fn assert_panic(&self, reason: PanicReason);
+}
+
+impl ReceiptsExt for [Receipt] {
+    fn script_result(&self) -> Option<ScriptExecutionResult> {
+        self.iter().rev().find_map(|receipt| match receipt {
+            Receipt::ScriptResult { result, .. } => Some(*result),
+            _ => None,
+        })
+    }
+
+    fn panic_reason(&self) -> Option<PanicReason> {
+        self.iter().rev().find_map(|receipt| match receipt {
+            Receipt::Panic { reason, .. } => Some(*reason.reason()),
+            _ => None,
+        })
+    }
+
+    fn returned_value(&self) -> Option<Word> {
+        self.iter().rev().find_map(|receipt| match receipt {
+            Receipt::Return { id, val, .. } if id == &ContractId::zeroed() => Some(*val),
+            _ => None,
+        })
+    }
+
+    fn return_data(&self) -> Option<&[u8]> {
+        self.iter().rev().find_map(|receipt| match receipt {
+            Receipt::ReturnData { id, data, .. } if id == &ContractId::zeroed() => {
+                data.as_deref()
+            }
+            _ => None,
+        })
+    }
+
+    fn logs(&self) -> Vec<LogView> {
+        self.iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::Log {
+                    id, ra, rb, rc, rd, ..
+                } => Some(LogView {
+                    id: *id,
+                    ra: *ra,
+                    rb: *rb,
+                    rc: *rc,
+                    rd: *rd,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn logs_data(&self) -> Vec<LogDataView<'_>> {
+        self.iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::LogData {
+                    id, ra, rb, data, ..
+                } => Some(LogDataView {
+                    id: *id,
+                    ra: *ra,
+                    rb: *rb,
+                    data: data.as_deref(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn transfers(&self) -> Vec<TransferView> {
+        self.iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::Transfer {
+                    id,
+                    to,
+                    amount,
+                    asset_id,
+                    ..
+                } => Some(TransferView {
+                    id: *id,
+                    to: TransferRecipient::Contract(*to),
+                    amount: *amount,
+                    asset_id: *asset_id,
+                }),
+                Receipt::TransferOut {
+                    id,
+                    to,
+                    amount,
+                    asset_id,
+                    ..
+                } => Some(TransferView {
+                    id: *id,
+                    to: TransferRecipient::Address(*to),
+                    amount: *amount,
+                    asset_id: *asset_id,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn messages_out(&self) -> Vec<MessageOutView<'_>> {
+        self.iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::MessageOut {
+                    sender,
+                    recipient,
+                    amount,
+                    nonce,
+                    data,
+                    ..
+                } => Some(MessageOutView {
+                    sender: *sender,
+                    recipient: *recipient,
+                    amount: *amount,
+                    nonce: *nonce,
+                    data: data.as_deref(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn calls(&self) -> Vec<CallView> {
+        self.iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::Call {
+                    id,
+                    to,
+                    amount,
+                    asset_id,
+                    gas,
+                    param1,
+                    param2,
+                    ..
+                } => Some(CallView {
+                    id: *id,
+                    to: *to,
+                    amount: *amount,
+                    asset_id: *asset_id,
+                    gas: *gas,
+                    param1: *param1,
+                    param2: *param2,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "test-helpers")]
+    fn assert_success(&self) {
+        let result = self.script_result();
+        assert_eq!(
+            result,
+            Some(ScriptExecutionResult::Success),
+            "Expected the script to succeed, got {result:?} with the panic reason {:?}",
+            self.panic_reason()
+        );
+    }
+
+    #[cfg(feature = "test-helpers")]
+    fn assert_panic(&self, reason: PanicReason) {
+        let result = self.script_result();
+        assert_eq!(
+            result,
+            Some(ScriptExecutionResult::Panic),
+            "Expected the script to panic, got {result:?}"
+        );
+        assert_eq!(
+            self.panic_reason(),
+            Some(reason),
+            "Panic reason differs from the expected reason"
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use fuel_asm::PanicInstruction;
+    use fuel_types::Bytes32;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    /// The receipts of the script calling the contract `outer`, which calls the
+    /// contract `inner`. Both contracts return a value and data before the script does.
+    fn nested_call_receipts(rng: &mut StdRng) -> (ContractId, ContractId, Vec<Receipt>) {
+        let script = ContractId::zeroed();
+        let outer: ContractId = rng.gen();
+        let inner: ContractId = rng.gen();
+        let receipts = vec![
+            Receipt::call(script, outer, 0, rng.gen(), 1000, 1, 2, 0, 0),
+            Receipt::call(outer, inner, 0, rng.gen(), 500, 3, 4, 0, 0),
+            Receipt::ret(inner, 3, 0, 0),
+            Receipt::return_data(inner, 0, 0, 0, vec![3; 8]),
+            Receipt::ret(outer, 2, 0, 0),
+            Receipt::return_data(outer, 0, 0, 0, vec![2; 8]),
+            Receipt::log(script, 4, 5, 6, 7, 0, 0),
+            Receipt::return_data(script, 0, 0, 0, vec![1; 8]),
+            Receipt::script_result(ScriptExecutionResult::Success, 100),
+        ];
+        (outer, inner, receipts)
+    }
+
+    #[test]
+    fn return_data__picks_script_return_after_nested_calls() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let (_, _, receipts) = nested_call_receipts(rng);
+
+        assert_eq!(receipts.return_data(), Some(&[1; 8][..]));
+        assert_eq!(
+            receipts.script_result(),
+            Some(ScriptExecutionResult::Success)
+        );
+        assert_eq!(receipts.panic_reason(), None);
+    }
+
+    #[test]
+    fn returned_value__ignores_returns_of_nested_calls() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let (_, _, mut receipts) = nested_call_receipts(rng);
+        assert_eq!(receipts.returned_value(), None);
+
+        // When
+        let script_result = receipts.pop().unwrap();
+        receipts.push(Receipt::ret(ContractId::zeroed(), 1, 0, 0));
+        receipts.push(script_result);
+
+        // Then
+        assert_eq!(receipts.returned_value(), Some(1));
+    }
+
+    #[test]
+    fn calls__are_reported_in_order_of_emission() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let (outer, inner, receipts) = nested_call_receipts(rng);
+
+        let calls = receipts.calls();
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!((calls[0].id, calls[0].to), (ContractId::zeroed(), outer));
+        assert_eq!((calls[1].id, calls[1].to), (outer, inner));
+        assert_eq!((calls[1].param1, calls[1].param2), (3, 4));
+        assert_eq!(
+            receipts.logs(),
+            vec![LogView {
+                id: ContractId::zeroed(),
+                ra: 4,
+                rb: 5,
+                rc: 6,
+                rd: 7,
+            }]
+        );
+    }
+
+    #[test]
+    fn transfers__include_transfers_to_contracts_and_addresses() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let contract: ContractId = rng.gen();
+        let address: Address = rng.gen();
+        let asset_id: AssetId = rng.gen();
+        let receipts = [
+            Receipt::transfer(ContractId::zeroed(), contract, 10, asset_id, 0, 0),
+            Receipt::log_data(ContractId::zeroed(), 1, 2, 0, 0, 0, vec![7; 3]),
+            Receipt::transfer_out(contract, address, 20, asset_id, 0, 0),
+        ];
+
+        let transfers = receipts.transfers();
+
+        assert_eq!(
+            transfers,
+            vec![
+                TransferView {
+                    id: ContractId::zeroed(),
+                    to: TransferRecipient::Contract(contract),
+                    amount: 10,
+                    asset_id,
+                },
+                TransferView {
+                    id: contract,
+                    to: TransferRecipient::Address(address),
+                    amount: 20,
+                    asset_id,
+                },
+            ]
+        );
+        assert_eq!(receipts.logs_data()[0].data, Some(&[7; 3][..]));
+        assert!(receipts.messages_out().is_empty());
+    }
+
+    #[test]
+    fn panic_reason__reports_panic_of_nested_call() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let contract: ContractId = rng.gen();
+        let sender: Address = rng.gen();
+        let receipts = [
+            Receipt::message_out(&Bytes32::zeroed(), 0, sender, rng.gen(), 5, vec![1]),
+            Receipt::call(ContractId::zeroed(), contract, 0, rng.gen(), 0, 0, 0, 0, 0),
+            Receipt::panic(
+                contract,
+                PanicInstruction::error(PanicReason::ContractNotInInputs, 0),
+                0,
+                0,
+            ),
+            Receipt::script_result(ScriptExecutionResult::Panic, 100),
+        ];
+
+        assert_eq!(
+            receipts.panic_reason(),
+            Some(PanicReason::ContractNotInInputs)
+        );
+        assert_eq!(receipts.returned_value(), None);
+        assert_eq!(receipts.messages_out()[0].sender, sender);
+        receipts.assert_panic(PanicReason::ContractNotInInputs);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected the script to succeed")]
+    fn assert_success__panics_on_script_panic() {
+        let receipts = [Receipt::script_result(ScriptExecutionResult::Panic, 100)];
+
+        receipts.assert_success();
+    }
+}
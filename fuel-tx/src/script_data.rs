@@ -0,0 +1,297 @@
+//! Typed encoding of the script data.
+//!
+//! The [`Builder`] appends values to the script data and records the offset of each of
+//! them under a name in the [`SymbolTable`], so the script can load the VM address of a
+//! value with [`fuel_asm::op::script_data_address`]. The [`Reader`] decodes the same
+//! layout in the host code.
+//!
+//! Fixed-size values are stored as is, with words encoded in big-endian. Raw bytes are
+//! prefixed with their length as a big-endian word, and the symbol points at the bytes
+//! right after the prefix. No padding is inserted between the values.
+
+use alloc::{
+    collections::BTreeMap,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use fuel_types::{
+    canonical::Error,
+    Address,
+    Bytes32,
+    Word,
+};
+
+/// The location of a value in the script data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// The offset of the value from the start of the script data.
+    pub offset: usize,
+    /// The length of the value in bytes, without the length prefix.
+    pub len: usize,
+}
+
+impl Symbol {
+    /// The range of the value in the script data.
+    pub const fn range(&self) -> core::ops::Range<usize> {
+        self.offset..self.offset.saturating_add(self.len)
+    }
+}
+
+/// The symbols recorded by the [`Builder`], indexed by name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SymbolTable {
+    symbols: BTreeMap<String, Symbol>,
+}
+
+impl SymbolTable {
+    /// Returns the symbol recorded under the `name`.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Iterates over the symbols ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Symbol)> {
+        self.symbols
+            .iter()
+            .map(|(name, symbol)| (name.as_str(), *symbol))
+    }
+
+    /// The number of recorded symbols.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns `true` if no symbols are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// Builder of the script data.
+///
+/// Pushing a value under a name that is already recorded replaces the symbol, but the
+/// previous value stays in the script data.
+#[derive(Debug, Default, Clone)]
+pub struct Builder {
+    data: Vec<u8>,
+    symbols: SymbolTable,
+}
+
+impl Builder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the `value` recorded under the `name`.
+    pub fn bytes32(&mut self, name: &str, value: &Bytes32) -> &mut Self {
+        self.push(name, value.as_ref())
+    }
+
+    /// Appends the `value` recorded under the `name`.
+    pub fn address(&mut self, name: &str, value: &Address) -> &mut Self {
+        self.push(name, value.as_ref())
+    }
+
+    /// Appends the `value` recorded under the `name`.
+    pub fn word(&mut self, name: &str, value: Word) -> &mut Self {
+        self.push(name, &value.to_be_bytes())
+    }
+
+    /// Appends the length of the `value` followed by the `value` recorded under the
+    /// `name`.
+    pub fn bytes(&mut self, name: &str, value: &[u8]) -> &mut Self {
+        self.data.extend((value.len() as Word).to_be_bytes());
+        self.push(name, value)
+    }
+
+    /// The current length of the script data.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if nothing was appended.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the script data and the table of recorded symbols.
+    pub fn finalize(self) -> (Vec<u8>, SymbolTable) {
+        (self.data, self.symbols)
+    }
+
+    fn push(&mut self, name: &str, value: &[u8]) -> &mut Self {
+        let symbol = Symbol {
+            offset: self.data.len(),
+            len: value.len(),
+        };
+        self.data.extend_from_slice(value);
+        self.symbols.symbols.insert(name.to_string(), symbol);
+        self
+    }
+}
+
+/// Sequential decoder of the script data produced by the [`Builder`].
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Creates the reader starting at the beginning of the `data`.
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// The offset of the next value.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of bytes not read yet.
+    pub const fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.position)
+    }
+
+    /// Reads the next `Bytes32`.
+    pub fn bytes32(&mut self) -> Result<Bytes32, Error> {
+        self.array().map(Bytes32::new)
+    }
+
+    /// Reads the next `Address`.
+    pub fn address(&mut self) -> Result<Address, Error> {
+        self.array().map(Address::new)
+    }
+
+    /// Reads the next word.
+    pub fn word(&mut self) -> Result<Word, Error> {
+        self.array().map(Word::from_be_bytes)
+    }
+
+    /// Reads the next length-prefixed bytes.
+    pub fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = usize::try_from(self.word()?).map_err(|_| Error::BufferIsTooShort)?;
+        self.take(len)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let bytes = self.take(N)?;
+        Ok(bytes.try_into().expect("`take` returns exactly `N` bytes"))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(Error::BufferIsTooShort)?;
+        let bytes = self
+            .data
+            .get(self.position..end)
+            .ok_or(Error::BufferIsTooShort)?;
+        self.position = end;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    #[test]
+    fn builder_records_offsets() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let contract: Bytes32 = rng.gen();
+        let recipient: Address = rng.gen();
+
+        let mut builder = Builder::new();
+        builder
+            .bytes32("contract", &contract)
+            .word("amount", 42)
+            .bytes("payload", b"hello")
+            .address("recipient", &recipient);
+        let (data, symbols) = builder.finalize();
+
+        assert_eq!(data.len(), 32 + 8 + 8 + 5 + 32);
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(symbols.get("contract"), Some(Symbol { offset: 0, len: 32 }));
+        assert_eq!(symbols.get("amount"), Some(Symbol { offset: 32, len: 8 }));
+        assert_eq!(symbols.get("payload"), Some(Symbol { offset: 48, len: 5 }));
+        assert_eq!(
+            symbols.get("recipient"),
+            Some(Symbol {
+                offset: 53,
+                len: 32
+            })
+        );
+        assert_eq!(symbols.get("missing"), None);
+        assert_eq!(
+            &data[symbols.get("contract").unwrap().range()],
+            contract.as_ref()
+        );
+        assert_eq!(&data[symbols.get("payload").unwrap().range()], b"hello");
+        assert_eq!(&data[40..48], &5u64.to_be_bytes());
+    }
+
+    #[test]
+    fn reader_decodes_builder_output() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let contract: Bytes32 = rng.gen();
+        let recipient: Address = rng.gen();
+
+        let mut builder = Builder::new();
+        builder
+            .bytes32("contract", &contract)
+            .word("amount", Word::MAX)
+            .bytes("empty", &[])
+            .bytes("payload", b"hello")
+            .address("recipient", &recipient);
+        let (data, _) = builder.finalize();
+
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.bytes32(), Ok(contract));
+        assert_eq!(reader.word(), Ok(Word::MAX));
+        assert_eq!(reader.bytes(), Ok(&[][..]));
+        assert_eq!(reader.bytes(), Ok(&b"hello"[..]));
+        assert_eq!(reader.address(), Ok(recipient));
+        assert_eq!(reader.remaining(), 0);
+        assert_eq!(reader.position(), data.len());
+    }
+
+    #[test]
+    fn reader_rejects_truncated_data() {
+        let mut builder = Builder::new();
+        builder.bytes("payload", b"hello");
+        let (data, _) = builder.finalize();
+
+        let mut reader = Reader::new(&data[..data.len() - 1]);
+        assert_eq!(reader.bytes(), Err(Error::BufferIsTooShort));
+
+        let mut reader = Reader::new(&data[..4]);
+        assert_eq!(reader.word(), Err(Error::BufferIsTooShort));
+        assert_eq!(reader.position(), 0);
+
+        let len = Word::MAX.to_be_bytes();
+        let mut reader = Reader::new(&len);
+        assert_eq!(reader.bytes(), Err(Error::BufferIsTooShort));
+    }
+
+    #[test]
+    fn builder_replaces_symbol_with_same_name() {
+        let mut builder = Builder::new();
+        builder.word("value", 1).word("value", 2);
+        let (data, symbols) = builder.finalize();
+
+        assert_eq!(data.len(), 16);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols.get("value"), Some(Symbol { offset: 8, len: 8 }));
+    }
+}
@@ -110,6 +110,7 @@ mod use_std {
                         Input::CoinSigned(_) => (),
                         Input::CoinPredicate(_) => (),
                         Input::Contract(_) => (),
+                        Input::ContractReadOnly(_) => (),
                         Input::MessageCoinSigned(_) => (),
                         Input::MessageCoinPredicate(_) => (),
                         Input::MessageDataSigned(_) => (),
@@ -285,13 +286,25 @@ mod use_std {
                         builder.add_input(input);
                     }
 
+                    7 => {
+                        let input = Input::contract_read_only(
+                            self.rng.gen(),
+                            self.rng.gen(),
+                            self.rng.gen(),
+                            self.rng.gen(),
+                            self.rng.gen(),
+                        );
+
+                        builder.add_input(input);
+                    }
+
                     _ => unreachable!(),
                 }
             }
 
             input_coin_keys.iter().for_each(|k| {
                 builder.add_unsigned_coin_input(
-                    *k,
+                    k.clone(),
                     self.rng.gen(),
                     self.rng.gen(),
                     self.rng.gen(),
@@ -302,7 +315,7 @@ mod use_std {
             input_message_keys.iter().for_each(|(t, k)| match t {
                 MessageType::MessageCoin => {
                     builder.add_unsigned_message_input(
-                        *k,
+                        k.clone(),
                         self.rng.gen(),
                         self.rng.gen(),
                         self.rng.gen(),
@@ -311,7 +324,7 @@ mod use_std {
                 }
                 MessageType::MessageData => {
                     builder.add_unsigned_message_input(
-                        *k,
+                        k.clone(),
                         self.rng.gen(),
                         self.rng.gen(),
                         self.rng.gen(),
@@ -8,3 +8,5 @@ mod display;
 
 #[cfg(not(feature = "serde"))]
 use bincode as _;
+// Used by the benchmarks.
+use criterion as _;
@@ -4,7 +4,10 @@ use super::PREDICATE_PARAMS;
 use crate::{
     builder::TransactionBuilder,
     field,
-    field::Witnesses,
+    field::{
+        Inputs,
+        Witnesses,
+    },
     test_helper::{
         generate_bytes,
         generate_nonempty_padded_bytes,
@@ -16,6 +19,7 @@ use crate::{
 use fuel_crypto::{
     PublicKey,
     SecretKey,
+    Signature,
 };
 use fuel_types::ChainId;
 use rand::{
@@ -101,7 +105,7 @@ fn input_coin_message_signature() {
                     amount,
                     asset_id,
                     tx_pointer,
-                    witness_index as u16,
+                    WitnessIndex::new(witness_index as u16),
                 )
             })
             .expect("Failed to validate transaction");
@@ -122,7 +126,7 @@ fn input_coin_message_signature() {
                     nonce,
                     amount,
                     data.clone(),
-                    witness_index as u16,
+                    WitnessIndex::new(witness_index as u16),
                 )
             })
             .expect("Failed to validate transaction");
@@ -139,8 +143,14 @@ fn coin_signed() {
     let rng = &mut StdRng::seed_from_u64(8586);
     let mut tx = TransactionBuilder::script(vec![], vec![]).finalize();
 
-    let input =
-        Input::coin_signed(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
+    let input = Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
     tx.add_input(input);
 
     let block_height = rng.gen();
@@ -148,7 +158,13 @@ fn coin_signed() {
         .check(block_height, &ConsensusParameters::standard())
         .expect_err("Expected failure");
 
-    assert_eq!(ValidityError::InputWitnessIndexBounds { index: 0 }, err);
+    assert_eq!(
+        ValidityError::InputWitnessIndexBounds {
+            index: 0,
+            witness_index: 0
+        },
+        err
+    );
 }
 
 #[test]
@@ -158,12 +174,12 @@ fn duplicate_secrets_reuse_witness() {
 
     let script = TransactionBuilder::script(vec![], vec![])
         // coin 1
-        .add_unsigned_coin_input(key, rng.gen(), 100, Default::default(), Default::default())
+        .add_unsigned_coin_input(key.clone(), rng.gen(), 100, Default::default(), Default::default())
         // coin 2
-        .add_unsigned_coin_input(key, rng.gen(), 200, rng.gen(), Default::default())
+        .add_unsigned_coin_input(key.clone(), rng.gen(), 200, rng.gen(), Default::default())
         // message 1
-        .add_unsigned_message_input(key, rng.gen(), rng.gen(), 100, vec![])
-        .add_unsigned_message_input(key, rng.gen(), rng.gen(), 100, vec![rng.gen()])
+        .add_unsigned_message_input(key.clone(), rng.gen(), rng.gen(), 100, vec![])
+        .add_unsigned_message_input(key.clone(), rng.gen(), rng.gen(), 100, vec![rng.gen()])
         .finalize();
 
     assert_eq!(
@@ -175,11 +191,11 @@ fn duplicate_secrets_reuse_witness() {
     // verify witness reuse for creation txs
     let create = TransactionBuilder::create(Witness::default(), rng.gen(), vec![])
         // coin 1
-        .add_unsigned_coin_input(key, rng.gen(), 100, Default::default(), Default::default())
+        .add_unsigned_coin_input(key.clone(), rng.gen(), 100, Default::default(), Default::default())
         // coin 2
-        .add_unsigned_coin_input(key, rng.gen(), 200, rng.gen(), Default::default())
+        .add_unsigned_coin_input(key.clone(), rng.gen(), 200, rng.gen(), Default::default())
         // message 1
-        .add_unsigned_message_input(key, rng.gen(), rng.gen(), 100, vec![])
+        .add_unsigned_message_input(key.clone(), rng.gen(), rng.gen(), 100, vec![])
         .add_unsigned_message_input(key, rng.gen(), rng.gen(), 100, vec![rng.gen()])
         .finalize();
 
@@ -234,6 +250,7 @@ fn coin_predicate() {
     let mut predicate = generate_nonempty_padded_bytes(rng);
     let owner = (*Contract::root_from_code(&predicate)).into();
     predicate[0] = predicate[0].wrapping_add(1);
+    let expected = Input::predicate_owner(&predicate);
 
     let err = Input::coin_predicate(
         rng.gen(),
@@ -249,7 +266,14 @@ fn coin_predicate() {
     .err()
     .unwrap();
 
-    assert_eq!(ValidityError::InputPredicateOwner { index: 1 }, err);
+    assert_eq!(
+        ValidityError::InputPredicateOwner {
+            index: 1,
+            expected,
+            actual: owner,
+        },
+        err
+    );
 }
 
 #[test]
@@ -314,6 +338,49 @@ fn contract() {
     );
 }
 
+#[test]
+fn contract_read_only() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let txhash: Bytes32 = rng.gen();
+
+    Input::contract_read_only(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen())
+        .check(1, &txhash, &[], &[], &Default::default(), &mut None)
+        .expect("Read-only contract input doesn't require an output");
+
+    let fee = Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
+    let read_only =
+        Input::contract_read_only(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen());
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.add_input(fee.clone()).add_input(read_only.clone());
+    builder.add_witness(rng.gen());
+    builder
+        .finalize()
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
+        .expect("Read-only contract input without an output is valid");
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
+        .add_input(fee)
+        .add_input(read_only)
+        .add_output(Output::contract(1, rng.gen(), rng.gen()));
+    builder.add_witness(rng.gen());
+    let err = builder
+        .finalize()
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
+        .expect_err("Output can't point to a read-only contract input");
+
+    assert_eq!(err, ValidityError::OutputContractInputIndex { index: 0 });
+}
+
 #[test]
 fn message_metadata() {
     let rng = &mut StdRng::seed_from_u64(8586);
@@ -342,11 +409,16 @@ fn message_metadata() {
         rng.gen(),
         rng.gen(),
         rng.gen(),
-        0,
+        WitnessIndex::new(0),
         generate_bytes(rng),
     );
-    let fee_input =
-        Input::message_coin_signed(rng.gen(), rng.gen(), rng.gen(), rng.gen(), 1);
+    let fee_input = Input::message_coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(1),
+    );
 
     tx.add_input(input);
     tx.add_input(fee_input);
@@ -356,11 +428,18 @@ fn message_metadata() {
         .check(block_height, &ConsensusParameters::standard())
         .expect_err("Expected failure");
 
-    assert_eq!(ValidityError::InputWitnessIndexBounds { index: 0 }, err,);
+    assert_eq!(
+        ValidityError::InputWitnessIndexBounds {
+            index: 0,
+            witness_index: 0
+        },
+        err,
+    );
 
     let mut predicate = generate_nonempty_padded_bytes(rng);
     let recipient = Input::predicate_owner(&predicate);
     predicate[0] = predicate[0].wrapping_add(1);
+    let expected = Input::predicate_owner(&predicate);
 
     let err = Input::message_data_predicate(
         rng.gen(),
@@ -375,7 +454,14 @@ fn message_metadata() {
     .check(1, &txhash, &[], &[], &Default::default(), &mut None)
     .expect_err("Expected failure");
 
-    assert_eq!(ValidityError::InputPredicateOwner { index: 1 }, err);
+    assert_eq!(
+        ValidityError::InputPredicateOwner {
+            index: 1,
+            expected,
+            actual: recipient,
+        },
+        err
+    );
 
     let data = vec![0xff; PREDICATE_PARAMS.max_message_data_length() as usize + 1];
 
@@ -384,7 +470,7 @@ fn message_metadata() {
         rng.gen(),
         rng.gen(),
         rng.gen(),
-        0,
+        WitnessIndex::new(0),
         data.clone(),
     )
     .check(
@@ -472,7 +558,13 @@ fn message_message_coin() {
 
     let mut tx = TransactionBuilder::script(vec![], vec![]).finalize();
 
-    let input = Input::message_coin_signed(rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
+    let input = Input::message_coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
     tx.add_input(input);
 
     let block_height = rng.gen();
@@ -480,11 +572,18 @@ fn message_message_coin() {
         .check(block_height, &ConsensusParameters::standard())
         .expect_err("Expected failure");
 
-    assert_eq!(ValidityError::InputWitnessIndexBounds { index: 0 }, err,);
+    assert_eq!(
+        ValidityError::InputWitnessIndexBounds {
+            index: 0,
+            witness_index: 0
+        },
+        err,
+    );
 
     let mut predicate = generate_nonempty_padded_bytes(rng);
     let recipient = Input::predicate_owner(&predicate);
     predicate[0] = predicate[0].wrapping_add(1);
+    let expected = Input::predicate_owner(&predicate);
 
     let err = Input::message_coin_predicate(
         rng.gen(),
@@ -498,7 +597,14 @@ fn message_message_coin() {
     .check(1, &txhash, &[], &[], &Default::default(), &mut None)
     .expect_err("Expected failure");
 
-    assert_eq!(ValidityError::InputPredicateOwner { index: 1 }, err);
+    assert_eq!(
+        ValidityError::InputPredicateOwner {
+            index: 1,
+            expected,
+            actual: recipient,
+        },
+        err
+    );
 
     let predicate = vec![0xff; PREDICATE_PARAMS.max_predicate_length() as usize + 1];
 
@@ -539,18 +645,35 @@ fn transaction_with_duplicate_coin_inputs_is_invalid() {
     let rng = &mut StdRng::seed_from_u64(8586);
     let utxo_id = rng.gen();
 
-    let a = Input::coin_signed(utxo_id, rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
-    let b = Input::coin_signed(utxo_id, rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
+    let a = Input::coin_signed(
+        utxo_id,
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
+    let b = Input::coin_signed(
+        utxo_id,
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
 
-    let err = TransactionBuilder::script(vec![], vec![])
-        .add_input(a)
-        .add_input(b)
-        .add_witness(rng.gen())
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.add_input(a).add_input(b);
+    builder.add_witness(rng.gen());
+    let err = builder
         .finalize()
         .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateInputUtxoId { utxo_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputUtxoId { index: 1, utxo_id }
+    );
 }
 
 #[test]
@@ -561,7 +684,7 @@ fn transaction_with_duplicate_message_inputs_is_invalid() {
         rng.gen(),
         rng.gen(),
         rng.gen(),
-        0,
+        WitnessIndex::new(0),
         generate_bytes(rng),
     );
     let message_id = message_input.message_id().unwrap();
@@ -574,20 +697,25 @@ fn transaction_with_duplicate_message_inputs_is_invalid() {
         rng.gen(),
     );
 
-    let err = TransactionBuilder::script(vec![], vec![])
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
         .add_input(fee)
         .add_input(message_input.clone())
         // duplicate input
-        .add_input(message_input)
-        .add_witness(rng.gen())
+        .add_input(message_input);
+    builder.add_witness(rng.gen());
+    let err = builder
         .finalize()
-        .check_without_signatures(
-            Default::default(),
-            &ConsensusParameters::standard(),
-        )
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateMessageInputId { message_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateMessageInputId {
+            index: 2,
+            message_id
+        }
+    );
 }
 
 #[test]
@@ -619,7 +747,56 @@ fn transaction_with_duplicate_contract_inputs_is_invalid() {
         .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateInputContractId { contract_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputContractId {
+            index: 2,
+            contract_id
+        }
+    );
+}
+
+#[test]
+fn transaction_with_read_only_and_writable_inputs_of_same_contract_is_invalid() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let contract_id = rng.gen();
+    let fee = Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
+
+    let a = Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), contract_id);
+    let b = Input::contract_read_only(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        contract_id,
+    );
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
+        .add_input(fee)
+        .add_input(a)
+        .add_input(b)
+        .add_output(Output::contract(1, rng.gen(), rng.gen()));
+    builder.add_witness(rng.gen());
+    let err = builder
+        .finalize()
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
+        .expect_err("Expected checkable failure");
+
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputContractId {
+            index: 2,
+            contract_id
+        }
+    );
 }
 
 #[test]
@@ -629,20 +806,291 @@ fn transaction_with_duplicate_contract_utxo_id_is_valid() {
 
     let a = Input::contract(input_utxo_id, rng.gen(), rng.gen(), rng.gen(), rng.gen());
     let b = Input::contract(input_utxo_id, rng.gen(), rng.gen(), rng.gen(), rng.gen());
-    let fee =
-        Input::coin_signed(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen(), 0);
+    let fee = Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    );
 
     let o = Output::contract(0, rng.gen(), rng.gen());
     let p = Output::contract(1, rng.gen(), rng.gen());
 
-    TransactionBuilder::script(vec![], vec![])
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
         .add_input(a)
         .add_input(b)
         .add_input(fee)
         .add_output(o)
-        .add_output(p)
-        .add_witness(rng.gen())
+        .add_output(p);
+    builder.add_witness(rng.gen());
+    builder
         .finalize()
         .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect("Duplicated UTXO id is valid for contract input");
 }
+
+#[test]
+fn invalid_signature_reports_index_of_input() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+
+    for _ in 0..6 {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+    }
+    // The witness `0` is signed by the owner of the first input
+    builder.add_input(Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(0),
+    ));
+    for _ in 0..3 {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+    }
+
+    let err = builder
+        .finalize()
+        .check(Default::default(), &ConsensusParameters::standard())
+        .expect_err("Expected invalid signature");
+
+    assert_eq!(err, ValidityError::InputInvalidSignature { index: 6 });
+    assert_eq!(err.to_string(), "Input 6 has an invalid signature");
+}
+
+#[test]
+fn check_signatures_accepts_duplicated_signature_witnesses() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let chain_id = ChainId::default();
+
+    // Given
+    let secret = SecretKey::random(rng);
+    let owner = Input::owner(&secret.public_key());
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    for _ in 0..4 {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+    }
+    let first = builder.add_witness(Witness::default());
+    let second = builder.add_witness(Witness::default());
+    for witness_index in [first, second, first] {
+        builder.add_input(Input::coin_signed(
+            rng.gen(),
+            owner,
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            witness_index,
+        ));
+    }
+    let mut tx = builder.finalize();
+    let signature = Signature::sign(
+        &secret,
+        fuel_crypto::Message::from_bytes_ref(&tx.id(&chain_id)),
+    );
+    tx.witnesses_mut()[first.as_usize()] = signature.as_ref().into();
+    tx.witnesses_mut()[second.as_usize()] = signature.as_ref().into();
+
+    // When
+    let result = tx.check_signatures(&chain_id);
+
+    // Then
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn check_signatures_reports_index_of_input_with_unrecoverable_signature() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let chain_id = ChainId::default();
+
+    // Given
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    for _ in 0..8 {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+    }
+    let mut tx = builder.finalize();
+    let witness_index = tx.inputs()[5].witness_index().expect("The input is signed");
+    tx.witnesses_mut()[witness_index as usize] = Signature::default().as_ref().into();
+
+    // When
+    let result = tx.check_signatures(&chain_id);
+
+    // Then
+    assert_eq!(
+        result,
+        Err(ValidityError::InputInvalidSignature { index: 5 })
+    );
+}
+
+#[test]
+fn missing_witness_reports_index_of_input_and_witness() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+
+    for _ in 0..3 {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
+    }
+    builder.add_input(Input::coin_signed(
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        WitnessIndex::new(7),
+    ));
+
+    let err = builder
+        .finalize()
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
+        .expect_err("Expected missing witness");
+
+    assert_eq!(
+        err,
+        ValidityError::InputWitnessIndexBounds {
+            index: 3,
+            witness_index: 7
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Input 3 refers to the witness 7 that doesn't exist"
+    );
+}
+
+#[test]
+fn duplicate_utxo_id_reports_index_of_second_input() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let utxo_ids: Vec<UtxoId> = (0..8).map(|_| rng.gen()).collect();
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+
+    for (index, utxo_id) in utxo_ids.iter().enumerate() {
+        let utxo_id = if index == 5 { utxo_ids[2] } else { *utxo_id };
+        builder.add_input(Input::coin_signed(
+            utxo_id,
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            WitnessIndex::new(0),
+        ));
+    }
+
+    builder.add_witness(rng.gen());
+    let err = builder
+        .finalize()
+        .check_without_signatures(Default::default(), &ConsensusParameters::standard())
+        .expect_err("Expected duplicated UTXO id");
+
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputUtxoId {
+            index: 5,
+            utxo_id: utxo_ids[2]
+        }
+    );
+}
+
+#[test]
+fn oversize_predicate_reports_index_of_input() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let max_predicate_length = 1024;
+    let mut params = ConsensusParameters::standard();
+    params.set_predicate_params(
+        PREDICATE_PARAMS.with_max_predicate_length(max_predicate_length as u64),
+    );
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+
+    for index in 0..5 {
+        let predicate = if index == 3 {
+            vec![0u8; max_predicate_length + 1]
+        } else {
+            generate_nonempty_padded_bytes(rng)
+        };
+        builder.add_input(Input::coin_predicate(
+            rng.gen(),
+            Input::predicate_owner(&predicate),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            0,
+            predicate,
+            generate_bytes(rng),
+        ));
+    }
+
+    let err = builder
+        .finalize()
+        .check_without_signatures(Default::default(), &params)
+        .expect_err("Expected oversize predicate");
+
+    assert_eq!(err, ValidityError::InputPredicateLength { index: 3 });
+}
+
+#[test]
+fn check_signatures_accepts_owner_derived_from_compressed_public_key() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let chain_id = ChainId::default();
+
+    // Given
+    let secret = SecretKey::random(rng);
+    let compressed = secret.public_key().to_compressed();
+    let owner = PublicKey::from_compressed(&compressed)
+        .expect("Valid compressed key")
+        .address();
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    let witness_index = builder.add_witness(Witness::default());
+    builder.add_input(Input::coin_signed(
+        rng.gen(),
+        owner,
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        witness_index,
+    ));
+    let mut tx = builder.finalize();
+    let signature = Signature::sign(
+        &secret,
+        fuel_crypto::Message::from_bytes_ref(&tx.id(&chain_id)),
+    );
+    tx.witnesses_mut()[witness_index.as_usize()] = signature.as_ref().into();
+
+    // When
+    let result = tx.check_signatures(&chain_id);
+
+    // Then
+    assert_eq!(owner, Input::owner(&secret.public_key()));
+    assert_eq!(result, Ok(()));
+}
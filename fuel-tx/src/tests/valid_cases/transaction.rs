@@ -24,9 +24,12 @@ use fuel_crypto::{
     SecretKey,
     Signature,
 };
-use fuel_types::canonical::{
-    Deserialize,
-    Serialize,
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Serialize,
+    },
+    BlockHeight,
 };
 use rand::{
     rngs::StdRng,
@@ -120,6 +123,86 @@ fn maturity() {
     assert_eq!(ValidityError::TransactionMaturity, err);
 }
 
+#[test]
+fn expiration() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let expiration: BlockHeight = 1000.into();
+    let tx = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
+        .expiration(expiration)
+        .add_random_fee_input()
+        .finalize();
+
+    tx.check(999.into(), &test_params())
+        .expect("Failed to validate script before expiration");
+    tx.check(expiration, &test_params())
+        .expect("Failed to validate script at expiration");
+
+    let err = tx
+        .check(1001.into(), &test_params())
+        .expect_err("Expected expired transaction");
+    assert_eq!(ValidityError::TransactionExpired, err);
+
+    let bytes = tx.to_bytes();
+    let decoded = Script::from_bytes(&bytes).expect("Failed to decode script");
+    assert_eq!(decoded.policies().get(PolicyType::Expiration), Some(1000));
+
+    let err = Transaction::create(
+        0,
+        Policies::new().with_expiration(999.into()).with_max_fee(0),
+        rng.gen(),
+        vec![],
+        vec![],
+        vec![],
+        vec![rng.gen()],
+    )
+    .check(1001.into(), &test_params())
+    .expect_err("Expected expired transaction");
+    assert_eq!(ValidityError::TransactionExpired, err);
+}
+
+#[test]
+fn expiration_below_maturity_is_invalid() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let err = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
+        .maturity(100.into())
+        .expiration(99.into())
+        .add_random_fee_input()
+        .finalize()
+        .check(100.into(), &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(ValidityError::TransactionPoliciesAreInvalid, err);
+}
+
+#[test]
+fn check__rejects_transaction_with_unknown_policies() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    // Given
+    let mut tx = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
+        .add_random_fee_input()
+        .finalize();
+    let mut policies_bytes = tx.policies().to_bytes();
+    let bits = u32::from_bytes(&policies_bytes[..8]).unwrap() | 1 << 31;
+    policies_bytes[..8].copy_from_slice(&bits.to_bytes());
+    policies_bytes.extend_from_slice(&[0xff; 8]);
+    *tx.policies_mut() =
+        Policies::from_bytes(&policies_bytes).expect("Unknown policies are decoded");
+    let bytes = tx.to_bytes();
+
+    // When
+    let decoded = Transaction::from_bytes(&bytes).expect("The transaction is decoded");
+    let err = decoded
+        .check(Default::default(), &test_params())
+        .expect_err("Unknown policies are invalid");
+
+    // Then
+    assert_eq!(decoded.to_bytes(), bytes);
+    assert_eq!(ValidityError::TransactionPoliciesAreInvalid, err);
+}
+
 #[test]
 fn script__check__not_set_witness_limit_success() {
     // Given
@@ -357,7 +440,13 @@ fn max_iow() {
 
     let asset_id: AssetId = AssetId::BASE;
     secrets.iter().for_each(|k| {
-        builder.add_unsigned_coin_input(*k, rng.gen(), rng.gen(), asset_id, rng.gen());
+        builder.add_unsigned_coin_input(
+            k.clone(),
+            rng.gen(),
+            rng.gen(),
+            asset_id,
+            rng.gen(),
+        );
     });
 
     while builder.outputs().len() < TX_PARAMS.max_outputs() as usize {
@@ -385,7 +474,13 @@ fn max_iow() {
         .collect();
 
     secrets.iter().for_each(|k| {
-        builder.add_unsigned_coin_input(*k, rng.gen(), rng.gen(), rng.gen(), rng.gen());
+        builder.add_unsigned_coin_input(
+            k.clone(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
     });
 
     while builder.outputs().len() < TX_PARAMS.max_outputs() as usize {
@@ -415,7 +510,13 @@ fn max_iow() {
         .collect();
 
     secrets.iter().for_each(|k| {
-        builder.add_unsigned_coin_input(*k, rng.gen(), rng.gen(), rng.gen(), rng.gen());
+        builder.add_unsigned_coin_input(
+            k.clone(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
     });
 
     while builder.outputs().len() < 1 + TX_PARAMS.max_outputs() as usize {
@@ -445,7 +546,13 @@ fn max_iow() {
         .collect();
 
     secrets.iter().for_each(|k| {
-        builder.add_unsigned_coin_input(*k, rng.gen(), rng.gen(), rng.gen(), rng.gen());
+        builder.add_unsigned_coin_input(
+            k.clone(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        );
     });
 
     while builder.outputs().len() < TX_PARAMS.max_outputs() as usize {
@@ -479,8 +586,8 @@ fn output_change_asset_id() {
 
     TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
         .maturity(maturity)
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), a, rng.gen())
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), b, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), a, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), b, rng.gen())
         .add_output(Output::change(rng.gen(), rng.next_u64(), a))
         .add_output(Output::change(rng.gen(), rng.next_u64(), b))
         .finalize()
@@ -489,8 +596,8 @@ fn output_change_asset_id() {
 
     let err = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
         .maturity(maturity)
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), a, rng.gen())
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), b, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), a, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), b, rng.gen())
         .add_output(Output::change(rng.gen(), rng.next_u64(), a))
         .add_output(Output::change(rng.gen(), rng.next_u64(), a))
         .finalize()
@@ -498,14 +605,17 @@ fn output_change_asset_id() {
         .expect_err("Expected erroneous transaction");
 
     assert_eq!(
-        ValidityError::TransactionOutputChangeAssetIdDuplicated(a),
+        ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        },
         err
     );
 
     let err = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
         .maturity(maturity)
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), a, rng.gen())
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), b, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), a, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), b, rng.gen())
         .add_output(Output::change(rng.gen(), rng.next_u64(), a))
         .add_output(Output::change(rng.gen(), rng.next_u64(), c))
         .finalize()
@@ -514,12 +624,12 @@ fn output_change_asset_id() {
 
     assert!(matches!(
         err,
-        ValidityError::TransactionOutputChangeAssetIdNotFound(asset_id) if asset_id == c
+        ValidityError::TransactionOutputChangeAssetIdNotFound { index: 1, asset_id } if asset_id == c
     ));
 
     let err = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
         .maturity(maturity)
-        .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), a, rng.gen())
+        .add_unsigned_coin_input(secret.clone(), rng.gen(), rng.gen(), a, rng.gen())
         .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), b, rng.gen())
         .add_output(Output::coin(rng.gen(), rng.next_u64(), a))
         .add_output(Output::coin(rng.gen(), rng.next_u64(), c))
@@ -529,10 +639,50 @@ fn output_change_asset_id() {
 
     assert!(matches!(
         err,
-        ValidityError::TransactionOutputCoinAssetIdNotFound(asset_id) if asset_id == c
+        ValidityError::TransactionOutputCoinAssetIdNotFound { index: 1, asset_id } if asset_id == c
     ));
 }
 
+#[test]
+fn output_change_asset_id__reports_index_of_unmatched_change_output() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+    let secret = SecretKey::random(rng);
+    let assets: Vec<AssetId> = (0..4).map(|_| rng.gen()).collect();
+    let unknown: AssetId = rng.gen();
+
+    let mut builder =
+        TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng));
+    for asset_id in &assets {
+        builder.add_unsigned_coin_input(
+            secret.clone(),
+            rng.gen(),
+            rng.gen(),
+            *asset_id,
+            rng.gen(),
+        );
+    }
+    builder
+        .add_output(Output::change(rng.gen(), 0, assets[0]))
+        .add_output(Output::coin(rng.gen(), 0, assets[1]))
+        .add_output(Output::change(rng.gen(), 0, assets[2]))
+        .add_output(Output::change(rng.gen(), 0, unknown))
+        .add_output(Output::change(rng.gen(), 0, assets[3]));
+
+    let err = builder
+        .finalize()
+        .check(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(
+        err,
+        ValidityError::TransactionOutputChangeAssetIdNotFound {
+            index: 3,
+            asset_id: unknown
+        }
+    );
+}
+
 #[test]
 fn script__check__happy_path() {
     let rng = &mut StdRng::seed_from_u64(8586);
@@ -688,7 +838,7 @@ fn create__check__cannot_have_message_input() {
     let err = TransactionBuilder::create(generate_bytes(rng).into(), rng.gen(), vec![])
         .maturity(maturity)
         .add_unsigned_message_input(
-            secret,
+            secret.clone(),
             rng.gen(),
             rng.gen(),
             rng.gen(),
@@ -754,7 +904,10 @@ fn create__check__cannot_have_multiple_change_outputs() {
 
     assert_eq!(
         err,
-        ValidityError::TransactionOutputChangeAssetIdDuplicated(AssetId::BASE)
+        ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: AssetId::BASE
+        }
     );
 }
 
@@ -800,7 +953,7 @@ fn create__check__cannot_create_multiple_contract_outputs() {
     let salt = rng.gen();
     let storage_slots: Vec<StorageSlot> = vec![];
     let state_root = Contract::initial_state_root(storage_slots.iter());
-    let contract_id = contract.id(&salt, &contract.root(), &state_root);
+    let contract_id = Contract::id(&salt, &contract.root(), &state_root);
 
     let err = TransactionBuilder::create(witness.into(), salt, storage_slots)
         .maturity(maturity)
@@ -880,7 +1033,7 @@ fn create__check_without_signatures__errors_if_wrong_witness_index() {
             rng.gen(),
             rng.gen(),
             rng.gen(),
-            0,
+            WitnessIndex::new(0),
         )],
         vec![],
         vec![Default::default()],
@@ -996,7 +1149,13 @@ fn script__check__transaction_at_maximum_size_is_valid() {
 
     let base_size = {
         let tx = TransactionBuilder::script(vec![], vec![])
-            .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), rng.gen(), rng.gen())
+            .add_unsigned_coin_input(
+                secret.clone(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            )
             .finalize();
         tx.size()
     };
@@ -1030,7 +1189,13 @@ fn script__check__transaction_exceeding_maximum_size_is_invalid() {
 
     let base_size = {
         let tx = TransactionBuilder::script(vec![], vec![])
-            .add_unsigned_coin_input(secret, rng.gen(), rng.gen(), rng.gen(), rng.gen())
+            .add_unsigned_coin_input(
+                secret.clone(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+                rng.gen(),
+            )
             .finalize();
         tx.size()
     };
@@ -81,10 +81,10 @@ fn check__set_witness_limit_for_empty_witness_success() {
     // Given
     let block_height = 1000.into();
     let limit = Signature::LEN + vec![0u8; 0].size_static();
-    let tx = valid_upgrade_transaction()
-        .witness_limit(limit as u64)
-        .add_witness(vec![0; Signature::LEN].into())
-        .finalize_as_transaction();
+    let mut builder = valid_upgrade_transaction();
+    builder.witness_limit(limit as u64);
+    builder.add_witness(vec![0; Signature::LEN].into());
+    let tx = builder.finalize_as_transaction();
 
     // When
     let result = tx.check(block_height, &test_params());
@@ -146,7 +146,7 @@ fn reached_max_inputs() {
             .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -177,7 +177,7 @@ fn reached_max_outputs() {
         .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -213,7 +213,7 @@ fn reached_max_witnesses() {
         .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -258,7 +258,10 @@ fn output_change_asset_id_duplicated_output() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(a)),
+        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        }),
         result
     );
 }
@@ -279,7 +282,10 @@ fn output_change_asset_id_foreign_asset() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdNotFound(c)),
+        Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+            index: 0,
+            asset_id: c
+        }),
         result
     );
 }
@@ -489,9 +495,9 @@ fn check__errors_when_transactions_too_big() {
     let block_height = 1000.into();
 
     // Given
-    let tx = valid_upgrade_transaction()
-        .add_witness(vec![0; test_params().tx_params().max_size() as usize].into())
-        .finalize_as_transaction();
+    let mut builder = valid_upgrade_transaction();
+    builder.add_witness(vec![0; test_params().tx_params().max_size() as usize].into());
+    let tx = builder.finalize_as_transaction();
 
     // When
     let result = tx.check(block_height, &test_params());
@@ -535,8 +541,8 @@ fn check__errors_when_consensus_parameters_invalid_witness_index() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::InputWitnessIndexBounds {
-            index: u16::MAX as usize
+        Err(ValidityError::TransactionWitnessIndexBounds {
+            witness_index: u16::MAX
         }),
         result
     );
@@ -547,9 +553,9 @@ fn check__errors_when_consensus_parameters_invalid_checksum() {
     let block_height = 1000.into();
 
     // Given
-    let mut tx = valid_upgrade_transaction()
-        .add_witness(vec![123; 1024].into())
-        .finalize();
+    let mut builder = valid_upgrade_transaction();
+    builder.add_witness(vec![123; 1024].into());
+    let mut tx = builder.finalize();
     *tx.upgrade_purpose_mut() = UpgradePurpose::ConsensusParameters {
         witness_index: 0,
         checksum: Default::default(),
@@ -571,9 +577,9 @@ fn check__errors_when_consensus_parameters_unable_decode_consensus_parameters()
     let serialized_consensus_parameters = vec![123; 1024];
 
     // Given
-    let mut tx = valid_upgrade_transaction()
-        .add_witness(serialized_consensus_parameters.clone().into())
-        .finalize();
+    let mut builder = valid_upgrade_transaction();
+    builder.add_witness(serialized_consensus_parameters.clone().into());
+    let mut tx = builder.finalize();
     *tx.upgrade_purpose_mut() = UpgradePurpose::ConsensusParameters {
         witness_index: 0,
         checksum: Hasher::hash(serialized_consensus_parameters.as_slice()),
@@ -598,9 +604,9 @@ fn check__errors_when_consensus_parameters_different_than_calculated_metadata()
     // `valid_upgrade_transaction` already returns a transaction with calculated metadata.
     // Setting a new `UpgradePurpose` below will cause mismatch between the calculated
     // metadata and the actual metadata.
-    let mut tx = valid_upgrade_transaction()
-        .add_witness(serialized_consensus_parameters.clone().into())
-        .finalize();
+    let mut builder = valid_upgrade_transaction();
+    builder.add_witness(serialized_consensus_parameters.clone().into());
+    let mut tx = builder.finalize();
     *tx.upgrade_purpose_mut() = UpgradePurpose::ConsensusParameters {
         witness_index: 0,
         checksum: Hasher::hash(serialized_consensus_parameters.as_slice()),
@@ -652,6 +658,7 @@ mod check_inputs {
         let block_height = 1000.into();
         let predicate = (0..100).map(|_| rng.gen()).collect_vec();
         let incorrect_owner: Address = [1; 32].into();
+        let expected = Input::predicate_owner(&predicate);
 
         // Given
         let tx = valid_upgrade_transaction()
@@ -671,7 +678,14 @@ mod check_inputs {
         let result = tx.check(block_height, &test_params());
 
         // Then
-        assert_eq!(Err(ValidityError::InputPredicateOwner { index: 1 }), result);
+        assert_eq!(
+            Err(ValidityError::InputPredicateOwner {
+                index: 1,
+                expected,
+                actual: incorrect_owner,
+            }),
+            result
+        );
     }
 
     #[test]
@@ -707,6 +721,7 @@ mod check_inputs {
         let block_height = 1000.into();
         let predicate = (0..100).map(|_| rng.gen()).collect_vec();
         let incorrect_owner: Address = [1; 32].into();
+        let expected = Input::predicate_owner(&predicate);
 
         // Given
         let tx = valid_upgrade_transaction()
@@ -725,6 +740,13 @@ mod check_inputs {
         let result = tx.check(block_height, &test_params());
 
         // Then
-        assert_eq!(Err(ValidityError::InputPredicateOwner { index: 1 }), result);
+        assert_eq!(
+            Err(ValidityError::InputPredicateOwner {
+                index: 1,
+                expected,
+                actual: incorrect_owner,
+            }),
+            result
+        );
     }
 }
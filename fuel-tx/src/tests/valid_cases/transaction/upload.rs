@@ -190,10 +190,10 @@ fn check__set_witness_limit_for_empty_witness_success() {
         .witnesses()
         .size_dynamic();
     let limit = subsection_size + Signature::LEN + vec![0u8; 0].size_static();
-    let tx = valid_upload_transaction()
-        .witness_limit(limit as u64)
-        .add_witness(vec![0; Signature::LEN].into())
-        .finalize_as_transaction();
+    let mut builder = valid_upload_transaction();
+    builder.witness_limit(limit as u64);
+    builder.add_witness(vec![0; Signature::LEN].into());
+    let tx = builder.finalize_as_transaction();
 
     // When
     let result = tx.check(block_height, &test_params());
@@ -213,10 +213,10 @@ fn check__set_witness_limit_less_than_witness_data_size_fails() {
 
     // Given
     let failing_limit = limit - 1;
-    let tx = valid_upload_transaction()
-        .witness_limit(failing_limit as u64)
-        .add_witness(vec![0; Signature::LEN].into())
-        .finalize_as_transaction();
+    let mut builder = valid_upload_transaction();
+    builder.witness_limit(failing_limit as u64);
+    builder.add_witness(vec![0; Signature::LEN].into());
+    let tx = builder.finalize_as_transaction();
 
     // When
     let result = tx.check(block_height, &test_params());
@@ -257,7 +257,7 @@ fn check__reached_max_inputs() {
             .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -288,7 +288,7 @@ fn check__reached_max_outputs() {
         .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -324,7 +324,7 @@ fn check__reached_max_witnesses() {
         .collect();
     secrets.iter().for_each(|k| {
         builder.add_unsigned_coin_input(
-            *k,
+            k.clone(),
             rng.gen(),
             rng.gen(),
             AssetId::BASE,
@@ -369,7 +369,10 @@ fn output_change_asset_id_duplicated_output() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(a)),
+        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        }),
         result
     );
 }
@@ -390,7 +393,10 @@ fn output_change_asset_id_foreign_asset() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdNotFound(c)),
+        Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+            index: 0,
+            asset_id: c
+        }),
         result
     );
 }
@@ -600,9 +606,9 @@ fn check__errors_when_transactions_too_big() {
     let block_height = 1000.into();
 
     // Given
-    let tx = valid_upload_transaction()
-        .add_witness(vec![0; test_params().tx_params().max_size() as usize].into())
-        .finalize_as_transaction();
+    let mut builder = valid_upload_transaction();
+    builder.add_witness(vec![0; test_params().tx_params().max_size() as usize].into());
+    let tx = builder.finalize_as_transaction();
 
     // When
     let result = tx.check(block_height, &test_params());
@@ -643,8 +649,8 @@ fn check__errors_when_bytecode_witness_index_is_invalid() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::InputWitnessIndexBounds {
-            index: u16::MAX as usize
+        Err(ValidityError::TransactionWitnessIndexBounds {
+            witness_index: u16::MAX
         }),
         result
     );
@@ -778,6 +784,7 @@ mod inputs {
         let block_height = 1000.into();
         let predicate = (0..100).map(|_| rng.gen()).collect_vec();
         let incorrect_owner: Address = [1; 32].into();
+        let expected = Input::predicate_owner(&predicate);
 
         // Given
         let tx = valid_upload_transaction()
@@ -797,7 +804,14 @@ mod inputs {
         let result = tx.check(block_height, &test_params());
 
         // Then
-        assert_eq!(Err(ValidityError::InputPredicateOwner { index: 1 }), result);
+        assert_eq!(
+            Err(ValidityError::InputPredicateOwner {
+                index: 1,
+                expected,
+                actual: incorrect_owner,
+            }),
+            result
+        );
     }
 
     #[test]
@@ -833,6 +847,7 @@ mod inputs {
         let block_height = 1000.into();
         let predicate = (0..100).map(|_| rng.gen()).collect_vec();
         let incorrect_owner: Address = [1; 32].into();
+        let expected = Input::predicate_owner(&predicate);
 
         // Given
         let tx = valid_upload_transaction()
@@ -851,6 +866,13 @@ mod inputs {
         let result = tx.check(block_height, &test_params());
 
         // Then
-        assert_eq!(Err(ValidityError::InputPredicateOwner { index: 1 }), result);
+        assert_eq!(
+            Err(ValidityError::InputPredicateOwner {
+                index: 1,
+                expected,
+                actual: incorrect_owner,
+            }),
+            result
+        );
     }
 }
@@ -46,7 +46,6 @@ use alloc::vec::{
 };
 use itertools::Itertools;
 
-mod fee;
 mod metadata;
 mod repr;
 mod types;
@@ -55,6 +54,7 @@ mod validity;
 mod id;
 
 pub mod consensus_parameters;
+pub mod fee;
 pub mod policies;
 
 pub use consensus_parameters::{
@@ -62,9 +62,11 @@ pub use consensus_parameters::{
     ContractParameters,
     DependentCost,
     FeeParameters,
+    GasCostValue,
     GasCosts,
     GasCostsValues,
     GasUnit,
+    NewStorageCost,
     PredicateParameters,
     ScriptParameters,
     TxParameters,
@@ -84,6 +86,9 @@ pub use validity::{
 #[cfg(feature = "alloc")]
 pub use id::Signable;
 
+#[cfg(all(feature = "async", feature = "test-helpers"))]
+pub(crate) use id::attach_signature;
+
 pub use id::{
     PrepareSign,
     UniqueIdentifier,
@@ -444,14 +449,18 @@ pub trait Executable: field::Inputs + field::Outputs + field::Witnesses {
         asset_ids.collect_vec().into_iter()
     }
 
-    /// Returns ids of all `Input::Contract` that are present in the inputs.
+    /// Returns ids of all `Input::Contract` and `Input::ContractReadOnly` that are
+    /// present in the inputs.
     // TODO: Return `Vec<input::Contract>` instead
     fn input_contracts(&self) -> IntoIter<&fuel_types::ContractId> {
         let mut inputs: Vec<_> = self
             .inputs()
             .iter()
             .filter_map(|input| match input {
-                Input::Contract(Contract { contract_id, .. }) => Some(contract_id),
+                Input::Contract(Contract { contract_id, .. })
+                | Input::ContractReadOnly(Contract { contract_id, .. }) => {
+                    Some(contract_id)
+                }
                 _ => None,
             })
             .collect();
@@ -500,7 +509,7 @@ pub trait Executable: field::Inputs + field::Outputs + field::Witnesses {
         amount: Word,
         asset_id: AssetId,
         tx_pointer: TxPointer,
-        witness_index: u16,
+        witness_index: WitnessIndex,
     ) {
         let owner = Input::owner(owner);
 
@@ -530,7 +539,7 @@ pub trait Executable: field::Inputs + field::Outputs + field::Witnesses {
         nonce: Nonce,
         amount: Word,
         data: Vec<u8>,
-        witness_index: u16,
+        witness_index: WitnessIndex,
     ) {
         let input = if data.is_empty() {
             Input::message_coin_signed(sender, recipient, amount, nonce, witness_index)
@@ -765,6 +774,26 @@ pub mod field {
         }
     }
 
+    pub trait Expiration {
+        fn expiration(&self) -> Option<BlockHeight>;
+        fn set_expiration(&mut self, value: BlockHeight);
+    }
+
+    impl<T: Policies + ?Sized> Expiration for T {
+        #[inline(always)]
+        fn expiration(&self) -> Option<BlockHeight> {
+            self.policies()
+                .get(PolicyType::Expiration)
+                .map(|value| u32::try_from(value).unwrap_or(u32::MAX).into())
+        }
+
+        #[inline(always)]
+        fn set_expiration(&mut self, block_height: BlockHeight) {
+            self.policies_mut()
+                .set(PolicyType::Expiration, Some(*block_height.deref() as u64))
+        }
+    }
+
     pub trait MaxFeeLimit {
         fn max_fee_limit(&self) -> Word;
         fn set_max_fee_limit(&mut self, value: Word);
@@ -10,14 +10,21 @@ pub mod gas;
 
 pub use gas::{
     DependentCost,
+    GasCostValue,
     GasCosts,
     GasCostsValues,
     GasUnit,
+    NewStorageCost,
+};
+#[cfg(feature = "serde")]
+pub use gas::{
+    GasCostChange,
+    GasCostWarning,
 };
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 const MAX_GAS: u64 = 100_000_000;
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 const MAX_SIZE: u64 = 110 * 1024;
 
 /// A versioned set of consensus parameters.
@@ -35,13 +42,13 @@ impl Default for ConsensusParameters {
 }
 
 impl ConsensusParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Constructor for the `ConsensusParameters` with Standard values.
     pub fn standard() -> Self {
         ConsensusParametersV1::standard().into()
     }
 
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Constructor for the `ConsensusParameters` with Standard values around `ChainId`.
     pub fn standard_with_id(chain_id: ChainId) -> Self {
         ConsensusParametersV1::standard_with_id(chain_id).into()
@@ -234,7 +241,7 @@ pub struct ConsensusParametersV1 {
     pub privileged_address: Address,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl ConsensusParametersV1 {
     /// Constructor for the `ConsensusParameters` with Standard values.
     pub fn standard() -> Self {
@@ -277,10 +284,11 @@ impl From<ConsensusParametersV1> for ConsensusParameters {
 )]
 pub enum FeeParameters {
     V1(FeeParametersV1),
+    V2(FeeParametersV2),
 }
 
 impl FeeParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Default fee parameters just for testing.
     pub const DEFAULT: Self = Self::V1(FeeParametersV1::DEFAULT);
 
@@ -291,6 +299,10 @@ impl FeeParameters {
                 params.gas_price_factor = gas_price_factor;
                 Self::V1(params)
             }
+            Self::V2(mut params) => {
+                params.gas_price_factor = gas_price_factor;
+                Self::V2(params)
+            }
         }
     }
 
@@ -300,6 +312,26 @@ impl FeeParameters {
                 params.gas_per_byte = gas_per_byte;
                 Self::V1(params)
             }
+            Self::V2(mut params) => {
+                params.gas_per_byte = gas_per_byte;
+                Self::V2(params)
+            }
+        }
+    }
+
+    /// Replace the DA gas per byte with the given argument. The `V1` parameters are
+    /// upgraded to `V2`.
+    pub const fn with_da_gas_per_byte(self, da_gas_per_byte: u64) -> Self {
+        match self {
+            Self::V1(params) => Self::V2(FeeParametersV2 {
+                gas_price_factor: params.gas_price_factor,
+                gas_per_byte: params.gas_per_byte,
+                da_gas_per_byte,
+            }),
+            Self::V2(mut params) => {
+                params.da_gas_per_byte = da_gas_per_byte;
+                Self::V2(params)
+            }
         }
     }
 }
@@ -309,6 +341,7 @@ impl FeeParameters {
     pub const fn gas_price_factor(&self) -> u64 {
         match self {
             Self::V1(params) => params.gas_price_factor,
+            Self::V2(params) => params.gas_price_factor,
         }
     }
 
@@ -316,6 +349,16 @@ impl FeeParameters {
     pub const fn gas_per_byte(&self) -> u64 {
         match self {
             Self::V1(params) => params.gas_per_byte,
+            Self::V2(params) => params.gas_per_byte,
+        }
+    }
+
+    /// Get the gas per byte posted to the DA layer: the witnesses and the predicates
+    /// with their data. The `V1` parameters price them with the gas per byte.
+    pub const fn da_gas_per_byte(&self) -> u64 {
+        match self {
+            Self::V1(params) => params.gas_per_byte,
+            Self::V2(params) => params.da_gas_per_byte,
         }
     }
 }
@@ -333,6 +376,12 @@ impl From<FeeParametersV1> for FeeParameters {
     }
 }
 
+impl From<FeeParametersV2> for FeeParameters {
+    fn from(params: FeeParametersV2) -> Self {
+        Self::V2(params)
+    }
+}
+
 /// Consensus configurable parameters used for verifying transactions
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
@@ -344,7 +393,7 @@ pub struct FeeParametersV1 {
     pub gas_per_byte: u64,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl FeeParametersV1 {
     /// Default fee parameters just for tests.
     pub const DEFAULT: Self = FeeParametersV1 {
@@ -360,6 +409,21 @@ impl Default for FeeParametersV1 {
     }
 }
 
+/// Consensus configurable parameters used for verifying transactions, with the separate
+/// price of the bytes posted to the DA layer.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct FeeParametersV2 {
+    /// Factor to convert between gas and transaction assets value.
+    pub gas_price_factor: u64,
+    /// A fixed ratio linking metered bytes to gas price
+    pub gas_per_byte: u64,
+    /// A fixed ratio linking the witness and predicate bytes to gas price. These bytes
+    /// are charged with it instead of the `gas_per_byte`.
+    pub da_gas_per_byte: u64,
+}
+
 /// Versioned predicate parameters.
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
@@ -369,7 +433,7 @@ pub enum PredicateParameters {
 }
 
 impl PredicateParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self::V1(PredicateParametersV1::DEFAULT);
 
@@ -478,7 +542,7 @@ pub struct PredicateParametersV1 {
     pub max_gas_per_predicate: u64,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl PredicateParametersV1 {
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self {
@@ -506,7 +570,7 @@ pub enum TxParameters {
 }
 
 impl TxParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self::V1(TxParametersV1::DEFAULT);
 
@@ -667,7 +731,7 @@ pub struct TxParametersV1 {
     pub max_bytecode_subsections: u16,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl TxParametersV1 {
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self {
@@ -696,7 +760,7 @@ pub enum ScriptParameters {
 }
 
 impl ScriptParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self::V1(ScriptParametersV1::DEFAULT);
 
@@ -760,7 +824,7 @@ pub struct ScriptParametersV1 {
     pub max_script_data_length: u64,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl ScriptParametersV1 {
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self {
@@ -785,7 +849,7 @@ pub enum ContractParameters {
 }
 
 impl ContractParameters {
-    #[cfg(feature = "test-helpers")]
+    #[cfg(feature = "builder")]
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self::V1(ContractParametersV1::DEFAULT);
 
@@ -850,7 +914,7 @@ pub struct ContractParametersV1 {
     pub max_storage_slots: u64,
 }
 
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 impl ContractParametersV1 {
     /// Default parameters just for testing.
     pub const DEFAULT: Self = Self {
@@ -1,10 +1,14 @@
 //! Tools for gas instrumentalization
 
-use core::ops::Deref;
+use core::{
+    fmt,
+    ops::Deref,
+};
 
 #[cfg(feature = "alloc")]
 use alloc::sync::Arc;
 
+use fuel_asm::Opcode;
 use fuel_types::Word;
 
 /// Default gas costs are generated from the
@@ -15,6 +19,15 @@ use fuel_types::Word;
 /// to generate the costs.
 #[allow(dead_code)]
 mod default_gas_costs;
+#[cfg(feature = "serde")]
+mod validation;
+
+#[cfg(feature = "serde")]
+pub use validation::{
+    opcode_cost_key,
+    GasCostChange,
+    GasCostWarning,
+};
 
 /// Gas unit cost that embeds a unit price and operations count.
 ///
@@ -144,6 +157,41 @@ impl GasUnit {
     }
 }
 
+/// The cost of an operation, fixed or dependent on the number of units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GasCostValue {
+    /// The cost of a fixed-cost operation.
+    Fixed(Word),
+    /// The cost of an operation depending on the number of units.
+    Dependent(DependentCost),
+}
+
+impl GasCostValue {
+    /// The gas charged for `units` units, the fixed cost whatever the units, or
+    /// [`DependentCost::resolve`].
+    pub fn resolve(&self, units: Word) -> Word {
+        match self {
+            Self::Fixed(cost) => *cost,
+            Self::Dependent(cost) => cost.resolve(units),
+        }
+    }
+}
+
+impl fmt::Display for GasCostValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(cost) => write!(f, "{cost}"),
+            Self::Dependent(DependentCost::LightOperation {
+                base,
+                units_per_gas,
+            }) => write!(f, "{base} + 1 per {units_per_gas} units"),
+            Self::Dependent(DependentCost::HeavyOperation { base, gas_per_unit }) => {
+                write!(f, "{base} + {gas_per_unit} per unit")
+            }
+        }
+    }
+}
+
 /// Gas costings for every op.
 /// The inner values are wrapped in an [`Arc`]
 /// so this is cheap to clone.
@@ -179,6 +227,35 @@ impl GasCosts {
     pub fn new(costs: GasCostsValues) -> Self {
         Self(Arc::new(costs))
     }
+
+    /// The gas the interpreter charges for executing the `opcode` on `units` units,
+    /// `None` if the opcode has no cost. `ECAL` is charged its base cost, the handler
+    /// of the VM charging the rest.
+    ///
+    /// The units are the bytes processed by the instruction, like the length copied by
+    /// `MCP` or hashed by `S256`, and are ignored by the fixed-cost opcodes. A
+    /// [`DependentCost::LightOperation`] charges `base + units / units_per_gas`, the
+    /// division rounded down, and a [`DependentCost::HeavyOperation`] charges
+    /// `base + units * gas_per_unit`, the product and the sum saturating at
+    /// [`Word::MAX`].
+    ///
+    /// The opcodes loading a contract, `CALL`, `CCP`, `CROO`, `CSIZ` and `LDC`, charge
+    /// the base before and the units after loading the contract, the units being the
+    /// bytes of the contract.
+    pub fn charge_for(&self, opcode: Opcode, units: Word) -> Option<Word> {
+        self.cost_of(opcode).map(|cost| cost.resolve(units))
+    }
+
+    /// The gas costs with the base cost of `ECAL` replaced by `cost`. The `V1` costs
+    /// are upgraded to `V2`.
+    pub fn with_ecal(&self, cost: Word) -> Self {
+        let mut values = match self.0.as_ref() {
+            GasCostsValues::V1(v1) => GasCostsValuesV2::from(v1.clone()),
+            GasCostsValues::V2(v2) => v2.clone(),
+        };
+        values.ecal = cost;
+        Self::new(values.into())
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -196,11 +273,133 @@ impl Default for GasCostsValues {
     }
 }
 
+impl GasCostsValues {
+    /// The cost charged for the `opcode`, `None` if it has no cost. `CFE` and `CFS` are
+    /// charged the costs of `CFEI` and `CFSI`.
+    pub fn cost_of(&self, opcode: Opcode) -> Option<GasCostValue> {
+        let cost = match opcode {
+            Opcode::ADD => GasCostValue::Fixed(self.add()),
+            Opcode::AND => GasCostValue::Fixed(self.and()),
+            Opcode::DIV => GasCostValue::Fixed(self.div()),
+            Opcode::EQ => GasCostValue::Fixed(self.eq_()),
+            Opcode::EXP => GasCostValue::Fixed(self.exp()),
+            Opcode::GT => GasCostValue::Fixed(self.gt()),
+            Opcode::LT => GasCostValue::Fixed(self.lt()),
+            Opcode::MLOG => GasCostValue::Fixed(self.mlog()),
+            Opcode::MROO => GasCostValue::Fixed(self.mroo()),
+            Opcode::MOD => GasCostValue::Fixed(self.mod_op()),
+            Opcode::MOVE => GasCostValue::Fixed(self.move_op()),
+            Opcode::MUL => GasCostValue::Fixed(self.mul()),
+            Opcode::NOT => GasCostValue::Fixed(self.not()),
+            Opcode::OR => GasCostValue::Fixed(self.or()),
+            Opcode::SLL => GasCostValue::Fixed(self.sll()),
+            Opcode::SRL => GasCostValue::Fixed(self.srl()),
+            Opcode::SUB => GasCostValue::Fixed(self.sub()),
+            Opcode::XOR => GasCostValue::Fixed(self.xor()),
+            Opcode::MLDV => GasCostValue::Fixed(self.mldv()),
+            Opcode::RET => GasCostValue::Fixed(self.ret()),
+            Opcode::RETD => GasCostValue::Dependent(self.retd()),
+            Opcode::ALOC => GasCostValue::Fixed(self.aloc()),
+            Opcode::MCL => GasCostValue::Dependent(self.mcl()),
+            Opcode::MCP => GasCostValue::Dependent(self.mcp()),
+            Opcode::MEQ => GasCostValue::Dependent(self.meq()),
+            Opcode::BHSH => GasCostValue::Fixed(self.bhsh()),
+            Opcode::BHEI => GasCostValue::Fixed(self.bhei()),
+            Opcode::BURN => GasCostValue::Fixed(self.burn()),
+            Opcode::CALL => GasCostValue::Dependent(self.call()),
+            Opcode::CCP => GasCostValue::Dependent(self.ccp()),
+            Opcode::CROO => GasCostValue::Dependent(self.croo()),
+            Opcode::CSIZ => GasCostValue::Dependent(self.csiz()),
+            Opcode::CB => GasCostValue::Fixed(self.cb()),
+            Opcode::LDC => GasCostValue::Dependent(self.ldc()),
+            Opcode::LOG => GasCostValue::Fixed(self.log()),
+            Opcode::LOGD => GasCostValue::Dependent(self.logd()),
+            Opcode::MINT => GasCostValue::Fixed(self.mint()),
+            Opcode::RVRT => GasCostValue::Fixed(self.rvrt()),
+            Opcode::SCWQ => GasCostValue::Dependent(self.scwq()),
+            Opcode::SRW => GasCostValue::Fixed(self.srw()),
+            Opcode::SRWQ => GasCostValue::Dependent(self.srwq()),
+            Opcode::SWW => GasCostValue::Fixed(self.sww()),
+            Opcode::SWWQ => GasCostValue::Dependent(self.swwq()),
+            Opcode::TR => GasCostValue::Fixed(self.tr()),
+            Opcode::TRO => GasCostValue::Fixed(self.tro()),
+            Opcode::ECK1 => GasCostValue::Fixed(self.eck1()),
+            Opcode::ECR1 => GasCostValue::Fixed(self.ecr1()),
+            Opcode::ED19 => GasCostValue::Fixed(self.ed19()),
+            Opcode::K256 => GasCostValue::Dependent(self.k256()),
+            Opcode::S256 => GasCostValue::Dependent(self.s256()),
+            Opcode::TIME => GasCostValue::Fixed(self.time()),
+            Opcode::NOOP => GasCostValue::Fixed(self.noop()),
+            Opcode::FLAG => GasCostValue::Fixed(self.flag()),
+            Opcode::BAL => GasCostValue::Fixed(self.bal()),
+            Opcode::JMP => GasCostValue::Fixed(self.jmp()),
+            Opcode::JNE => GasCostValue::Fixed(self.jne()),
+            Opcode::SMO => GasCostValue::Dependent(self.smo()),
+            Opcode::ADDI => GasCostValue::Fixed(self.addi()),
+            Opcode::ANDI => GasCostValue::Fixed(self.andi()),
+            Opcode::DIVI => GasCostValue::Fixed(self.divi()),
+            Opcode::EXPI => GasCostValue::Fixed(self.expi()),
+            Opcode::MODI => GasCostValue::Fixed(self.modi()),
+            Opcode::MULI => GasCostValue::Fixed(self.muli()),
+            Opcode::ORI => GasCostValue::Fixed(self.ori()),
+            Opcode::SLLI => GasCostValue::Fixed(self.slli()),
+            Opcode::SRLI => GasCostValue::Fixed(self.srli()),
+            Opcode::SUBI => GasCostValue::Fixed(self.subi()),
+            Opcode::XORI => GasCostValue::Fixed(self.xori()),
+            Opcode::JNEI => GasCostValue::Fixed(self.jnei()),
+            Opcode::LB => GasCostValue::Fixed(self.lb()),
+            Opcode::LW => GasCostValue::Fixed(self.lw()),
+            Opcode::SB => GasCostValue::Fixed(self.sb()),
+            Opcode::SW => GasCostValue::Fixed(self.sw()),
+            Opcode::MCPI => GasCostValue::Dependent(self.mcpi()),
+            Opcode::GTF => GasCostValue::Fixed(self.gtf()),
+            Opcode::MCLI => GasCostValue::Dependent(self.mcli()),
+            Opcode::GM => GasCostValue::Fixed(self.gm()),
+            Opcode::MOVI => GasCostValue::Fixed(self.movi()),
+            Opcode::JNZI => GasCostValue::Fixed(self.jnzi()),
+            Opcode::JMPF => GasCostValue::Fixed(self.jmpf()),
+            Opcode::JMPB => GasCostValue::Fixed(self.jmpb()),
+            Opcode::JNZF => GasCostValue::Fixed(self.jnzf()),
+            Opcode::JNZB => GasCostValue::Fixed(self.jnzb()),
+            Opcode::JNEF => GasCostValue::Fixed(self.jnef()),
+            Opcode::JNEB => GasCostValue::Fixed(self.jneb()),
+            Opcode::JI => GasCostValue::Fixed(self.ji()),
+            Opcode::CFEI => GasCostValue::Fixed(self.cfei()),
+            Opcode::CFSI => GasCostValue::Fixed(self.cfsi()),
+            Opcode::CFE => GasCostValue::Fixed(self.cfei()),
+            Opcode::CFS => GasCostValue::Fixed(self.cfsi()),
+            Opcode::PSHL => GasCostValue::Fixed(self.pshl()),
+            Opcode::PSHH => GasCostValue::Fixed(self.pshh()),
+            Opcode::POPL => GasCostValue::Fixed(self.popl()),
+            Opcode::POPH => GasCostValue::Fixed(self.poph()),
+            Opcode::WDCM => GasCostValue::Fixed(self.wdcm()),
+            Opcode::WQCM => GasCostValue::Fixed(self.wqcm()),
+            Opcode::WDOP => GasCostValue::Fixed(self.wdop()),
+            Opcode::WQOP => GasCostValue::Fixed(self.wqop()),
+            Opcode::WDML => GasCostValue::Fixed(self.wdml()),
+            Opcode::WQML => GasCostValue::Fixed(self.wqml()),
+            Opcode::WDDV => GasCostValue::Fixed(self.wddv()),
+            Opcode::WQDV => GasCostValue::Fixed(self.wqdv()),
+            Opcode::WDMD => GasCostValue::Fixed(self.wdmd()),
+            Opcode::WQMD => GasCostValue::Fixed(self.wqmd()),
+            Opcode::WDAM => GasCostValue::Fixed(self.wdam()),
+            Opcode::WQAM => GasCostValue::Fixed(self.wqam()),
+            Opcode::WDMM => GasCostValue::Fixed(self.wdmm()),
+            Opcode::WQMM => GasCostValue::Fixed(self.wqmm()),
+            Opcode::ECAL => GasCostValue::Fixed(self.ecal()),
+        };
+        Some(cost)
+    }
+}
+
 /// The versioned gas costs for every op.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum GasCostsValues {
     /// Version 1 of the gas costs.
     V1(GasCostsValuesV1),
+    /// Version 2 of the gas costs, with the base cost of `ECAL` and the cost of the new
+    /// storage slots.
+    V2(GasCostsValuesV2),
 }
 
 #[allow(missing_docs)]
@@ -208,660 +407,794 @@ impl GasCostsValues {
     pub fn add(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.add,
+            GasCostsValues::V2(v2) => v2.add,
         }
     }
 
     pub fn addi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.addi,
+            GasCostsValues::V2(v2) => v2.addi,
         }
     }
 
     pub fn aloc(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.aloc,
+            GasCostsValues::V2(v2) => v2.aloc,
         }
     }
 
     pub fn and(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.and,
+            GasCostsValues::V2(v2) => v2.and,
         }
     }
 
     pub fn andi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.andi,
+            GasCostsValues::V2(v2) => v2.andi,
         }
     }
 
     pub fn bal(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.bal,
+            GasCostsValues::V2(v2) => v2.bal,
         }
     }
 
     pub fn bhei(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.bhei,
+            GasCostsValues::V2(v2) => v2.bhei,
         }
     }
 
     pub fn bhsh(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.bhsh,
+            GasCostsValues::V2(v2) => v2.bhsh,
         }
     }
 
     pub fn burn(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.burn,
+            GasCostsValues::V2(v2) => v2.burn,
         }
     }
 
     pub fn cb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.cb,
+            GasCostsValues::V2(v2) => v2.cb,
         }
     }
 
     pub fn cfei(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.cfei,
+            GasCostsValues::V2(v2) => v2.cfei,
         }
     }
 
     pub fn cfsi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.cfsi,
+            GasCostsValues::V2(v2) => v2.cfsi,
         }
     }
 
     pub fn div(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.div,
+            GasCostsValues::V2(v2) => v2.div,
         }
     }
 
     pub fn divi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.divi,
+            GasCostsValues::V2(v2) => v2.divi,
+        }
+    }
+
+    /// The base cost of `ECAL`. The `V1` costs don't charge it.
+    pub fn ecal(&self) -> Word {
+        match self {
+            GasCostsValues::V1(_) => 0,
+            GasCostsValues::V2(v2) => v2.ecal,
         }
     }
 
     pub fn eck1(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.eck1,
+            GasCostsValues::V2(v2) => v2.eck1,
         }
     }
 
     pub fn ecr1(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.ecr1,
+            GasCostsValues::V2(v2) => v2.ecr1,
         }
     }
 
     pub fn ed19(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.ed19,
+            GasCostsValues::V2(v2) => v2.ed19,
         }
     }
 
     pub fn eq_(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.eq,
+            GasCostsValues::V2(v2) => v2.eq,
         }
     }
 
     pub fn exp(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.exp,
+            GasCostsValues::V2(v2) => v2.exp,
         }
     }
 
     pub fn expi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.expi,
+            GasCostsValues::V2(v2) => v2.expi,
         }
     }
 
     pub fn flag(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.flag,
+            GasCostsValues::V2(v2) => v2.flag,
         }
     }
 
     pub fn gm(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.gm,
+            GasCostsValues::V2(v2) => v2.gm,
         }
     }
 
     pub fn gt(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.gt,
+            GasCostsValues::V2(v2) => v2.gt,
         }
     }
 
     pub fn gtf(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.gtf,
+            GasCostsValues::V2(v2) => v2.gtf,
         }
     }
 
     pub fn ji(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.ji,
+            GasCostsValues::V2(v2) => v2.ji,
         }
     }
 
     pub fn jmp(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jmp,
+            GasCostsValues::V2(v2) => v2.jmp,
         }
     }
 
     pub fn jne(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jne,
+            GasCostsValues::V2(v2) => v2.jne,
         }
     }
 
     pub fn jnei(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jnei,
+            GasCostsValues::V2(v2) => v2.jnei,
         }
     }
 
     pub fn jnzi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jnzi,
+            GasCostsValues::V2(v2) => v2.jnzi,
         }
     }
 
     pub fn jmpf(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jmpf,
+            GasCostsValues::V2(v2) => v2.jmpf,
         }
     }
 
     pub fn jmpb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jmpb,
+            GasCostsValues::V2(v2) => v2.jmpb,
         }
     }
 
     pub fn jnzf(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jnzf,
+            GasCostsValues::V2(v2) => v2.jnzf,
         }
     }
 
     pub fn jnzb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jnzb,
+            GasCostsValues::V2(v2) => v2.jnzb,
         }
     }
 
     pub fn jnef(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jnef,
+            GasCostsValues::V2(v2) => v2.jnef,
         }
     }
 
     pub fn jneb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.jneb,
+            GasCostsValues::V2(v2) => v2.jneb,
         }
     }
 
     pub fn lb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.lb,
+            GasCostsValues::V2(v2) => v2.lb,
         }
     }
 
     pub fn log(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.log,
+            GasCostsValues::V2(v2) => v2.log,
         }
     }
 
     pub fn lt(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.lt,
+            GasCostsValues::V2(v2) => v2.lt,
         }
     }
 
     pub fn lw(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.lw,
+            GasCostsValues::V2(v2) => v2.lw,
         }
     }
 
     pub fn mint(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mint,
+            GasCostsValues::V2(v2) => v2.mint,
         }
     }
 
     pub fn mlog(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mlog,
+            GasCostsValues::V2(v2) => v2.mlog,
         }
     }
 
     pub fn mod_op(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mod_op,
+            GasCostsValues::V2(v2) => v2.mod_op,
         }
     }
 
     pub fn modi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.modi,
+            GasCostsValues::V2(v2) => v2.modi,
         }
     }
 
     pub fn move_op(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.move_op,
+            GasCostsValues::V2(v2) => v2.move_op,
         }
     }
 
     pub fn movi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.movi,
+            GasCostsValues::V2(v2) => v2.movi,
         }
     }
 
     pub fn mroo(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mroo,
+            GasCostsValues::V2(v2) => v2.mroo,
         }
     }
 
     pub fn mul(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mul,
+            GasCostsValues::V2(v2) => v2.mul,
         }
     }
 
     pub fn muli(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.muli,
+            GasCostsValues::V2(v2) => v2.muli,
         }
     }
 
     pub fn mldv(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.mldv,
+            GasCostsValues::V2(v2) => v2.mldv,
         }
     }
 
     pub fn noop(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.noop,
+            GasCostsValues::V2(v2) => v2.noop,
         }
     }
 
     pub fn not(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.not,
+            GasCostsValues::V2(v2) => v2.not,
         }
     }
 
     pub fn or(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.or,
+            GasCostsValues::V2(v2) => v2.or,
         }
     }
 
     pub fn ori(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.ori,
+            GasCostsValues::V2(v2) => v2.ori,
         }
     }
 
     pub fn poph(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.poph,
+            GasCostsValues::V2(v2) => v2.poph,
         }
     }
 
     pub fn popl(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.popl,
+            GasCostsValues::V2(v2) => v2.popl,
         }
     }
 
     pub fn pshh(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.pshh,
+            GasCostsValues::V2(v2) => v2.pshh,
         }
     }
 
     pub fn pshl(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.pshl,
+            GasCostsValues::V2(v2) => v2.pshl,
         }
     }
 
     pub fn ret(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.ret,
+            GasCostsValues::V2(v2) => v2.ret,
         }
     }
 
     pub fn rvrt(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.rvrt,
+            GasCostsValues::V2(v2) => v2.rvrt,
         }
     }
 
     pub fn sb(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.sb,
+            GasCostsValues::V2(v2) => v2.sb,
         }
     }
 
     pub fn sll(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.sll,
+            GasCostsValues::V2(v2) => v2.sll,
         }
     }
 
     pub fn slli(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.slli,
+            GasCostsValues::V2(v2) => v2.slli,
         }
     }
 
     pub fn srl(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.srl,
+            GasCostsValues::V2(v2) => v2.srl,
         }
     }
 
     pub fn srli(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.srli,
+            GasCostsValues::V2(v2) => v2.srli,
         }
     }
 
     pub fn srw(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.srw,
+            GasCostsValues::V2(v2) => v2.srw,
         }
     }
 
     pub fn sub(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.sub,
+            GasCostsValues::V2(v2) => v2.sub,
         }
     }
 
     pub fn subi(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.subi,
+            GasCostsValues::V2(v2) => v2.subi,
         }
     }
 
     pub fn sw(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.sw,
+            GasCostsValues::V2(v2) => v2.sw,
         }
     }
 
     pub fn sww(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.sww,
+            GasCostsValues::V2(v2) => v2.sww,
         }
     }
 
     pub fn time(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.time,
+            GasCostsValues::V2(v2) => v2.time,
         }
     }
 
     pub fn tr(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.tr,
+            GasCostsValues::V2(v2) => v2.tr,
         }
     }
 
     pub fn tro(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.tro,
+            GasCostsValues::V2(v2) => v2.tro,
         }
     }
 
     pub fn wdcm(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdcm,
+            GasCostsValues::V2(v2) => v2.wdcm,
         }
     }
 
     pub fn wqcm(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqcm,
+            GasCostsValues::V2(v2) => v2.wqcm,
         }
     }
 
     pub fn wdop(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdop,
+            GasCostsValues::V2(v2) => v2.wdop,
         }
     }
 
     pub fn wqop(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqop,
+            GasCostsValues::V2(v2) => v2.wqop,
         }
     }
 
     pub fn wdml(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdml,
+            GasCostsValues::V2(v2) => v2.wdml,
         }
     }
 
     pub fn wqml(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqml,
+            GasCostsValues::V2(v2) => v2.wqml,
         }
     }
 
     pub fn wddv(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wddv,
+            GasCostsValues::V2(v2) => v2.wddv,
         }
     }
 
     pub fn wqdv(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqdv,
+            GasCostsValues::V2(v2) => v2.wqdv,
         }
     }
 
     pub fn wdmd(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdmd,
+            GasCostsValues::V2(v2) => v2.wdmd,
         }
     }
 
     pub fn wqmd(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqmd,
+            GasCostsValues::V2(v2) => v2.wqmd,
         }
     }
 
     pub fn wdam(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdam,
+            GasCostsValues::V2(v2) => v2.wdam,
         }
     }
 
     pub fn wqam(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqam,
+            GasCostsValues::V2(v2) => v2.wqam,
         }
     }
 
     pub fn wdmm(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wdmm,
+            GasCostsValues::V2(v2) => v2.wdmm,
         }
     }
 
     pub fn wqmm(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.wqmm,
+            GasCostsValues::V2(v2) => v2.wqmm,
         }
     }
 
     pub fn xor(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.xor,
+            GasCostsValues::V2(v2) => v2.xor,
         }
     }
 
     pub fn xori(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.xori,
+            GasCostsValues::V2(v2) => v2.xori,
         }
     }
 
     pub fn call(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.call,
+            GasCostsValues::V2(v2) => v2.call,
         }
     }
 
     pub fn ccp(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.ccp,
+            GasCostsValues::V2(v2) => v2.ccp,
         }
     }
 
     pub fn croo(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.croo,
+            GasCostsValues::V2(v2) => v2.croo,
         }
     }
 
     pub fn csiz(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.csiz,
+            GasCostsValues::V2(v2) => v2.csiz,
         }
     }
 
     pub fn k256(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.k256,
+            GasCostsValues::V2(v2) => v2.k256,
         }
     }
 
     pub fn ldc(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.ldc,
+            GasCostsValues::V2(v2) => v2.ldc,
         }
     }
 
     pub fn logd(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.logd,
+            GasCostsValues::V2(v2) => v2.logd,
         }
     }
 
     pub fn mcl(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.mcl,
+            GasCostsValues::V2(v2) => v2.mcl,
         }
     }
 
     pub fn mcli(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.mcli,
+            GasCostsValues::V2(v2) => v2.mcli,
         }
     }
 
     pub fn mcp(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.mcp,
+            GasCostsValues::V2(v2) => v2.mcp,
         }
     }
 
     pub fn mcpi(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.mcpi,
+            GasCostsValues::V2(v2) => v2.mcpi,
         }
     }
 
     pub fn meq(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.meq,
+            GasCostsValues::V2(v2) => v2.meq,
         }
     }
 
     pub fn retd(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.retd,
+            GasCostsValues::V2(v2) => v2.retd,
         }
     }
 
     pub fn s256(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.s256,
+            GasCostsValues::V2(v2) => v2.s256,
         }
     }
 
     pub fn scwq(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.scwq,
+            GasCostsValues::V2(v2) => v2.scwq,
         }
     }
 
     pub fn smo(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.smo,
+            GasCostsValues::V2(v2) => v2.smo,
         }
     }
 
     pub fn srwq(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.srwq,
+            GasCostsValues::V2(v2) => v2.srwq,
         }
     }
 
     pub fn swwq(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.swwq,
+            GasCostsValues::V2(v2) => v2.swwq,
         }
     }
 
     pub fn contract_root(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.contract_root,
+            GasCostsValues::V2(v2) => v2.contract_root,
         }
     }
 
     pub fn state_root(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.state_root,
+            GasCostsValues::V2(v2) => v2.state_root,
         }
     }
 
     pub fn new_storage_per_byte(&self) -> Word {
         match self {
             GasCostsValues::V1(v1) => v1.new_storage_per_byte,
+            GasCostsValues::V2(v2) => v2.new_storage_per_byte,
+        }
+    }
+
+    /// The cost of each new storage slot. The `V1` costs don't charge it.
+    pub fn new_storage_per_slot(&self) -> Word {
+        match self {
+            GasCostsValues::V1(_) => 0,
+            GasCostsValues::V2(v2) => v2.new_storage_per_slot,
+        }
+    }
+
+    /// The cost of the storage created by a transaction.
+    pub fn new_storage(&self) -> NewStorageCost {
+        NewStorageCost {
+            per_slot: self.new_storage_per_slot(),
+            per_byte: self.new_storage_per_byte(),
         }
     }
 
     pub fn vm_initialization(&self) -> DependentCost {
         match self {
             GasCostsValues::V1(v1) => v1.vm_initialization,
+            GasCostsValues::V2(v2) => v2.vm_initialization,
         }
     }
 }
@@ -992,6 +1325,177 @@ pub struct GasCostsValuesV1 {
     pub vm_initialization: DependentCost,
 }
 
+/// Gas costs for every op, with the base cost of `ECAL` and the cost of the new storage
+/// slots.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(default = "GasCostsValuesV2::unit")]
+pub struct GasCostsValuesV2 {
+    pub add: Word,
+    pub addi: Word,
+    pub aloc: Word,
+    pub and: Word,
+    pub andi: Word,
+    pub bal: Word,
+    pub bhei: Word,
+    pub bhsh: Word,
+    pub burn: Word,
+    pub cb: Word,
+    pub cfei: Word,
+    pub cfsi: Word,
+    pub div: Word,
+    pub divi: Word,
+    /// The base cost of `ECAL`, before the gas charged by the handler.
+    pub ecal: Word,
+    pub eck1: Word,
+    pub ecr1: Word,
+    pub ed19: Word,
+    pub eq: Word,
+    pub exp: Word,
+    pub expi: Word,
+    pub flag: Word,
+    pub gm: Word,
+    pub gt: Word,
+    pub gtf: Word,
+    pub ji: Word,
+    pub jmp: Word,
+    pub jne: Word,
+    pub jnei: Word,
+    pub jnzi: Word,
+    pub jmpf: Word,
+    pub jmpb: Word,
+    pub jnzf: Word,
+    pub jnzb: Word,
+    pub jnef: Word,
+    pub jneb: Word,
+    pub lb: Word,
+    pub log: Word,
+    pub lt: Word,
+    pub lw: Word,
+    pub mint: Word,
+    pub mlog: Word,
+    #[cfg_attr(feature = "serde", serde(rename = "mod"))]
+    pub mod_op: Word,
+    pub modi: Word,
+    #[cfg_attr(feature = "serde", serde(rename = "move"))]
+    pub move_op: Word,
+    pub movi: Word,
+    pub mroo: Word,
+    pub mul: Word,
+    pub muli: Word,
+    pub mldv: Word,
+    pub noop: Word,
+    pub not: Word,
+    pub or: Word,
+    pub ori: Word,
+    pub poph: Word,
+    pub popl: Word,
+    pub pshh: Word,
+    pub pshl: Word,
+    #[cfg_attr(feature = "serde", serde(rename = "ret_contract"))]
+    pub ret: Word,
+    #[cfg_attr(feature = "serde", serde(rename = "rvrt_contract"))]
+    pub rvrt: Word,
+    pub sb: Word,
+    pub sll: Word,
+    pub slli: Word,
+    pub srl: Word,
+    pub srli: Word,
+    pub srw: Word,
+    pub sub: Word,
+    pub subi: Word,
+    pub sw: Word,
+    pub sww: Word,
+    pub time: Word,
+    pub tr: Word,
+    pub tro: Word,
+    pub wdcm: Word,
+    pub wqcm: Word,
+    pub wdop: Word,
+    pub wqop: Word,
+    pub wdml: Word,
+    pub wqml: Word,
+    pub wddv: Word,
+    pub wqdv: Word,
+    pub wdmd: Word,
+    pub wqmd: Word,
+    pub wdam: Word,
+    pub wqam: Word,
+    pub wdmm: Word,
+    pub wqmm: Word,
+    pub xor: Word,
+    pub xori: Word,
+
+    // Dependent
+    pub call: DependentCost,
+    pub ccp: DependentCost,
+    pub croo: DependentCost,
+    pub csiz: DependentCost,
+    pub k256: DependentCost,
+    pub ldc: DependentCost,
+    pub logd: DependentCost,
+    pub mcl: DependentCost,
+    pub mcli: DependentCost,
+    pub mcp: DependentCost,
+    pub mcpi: DependentCost,
+    pub meq: DependentCost,
+    #[cfg_attr(feature = "serde", serde(rename = "retd_contract"))]
+    pub retd: DependentCost,
+    pub s256: DependentCost,
+    pub scwq: DependentCost,
+    pub smo: DependentCost,
+    pub srwq: DependentCost,
+    pub swwq: DependentCost,
+
+    // Non-opcode costs
+    pub contract_root: DependentCost,
+    pub state_root: DependentCost,
+    pub new_storage_per_byte: Word,
+    /// The cost of each new storage slot, on top of its bytes charged with
+    /// `new_storage_per_byte`.
+    pub new_storage_per_slot: Word,
+    pub vm_initialization: DependentCost,
+}
+
+/// The cost of the storage created by a transaction, charged once for every new entry
+/// of the state: a storage slot written by `SWW` or `SWWQ`, a balance created by
+/// `MINT`, `TR`, `TRO` or `CALL`, a message sent by `SMO`, and an initial storage slot
+/// of a `Create` transaction, charged at check time. `SMO` and `Create` charge it only
+/// if the `per_slot` cost is set, see [`NewStorageCost::if_per_slot_set`].
+///
+/// An entry costs `per_slot` plus `per_byte` for each of its bytes: the key and the
+/// value of a storage slot, the asset id and the amount of a balance, and the data of
+/// a message. Creating the same entries costs the same whichever the path creating
+/// them, the bulk paths included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NewStorageCost {
+    /// The cost of every new entry.
+    pub per_slot: Word,
+    /// The cost of every byte of the new entries.
+    pub per_byte: Word,
+}
+
+impl NewStorageCost {
+    /// The cost of `slots` new entries of `bytes` bytes in total, saturating at
+    /// [`Word::MAX`], as the costs are configurable.
+    pub fn resolve(&self, slots: Word, bytes: Word) -> Word {
+        slots
+            .saturating_mul(self.per_slot)
+            .saturating_add(bytes.saturating_mul(self.per_byte))
+    }
+
+    /// The cost if the `per_slot` cost is set, otherwise free. `SMO` and the initial
+    /// slots of `Create` didn't charge the new storage before the `per_slot` cost, and
+    /// keep their gas under the gas costs without it.
+    pub fn if_per_slot_set(self) -> Self {
+        if self.per_slot > 0 {
+            self
+        } else {
+            Self::default()
+        }
+    }
+}
+
 /// Dependent cost is a cost that depends on the number of units.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
@@ -1035,12 +1539,12 @@ impl GasCosts {
 impl GasCostsValues {
     /// Create costs that are all set to zero.
     pub fn free() -> Self {
-        GasCostsValuesV1::free().into()
+        GasCostsValuesV2::free().into()
     }
 
     /// Create costs that are all set to one.
     pub fn unit() -> Self {
-        GasCostsValuesV1::unit().into()
+        GasCostsValuesV2::unit().into()
     }
 }
 
@@ -1282,6 +1786,369 @@ impl GasCostsValuesV1 {
     }
 }
 
+impl GasCostsValuesV2 {
+    /// Create costs that are all set to zero.
+    pub fn free() -> Self {
+        Self {
+            add: 0,
+            addi: 0,
+            aloc: 0,
+            and: 0,
+            andi: 0,
+            bal: 0,
+            bhei: 0,
+            bhsh: 0,
+            burn: 0,
+            cb: 0,
+            cfei: 0,
+            cfsi: 0,
+            div: 0,
+            divi: 0,
+            ecal: 0,
+            eck1: 0,
+            ecr1: 0,
+            ed19: 0,
+            eq: 0,
+            exp: 0,
+            expi: 0,
+            flag: 0,
+            gm: 0,
+            gt: 0,
+            gtf: 0,
+            ji: 0,
+            jmp: 0,
+            jne: 0,
+            jnei: 0,
+            jnzi: 0,
+            jmpf: 0,
+            jmpb: 0,
+            jnzf: 0,
+            jnzb: 0,
+            jnef: 0,
+            jneb: 0,
+            lb: 0,
+            log: 0,
+            lt: 0,
+            lw: 0,
+            mint: 0,
+            mlog: 0,
+            mod_op: 0,
+            modi: 0,
+            move_op: 0,
+            movi: 0,
+            mroo: 0,
+            mul: 0,
+            muli: 0,
+            mldv: 0,
+            noop: 0,
+            not: 0,
+            or: 0,
+            ori: 0,
+            poph: 0,
+            popl: 0,
+            pshh: 0,
+            pshl: 0,
+            ret: 0,
+            rvrt: 0,
+            sb: 0,
+            sll: 0,
+            slli: 0,
+            srl: 0,
+            srli: 0,
+            srw: 0,
+            sub: 0,
+            subi: 0,
+            sw: 0,
+            sww: 0,
+            time: 0,
+            tr: 0,
+            tro: 0,
+            wdcm: 0,
+            wqcm: 0,
+            wdop: 0,
+            wqop: 0,
+            wdml: 0,
+            wqml: 0,
+            wddv: 0,
+            wqdv: 0,
+            wdmd: 0,
+            wqmd: 0,
+            wdam: 0,
+            wqam: 0,
+            wdmm: 0,
+            wqmm: 0,
+            xor: 0,
+            xori: 0,
+            call: DependentCost::free(),
+            ccp: DependentCost::free(),
+            croo: DependentCost::free(),
+            csiz: DependentCost::free(),
+            k256: DependentCost::free(),
+            ldc: DependentCost::free(),
+            logd: DependentCost::free(),
+            mcl: DependentCost::free(),
+            mcli: DependentCost::free(),
+            mcp: DependentCost::free(),
+            mcpi: DependentCost::free(),
+            meq: DependentCost::free(),
+            retd: DependentCost::free(),
+            s256: DependentCost::free(),
+            scwq: DependentCost::free(),
+            smo: DependentCost::free(),
+            srwq: DependentCost::free(),
+            swwq: DependentCost::free(),
+
+            // Non-opcode costs
+            contract_root: DependentCost::free(),
+            state_root: DependentCost::free(),
+            new_storage_per_byte: 0,
+            new_storage_per_slot: 0,
+            vm_initialization: DependentCost::free(),
+        }
+    }
+
+    /// Create costs that are all set to one.
+    pub fn unit() -> Self {
+        Self {
+            add: 1,
+            addi: 1,
+            aloc: 1,
+            and: 1,
+            andi: 1,
+            bal: 1,
+            bhei: 1,
+            bhsh: 1,
+            burn: 1,
+            cb: 1,
+            cfei: 1,
+            cfsi: 1,
+            div: 1,
+            divi: 1,
+            ecal: 1,
+            eck1: 1,
+            ecr1: 1,
+            ed19: 1,
+            eq: 1,
+            exp: 1,
+            expi: 1,
+            flag: 1,
+            gm: 1,
+            gt: 1,
+            gtf: 1,
+            ji: 1,
+            jmp: 1,
+            jne: 1,
+            jnei: 1,
+            jnzi: 1,
+            jmpf: 1,
+            jmpb: 1,
+            jnzf: 1,
+            jnzb: 1,
+            jnef: 1,
+            jneb: 1,
+            lb: 1,
+            log: 1,
+            lt: 1,
+            lw: 1,
+            mint: 1,
+            mlog: 1,
+            mod_op: 1,
+            modi: 1,
+            move_op: 1,
+            movi: 1,
+            mroo: 1,
+            mul: 1,
+            muli: 1,
+            mldv: 1,
+            noop: 1,
+            not: 1,
+            or: 1,
+            ori: 1,
+            ret: 1,
+            poph: 1,
+            popl: 1,
+            pshh: 1,
+            pshl: 1,
+            rvrt: 1,
+            sb: 1,
+            sll: 1,
+            slli: 1,
+            srl: 1,
+            srli: 1,
+            srw: 1,
+            sub: 1,
+            subi: 1,
+            sw: 1,
+            sww: 1,
+            time: 1,
+            tr: 1,
+            tro: 1,
+            wdcm: 1,
+            wqcm: 1,
+            wdop: 1,
+            wqop: 1,
+            wdml: 1,
+            wqml: 1,
+            wddv: 1,
+            wqdv: 1,
+            wdmd: 1,
+            wqmd: 1,
+            wdam: 1,
+            wqam: 1,
+            wdmm: 1,
+            wqmm: 1,
+            xor: 1,
+            xori: 1,
+            call: DependentCost::unit(),
+            ccp: DependentCost::unit(),
+            croo: DependentCost::unit(),
+            csiz: DependentCost::unit(),
+            k256: DependentCost::unit(),
+            ldc: DependentCost::unit(),
+            logd: DependentCost::unit(),
+            mcl: DependentCost::unit(),
+            mcli: DependentCost::unit(),
+            mcp: DependentCost::unit(),
+            mcpi: DependentCost::unit(),
+            meq: DependentCost::unit(),
+            retd: DependentCost::unit(),
+            s256: DependentCost::unit(),
+            scwq: DependentCost::unit(),
+            smo: DependentCost::unit(),
+            srwq: DependentCost::unit(),
+            swwq: DependentCost::unit(),
+
+            // Non-opcode costs
+            contract_root: DependentCost::unit(),
+            state_root: DependentCost::unit(),
+            new_storage_per_byte: 1,
+            new_storage_per_slot: 1,
+            vm_initialization: DependentCost::unit(),
+        }
+    }
+}
+
+impl From<GasCostsValuesV1> for GasCostsValuesV2 {
+    /// The `V1` costs, without a base cost of `ECAL` nor a cost of the new storage
+    /// slots.
+    fn from(v1: GasCostsValuesV1) -> Self {
+        Self {
+            add: v1.add,
+            addi: v1.addi,
+            aloc: v1.aloc,
+            and: v1.and,
+            andi: v1.andi,
+            bal: v1.bal,
+            bhei: v1.bhei,
+            bhsh: v1.bhsh,
+            burn: v1.burn,
+            cb: v1.cb,
+            cfei: v1.cfei,
+            cfsi: v1.cfsi,
+            div: v1.div,
+            divi: v1.divi,
+            ecal: 0,
+            eck1: v1.eck1,
+            ecr1: v1.ecr1,
+            ed19: v1.ed19,
+            eq: v1.eq,
+            exp: v1.exp,
+            expi: v1.expi,
+            flag: v1.flag,
+            gm: v1.gm,
+            gt: v1.gt,
+            gtf: v1.gtf,
+            ji: v1.ji,
+            jmp: v1.jmp,
+            jne: v1.jne,
+            jnei: v1.jnei,
+            jnzi: v1.jnzi,
+            jmpf: v1.jmpf,
+            jmpb: v1.jmpb,
+            jnzf: v1.jnzf,
+            jnzb: v1.jnzb,
+            jnef: v1.jnef,
+            jneb: v1.jneb,
+            lb: v1.lb,
+            log: v1.log,
+            lt: v1.lt,
+            lw: v1.lw,
+            mint: v1.mint,
+            mlog: v1.mlog,
+            mod_op: v1.mod_op,
+            modi: v1.modi,
+            move_op: v1.move_op,
+            movi: v1.movi,
+            mroo: v1.mroo,
+            mul: v1.mul,
+            muli: v1.muli,
+            mldv: v1.mldv,
+            noop: v1.noop,
+            not: v1.not,
+            or: v1.or,
+            ori: v1.ori,
+            poph: v1.poph,
+            popl: v1.popl,
+            pshh: v1.pshh,
+            pshl: v1.pshl,
+            ret: v1.ret,
+            rvrt: v1.rvrt,
+            sb: v1.sb,
+            sll: v1.sll,
+            slli: v1.slli,
+            srl: v1.srl,
+            srli: v1.srli,
+            srw: v1.srw,
+            sub: v1.sub,
+            subi: v1.subi,
+            sw: v1.sw,
+            sww: v1.sww,
+            time: v1.time,
+            tr: v1.tr,
+            tro: v1.tro,
+            wdcm: v1.wdcm,
+            wqcm: v1.wqcm,
+            wdop: v1.wdop,
+            wqop: v1.wqop,
+            wdml: v1.wdml,
+            wqml: v1.wqml,
+            wddv: v1.wddv,
+            wqdv: v1.wqdv,
+            wdmd: v1.wdmd,
+            wqmd: v1.wqmd,
+            wdam: v1.wdam,
+            wqam: v1.wqam,
+            wdmm: v1.wdmm,
+            wqmm: v1.wqmm,
+            xor: v1.xor,
+            xori: v1.xori,
+            call: v1.call,
+            ccp: v1.ccp,
+            croo: v1.croo,
+            csiz: v1.csiz,
+            k256: v1.k256,
+            ldc: v1.ldc,
+            logd: v1.logd,
+            mcl: v1.mcl,
+            mcli: v1.mcli,
+            mcp: v1.mcp,
+            mcpi: v1.mcpi,
+            meq: v1.meq,
+            retd: v1.retd,
+            s256: v1.s256,
+            scwq: v1.scwq,
+            smo: v1.smo,
+            srwq: v1.srwq,
+            swwq: v1.swwq,
+            contract_root: v1.contract_root,
+            state_root: v1.state_root,
+            new_storage_per_byte: v1.new_storage_per_byte,
+            new_storage_per_slot: 0,
+            vm_initialization: v1.vm_initialization,
+        }
+    }
+}
+
 impl DependentCost {
     /// Create costs that make operations free.
     pub fn free() -> Self {
@@ -1328,12 +2195,17 @@ impl DependentCost {
         };
     }
 
+    /// The gas charged for `units` units, saturating at [`Word::MAX`] instead of
+    /// overflowing. A saturated cost exceeds any gas limit, so charging it runs out of
+    /// gas.
     pub fn resolve(&self, units: Word) -> Word {
         let base = self.base();
         let dependent_value = self.resolve_without_base(units);
-        base + dependent_value
+        base.saturating_add(dependent_value)
     }
 
+    /// The gas charged for `units` units without the base, saturating at
+    /// [`Word::MAX`].
     pub fn resolve_without_base(&self, units: Word) -> Word {
         match self {
             DependentCost::LightOperation { units_per_gas, .. } => {
@@ -1383,9 +2255,68 @@ impl From<GasCostsValuesV1> for GasCostsValues {
     }
 }
 
+impl From<GasCostsValuesV2> for GasCostsValues {
+    fn from(i: GasCostsValuesV2) -> Self {
+        GasCostsValues::V2(i)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::DependentCost;
+    use super::{
+        GasCostsValuesV1,
+        GasCostsValuesV2,
+    };
+    use crate::{
+        DependentCost,
+        GasCosts,
+        GasCostsValues,
+        NewStorageCost,
+    };
+    use fuel_asm::Opcode;
+
+    #[test]
+    fn charge_for_resolves_the_fixed_and_the_dependent_costs() {
+        let mut values = GasCostsValuesV2::unit();
+        values.add = 3;
+        values.mcp = DependentCost::from_units_per_gas(2, 10);
+        values.s256 = DependentCost::from_gas_per_unit(5, 4);
+        let gas_costs = GasCosts::new(GasCostsValues::V2(values));
+
+        assert_eq!(gas_costs.charge_for(Opcode::ADD, 1_000), Some(3));
+        assert_eq!(gas_costs.charge_for(Opcode::MCP, 19), Some(3));
+        assert_eq!(gas_costs.charge_for(Opcode::MCP, 20), Some(4));
+        assert_eq!(gas_costs.charge_for(Opcode::S256, 7), Some(33));
+        assert_eq!(gas_costs.charge_for(Opcode::ECAL, 7), Some(1));
+    }
+
+    #[test]
+    fn with_ecal_upgrades_the_v1_costs_without_changing_the_other_costs() {
+        let v1 = GasCosts::new(GasCostsValues::V1(GasCostsValuesV1::unit()));
+
+        let v2 = v1.with_ecal(5);
+
+        assert_eq!(v1.charge_for(Opcode::ECAL, 0), Some(0));
+        assert_eq!(v2.charge_for(Opcode::ECAL, 0), Some(5));
+        assert!(matches!(*v2, GasCostsValues::V2(_)));
+        for opcode in Opcode::ALL.iter().filter(|opcode| **opcode != Opcode::ECAL) {
+            assert_eq!(v1.cost_of(*opcode), v2.cost_of(*opcode));
+        }
+    }
+
+    #[test]
+    fn v1_costs_keep_their_postcard_encoding() {
+        // The `V1` costs are encoded on chain in the consensus parameters of the
+        // upgrade transactions: their fields can't change.
+        let v1 = GasCostsValues::V1(GasCostsValuesV1::unit());
+
+        let encoded = postcard::to_allocvec(&v1).unwrap();
+
+        // The version, 89 fixed costs of one byte each, and 21 dependent costs of
+        // three bytes each.
+        assert_eq!(encoded.len(), 1 + 89 + 21 * 3);
+        assert_eq!(postcard::from_bytes::<GasCostsValues>(&encoded).unwrap(), v1);
+    }
 
     #[test]
     fn light_operation_gas_cost_resolves_correctly() {
@@ -1426,4 +2357,45 @@ mod tests {
         let total = cost.resolve(721);
         assert_eq!(total, 7_210);
     }
+
+    #[test]
+    fn dependent_gas_cost_saturates_instead_of_overflowing() {
+        let heavy = DependentCost::from_gas_per_unit(10, u64::MAX / 2);
+        let light = DependentCost::from_units_per_gas(u64::MAX, 1);
+
+        assert_eq!(heavy.resolve_without_base(3), u64::MAX);
+        assert_eq!(heavy.resolve(2), u64::MAX);
+        assert_eq!(heavy.resolve(1), u64::MAX / 2 + 10);
+        assert_eq!(light.resolve(1), u64::MAX);
+    }
+
+    #[test]
+    fn new_storage_cost_charges_the_slots_and_their_bytes() {
+        let mut values = GasCostsValuesV2::free();
+        values.new_storage_per_slot = 100;
+        values.new_storage_per_byte = 2;
+        let cost = GasCosts::new(GasCostsValues::V2(values)).new_storage();
+
+        assert_eq!(
+            cost,
+            NewStorageCost {
+                per_slot: 100,
+                per_byte: 2,
+            }
+        );
+        assert_eq!(cost.resolve(0, 0), 0);
+        assert_eq!(cost.resolve(1, 64), 228);
+        assert_eq!(cost.resolve(10, 640), 10 * cost.resolve(1, 64));
+        assert_eq!(cost.resolve(u64::MAX / 50, 0), u64::MAX);
+        assert_eq!(cost.if_per_slot_set(), cost);
+    }
+
+    #[test]
+    fn new_storage_cost_without_per_slot_is_free_if_per_slot_set() {
+        let cost =
+            GasCosts::new(GasCostsValues::V1(GasCostsValuesV1::unit())).new_storage();
+
+        assert_eq!(cost.per_byte, 1);
+        assert_eq!(cost.if_per_slot_set(), NewStorageCost::default());
+    }
 }
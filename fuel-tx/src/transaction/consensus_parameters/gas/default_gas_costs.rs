@@ -3,7 +3,7 @@ use super::*;
 /// hash
 pub const GIT: &str = "98341e564b75d1157e61d7d5f38612f6224a5b30";
 pub fn default_gas_costs() -> GasCostsValues {
-    GasCostsValuesV1 {
+    GasCostsValuesV2 {
         add: 1,
         addi: 1,
         aloc: 1,
@@ -18,6 +18,7 @@ pub fn default_gas_costs() -> GasCostsValues {
         cfsi: 1,
         div: 1,
         divi: 1,
+        ecal: 0,
         eck1: 951,
         ecr1: 3000,
         ed19: 3000,
@@ -175,6 +176,7 @@ pub fn default_gas_costs() -> GasCostsValues {
             units_per_gas: 1,
         },
         new_storage_per_byte: 1,
+        new_storage_per_slot: 0,
         vm_initialization: DependentCost::HeavyOperation {
             base: 2000,
             gas_per_unit: 0,
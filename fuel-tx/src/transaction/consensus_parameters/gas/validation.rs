@@ -0,0 +1,263 @@
+//! Completeness of the gas costs against the opcodes of the instruction table, for the
+//! gas schedules tuned by the configuration of a chain.
+//!
+//! The cost of an opcode is named by its lowercase mnemonic, except the few opcodes
+//! charged with the cost of another name in [`opcode_cost_key`].
+
+use alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use core::fmt;
+
+use fuel_asm::Opcode;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::{
+    GasCostValue,
+    GasCosts,
+    GasCostsValues,
+};
+
+/// The costs that don't belong to an opcode.
+const NON_OPCODE_COSTS: [&str; 5] = [
+    "contract_root",
+    "state_root",
+    "new_storage_per_byte",
+    "new_storage_per_slot",
+    "vm_initialization",
+];
+
+/// The name of the cost charged for the `opcode`, `None` if it has no cost.
+pub fn opcode_cost_key(opcode: Opcode) -> Option<String> {
+    let key = match opcode {
+        Opcode::CFE => "cfei",
+        Opcode::CFS => "cfsi",
+        Opcode::RET => "ret_contract",
+        Opcode::RETD => "retd_contract",
+        Opcode::RVRT => "rvrt_contract",
+        _ => return Some(opcode.mnemonic().to_ascii_lowercase()),
+    };
+    Some(key.to_string())
+}
+
+/// An incompleteness of the gas costs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum GasCostWarning {
+    /// The opcode has no cost, like an opcode added to the instruction table since.
+    #[display(fmt = "The opcode {} has no cost", "_0.mnemonic()")]
+    MissingOpcode(Opcode),
+    /// The cost is missing in the loaded file and takes its default value.
+    #[display(fmt = "The cost {_0} is missing")]
+    MissingKey(String),
+    /// The key is neither the cost of an opcode nor a known cost, like a typo.
+    #[display(fmt = "The key {_0} isn't a known cost")]
+    UnknownKey(String),
+}
+
+/// A cost changed between two gas costs, rendered as `key: old -> new`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GasCostChange {
+    /// The name of the cost.
+    pub key: String,
+    /// The cost before the change.
+    pub old: GasCostValue,
+    /// The cost after the change.
+    pub new: GasCostValue,
+}
+
+impl fmt::Display for GasCostChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.key, self.old, self.new)
+    }
+}
+
+/// The costs of the `values` by name, from their serialization.
+fn entries(values: &GasCostsValues) -> Map<String, Value> {
+    let versioned = serde_json::to_value(values).expect("Gas costs are serializable");
+    match versioned {
+        Value::Object(versions) => match versions.into_iter().next() {
+            Some((_, Value::Object(entries))) => entries,
+            _ => Map::new(),
+        },
+        _ => Map::new(),
+    }
+}
+
+/// The warnings of the names of the costs of `keys`.
+fn warnings<'a>(keys: impl Iterator<Item = &'a String> + Clone) -> Vec<GasCostWarning> {
+    let opcode_keys = Opcode::ALL
+        .iter()
+        .filter_map(|opcode| opcode_cost_key(*opcode).map(|key| (*opcode, key)))
+        .collect::<Vec<_>>();
+    let missing = opcode_keys
+        .iter()
+        .filter(|(_, key)| !keys.clone().any(|k| k == key))
+        .map(|(opcode, _)| GasCostWarning::MissingOpcode(*opcode));
+    let unknown = keys
+        .clone()
+        .filter(|key| {
+            !NON_OPCODE_COSTS.contains(&key.as_str())
+                && !opcode_keys.iter().any(|(_, k)| k == *key)
+        })
+        .map(|key| GasCostWarning::UnknownKey(key.clone()));
+    missing.chain(unknown).collect()
+}
+
+impl GasCosts {
+    /// The opcodes of the instruction table without a cost, and the costs that belong
+    /// neither to an opcode nor to the known non-opcode costs.
+    pub fn validate(&self) -> Vec<GasCostWarning> {
+        warnings(entries(self).keys())
+    }
+
+    /// The costs of `other` differing from these costs, by name.
+    pub fn diff(&self, other: &Self) -> Vec<GasCostChange> {
+        let new = entries(other);
+        entries(self)
+            .into_iter()
+            .filter_map(|(key, old)| {
+                let new = new.get(&key)?;
+                if old == *new {
+                    return None
+                }
+                Some(GasCostChange {
+                    old: cost_value(&old)?,
+                    new: cost_value(new)?,
+                    key,
+                })
+            })
+            .collect()
+    }
+
+    /// Loads the gas costs serialized in JSON from the `reader`, with the warnings of
+    /// [`Self::validate`], the costs missing in the JSON, taking their default value,
+    /// and the unknown keys of the JSON, ignored.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+    ) -> Result<(Self, Vec<GasCostWarning>), serde_json::Error> {
+        let json: Value = serde_json::from_reader(reader)?;
+        let gas_costs: Self = serde_json::from_value(json.clone())?;
+
+        let keys = json
+            .as_object()
+            .and_then(|versions| versions.values().next())
+            .and_then(Value::as_object)
+            .map(|entries| entries.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let mut warnings = warnings(keys.iter());
+        let loaded = entries(&gas_costs);
+        warnings.extend(
+            NON_OPCODE_COSTS
+                .iter()
+                .filter(|key| {
+                    loaded.contains_key(**key) && !keys.iter().any(|k| k == *key)
+                })
+                .map(|key| GasCostWarning::MissingKey(key.to_string())),
+        );
+        Ok((gas_costs, warnings))
+    }
+}
+
+fn cost_value(value: &Value) -> Option<GasCostValue> {
+    match value {
+        Value::Number(cost) => cost.as_u64().map(GasCostValue::Fixed),
+        value => serde_json::from_value(value.clone())
+            .ok()
+            .map(GasCostValue::Dependent),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// The default gas costs without the cost of `ADD` and with the unknown `frob`.
+    fn fixture() -> Vec<u8> {
+        let mut json = serde_json::to_value(GasCosts::default()).unwrap();
+        let entries = json["V2"].as_object_mut().unwrap();
+        entries.remove("add");
+        entries.insert("frob".to_string(), Value::from(1));
+        serde_json::to_vec(&json).unwrap()
+    }
+
+    #[test]
+    fn default_gas_costs_have_a_cost_for_each_opcode() {
+        assert_eq!(GasCosts::default().validate(), vec![]);
+    }
+
+    #[test]
+    fn from_reader_warns_about_the_missing_and_the_unknown_opcodes() {
+        let (gas_costs, warnings) = GasCosts::from_reader(fixture().as_slice()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![
+                GasCostWarning::MissingOpcode(Opcode::ADD),
+                GasCostWarning::UnknownKey("frob".to_string()),
+            ]
+        );
+        assert_eq!(warnings[0].to_string(), "The opcode ADD has no cost");
+        assert_eq!(gas_costs.validate(), vec![]);
+    }
+
+    #[test]
+    fn from_reader_warns_about_the_missing_non_opcode_costs() {
+        let mut json = serde_json::to_value(GasCosts::default()).unwrap();
+        json["V2"].as_object_mut().unwrap().remove("state_root");
+
+        let (_, warnings) =
+            GasCosts::from_reader(serde_json::to_vec(&json).unwrap().as_slice()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![GasCostWarning::MissingKey("state_root".to_string())]
+        );
+    }
+
+    #[test]
+    fn gas_costs_round_trip_through_json() {
+        let gas_costs = GasCosts::default();
+
+        let json = serde_json::to_vec(&gas_costs).unwrap();
+        let (loaded, warnings) = GasCosts::from_reader(json.as_slice()).unwrap();
+
+        assert_eq!(loaded, gas_costs);
+        assert_eq!(warnings, vec![]);
+        assert_eq!(gas_costs.diff(&loaded), vec![]);
+    }
+
+    #[test]
+    fn diff_lists_the_changed_costs() {
+        let mut json = serde_json::to_value(GasCosts::default()).unwrap();
+        json["V2"]["add"] = Value::from(5);
+        json["V2"]["k256"] = serde_json::json!({
+            "HeavyOperation": { "base": 7, "gas_per_unit": 3 }
+        });
+        let changed: GasCosts = serde_json::from_value(json).unwrap();
+        let default = GasCosts::default();
+
+        let diff = default.diff(&changed);
+
+        let rendered = diff.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                format!("add: {} -> 5", default.add()),
+                format!(
+                    "k256: {} -> 7 + 3 per unit",
+                    GasCostValue::Dependent(default.k256())
+                ),
+            ]
+        );
+    }
+}
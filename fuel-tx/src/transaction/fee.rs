@@ -23,7 +23,10 @@ use crate::{
     Input,
 };
 use fuel_asm::Word;
-use fuel_types::canonical::Serialize;
+use fuel_types::{
+    bytes,
+    canonical::Serialize,
+};
 use hashbrown::HashSet;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -33,6 +36,16 @@ pub struct TransactionFee {
     pub(crate) max_fee: Word,
     pub(crate) min_gas: Word,
     pub(crate) max_gas: Word,
+    pub(crate) bytes_gas: Word,
+    pub(crate) da_bytes_gas: Word,
+    pub(crate) inputs_gas: Word,
+    pub(crate) predicate_gas: Word,
+    pub(crate) script_gas_limit: Word,
+    pub(crate) refundable: Word,
+    pub(crate) gas_price: Word,
+    pub(crate) gas_price_factor: Word,
+    pub(crate) tip: Word,
+    pub(crate) max_fee_limit: Word,
 }
 
 impl From<TransactionFee> for Word {
@@ -42,15 +55,6 @@ impl From<TransactionFee> for Word {
 }
 
 impl TransactionFee {
-    pub const fn new(min_fee: Word, max_fee: Word, min_gas: Word, max_gas: Word) -> Self {
-        Self {
-            min_fee,
-            max_fee,
-            min_gas,
-            max_gas,
-        }
-    }
-
     /// Minimum fee value to pay for the base transaction without script execution.
     pub const fn min_fee(&self) -> Word {
         self.min_fee
@@ -71,6 +75,48 @@ impl TransactionFee {
         self.max_gas
     }
 
+    /// The gas charged for the metered bytes of the transaction. It is the sum of
+    /// [`Self::execution_bytes_gas`] and [`Self::da_bytes_gas`].
+    pub const fn bytes_gas(&self) -> Word {
+        self.bytes_gas
+    }
+
+    /// The gas charged for the witness and predicate bytes with the
+    /// [`FeeParameters::da_gas_per_byte`].
+    pub const fn da_bytes_gas(&self) -> Word {
+        self.da_bytes_gas
+    }
+
+    /// The gas charged for the rest of the metered bytes with the
+    /// [`FeeParameters::gas_per_byte`].
+    pub const fn execution_bytes_gas(&self) -> Word {
+        self.bytes_gas.saturating_sub(self.da_bytes_gas)
+    }
+
+    /// The gas charged for the verification of the inputs: recovery of signatures and
+    /// the initialization of predicates. It doesn't include
+    /// [`Self::predicate_gas`].
+    pub const fn inputs_gas(&self) -> Word {
+        self.inputs_gas
+    }
+
+    /// The gas used by the execution of predicates.
+    pub const fn predicate_gas(&self) -> Word {
+        self.predicate_gas
+    }
+
+    /// The gas reserved for the execution of the script. It is zero for transactions
+    /// without a script.
+    pub const fn script_gas_limit(&self) -> Word {
+        self.script_gas_limit
+    }
+
+    /// The part of the max fee limit that is refunded if the transaction doesn't use
+    /// any gas during execution.
+    pub const fn refundable(&self) -> Word {
+        self.refundable
+    }
+
     /// Convert into a tuple containing the inner min & total fee values
     pub const fn into_inner(self) -> (Word, Word) {
         (self.min_fee, self.max_fee)
@@ -85,9 +131,50 @@ impl TransactionFee {
         balance.checked_sub(fee)
     }
 
+    /// Returns the final fee and the refund of the transaction after the execution
+    /// used `gas_used` gas, with the predicates using all of their declared
+    /// [`Self::predicate_gas`]. See [`Self::fee_after_execution_with_predicate_gas`].
+    ///
+    /// Will return `None` if the fee doesn't fit in a [`Word`] or exceeds the max fee
+    /// limit, the refund being negative.
+    pub fn fee_after_execution(&self, gas_used: Word) -> Option<(Word, Word)> {
+        self.fee_after_execution_with_predicate_gas(gas_used, self.predicate_gas)
+    }
+
+    /// Returns the final fee and the refund of the transaction after the execution
+    /// used `gas_used` gas and the verification of the predicates used
+    /// `predicate_gas_used` gas. The same values are charged and refunded by the
+    /// interpreter.
+    ///
+    /// The predicate gas charged is at most the declared [`Self::predicate_gas`], the
+    /// part of it unused by the verification being refunded.
+    ///
+    /// Will return `None` if the fee doesn't fit in a [`Word`] or exceeds the max fee
+    /// limit, the refund being negative.
+    pub fn fee_after_execution_with_predicate_gas(
+        &self,
+        gas_used: Word,
+        predicate_gas_used: Word,
+    ) -> Option<(Word, Word)> {
+        let total_used_gas = self
+            .min_gas
+            .saturating_sub(self.predicate_gas)
+            .saturating_add(predicate_gas_used.min(self.predicate_gas))
+            .saturating_add(gas_used);
+        let fee = gas_to_fee(total_used_gas, self.gas_price, self.gas_price_factor)
+            .saturating_add(self.tip as u128);
+        let fee: Word = fee.try_into().ok()?;
+        let refund = self.max_fee_limit.checked_sub(fee)?;
+
+        Some((fee, refund))
+    }
+
     /// Attempt to create a transaction fee from parameters and transaction internals
     ///
-    /// Will return `None` if arithmetic overflow occurs.
+    /// The gas is summed saturating at [`Word::MAX`], bounded anyway by the max gas per
+    /// transaction. Will return `None` if the min or the max fee doesn't fit in a
+    /// [`Word`], which the checks of the transaction report as
+    /// `ValidityError::BalanceOverflow`.
     pub fn checked_from_tx<T>(
         gas_costs: &GasCosts,
         params: &FeeParameters,
@@ -106,18 +193,91 @@ impl TransactionFee {
             return None;
         }
 
-        Some(Self::new(min_fee, max_fee, min_gas, max_gas))
+        let (execution_bytes_gas, da_bytes_gas) = bytes_gas(tx, params);
+        let bytes_gas = execution_bytes_gas.saturating_add(da_bytes_gas);
+        let predicate_gas = tx
+            .inputs()
+            .iter()
+            .filter_map(Input::predicate_gas_used)
+            .fold(0, Word::saturating_add);
+        let inputs_gas = tx
+            .gas_used_by_inputs(gas_costs)
+            .saturating_sub(predicate_gas);
+        let remaining_witness_gas = tx
+            .witness_limit()
+            .saturating_sub(tx.witnesses().size_dynamic() as Word)
+            .saturating_mul(params.da_gas_per_byte());
+        let script_gas_limit = max_gas
+            .saturating_sub(min_gas)
+            .saturating_sub(remaining_witness_gas);
+        let max_fee_limit = tx.max_fee_limit();
+
+        let mut fee = Self {
+            min_fee,
+            max_fee,
+            min_gas,
+            max_gas,
+            bytes_gas,
+            da_bytes_gas,
+            inputs_gas,
+            predicate_gas,
+            script_gas_limit,
+            refundable: 0,
+            gas_price,
+            gas_price_factor: params.gas_price_factor(),
+            tip: tx.tip(),
+            max_fee_limit,
+        };
+        fee.refundable = fee.fee_after_execution(0).map_or(0, |(_, refund)| refund);
+
+        Some(fee)
     }
 }
 
+/// Converts the `gas` into a fee with the `gas_price` divided by the `factor`, rounded
+/// up.
+///
+/// The product of two `u64` fits in a `u128`, so the conversion can't overflow, but the
+/// fee may not fit in a [`Word`]: the callers convert it with `try_into`, failing on
+/// overflow. A zero `factor` is a misconfiguration making any fee unpayable, and
+/// returns [`u128::MAX`] instead of dividing by zero.
 fn gas_to_fee(gas: Word, gas_price: Word, factor: Word) -> u128 {
     let total_price = (gas as u128)
         .checked_mul(gas_price as u128)
         .expect("Impossible to overflow because multiplication of two `u64` <= `u128`");
+    if factor == 0 {
+        return u128::MAX;
+    }
     total_price.div_ceil(factor as u128)
 }
 
+/// Returns the gas charged for the metered bytes of the transaction, split into the
+/// bytes priced with the [`FeeParameters::gas_per_byte`] and the witness and predicate
+/// bytes priced with the [`FeeParameters::da_gas_per_byte`].
+fn bytes_gas<Tx>(tx: &Tx, fee: &FeeParameters) -> (Word, Word)
+where
+    Tx: Chargeable + ?Sized,
+{
+    let da_bytes_size = tx.da_bytes_size();
+    let execution_bytes_size = tx.metered_bytes_size().saturating_sub(da_bytes_size);
+
+    (
+        (execution_bytes_size as Word).saturating_mul(fee.gas_per_byte()),
+        (da_bytes_size as Word).saturating_mul(fee.da_gas_per_byte()),
+    )
+}
+
 /// Returns the minimum gas required to start execution of any transaction.
+///
+/// It is the sum of:
+/// - the gas of the inputs: the recovery of the signatures of unique witnesses, and the
+///   root, the VM initialization and the `predicate_gas_used` of predicates;
+/// - the gas used by the metadata of the transaction, e.g. the id computation;
+/// - the gas of the metered bytes of the transaction;
+/// - the gas of the VM initialization.
+///
+/// It is the canonical value used by the checks and the interpreter, the same as
+/// [`Chargeable::min_gas`].
 pub fn min_gas<Tx>(tx: &Tx, gas_costs: &GasCosts, fee: &FeeParameters) -> Word
 where
     Tx: Chargeable + ?Sized,
@@ -126,7 +286,8 @@ where
 
     let vm_initialization_gas = gas_costs.vm_initialization().resolve(bytes_size as Word);
 
-    let bytes_gas = bytes_size as u64 * fee.gas_per_byte();
+    let (execution_bytes_gas, da_bytes_gas) = bytes_gas(tx, fee);
+    let bytes_gas = execution_bytes_gas.saturating_add(da_bytes_gas);
     // It's okay to saturate because we have the `max_gas_per_tx` rule for transaction
     // validity. In the production, the value always will be lower than
     // `u64::MAX`.
@@ -136,6 +297,42 @@ where
         .saturating_add(vm_initialization_gas)
 }
 
+/// Returns the maximum gas the transaction may use: the [`min_gas`], the gas of the
+/// witnesses that may still be added up to the witness limit, and the gas limit of the
+/// script for transactions with a script.
+///
+/// It is the canonical value used by the checks and the interpreter, the same as
+/// [`Chargeable::max_gas`]. The max fee of the transaction is this gas converted with
+/// the gas price, plus the tip.
+pub fn max_gas<Tx>(tx: &Tx, gas_costs: &GasCosts, fee: &FeeParameters) -> Word
+where
+    Tx: Chargeable + ?Sized,
+{
+    tx.max_gas(gas_costs, fee)
+}
+
+/// The refund of the max fee limit of the `tx` after using `total_used_gas`, `None` if
+/// the used fee overflows or exceeds the max fee limit.
+fn refund_of_used_gas<Tx>(
+    tx: &Tx,
+    fee: &FeeParameters,
+    total_used_gas: Word,
+    gas_price: Word,
+) -> Option<Word>
+where
+    Tx: Chargeable + ?Sized,
+{
+    let tip = tx.policies().get(PolicyType::Tip).unwrap_or(0);
+    let used_fee = gas_to_fee(total_used_gas, gas_price, fee.gas_price_factor())
+        .saturating_add(tip as u128);
+
+    // It is okay to saturate everywhere above because it only can decrease the value
+    // of `refund`. But here, because we need to return the amount we
+    // want to refund, we need to handle the overflow caused by the price.
+    let used_fee: u64 = used_fee.try_into().ok()?;
+    tx.max_fee_limit().checked_sub(used_fee)
+}
+
 /// Means that the blockchain charges fee for the transaction.
 pub trait Chargeable: field::Inputs + field::Witnesses + field::Policies {
     /// Returns the minimum gas required to start transaction execution.
@@ -150,7 +347,7 @@ pub trait Chargeable: field::Inputs + field::Witnesses + field::Policies {
         let remaining_allowed_witness_gas = self
             .witness_limit()
             .saturating_sub(self.witnesses().size_dynamic() as u64)
-            .saturating_mul(fee.gas_per_byte());
+            .saturating_mul(fee.da_gas_per_byte());
 
         self.min_gas(gas_costs, fee)
             .saturating_add(remaining_allowed_witness_gas)
@@ -193,7 +390,8 @@ pub trait Chargeable: field::Inputs + field::Witnesses + field::Policies {
     /// Returns the fee amount that can be refunded back based on the `used_gas` and
     /// current state of the transaction.
     ///
-    /// Return `None` if overflow occurs.
+    /// Return `None` if overflow occurs, or if the used fee exceeds the max fee limit:
+    /// the refund is never negative.
     fn refund_fee(
         &self,
         gas_costs: &GasCosts,
@@ -206,20 +404,62 @@ pub trait Chargeable: field::Inputs + field::Witnesses + field::Policies {
         let min_gas = self.min_gas(gas_costs, fee);
 
         let total_used_gas = min_gas.saturating_add(used_gas);
-        let tip = self.policies().get(PolicyType::Tip).unwrap_or(0);
-        let used_fee = gas_to_fee(total_used_gas, gas_price, fee.gas_price_factor())
-            .saturating_add(tip as u128);
-
-        // It is okay to saturate everywhere above because it only can decrease the value
-        // of `refund`. But here, because we need to return the amount we
-        // want to refund, we need to handle the overflow caused by the price.
-        let used_fee: u64 = used_fee.try_into().ok()?;
-        self.max_fee_limit().checked_sub(used_fee)
+        refund_of_used_gas(self, fee, total_used_gas, gas_price)
+    }
+
+    /// Returns the fee amount that can be refunded back based on the `used_gas`, like
+    /// [`Self::refund_fee`], but charging the `predicate_gas_used` by the verification
+    /// of the predicates instead of the `predicate_gas_used` of the inputs.
+    ///
+    /// The caller bounds the `predicate_gas_used` by the declared gas of the
+    /// predicates, which may differ from the gas of the inputs of the transaction
+    /// prepared for the execution.
+    fn refund_fee_with_predicate_gas(
+        &self,
+        gas_costs: &GasCosts,
+        fee: &FeeParameters,
+        used_gas: Word,
+        predicate_gas_used: Word,
+        gas_price: Word,
+    ) -> Option<Word> {
+        let inputs_predicate_gas = self
+            .inputs()
+            .iter()
+            .filter_map(Input::predicate_gas_used)
+            .fold(0, Word::saturating_add);
+        let total_used_gas = self
+            .min_gas(gas_costs, fee)
+            .saturating_sub(inputs_predicate_gas)
+            .saturating_add(predicate_gas_used)
+            .saturating_add(used_gas);
+        refund_of_used_gas(self, fee, total_used_gas, gas_price)
     }
 
     /// Used for accounting purposes when charging byte based fees.
     fn metered_bytes_size(&self) -> usize;
 
+    /// The part of the [`Self::metered_bytes_size`] charged with the
+    /// [`FeeParameters::da_gas_per_byte`]: the witnesses, and the predicates of the
+    /// inputs with their data.
+    fn da_bytes_size(&self) -> usize {
+        let predicates_size = self
+            .inputs()
+            .iter()
+            .filter_map(Input::predicate)
+            .map(|(predicate, predicate_data, _)| {
+                bytes::padded_len(predicate)
+                    .unwrap_or(usize::MAX)
+                    .saturating_add(
+                        bytes::padded_len(predicate_data).unwrap_or(usize::MAX),
+                    )
+            })
+            .fold(0usize, usize::saturating_add);
+
+        self.witnesses()
+            .size_dynamic()
+            .saturating_add(predicates_size)
+    }
+
     /// Returns the gas used by the inputs.
     fn gas_used_by_inputs(&self, gas_costs: &GasCosts) -> Word {
         let mut witness_cache: HashSet<u16> = HashSet::new();
@@ -15,6 +15,8 @@ use fuel_crypto::{
     PublicKey,
     SecretKey,
     Signature,
+    Signer,
+    SignerError,
 };
 use fuel_types::{
     Bytes32,
@@ -65,6 +67,15 @@ impl UniqueIdentifier for Transaction {
 pub trait Signable: UniqueIdentifier {
     /// Signs inputs of the transaction.
     fn sign_inputs(&mut self, secret: &SecretKey, chain_id: &ChainId);
+
+    /// Signs the inputs owned by the key of the `signer`, like [`Self::sign_inputs`].
+    ///
+    /// The transaction is left untouched if the `signer` fails.
+    fn sign_inputs_with<S: Signer>(
+        &mut self,
+        signer: &S,
+        chain_id: &ChainId,
+    ) -> Result<(), SignerError>;
 }
 
 impl<T> Signable for T
@@ -74,45 +85,61 @@ where
     /// For all inputs of type `coin` or `message`, check if its `owner` equals the public
     /// counterpart of the provided key. Sign all matches.
     fn sign_inputs(&mut self, secret: &SecretKey, chain_id: &ChainId) {
-        use itertools::Itertools;
+        self.sign_inputs_with(secret, chain_id)
+            .expect("The in-memory signer can't fail")
+    }
 
-        let pk = PublicKey::from(secret);
-        let pk = Input::owner(&pk);
+    fn sign_inputs_with<S: Signer>(
+        &mut self,
+        signer: &S,
+        chain_id: &ChainId,
+    ) -> Result<(), SignerError> {
         let id = self.id(chain_id);
+        let signature = signer.sign(Message::from_bytes_ref(&id))?;
 
-        let message = Message::from_bytes_ref(&id);
+        attach_signature(self, &signer.public_key(), &signature);
 
-        let signature = Signature::sign(secret, message);
+        Ok(())
+    }
+}
 
-        let inputs = self.inputs();
+/// Sets the `signature` to the witnesses of the signed inputs owned by the `public`
+/// key.
+pub(crate) fn attach_signature<T>(tx: &mut T, public: &PublicKey, signature: &Signature)
+where
+    T: field::Witnesses + field::Inputs,
+{
+    use itertools::Itertools;
 
-        let witness_indexes = inputs
-            .iter()
-            .filter_map(|input| match input {
-                Input::CoinSigned(CoinSigned {
-                    owner,
-                    witness_index,
-                    ..
-                })
-                | Input::MessageCoinSigned(MessageCoinSigned {
-                    recipient: owner,
-                    witness_index,
-                    ..
-                })
-                | Input::MessageDataSigned(MessageDataSigned {
-                    recipient: owner,
-                    witness_index,
-                    ..
-                }) if owner == &pk => Some(*witness_index as usize),
-                _ => None,
-            })
-            .dedup()
-            .collect_vec();
+    let pk = Input::owner(public);
 
-        for w in witness_indexes {
-            if let Some(w) = self.witnesses_mut().get_mut(w) {
-                *w = signature.as_ref().into();
-            }
+    let witness_indexes = tx
+        .inputs()
+        .iter()
+        .filter_map(|input| match input {
+            Input::CoinSigned(CoinSigned {
+                owner,
+                witness_index,
+                ..
+            })
+            | Input::MessageCoinSigned(MessageCoinSigned {
+                recipient: owner,
+                witness_index,
+                ..
+            })
+            | Input::MessageDataSigned(MessageDataSigned {
+                recipient: owner,
+                witness_index,
+                ..
+            }) if owner == &pk => Some(*witness_index as usize),
+            _ => None,
+        })
+        .dedup()
+        .collect_vec();
+
+    for w in witness_indexes {
+        if let Some(w) = tx.witnesses_mut().get_mut(w) {
+            *w = signature.as_ref().into();
         }
     }
 }
@@ -144,6 +171,7 @@ mod tests {
         Output,
         StorageSlot,
         Transaction,
+        UniqueIdentifier,
         UpgradePurpose as UpgradePurposeType,
         UploadBody,
         UtxoId,
@@ -376,6 +404,35 @@ mod tests {
                 invert
             );
 
+            assert_io_eq!(
+                tx,
+                inputs_mut,
+                Input::ContractReadOnly[input::contract::Contract],
+                utxo_id,
+                invert_utxo_id
+            );
+            assert_io_eq!(
+                tx,
+                inputs_mut,
+                Input::ContractReadOnly[input::contract::Contract],
+                balance_root,
+                invert
+            );
+            assert_io_eq!(
+                tx,
+                inputs_mut,
+                Input::ContractReadOnly[input::contract::Contract],
+                state_root,
+                invert
+            );
+            assert_io_ne!(
+                tx,
+                inputs_mut,
+                Input::ContractReadOnly[input::contract::Contract],
+                contract_id,
+                invert
+            );
+
             assert_io_ne!(
                 tx,
                 inputs_mut,
@@ -633,6 +690,13 @@ mod tests {
                     generate_bytes(rng),
                 ),
                 Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()),
+                Input::contract_read_only(
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                    rng.gen(),
+                ),
                 Input::message_coin_signed(
                     rng.gen(),
                     rng.gen(),
@@ -800,4 +864,83 @@ mod tests {
             }
         }
     }
+
+    /// Computes the id like before the streaming serialization: clones the whole
+    /// transaction and hashes its serialized bytes.
+    fn buffered_id(tx: &Transaction, chain_id: &ChainId) -> fuel_types::Bytes32 {
+        use crate::PrepareSign;
+
+        fn chargeable_bytes<Tx: Buildable + PrepareSign + Serialize>(tx: &Tx) -> Vec<u8> {
+            let mut tx = tx.clone();
+            tx.prepare_sign();
+            tx.witnesses_mut().clear();
+            tx.to_bytes()
+        }
+
+        let bytes = match tx {
+            Transaction::Script(tx) => chargeable_bytes(tx),
+            Transaction::Create(tx) => chargeable_bytes(tx),
+            Transaction::Upgrade(tx) => chargeable_bytes(tx),
+            Transaction::Upload(tx) => chargeable_bytes(tx),
+            Transaction::Mint(tx) => {
+                let mut tx = tx.clone();
+                tx.input_contract.prepare_sign();
+                tx.output_contract.prepare_sign();
+                tx.to_bytes()
+            }
+        };
+
+        fuel_crypto::Hasher::default()
+            .chain(chain_id.to_be_bytes())
+            .chain(bytes)
+            .finalize()
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn id_matches_buffered_id_of_arbitrary_transactions() {
+        use arbitrary::{
+            Arbitrary,
+            Unstructured,
+        };
+
+        let rng = &mut StdRng::seed_from_u64(8586);
+
+        for _ in 0..1_000 {
+            let len = rng.gen_range(0..4096);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen::<u8>()).collect();
+            let Ok(tx) = Transaction::arbitrary(&mut Unstructured::new(&data)) else {
+                continue
+            };
+            let chain_id = ChainId::new(rng.gen());
+
+            assert_eq!(tx.id(&chain_id), buffered_id(&tx, &chain_id));
+        }
+    }
+
+    #[test]
+    fn id_matches_buffered_id_of_upgrade_and_upload() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let chain_id = ChainId::default();
+
+        let mut upgrade =
+            crate::TransactionBuilder::upgrade(UpgradePurposeType::StateTransition {
+                root: rng.gen(),
+            });
+        upgrade.add_witness(generate_bytes(rng).into());
+        let upgrade = upgrade.finalize_without_signature_as_transaction();
+        let mut upload = crate::TransactionBuilder::upload(UploadBody {
+            root: rng.gen(),
+            witness_index: 0,
+            subsection_index: 0,
+            subsections_number: 1,
+            proof_set: vec![rng.gen()],
+        });
+        upload.add_witness(generate_bytes(rng).into());
+        let upload = upload.finalize_without_signature_as_transaction();
+
+        for tx in [upgrade, upload] {
+            assert_eq!(tx.id(&chain_id), buffered_id(&tx, &chain_id));
+        }
+    }
 }
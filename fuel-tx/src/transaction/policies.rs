@@ -1,5 +1,7 @@
+use alloc::vec::Vec;
 use core::ops::Deref;
 use fuel_types::{
+    bytes::WORD_SIZE,
     canonical::{
         Deserialize,
         Error,
@@ -33,6 +35,8 @@ bitflags::bitflags! {
         const Maturity = 1 << 2;
         /// If set, the max fee is present in the policies.
         const MaxFee = 1 << 3;
+        /// If set, the expiration is present in the policies.
+        const Expiration = 1 << 4;
     }
 }
 
@@ -54,6 +58,7 @@ pub enum PolicyType {
     WitnessLimit,
     Maturity,
     MaxFee,
+    Expiration,
 }
 
 impl PolicyType {
@@ -63,6 +68,7 @@ impl PolicyType {
             PolicyType::WitnessLimit => 1,
             PolicyType::Maturity => 2,
             PolicyType::MaxFee => 3,
+            PolicyType::Expiration => 4,
         }
     }
 
@@ -72,6 +78,7 @@ impl PolicyType {
             PolicyType::WitnessLimit => PoliciesBits::WitnessLimit,
             PolicyType::Maturity => PoliciesBits::Maturity,
             PolicyType::MaxFee => PoliciesBits::MaxFee,
+            PolicyType::Expiration => PoliciesBits::Expiration,
         }
     }
 }
@@ -79,15 +86,57 @@ impl PolicyType {
 /// The total number of policies.
 pub const POLICIES_NUMBER: usize = PoliciesBits::all().bits().count_ones() as usize;
 
+// The known policies occupy the lowest bits, so the policies added by newer versions
+// are encoded after them.
+const _: () = assert!(PoliciesBits::all().bits() == (1 << POLICIES_NUMBER) - 1);
+
 /// Container for managing policies.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PoliciesFields"))]
 #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
 pub struct Policies {
     /// A bitmask that indicates what policies are set.
     bits: PoliciesBits,
     /// The array of policy values.
     values: [Word; POLICIES_NUMBER],
+    /// The opaque encoded values of the policies added by newer versions, in the order
+    /// of their bits. They are kept to re-encode the policies into the same bytes, and
+    /// are empty unless a transaction of a newer version is decoded.
+    unknown_values: Vec<[u8; WORD_SIZE]>,
+}
+
+/// The serde representation of the [`Policies`], checked to have a value for each of
+/// the unknown bits.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PoliciesFields {
+    bits: PoliciesBits,
+    values: [Word; POLICIES_NUMBER],
+    #[serde(default)]
+    unknown_values: Vec<[u8; WORD_SIZE]>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PoliciesFields> for Policies {
+    type Error = &'static str;
+
+    fn try_from(fields: PoliciesFields) -> Result<Self, Self::Error> {
+        let PoliciesFields {
+            bits,
+            values,
+            unknown_values,
+        } = fields;
+        if Self::unknown_bits(bits).count() != unknown_values.len() {
+            return Err("The number of the unknown policy values doesn't match the bits")
+        }
+
+        Ok(Self {
+            bits,
+            values,
+            unknown_values,
+        })
+    }
 }
 
 impl Policies {
@@ -96,6 +145,7 @@ impl Policies {
         Self {
             bits: PoliciesBits::empty(),
             values: [0; POLICIES_NUMBER],
+            unknown_values: Vec::new(),
         }
     }
 
@@ -138,6 +188,12 @@ impl Policies {
         self
     }
 
+    /// Sets the `expiration` policy.
+    pub fn with_expiration(mut self, expiration: BlockHeight) -> Self {
+        self.set(PolicyType::Expiration, Some(*expiration.deref() as u64));
+        self
+    }
+
     /// Returns a policy's value if the corresponding bit is set.
     pub fn get(&self, policy_type: PolicyType) -> Option<Word> {
         if self.bits.contains(policy_type.bit()) {
@@ -152,6 +208,18 @@ impl Policies {
         self.bits.contains(policy_type.bit())
     }
 
+    /// Returns `true` if the policies contain bits unknown to this version. Such
+    /// policies are decoded and re-encoded without changes, but they are not valid.
+    pub fn has_unknown(&self) -> bool {
+        !PoliciesBits::all().contains(self.bits)
+    }
+
+    /// Returns the bits unknown to this version with the encoded values of the
+    /// corresponding policies, in the order of the encoding.
+    pub fn unknown(&self) -> impl Iterator<Item = (u32, [u8; WORD_SIZE])> + '_ {
+        Self::unknown_bits(self.bits).zip(self.unknown_values.iter().copied())
+    }
+
     /// Returns a policy's type by the `index`.
     pub fn get_type_by_index(&self, index: usize) -> Option<u32> {
         self.bits.iter().nth(index).map(|bit| bit.bits())
@@ -172,7 +240,8 @@ impl Policies {
     pub fn is_valid(&self) -> bool {
         let expected_values = Self::values_for_bitmask(self.bits, self.values);
 
-        if self.bits.bits() > PoliciesBits::all().bits() {
+        // The semantics of the unknown policies can't be enforced by this version.
+        if self.has_unknown() {
             return false;
         }
 
@@ -186,9 +255,75 @@ impl Policies {
             }
         }
 
+        if let Some(expiration) = self.get(PolicyType::Expiration) {
+            if expiration > u32::MAX as u64 {
+                return false;
+            }
+
+            // The transaction can't expire before it becomes mature.
+            let maturity = self.get(PolicyType::Maturity).unwrap_or_default();
+            if expiration < maturity {
+                return false;
+            }
+        }
+
         true
     }
 
+    /// Returns the values of the set policies in the order of the encoding. The
+    /// encoded bytes of the unknown policies are read as a `Word`.
+    #[cfg(feature = "interop-proto")]
+    pub(crate) fn encoded_values(&self) -> impl Iterator<Item = Word> + '_ {
+        self.values
+            .iter()
+            .zip(PoliciesBits::all().iter())
+            .filter(|(_, bit)| self.bits.contains(*bit))
+            .map(|(value, _)| *value)
+            .chain(self.unknown().map(|(_, value)| Word::from_be_bytes(value)))
+    }
+
+    /// Creates the policies from the `bits` and the [`Self::encoded_values`]. Returns
+    /// `None` if the number of `values` doesn't match the `bits`, or if the policies
+    /// can't be decoded from their canonical encoding.
+    #[cfg(feature = "interop-proto")]
+    pub(crate) fn from_encoded_values(bits: u32, values: &[Word]) -> Option<Self> {
+        let mut policies = Self {
+            bits: PoliciesBits::from_bits_retain(bits),
+            ..Self::new()
+        };
+        if policies.len() != values.len() {
+            return None
+        }
+
+        let mut values = values.iter().copied();
+        for (index, bit) in PoliciesBits::all().iter().enumerate() {
+            if policies.bits.contains(bit) {
+                policies.values[index] = values.next()?;
+            }
+        }
+        for _ in Self::unknown_bits(policies.bits) {
+            policies.unknown_values.push(values.next()?.to_be_bytes());
+        }
+
+        let height = |policy_type| {
+            policies
+                .get(policy_type)
+                .is_none_or(|height| height <= u32::MAX as u64)
+        };
+        if !height(PolicyType::Maturity) || !height(PolicyType::Expiration) {
+            return None
+        }
+
+        Some(policies)
+    }
+
+    /// The bits of the `bits` unknown to this version, in the order of the encoding.
+    fn unknown_bits(bits: PoliciesBits) -> impl Iterator<Item = u32> {
+        (POLICIES_NUMBER..u32::BITS as usize)
+            .map(|index| 1u32 << index)
+            .filter(move |bit| bits.bits() & bit != 0)
+    }
+
     /// Helper function to generate values arrays based on the `PoliciesBits`.
     fn values_for_bitmask(
         bits: PoliciesBits,
@@ -224,6 +359,9 @@ impl Serialize for Policies {
                 value.encode(buffer)?;
             }
         }
+        for (_, value) in self.unknown() {
+            buffer.write(&value)?;
+        }
         Ok(())
     }
 }
@@ -231,11 +369,11 @@ impl Serialize for Policies {
 impl Deserialize for Policies {
     fn decode_static<I: Input + ?Sized>(buffer: &mut I) -> Result<Self, Error> {
         let bits = u32::decode(buffer)?;
-        let bits = PoliciesBits::from_bits(bits)
-            .ok_or(Error::Unknown("Invalid policies bits"))?;
+        // The unknown bits are kept, and the validity checks reject them.
+        let bits = PoliciesBits::from_bits_retain(bits);
         Ok(Self {
             bits,
-            values: Default::default(),
+            ..Self::new()
         })
     }
 
@@ -246,12 +384,24 @@ impl Deserialize for Policies {
             }
         }
 
+        for _ in Self::unknown_bits(self.bits) {
+            let mut value = [0; WORD_SIZE];
+            buffer.read(&mut value)?;
+            self.unknown_values.push(value);
+        }
+
         if let Some(maturity) = self.get(PolicyType::Maturity) {
             if maturity > u32::MAX as u64 {
                 return Err(Error::Unknown("The maturity in more than `u32::MAX`"));
             }
         }
 
+        if let Some(expiration) = self.get(PolicyType::Expiration) {
+            if expiration > u32::MAX as u64 {
+                return Err(Error::Unknown("The expiration in more than `u32::MAX`"));
+            }
+        }
+
         Ok(())
     }
 }
@@ -266,6 +416,7 @@ impl Distribution<Policies> for Standard {
         let mut policies = Policies {
             bits,
             values: Policies::values_for_bitmask(bits, values),
+            ..Policies::new()
         };
 
         if policies.get(PolicyType::Maturity).is_some() {
@@ -273,6 +424,12 @@ impl Distribution<Policies> for Standard {
             policies.set(PolicyType::Maturity, Some(maturity as u64));
         }
 
+        if policies.get(PolicyType::Expiration).is_some() {
+            let maturity = policies.get(PolicyType::Maturity).unwrap_or_default();
+            let expiration = rng.gen_range(maturity..=u32::MAX as u64);
+            policies.set(PolicyType::Expiration, Some(expiration));
+        }
+
         policies
     }
 }
@@ -324,7 +481,8 @@ pub mod typescript {
 #[test]
 fn values_for_bitmask_produces_expected_values() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] =
+        [0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001];
 
     // Given
     let mut set = hashbrown::HashSet::new();
@@ -343,7 +501,8 @@ fn values_for_bitmask_produces_expected_values() {
 #[test]
 fn canonical_serialization_deserialization_for_any_combination_of_values_works() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] =
+        [0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001];
 
     for bitmask in 0..MAX_BITMASK {
         let bits =
@@ -351,6 +510,7 @@ fn canonical_serialization_deserialization_for_any_combination_of_values_works()
         let policies = Policies {
             bits,
             values: Policies::values_for_bitmask(bits, VALUES),
+            ..Policies::new()
         };
 
         let size = policies.size();
@@ -382,3 +542,90 @@ fn canonical_serialization_deserialization_for_any_combination_of_values_works()
         );
     }
 }
+
+#[cfg(test)]
+fn policies_with_unknown_bytes() -> ([u8; WORD_SIZE], [u8; WORD_SIZE], Vec<u8>) {
+    let future_a = [0xaa; WORD_SIZE];
+    let future_b = [0xbb; WORD_SIZE];
+
+    let mut bytes = Vec::new();
+    let bits = PoliciesBits::Tip.bits() | 1 << 7 | 1 << 9;
+    bytes.extend_from_slice(&bits.to_bytes());
+    bytes.extend_from_slice(&123u64.to_bytes());
+    bytes.extend_from_slice(&future_a);
+    bytes.extend_from_slice(&future_b);
+
+    (future_a, future_b, bytes)
+}
+
+#[test]
+fn unknown_policies_are_decoded_and_reencoded_byte_exact() {
+    // Given
+    let (future_a, future_b, bytes) = policies_with_unknown_bytes();
+
+    // When
+    let policies = Policies::from_bytes(&bytes).expect("Unknown policies are decoded");
+
+    // Then
+    assert!(policies.has_unknown());
+    assert_eq!(policies.get(PolicyType::Tip), Some(123));
+    assert_eq!(
+        policies.unknown().collect::<Vec<_>>(),
+        vec![(1 << 7, future_a), (1 << 9, future_b)]
+    );
+    assert_eq!(policies.size(), bytes.len());
+    assert_eq!(policies.to_bytes(), bytes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_the_unknown_policies() {
+    // Given
+    let (_, _, bytes) = policies_with_unknown_bytes();
+    let policies = Policies::from_bytes(&bytes).expect("Unknown policies are decoded");
+
+    // When
+    let json = serde_json::to_string(&policies).expect("Policies are serialized");
+    let postcard = postcard::to_allocvec(&policies).expect("Policies are serialized");
+
+    // Then
+    let from_json: Policies = serde_json::from_str(&json).expect("Policies from JSON");
+    let from_postcard: Policies =
+        postcard::from_bytes(&postcard).expect("Policies from postcard");
+    assert_eq!(from_json, policies);
+    assert_eq!(from_postcard, policies);
+    assert_eq!(from_json.to_bytes(), bytes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_rejects_the_unknown_bits_without_their_values() {
+    // Given
+    let (_, _, bytes) = policies_with_unknown_bytes();
+    let policies = Policies::from_bytes(&bytes).expect("Unknown policies are decoded");
+    let mut json = serde_json::to_value(&policies).expect("Policies are serialized");
+    json["unknown_values"]
+        .as_array_mut()
+        .expect("The unknown values are serialized")
+        .pop();
+
+    // When
+    let result = serde_json::from_value::<Policies>(json);
+
+    // Then
+    assert!(result.is_err());
+}
+
+#[test]
+fn unknown_policies_are_invalid() {
+    // Given
+    let (_, _, bytes) = policies_with_unknown_bytes();
+    let policies = Policies::from_bytes(&bytes).expect("Unknown policies are decoded");
+
+    // When
+    let valid = policies.is_valid();
+
+    // Then
+    assert!(!valid);
+    assert!(Policies::new().with_tip(123).is_valid());
+}
@@ -37,7 +37,10 @@ pub use upload::{
     UploadSubsection,
 };
 pub use utxo_id::UtxoId;
-pub use witness::Witness;
+pub use witness::{
+    Witness,
+    WitnessIndex,
+};
 
 pub fn compute_transaction_id<T: fuel_types::canonical::Serialize>(
     chain_id: &fuel_types::ChainId,
@@ -46,7 +49,8 @@ pub fn compute_transaction_id<T: fuel_types::canonical::Serialize>(
     let mut hasher = fuel_crypto::Hasher::default();
     // chain ID
     hasher.input(chain_id.to_be_bytes());
-    // transaction bytes
-    hasher.input(tx.to_bytes().as_slice());
+    // transaction bytes, streamed into the hasher without the intermediate buffer
+    tx.encode(&mut hasher)
+        .expect("Encoding into the hasher can't fail");
     hasher.finalize()
 }
@@ -96,8 +96,7 @@ where
 
 impl<Body, MetadataBody> UniqueIdentifier for ChargeableTransaction<Body, MetadataBody>
 where
-    Body: PrepareSign,
-    Self: Clone,
+    Body: PrepareSign + Clone,
     Self: ChargeableBody<Body>,
     Self: fuel_types::canonical::Serialize,
 {
@@ -106,11 +105,18 @@ where
             return id;
         }
 
-        let mut clone = self.clone();
+        // Witnesses are not a part of the id, so they are not cloned.
+        let mut clone = Self {
+            body: self.body.clone(),
+            policies: self.policies.clone(),
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.clone(),
+            witnesses: Vec::new(),
+            metadata: None,
+        };
 
         // Empties fields that should be zero during the signing.
         clone.prepare_sign();
-        clone.witnesses_mut().clear();
 
         crate::transaction::compute_transaction_id(chain_id, &mut clone)
     }
@@ -131,7 +137,7 @@ pub(crate) trait UniqueFormatValidityChecks {
 impl<Body, MetadataBody> FormatValidityChecks
     for ChargeableTransaction<Body, MetadataBody>
 where
-    Body: PrepareSign,
+    Body: PrepareSign + Clone,
     Self: Clone,
     Self: ChargeableBody<Body>,
     Self: fuel_types::canonical::Serialize,
@@ -142,7 +148,10 @@ where
         let id = self.id(chain_id);
 
         // There will be at most len(witnesses) signatures to cache
-        let mut recovery_cache = Some(HashMap::with_capacity(self.witnesses().len()));
+        let mut recovery_cache = HashMap::with_capacity(self.witnesses().len());
+        #[cfg(feature = "std")]
+        batch_recover_owners(self.inputs(), self.witnesses(), &id, &mut recovery_cache);
+        let mut recovery_cache = Some(recovery_cache);
 
         self.inputs()
             .iter()
@@ -166,6 +175,57 @@ where
     }
 }
 
+/// Recovers the owners of the distinct signatures of the signed inputs with a single
+/// batch, and caches them by witness index.
+///
+/// Nothing is cached if any of the signatures fails, so the sequential check reports
+/// the failure of the right input.
+#[cfg(feature = "std")]
+fn batch_recover_owners(
+    inputs: &[Input],
+    witnesses: &[Witness],
+    id: &Bytes32,
+    cache: &mut HashMap<u16, fuel_types::Address>,
+) {
+    use fuel_crypto::{
+        Message,
+        Signature,
+    };
+
+    let message = Message::from_bytes_ref(id);
+    let mut batch = Vec::new();
+    let mut batch_indexes = HashMap::<[u8; Signature::LEN], usize>::new();
+    let mut witness_slots = HashMap::<u16, usize>::new();
+
+    for witness_index in inputs.iter().filter_map(Input::witness_index) {
+        if witness_slots.contains_key(&witness_index) {
+            continue
+        }
+        let Some(bytes) = witnesses
+            .get(witness_index as usize)
+            .and_then(|witness| <[u8; Signature::LEN]>::try_from(witness.as_ref()).ok())
+        else {
+            return
+        };
+        let slot = *batch_indexes.entry(bytes).or_insert_with(|| {
+            batch.push((Signature::from_bytes(bytes), *message));
+            batch.len() - 1
+        });
+        witness_slots.insert(witness_index, slot);
+    }
+
+    // A single signature gains nothing from the batch
+    if batch.len() < 2 {
+        return
+    }
+
+    if let Ok(public_keys) = fuel_crypto::batch::verify_recover(&batch) {
+        cache.extend(witness_slots.into_iter().map(|(witness_index, slot)| {
+            (witness_index, Input::owner(&public_keys[slot]))
+        }));
+    }
+}
+
 mod field {
     use super::*;
     use crate::field::ChargeableBody;
@@ -1,5 +1,6 @@
 use crate::{
     transaction::{
+        fee::min_gas,
         field::{
             BytecodeWitnessIndex,
             Salt as SaltField,
@@ -15,6 +16,7 @@ use crate::{
     Chargeable,
     ConsensusParameters,
     Contract,
+    FeeParameters,
     GasCosts,
     Input,
     Output,
@@ -59,7 +61,7 @@ impl CreateMetadata {
         let contract = Contract::try_from(tx)?;
         let contract_root = contract.root();
         let state_root = Contract::initial_state_root(storage_slots.iter());
-        let contract_id = contract.id(salt, &contract_root, &state_root);
+        let contract_id = Contract::id(salt, &contract_root, &state_root);
 
         Ok(Self {
             contract_id,
@@ -85,6 +87,18 @@ impl PrepareSign for CreateBody {
 }
 
 impl Chargeable for Create {
+    fn min_gas(&self, gas_costs: &GasCosts, fee: &FeeParameters) -> Word {
+        // The initial storage slots are new storage of the contract, charged at check
+        // time as the `SWW`s and `SWWQ`s creating slots are charged during execution.
+        let slots = self.body.storage_slots.len() as Word;
+        let storage_gas = gas_costs
+            .new_storage()
+            .if_per_slot_set()
+            .resolve(slots, slots.saturating_mul(StorageSlot::SLOT_SIZE as Word));
+
+        min_gas(self, gas_costs, fee).saturating_add(storage_gas)
+    }
+
     #[inline(always)]
     fn metered_bytes_size(&self) -> usize {
         canonical::Serialize::size(self)
@@ -151,13 +165,7 @@ impl UniqueFormatValidityChecks for Create {
         }
 
         // Verify storage slots are sorted
-        if !self
-            .body
-            .storage_slots
-            .as_slice()
-            .windows(2)
-            .all(|s| s[0] < s[1])
-        {
+        if !Contract::storage_slots_are_sorted(&self.body.storage_slots) {
             return Err(ValidityError::TransactionCreateStorageSlotOrder);
         }
 
@@ -176,7 +184,7 @@ impl UniqueFormatValidityChecks for Create {
                 }
 
                 match input {
-                    Input::Contract(_) => {
+                    Input::Contract(_) | Input::ContractReadOnly(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
                     Input::MessageDataSigned(_) | Input::MessageDataPredicate(_) => {
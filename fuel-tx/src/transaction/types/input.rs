@@ -1,6 +1,7 @@
 use crate::{
     TxPointer,
     UtxoId,
+    WitnessIndex,
 };
 use alloc::{
     string::ToString,
@@ -205,6 +206,11 @@ pub enum Input {
     MessageCoinPredicate(MessageCoinPredicate),
     MessageDataSigned(MessageDataSigned),
     MessageDataPredicate(MessageDataPredicate),
+    /// A contract that the transaction only reads from. Unlike [`Input::Contract`], it
+    /// doesn't require an associated [`Output::Contract`](crate::Output::Contract),
+    /// and any attempt to mutate its state or balances panics with
+    /// [`PanicReason::ContractNotWritable`](fuel_asm::PanicReason::ContractNotWritable).
+    ContractReadOnly(Contract),
 }
 
 impl Default for Input {
@@ -225,9 +231,7 @@ impl Input {
     }
 
     pub fn owner(pk: &PublicKey) -> Address {
-        let owner: [u8; Address::LEN] = pk.hash().into();
-
-        owner.into()
+        pk.address()
     }
 
     pub const fn coin_predicate(
@@ -259,7 +263,7 @@ impl Input {
         amount: Word,
         asset_id: AssetId,
         tx_pointer: TxPointer,
-        witness_index: u16,
+        witness_index: WitnessIndex,
     ) -> Self {
         Self::CoinSigned(CoinSigned {
             utxo_id,
@@ -267,7 +271,7 @@ impl Input {
             amount,
             asset_id,
             tx_pointer,
-            witness_index,
+            witness_index: witness_index.as_u16(),
             predicate_gas_used: Empty::new(),
             predicate: Empty::new(),
             predicate_data: Empty::new(),
@@ -290,19 +294,35 @@ impl Input {
         })
     }
 
+    pub const fn contract_read_only(
+        utxo_id: UtxoId,
+        balance_root: Bytes32,
+        state_root: Bytes32,
+        tx_pointer: TxPointer,
+        contract_id: ContractId,
+    ) -> Self {
+        Self::ContractReadOnly(Contract {
+            utxo_id,
+            balance_root,
+            state_root,
+            tx_pointer,
+            contract_id,
+        })
+    }
+
     pub const fn message_coin_signed(
         sender: Address,
         recipient: Address,
         amount: Word,
         nonce: Nonce,
-        witness_index: u16,
+        witness_index: WitnessIndex,
     ) -> Self {
         Self::MessageCoinSigned(MessageCoinSigned {
             sender,
             recipient,
             amount,
             nonce,
-            witness_index,
+            witness_index: witness_index.as_u16(),
             predicate_gas_used: Empty::new(),
             data: Empty::new(),
             predicate: Empty::new(),
@@ -337,7 +357,7 @@ impl Input {
         recipient: Address,
         amount: Word,
         nonce: Nonce,
-        witness_index: u16,
+        witness_index: WitnessIndex,
         data: Vec<u8>,
     ) -> Self {
         Self::MessageDataSigned(MessageDataSigned {
@@ -345,7 +365,7 @@ impl Input {
             recipient,
             amount,
             nonce,
-            witness_index,
+            witness_index: witness_index.as_u16(),
             data,
             predicate: Empty::new(),
             predicate_data: Empty::new(),
@@ -380,7 +400,8 @@ impl Input {
         match self {
             Self::CoinSigned(CoinSigned { utxo_id, .. })
             | Self::CoinPredicate(CoinPredicate { utxo_id, .. })
-            | Self::Contract(Contract { utxo_id, .. }) => Some(utxo_id),
+            | Self::Contract(Contract { utxo_id, .. })
+            | Self::ContractReadOnly(Contract { utxo_id, .. }) => Some(utxo_id),
             Self::MessageCoinSigned(_) => None,
             Self::MessageCoinPredicate(_) => None,
             Self::MessageDataSigned(_) => None,
@@ -396,7 +417,8 @@ impl Input {
             | Self::MessageCoinPredicate(_)
             | Self::MessageDataSigned(_)
             | Self::MessageDataPredicate(_)
-            | Self::Contract(_) => None,
+            | Self::Contract(_)
+            | Self::ContractReadOnly(_) => None,
         }
     }
 
@@ -411,13 +433,14 @@ impl Input {
             | Input::MessageCoinPredicate(_)
             | Input::MessageDataSigned(_)
             | Input::MessageDataPredicate(_) => Some(base_asset_id),
-            Input::Contract(_) => None,
+            Input::Contract(_) | Input::ContractReadOnly(_) => None,
         }
     }
 
     pub const fn contract_id(&self) -> Option<&ContractId> {
         match self {
-            Self::Contract(Contract { contract_id, .. }) => Some(contract_id),
+            Self::Contract(Contract { contract_id, .. })
+            | Self::ContractReadOnly(Contract { contract_id, .. }) => Some(contract_id),
             _ => None,
         }
     }
@@ -432,7 +455,7 @@ impl Input {
             | Input::MessageDataPredicate(MessageDataPredicate { amount, .. }) => {
                 Some(*amount)
             }
-            Input::Contract(_) => None,
+            Input::Contract(_) | Input::ContractReadOnly(_) => None,
         }
     }
 
@@ -445,6 +468,7 @@ impl Input {
             }
             Input::CoinPredicate(_)
             | Input::Contract(_)
+            | Input::ContractReadOnly(_)
             | Input::MessageCoinPredicate(_)
             | Input::MessageDataPredicate(_) => None,
         }
@@ -461,6 +485,7 @@ impl Input {
             }
             Input::CoinSigned(_)
             | Input::Contract(_)
+            | Input::ContractReadOnly(_)
             | Input::MessageCoinSigned(_)
             | Input::MessageDataSigned(_) => None,
         }
@@ -477,6 +502,7 @@ impl Input {
             }
             Input::CoinSigned(_)
             | Input::Contract(_)
+            | Input::ContractReadOnly(_)
             | Input::MessageCoinSigned(_)
             | Input::MessageDataSigned(_) => None,
         }
@@ -492,7 +518,7 @@ impl Input {
             Input::CoinSigned(_)
             | Input::MessageCoinSigned(_)
             | Input::MessageDataSigned(_) => Some(0),
-            Input::Contract(_) => None,
+            Input::Contract(_) | Input::ContractReadOnly(_) => None,
         }
     }
 
@@ -508,7 +534,7 @@ impl Input {
             Input::CoinSigned(_)
             | Input::MessageCoinSigned(_)
             | Input::MessageDataSigned(_) => Some(0),
-            Input::Contract(_) => None,
+            Input::Contract(_) | Input::ContractReadOnly(_) => None,
         }
     }
 
@@ -528,7 +554,8 @@ impl Input {
             Input::CoinSigned(_)
             | Input::MessageCoinSigned(_)
             | Input::MessageDataSigned(_)
-            | Input::Contract(_) => None,
+            | Input::Contract(_)
+            | Input::ContractReadOnly(_) => None,
         }
     }
 
@@ -546,7 +573,8 @@ impl Input {
         match self {
             Input::CoinSigned(CoinSigned { tx_pointer, .. })
             | Input::CoinPredicate(CoinPredicate { tx_pointer, .. })
-            | Input::Contract(Contract { tx_pointer, .. }) => Some(tx_pointer),
+            | Input::Contract(Contract { tx_pointer, .. })
+            | Input::ContractReadOnly(Contract { tx_pointer, .. }) => Some(tx_pointer),
             _ => None,
         }
     }
@@ -664,8 +692,13 @@ impl Input {
         matches!(self, Input::MessageDataPredicate(_))
     }
 
+    /// Returns `true` for both writable and read-only contract inputs.
     pub const fn is_contract(&self) -> bool {
-        matches!(self, Input::Contract(_))
+        matches!(self, Input::Contract(_) | Input::ContractReadOnly(_))
+    }
+
+    pub const fn is_contract_read_only(&self) -> bool {
+        matches!(self, Input::ContractReadOnly(_))
     }
 
     pub const fn coin_predicate_offset() -> usize {
@@ -678,14 +711,18 @@ impl Input {
 
     pub const fn balance_root(&self) -> Option<&Bytes32> {
         match self {
-            Input::Contract(Contract { balance_root, .. }) => Some(balance_root),
+            Input::Contract(Contract { balance_root, .. })
+            | Input::ContractReadOnly(Contract { balance_root, .. }) => {
+                Some(balance_root)
+            }
             _ => None,
         }
     }
 
     pub const fn state_root(&self) -> Option<&Bytes32> {
         match self {
-            Input::Contract(Contract { state_root, .. }) => Some(state_root),
+            Input::Contract(Contract { state_root, .. })
+            | Input::ContractReadOnly(Contract { state_root, .. }) => Some(state_root),
             _ => None,
         }
     }
@@ -731,7 +768,9 @@ impl Input {
         match self {
             Input::CoinSigned(coin) => coin.prepare_sign(),
             Input::CoinPredicate(coin) => coin.prepare_sign(),
-            Input::Contract(contract) => contract.prepare_sign(),
+            Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                contract.prepare_sign()
+            }
             Input::MessageCoinSigned(message) => message.prepare_sign(),
             Input::MessageCoinPredicate(message) => message.prepare_sign(),
             Input::MessageDataSigned(message) => message.prepare_sign(),
@@ -749,6 +788,13 @@ impl Input {
         compute_message_id(sender, recipient, nonce, amount, data)
     }
 
+    /// Returns the owner of the coins and messages spendable by the predicate `code`.
+    ///
+    /// The owner is `sha256(ContractId::SEED || root)`, where `root` is the binary
+    /// Merkle root of the code split into 16 KiB leaves, the same root as of the
+    /// contract bytecode. The owner doesn't depend on the chain, so the same predicate
+    /// owns the same address on every network. An empty `predicate` has an owner too,
+    /// but inputs with an empty predicate are rejected by the checks.
     pub fn predicate_owner<P>(predicate: P) -> Address
     where
         P: AsRef<[u8]>,
@@ -769,7 +815,7 @@ impl Input {
     where
         P: AsRef<[u8]>,
     {
-        owner == &Self::predicate_owner(predicate)
+        owner.ct_eq(&Self::predicate_owner(predicate))
     }
 }
 
@@ -780,7 +826,9 @@ impl Serialize for Input {
             match self {
                 Input::CoinSigned(coin) => coin.size_static(),
                 Input::CoinPredicate(coin) => coin.size_static(),
-                Input::Contract(contract) => contract.size_static(),
+                Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                    contract.size_static()
+                }
                 Input::MessageCoinSigned(message) => message.size_static(),
                 Input::MessageCoinPredicate(message) => message.size_static(),
                 Input::MessageDataSigned(message) => message.size_static(),
@@ -793,7 +841,9 @@ impl Serialize for Input {
         match self {
             Input::CoinSigned(coin) => coin.size_dynamic(),
             Input::CoinPredicate(coin) => coin.size_dynamic(),
-            Input::Contract(contract) => contract.size_dynamic(),
+            Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                contract.size_dynamic()
+            }
             Input::MessageCoinSigned(message) => message.size_dynamic(),
             Input::MessageCoinPredicate(message) => message.size_dynamic(),
             Input::MessageDataSigned(message) => message.size_dynamic(),
@@ -807,7 +857,9 @@ impl Serialize for Input {
         match self {
             Input::CoinSigned(coin) => coin.encode_static(buffer),
             Input::CoinPredicate(coin) => coin.encode_static(buffer),
-            Input::Contract(contract) => contract.encode_static(buffer),
+            Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                contract.encode_static(buffer)
+            }
             Input::MessageCoinSigned(message) => message.encode_static(buffer),
             Input::MessageCoinPredicate(message) => message.encode_static(buffer),
             Input::MessageDataSigned(message) => message.encode_static(buffer),
@@ -821,7 +873,9 @@ impl Serialize for Input {
         match self {
             Input::CoinSigned(coin) => coin.encode_dynamic(buffer),
             Input::CoinPredicate(coin) => coin.encode_dynamic(buffer),
-            Input::Contract(contract) => contract.encode_dynamic(buffer),
+            Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                contract.encode_dynamic(buffer)
+            }
             Input::MessageCoinSigned(message) => message.encode_dynamic(buffer),
             Input::MessageCoinPredicate(message) => message.encode_dynamic(buffer),
             Input::MessageDataSigned(message) => message.encode_dynamic(buffer),
@@ -850,6 +904,10 @@ impl Deserialize for Input {
                     let contract = Contract::decode_static(buffer)?;
                     Input::Contract(contract)
                 }
+                InputRepr::ContractReadOnly => {
+                    let contract = Contract::decode_static(buffer)?;
+                    Input::ContractReadOnly(contract)
+                }
                 InputRepr::Message => {
                     let message = FullMessage::decode_static(buffer)?;
                     match (
@@ -881,7 +939,9 @@ impl Deserialize for Input {
         match self {
             Input::CoinSigned(coin) => coin.decode_dynamic(buffer),
             Input::CoinPredicate(coin) => coin.decode_dynamic(buffer),
-            Input::Contract(contract) => contract.decode_dynamic(buffer),
+            Input::Contract(contract) | Input::ContractReadOnly(contract) => {
+                contract.decode_dynamic(buffer)
+            }
             Input::MessageCoinSigned(message) => message.decode_dynamic(buffer),
             Input::MessageCoinPredicate(message) => message.decode_dynamic(buffer),
             Input::MessageDataSigned(message) => message.decode_dynamic(buffer),
@@ -1012,6 +1072,23 @@ pub mod typescript {
             })))
         }
 
+        #[wasm_bindgen]
+        pub fn contract_read_only(
+            utxo_id: UtxoId,
+            balance_root: Bytes32,
+            state_root: Bytes32,
+            tx_pointer: TxPointer,
+            contract_id: ContractId,
+        ) -> Input {
+            Input(Box::new(crate::Input::ContractReadOnly(Contract {
+                utxo_id,
+                balance_root,
+                state_root,
+                tx_pointer,
+                contract_id,
+            })))
+        }
+
         #[wasm_bindgen]
         pub fn message_coin_signed(
             sender: Address,
@@ -60,3 +60,26 @@ fn test_input_serialization() {
     let input2 = Input::from_bytes(&bytes).unwrap();
     assert_eq!(input, input2);
 }
+
+#[test]
+fn test_input_serde_variant_index() {
+    let input = Input::message_coin_signed(
+        Address::from([2u8; 32]),
+        Address::from([3u8; 32]),
+        5,
+        Nonce::from([6u8; 32]),
+        WitnessIndex::new(0),
+    );
+
+    let bytes = postcard::to_allocvec(&input).unwrap();
+
+    // `MessageCoinSigned` is the fourth variant since the first version of the inputs,
+    // the read-only contracts being appended after the last one.
+    assert_eq!(bytes[0], 3);
+    assert_eq!(postcard::from_bytes::<Input>(&bytes).unwrap(), input);
+
+    let read_only = Input::ContractReadOnly(Default::default());
+    let bytes = postcard::to_allocvec(&read_only).unwrap();
+    assert_eq!(bytes[0], 7);
+    assert_eq!(postcard::from_bytes::<Input>(&bytes).unwrap(), read_only);
+}
@@ -53,11 +53,11 @@ impl UpgradeMetadata {
                 witness_index,
                 checksum,
             } => {
-                let index = *witness_index as usize;
-                let witness = tx
-                    .witnesses
-                    .get(index)
-                    .ok_or(ValidityError::InputWitnessIndexBounds { index })?;
+                let witness = tx.witnesses.get(*witness_index as usize).ok_or(
+                    ValidityError::TransactionWitnessIndexBounds {
+                        witness_index: *witness_index,
+                    },
+                )?;
 
                 let serialized_consensus_parameters = witness.as_vec();
                 let actual_checksum = Hasher::hash(serialized_consensus_parameters);
@@ -219,7 +219,7 @@ impl UniqueFormatValidityChecks for Upgrade {
                 }
 
                 match input {
-                    Input::Contract(_) => {
+                    Input::Contract(_) | Input::ContractReadOnly(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
                     Input::MessageDataSigned(_) | Input::MessageDataPredicate(_) => {
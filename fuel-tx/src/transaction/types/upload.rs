@@ -188,11 +188,11 @@ impl UniqueFormatValidityChecks for Upload {
             return Err(ValidityError::TransactionUploadTooManyBytecodeSubsections);
         }
 
-        let index = self.body.witness_index as usize;
+        let witness_index = self.body.witness_index;
         let witness = self
             .witnesses
-            .get(index)
-            .ok_or(ValidityError::InputWitnessIndexBounds { index })?;
+            .get(witness_index as usize)
+            .ok_or(ValidityError::TransactionWitnessIndexBounds { witness_index })?;
 
         let proof_set = self
             .body
@@ -230,7 +230,7 @@ impl UniqueFormatValidityChecks for Upload {
                 }
 
                 match input {
-                    Input::Contract(_) => {
+                    Input::Contract(_) | Input::ContractReadOnly(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
                     Input::MessageDataSigned(_) | Input::MessageDataPredicate(_) => {
@@ -11,6 +11,7 @@ use crate::{
 use fuel_crypto::{
     Message,
     Signature,
+    SignatureMode,
 };
 
 #[cfg(feature = "random")]
@@ -33,6 +34,11 @@ pub struct Witness {
 }
 
 impl Witness {
+    /// The handling of the high `s` signatures by [`Self::recover_witness`], and so by
+    /// the signature check of the signed inputs. The malleated form of a signature is
+    /// accepted, so the signatures must not be compared byte-wise.
+    pub const SIGNATURE_MODE: SignatureMode = SignatureMode::Lax;
+
     pub const fn as_vec(&self) -> &Vec<u8> {
         &self.data
     }
@@ -58,7 +64,7 @@ impl Witness {
         let message = Message::from_bytes_ref(txhash);
 
         signature
-            .recover(message)
+            .recover_with_mode(message, Self::SIGNATURE_MODE)
             .map_err(|_| ValidityError::InputInvalidSignature { index: input_index })
             .map(|pk| Input::owner(&pk))
     }
@@ -106,6 +112,75 @@ impl Distribution<Witness> for Standard {
     }
 }
 
+/// The index of a witness in the `witnesses` of the transaction, referenced by signed
+/// inputs.
+///
+/// It is serialized like the `u16` it wraps. The [`TransactionBuilder`] returns it from
+/// [`TransactionBuilder::push_witness`], so the index always points at an existing
+/// witness of the builder.
+///
+/// [`TransactionBuilder`]: crate::TransactionBuilder
+/// [`TransactionBuilder::push_witness`]: crate::TransactionBuilder::push_witness
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    derive_more::Display,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+#[repr(transparent)]
+pub struct WitnessIndex(u16);
+
+impl WitnessIndex {
+    /// Wraps the raw index of the witness.
+    pub const fn new(index: u16) -> Self {
+        Self(index)
+    }
+
+    /// Returns the raw index as stored in the inputs.
+    pub const fn as_u16(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the index to look up the witness in the `witnesses` vector.
+    pub const fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<u16> for WitnessIndex {
+    fn from(index: u16) -> Self {
+        Self(index)
+    }
+}
+
+impl From<WitnessIndex> for u16 {
+    fn from(index: WitnessIndex) -> Self {
+        index.0
+    }
+}
+
+impl From<WitnessIndex> for usize {
+    fn from(index: WitnessIndex) -> Self {
+        index.as_usize()
+    }
+}
+
+#[cfg(feature = "random")]
+impl Distribution<WitnessIndex> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> WitnessIndex {
+        WitnessIndex(rng.gen())
+    }
+}
+
 #[cfg(feature = "typescript")]
 pub mod typescript {
     use wasm_bindgen::prelude::*;
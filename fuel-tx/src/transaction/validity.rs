@@ -1,5 +1,8 @@
 use crate::{
-    field::Maturity,
+    field::{
+        Expiration,
+        Maturity,
+    },
     input::{
         coin::{
             CoinPredicate,
@@ -38,8 +41,10 @@ use fuel_types::{
     Bytes32,
     ChainId,
 };
-use hashbrown::HashMap;
-use itertools::Itertools;
+use hashbrown::{
+    HashMap,
+    HashSet,
+};
 
 mod error;
 
@@ -89,9 +94,12 @@ impl Input {
             }) => {
                 // Helper function for recovering the address from a witness
                 let recover_address = || -> Result<Address, ValidityError> {
-                    let witness = witnesses
-                        .get(*witness_index as usize)
-                        .ok_or(ValidityError::InputWitnessIndexBounds { index })?;
+                    let witness = witnesses.get(*witness_index as usize).ok_or(
+                        ValidityError::InputWitnessIndexBounds {
+                            index,
+                            witness_index: *witness_index,
+                        },
+                    )?;
 
                     witness.recover_witness(txhash, index)
                 };
@@ -112,7 +120,7 @@ impl Input {
                     recover_address()?
                 };
 
-                if owner != &recovered_address {
+                if !owner.ct_eq(&recovered_address) {
                     return Err(ValidityError::InputInvalidSignature { index });
                 }
 
@@ -132,7 +140,11 @@ impl Input {
                 predicate,
                 ..
             }) if !Input::is_predicate_owner_valid(owner, predicate) => {
-                Err(ValidityError::InputPredicateOwner { index })
+                Err(ValidityError::InputPredicateOwner {
+                    index,
+                    expected: Input::predicate_owner(predicate),
+                    actual: *owner,
+                })
             }
 
             _ => Ok(()),
@@ -180,7 +192,10 @@ impl Input {
             | Self::MessageDataSigned(MessageDataSigned { witness_index, .. })
                 if *witness_index as usize >= witnesses.len() =>
             {
-                Err(ValidityError::InputWitnessIndexBounds { index })
+                Err(ValidityError::InputWitnessIndexBounds {
+                    index,
+                    witness_index: *witness_index,
+                })
             }
 
             // ∀ inputContract ∃! outputContract : outputContract.inputIndex =
@@ -355,6 +370,12 @@ where
         Err(ValidityError::TransactionMaturity)?;
     }
 
+    if let Some(expiration) = tx.expiration() {
+        if expiration < block_height {
+            Err(ValidityError::TransactionExpired)?;
+        }
+    }
+
     if tx.inputs().len() > tx_params.max_inputs() as usize {
         Err(ValidityError::TransactionInputsMax)?
     }
@@ -374,7 +395,8 @@ where
         | Input::MessageCoinPredicate(_) => true,
         Input::MessageDataSigned(_)
         | Input::MessageDataPredicate(_)
-        | Input::Contract(_) => false,
+        | Input::Contract(_)
+        | Input::ContractReadOnly(_) => false,
     });
 
     if any_spendable_input.is_none() {
@@ -384,52 +406,64 @@ where
     tx.input_asset_ids_unique(base_asset_id)
         .try_for_each(|input_asset_id| {
             // check for duplicate change outputs
-            if tx
+            let duplicated_change = tx
                 .outputs()
                 .iter()
-                .filter_map(|output| match output {
-                    Output::Change { asset_id, .. } if input_asset_id == asset_id => {
-                        Some(())
-                    }
-                    Output::Change { asset_id, .. }
-                        if asset_id != base_asset_id && input_asset_id == asset_id =>
-                    {
-                        Some(())
-                    }
-                    _ => None,
+                .enumerate()
+                .filter(|(_, output)| {
+                    matches!(
+                        output,
+                        Output::Change { asset_id, .. } if input_asset_id == asset_id
+                    )
                 })
-                .count()
-                > 1
-            {
-                return Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(
-                    *input_asset_id,
-                ));
+                .nth(1);
+
+            if let Some((index, _)) = duplicated_change {
+                return Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+                    index,
+                    asset_id: *input_asset_id,
+                });
             }
 
             Ok(())
         })?;
 
     // Check for duplicated input utxo id
-    let duplicated_utxo_id = tx
-        .inputs()
-        .iter()
-        .filter_map(|i| i.is_coin().then(|| i.utxo_id()).flatten());
+    let duplicated_utxo_id = tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+        i.is_coin()
+            .then(|| i.utxo_id())
+            .flatten()
+            .map(|utxo_id| (index, utxo_id))
+    });
 
-    if let Some(utxo_id) = next_duplicate(duplicated_utxo_id).copied() {
-        return Err(ValidityError::DuplicateInputUtxoId { utxo_id });
+    if let Some((index, utxo_id)) = next_duplicate(duplicated_utxo_id) {
+        return Err(ValidityError::DuplicateInputUtxoId {
+            index,
+            utxo_id: *utxo_id,
+        });
     }
 
     // Check for duplicated input contract id
-    let duplicated_contract_id = tx.inputs().iter().filter_map(Input::contract_id);
-
-    if let Some(contract_id) = next_duplicate(duplicated_contract_id).copied() {
-        return Err(ValidityError::DuplicateInputContractId { contract_id });
+    let duplicated_contract_id =
+        tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+            i.contract_id().map(|contract_id| (index, contract_id))
+        });
+
+    if let Some((index, contract_id)) = next_duplicate(duplicated_contract_id) {
+        return Err(ValidityError::DuplicateInputContractId {
+            index,
+            contract_id: *contract_id,
+        });
     }
 
     // Check for duplicated input message id
-    let duplicated_message_id = tx.inputs().iter().filter_map(Input::message_id);
-    if let Some(message_id) = next_duplicate(duplicated_message_id) {
-        return Err(ValidityError::DuplicateMessageInputId { message_id });
+    let duplicated_message_id =
+        tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+            i.message_id().map(|message_id| (index, message_id))
+        });
+
+    if let Some((index, message_id)) = next_duplicate(duplicated_message_id) {
+        return Err(ValidityError::DuplicateMessageInputId { index, message_id });
     }
 
     // Validate the inputs without checking signature
@@ -456,9 +490,10 @@ where
                     .input_asset_ids(base_asset_id)
                     .any(|input_asset_id| input_asset_id == asset_id)
                 {
-                    return Err(ValidityError::TransactionOutputChangeAssetIdNotFound(
-                        *asset_id,
-                    ));
+                    return Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+                        index,
+                        asset_id: *asset_id,
+                    });
                 }
             }
 
@@ -467,9 +502,10 @@ where
                     .input_asset_ids(base_asset_id)
                     .any(|input_asset_id| input_asset_id == asset_id)
                 {
-                    return Err(ValidityError::TransactionOutputCoinAssetIdNotFound(
-                        *asset_id,
-                    ));
+                    return Err(ValidityError::TransactionOutputCoinAssetIdNotFound {
+                        index,
+                        asset_id: *asset_id,
+                    });
                 }
             }
 
@@ -479,24 +515,15 @@ where
     Ok(())
 }
 
-// TODO https://github.com/FuelLabs/fuel-tx/issues/148
-pub(crate) fn next_duplicate<U>(iter: impl Iterator<Item = U>) -> Option<U>
+/// Returns the first indexed item that is equal to one of the previous items.
+pub(crate) fn next_duplicate<U>(
+    mut iter: impl Iterator<Item = (usize, U)>,
+) -> Option<(usize, U)>
 where
-    U: PartialEq + Ord + Copy + Hash,
+    U: Eq + Copy + Hash,
 {
-    #[cfg(not(feature = "std"))]
-    {
-        iter.sorted()
-            .as_slice()
-            .windows(2)
-            .filter_map(|u| (u[0] == u[1]).then(|| u[0]))
-            .next()
-    }
-
-    #[cfg(feature = "std")]
-    {
-        iter.duplicates().next()
-    }
+    let mut seen = HashSet::new();
+    iter.find(|(_, item)| !seen.insert(*item))
 }
 
 #[cfg(feature = "typescript")]
@@ -1,5 +1,6 @@
 use crate::UtxoId;
 use fuel_types::{
+    Address,
     AssetId,
     ContractId,
     MessageId,
@@ -14,69 +15,129 @@ pub enum ValidityError {
     TransactionMetadataMismatch,
     /// Transaction doesn't have spendable input message or coin.
     NoSpendableInput,
+    /// The input at `index` refers to the witness that doesn't exist.
+    #[display(
+        fmt = "Input {} refers to the witness {} that doesn't exist",
+        index,
+        witness_index
+    )]
     InputWitnessIndexBounds {
+        /// The index of the input
         index: usize,
+        /// The index of the missing witness
+        witness_index: u16,
     },
+    #[display(fmt = "Input {} has an empty predicate", index)]
     InputPredicateEmpty {
         index: usize,
     },
+    #[display(fmt = "Input {} has a predicate that exceeds the length limit", index)]
     InputPredicateLength {
         index: usize,
     },
+    #[display(
+        fmt = "Input {} has a predicate data that exceeds the length limit",
+        index
+    )]
     InputPredicateDataLength {
         index: usize,
     },
+    #[display(
+        fmt = "Input {} has the owner {} that doesn't match the owner {} of the predicate",
+        index,
+        actual,
+        expected
+    )]
     InputPredicateOwner {
         index: usize,
+        /// The owner derived from the predicate code with [`Input::predicate_owner`].
+        ///
+        /// [`Input::predicate_owner`]: crate::Input::predicate_owner
+        expected: Address,
+        /// The owner declared by the input.
+        actual: Address,
     },
+    #[display(fmt = "Input {} has an invalid signature", index)]
     InputInvalidSignature {
         index: usize,
     },
+    #[display(
+        fmt = "Input {} doesn't have exactly one associated contract output",
+        index
+    )]
     InputContractAssociatedOutputContract {
         index: usize,
     },
+    #[display(
+        fmt = "Input {} has a message data that exceeds the length limit",
+        index
+    )]
     InputMessageDataLength {
         index: usize,
     },
+    /// The input at `index` spends the same UTXO as one of the previous inputs.
+    #[display(fmt = "Input {} spends the duplicated UTXO {}", index, utxo_id)]
     DuplicateInputUtxoId {
+        /// The index of the duplicated input
+        index: usize,
         utxo_id: UtxoId,
     },
+    /// The input at `index` spends the same message as one of the previous inputs.
+    #[display(fmt = "Input {} spends the duplicated message {}", index, message_id)]
     DuplicateMessageInputId {
+        /// The index of the duplicated input
+        index: usize,
         message_id: MessageId,
     },
+    /// The input at `index` uses the same contract as one of the previous inputs.
+    #[display(fmt = "Input {} uses the duplicated contract {}", index, contract_id)]
     DuplicateInputContractId {
+        /// The index of the duplicated input
+        index: usize,
         contract_id: ContractId,
     },
+    #[display(fmt = "Output {} refers to an invalid contract input", index)]
     OutputContractInputIndex {
         index: usize,
     },
     /// One of inputs' `AssetId` is not base asset id.
+    #[display(fmt = "Input {} uses a non-base asset id", index)]
     TransactionInputContainsNonBaseAssetId {
         index: usize,
     },
     /// One of inputs is a `Input::Contract` when it is not allowed.
+    #[display(fmt = "Input {} is a contract input that is not allowed", index)]
     TransactionInputContainsContract {
         index: usize,
     },
     /// One of inputs contains retryable message when it is not allowed.
+    #[display(fmt = "Input {} is a message with data that is not allowed", index)]
     TransactionInputContainsMessageData {
         index: usize,
     },
     /// One of outputs is a `Output::Contract` when it is not allowed.
+    #[display(fmt = "Output {} is a contract output that is not allowed", index)]
     TransactionOutputContainsContract {
         index: usize,
     },
     /// One of outputs is a `Output::Variable` when it is not allowed.
+    #[display(fmt = "Output {} is a variable output that is not allowed", index)]
     TransactionOutputContainsVariable {
         index: usize,
     },
     /// One of `Output::Change` outputs uses a non-base asset id.
+    #[display(fmt = "Output {} is a change output of a non-base asset", index)]
     TransactionChangeChangeUsesNotBaseAsset {
         index: usize,
     },
+    #[display(
+        fmt = "Output {} is a created contract output that doesn't match the transaction",
+        index
+    )]
     TransactionCreateOutputContractCreatedDoesntMatch {
         index: usize,
     },
+    #[display(fmt = "Output {} is one of multiple created contract outputs", index)]
     TransactionCreateOutputContractCreatedMultiple {
         index: usize,
     },
@@ -87,6 +148,10 @@ pub enum ValidityError {
     TransactionScriptLength,
     TransactionScriptDataLength,
     /// The output contains a `Output::ContractCreated` which is not allowed.
+    #[display(
+        fmt = "Output {} is a created contract output that is not allowed",
+        index
+    )]
     TransactionOutputContainsContractCreated {
         index: usize,
     },
@@ -107,6 +172,15 @@ pub enum ValidityError {
     TransactionUpgradeConsensusParametersSerialization,
     /// The `Upgrade` transaction's consensus parameters deserialization failed.
     TransactionUpgradeConsensusParametersDeserialization,
+    /// The body of the transaction refers to the witness that doesn't exist.
+    #[display(
+        fmt = "The transaction refers to the witness {} that doesn't exist",
+        witness_index
+    )]
+    TransactionWitnessIndexBounds {
+        /// The index of the missing witness
+        witness_index: u16,
+    },
     /// The verification of the bytecode root of the `Upload` transaction failed.
     TransactionUploadRootVerificationFailed,
     /// The total number of bytecode subsections in the `Upload` transaction exceeds the
@@ -120,15 +194,47 @@ pub enum ValidityError {
     TransactionPoliciesAreInvalid,
     TransactionNoGasPricePolicy,
     TransactionMaturity,
+    /// The block height of the checking is above the transaction's expiration.
+    TransactionExpired,
     TransactionMaxFeeNotSet,
     TransactionInputsMax,
     TransactionOutputsMax,
     TransactionWitnessesMax,
-    TransactionOutputChangeAssetIdDuplicated(AssetId),
-    TransactionOutputChangeAssetIdNotFound(AssetId),
+    /// The change output at `index` uses the same asset as one of the previous change
+    /// outputs.
+    #[display(
+        fmt = "Output {} is a duplicated change output of the asset {}",
+        index,
+        asset_id
+    )]
+    TransactionOutputChangeAssetIdDuplicated {
+        /// The index of the duplicated change output
+        index: usize,
+        asset_id: AssetId,
+    },
+    /// The change output at `index` uses an asset that doesn't exist in the inputs.
+    #[display(
+        fmt = "Output {} is a change output of the asset {} missing in the inputs",
+        index,
+        asset_id
+    )]
+    TransactionOutputChangeAssetIdNotFound {
+        /// The index of the change output
+        index: usize,
+        asset_id: AssetId,
+    },
     /// This error happens when a transaction attempts to create a coin output for an
     /// asset type that doesn't exist in the coin inputs.
-    TransactionOutputCoinAssetIdNotFound(AssetId),
+    #[display(
+        fmt = "Output {} is a coin output of the asset {} missing in the inputs",
+        index,
+        asset_id
+    )]
+    TransactionOutputCoinAssetIdNotFound {
+        /// The index of the coin output
+        index: usize,
+        asset_id: AssetId,
+    },
     /// The transaction doesn't provide enough input amount of the native chain asset to
     /// cover all potential execution fees
     #[display(
@@ -158,6 +264,12 @@ pub enum ValidityError {
         /// The total amount provided by coin inputs
         provided: u64,
     },
+    /// The inputs don't cover the coin outputs and the max fee for some assets.
+    #[display(fmt = "Unbalanced assets: {:?}", deficits)]
+    UnbalancedAssets {
+        /// Each unbalanced asset with the amount missing from the inputs.
+        deficits: alloc::vec::Vec<(AssetId, u64)>,
+    },
     /// The given coins is too large
     BalanceOverflow,
     /// The given gas costs is are too large
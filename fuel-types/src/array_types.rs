@@ -33,6 +33,9 @@ macro_rules! key {
     ($i:ident, $s:expr) => {
         #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         /// FuelVM atomic array type.
+        ///
+        /// `==` may return as soon as a byte differs, use [`Self::ct_eq`] to compare
+        /// secret values.
         #[repr(transparent)]
         #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
         #[derive(
@@ -54,6 +57,9 @@ macro_rules! key {
 macro_rules! key_with_big_array {
     ($i:ident, $s:expr) => {
         /// FuelVM atomic type.
+        ///
+        /// `==` may return as soon as a byte differs, use [`Self::ct_eq`] to compare
+        /// secret values.
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         #[repr(transparent)]
         #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
@@ -139,6 +145,11 @@ macro_rules! key_methods {
             pub const fn size(&self) -> usize {
                 Self::LEN
             }
+
+            /// Equality in time independent of the bytes of `self` and `other`.
+            pub fn ct_eq(&self, other: &Self) -> bool {
+                subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..]).into()
+            }
         }
 
         #[cfg(feature = "typescript")]
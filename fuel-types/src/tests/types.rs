@@ -260,3 +260,42 @@ fn test_key_types_hex_serialization() {
         serde_json::to_string(&bytes64).expect("Failed to serialize Bytes64");
     assert_eq!(format!("\"{bytes64}\""), bytes64_to_string);
 }
+
+macro_rules! check_ct_eq {
+    ($i:ident, $r:expr) => {
+        for _ in 0..64 {
+            let a: $i = $r.gen();
+            let b: $i = $r.gen();
+            assert_eq!(a.ct_eq(&b), a == b);
+            assert!(a.ct_eq(&a));
+
+            // Differ by a single bit at the start, the middle or the end
+            for index in [0, $i::LEN / 2, $i::LEN - 1] {
+                let mut c = a;
+                c[index] ^= 1 << $r.gen_range(0..8);
+                assert!(!a.ct_eq(&c));
+                assert_eq!(a.ct_eq(&c), a == c);
+            }
+        }
+
+        assert!($i::zeroed().ct_eq(&$i::zeroed()));
+        assert!(!$i::zeroed().ct_eq(&$i::new([0xff; $i::LEN])));
+    };
+}
+
+#[test]
+fn ct_eq_agrees_with_eq() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    check_ct_eq!(Address, rng);
+    check_ct_eq!(AssetId, rng);
+    check_ct_eq!(ContractId, rng);
+    check_ct_eq!(Bytes4, rng);
+    check_ct_eq!(Bytes8, rng);
+    check_ct_eq!(Bytes20, rng);
+    check_ct_eq!(Bytes32, rng);
+    check_ct_eq!(Nonce, rng);
+    check_ct_eq!(MessageId, rng);
+    check_ct_eq!(Salt, rng);
+    check_ct_eq!(Bytes64, rng);
+}
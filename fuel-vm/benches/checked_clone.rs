@@ -0,0 +1,51 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    FeeParameters,
+    Finalizable,
+    ScriptParameters,
+    TransactionBuilder,
+    TxParameters,
+};
+use fuel_vm::checked_transaction::IntoChecked;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+const SCRIPT_DATA_SIZE: usize = 500 * 1024;
+
+fn checked_clone(c: &mut Criterion) {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let mut params = ConsensusParameters::standard();
+    params.set_tx_params(TxParameters::default().with_max_size(2 * 1024 * 1024));
+    params.set_script_params(
+        ScriptParameters::default().with_max_script_data_length(SCRIPT_DATA_SIZE as u64),
+    );
+    params.set_fee_params(FeeParameters::default().with_gas_per_byte(0));
+
+    let script_data: Vec<u8> = (0..SCRIPT_DATA_SIZE).map(|_| rng.gen::<u8>()).collect();
+    let tx = TransactionBuilder::script(vec![], script_data)
+        .add_random_fee_input()
+        .finalize();
+    let checked = tx
+        .clone()
+        .into_checked_basic(Default::default(), &params)
+        .expect("The transaction should be valid");
+
+    c.bench_function("clone_script_500kib", |b| b.iter(|| black_box(tx.clone())));
+
+    c.bench_function("clone_checked_script_500kib", |b| {
+        b.iter(|| black_box(checked.clone()))
+    });
+}
+
+criterion_group!(benches, checked_clone);
+criterion_main!(benches);
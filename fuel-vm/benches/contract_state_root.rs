@@ -0,0 +1,80 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BatchSize,
+    Criterion,
+};
+use fuel_storage::{
+    StorageAsMut,
+    StorageAsRef,
+};
+use fuel_types::{
+    Bytes32,
+    ContractId,
+};
+use fuel_vm::storage::{
+    ContractsState,
+    MemoryStorage,
+};
+
+const SLOTS: u64 = 10_000;
+const CONTRACT: ContractId = ContractId::new([1; 32]);
+
+fn slot_key(i: u64) -> Bytes32 {
+    let mut key = [0; 32];
+    key[24..].copy_from_slice(&i.to_be_bytes());
+    key.into()
+}
+
+fn write_slots(storage: &mut MemoryStorage, slots: impl Iterator<Item = u64>, value: u8) {
+    for i in slots {
+        storage
+            .storage_as_mut::<ContractsState>()
+            .insert(&(&CONTRACT, &slot_key(i)).into(), &[value; 32])
+            .unwrap();
+    }
+}
+
+fn root(storage: &MemoryStorage) -> [u8; 32] {
+    storage.storage::<ContractsState>().root(&CONTRACT).unwrap()
+}
+
+fn contract_state_root(c: &mut Criterion) {
+    c.bench_function("write_10k_slots_then_root", |b| {
+        b.iter(|| {
+            let mut storage = MemoryStorage::default();
+            write_slots(&mut storage, 0..SLOTS, 1);
+            black_box(root(&storage))
+        })
+    });
+
+    // The tree of the state is cached by the first root, so each iteration updates
+    // only the paths of the rewritten slots.
+    let mut storage = MemoryStorage::default();
+    write_slots(&mut storage, 0..SLOTS, 1);
+    root(&storage);
+    let mut value = 0u8;
+    c.bench_function("rewrite_100_of_10k_slots_then_root", |b| {
+        b.iter(|| {
+            value = value.wrapping_add(1);
+            write_slots(&mut storage, (0..SLOTS).step_by(100), value);
+            black_box(root(&storage))
+        })
+    });
+
+    c.bench_function("root_of_10k_slots_from_scratch", |b| {
+        b.iter_batched(
+            || {
+                let mut storage = MemoryStorage::default();
+                write_slots(&mut storage, 0..SLOTS, 1);
+                storage
+            },
+            |storage| black_box(root(&storage)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, contract_state_root);
+criterion_main!(benches);
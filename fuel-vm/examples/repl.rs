@@ -0,0 +1,26 @@
+//! This example runs an interactive session executing the FuelVM assembly typed on the
+//! standard input, one instruction per line, e.g. `movi $r16 42` then `:regs`.
+
+use std::io::{
+    self,
+    BufRead,
+    Write,
+};
+
+use fuel_vm::repl::ReplSession;
+
+fn main() -> io::Result<()> {
+    let mut session = ReplSession::default();
+    let mut stdout = io::stdout();
+    write!(stdout, "> ")?;
+    stdout.flush()?;
+    for line in io::stdin().lock().lines() {
+        match session.eval(&line?) {
+            Ok(output) => write!(stdout, "{output}")?,
+            Err(error) => writeln!(stdout, "error: {error}")?,
+        }
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+    }
+    writeln!(stdout)
+}
@@ -1,39 +1,36 @@
-#![feature(bench_black_box)]
 #![no_main]
 
 use std::hint::black_box;
 
 use libfuzzer_sys::fuzz_target;
 
-use fuel_vm::prelude::*;
+use fuel_vm::{
+    fuzz_util::{
+        execute_reproducer,
+        make_reproducer,
+        ReproducerGuard,
+    },
+    prelude::*,
+};
 
 #[derive(arbitrary::Arbitrary, Debug)]
 struct FuzzData {
-    program: Vec<Opcode>,
+    program: Vec<u8>,
     script_data: Vec<u8>,
 }
 
 fuzz_target!(|data: FuzzData| {
-    let mut client = MemoryClient::default();
-
-    let gas_price = 0;
-    let gas_limit = 1_000;
-    let maturity = Default::default();
-    let height = Default::default();
-    let params = ConsensusParameters::DEFAULT;
-
-    let tx = Transaction::script(
-        gas_price,
-        gas_limit,
-        maturity,
-        data.program.iter().copied().collect(),
-        data.script_data,
-        vec![],
-        vec![],
-        vec![],
-    )
-    .into_checked(height, &params)
-    .expect("failed to generate a checked tx");
-
-    drop(black_box(client.transact(tx)));
+    let params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(data.program, data.script_data)
+        .script_gas_limit(1_000)
+        .add_random_fee_input()
+        .finalize()
+        .into();
+    let reproducer = make_reproducer(&params, &MemoryStorage::default(), &tx, 0);
+
+    // The reproducer is left in the working directory if the execution panics or
+    // times out, to be replayed with `execute_reproducer`.
+    let _guard = ReproducerGuard::new("grammar_aware.repro", &reproducer)
+        .expect("failed to save the reproducer");
+    black_box(execute_reproducer(&reproducer));
 });
@@ -9,26 +9,47 @@ use alloc::{
 
 use crate::{
     call::CallFrame,
+    constraints::InstructionLocation,
     consts::*,
     interpreter::{
         InitialBalances,
         Interpreter,
     },
+    source_map::SourceMap,
 };
+use core::fmt;
 use derivative::Derivative;
+use fuel_asm::RegId;
 
 use crate::interpreter::Memory;
 use fuel_tx::ScriptExecutionResult;
 use fuel_types::{
+    AssetId,
     ContractId,
     Word,
 };
 
+/// A contract call of the call chain of a [`Backtrace`], captured from its call frame.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BacktraceCall {
+    /// The called contract.
+    pub contract: ContractId,
+    /// The forwarded asset.
+    pub asset_id: AssetId,
+    /// The forwarded amount of the asset.
+    pub amount: Word,
+    /// The first parameter of the call, usually the function selector.
+    pub a: Word,
+    /// The second parameter of the call, usually the arguments.
+    pub b: Word,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 /// Runtime description derived from a VM error.
 pub struct Backtrace {
     call_stack: Vec<CallFrame>,
+    call_chain: Vec<BacktraceCall>,
     contract: ContractId,
     registers: [Word; VM_REGISTER_COUNT],
     memory: Memory,
@@ -52,8 +73,28 @@ impl Backtrace {
 
         registers.copy_from_slice(vm.registers());
 
+        // The amount forwarded to a frame is the `$bal` of its context, saved in the
+        // next frame or still in the registers for the last one.
+        let call_chain = call_stack
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let callee_registers = call_stack
+                    .get(index + 1)
+                    .map_or(&registers[..], |next| next.registers());
+                BacktraceCall {
+                    contract: *frame.to(),
+                    asset_id: *frame.asset_id(),
+                    amount: callee_registers[RegId::BAL],
+                    a: frame.a(),
+                    b: frame.b(),
+                }
+            })
+            .collect();
+
         Self {
             call_stack,
+            call_chain,
             contract,
             registers,
             memory,
@@ -67,6 +108,11 @@ impl Backtrace {
         self.call_stack.as_slice()
     }
 
+    /// The contract calls leading to the error, from the one made by the script.
+    pub fn call_chain(&self) -> &[BacktraceCall] {
+        self.call_chain.as_slice()
+    }
+
     /// Last contract of the context when the error occurred.
     pub const fn contract(&self) -> &ContractId {
         &self.contract
@@ -92,6 +138,37 @@ impl Backtrace {
         &self.initial_balances
     }
 
+    /// The locations of the execution when the error occurred, from the failing
+    /// instruction to the calls of the script.
+    pub fn locations(&self) -> Vec<InstructionLocation> {
+        let location = |context: Option<&CallFrame>, registers: &[Word]| {
+            InstructionLocation::new(
+                context.map(|frame| *frame.to()),
+                registers[RegId::PC].saturating_sub(registers[RegId::IS]),
+            )
+        };
+        let current = location(self.call_stack.last(), &self.registers);
+        let callers = self
+            .call_stack
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, frame)| {
+                let caller = index.checked_sub(1).map(|caller| &self.call_stack[caller]);
+                location(caller, frame.registers())
+            });
+        core::iter::once(current).chain(callers).collect()
+    }
+
+    /// Renders the [`locations`](Self::locations) of the backtrace with their location
+    /// in the source code, when the `source_map` has it.
+    pub fn display<'a>(&'a self, source_map: &'a SourceMap) -> BacktraceDisplay<'a> {
+        BacktraceDisplay {
+            backtrace: self,
+            source_map,
+        }
+    }
+
     /// Expose the internal attributes of the backtrace.
     pub fn into_inner(
         self,
@@ -106,6 +183,7 @@ impl Backtrace {
         let Self {
             call_stack,
             contract,
+            call_chain: _,
             registers,
             memory,
             result,
@@ -122,3 +200,47 @@ impl Backtrace {
         )
     }
 }
+
+/// A [`Backtrace`] rendered with a [`SourceMap`] by [`Backtrace::display`].
+pub struct BacktraceDisplay<'a> {
+    backtrace: &'a Backtrace,
+    source_map: &'a SourceMap,
+}
+
+impl fmt::Display for BacktraceDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?}", self.backtrace.result)?;
+        // The locations are in the calls, from the last one, then in the script.
+        let mut calls = self.backtrace.call_chain.iter().rev();
+        for (index, location) in self.backtrace.locations().iter().enumerate() {
+            match self.source_map.get(location) {
+                Some(source) => writeln!(f, "{index:>4}: {source}")?,
+                None => match location.context() {
+                    Some(contract) => writeln!(
+                        f,
+                        "{index:>4}: contract {contract} at offset {}",
+                        location.offset()
+                    )?,
+                    None => {
+                        writeln!(f, "{index:>4}: script at offset {}", location.offset())?
+                    }
+                },
+            }
+            if let Some(call) = calls.next() {
+                writeln!(
+                    f,
+                    "      call of {} with a {}, b {}, forwarding {} of {}",
+                    call.contract, call.a, call.b, call.amount, call.asset_id
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders the backtrace without a [`SourceMap`], like [`Backtrace::display`].
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(&SourceMap::new()).fmt(f)
+    }
+}
@@ -0,0 +1,41 @@
+//! Benchmarks the opcodes in the interpreter, printing the fitted gas costs as JSON and
+//! the comparison with the current defaults on the standard error.
+//!
+//! ```text
+//! cargo run --release --features gas-bench --bin gas-bench [OPCODE..] > gas_costs.json
+//! ```
+//!
+//! The opcodes, like `MCP`, restrict the benchmarked opcodes.
+
+use std::process::ExitCode;
+
+use fuel_asm::Opcode;
+use fuel_vm::gas_bench::{
+    GasBench,
+    GasBenchConfig,
+};
+
+fn main() -> ExitCode {
+    let mut opcodes = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match Opcode::ALL
+            .iter()
+            .find(|opcode| opcode.mnemonic().eq_ignore_ascii_case(&arg))
+        {
+            Some(opcode) => opcodes.push(*opcode),
+            None => {
+                eprintln!("Unknown opcode {arg}");
+                return ExitCode::FAILURE
+            }
+        }
+    }
+
+    let mut config = GasBenchConfig::default();
+    if !opcodes.is_empty() {
+        config = config.with_opcodes(opcodes);
+    }
+    let report = GasBench::new(config).run();
+    eprint!("{report}");
+    println!("{}", report.to_json());
+    ExitCode::SUCCESS
+}
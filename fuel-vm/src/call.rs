@@ -63,12 +63,17 @@ impl Call {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Call frame representation in the VM stack.
 ///
 /// <https://github.com/FuelLabs/fuel-specs/blob/master/src/fuel-vm/index.md#call-frames>
 pub struct CallFrame {
     to: ContractId,
     asset_id: AssetId,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<[serde_with::Same; VM_REGISTER_COUNT]>")
+    )]
     registers: [Word; VM_REGISTER_COUNT],
     code_size: usize,
     a: Word,
@@ -21,6 +21,7 @@ use fuel_types::{
 
 use alloc::{
     boxed::Box,
+    sync::Arc,
     vec::Vec,
 };
 use core::{
@@ -35,7 +36,7 @@ use fuel_tx::{
 };
 
 mod balances;
-#[cfg(feature = "test-helpers")]
+#[cfg(feature = "builder")]
 pub mod builder;
 pub mod types;
 
@@ -49,6 +50,7 @@ use crate::{
 bitflags::bitflags! {
     /// Possible types of transaction checks.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Checks: u32 {
         /// Basic checks defined in the specification for each transaction:
         /// https://github.com/FuelLabs/fuel-specs/blob/master/src/tx-format/transaction.md#transaction
@@ -58,6 +60,9 @@ bitflags::bitflags! {
         const Signatures    = 0b00000010;
         /// Check that predicate in the transactions are valid.
         const Predicates    = 0b00000100;
+        /// Check that the inputs cover the max fee of the transaction, deducted from the
+        /// free balances. Only [`IntoChecked::into_checked_free_gas`] skips it.
+        const Fees          = 0b00001000;
     }
 }
 
@@ -70,20 +75,26 @@ impl core::fmt::Display for Checks {
 /// The type describes that the inner transaction was already checked.
 ///
 /// All fields are private, and there is no constructor, so it is impossible to create the
-/// instance of `Checked` outside the `fuel-tx` crate.
+/// instance of `Checked` outside the `fuel-tx` crate without performing the checks. The
+/// only exception is [`Checked::from_parts_unchecked`] for the transactions checked
+/// outside the VM.
 ///
 /// The inner data is immutable to prevent modification to invalidate the checking.
 ///
 /// If you need to modify an inner state, you need to get inner values
 /// (via the `Into<(Tx, Tx ::Metadata)>` trait), modify them and check again.
 ///
+/// The transaction is shared between the clones of the `Checked`, so cloning doesn't
+/// copy the script, the bytecode or the witnesses. Taking the ownership of the
+/// transaction copies it only if some other clone is still alive.
+///
 /// # Dev note: Avoid serde serialization of this type.
 ///
 /// Since checked tx would need to be re-validated on deserialization anyways,
 /// it's cleaner to redo the tx check.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Checked<Tx: IntoChecked> {
-    transaction: Tx,
+    transaction: Arc<Tx>,
     metadata: Tx::Metadata,
     checks_bitmask: Checks,
 }
@@ -91,14 +102,19 @@ pub struct Checked<Tx: IntoChecked> {
 impl<Tx: IntoChecked> Checked<Tx> {
     fn new(transaction: Tx, metadata: Tx::Metadata, checks_bitmask: Checks) -> Self {
         Checked {
-            transaction,
+            transaction: Arc::new(transaction),
             metadata,
             checks_bitmask,
         }
     }
 
-    pub(crate) fn basic(transaction: Tx, metadata: Tx::Metadata) -> Self {
-        Checked::new(transaction, metadata, Checks::Basic)
+    /// The transaction passed `Checks::Basic` and the `Checks::Fees` of the `checks`.
+    pub(crate) fn basic(transaction: Tx, metadata: Tx::Metadata, checks: Checks) -> Self {
+        Checked::new(
+            transaction,
+            metadata,
+            Checks::Basic | (checks & Checks::Fees),
+        )
     }
 
     /// Returns reference on inner transaction.
@@ -126,11 +142,77 @@ impl<Tx: IntoChecked> Checked<Tx> {
     }
 }
 
+impl<Tx: IntoChecked + Cacheable> Checked<Tx> {
+    /// Creates `Checked` from the parts of the transaction checked outside the VM, e.g.
+    /// by the pipeline verifying signatures and predicates in a separate stage.
+    ///
+    /// The `checks` are trusted: the caller is responsible for performing them with the
+    /// same consensus parameters and block height as the `metadata`, and executors only
+    /// compare them with the checks they require. Otherwise, the transaction may be
+    /// executed without being valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `checks` don't contain [`Checks::Basic`], which produces the
+    /// `metadata`, or if the cache of the `transaction` is not computed.
+    pub fn from_parts_unchecked(
+        transaction: Tx,
+        metadata: Tx::Metadata,
+        checks: Checks,
+    ) -> Self {
+        assert!(
+            checks.contains(Checks::Basic),
+            "The metadata is produced by the basic checks"
+        );
+        assert!(
+            transaction.is_computed(),
+            "The cache of the checked transaction is computed"
+        );
+        Checked::new(transaction, metadata, checks)
+    }
+}
+
+impl<Tx: IntoChecked> Checked<Tx> {
+    /// Decomposes into the parts accepted by [`Checked::from_parts_unchecked`].
+    pub fn into_parts(self) -> (Tx, Tx::Metadata, Checks) {
+        let Checked {
+            transaction,
+            metadata,
+            checks_bitmask,
+        } = self;
+        (Arc::unwrap_or_clone(transaction), metadata, checks_bitmask)
+    }
+}
+
+impl<Tx: IntoChecked> Checked<Tx>
+where
+    Self: CheckPredicates,
+{
+    /// Performs the `checks` that are not yet done.
+    pub fn perform_additional_checks(
+        self,
+        checks: Checks,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<Self, CheckError> {
+        let mut checked = self;
+
+        if checks.contains(Checks::Signatures) {
+            checked = checked.check_signatures(&consensus_params.chain_id())?;
+        }
+
+        if checks.contains(Checks::Predicates) {
+            checked = checked.check_predicates(&consensus_params.into())?;
+        }
+
+        Ok(checked)
+    }
+}
+
 /// Transaction that has checks for all dynamic values, e.g. `gas_price`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Ready<Tx: IntoChecked> {
     gas_price: Word,
-    transaction: Tx,
+    transaction: Arc<Tx>,
     metadata: Tx::Metadata,
     checks_bitmask: Checks,
 }
@@ -144,7 +226,11 @@ impl<Tx: IntoChecked> Ready<Tx> {
             metadata,
             checks_bitmask,
         } = self;
-        let checked = Checked::new(transaction, metadata, checks_bitmask);
+        let checked = Checked {
+            transaction,
+            metadata,
+            checks_bitmask,
+        };
         (gas_price, checked)
     }
 
@@ -152,6 +238,11 @@ impl<Tx: IntoChecked> Ready<Tx> {
     pub fn gas_price(&self) -> Word {
         self.gas_price
     }
+
+    /// Returns the bitmask of all passed checks.
+    pub fn checks(&self) -> &Checks {
+        &self.checks_bitmask
+    }
 }
 
 #[cfg(feature = "test-helpers")]
@@ -188,7 +279,7 @@ impl<Tx: IntoChecked + Chargeable> Checked<Tx> {
         let fee = TransactionFee::checked_from_tx(
             gas_costs,
             fee_parameters,
-            &transaction,
+            transaction.as_ref(),
             gas_price,
         )
         .ok_or(CheckError::Validity(ValidityError::BalanceOverflow))?;
@@ -196,7 +287,10 @@ impl<Tx: IntoChecked + Chargeable> Checked<Tx> {
         let max_fee_from_policies = transaction.max_fee_limit();
         let max_fee_from_gas_price = fee.max_fee();
 
-        if max_fee_from_gas_price > max_fee_from_policies {
+        // Without `Checks::Fees`, the max fee isn't funded by the inputs anyway
+        if checks_bitmask.contains(Checks::Fees)
+            && max_fee_from_gas_price > max_fee_from_policies
+        {
             Err(CheckError::InsufficientMaxFee {
                 max_fee_from_policies,
                 max_fee_from_gas_price,
@@ -241,7 +335,7 @@ impl<Tx: IntoChecked> From<Checked<Tx>> for (Tx, Tx::Metadata) {
             ..
         } = checked;
 
-        (transaction, metadata)
+        (Arc::unwrap_or_clone(transaction), metadata)
     }
 }
 
@@ -254,7 +348,7 @@ impl<Tx: IntoChecked> AsRef<Tx> for Checked<Tx> {
 #[cfg(feature = "test-helpers")]
 impl<Tx: IntoChecked> AsMut<Tx> for Checked<Tx> {
     fn as_mut(&mut self) -> &mut Tx {
-        &mut self.transaction
+        Arc::make_mut(&mut self.transaction)
     }
 }
 
@@ -280,10 +374,24 @@ pub enum CheckError {
         /// The max fee calculated from the gas price and gas used by the transaction.
         max_fee_from_gas_price: Word,
     },
+    /// The estimation of the predicate of the input failed.
+    PredicateEstimationFailed {
+        /// The index of the input with the predicate.
+        index: usize,
+        /// The reason of the failure.
+        error: PredicateVerificationFailed,
+    },
+    /// The transaction didn't pass all checks required by the executor.
+    InsufficientChecks {
+        /// The checks required by the executor.
+        required: Checks,
+        /// The checks passed by the transaction.
+        performed: Checks,
+    },
 }
 
 /// Performs checks for a transaction
-pub trait IntoChecked: FormatValidityChecks + Sized {
+pub trait IntoChecked: FormatValidityChecks + Clone + Sized {
     /// Metadata produced during the check.
     type Metadata: Sized;
 
@@ -296,17 +404,60 @@ pub trait IntoChecked: FormatValidityChecks + Sized {
     where
         Checked<Self>: CheckPredicates,
     {
-        let check_predicate_params = consensus_params.into();
+        self.into_checked_with(block_height, consensus_params, Checks::all())
+    }
+
+    /// Returns transaction that passed `Checks::Basic` and the requested `checks`.
+    /// The basic checks are always performed because they produce the metadata.
+    fn into_checked_with(
+        self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        checks: Checks,
+    ) -> Result<Checked<Self>, CheckError>
+    where
+        Checked<Self>: CheckPredicates,
+    {
         self.into_checked_basic(block_height, consensus_params)?
-            .check_signatures(&consensus_params.chain_id())?
-            .check_predicates(&check_predicate_params)
+            .perform_additional_checks(checks, consensus_params)
+    }
+
+    /// Returns transaction that passed `Checks::Signatures` and `Checks::Predicates`,
+    /// but neither of the fee checks: the inputs don't need to cover the max fee, and it
+    /// isn't deducted from the free balances. Used for the zero-gas execution of
+    /// [`InterpreterParams::free_gas`](crate::interpreter::InterpreterParams::free_gas)
+    /// during local development; executors requiring `Checks::Fees` reject it.
+    fn into_checked_free_gas(
+        self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<Checked<Self>, CheckError>
+    where
+        Checked<Self>: CheckPredicates,
+    {
+        self.into_checked_basic_with(block_height, consensus_params, Checks::empty())?
+            .perform_additional_checks(
+                Checks::Signatures | Checks::Predicates,
+                consensus_params,
+            )
     }
 
-    /// Returns transaction that passed only `Checks::Basic`.
+    /// Returns transaction that passed only `Checks::Basic` and `Checks::Fees`.
     fn into_checked_basic(
         self,
         block_height: BlockHeight,
         consensus_params: &ConsensusParameters,
+    ) -> Result<Checked<Self>, CheckError> {
+        self.into_checked_basic_with(block_height, consensus_params, Checks::Fees)
+    }
+
+    /// Returns transaction that passed `Checks::Basic`, and `Checks::Fees` if the
+    /// `checks` contain it. The other `checks` are ignored.
+    fn into_checked_basic_with(
+        self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        checks: Checks,
     ) -> Result<Checked<Self>, CheckError>;
 }
 
@@ -424,7 +575,12 @@ where
         params: &CheckPredicateParams,
     ) -> Result<Self, CheckError> {
         if !self.checks_bitmask.contains(Checks::Predicates) {
-            Interpreter::<PredicateStorage, _>::check_predicates(&self, params)?;
+            let predicates_checked =
+                Interpreter::<PredicateStorage, _>::check_predicates(&self, params)?;
+            crate::interpreter::CheckedMetadata::record_predicate_gas_used(
+                &mut self.metadata,
+                predicates_checked.gas_used_by_input(),
+            );
             self.checks_bitmask.insert(Checks::Predicates);
         }
         Ok(self)
@@ -438,10 +594,15 @@ where
         E: ParallelExecutor,
     {
         if !self.checks_bitmask.contains(Checks::Predicates) {
-            Interpreter::<PredicateStorage, _>::check_predicates_async::<E>(
-                &self, params,
-            )
-            .await?;
+            let predicates_checked =
+                Interpreter::<PredicateStorage, _>::check_predicates_async::<E>(
+                    &self, params,
+                )
+                .await?;
+            crate::interpreter::CheckedMetadata::record_predicate_gas_used(
+                &mut self.metadata,
+                predicates_checked.gas_used_by_input(),
+            );
 
             self.checks_bitmask.insert(Checks::Predicates);
 
@@ -613,7 +774,7 @@ impl From<Checked<Transaction>> for CheckedTransaction {
         } = checked;
 
         // # Dev note: Avoid wildcard pattern to be sure that all variants are covered.
-        match (transaction, metadata) {
+        match (Arc::unwrap_or_clone(transaction), metadata) {
             (Transaction::Script(transaction), CheckedMetadata::Script(metadata)) => {
                 Self::Script(Checked::new(transaction, metadata, checks_bitmask))
             }
@@ -679,27 +840,47 @@ impl From<CheckedTransaction> for Checked<Transaction> {
                 transaction,
                 metadata,
                 checks_bitmask,
-            }) => Checked::new(transaction.into(), metadata.into(), checks_bitmask),
+            }) => Checked::new(
+                Arc::unwrap_or_clone(transaction).into(),
+                metadata.into(),
+                checks_bitmask,
+            ),
             CheckedTransaction::Create(Checked {
                 transaction,
                 metadata,
                 checks_bitmask,
-            }) => Checked::new(transaction.into(), metadata.into(), checks_bitmask),
+            }) => Checked::new(
+                Arc::unwrap_or_clone(transaction).into(),
+                metadata.into(),
+                checks_bitmask,
+            ),
             CheckedTransaction::Mint(Checked {
                 transaction,
                 metadata,
                 checks_bitmask,
-            }) => Checked::new(transaction.into(), metadata.into(), checks_bitmask),
+            }) => Checked::new(
+                Arc::unwrap_or_clone(transaction).into(),
+                metadata.into(),
+                checks_bitmask,
+            ),
             CheckedTransaction::Upgrade(Checked {
                 transaction,
                 metadata,
                 checks_bitmask,
-            }) => Checked::new(transaction.into(), metadata.into(), checks_bitmask),
+            }) => Checked::new(
+                Arc::unwrap_or_clone(transaction).into(),
+                metadata.into(),
+                checks_bitmask,
+            ),
             CheckedTransaction::Upload(Checked {
                 transaction,
                 metadata,
                 checks_bitmask,
-            }) => Checked::new(transaction.into(), metadata.into(), checks_bitmask),
+            }) => Checked::new(
+                Arc::unwrap_or_clone(transaction).into(),
+                metadata.into(),
+                checks_bitmask,
+            ),
         }
     }
 }
@@ -748,44 +929,45 @@ impl From<<Upload as IntoChecked>::Metadata> for CheckedMetadata {
 impl IntoChecked for Transaction {
     type Metadata = CheckedMetadata;
 
-    fn into_checked_basic(
+    fn into_checked_basic_with(
         self,
         block_height: BlockHeight,
         consensus_params: &ConsensusParameters,
+        checks: Checks,
     ) -> Result<Checked<Self>, CheckError> {
         match self {
             Self::Script(tx) => {
                 let (transaction, metadata) = tx
-                    .into_checked_basic(block_height, consensus_params)?
+                    .into_checked_basic_with(block_height, consensus_params, checks)?
                     .into();
                 Ok((transaction.into(), metadata.into()))
             }
             Self::Create(tx) => {
                 let (transaction, metadata) = tx
-                    .into_checked_basic(block_height, consensus_params)?
+                    .into_checked_basic_with(block_height, consensus_params, checks)?
                     .into();
                 Ok((transaction.into(), metadata.into()))
             }
             Self::Mint(tx) => {
                 let (transaction, metadata) = tx
-                    .into_checked_basic(block_height, consensus_params)?
+                    .into_checked_basic_with(block_height, consensus_params, checks)?
                     .into();
                 Ok((transaction.into(), metadata.into()))
             }
             Self::Upgrade(tx) => {
                 let (transaction, metadata) = tx
-                    .into_checked_basic(block_height, consensus_params)?
+                    .into_checked_basic_with(block_height, consensus_params, checks)?
                     .into();
                 Ok((transaction.into(), metadata.into()))
             }
             Self::Upload(tx) => {
                 let (transaction, metadata) = tx
-                    .into_checked_basic(block_height, consensus_params)?
+                    .into_checked_basic_with(block_height, consensus_params, checks)?
                     .into();
                 Ok((transaction.into(), metadata.into()))
             }
         }
-        .map(|(transaction, metadata)| Checked::basic(transaction, metadata))
+        .map(|(transaction, metadata)| Checked::basic(transaction, metadata, checks))
     }
 }
 
@@ -809,10 +991,14 @@ mod tests {
 
     use super::*;
     use alloc::vec;
-    use fuel_asm::op;
+    use fuel_asm::{
+        op,
+        RegId,
+    };
     use fuel_crypto::SecretKey;
     use fuel_tx::{
         field::{
+            Script as ScriptField,
             ScriptGasLimit,
             Tip,
             WitnessLimit,
@@ -822,7 +1008,10 @@ mod tests {
         TransactionBuilder,
         ValidityError,
     };
-    use fuel_types::canonical::Serialize;
+    use fuel_types::canonical::{
+        Deserialize,
+        Serialize,
+    };
     use quickcheck::TestResult;
     use quickcheck_macros::quickcheck;
     use rand::{
@@ -871,6 +1060,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clone__shares_transaction_with_the_original() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let checked = valid_coin_tx(rng, 1000, 1000, 10, 500)
+            .into_checked(Default::default(), &ConsensusParameters::standard())
+            .expect("Expected valid transaction");
+
+        // When
+        let cloned = checked.clone();
+
+        // Then
+        assert!(Arc::ptr_eq(&checked.transaction, &cloned.transaction));
+        assert_eq!(checked.id(), cloned.id());
+        assert_eq!(checked, cloned);
+    }
+
+    #[test]
+    fn as_mut__modification_of_clone_does_not_affect_the_original() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx = valid_coin_tx(rng, 1000, 1000, 10, 500);
+        let checked = tx
+            .clone()
+            .into_checked(Default::default(), &ConsensusParameters::standard())
+            .expect("Expected valid transaction");
+        let mut cloned = checked.clone();
+
+        // When
+        cloned.as_mut().script_mut().push(0xff);
+
+        // Then
+        assert!(!Arc::ptr_eq(&checked.transaction, &cloned.transaction));
+        assert_eq!(checked.transaction(), &tx);
+        assert_eq!(checked.transaction().script(), tx.script());
+        assert_eq!(cloned.transaction().script(), &[0xff]);
+    }
+
+    #[test]
+    fn into__takes_transaction_from_clone_after_original_is_dropped() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let tx = valid_coin_tx(rng, 1000, 1000, 10, 500);
+        let checked = tx
+            .clone()
+            .into_checked(Default::default(), &ConsensusParameters::standard())
+            .expect("Expected valid transaction");
+        let id = checked.id();
+        let cloned = checked.clone();
+        drop(checked);
+
+        // When
+        let (transaction, _): (Script, _) = cloned.into();
+
+        // Then
+        assert_eq!(transaction, tx);
+        assert_eq!(transaction.cached_id(), Some(id));
+    }
+
     #[test]
     fn into_checked__tx_accepts_valid_signed_message_coin_for_fees() {
         // simple test to ensure a tx that only has a message input can cover fees
@@ -1213,14 +1458,14 @@ mod tests {
             .script_gas_limit(gas_limit)
             // Set up 3 signed inputs
             .add_unsigned_message_input(
-                secret,
+                secret.clone(),
                 rng.gen(),
                 rng.gen(),
                 rng.gen::<u32>() as u64,
                 vec![],
             )
             .add_unsigned_message_input(
-                secret,
+                secret.clone(),
                 rng.gen(),
                 rng.gen(),
                 rng.gen::<u32>() as u64,
@@ -1441,6 +1686,10 @@ mod tests {
         let expected_min_fee = (tx.metered_bytes_size() as u64
             * fee_params.gas_per_byte()
             + gas_costs.state_root().resolve(storage_slots_len as Word)
+            + gas_costs.new_storage().if_per_slot_set().resolve(
+                storage_slots_len as Word,
+                (storage_slots_len * StorageSlot::SLOT_SIZE) as Word,
+            )
             + gas_costs.contract_root().resolve(bytecode_len as Word)
             + gas_costs.vm_initialization().resolve(tx.size() as u64)
             + gas_costs.s256().resolve(100)
@@ -1500,7 +1749,8 @@ mod tests {
         let input_amount = 1_000;
 
         // create a tx with invalid signature
-        let tx = TransactionBuilder::script(vec![], vec![])
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
             .script_gas_limit(gas_limit)
             .add_input(Input::coin_signed(
                 rng.gen(),
@@ -1519,9 +1769,9 @@ mod tests {
             ))
             .add_output(Output::contract(1, rng.gen(), rng.gen()))
             .add_output(Output::coin(rng.gen(), 10, asset))
-            .add_output(Output::change(rng.gen(), 0, asset))
-            .add_witness(Default::default())
-            .finalize();
+            .add_output(Output::change(rng.gen(), 0, asset));
+        builder.add_witness(Default::default());
+        let tx = builder.finalize();
 
         let err = tx
             .into_checked(Default::default(), &ConsensusParameters::standard())
@@ -1769,7 +2019,7 @@ mod tests {
             .script_gas_limit(100)
             // base asset
             .add_unsigned_coin_input(
-                secret,
+                secret.clone(),
                 rng.gen(),
                 input_amount,
                 AssetId::default(),
@@ -1867,6 +2117,209 @@ mod tests {
             .contains(Checks::Basic | Checks::Predicates));
     }
 
+    fn tx_with_invalid_signature(rng: &mut StdRng) -> Script {
+        let mut tx =
+            TransactionBuilder::script(vec![op::ret(1)].into_iter().collect(), vec![])
+                .script_gas_limit(1000)
+                .add_unsigned_coin_input(
+                    SecretKey::random(rng),
+                    rng.gen(),
+                    1000,
+                    AssetId::default(),
+                    Default::default(),
+                )
+                .finalize();
+        tx.witnesses_mut()[0] = Signature::default().as_ref().into();
+        tx
+    }
+
+    #[test]
+    fn into_checked_with__performs_only_requested_checks() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let tx = tx_with_invalid_signature(&mut rng);
+
+        // When
+        let checked = tx
+            .into_checked_with(Default::default(), &params, Checks::Basic)
+            .expect("Basic checks don't verify signatures");
+
+        // Then
+        assert_eq!(checked.checks(), &(Checks::Basic | Checks::Fees));
+        let err = checked
+            .perform_additional_checks(Checks::Signatures, &params)
+            .expect_err("Expected invalid signature");
+        assert_eq!(
+            err,
+            CheckError::Validity(ValidityError::InputInvalidSignature { index: 0 })
+        );
+    }
+
+    #[test]
+    fn perform_additional_checks__upgrades_checked_tx() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let tx = valid_coin_tx(&mut rng, 100000, 1000000, 10, 0);
+
+        // Given
+        let checked = tx
+            .into_checked_with(Default::default(), &params, Checks::Basic)
+            .unwrap();
+
+        // When
+        let checked = checked
+            .perform_additional_checks(Checks::Signatures | Checks::Predicates, &params)
+            .unwrap();
+
+        // Then
+        assert_eq!(checked.checks(), &Checks::all());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transactor__rejects_tx_without_required_checks() {
+        use crate::{
+            interpreter::InterpreterParams,
+            storage::MemoryStorage,
+            transactor::Transactor,
+        };
+
+        let mut rng = StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let tx = tx_with_invalid_signature(&mut rng);
+        let checked = tx
+            .into_checked_with(Default::default(), &params, Checks::Basic)
+            .unwrap();
+
+        // Given
+        let mut transactor = Transactor::<_, Script>::new(
+            MemoryStorage::default(),
+            InterpreterParams::new(0, &params),
+        );
+        transactor.set_required_checks(Checks::all());
+
+        // When
+        transactor.transact(checked);
+
+        // Then
+        let err = transactor
+            .result()
+            .expect_err("Expected insufficient checks");
+        assert!(matches!(
+            err,
+            InterpreterError::CheckError(CheckError::InsufficientChecks {
+                required,
+                performed,
+            }) if *required == Checks::all() && *performed == Checks::Basic | Checks::Fees
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_parts_unchecked__reconstructed_tx_executes_identically() {
+        use crate::{
+            interpreter::InterpreterParams,
+            storage::MemoryStorage,
+            transactor::Transactor,
+        };
+
+        let mut rng = StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let script = vec![
+            op::movi(0x10, 42),
+            op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(0x10),
+        ];
+        let checked = TransactionBuilder::script(script.into_iter().collect(), vec![])
+            .script_gas_limit(1000)
+            .add_unsigned_coin_input(
+                SecretKey::random(&mut rng),
+                rng.gen(),
+                1000,
+                AssetId::default(),
+                Default::default(),
+            )
+            .finalize()
+            .into_checked(Default::default(), &params)
+            .unwrap();
+        let transact = |checked: Checked<Script>| {
+            let mut transactor = Transactor::<_, Script>::new(
+                MemoryStorage::default(),
+                InterpreterParams::new(0, &params),
+            );
+            transactor.set_required_checks(Checks::all());
+            transactor.transact(checked);
+            transactor.receipts().expect("Expected execution").to_vec()
+        };
+
+        // Given
+        let (tx, metadata, checks) = checked.clone().into_parts();
+
+        // When
+        let reconstructed = Checked::from_parts_unchecked(tx, metadata, checks);
+
+        // Then
+        assert_eq!(reconstructed, checked);
+        assert_eq!(reconstructed.id(), checked.id());
+        assert_eq!(transact(reconstructed), transact(checked));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_parts_unchecked__transactor_trusts_declared_checks() {
+        use crate::{
+            interpreter::InterpreterParams,
+            storage::MemoryStorage,
+            transactor::Transactor,
+        };
+
+        let mut rng = StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let (tx, metadata, _) = tx_with_invalid_signature(&mut rng)
+            .into_checked_with(Default::default(), &params, Checks::Basic)
+            .unwrap()
+            .into_parts();
+
+        // Given
+        let checked = Checked::from_parts_unchecked(tx, metadata, Checks::all());
+        let mut transactor = Transactor::<_, Script>::new(
+            MemoryStorage::default(),
+            InterpreterParams::new(0, &params),
+        );
+        transactor.set_required_checks(Checks::all());
+
+        // When
+        transactor.transact(checked);
+
+        // Then
+        assert!(transactor.result().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "The cache of the checked transaction is computed")]
+    fn from_parts_unchecked__panics_if_cache_is_not_computed() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        let (tx, metadata, checks) = valid_coin_tx(&mut rng, 1000, 1000, 10, 500)
+            .into_checked(Default::default(), &ConsensusParameters::standard())
+            .unwrap()
+            .into_parts();
+        let tx = Script::from_bytes(&tx.to_bytes()).unwrap();
+
+        Checked::from_parts_unchecked(tx, metadata, checks);
+    }
+
+    #[test]
+    #[should_panic(expected = "The metadata is produced by the basic checks")]
+    fn from_parts_unchecked__panics_without_basic_checks() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        let (tx, metadata, _) = valid_coin_tx(&mut rng, 1000, 1000, 10, 500)
+            .into_checked(Default::default(), &ConsensusParameters::standard())
+            .unwrap()
+            .into_parts();
+
+        Checked::from_parts_unchecked(tx, metadata, Checks::Signatures);
+    }
+
     fn is_valid_max_fee(
         tx: &Script,
         gas_price: u64,
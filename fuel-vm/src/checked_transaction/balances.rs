@@ -22,12 +22,16 @@ use fuel_types::{
     Word,
 };
 
+use super::Checks;
 use alloc::collections::BTreeMap;
 use fuel_tx::policies::PolicyType;
 
+/// The free balances of the inputs of the `tx` after its coin outputs, and its max fee
+/// if the `checks` contain [`Checks::Fees`].
 pub(crate) fn initial_free_balances<T>(
     tx: &T,
     base_asset_id: &AssetId,
+    checks: Checks,
 ) -> Result<AvailableBalances, ValidityError>
 where
     T: Chargeable + field::Inputs + field::Outputs,
@@ -39,7 +43,13 @@ where
         .policies()
         .get(PolicyType::MaxFee)
         .ok_or(ValidityError::TransactionMaxFeeNotSet)?;
-    deduct_max_fee_from_base_asset(&mut non_retryable_balances, base_asset_id, max_fee)?;
+    if checks.contains(Checks::Fees) {
+        deduct_max_fee_from_base_asset(
+            &mut non_retryable_balances,
+            base_asset_id,
+            max_fee,
+        )?;
+    }
 
     reduce_free_balances_by_coin_outputs(&mut non_retryable_balances, tx)?;
 
@@ -79,7 +89,7 @@ fn add_up_input_balances<T: field::Inputs>(
             | Input::MessageDataPredicate(MessageDataPredicate { amount, .. }) => {
                 retryable_balance += *amount;
             }
-            Input::Contract(_) => {}
+            Input::Contract(_) | Input::ContractReadOnly(_) => {}
         }
     }
 
@@ -107,19 +117,19 @@ fn reduce_free_balances_by_coin_outputs(
     transaction: &impl field::Outputs,
 ) -> Result<(), ValidityError> {
     // reduce free balances by coin outputs
-    for (asset_id, amount) in
-        transaction
-            .outputs()
-            .iter()
-            .filter_map(|output| match output {
-                Output::Coin {
-                    asset_id, amount, ..
-                } => Some((asset_id, amount)),
-                _ => None,
-            })
-    {
+    for (index, asset_id, amount) in transaction.outputs().iter().enumerate().filter_map(
+        |(index, output)| match output {
+            Output::Coin {
+                asset_id, amount, ..
+            } => Some((index, asset_id, amount)),
+            _ => None,
+        },
+    ) {
         let balance = non_retryable_balances.get_mut(asset_id).ok_or(
-            ValidityError::TransactionOutputCoinAssetIdNotFound(*asset_id),
+            ValidityError::TransactionOutputCoinAssetIdNotFound {
+                index,
+                asset_id: *asset_id,
+            },
         )?;
         *balance = balance.checked_sub(*amount).ok_or(
             ValidityError::InsufficientInputAmount {
@@ -1,20 +1,30 @@
-//! Extension trait for [`fuel_tx::TransactionBuilder`]
+//! Extension traits for [`fuel_tx::TransactionBuilder`]
 
 use super::{
-    Checked,
-    IntoChecked,
+    CheckError,
+    CheckPredicateParams,
 };
 use crate::{
-    checked_transaction::CheckPredicates,
+    interpreter::Memory,
     prelude::*,
+    storage::predicate::PredicateStorage,
 };
 use fuel_tx::{
+    Buildable,
     Finalizable,
     TransactionBuilder,
 };
+#[cfg(feature = "test-helpers")]
+use super::{
+    CheckPredicates,
+    Checked,
+    IntoChecked,
+};
+#[cfg(feature = "test-helpers")]
 use fuel_types::BlockHeight;
 
 /// Extension trait for [`fuel_tx::TransactionBuilder`] adding finalization methods
+#[cfg(feature = "test-helpers")]
 pub trait TransactionBuilderExt<Tx>
 where
     Tx: IntoChecked,
@@ -25,10 +35,20 @@ where
     /// Finalize the builder into a [`Checked<Tx>`] of the correct type, with basic checks
     /// only
     fn finalize_checked_basic(&self, height: BlockHeight) -> Checked<Tx>;
+
+    /// Balances the builder with [`TransactionBuilder::finalize_balanced`], adding the
+    /// missing change outputs to the `owner`, and checks the finalized transaction.
+    fn finalize_checked_balanced(
+        &mut self,
+        height: BlockHeight,
+        owner: Address,
+    ) -> Result<Checked<Tx>, CheckError>;
 }
 
+#[cfg(feature = "test-helpers")]
 impl<Tx: ExecutableTransaction> TransactionBuilderExt<Tx> for TransactionBuilder<Tx>
 where
+    Tx: Buildable,
     Self: Finalizable<Tx>,
     Checked<Tx>: CheckPredicates,
 {
@@ -43,4 +63,90 @@ where
             .into_checked_basic(height, self.get_params())
             .expect("failed to check tx")
     }
+
+    fn finalize_checked_balanced(
+        &mut self,
+        height: BlockHeight,
+        owner: Address,
+    ) -> Result<Checked<Tx>, CheckError> {
+        self.finalize_balanced(owner)?
+            .into_checked(height, self.get_params())
+    }
+}
+
+/// Extension trait for [`fuel_tx::TransactionBuilder`] estimating the predicates of the
+/// inputs
+pub trait EstimatePredicatesBuilderExt {
+    /// Estimates the predicates of the inputs currently in the builder and stores the
+    /// gas used by each of them, so the finalized transaction passes the predicate
+    /// checks.
+    ///
+    /// The predicates are executed against the transaction produced by
+    /// [`Finalizable::finalize`], with the `memory` and the `storage` of the caller.
+    /// Inputs without predicates are skipped, and the `predicate_gas_used` of already
+    /// estimated inputs is always overwritten because it depends on the whole
+    /// transaction. If any predicate fails, the builder is left unchanged and
+    /// [`CheckError::PredicateEstimationFailed`] contains the index of the input.
+    fn estimate_predicates<S>(
+        &mut self,
+        params: &CheckPredicateParams,
+        memory: &mut Memory,
+        storage: &mut S,
+    ) -> Result<(), CheckError>
+    where
+        S: InterpreterStorage;
+
+    /// Chaining variant of [`Self::estimate_predicates`].
+    fn with_estimated_predicates<S>(
+        &mut self,
+        params: &CheckPredicateParams,
+        memory: &mut Memory,
+        storage: &mut S,
+    ) -> Result<&mut Self, CheckError>
+    where
+        S: InterpreterStorage,
+    {
+        self.estimate_predicates(params, memory, storage)?;
+        Ok(self)
+    }
+}
+
+impl<Tx> EstimatePredicatesBuilderExt for TransactionBuilder<Tx>
+where
+    Tx: ExecutableTransaction + Buildable,
+    Self: Finalizable<Tx>,
+{
+    fn estimate_predicates<S>(
+        &mut self,
+        params: &CheckPredicateParams,
+        memory: &mut Memory,
+        storage: &mut S,
+    ) -> Result<(), CheckError>
+    where
+        S: InterpreterStorage,
+    {
+        let tx = self.finalize();
+
+        let mut estimated = vec![];
+        for index in 0..tx.inputs().len() {
+            if let Some(result) = Interpreter::<PredicateStorage, Tx>::estimate_predicate(
+                &tx,
+                index,
+                params,
+                memory,
+                &mut *storage,
+            ) {
+                let gas_used = result.map_err(|error| {
+                    CheckError::PredicateEstimationFailed { index, error }
+                })?;
+                estimated.push((index, gas_used));
+            }
+        }
+
+        for (index, gas_used) in estimated {
+            self.set_predicate_gas_used(index, gas_used);
+        }
+
+        Ok(())
+    }
 }
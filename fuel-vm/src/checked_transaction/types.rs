@@ -6,7 +6,11 @@ pub use self::{
     upgrade::CheckedMetadata as UpgradeCheckedMetadata,
     upload::CheckedMetadata as UploadCheckedMetadata,
 };
-use alloc::collections::BTreeMap;
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use fuel_tx::Input;
 use fuel_types::{
     AssetId,
     Word,
@@ -55,6 +59,88 @@ impl core::ops::Deref for RetryableAmount {
     }
 }
 
+/// The gas of the predicate of an input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PredicateGas {
+    /// The index of the input.
+    pub input_index: usize,
+    /// The `predicate_gas_used` declared by the input, the most the predicate can use.
+    pub declared: Word,
+    /// The gas used by the verification of the predicate, `None` until
+    /// [`Checks::Predicates`](super::Checks::Predicates).
+    pub used: Option<Word>,
+}
+
+impl PredicateGas {
+    /// The gas charged for the predicate: the gas used by its verification, or the
+    /// declared gas if it wasn't verified, never more than the declared gas.
+    pub fn charged(&self) -> Word {
+        self.used
+            .map_or(self.declared, |used| used.min(self.declared))
+    }
+}
+
+/// The gas of the predicates of the inputs of the transaction, see [`PredicateGas`].
+#[derive(Default, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PredicatesGas(pub(crate) Vec<PredicateGas>);
+
+impl PredicatesGas {
+    /// The declared gas of the predicates of the `inputs`, not verified yet.
+    pub(crate) fn from_inputs(inputs: &[Input]) -> Self {
+        Self(
+            inputs
+                .iter()
+                .enumerate()
+                .filter_map(|(input_index, input)| {
+                    Some(PredicateGas {
+                        input_index,
+                        declared: input.predicate_gas_used()?,
+                        used: None,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Records the gas used by the verification of the predicates, by input index.
+    pub(crate) fn record_used(&mut self, gas_used_by_input: &[(usize, Word)]) {
+        for (index, gas_used) in gas_used_by_input {
+            if let Some(predicate) = self
+                .0
+                .iter_mut()
+                .find(|predicate| predicate.input_index == *index)
+            {
+                predicate.used = Some(*gas_used);
+            }
+        }
+    }
+
+    /// The sum of the declared gas of the predicates.
+    pub fn declared(&self) -> Word {
+        self.0
+            .iter()
+            .map(|predicate| predicate.declared)
+            .fold(0, Word::saturating_add)
+    }
+
+    /// The sum of the [`PredicateGas::charged`] gas of the predicates, not more than
+    /// [`Self::declared`].
+    pub fn charged(&self) -> Word {
+        self.0
+            .iter()
+            .map(PredicateGas::charged)
+            .fold(0, Word::saturating_add)
+    }
+}
+
+impl core::ops::Deref for PredicatesGas {
+    type Target = [PredicateGas];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// For [`fuel_tx::Create`]
 pub mod create {
     use super::super::{
@@ -63,13 +149,16 @@ pub mod create {
             AvailableBalances,
         },
         Checked,
+        Checks,
         IntoChecked,
     };
     use crate::checked_transaction::{
         CheckError,
         NonRetryableFreeBalances,
+        PredicatesGas,
     };
     use fuel_tx::{
+        field::Inputs,
         Cacheable,
         Chargeable,
         ConsensusParameters,
@@ -89,15 +178,21 @@ pub mod create {
         pub min_gas: u64,
         /// The maximum gas required for this transaction.
         pub max_gas: u64,
+        /// Whether the max fee is deducted from the free balances, with
+        /// [`Checks::Fees`], to be refunded minus the used fee after the execution.
+        pub max_fee_deducted: bool,
+        /// See [`PredicatesGas`].
+        pub predicate_gas: PredicatesGas,
     }
 
     impl IntoChecked for Create {
         type Metadata = CheckedMetadata;
 
-        fn into_checked_basic(
+        fn into_checked_basic_with(
             mut self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
+            checks: Checks,
         ) -> Result<Checked<Self>, CheckError> {
             let chain_id = consensus_params.chain_id();
             self.precompute(&chain_id)?;
@@ -107,7 +202,7 @@ pub mod create {
             let AvailableBalances {
                 non_retryable_balances,
                 retryable_balance,
-            } = initial_free_balances(&self, consensus_params.base_asset_id())?;
+            } = initial_free_balances(&self, consensus_params.base_asset_id(), checks)?;
             debug_assert_eq!(
                 retryable_balance, 0,
                 "The `check_without_signatures` should return `TransactionInputContainsMessageData` above"
@@ -120,9 +215,11 @@ pub mod create {
                     .min_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
                 max_gas: self
                     .max_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
+                max_fee_deducted: checks.contains(Checks::Fees),
+                predicate_gas: PredicatesGas::from_inputs(self.inputs()),
             };
 
-            Ok(Checked::basic(self, metadata))
+            Ok(Checked::basic(self, metadata, checks))
         }
     }
 }
@@ -131,6 +228,7 @@ pub mod create {
 pub mod mint {
     use super::super::{
         Checked,
+        Checks,
         IntoChecked,
     };
     use crate::checked_transaction::CheckError;
@@ -145,16 +243,17 @@ pub mod mint {
     impl IntoChecked for Mint {
         type Metadata = ();
 
-        fn into_checked_basic(
+        fn into_checked_basic_with(
             mut self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
+            checks: Checks,
         ) -> Result<Checked<Self>, CheckError> {
             let chain_id = consensus_params.chain_id();
             self.precompute(&chain_id)?;
             self.check_without_signatures(block_height, consensus_params)?;
 
-            Ok(Checked::basic(self, ()))
+            Ok(Checked::basic(self, (), checks))
         }
     }
 }
@@ -167,14 +266,17 @@ pub mod script {
             AvailableBalances,
         },
         Checked,
+        Checks,
         IntoChecked,
     };
     use crate::checked_transaction::{
         CheckError,
         NonRetryableFreeBalances,
+        PredicatesGas,
         RetryableAmount,
     };
     use fuel_tx::{
+        field::Inputs,
         Cacheable,
         Chargeable,
         ConsensusParameters,
@@ -196,15 +298,21 @@ pub mod script {
         pub min_gas: u64,
         /// The maximum gas required for this transaction.
         pub max_gas: u64,
+        /// Whether the max fee is deducted from the free balances, with
+        /// [`Checks::Fees`], to be refunded minus the used fee after the execution.
+        pub max_fee_deducted: bool,
+        /// See [`PredicatesGas`].
+        pub predicate_gas: PredicatesGas,
     }
 
     impl IntoChecked for Script {
         type Metadata = CheckedMetadata;
 
-        fn into_checked_basic(
+        fn into_checked_basic_with(
             mut self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
+            checks: Checks,
         ) -> Result<Checked<Self>, CheckError> {
             let chain_id = consensus_params.chain_id();
             self.precompute(&chain_id)?;
@@ -214,7 +322,7 @@ pub mod script {
             let AvailableBalances {
                 non_retryable_balances,
                 retryable_balance,
-            } = initial_free_balances(&self, consensus_params.base_asset_id())?;
+            } = initial_free_balances(&self, consensus_params.base_asset_id(), checks)?;
 
             let metadata = CheckedMetadata {
                 non_retryable_balances: NonRetryableFreeBalances(non_retryable_balances),
@@ -227,9 +335,11 @@ pub mod script {
                     .min_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
                 max_gas: self
                     .max_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
+                max_fee_deducted: checks.contains(Checks::Fees),
+                predicate_gas: PredicatesGas::from_inputs(self.inputs()),
             };
 
-            Ok(Checked::basic(self, metadata))
+            Ok(Checked::basic(self, metadata, checks))
         }
     }
 }
@@ -242,13 +352,16 @@ pub mod upgrade {
             AvailableBalances,
         },
         Checked,
+        Checks,
         IntoChecked,
     };
     use crate::checked_transaction::{
         CheckError,
         NonRetryableFreeBalances,
+        PredicatesGas,
     };
     use fuel_tx::{
+        field::Inputs,
         Cacheable,
         Chargeable,
         ConsensusParameters,
@@ -268,15 +381,21 @@ pub mod upgrade {
         pub min_gas: u64,
         /// The maximum gas required for this transaction.
         pub max_gas: u64,
+        /// Whether the max fee is deducted from the free balances, with
+        /// [`Checks::Fees`], to be refunded minus the used fee after the execution.
+        pub max_fee_deducted: bool,
+        /// See [`PredicatesGas`].
+        pub predicate_gas: PredicatesGas,
     }
 
     impl IntoChecked for Upgrade {
         type Metadata = CheckedMetadata;
 
-        fn into_checked_basic(
+        fn into_checked_basic_with(
             mut self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
+            checks: Checks,
         ) -> Result<Checked<Self>, CheckError> {
             let chain_id = consensus_params.chain_id();
             self.precompute(&chain_id)?;
@@ -286,7 +405,7 @@ pub mod upgrade {
             let AvailableBalances {
                 non_retryable_balances,
                 retryable_balance,
-            } = initial_free_balances(&self, consensus_params.base_asset_id())?;
+            } = initial_free_balances(&self, consensus_params.base_asset_id(), checks)?;
             debug_assert_eq!(
                 retryable_balance, 0,
                 "The `check_without_signatures` should return `TransactionInputContainsMessageData` above"
@@ -299,9 +418,11 @@ pub mod upgrade {
                     .min_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
                 max_gas: self
                     .max_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
+                max_fee_deducted: checks.contains(Checks::Fees),
+                predicate_gas: PredicatesGas::from_inputs(self.inputs()),
             };
 
-            Ok(Checked::basic(self, metadata))
+            Ok(Checked::basic(self, metadata, checks))
         }
     }
 }
@@ -314,13 +435,16 @@ pub mod upload {
             AvailableBalances,
         },
         Checked,
+        Checks,
         IntoChecked,
     };
     use crate::checked_transaction::{
         CheckError,
         NonRetryableFreeBalances,
+        PredicatesGas,
     };
     use fuel_tx::{
+        field::Inputs,
         Cacheable,
         Chargeable,
         ConsensusParameters,
@@ -340,15 +464,21 @@ pub mod upload {
         pub min_gas: u64,
         /// The maximum gas required for this transaction.
         pub max_gas: u64,
+        /// Whether the max fee is deducted from the free balances, with
+        /// [`Checks::Fees`], to be refunded minus the used fee after the execution.
+        pub max_fee_deducted: bool,
+        /// See [`PredicatesGas`].
+        pub predicate_gas: PredicatesGas,
     }
 
     impl IntoChecked for Upload {
         type Metadata = CheckedMetadata;
 
-        fn into_checked_basic(
+        fn into_checked_basic_with(
             mut self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
+            checks: Checks,
         ) -> Result<Checked<Self>, CheckError> {
             let chain_id = consensus_params.chain_id();
             self.precompute(&chain_id)?;
@@ -358,7 +488,7 @@ pub mod upload {
             let AvailableBalances {
                 non_retryable_balances,
                 retryable_balance,
-            } = initial_free_balances(&self, consensus_params.base_asset_id())?;
+            } = initial_free_balances(&self, consensus_params.base_asset_id(), checks)?;
             debug_assert_eq!(
                 retryable_balance, 0,
                 "The `check_without_signatures` should return `TransactionInputContainsMessageData` above"
@@ -371,9 +501,11 @@ pub mod upload {
                     .min_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
                 max_gas: self
                     .max_gas(consensus_params.gas_costs(), consensus_params.fee_params()),
+                max_fee_deducted: checks.contains(Checks::Fees),
+                predicate_gas: PredicatesGas::from_inputs(self.inputs()),
             };
 
-            Ok(Checked::basic(self, metadata))
+            Ok(Checked::basic(self, metadata, checks))
         }
     }
 }
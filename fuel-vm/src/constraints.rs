@@ -12,3 +12,20 @@ pub struct InstructionLocation {
     /// Offset from the IS register
     pub offset: u64,
 }
+
+impl InstructionLocation {
+    /// New location from context and offset
+    pub const fn new(context: Option<ContractId>, offset: u64) -> Self {
+        Self { context, offset }
+    }
+
+    /// Context, i.e. current contract
+    pub const fn context(&self) -> Option<ContractId> {
+        self.context
+    }
+
+    /// Offset from the IS register
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+}
@@ -0,0 +1,444 @@
+//! Coverage of the bytecode of the scripts and the contracts.
+//!
+//! A [`Coverage`] attached to the interpreter with
+//! [`Interpreter::with_coverage`](crate::interpreter::Interpreter::with_coverage) marks
+//! every dispatched instruction in a bitmap of the code identity executing it. The
+//! bitmaps outlive the transactions, so the coverage of a test suite is the coverage of
+//! the interpreter running all its transactions.
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+use fuel_asm::Instruction;
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    constraints::InstructionLocation,
+    convert::to_usize,
+    source_map::SourceMap,
+};
+
+/// The index of the instruction at the `offset`, in bytes.
+#[allow(clippy::cast_possible_truncation)] // The offsets are within the VM memory.
+const fn instruction_index(offset: Word) -> usize {
+    offset as usize / Instruction::SIZE
+}
+
+/// The magic prefix of the [binary format](Coverage::to_bytes).
+const MAGIC: &[u8; 4] = b"FVMC";
+/// The version of the [binary format](Coverage::to_bytes).
+const VERSION: u8 = 1;
+const SCRIPT_TAG: u8 = 0;
+const CONTRACT_TAG: u8 = 1;
+
+/// The identity of a covered bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeId {
+    /// A script, identified by the hash of its bytecode.
+    Script(Bytes32),
+    /// A contract, identified by its id.
+    Contract(ContractId),
+}
+
+impl CodeId {
+    /// The context of the code in the [`SourceMap`].
+    pub const fn source_context(&self) -> Option<ContractId> {
+        match self {
+            Self::Script(_) => None,
+            Self::Contract(id) => Some(*id),
+        }
+    }
+}
+
+impl fmt::Display for CodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Script(hash) => write!(f, "script {hash}"),
+            Self::Contract(id) => write!(f, "contract {id}"),
+        }
+    }
+}
+
+/// The error of the decoding of a [`Coverage`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum CoverageError {
+    /// The bytes aren't an encoded coverage.
+    #[display(fmt = "Invalid encoding of the coverage")]
+    InvalidEncoding,
+}
+
+/// The bitmap of the executed instructions of a bytecode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeCoverage {
+    /// The number of instructions of the bytecode.
+    instructions: usize,
+    /// The bit of the index of each executed instruction.
+    bits: Vec<u64>,
+}
+
+impl CodeCoverage {
+    /// New empty coverage of a bytecode of `instructions` instructions.
+    pub fn new(instructions: usize) -> Self {
+        Self {
+            instructions,
+            bits: alloc::vec![0; instructions.div_ceil(64)],
+        }
+    }
+
+    /// The number of instructions of the bytecode.
+    pub const fn instructions(&self) -> usize {
+        self.instructions
+    }
+
+    #[inline(always)]
+    fn cover(&mut self, index: usize) {
+        let word = index / 64;
+        if word >= self.bits.len() {
+            self.bits.resize(word + 1, 0);
+        }
+        self.bits[word] |= 1 << (index % 64);
+    }
+
+    fn is_index_covered(&self, index: usize) -> bool {
+        self.bits
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    /// Whether the instruction at the `offset`, in bytes, was executed.
+    pub fn is_covered(&self, offset: Word) -> bool {
+        self.is_index_covered(instruction_index(offset))
+    }
+
+    /// The offsets of the executed instructions, in bytes and increasing.
+    ///
+    /// It includes the executed offsets past the end of the bytecode.
+    pub fn covered(&self) -> impl Iterator<Item = Word> + '_ {
+        self.bits.iter().enumerate().flat_map(|(word, bits)| {
+            (0..64)
+                .filter(move |bit| bits & (1 << bit) != 0)
+                .map(move |bit| ((word * 64 + bit) * Instruction::SIZE) as Word)
+        })
+    }
+
+    /// The offsets of the instructions of the bytecode never executed, in bytes and
+    /// increasing.
+    pub fn uncovered(&self) -> impl Iterator<Item = Word> + '_ {
+        (0..self.instructions)
+            .filter(|index| !self.is_index_covered(*index))
+            .map(|index| (index * Instruction::SIZE) as Word)
+    }
+
+    /// Whether every instruction of the bytecode was executed.
+    pub fn is_fully_covered(&self) -> bool {
+        self.uncovered().next().is_none()
+    }
+
+    /// Adds the executed instructions of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.instructions = self.instructions.max(other.instructions);
+        if self.bits.len() < other.bits.len() {
+            self.bits.resize(other.bits.len(), 0);
+        }
+        for (bits, other) in self.bits.iter_mut().zip(&other.bits) {
+            *bits |= other;
+        }
+    }
+}
+
+/// The collector of the executed instructions of each bytecode run by the interpreter.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    /// The coverage of each code identity, in the order of the first execution.
+    codes: Vec<(CodeId, CodeCoverage)>,
+    /// The contract executing and the index of its code, cached between the
+    /// instructions so that the dispatch only sets a bit.
+    active: Option<(Option<ContractId>, usize)>,
+}
+
+impl Coverage {
+    /// New empty coverage.
+    pub const fn new() -> Self {
+        Self {
+            codes: Vec::new(),
+            active: None,
+        }
+    }
+
+    /// Forgets the executing code, at the start of a transaction.
+    pub(crate) fn on_transaction(&mut self) {
+        self.active = None;
+    }
+
+    /// Whether the instructions of the `contract`, or of the script for `None`, are the
+    /// ones being recorded.
+    #[inline(always)]
+    pub(crate) fn is_active(&self, contract: Option<ContractId>) -> bool {
+        matches!(self.active, Some((active, _)) if active == contract)
+    }
+
+    /// Records the following instructions of the `contract`, or of the script for
+    /// `None`, in the bitmap of the `code` of `instructions` instructions.
+    pub(crate) fn activate(
+        &mut self,
+        contract: Option<ContractId>,
+        code: CodeId,
+        instructions: usize,
+    ) {
+        let index = self.index_or_insert(code, instructions);
+        self.active = Some((contract, index));
+    }
+
+    /// Marks the instruction at the `offset` of the active code as executed.
+    #[inline(always)]
+    pub(crate) fn cover(&mut self, offset: Word) {
+        if let Some((_, index)) = self.active {
+            self.codes[index].1.cover(instruction_index(offset));
+        }
+    }
+
+    fn index_or_insert(&mut self, code: CodeId, instructions: usize) -> usize {
+        match self.codes.iter().position(|(id, _)| *id == code) {
+            Some(index) => {
+                let coverage = &mut self.codes[index].1;
+                coverage.instructions = coverage.instructions.max(instructions);
+                index
+            }
+            None => {
+                self.codes.push((code, CodeCoverage::new(instructions)));
+                self.codes.len() - 1
+            }
+        }
+    }
+
+    /// The coverage of the `code`.
+    pub fn get(&self, code: &CodeId) -> Option<&CodeCoverage> {
+        self.codes
+            .iter()
+            .find_map(|(id, coverage)| (id == code).then_some(coverage))
+    }
+
+    /// The number of covered code identities.
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Whether no code was executed.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Iterates over the coverage of each code identity, sorted by identity.
+    pub fn iter(&self) -> impl Iterator<Item = (&CodeId, &CodeCoverage)> {
+        let mut codes = self
+            .codes
+            .iter()
+            .map(|(id, coverage)| (id, coverage))
+            .collect::<Vec<_>>();
+        codes.sort_by_key(|(id, _)| **id);
+        codes.into_iter()
+    }
+
+    /// Adds the executed instructions of each code of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for (code, coverage) in &other.codes {
+            let index = self.index_or_insert(*code, coverage.instructions);
+            self.codes[index].1.merge(coverage);
+        }
+    }
+
+    /// Encodes the bitmaps in a compact binary format.
+    ///
+    /// The format is the `FVMC` magic, a version byte and a big-endian `u64` count of
+    /// codes. Each code is a tag byte, `0` for a script and `1` for a contract, the 32
+    /// bytes of its identity, the `u64` number of instructions, the `u64` number of
+    /// words of the bitmap and the `u64` words, with the bit `i % 64` of the word
+    /// `i / 64` set for the executed instruction `i`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.codes.len() as u64).to_be_bytes());
+        for (code, coverage) in self.iter() {
+            match code {
+                CodeId::Script(hash) => {
+                    bytes.push(SCRIPT_TAG);
+                    bytes.extend_from_slice(hash.as_ref());
+                }
+                CodeId::Contract(id) => {
+                    bytes.push(CONTRACT_TAG);
+                    bytes.extend_from_slice(id.as_ref());
+                }
+            }
+            bytes.extend_from_slice(&(coverage.instructions as u64).to_be_bytes());
+            bytes.extend_from_slice(&(coverage.bits.len() as u64).to_be_bytes());
+            for word in &coverage.bits {
+                bytes.extend_from_slice(&word.to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decodes the coverage encoded with [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CoverageError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+            return Err(CoverageError::InvalidEncoding)
+        }
+        let mut coverage = Self::new();
+        for _ in 0..reader.u64()? {
+            let tag = reader.take(1)?[0];
+            let id: [u8; 32] =
+                reader.take(32)?.try_into().expect("The length was checked");
+            let code = match tag {
+                SCRIPT_TAG => CodeId::Script(id.into()),
+                CONTRACT_TAG => CodeId::Contract(id.into()),
+                _ => return Err(CoverageError::InvalidEncoding),
+            };
+            let instructions = reader.usize()?;
+            let words = reader.usize()?;
+            if reader.0.len() < words.saturating_mul(8) {
+                return Err(CoverageError::InvalidEncoding)
+            }
+            let bits = (0..words).map(|_| reader.u64()).collect::<Result<_, _>>()?;
+            if coverage.get(&code).is_some() {
+                return Err(CoverageError::InvalidEncoding)
+            }
+            coverage
+                .codes
+                .push((code, CodeCoverage { instructions, bits }));
+        }
+        if !reader.0.is_empty() {
+            return Err(CoverageError::InvalidEncoding)
+        }
+        Ok(coverage)
+    }
+
+    /// Renders the coverage in the `lcov` tracefile format.
+    ///
+    /// Without a source map each code is a record, with the instruction offsets as
+    /// lines. The instructions found in the `source_map` are instead attributed to the
+    /// lines of their source files, covered when any of their instructions is executed.
+    pub fn lcov<'a>(&'a self, source_map: Option<&'a SourceMap>) -> Lcov<'a> {
+        Lcov {
+            coverage: self,
+            source_map,
+        }
+    }
+}
+
+impl PartialEq for Coverage {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Coverage {}
+
+/// Reads the fields of the [binary format](Coverage::to_bytes).
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CoverageError> {
+        if self.0.len() < len {
+            return Err(CoverageError::InvalidEncoding)
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn usize(&mut self) -> Result<usize, CoverageError> {
+        to_usize(self.u64()?).ok_or(CoverageError::InvalidEncoding)
+    }
+
+    fn u64(&mut self) -> Result<u64, CoverageError> {
+        let bytes = self.take(8)?.try_into().expect("The length was checked");
+        Ok(u64::from_be_bytes(bytes))
+    }
+}
+
+/// The `lcov` rendering of a coverage, by [`Coverage::lcov`].
+pub struct Lcov<'a> {
+    coverage: &'a Coverage,
+    source_map: Option<&'a SourceMap>,
+}
+
+impl Lcov<'_> {
+    fn record(
+        f: &mut fmt::Formatter<'_>,
+        file: &dyn fmt::Display,
+        lines: &[(Word, bool)],
+    ) -> fmt::Result {
+        writeln!(f, "SF:{file}")?;
+        for (line, covered) in lines {
+            writeln!(f, "DA:{line},{}", u8::from(*covered))?;
+        }
+        writeln!(f, "LF:{}", lines.len())?;
+        writeln!(
+            f,
+            "LH:{}",
+            lines.iter().filter(|(_, covered)| *covered).count()
+        )?;
+        writeln!(f, "end_of_record")
+    }
+}
+
+impl fmt::Display for Lcov<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The lines of each source file, with whether they are covered.
+        let mut files: Vec<(String, Vec<(Word, bool)>)> = Vec::new();
+        for (code, coverage) in self.coverage.iter() {
+            let mut unmapped = Vec::new();
+            let instructions = coverage.instructions.max(
+                coverage
+                    .covered()
+                    .last()
+                    .map_or(0, |offset| instruction_index(offset) + 1),
+            );
+            for index in 0..instructions {
+                let offset = (index * Instruction::SIZE) as Word;
+                let covered = coverage.is_index_covered(index);
+                let location = self.source_map.and_then(|source_map| {
+                    source_map
+                        .get(&InstructionLocation::new(code.source_context(), offset))
+                });
+                let Some(location) = location else {
+                    unmapped.push((offset, covered));
+                    continue
+                };
+                let file = match files.iter().position(|(file, _)| *file == location.file)
+                {
+                    Some(file) => file,
+                    None => {
+                        files.push((location.file.clone(), Vec::new()));
+                        files.len() - 1
+                    }
+                };
+                let lines = &mut files[file].1;
+                let line = Word::from(location.line);
+                match lines.iter_mut().find(|(found, _)| *found == line) {
+                    Some((_, line_covered)) => *line_covered |= covered,
+                    None => lines.push((line, covered)),
+                }
+            }
+            if !unmapped.is_empty() {
+                Self::record(f, code, &unmapped)?;
+            }
+        }
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (file, mut lines) in files {
+            lines.sort();
+            Self::record(f, &file, &lines)?;
+        }
+        Ok(())
+    }
+}
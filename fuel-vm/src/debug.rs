@@ -0,0 +1,3 @@
+//! Debugging of the interpreter from external tools.
+
+pub mod server;
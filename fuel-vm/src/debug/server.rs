@@ -0,0 +1,319 @@
+//! Remote debugging of a transaction over a JSON-over-TCP protocol.
+//!
+//! The client sends one [`DebugRequest`] per line, encoded as a JSON object tagged
+//! with its `command`, and the server answers each of them with one [`DebugResponse`]
+//! line, tagged with its `response`. For example:
+//!
+//! ```text
+//! > {"command":"set_breakpoint","contract":"0x00..00","pc":3}
+//! < {"response":"ok"}
+//! > {"command":"continue"}
+//! < {"response":"paused","contract":"0x00..00","pc":3}
+//! ```
+//!
+//! The program counters of the breakpoints are counted in instructions from the start
+//! of the script or of the contract code, like in [`Breakpoint::new`]. The script and
+//! the predicates use the zero contract id.
+
+use crate::{
+    call::CallFrame,
+    checked_transaction::{
+        IntoChecked,
+        Ready,
+    },
+    interpreter::{
+        CheckedMetadata,
+        EcalHandler,
+        ExecutableTransaction,
+        Interpreter,
+        NotSupportedEcal,
+    },
+    state::{
+        Breakpoint,
+        DebugCommand,
+        DebugEval,
+        ProgramState,
+        WatchValue,
+    },
+    storage::InterpreterStorage,
+};
+
+use fuel_asm::Instruction;
+use fuel_tx::Receipt;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use std::{
+    io::{
+        self,
+        BufRead,
+        BufReader,
+        Write,
+    },
+    net::TcpListener,
+    string::{
+        String,
+        ToString,
+    },
+    thread::{
+        self,
+        JoinHandle,
+    },
+    vec::Vec,
+};
+
+/// A command of the debugging protocol.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum DebugRequest {
+    /// Breaks before executing the instruction at the `pc` of the `contract`.
+    SetBreakpoint {
+        /// The contract of the instruction.
+        contract: ContractId,
+        /// The index of the instruction.
+        pc: Word,
+    },
+    /// Removes the breakpoint at the `pc` of the `contract`.
+    ClearBreakpoint {
+        /// The contract of the instruction.
+        contract: ContractId,
+        /// The index of the instruction.
+        pc: Word,
+    },
+    /// Starts or resumes the execution until the next breakpoint or the end of the
+    /// transaction.
+    Continue,
+    /// Starts or resumes the execution for one instruction.
+    Step,
+    /// Resumes the execution until the next instruction at the same call depth,
+    /// executing the whole called contract of a `CALL`.
+    StepOver,
+    /// Resumes the execution until the current call frame returns.
+    StepOut,
+    /// Reads the registers.
+    ReadRegisters,
+    /// Reads the `len` bytes of the memory from the `start` address.
+    ReadMemory {
+        /// The first address to read.
+        start: Word,
+        /// The number of bytes to read.
+        len: Word,
+    },
+    /// Lists the call frames, from the outermost one.
+    CallFrames,
+    /// Fetches the receipts of the execution so far.
+    Receipts,
+}
+
+/// The answer of the server to a [`DebugRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum DebugResponse {
+    /// The command is done.
+    Ok,
+    /// The execution is paused before the instruction at the `pc` of the `contract`.
+    Paused {
+        /// The contract of the instruction.
+        contract: ContractId,
+        /// The index of the instruction.
+        pc: Word,
+        /// The values of the expressions watched by the interpreter, omitted if
+        /// there are none.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        watches: Vec<WatchValue>,
+    },
+    /// The execution of the transaction is finished.
+    Terminated {
+        /// The final state of the program.
+        state: ProgramState,
+    },
+    /// The registers of the interpreter.
+    Registers {
+        /// The values of the registers, by register id.
+        registers: Vec<Word>,
+    },
+    /// The bytes of a range of the memory.
+    Memory {
+        /// The bytes read.
+        bytes: Vec<u8>,
+    },
+    /// The call frames of the execution.
+    CallFrames {
+        /// The frames, from the outermost one.
+        frames: Vec<CallFrame>,
+    },
+    /// The receipts of the execution so far.
+    Receipts {
+        /// The receipts, in the order of their creation.
+        receipts: Vec<Receipt>,
+    },
+    /// The command failed.
+    Error {
+        /// The description of the failure.
+        message: String,
+    },
+}
+
+/// The progress of the execution of the debugged transaction.
+enum Session<Tx: IntoChecked> {
+    /// The transaction isn't executed yet.
+    Ready(Ready<Tx>),
+    /// The execution is paused at a debug event.
+    Paused,
+    /// The execution is finished or failed.
+    Finished,
+}
+
+/// Server driving the execution of one transaction by an [`Interpreter`] for a remote
+/// debugger.
+///
+/// The execution starts with the first [`DebugRequest::Continue`] or step request, and
+/// pauses at the breakpoints and after each step with the [`DebugCommand`]s of the
+/// interpreter.
+pub struct DebugServer<S, Tx: IntoChecked, Ecal = NotSupportedEcal> {
+    vm: Interpreter<S, Tx, Ecal>,
+    session: Session<Tx>,
+}
+
+impl<S, Tx, Ecal> DebugServer<S, Tx, Ecal>
+where
+    Tx: IntoChecked,
+{
+    /// Debugs the execution of the `tx` by the `vm`.
+    pub fn new(vm: Interpreter<S, Tx, Ecal>, tx: Ready<Tx>) -> Self {
+        Self {
+            vm,
+            session: Session::Ready(tx),
+        }
+    }
+
+    /// The debugged interpreter.
+    pub fn interpreter(&self) -> &Interpreter<S, Tx, Ecal> {
+        &self.vm
+    }
+
+    /// Consumes the server and returns the debugged interpreter.
+    pub fn into_interpreter(self) -> Interpreter<S, Tx, Ecal> {
+        self.vm
+    }
+}
+
+impl<S, Tx, Ecal> DebugServer<S, Tx, Ecal>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    Ecal: EcalHandler,
+{
+    /// Executes the `request`.
+    pub fn handle(&mut self, request: DebugRequest) -> DebugResponse {
+        match request {
+            DebugRequest::SetBreakpoint { contract, pc } => {
+                self.vm.set_breakpoint(Breakpoint::new(contract, pc));
+                DebugResponse::Ok
+            }
+            DebugRequest::ClearBreakpoint { contract, pc } => {
+                self.vm.remove_breakpoint(&Breakpoint::new(contract, pc));
+                DebugResponse::Ok
+            }
+            DebugRequest::Continue => self.run(DebugCommand::Continue),
+            DebugRequest::Step => self.run(DebugCommand::Step),
+            DebugRequest::StepOver => self.run(DebugCommand::StepOver),
+            DebugRequest::StepOut => self.run(DebugCommand::StepOut),
+            DebugRequest::ReadRegisters => DebugResponse::Registers {
+                registers: self.vm.registers().to_vec(),
+            },
+            DebugRequest::ReadMemory { start, len } => {
+                match self.vm.memory().read(start, len) {
+                    Ok(bytes) => DebugResponse::Memory {
+                        bytes: bytes.to_vec(),
+                    },
+                    Err(reason) => DebugResponse::Error {
+                        message: reason.to_string(),
+                    },
+                }
+            }
+            DebugRequest::CallFrames => DebugResponse::CallFrames {
+                frames: self.vm.call_stack().to_vec(),
+            },
+            DebugRequest::Receipts => DebugResponse::Receipts {
+                receipts: self.vm.receipts().to_vec(),
+            },
+        }
+    }
+
+    fn run(&mut self, command: DebugCommand) -> DebugResponse {
+        self.vm.set_debug_command(command);
+        let result = match core::mem::replace(&mut self.session, Session::Paused) {
+            Session::Ready(tx) => self.vm.transact(tx).map(ProgramState::from),
+            Session::Paused => self.vm.resume(),
+            Session::Finished => {
+                self.session = Session::Finished;
+                return DebugResponse::Error {
+                    message: "The execution of the transaction is finished".to_string(),
+                }
+            }
+        };
+
+        match result {
+            Ok(ProgramState::RunProgram(DebugEval::Breakpoint(breakpoint))) => {
+                DebugResponse::Paused {
+                    contract: *breakpoint.contract(),
+                    pc: breakpoint.pc() / Instruction::SIZE as Word,
+                    watches: self.vm.watch_values().to_vec(),
+                }
+            }
+            Ok(state) => {
+                self.session = Session::Finished;
+                DebugResponse::Terminated { state }
+            }
+            Err(error) => {
+                self.session = Session::Finished;
+                DebugResponse::Error {
+                    message: format!("{error:?}"),
+                }
+            }
+        }
+    }
+
+    /// Answers the requests read from the `reader` line by line, until its end.
+    pub fn serve<R: BufRead, W: Write>(
+        &mut self,
+        reader: R,
+        mut writer: W,
+    ) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue
+            }
+            let response = match serde_json::from_str(&line) {
+                Ok(request) => self.handle(request),
+                Err(error) => DebugResponse::Error {
+                    message: error.to_string(),
+                },
+            };
+            serde_json::to_writer(&mut writer, &response)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Serves the first client connecting to the `listener` on a new thread, until it
+    /// disconnects. The thread returns the server, to inspect the interpreter after
+    /// the session.
+    pub fn spawn(self, listener: TcpListener) -> JoinHandle<io::Result<Self>>
+    where
+        Self: Send + 'static,
+    {
+        thread::spawn(move || {
+            let mut server = self;
+            let (stream, _) = listener.accept()?;
+            server.serve(BufReader::new(stream.try_clone()?), stream)?;
+            Ok(server)
+        })
+    }
+}
@@ -0,0 +1,395 @@
+//! Comparison of two executions of a transaction, instruction by instruction.
+//!
+//! The [`ExecutionTrace`] of each run is recorded by an
+//! [observer](crate::observer::ExecutionObserver) attached to the interpreter, in
+//! memory with a [`TraceRecorder`] or streamed to a file with a [`TraceWriter`] for
+//! the long runs. [`compare`] then finds the first step where the runs diverge, e.g.
+//! after a change of the gas costs or of the interpreter.
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+use fuel_asm::{
+    Opcode,
+    RawInstruction,
+    RegId,
+};
+use fuel_types::Word;
+
+use crate::{
+    constraints::InstructionLocation,
+    observer::ExecutedStep,
+};
+
+#[cfg(feature = "std")]
+pub use self::stream::{
+    TraceReader,
+    TraceRecorder,
+    TraceWriter,
+};
+
+/// An instruction of an [`ExecutionTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceStep {
+    /// The location of the instruction.
+    pub location: InstructionLocation,
+    /// The executed instruction.
+    pub instruction: RawInstruction,
+    /// The registers written by the instruction with their new value, see
+    /// [`ExecutedStep::written_registers`].
+    pub written: Vec<(RegId, Word)>,
+    /// The gas remaining in the transaction after the instruction.
+    pub gas: Word,
+}
+
+impl TraceStep {
+    /// The opcode of the instruction, `None` for an invalid instruction.
+    pub fn opcode(&self) -> Option<Opcode> {
+        Opcode::try_from(self.instruction.to_be_bytes()[0]).ok()
+    }
+}
+
+impl From<&ExecutedStep<'_>> for TraceStep {
+    fn from(step: &ExecutedStep<'_>) -> Self {
+        Self {
+            location: step.location,
+            instruction: step.instruction,
+            written: step.written_registers().collect(),
+            gas: step.gas(),
+        }
+    }
+}
+
+/// The steps of an execution, in their order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    steps: Vec<TraceStep>,
+}
+
+impl ExecutionTrace {
+    /// New empty trace.
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Appends the `step`.
+    pub fn push(&mut self, step: TraceStep) {
+        self.steps.push(step);
+    }
+
+    /// The steps of the execution.
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    /// The number of executed instructions.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether no instruction was executed.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl FromIterator<TraceStep> for ExecutionTrace {
+    fn from_iter<I: IntoIterator<Item = TraceStep>>(iter: I) -> Self {
+        Self {
+            steps: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for ExecutionTrace {
+    type IntoIter = alloc::vec::IntoIter<TraceStep>;
+    type Item = TraceStep;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.into_iter()
+    }
+}
+
+/// The result of the comparison of two traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// Both runs executed the same `steps`.
+    Identical {
+        /// The number of steps of the runs.
+        steps: usize,
+    },
+    /// The runs differ from the step at the `index`.
+    Step {
+        /// The index of the first differing step.
+        index: usize,
+        /// The step of the first run, `None` if it stopped before.
+        a: Option<TraceStep>,
+        /// The step of the second run, `None` if it stopped before.
+        b: Option<TraceStep>,
+    },
+}
+
+impl Divergence {
+    /// The index of the first differing step, `None` for identical runs.
+    pub const fn index(&self) -> Option<usize> {
+        match self {
+            Self::Identical { .. } => None,
+            Self::Step { index, .. } => Some(*index),
+        }
+    }
+}
+
+/// Finds the first step where the runs diverge.
+pub fn compare(run_a: ExecutionTrace, run_b: ExecutionTrace) -> Divergence {
+    let Ok(divergence) = try_compare(
+        run_a.into_iter().map(Ok::<_, Infallible>),
+        run_b.into_iter().map(Ok),
+    );
+    divergence
+}
+
+/// Finds the first step where the runs diverge, reading the steps from fallible
+/// sources like the [`TraceReader`]s of the traces streamed to disk.
+///
+/// It stops reading at the first divergence.
+pub fn try_compare<A, B, E>(run_a: A, run_b: B) -> Result<Divergence, E>
+where
+    A: IntoIterator<Item = Result<TraceStep, E>>,
+    B: IntoIterator<Item = Result<TraceStep, E>>,
+{
+    let mut run_a = run_a.into_iter();
+    let mut run_b = run_b.into_iter();
+    let mut index = 0;
+    loop {
+        let a = run_a.next().transpose()?;
+        let b = run_b.next().transpose()?;
+        match (a, b) {
+            (None, None) => return Ok(Divergence::Identical { steps: index }),
+            (a, b) if a != b => return Ok(Divergence::Step { index, a, b }),
+            _ => index += 1,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod stream {
+    use super::*;
+    use fuel_types::ContractId;
+    use std::{
+        io,
+        sync::Mutex,
+    };
+
+    use crate::observer::ExecutionObserver;
+
+    /// The magic prefix of the binary format of the traces.
+    const MAGIC: &[u8; 4] = b"FVMT";
+    /// The version of the binary format of the traces.
+    const VERSION: u8 = 1;
+
+    impl TraceStep {
+        /// Appends the binary encoding of the step to `bytes`.
+        ///
+        /// The step is a byte set to `1` when the instruction is in a contract, followed
+        /// by the id of the contract, the `u64` offset of the instruction, the `u32`
+        /// instruction, the `u64` gas, the `u8` number of written registers and each
+        /// register with its `u64` value, in big-endian.
+        fn encode(&self, bytes: &mut Vec<u8>) {
+            match self.location.context {
+                Some(contract) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(contract.as_ref());
+                }
+                None => bytes.push(0),
+            }
+            bytes.extend_from_slice(&self.location.offset.to_be_bytes());
+            bytes.extend_from_slice(&self.instruction.to_be_bytes());
+            bytes.extend_from_slice(&self.gas.to_be_bytes());
+            bytes.push(u8::try_from(self.written.len()).expect("There are 64 registers"));
+            for (register, value) in &self.written {
+                bytes.push(register.to_u8());
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+
+    fn invalid(message: &'static str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    impl ExecutionTrace {
+        /// Writes the trace in the binary format read by the [`TraceReader`].
+        pub fn write_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+            let mut bytes = Vec::new();
+            bytes.extend_from_slice(MAGIC);
+            bytes.push(VERSION);
+            for step in &self.steps {
+                step.encode(&mut bytes);
+            }
+            writer.write_all(&bytes)
+        }
+
+        /// Reads the trace written by [`Self::write_to`] or by a [`TraceWriter`].
+        pub fn read_from<R: io::Read>(reader: R) -> io::Result<Self> {
+            TraceReader::new(reader)?.collect()
+        }
+    }
+
+    /// Records the trace of the execution in memory.
+    #[derive(Debug, Default)]
+    pub struct TraceRecorder {
+        trace: Mutex<ExecutionTrace>,
+    }
+
+    impl TraceRecorder {
+        /// New recorder with an empty trace.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Takes the recorded trace, leaving an empty trace for the next run.
+        pub fn take(&self) -> ExecutionTrace {
+            core::mem::take(&mut *self.trace.lock().expect("The lock is poisoned"))
+        }
+    }
+
+    impl ExecutionObserver for TraceRecorder {
+        fn on_step(&self, step: &ExecutedStep<'_>) {
+            self.trace
+                .lock()
+                .expect("The lock is poisoned")
+                .push(step.into());
+        }
+    }
+
+    #[derive(Debug)]
+    struct WriterState<W> {
+        writer: W,
+        /// The first failure of a write, reported by [`TraceWriter::flush`].
+        error: Option<io::Error>,
+        bytes: Vec<u8>,
+    }
+
+    /// Streams the trace of the execution to a writer, without keeping the steps in
+    /// memory.
+    ///
+    /// The observer can't fail, so the first error of the writer stops the streaming
+    /// and is reported by [`Self::flush`].
+    #[derive(Debug)]
+    pub struct TraceWriter<W> {
+        state: Mutex<WriterState<W>>,
+    }
+
+    impl<W: io::Write> TraceWriter<W> {
+        /// New writer, writing the header of the trace to the `writer`.
+        pub fn new(mut writer: W) -> io::Result<Self> {
+            writer.write_all(MAGIC)?;
+            writer.write_all(&[VERSION])?;
+            Ok(Self {
+                state: Mutex::new(WriterState {
+                    writer,
+                    error: None,
+                    bytes: Vec::new(),
+                }),
+            })
+        }
+
+        /// Flushes the writer, or returns the error that stopped the streaming.
+        pub fn flush(&self) -> io::Result<()> {
+            let mut state = self.state.lock().expect("The lock is poisoned");
+            match state.error.take() {
+                Some(error) => Err(error),
+                None => state.writer.flush(),
+            }
+        }
+
+        /// The underlying writer.
+        pub fn into_inner(self) -> W {
+            self.state
+                .into_inner()
+                .expect("The lock is poisoned")
+                .writer
+        }
+    }
+
+    impl<W> ExecutionObserver for TraceWriter<W>
+    where
+        W: io::Write + core::fmt::Debug + Send,
+    {
+        fn on_step(&self, step: &ExecutedStep<'_>) {
+            let mut state = self.state.lock().expect("The lock is poisoned");
+            let state = &mut *state;
+            if state.error.is_some() {
+                return
+            }
+            state.bytes.clear();
+            TraceStep::from(step).encode(&mut state.bytes);
+            if let Err(error) = state.writer.write_all(&state.bytes) {
+                state.error = Some(error);
+            }
+        }
+    }
+
+    /// Reads the steps of a trace written by a [`TraceWriter`] or by
+    /// [`ExecutionTrace::write_to`], one at a time.
+    #[derive(Debug)]
+    pub struct TraceReader<R> {
+        reader: R,
+    }
+
+    impl<R: io::Read> TraceReader<R> {
+        /// New reader, checking the header of the trace.
+        pub fn new(mut reader: R) -> io::Result<Self> {
+            let mut header = [0; 5];
+            reader.read_exact(&mut header)?;
+            if header[..4] != MAGIC[..] || header[4] != VERSION {
+                return Err(invalid("Not a trace of the execution"))
+            }
+            Ok(Self { reader })
+        }
+
+        fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+            let mut bytes = [0; N];
+            self.reader.read_exact(&mut bytes)?;
+            Ok(bytes)
+        }
+
+        fn step(&mut self, tag: u8) -> io::Result<TraceStep> {
+            let context = match tag {
+                0 => None,
+                1 => Some(ContractId::from(self.array::<32>()?)),
+                _ => return Err(invalid("Invalid context of the step")),
+            };
+            let offset = Word::from_be_bytes(self.array()?);
+            let instruction = RawInstruction::from_be_bytes(self.array()?);
+            let gas = Word::from_be_bytes(self.array()?);
+            let [count] = self.array()?;
+            let written = (0..count)
+                .map(|_| {
+                    let [register] = self.array()?;
+                    let value = Word::from_be_bytes(self.array()?);
+                    Ok((RegId::new(register), value))
+                })
+                .collect::<io::Result<_>>()?;
+            Ok(TraceStep {
+                location: InstructionLocation::new(context, offset),
+                instruction,
+                written,
+                gas,
+            })
+        }
+    }
+
+    impl<R: io::Read> Iterator for TraceReader<R> {
+        type Item = io::Result<TraceStep>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let mut tag = [0];
+            match self.reader.read(&mut tag) {
+                Ok(0) => None,
+                Ok(_) => Some(self.step(tag[0])),
+                Err(error) => Some(Err(error)),
+            }
+        }
+    }
+}
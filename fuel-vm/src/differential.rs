@@ -0,0 +1,480 @@
+//! Differential execution of a corpus of transactions by two interpreters, e.g. the
+//! current build and the build of a previous release, comparing their results.
+//!
+//! Each [`CorpusCase`] is a transaction with the snapshot of the storage it is
+//! executed on. The [`DifferentialHarness`] executes every case with both
+//! [`ReferenceExecutor`]s and reports the cases where the final state of the program,
+//! the receipts, the remaining gas, the storage or the executed steps differ, with the
+//! steps compared by [`diff::compare`](crate::diff::compare).
+//!
+//! The [`InterpreterExecutor`] is the interpreter of this build. Two of them with
+//! different gas costs give a reference that is expected to differ.
+
+use alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt;
+
+use fuel_tx::{
+    ConsensusParameters,
+    GasCosts,
+    Receipt,
+    Transaction,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::IntoChecked,
+    diff::{
+        self,
+        Divergence,
+        ExecutionTrace,
+        TraceRecorder,
+    },
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        Interpreter,
+        InterpreterParams,
+    },
+    state::ProgramState,
+    storage::{
+        InterpreterStorage,
+        MemoryStorage,
+        StorageDiff,
+        StorageSnapshot,
+    },
+    transactor::Transactor,
+};
+
+/// A transaction of the corpus with the storage it is executed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorpusCase {
+    /// The name of the case in the reports.
+    pub name: String,
+    /// The storage before the transaction.
+    pub storage: StorageSnapshot,
+    /// The executed transaction.
+    pub transaction: Transaction,
+}
+
+/// The cases executed by the [`DifferentialHarness`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Corpus {
+    cases: Vec<CorpusCase>,
+}
+
+impl Corpus {
+    /// New empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `case` to the corpus.
+    pub fn push(&mut self, case: CorpusCase) {
+        self.cases.push(case);
+    }
+
+    /// The cases of the corpus.
+    pub fn cases(&self) -> &[CorpusCase] {
+        &self.cases
+    }
+}
+
+impl FromIterator<CorpusCase> for Corpus {
+    fn from_iter<I: IntoIterator<Item = CorpusCase>>(iter: I) -> Self {
+        Self {
+            cases: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod files {
+    use super::*;
+    use fuel_types::canonical::{
+        Deserialize,
+        Serialize,
+    };
+    use std::{
+        fs,
+        io,
+        path::Path,
+    };
+
+    /// The extension of the files of the transactions of a corpus.
+    const TRANSACTION_EXTENSION: &str = "tx";
+    /// The extension of the files of the storage snapshots of a corpus.
+    const SNAPSHOT_EXTENSION: &str = "snapshot";
+
+    fn invalid(error: impl fmt::Debug) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, alloc::format!("{error:?}"))
+    }
+
+    impl CorpusCase {
+        /// Saves the case to the `directory` as the `<name>.tx` file with the canonical
+        /// encoding of the transaction and the `<name>.snapshot` file with the storage
+        /// snapshot, in the format of [`MemoryStorage::save_to`].
+        pub fn save_to<P: AsRef<Path>>(&self, directory: P) -> io::Result<()> {
+            let directory = directory.as_ref();
+            let snapshot = postcard::to_allocvec(&self.storage).map_err(invalid)?;
+            fs::write(
+                directory
+                    .join(&self.name)
+                    .with_extension(SNAPSHOT_EXTENSION),
+                snapshot,
+            )?;
+            fs::write(
+                directory
+                    .join(&self.name)
+                    .with_extension(TRANSACTION_EXTENSION),
+                self.transaction.to_bytes(),
+            )
+        }
+    }
+
+    impl Corpus {
+        /// Loads the cases saved to the `directory` by [`CorpusCase::save_to`], sorted
+        /// by name. Every `.tx` file must have its `.snapshot` file.
+        pub fn load_dir<P: AsRef<Path>>(directory: P) -> io::Result<Self> {
+            let mut transactions = Vec::new();
+            for entry in fs::read_dir(directory)? {
+                let path = entry?.path();
+                if path.extension() == Some(TRANSACTION_EXTENSION.as_ref()) {
+                    transactions.push(path);
+                }
+            }
+            transactions.sort();
+
+            transactions
+                .into_iter()
+                .map(|path| {
+                    let name = path
+                        .file_stem()
+                        .and_then(|name| name.to_str())
+                        .ok_or_else(|| invalid(&path))?
+                        .to_string();
+                    let transaction =
+                        Transaction::from_bytes(&fs::read(&path)?).map_err(invalid)?;
+                    let snapshot = fs::read(path.with_extension(SNAPSHOT_EXTENSION))?;
+                    let storage = postcard::from_bytes(&snapshot).map_err(invalid)?;
+                    Ok(CorpusCase {
+                        name,
+                        storage,
+                        transaction,
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+/// The result of the execution of a transaction by a [`ReferenceExecutor`].
+#[derive(Debug, Clone)]
+pub struct ExecutionOutcome {
+    /// The final state of the program, or the error of the check or the execution of
+    /// the transaction.
+    pub result: Result<ProgramState, String>,
+    /// The receipts of the execution.
+    pub receipts: Vec<Receipt>,
+    /// The gas remaining after the execution.
+    pub gas_remaining: Word,
+    /// The executed steps.
+    pub trace: ExecutionTrace,
+    /// The storage after the execution.
+    pub storage: MemoryStorage,
+}
+
+impl ExecutionOutcome {
+    /// The outcome of a transaction that wasn't executed because of the `error`,
+    /// leaving the `storage` unchanged.
+    pub fn rejected(storage: MemoryStorage, error: impl Into<String>) -> Self {
+        Self {
+            result: Err(error.into()),
+            receipts: Vec::new(),
+            gas_remaining: 0,
+            trace: ExecutionTrace::new(),
+            storage,
+        }
+    }
+}
+
+/// An interpreter executing the transactions of the corpus, e.g. the build of a
+/// previous release behind a dynamic library.
+pub trait ReferenceExecutor {
+    /// The name of the interpreter in the reports.
+    fn name(&self) -> &str;
+
+    /// Executes the `transaction` on the `storage`.
+    fn execute(
+        &self,
+        storage: MemoryStorage,
+        transaction: &Transaction,
+    ) -> ExecutionOutcome;
+}
+
+/// The interpreter of this build as a [`ReferenceExecutor`].
+#[derive(Debug, Clone)]
+pub struct InterpreterExecutor {
+    name: String,
+    consensus_params: ConsensusParameters,
+    params: InterpreterParams,
+}
+
+impl InterpreterExecutor {
+    /// New executor checking and executing the transactions with the
+    /// `consensus_params`, with a zero gas price.
+    pub fn new(name: impl Into<String>, consensus_params: ConsensusParameters) -> Self {
+        let params = InterpreterParams::new(0, &consensus_params);
+        Self {
+            name: name.into(),
+            consensus_params,
+            params,
+        }
+    }
+
+    /// Replaces the gas costs used for the check and the execution of the
+    /// transactions.
+    pub fn with_gas_costs(mut self, gas_costs: GasCosts) -> Self {
+        self.consensus_params.set_gas_costs(gas_costs.clone());
+        self.params.gas_costs = gas_costs;
+        self
+    }
+
+    fn run<Tx>(&self, storage: MemoryStorage, tx: Tx) -> ExecutionOutcome
+    where
+        Tx: ExecutableTransaction + Send + Sync + 'static,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata + Send + Sync + 'static,
+    {
+        let Ok(block_height) = storage.block_height();
+        let checked = match tx.into_checked(block_height, &self.consensus_params) {
+            Ok(checked) => checked,
+            Err(error) => {
+                return ExecutionOutcome::rejected(storage, alloc::format!("{error:?}"))
+            }
+        };
+
+        let recorder = Arc::new(TraceRecorder::new());
+        let mut interpreter =
+            Interpreter::<_, Tx>::with_storage(storage, self.params.clone());
+        interpreter.with_observer(recorder.clone());
+        let mut transactor = Transactor::from(interpreter);
+        transactor.transact(checked);
+
+        let result = transactor
+            .result()
+            .map(|transition| *transition.state())
+            .map_err(|error| error.to_string());
+        let interpreter = transactor.interpreter();
+        ExecutionOutcome {
+            result,
+            receipts: interpreter.receipts().to_vec(),
+            gas_remaining: interpreter.remaining_gas(),
+            trace: recorder.take(),
+            storage: interpreter.as_ref().clone(),
+        }
+    }
+}
+
+impl ReferenceExecutor for InterpreterExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute(
+        &self,
+        storage: MemoryStorage,
+        transaction: &Transaction,
+    ) -> ExecutionOutcome {
+        match transaction.clone() {
+            Transaction::Script(tx) => self.run(storage, tx),
+            Transaction::Create(tx) => self.run(storage, tx),
+            Transaction::Upgrade(tx) => self.run(storage, tx),
+            Transaction::Upload(tx) => self.run(storage, tx),
+            Transaction::Mint(_) => ExecutionOutcome::rejected(
+                storage,
+                "The mint transactions are executed by the block producer",
+            ),
+        }
+    }
+}
+
+/// A difference between the outcomes of a case, the value of the current
+/// interpreter first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The final states of the programs, or the errors, differ.
+    Result(Result<ProgramState, String>, Result<ProgramState, String>),
+    /// The receipts differ.
+    Receipts(Vec<Receipt>, Vec<Receipt>),
+    /// The remaining gas differs.
+    Gas(Word, Word),
+    /// The storages after the execution differ.
+    Storage(StorageDiff),
+    /// The executed steps differ.
+    Steps(Divergence),
+}
+
+impl Mismatch {
+    /// The differences between the outcomes of the `current` and the `reference`
+    /// interpreters, empty if they match.
+    pub fn between(current: ExecutionOutcome, reference: ExecutionOutcome) -> Vec<Self> {
+        let mut mismatches = Vec::new();
+        if current.result != reference.result {
+            mismatches.push(Self::Result(current.result, reference.result));
+        }
+        if current.receipts != reference.receipts {
+            mismatches.push(Self::Receipts(current.receipts, reference.receipts));
+        }
+        if current.gas_remaining != reference.gas_remaining {
+            mismatches.push(Self::Gas(current.gas_remaining, reference.gas_remaining));
+        }
+        let storage = current.storage.diff(&reference.storage);
+        if !storage.is_empty() {
+            mismatches.push(Self::Storage(storage));
+        }
+        let steps = diff::compare(current.trace, reference.trace);
+        if steps.index().is_some() {
+            mismatches.push(Self::Steps(steps));
+        }
+        mismatches
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Result(current, reference) => {
+                write!(f, "result: {current:?} != {reference:?}")
+            }
+            Self::Receipts(current, reference) => {
+                let index = current
+                    .iter()
+                    .zip(reference)
+                    .take_while(|(current, reference)| current == reference)
+                    .count();
+                write!(
+                    f,
+                    "receipts: {} != {} receipts, differing from the receipt {index}",
+                    current.len(),
+                    reference.len()
+                )
+            }
+            Self::Gas(current, reference) => {
+                write!(f, "gas remaining: {current} != {reference}")
+            }
+            Self::Storage(diff) => {
+                let entries = diff.contracts.len()
+                    + diff.balances.len()
+                    + diff.contract_state.len()
+                    + diff.consensus_parameters_versions.len()
+                    + diff.state_transition_bytecodes.len()
+                    + diff.state_transition_bytecodes_versions.len();
+                write!(f, "storage: {entries} entries differ")
+            }
+            Self::Steps(divergence) => match divergence.index() {
+                Some(index) => write!(f, "steps: diverging from the step {index}"),
+                None => f.write_str("steps: identical"),
+            },
+        }
+    }
+}
+
+/// The differences found for a case of the corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseReport {
+    /// The name of the case.
+    pub name: String,
+    /// The differences between the outcomes, empty if they match.
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// The report of a [`DifferentialHarness::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferentialReport {
+    /// The name of the current interpreter.
+    pub current: String,
+    /// The name of the reference interpreter.
+    pub reference: String,
+    /// The report of each case, in the order of the corpus.
+    pub cases: Vec<CaseReport>,
+}
+
+impl DifferentialReport {
+    /// Whether the outcomes of every case match.
+    pub fn is_match(&self) -> bool {
+        self.cases.iter().all(|case| case.mismatches.is_empty())
+    }
+
+    /// The cases with differing outcomes.
+    pub fn mismatched(&self) -> impl Iterator<Item = &CaseReport> {
+        self.cases.iter().filter(|case| !case.mismatches.is_empty())
+    }
+}
+
+impl fmt::Display for DifferentialReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} cases differ between `{}` and `{}`",
+            self.mismatched().count(),
+            self.cases.len(),
+            self.current,
+            self.reference
+        )?;
+        for case in self.mismatched() {
+            writeln!(f, "{}:", case.name)?;
+            for mismatch in &case.mismatches {
+                writeln!(f, "    {mismatch}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executes the corpus with the current and the reference interpreters.
+#[derive(Debug, Clone)]
+pub struct DifferentialHarness<Current, Reference> {
+    current: Current,
+    reference: Reference,
+}
+
+impl<Current, Reference> DifferentialHarness<Current, Reference>
+where
+    Current: ReferenceExecutor,
+    Reference: ReferenceExecutor,
+{
+    /// New harness comparing the `current` interpreter to the `reference` one.
+    pub fn new(current: Current, reference: Reference) -> Self {
+        Self { current, reference }
+    }
+
+    /// Executes the `case` with both interpreters.
+    pub fn run_case(&self, case: &CorpusCase) -> CaseReport {
+        let storage = MemoryStorage::from_snapshot(case.storage.clone());
+        let current = self.current.execute(storage.clone(), &case.transaction);
+        let reference = self.reference.execute(storage, &case.transaction);
+        CaseReport {
+            name: case.name.clone(),
+            mismatches: Mismatch::between(current, reference),
+        }
+    }
+
+    /// Executes every case of the `corpus` with both interpreters.
+    pub fn run(&self, corpus: &Corpus) -> DifferentialReport {
+        DifferentialReport {
+            current: self.current.name().to_string(),
+            reference: self.reference.name().to_string(),
+            cases: corpus
+                .cases()
+                .iter()
+                .map(|case| self.run_case(case))
+                .collect(),
+        }
+    }
+}
@@ -21,8 +21,6 @@ use core::{
     fmt,
 };
 
-use crate::storage::predicate;
-
 /// Interpreter runtime error variants.
 #[derive(Debug, derive_more::Display)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -248,7 +246,10 @@ impl<StorageError> From<Infallible> for RuntimeError<StorageError> {
 #[derive(Debug, Clone, PartialEq, derive_more::Display)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PredicateVerificationFailed {
-    /// The predicate did not use the amount of gas provided
+    /// The predicate did not use the amount of gas provided.
+    ///
+    /// Not returned anymore: a predicate may use less than its declared gas, and only
+    /// the gas used is charged.
     #[display(fmt = "Predicate used less than the required amount of gas")]
     GasMismatch,
     /// The transaction doesn't contain enough gas to evaluate the predicate
@@ -285,10 +286,8 @@ pub enum PredicateVerificationFailed {
     Storage,
 }
 
-impl From<InterpreterError<predicate::StorageUnavailable>>
-    for PredicateVerificationFailed
-{
-    fn from(error: InterpreterError<predicate::StorageUnavailable>) -> Self {
+impl<StorageError> From<InterpreterError<StorageError>> for PredicateVerificationFailed {
+    fn from(error: InterpreterError<StorageError>) -> Self {
         match error {
             error if error.panic_reason() == Some(PanicReason::OutOfGas) => {
                 PredicateVerificationFailed::OutOfGas
@@ -377,6 +376,10 @@ pub enum BugVariant {
         message = "The witness subsection index is higher than the total number of parts."
     )]
     NextSubsectionIndexIsHigherThanTotalNumberOfParts,
+
+    /// The opcode charged by the interpreter has no gas cost.
+    #[strum(message = "The opcodes charged by the interpreter have a gas cost.")]
+    MissingGasCost,
 }
 
 impl fmt::Display for BugVariant {
@@ -0,0 +1,236 @@
+//! Reproduction of the transactions found by the fuzzers.
+//!
+//! A reproducer is a self-contained blob with the consensus parameters, the snapshot
+//! of the storage and the transaction, created by [`make_reproducer`]. The fuzz
+//! targets keep it in a [`ReproducerGuard`] while executing the transaction, so it is
+//! left on disk after a panic or a timeout, and [`execute_reproducer`] executes it
+//! again with the same result.
+//!
+//! [`minimize_script`] then shortens the script of the reproducer to the shortest
+//! prefix that still fails.
+
+use alloc::{
+    format,
+    string::String,
+    vec::Vec,
+};
+use std::{
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use fuel_asm::{
+    Instruction,
+    PanicReason,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+    Transaction,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::IntoChecked,
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        InterpreterParams,
+    },
+    state::ProgramState,
+    storage::{
+        InterpreterStorage,
+        MemoryStorage,
+        StorageSnapshot,
+    },
+    transactor::Transactor,
+};
+
+/// The content of a reproducer, serialized with `postcard` by [`make_reproducer`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Reproducer {
+    /// The consensus parameters used to check and execute the transaction.
+    pub consensus_params: ConsensusParameters,
+    /// The storage before the transaction, with the block height of the execution.
+    pub storage: StorageSnapshot,
+    /// The executed transaction.
+    pub transaction: Transaction,
+    /// The gas price of the execution.
+    pub gas_price: Word,
+}
+
+/// The result of [`execute_reproducer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReproResult {
+    /// The blob isn't a reproducer.
+    Invalid(String),
+    /// The transaction failed the checks.
+    Rejected(String),
+    /// The transaction was executed to its final `state`.
+    Executed {
+        /// The final state of the program.
+        state: ProgramState,
+        /// The receipts of the execution.
+        receipts: Vec<Receipt>,
+    },
+    /// The execution failed with an error of the interpreter.
+    Failed {
+        /// The error of the interpreter.
+        error: String,
+        /// The receipts before the error.
+        receipts: Vec<Receipt>,
+    },
+}
+
+impl ReproResult {
+    /// The receipts of the execution, empty if the transaction wasn't executed.
+    pub fn receipts(&self) -> &[Receipt] {
+        match self {
+            Self::Executed { receipts, .. } | Self::Failed { receipts, .. } => receipts,
+            Self::Invalid(_) | Self::Rejected(_) => &[],
+        }
+    }
+
+    /// The reason of the panic of the execution, if any.
+    pub fn panic_reason(&self) -> Option<PanicReason> {
+        self.receipts()
+            .iter()
+            .find_map(|receipt| receipt.reason())
+            .map(|reason| *reason.reason())
+    }
+}
+
+/// Creates the reproducer of the execution of the `transaction` on the `storage`.
+pub fn make_reproducer(
+    consensus_params: &ConsensusParameters,
+    storage: &MemoryStorage,
+    transaction: &Transaction,
+    gas_price: Word,
+) -> Vec<u8> {
+    let reproducer = Reproducer {
+        consensus_params: consensus_params.clone(),
+        storage: storage.snapshot(),
+        transaction: transaction.clone(),
+        gas_price,
+    };
+    postcard::to_allocvec(&reproducer).expect("The reproducer is serializable")
+}
+
+/// Executes the reproducer created by [`make_reproducer`].
+///
+/// The storage is restored from its snapshot, so the execution only depends on the
+/// content of the reproducer.
+pub fn execute_reproducer(bytes: &[u8]) -> ReproResult {
+    let reproducer: Reproducer = match postcard::from_bytes(bytes) {
+        Ok(reproducer) => reproducer,
+        Err(error) => return ReproResult::Invalid(format!("{error}")),
+    };
+    let storage = MemoryStorage::from_snapshot(reproducer.storage);
+    let params = &reproducer.consensus_params;
+    let gas_price = reproducer.gas_price;
+    match reproducer.transaction {
+        Transaction::Script(tx) => transact(storage, params, gas_price, tx),
+        Transaction::Create(tx) => transact(storage, params, gas_price, tx),
+        Transaction::Upgrade(tx) => transact(storage, params, gas_price, tx),
+        Transaction::Upload(tx) => transact(storage, params, gas_price, tx),
+        Transaction::Mint(_) => ReproResult::Rejected(
+            "The mint transactions are executed by the block producer".into(),
+        ),
+    }
+}
+
+fn transact<Tx>(
+    storage: MemoryStorage,
+    consensus_params: &ConsensusParameters,
+    gas_price: Word,
+    tx: Tx,
+) -> ReproResult
+where
+    Tx: ExecutableTransaction + Send + Sync + 'static,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata + Send + Sync + 'static,
+{
+    let Ok(block_height) = storage.block_height();
+    let checked = match tx.into_checked(block_height, consensus_params) {
+        Ok(checked) => checked,
+        Err(error) => return ReproResult::Rejected(format!("{error:?}")),
+    };
+
+    let params = InterpreterParams::new(gas_price, consensus_params);
+    let mut transactor = Transactor::<_, Tx>::new(storage, params);
+    transactor.transact(checked);
+
+    let receipts = transactor.interpreter().receipts().to_vec();
+    match transactor.result() {
+        Ok(transition) => ReproResult::Executed {
+            state: *transition.state(),
+            receipts,
+        },
+        Err(error) => ReproResult::Failed {
+            error: format!("{error}"),
+            receipts,
+        },
+    }
+}
+
+/// Keeps a reproducer on disk while its transaction is executed.
+///
+/// The reproducer is written when the guard is created and removed when the guard is
+/// dropped, unless the thread is panicking. It is left on disk after a timeout that
+/// kills the process.
+#[derive(Debug)]
+pub struct ReproducerGuard {
+    path: PathBuf,
+}
+
+impl ReproducerGuard {
+    /// Writes the reproducer `bytes` to the file at the `path`.
+    pub fn new<P: AsRef<Path>>(path: P, bytes: &[u8]) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        fs::write(&path, bytes)?;
+        Ok(Self { path })
+    }
+
+    /// The path of the reproducer.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ReproducerGuard {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// The shortest prefix of the `script` for which `fails` holds, found by bisection.
+///
+/// The failure is expected to be kept by the longer prefixes, like a panic at an
+/// instruction. Returns `None` if the whole script doesn't fail.
+pub fn minimize_script<F>(
+    script: &[Instruction],
+    mut fails: F,
+) -> Option<Vec<Instruction>>
+where
+    F: FnMut(&[Instruction]) -> bool,
+{
+    if !fails(script) {
+        return None
+    }
+    // The prefix of `high` instructions fails and the ones shorter than `low` don't.
+    let (mut low, mut high) = (0, script.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if fails(&script[..middle]) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Some(script[..high].to_vec())
+}
@@ -0,0 +1,511 @@
+//! Benchmarks of the opcodes in the interpreter, fitted into a gas schedule.
+//!
+//! [`GasBench::run`] executes each benchmarked opcode in a loop of scripts inside the
+//! real [`Interpreter`], the fixed-cost opcodes at a single size and the
+//! dependent-cost opcodes at each of the [`GasBenchConfig::sizes`], and subtracts the
+//! time of the same loop without the opcode. The times are relative to the time of the
+//! [`GasBenchConfig::reference`] opcode, costing [`GasBenchConfig::reference_gas`], so
+//! that the schedule doesn't depend on the frequency of the CPU: the gas of an opcode
+//! is its time divided by the time of the reference, times the gas of the reference.
+//! The dependent costs are fitted by least squares, the base being the intercept and
+//! the gas per unit the slope.
+//!
+//! The opcodes that aren't benchmarked, like the jumps or the opcodes of the
+//! blockchain, keep the cost of [`GasCostsValues::default`] in the emitted schedule,
+//! and are marked as such in the [`GasBenchReport`].
+
+use alloc::{
+    collections::BTreeSet,
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    Opcode,
+    RegId,
+};
+use fuel_tx::{
+    consensus_parameters::gas::opcode_cost_key,
+    ConsensusParameters,
+    DependentCost,
+    Finalizable,
+    GasCostValue,
+    GasCosts,
+    GasCostsValues,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::IntoChecked,
+    interpreter::{
+        Interpreter,
+        InterpreterParams,
+    },
+    state::ProgramState,
+    storage::MemoryStorage,
+};
+
+/// The register of the result of the benchmarked opcodes.
+const RESULT: u8 = 0x10;
+/// The register of the first operand of the fixed-cost opcodes.
+const LHS: u8 = 0x11;
+/// The register of the second operand of the fixed-cost opcodes.
+const RHS: u8 = 0x12;
+/// The register counting down the iterations of the loop.
+const COUNTER: u8 = 0x13;
+/// The register of the address of the memory of the opcodes, in the heap.
+const MEMORY: u8 = 0x14;
+/// The register of the address of the second half of the memory.
+const SECOND_HALF: u8 = 0x15;
+/// The register of the number of units of the dependent-cost opcodes.
+const UNITS: u8 = 0x16;
+/// The bytes of memory of the fixed-cost opcodes.
+const FIXED_MEMORY: u32 = 128;
+
+/// The configuration of a [`GasBench`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasBenchConfig {
+    /// The iterations of the loop of a script, less than 2^18.
+    pub iterations: u32,
+    /// The executions of the opcode in an iteration of the loop, unrolled, less than
+    /// 2^12.
+    pub unroll: u16,
+    /// The executions of each script, the fastest being kept.
+    pub samples: usize,
+    /// The numbers of units of the dependent-cost opcodes, like the copied bytes of
+    /// `MCP`, at least two.
+    pub sizes: Vec<Word>,
+    /// The fixed-cost opcode the times are relative to.
+    pub reference: Opcode,
+    /// The gas of the reference opcode.
+    pub reference_gas: Word,
+    /// The benchmarked opcodes, all of the benchmarkable ones if `None`.
+    pub opcodes: Option<Vec<Opcode>>,
+}
+
+impl Default for GasBenchConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 1_000,
+            unroll: 64,
+            samples: 7,
+            sizes: vec![0, 1 << 10, 1 << 12, 1 << 14, 1 << 16, 1 << 18],
+            reference: Opcode::ADD,
+            reference_gas: 1,
+            opcodes: None,
+        }
+    }
+}
+
+impl GasBenchConfig {
+    /// Benchmarks only the `opcodes`, with the reference opcode.
+    pub fn with_opcodes(mut self, opcodes: impl IntoIterator<Item = Opcode>) -> Self {
+        self.opcodes = Some(opcodes.into_iter().collect());
+        self
+    }
+}
+
+/// The benchmarked instruction of an opcode.
+#[derive(Debug, Clone, Copy)]
+enum Scenario {
+    /// Executed with the operands in [`LHS`] and [`RHS`] and [`FIXED_MEMORY`] bytes at
+    /// [`MEMORY`].
+    Fixed(Instruction),
+    /// Executed on [`UNITS`] units, with twice the largest size and 64 bytes of memory
+    /// at [`MEMORY`], and [`SECOND_HALF`] in the middle.
+    Dependent(Instruction),
+}
+
+/// The instruction benchmarking the `opcode`, `None` for the opcodes changing the
+/// control flow, requiring a contract or the storage, with an immediate number of
+/// units, or without a cost.
+fn scenario(opcode: Opcode) -> Option<Scenario> {
+    let dependent = match opcode {
+        Opcode::MCP => Some(op::mcp(MEMORY, SECOND_HALF, UNITS)),
+        Opcode::MCL => Some(op::mcl(MEMORY, UNITS)),
+        Opcode::MEQ => Some(op::meq(RESULT, MEMORY, SECOND_HALF, UNITS)),
+        Opcode::S256 => Some(op::s256(SECOND_HALF, MEMORY, UNITS)),
+        Opcode::K256 => Some(op::k256(SECOND_HALF, MEMORY, UNITS)),
+        _ => None,
+    };
+    if let Some(instruction) = dependent {
+        return Some(Scenario::Dependent(instruction))
+    }
+
+    let fixed = match opcode {
+        Opcode::ADD => op::add(RESULT, LHS, RHS),
+        Opcode::ADDI => op::addi(RESULT, LHS, 3),
+        Opcode::AND => op::and(RESULT, LHS, RHS),
+        Opcode::ANDI => op::andi(RESULT, LHS, 3),
+        Opcode::DIV => op::div(RESULT, LHS, RHS),
+        Opcode::DIVI => op::divi(RESULT, LHS, 3),
+        Opcode::EQ => op::eq(RESULT, LHS, RHS),
+        Opcode::EXP => op::exp(RESULT, LHS, RHS),
+        Opcode::EXPI => op::expi(RESULT, LHS, 3),
+        Opcode::GT => op::gt(RESULT, LHS, RHS),
+        Opcode::LT => op::lt(RESULT, LHS, RHS),
+        Opcode::MLOG => op::mlog(RESULT, LHS, RHS),
+        Opcode::MROO => op::mroo(RESULT, LHS, RHS),
+        Opcode::MOD => op::mod_(RESULT, LHS, RHS),
+        Opcode::MODI => op::modi(RESULT, LHS, 3),
+        Opcode::MOVE => op::move_(RESULT, LHS),
+        Opcode::MOVI => op::movi(RESULT, 7),
+        Opcode::MUL => op::mul(RESULT, LHS, RHS),
+        Opcode::MULI => op::muli(RESULT, LHS, 3),
+        Opcode::MLDV => op::mldv(RESULT, LHS, RHS, RHS),
+        Opcode::NOOP => op::noop(),
+        Opcode::NOT => op::not(RESULT, LHS),
+        Opcode::OR => op::or(RESULT, LHS, RHS),
+        Opcode::ORI => op::ori(RESULT, LHS, 3),
+        Opcode::SLL => op::sll(RESULT, LHS, RHS),
+        Opcode::SLLI => op::slli(RESULT, LHS, 3),
+        Opcode::SRL => op::srl(RESULT, LHS, RHS),
+        Opcode::SRLI => op::srli(RESULT, LHS, 3),
+        Opcode::SUB => op::sub(RESULT, LHS, RHS),
+        Opcode::SUBI => op::subi(RESULT, LHS, 3),
+        Opcode::XOR => op::xor(RESULT, LHS, RHS),
+        Opcode::XORI => op::xori(RESULT, LHS, 3),
+        Opcode::LB => op::lb(RESULT, MEMORY, 0),
+        Opcode::LW => op::lw(RESULT, MEMORY, 0),
+        Opcode::SB => op::sb(MEMORY, LHS, 0),
+        Opcode::SW => op::sw(MEMORY, LHS, 0),
+        Opcode::BHEI => op::bhei(RESULT),
+        Opcode::GTF => op::gtf_args(RESULT, RegId::ZERO, GTFArgs::ScriptData),
+        Opcode::ECK1 => op::eck1(MEMORY, MEMORY, MEMORY),
+        Opcode::ECR1 => op::ecr1(MEMORY, MEMORY, MEMORY),
+        _ => return None,
+    };
+    Some(Scenario::Fixed(fixed))
+}
+
+/// The cost of an opcode, benchmarked or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasBenchEntry {
+    /// The name of the cost, as in the JSON of the gas costs.
+    pub key: String,
+    /// The opcode of the cost.
+    pub opcode: Opcode,
+    /// The cost of [`GasCostsValues::default`].
+    pub current: GasCostValue,
+    /// The benchmarked cost, `None` for an opcode not benchmarked, or whose script
+    /// didn't return.
+    pub measured: Option<GasCostValue>,
+}
+
+/// The gas schedule fitted by a [`GasBench`], rendered as a table comparing the costs
+/// with the current defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasBenchReport {
+    entries: Vec<GasBenchEntry>,
+    values: GasCostsValues,
+    max_size: Word,
+}
+
+impl GasBenchReport {
+    /// The costs of the opcodes, by opcode.
+    pub fn entries(&self) -> &[GasBenchEntry] {
+        &self.entries
+    }
+
+    /// The complete gas costs, with the benchmarked costs, and the defaults for the
+    /// others.
+    pub fn values(&self) -> &GasCostsValues {
+        &self.values
+    }
+
+    /// The JSON of [`Self::values`], as loaded by
+    /// [`GasCosts::from_reader`](fuel_tx::GasCosts::from_reader).
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.values).expect("Gas costs are serializable")
+    }
+
+    /// The change of the measured cost from the current one, in percent, at the
+    /// largest size for the dependent costs, `None` for the unmeasured costs.
+    pub fn delta(&self, entry: &GasBenchEntry) -> Option<f64> {
+        let measured = entry.measured?.resolve(self.max_size) as f64;
+        let current = entry.current.resolve(self.max_size) as f64;
+        Some((measured - current) / current.max(1.0) * 100.0)
+    }
+}
+
+impl fmt::Display for GasBenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<14} {:<32} {:<32} delta",
+            "cost", "current", "measured"
+        )?;
+        for entry in &self.entries {
+            let current = entry.current.to_string();
+            match (entry.measured, self.delta(entry)) {
+                (Some(measured), Some(delta)) => writeln!(
+                    f,
+                    "{:<14} {current:<32} {:<32} {delta:+.1}%",
+                    entry.key,
+                    measured.to_string()
+                )?,
+                _ => writeln!(f, "{:<14} {current:<32} {:<32} -", entry.key, "-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Benchmarks the opcodes in the interpreter, with the gas costs of
+/// [`GasCosts::free`] to execute long loops.
+#[derive(Debug, Clone)]
+pub struct GasBench {
+    config: GasBenchConfig,
+    params: ConsensusParameters,
+}
+
+impl GasBench {
+    /// Benchmarks with the `config`.
+    pub fn new(config: GasBenchConfig) -> Self {
+        let mut params = ConsensusParameters::standard();
+        params.set_gas_costs(GasCosts::free());
+        Self { config, params }
+    }
+
+    /// Benchmarks the opcodes and fits the gas schedule.
+    pub fn run(&self) -> GasBenchReport {
+        let defaults = GasCostsValues::default();
+        let max_size = self.config.sizes.iter().copied().max().unwrap_or_default();
+        let reference_time = self
+            .fixed_time(self.config.reference)
+            .unwrap_or_default()
+            .max(f64::MIN_POSITIVE);
+
+        let mut keys = BTreeSet::new();
+        let mut entries = Vec::new();
+        for opcode in Opcode::ALL.iter().copied() {
+            let (Some(key), Some(current)) =
+                (opcode_cost_key(opcode), defaults.cost_of(opcode))
+            else {
+                continue;
+            };
+            if !keys.insert(key.clone()) {
+                continue;
+            }
+            let benchmarked = match &self.config.opcodes {
+                Some(opcodes) => opcodes.contains(&opcode),
+                None => true,
+            };
+            let measured = if opcode == self.config.reference {
+                Some(GasCostValue::Fixed(self.config.reference_gas))
+            } else if benchmarked {
+                self.measure(opcode, reference_time)
+            } else {
+                None
+            };
+            entries.push(GasBenchEntry {
+                key,
+                opcode,
+                current,
+                measured,
+            });
+        }
+
+        let mut json =
+            serde_json::to_value(&defaults).expect("Gas costs are serializable");
+        let costs = json
+            .as_object_mut()
+            .and_then(|versions| versions.values_mut().next())
+            .and_then(serde_json::Value::as_object_mut)
+            .expect("Gas costs are versioned objects");
+        for entry in &entries {
+            let value = match entry.measured {
+                Some(GasCostValue::Fixed(gas)) => serde_json::Value::from(gas),
+                Some(GasCostValue::Dependent(cost)) => {
+                    serde_json::to_value(cost).expect("Costs are serializable")
+                }
+                None => continue,
+            };
+            costs.insert(entry.key.clone(), value);
+        }
+        let values = serde_json::from_value(json).expect("The costs are complete");
+
+        GasBenchReport {
+            entries,
+            values,
+            max_size,
+        }
+    }
+
+    /// The cost of the `opcode` relative to the `reference_time`.
+    fn measure(&self, opcode: Opcode, reference_time: f64) -> Option<GasCostValue> {
+        let to_gas = |time: f64| time / reference_time * self.config.reference_gas as f64;
+        match scenario(opcode)? {
+            Scenario::Fixed(_) => {
+                let gas = to_gas(self.fixed_time(opcode)?);
+                Some(GasCostValue::Fixed(round(gas)))
+            }
+            Scenario::Dependent(instruction) => {
+                let points = self
+                    .config
+                    .sizes
+                    .iter()
+                    .map(|size| {
+                        let time = self.dependent_time(instruction, *size)?;
+                        Some((*size as f64, to_gas(time)))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let (base, slope) = fit(&points);
+                let base = round(base);
+                let cost = if slope >= 1.0 {
+                    DependentCost::from_gas_per_unit(base, round(slope))
+                } else if slope > 0.0 {
+                    DependentCost::from_units_per_gas(base, round(1.0 / slope).max(1))
+                } else {
+                    DependentCost::from_gas_per_unit(base, 0)
+                };
+                Some(GasCostValue::Dependent(cost))
+            }
+        }
+    }
+
+    /// The time of an execution of the fixed-cost `opcode`, in nanoseconds.
+    fn fixed_time(&self, opcode: Opcode) -> Option<f64> {
+        let Some(Scenario::Fixed(instruction)) = scenario(opcode) else {
+            return None
+        };
+        let setup = [
+            op::movi(LHS, 7),
+            op::movi(RHS, 3),
+            op::movi(MEMORY, FIXED_MEMORY),
+            op::aloc(MEMORY),
+            op::move_(MEMORY, RegId::HP),
+            op::addi(SECOND_HALF, MEMORY, 64),
+        ];
+        self.time(&setup, instruction, Vec::new())
+    }
+
+    /// The time of an execution of the dependent-cost `instruction` on `size` units, in
+    /// nanoseconds.
+    fn dependent_time(&self, instruction: Instruction, size: Word) -> Option<f64> {
+        let max_size = self.config.sizes.iter().copied().max().unwrap_or_default();
+        let setup = [
+            op::gtf_args(UNITS, RegId::ZERO, GTFArgs::ScriptData),
+            op::lw(MEMORY, UNITS, 1),
+            op::lw(SECOND_HALF, UNITS, 2),
+            op::lw(UNITS, UNITS, 0),
+            op::aloc(MEMORY),
+            op::move_(MEMORY, RegId::HP),
+            op::add(SECOND_HALF, MEMORY, SECOND_HALF),
+        ];
+        let half = max_size.saturating_add(32);
+        let data = [size, half.saturating_mul(2), half]
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect();
+        self.time(&setup, instruction, data)
+    }
+
+    /// The time of an execution of the `instruction` after the `setup`, less the time
+    /// of the empty loop, in nanoseconds.
+    fn time(
+        &self,
+        setup: &[Instruction],
+        instruction: Instruction,
+        data: Vec<u8>,
+    ) -> Option<f64> {
+        let unroll = self.config.unroll.max(1);
+        let executions = f64::from(self.config.iterations) * f64::from(unroll);
+        let with = self.loop_time(setup, Some(instruction), data.clone())?;
+        let without = self.loop_time(setup, None, data)?;
+        Some(with.saturating_sub(without).as_nanos() as f64 / executions)
+    }
+
+    /// The fastest of the samples of the loop executing the `instruction`
+    /// [`GasBenchConfig::unroll`] times by iteration, `None` if the script doesn't
+    /// return.
+    fn loop_time(
+        &self,
+        setup: &[Instruction],
+        instruction: Option<Instruction>,
+        data: Vec<u8>,
+    ) -> Option<Duration> {
+        let unroll = self.config.unroll.max(1);
+        let body = instruction
+            .into_iter()
+            .flat_map(|instruction| vec![instruction; unroll.into()]);
+        let script = setup
+            .iter()
+            .copied()
+            .chain([op::movi(COUNTER, self.config.iterations)])
+            .chain(body)
+            .chain([
+                op::subi(COUNTER, COUNTER, 1),
+                op::jnzb(
+                    COUNTER,
+                    RegId::ZERO,
+                    if instruction.is_some() { unroll } else { 0 },
+                ),
+                op::ret(RegId::ONE),
+            ])
+            .collect::<Vec<u8>>();
+        let tx = TransactionBuilder::<Script>::script(script, data)
+            .with_params(self.params.clone())
+            .script_gas_limit(self.params.tx_params().max_gas_per_tx() / 2)
+            .add_random_fee_input()
+            .finalize()
+            .into_checked(Default::default(), &self.params)
+            .ok()?
+            .into_ready(0, self.params.gas_costs(), self.params.fee_params())
+            .ok()?;
+
+        let mut fastest: Option<Duration> = None;
+        for _ in 0..self.config.samples.max(1) {
+            let mut vm = Interpreter::<_, Script>::with_storage(
+                MemoryStorage::default(),
+                InterpreterParams::new(0, &self.params),
+            );
+            let start = Instant::now();
+            let state = *vm.transact(tx.clone()).ok()?.state();
+            let elapsed = start.elapsed();
+            if !matches!(state, ProgramState::Return(1)) {
+                return None
+            }
+            fastest = Some(fastest.map_or(elapsed, |fastest| fastest.min(elapsed)));
+        }
+        fastest
+    }
+}
+
+/// The intercept and the slope of the least squares line of the `points`.
+fn fit(points: &[(f64, f64)]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    let slope = if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    };
+    (mean_y - slope * mean_x, slope)
+}
+
+/// The gas of a fitted value, rounded to the nearest, negative values counting as zero.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn round(gas: f64) -> Word {
+    gas.round().max(0.0) as Word
+}
@@ -9,7 +9,11 @@ use crate::{
     error::SimpleResult,
     state::Debugger,
 };
-use alloc::vec::Vec;
+use alloc::{
+    collections::BTreeMap,
+    sync::Arc,
+    vec::Vec,
+};
 use core::{
     mem,
     ops::Index,
@@ -49,38 +53,58 @@ use fuel_types::{
 mod alu;
 mod balances;
 mod blockchain;
+#[cfg(feature = "tracing")]
+mod call_spans;
 mod constructors;
 pub mod contract;
 mod crypto;
 pub mod diff;
 mod executors;
 mod flow;
+mod frame_gas;
 mod gas;
 mod initialization;
 mod internal;
 mod log;
 mod memory;
 mod metadata;
+mod params;
 mod post_execution;
 mod receipts;
 
 mod debug;
 mod ecal;
 
-use crate::profiler::Profiler;
+use crate::{
+    coverage::Coverage,
+    observer::ExecutionObserver,
+    profiler::Profiler,
+};
 
 #[cfg(feature = "profile-gas")]
 use crate::profiler::InstructionLocation;
 
+#[cfg(feature = "memory-heat-map")]
+use crate::memory_heat_map::{
+    HeatMapFrame,
+    MemoryHeatMap,
+};
+
 pub use balances::RuntimeBalances;
 pub use ecal::{
     EcalHandler,
+    GasCharger,
     PredicateErrorEcal,
 };
+pub use frame_gas::FrameGas;
 pub use memory::{
     Memory,
     MemoryRange,
 };
+pub use params::{
+    InterpreterParamsBuilder,
+    InterpreterParamsError,
+};
 
 use crate::checked_transaction::{
     CreateCheckedMetadata,
@@ -124,6 +148,15 @@ pub struct Interpreter<S, Tx = (), Ecal = NotSupportedEcal> {
     context: Context,
     balances: RuntimeBalances,
     profiler: Profiler,
+    /// The bitmaps of the executed instructions, when collecting the coverage.
+    coverage: Option<Coverage>,
+    /// The observer notified after each instruction.
+    observer: Option<Arc<dyn ExecutionObserver>>,
+    /// The gas consumed by the frames of the script.
+    frame_gas: frame_gas::FrameGasTracker,
+    /// The `tracing` spans of the frames of the script.
+    #[cfg(feature = "tracing")]
+    call_spans: call_spans::CallSpans,
     interpreter_params: InterpreterParams,
     /// `PanicContext` after the latest execution. It is consumed by
     /// `append_panic_receipt` and is `PanicContext::None` after consumption.
@@ -132,7 +165,10 @@ pub struct Interpreter<S, Tx = (), Ecal = NotSupportedEcal> {
 }
 
 /// Interpreter parameters
+///
+/// See [`InterpreterParamsBuilder`] to build and validate them.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterpreterParams {
     /// Gas Price
     pub gas_price: Word,
@@ -152,40 +188,47 @@ pub struct InterpreterParams {
     pub fee_params: FeeParameters,
     /// Base Asset ID
     pub base_asset_id: AssetId,
+    /// Whether the scripts run with unlimited gas, see [`Self::free_gas`].
+    pub free_gas: bool,
+    /// The gas a call frame of each contract may consume by its own instructions,
+    /// excluding the frames it calls, like the [`FrameGas::self_gas`].
+    ///
+    /// A frame exceeding the allowance of its contract panics with `OutOfGas`. The
+    /// panic receipt is attributed to the contract, and its frame returns `0` to the
+    /// caller, which continues with the remaining gas. The allowance is checked after
+    /// each instruction, so the instruction exceeding it completes, and the state
+    /// changes of the frame aren't reverted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_allowance: BTreeMap<ContractId, Word>,
 }
 
 #[cfg(feature = "test-helpers")]
 impl Default for InterpreterParams {
     fn default() -> Self {
-        Self {
-            gas_price: 0,
-            gas_costs: Default::default(),
-            max_inputs: fuel_tx::TxParameters::DEFAULT.max_inputs(),
-            contract_max_size: fuel_tx::ContractParameters::DEFAULT.contract_max_size(),
-            tx_offset: fuel_tx::TxParameters::DEFAULT.tx_offset(),
-            max_message_data_length: fuel_tx::PredicateParameters::DEFAULT
-                .max_message_data_length(),
-            chain_id: ChainId::default(),
-            fee_params: FeeParameters::default(),
-            base_asset_id: Default::default(),
-        }
+        InterpreterParamsBuilder::default().into_unchecked()
     }
 }
 
 impl InterpreterParams {
     /// Constructor for `InterpreterParams`
     pub fn new<T: Into<CheckPredicateParams>>(gas_price: Word, params: T) -> Self {
-        let params: CheckPredicateParams = params.into();
+        let mut builder = InterpreterParamsBuilder::new(params);
+        builder.gas_price(gas_price);
+        builder.into_unchecked()
+    }
+
+    /// Parameters of the zero-gas execution for the local development: the gas price is
+    /// zero and the gas of the scripts is unlimited. The gas is still counted with the
+    /// gas costs of the `params`, see [`GasCosts::free`] to count nothing, but never
+    /// runs out.
+    ///
+    /// The transactions checked with
+    /// [`IntoChecked::into_checked_free_gas`](crate::checked_transaction::IntoChecked::into_checked_free_gas)
+    /// run with zero-value inputs, since their max fee isn't deducted.
+    pub fn free_gas<T: Into<CheckPredicateParams>>(params: T) -> Self {
         Self {
-            gas_price,
-            gas_costs: params.gas_costs,
-            max_inputs: params.max_inputs,
-            contract_max_size: params.contract_max_size,
-            tx_offset: params.tx_offset,
-            max_message_data_length: params.max_message_data_length,
-            chain_id: params.chain_id,
-            fee_params: params.fee_params,
-            base_asset_id: params.base_asset_id,
+            free_gas: true,
+            ..Self::new(0, params)
         }
     }
 }
@@ -223,7 +266,8 @@ impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
         &mut self.registers
     }
 
-    pub(crate) fn call_stack(&self) -> &[CallFrame] {
+    /// The call frames of the current execution, from the outermost one.
+    pub fn call_stack(&self) -> &[CallFrame] {
         self.frames.as_slice()
     }
 
@@ -313,11 +357,42 @@ impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
         self.frames.last().map(|frame| *frame.to())
     }
 
+    pub(crate) const fn interpreter_params(&self) -> &InterpreterParams {
+        &self.interpreter_params
+    }
+
     /// Reference to the underlying profiler
     #[cfg(feature = "profile-any")]
     pub const fn profiler(&self) -> &Profiler {
         &self.profiler
     }
+
+    /// The coverage collected since it was attached with
+    /// [`Self::with_coverage`].
+    pub const fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Detaches the collected coverage, stopping the collection.
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
+    /// The memory accesses recorded since the heat map was attached with
+    /// [`Self::with_memory_heat_map`].
+    #[cfg(feature = "memory-heat-map")]
+    pub fn memory_heat_map(&self) -> Option<MemoryHeatMap> {
+        self.memory.heat_map()
+    }
+
+    /// Attributes the next memory accesses to the current call frame.
+    #[cfg(feature = "memory-heat-map")]
+    pub(crate) fn enter_heat_map_frame(&self) {
+        self.memory.set_heat_map_frame(HeatMapFrame {
+            depth: self.frames.len(),
+            contract: self.frames.last().map(|frame| *frame.to()),
+        });
+    }
 }
 
 pub(crate) fn flags(flag: Reg<FLAG>) -> Flags {
@@ -446,6 +521,9 @@ pub trait ExecutableTransaction:
     /// `initial_balances` contains the initial state of the free balances
     ///
     /// `balances` will contain the current state of the free balances
+    ///
+    /// The unused max fee is refunded only if `initial_balances` deducted it, charging
+    /// the predicate gas of `initial_balances`.
     #[allow(clippy::too_many_arguments)]
     fn update_outputs<I>(
         &mut self,
@@ -461,9 +539,21 @@ pub trait ExecutableTransaction:
     where
         I: for<'a> Index<&'a AssetId, Output = Word>,
     {
-        let gas_refund = self
-            .refund_fee(gas_costs, fee_params, used_gas, gas_price)
-            .ok_or(ValidityError::GasCostsCoinsOverflow)?;
+        let gas_refund = if initial_balances.max_fee_deducted {
+            match initial_balances.predicate_gas_used {
+                Some(predicate_gas_used) => self.refund_fee_with_predicate_gas(
+                    gas_costs,
+                    fee_params,
+                    used_gas,
+                    predicate_gas_used,
+                    gas_price,
+                ),
+                None => self.refund_fee(gas_costs, fee_params, used_gas, gas_price),
+            }
+            .ok_or(ValidityError::GasCostsCoinsOverflow)?
+        } else {
+            0
+        };
 
         self.outputs_mut().iter_mut().try_for_each(|o| match o {
             // If revert, set base asset to initial balance and refund unused gas
@@ -592,12 +682,23 @@ pub struct InitialBalances {
     pub non_retryable: NonRetryableFreeBalances,
     /// See [`RetryableAmount`].
     pub retryable: Option<RetryableAmount>,
+    /// Whether the max fee is deducted from the base asset, and the unused part is
+    /// refunded to its change output.
+    pub max_fee_deducted: bool,
+    /// The gas charged for the predicates instead of their declared
+    /// `predicate_gas_used`, see
+    /// [`PredicatesGas::charged`](crate::checked_transaction::PredicatesGas::charged).
+    /// `None` charges the `predicate_gas_used` of the inputs.
+    pub predicate_gas_used: Option<Word>,
 }
 
 /// Methods that should be implemented by the checked metadata of supported transactions.
 pub trait CheckedMetadata {
     /// Returns the initial balances from the checked metadata of the transaction.
     fn balances(&self) -> InitialBalances;
+
+    /// Records the gas used by the verification of the predicates, by input index.
+    fn record_predicate_gas_used(&mut self, gas_used_by_input: &[(usize, Word)]);
 }
 
 impl CheckedMetadata for ScriptCheckedMetadata {
@@ -605,8 +706,14 @@ impl CheckedMetadata for ScriptCheckedMetadata {
         InitialBalances {
             non_retryable: self.non_retryable_balances.clone(),
             retryable: Some(self.retryable_balance),
+            max_fee_deducted: self.max_fee_deducted,
+            predicate_gas_used: Some(self.predicate_gas.charged()),
         }
     }
+
+    fn record_predicate_gas_used(&mut self, gas_used_by_input: &[(usize, Word)]) {
+        self.predicate_gas.record_used(gas_used_by_input)
+    }
 }
 
 impl CheckedMetadata for CreateCheckedMetadata {
@@ -614,8 +721,14 @@ impl CheckedMetadata for CreateCheckedMetadata {
         InitialBalances {
             non_retryable: self.free_balances.clone(),
             retryable: None,
+            max_fee_deducted: self.max_fee_deducted,
+            predicate_gas_used: Some(self.predicate_gas.charged()),
         }
     }
+
+    fn record_predicate_gas_used(&mut self, gas_used_by_input: &[(usize, Word)]) {
+        self.predicate_gas.record_used(gas_used_by_input)
+    }
 }
 
 impl CheckedMetadata for UpgradeCheckedMetadata {
@@ -623,8 +736,14 @@ impl CheckedMetadata for UpgradeCheckedMetadata {
         InitialBalances {
             non_retryable: self.free_balances.clone(),
             retryable: None,
+            max_fee_deducted: self.max_fee_deducted,
+            predicate_gas_used: Some(self.predicate_gas.charged()),
         }
     }
+
+    fn record_predicate_gas_used(&mut self, gas_used_by_input: &[(usize, Word)]) {
+        self.predicate_gas.record_used(gas_used_by_input)
+    }
 }
 
 impl CheckedMetadata for UploadCheckedMetadata {
@@ -632,8 +751,14 @@ impl CheckedMetadata for UploadCheckedMetadata {
         InitialBalances {
             non_retryable: self.free_balances.clone(),
             retryable: None,
+            max_fee_deducted: self.max_fee_deducted,
+            predicate_gas_used: Some(self.predicate_gas.charged()),
         }
     }
+
+    fn record_predicate_gas_used(&mut self, gas_used_by_input: &[(usize, Word)]) {
+        self.predicate_gas.record_used(gas_used_by_input)
+    }
 }
 
 pub(crate) struct InputContracts<'vm, I> {
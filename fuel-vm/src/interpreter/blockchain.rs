@@ -14,6 +14,7 @@ use crate::{
             balance,
             balance_decrease,
             contract_size,
+            copy_contract_code_zero_fill_noownerchecks,
         },
         gas::{
             dependent_gas_charge_without_base,
@@ -27,10 +28,7 @@ use crate::{
             internal_contract,
             tx_id,
         },
-        memory::{
-            copy_from_slice_zero_fill_noownerchecks,
-            OwnershipRegisters,
-        },
+        memory::OwnershipRegisters,
         receipts::ReceiptsCtx,
         ExecutableTransaction,
         InputContracts,
@@ -40,19 +38,26 @@ use crate::{
     },
     prelude::Profiler,
     storage::{
+        contract_state_batch,
         ContractsAssetsStorage,
         ContractsRawCode,
+        ContractsState,
         ContractsStateData,
         InterpreterStorage,
     },
 };
-use alloc::vec::Vec;
+use alloc::borrow::Cow;
 use fuel_asm::PanicReason;
-use fuel_storage::StorageSize;
+use fuel_storage::{
+    StorageBatchInspect,
+    StorageSize,
+};
 use fuel_tx::{
     ContractIdExt,
     DependentCost,
+    NewStorageCost,
     Receipt,
+    StorageSlot,
 };
 use fuel_types::{
     bytes,
@@ -139,6 +144,8 @@ where
     }
 
     pub(crate) fn burn(&mut self, a: Word, b: Word) -> IoResult<(), S::DataError> {
+        self.ensure_current_contract_writable()?;
+
         let (SystemRegisters { fp, pc, is, .. }, _) =
             split_registers(&mut self.registers);
         BurnCtx {
@@ -154,7 +161,9 @@ where
     }
 
     pub(crate) fn mint(&mut self, a: Word, b: Word) -> IoResult<(), S::DataError> {
-        let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
+        self.ensure_current_contract_writable()?;
+
+        let new_storage_cost = self.gas_costs().new_storage();
         let (
             SystemRegisters {
                 cgas,
@@ -172,7 +181,7 @@ where
             memory: &self.memory,
             receipts: &mut self.receipts,
             profiler: &mut self.profiler,
-            new_storage_gas_per_byte,
+            new_storage_cost,
             cgas,
             ggas,
             fp: fp.as_ref(),
@@ -324,6 +333,8 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
+        self.ensure_current_contract_writable()?;
+
         let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
@@ -410,7 +421,9 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
-        let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
+        self.ensure_current_contract_writable()?;
+
+        let new_storage_cost = self.gas_costs().new_storage();
         let (
             SystemRegisters {
                 cgas,
@@ -435,7 +448,7 @@ where
                 memory,
                 context,
                 profiler: &mut self.profiler,
-                new_storage_gas_per_byte,
+                new_storage_cost,
                 current_contract: self.frames.last().map(|frame| frame.to()).copied(),
                 cgas,
                 ggas,
@@ -456,7 +469,9 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
-        let new_storage_per_byte = self.gas_costs().new_storage_per_byte();
+        self.ensure_current_contract_writable()?;
+
+        let new_storage_cost = self.gas_costs().new_storage();
         let contract_id = self.internal_contract();
         let (
             SystemRegisters {
@@ -483,7 +498,7 @@ where
             storage,
             memory,
             &mut self.profiler,
-            new_storage_per_byte,
+            new_storage_cost,
             self.frames.last().map(|frame| frame.to()).copied(),
             cgas,
             ggas,
@@ -512,9 +527,24 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
+        if d > 0 {
+            self.ensure_current_contract_writable()?;
+        }
+
         let base_asset_id = self.interpreter_params.base_asset_id;
         let max_message_data_length = self.max_message_data_length();
-        let (SystemRegisters { fp, pc, .. }, _) = split_registers(&mut self.registers);
+        let new_storage_cost = self.gas_costs().new_storage().if_per_slot_set();
+        let (
+            SystemRegisters {
+                cgas,
+                ggas,
+                fp,
+                is,
+                pc,
+                ..
+            },
+            _,
+        ) = split_registers(&mut self.registers);
         let input = MessageOutputCtx {
             base_asset_id,
             max_message_data_length,
@@ -522,8 +552,13 @@ where
             receipts: &mut self.receipts,
             balances: &mut self.balances,
             storage: &mut self.storage,
+            profiler: &mut self.profiler,
+            new_storage_cost,
             current_contract: self.frames.last().map(|frame| frame.to()).copied(),
+            cgas,
+            ggas,
             fp: fp.as_ref(),
+            is: is.as_ref(),
             pc,
             recipient_mem_address: a,
             msg_data_ptr: b,
@@ -604,10 +639,7 @@ where
 
         self.input_contracts.check(&contract_id)?;
 
-        // Fetch the storage contract
-        let contract = super::contract::contract(self.storage, &contract_id)?;
-        let contract_bytes = contract.as_ref().as_ref();
-        let contract_len = contract_bytes.len();
+        let contract_len = contract_size(self.storage, &contract_id)?;
         let profiler = ProfileGas {
             pc: self.pc.as_ref(),
             is: self.is,
@@ -627,9 +659,10 @@ where
         *self.ssp = new_sp;
 
         // Copy the code. Ownership checks are not used as the stack is adjusted above.
-        copy_from_slice_zero_fill_noownerchecks(
+        copy_contract_code_zero_fill_noownerchecks(
+            self.storage,
             self.memory,
-            contract_bytes,
+            &contract_id,
             region_start,
             contract_offset,
             length,
@@ -699,7 +732,7 @@ struct MintCtx<'vm, S> {
     memory: &'vm Memory,
     profiler: &'vm mut Profiler,
     receipts: &'vm mut ReceiptsCtx,
-    new_storage_gas_per_byte: Word,
+    new_storage_cost: NewStorageCost,
     cgas: RegMut<'vm, CGAS>,
     ggas: RegMut<'vm, GGAS>,
     fp: Reg<'vm, FP>,
@@ -736,7 +769,8 @@ where
                 self.cgas,
                 self.ggas,
                 profiler,
-                ((AssetId::LEN + WORD_SIZE) as u64) * self.new_storage_gas_per_byte,
+                self.new_storage_cost
+                    .resolve(1, (AssetId::LEN + WORD_SIZE) as Word),
             )?;
         }
 
@@ -789,9 +823,7 @@ where
 
         self.input_contracts.check(&contract_id)?;
 
-        let contract = super::contract::contract(self.storage, &contract_id)?;
-        let contract_bytes = contract.as_ref().as_ref();
-        let contract_len = contract_bytes.len();
+        let contract_len = contract_size(self.storage, &contract_id)?;
         let profiler = ProfileGas {
             pc: self.pc.as_ref(),
             is: self.is,
@@ -807,9 +839,10 @@ where
         )?;
 
         // Owner checks already performed above
-        copy_from_slice_zero_fill_noownerchecks(
+        copy_contract_code_zero_fill_noownerchecks(
+            self.storage,
             self.memory,
-            contract.as_ref().as_ref(),
+            &contract_id,
             dst_addr,
             offset,
             length,
@@ -1011,7 +1044,7 @@ pub(crate) struct StateWriteWordCtx<'vm, S> {
     pub memory: &'vm Memory,
     pub context: &'vm Context,
     pub profiler: &'vm mut Profiler,
-    pub new_storage_gas_per_byte: Word,
+    pub new_storage_cost: NewStorageCost,
     pub current_contract: Option<ContractId>,
     pub cgas: RegMut<'vm, CGAS>,
     pub ggas: RegMut<'vm, GGAS>,
@@ -1026,7 +1059,7 @@ pub(crate) fn state_write_word<S: InterpreterStorage>(
         memory,
         context,
         profiler,
-        new_storage_gas_per_byte,
+        new_storage_cost,
         current_contract,
         cgas,
         ggas,
@@ -1062,7 +1095,7 @@ pub(crate) fn state_write_word<S: InterpreterStorage>(
             cgas,
             ggas,
             profiler,
-            (2 * Bytes32::LEN as u64) * new_storage_gas_per_byte,
+            new_storage_cost.resolve(1, StorageSlot::SLOT_SIZE as Word),
         )?;
     }
 
@@ -1097,8 +1130,13 @@ where
     receipts: &'vm mut ReceiptsCtx,
     balances: &'vm mut RuntimeBalances,
     storage: &'vm mut S,
+    profiler: &'vm mut Profiler,
+    new_storage_cost: NewStorageCost,
     current_contract: Option<ContractId>,
+    cgas: RegMut<'vm, CGAS>,
+    ggas: RegMut<'vm, GGAS>,
     fp: Reg<'vm, FP>,
+    is: Reg<'vm, IS>,
     pc: RegMut<'vm, PC>,
     /// A
     recipient_mem_address: Word,
@@ -1126,6 +1164,20 @@ where
         let recipient = Address::new(self.memory.read_bytes(self.recipient_mem_address)?);
         let sender = Address::new(self.memory.read_bytes(*self.fp)?);
 
+        // The data of the message is retained in the outbox, charge gas for it
+        let profiler = ProfileGas {
+            pc: self.pc.as_ref(),
+            is: self.is,
+            current_contract: self.current_contract,
+            profiler: self.profiler,
+        };
+        gas_charge(
+            self.cgas,
+            self.ggas,
+            profiler,
+            self.new_storage_cost.resolve(1, self.msg_data_len),
+        )?;
+
         // validations passed, perform the mutations
 
         if let Some(source_contract) = self.current_contract {
@@ -1166,7 +1218,7 @@ struct StateReadQWordParams {
     num_slots: Word,
 }
 
-fn state_read_qword<S: InterpreterStorage>(
+fn state_read_qword<S>(
     contract_id: &ContractId,
     storage: &S,
     memory: &mut Memory,
@@ -1174,7 +1226,10 @@ fn state_read_qword<S: InterpreterStorage>(
     ownership_registers: OwnershipRegisters,
     result_register: &mut Word,
     params: StateReadQWordParams,
-) -> IoResult<(), S::DataError> {
+) -> IoResult<(), S::Error>
+where
+    S: StorageBatchInspect<ContractsState> + ?Sized,
+{
     let StateReadQWordParams {
         destination_pointer,
         origin_key_pointer,
@@ -1189,25 +1244,31 @@ fn state_read_qword<S: InterpreterStorage>(
 
     let origin_key = Bytes32::new(memory.read_bytes(origin_key_pointer)?);
 
-    let mut all_set = true;
-    let result: Vec<u8> = storage
-        .contract_state_range(contract_id, &origin_key, num_slots)
-        .map_err(RuntimeError::Storage)?
-        .into_iter()
-        .flat_map(|bytes| match bytes {
-            Some(bytes) => bytes.into_owned(),
-            None => {
-                all_set = false;
-                ContractsStateData::from(Bytes32::zeroed().as_ref())
-            }
-        })
-        .collect();
+    let values = contract_state_batch(storage, contract_id, &origin_key, num_slots)
+        .map_err(RuntimeError::Storage)?;
+    fn value_bytes<'a>(value: &'a Option<Cow<'_, ContractsStateData>>) -> &'a [u8] {
+        match value {
+            Some(bytes) => bytes.as_ref().as_ref(),
+            None => &[0; Bytes32::LEN],
+        }
+    }
 
-    *result_register = all_set as Word;
+    let len = values
+        .iter()
+        .map(|value| value_bytes(value).len())
+        .fold(0usize, usize::saturating_add);
+
+    // The values are copied into the memory from the borrowed slots, without
+    // collecting them first.
+    let mut destination = memory.write_noownerchecks(destination_pointer, len)?;
+    for value in &values {
+        let bytes = value_bytes(value);
+        let (slot, rest) = core::mem::take(&mut destination).split_at_mut(bytes.len());
+        slot.copy_from_slice(bytes);
+        destination = rest;
+    }
 
-    memory
-        .write_noownerchecks(destination_pointer, result.len())?
-        .copy_from_slice(&result);
+    *result_register = values.iter().all(Option::is_some) as Word;
 
     inc_pc(pc)?;
 
@@ -1229,7 +1290,7 @@ fn state_write_qword<'vm, S: InterpreterStorage>(
     storage: &mut S,
     memory: &Memory,
     profiler: &'vm mut Profiler,
-    new_storage_gas_per_byte: Word,
+    new_storage_cost: NewStorageCost,
     current_contract: Option<ContractId>,
     cgas: RegMut<'vm, CGAS>,
     ggas: RegMut<'vm, GGAS>,
@@ -1265,8 +1326,11 @@ fn state_write_qword<'vm, S: InterpreterStorage>(
             cgas,
             ggas,
             profiler,
-            // Overflow safety: unset_count * 32 can be at most VM_MAX_RAM
-            (unset_count as u64) * (2 * Bytes32::LEN as u64) * new_storage_gas_per_byte,
+            // Overflow safety: unset_count * 64 can be at most 2 * VM_MAX_RAM
+            new_storage_cost.resolve(
+                unset_count as Word,
+                (unset_count as Word) * StorageSlot::SLOT_SIZE as Word,
+            ),
         )?;
     }
 
@@ -1,7 +1,11 @@
 #![allow(clippy::cast_possible_truncation)]
+#![allow(non_snake_case)]
 use core::convert::Infallible;
 
-use alloc::vec;
+use alloc::{
+    vec,
+    vec::Vec,
+};
 
 use super::*;
 use crate::{
@@ -9,7 +13,11 @@ use crate::{
         memory::Memory,
         PanicContext,
     },
-    storage::MemoryStorage,
+    storage::{
+        InstrumentedStorage,
+        MemoryStorage,
+        StorageMetrics,
+    },
 };
 use fuel_tx::Contract;
 
@@ -125,3 +133,108 @@ fn test_code_copy() -> IoResult<(), Infallible> {
 
     Ok(())
 }
+
+const LARGE_CONTRACT_SIZE: usize = 100 * 1024;
+
+fn large_contract_code() -> Vec<u8> {
+    (0..LARGE_CONTRACT_SIZE).map(|i| (i % 251) as u8).collect()
+}
+
+/// Copies `num_bytes` of the large contract from the `offset` with `ccp`, returning
+/// the copied memory and the storage metrics.
+fn code_copy_large_contract(offset: Word, num_bytes: Word) -> (Vec<u8>, StorageMetrics) {
+    let mut storage = MemoryStorage::default();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut cgas = 1_000_000;
+    let mut ggas = 1_000_000;
+    let mut pc = 4;
+    let is = 0;
+
+    let contract_id = ContractId::from([4u8; 32]);
+    let dest_mem_address = 2001;
+    let contract_id_mem_address: Word = 32;
+
+    memory[contract_id_mem_address as usize
+        ..contract_id_mem_address as usize + ContractId::LEN]
+        .copy_from_slice(contract_id.as_ref());
+    storage
+        .storage_contract_insert(&contract_id, &Contract::from(large_contract_code()))
+        .unwrap();
+    let storage = InstrumentedStorage::new(storage);
+
+    let input_contracts = [contract_id];
+    let mut panic_context = PanicContext::None;
+    let input = CodeCopyCtx {
+        storage: &storage,
+        memory: &mut memory,
+        input_contracts: InputContracts::new(input_contracts.iter(), &mut panic_context),
+        profiler: &mut Profiler::default(),
+        current_contract: None,
+        owner: OwnershipRegisters {
+            sp: 1000,
+            ssp: 1000,
+            hp: 2000,
+            prev_hp: VM_MAX_RAM - 1,
+            context: Context::Call {
+                block_height: Default::default(),
+            },
+        },
+        gas_cost: DependentCost::from_units_per_gas(13, 1),
+        cgas: RegMut::new(&mut cgas),
+        ggas: RegMut::new(&mut ggas),
+        pc: RegMut::new(&mut pc),
+        is: Reg::new(&is),
+    };
+    input
+        .code_copy(dest_mem_address, contract_id_mem_address, offset, num_bytes)
+        .unwrap();
+    assert_eq!(cgas, 1_000_000 - LARGE_CONTRACT_SIZE as Word);
+
+    let copied = memory[dest_mem_address as usize..][..num_bytes as usize].to_vec();
+    (copied, storage.metrics())
+}
+
+#[test]
+fn code_copy__reads_only_the_copied_bytes_of_a_large_contract() {
+    let offset = LARGE_CONTRACT_SIZE / 2;
+
+    let (copied, metrics) = code_copy_large_contract(offset as Word, 1024);
+
+    assert_eq!(copied, large_contract_code()[offset..][..1024]);
+    assert_eq!(
+        metrics
+            .operation("ContractsRawCode::read_range")
+            .value_bytes,
+        1024
+    );
+    assert_eq!(metrics.total().value_bytes, 1024);
+    assert_eq!(metrics.operation("ContractsRawCode::get").calls, 0);
+    assert_eq!(metrics.operation("ContractsRawCode::read_alloc").calls, 0);
+}
+
+#[test]
+fn code_copy__from_the_start_of_the_code() {
+    let (copied, metrics) = code_copy_large_contract(0, 64);
+
+    assert_eq!(copied, large_contract_code()[..64]);
+    assert_eq!(metrics.total().value_bytes, 64);
+}
+
+#[test]
+fn code_copy__past_the_end_of_the_code_fills_zeros() {
+    let offset = LARGE_CONTRACT_SIZE - 16;
+
+    let (copied, metrics) = code_copy_large_contract(offset as Word, 64);
+
+    assert_eq!(copied[..16], large_contract_code()[offset..]);
+    assert_eq!(copied[16..], [0; 48]);
+    assert_eq!(metrics.total().value_bytes, 16);
+}
+
+#[test]
+fn code_copy__after_the_end_of_the_code_copies_only_zeros() {
+    let (copied, metrics) = code_copy_large_contract(LARGE_CONTRACT_SIZE as Word + 8, 64);
+
+    assert_eq!(copied, [0; 64]);
+    assert_eq!(metrics.total().value_bytes, 0);
+}
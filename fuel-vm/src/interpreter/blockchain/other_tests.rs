@@ -146,7 +146,10 @@ fn test_mint(
         receipts: &mut receipts,
         memory: &mut memory,
         profiler: &mut Profiler::default(),
-        new_storage_gas_per_byte: 1,
+        new_storage_cost: NewStorageCost {
+            per_slot: 0,
+            per_byte: 1,
+        },
         cgas: RegMut::new(&mut cgas),
         ggas: RegMut::new(&mut ggas),
         fp: Reg::new(&fp),
@@ -229,6 +229,9 @@ fn test_smo(
         .expect("Should be valid balance");
     let fp = 0;
     let mut pc = 0;
+    let mut cgas = 1_000_000;
+    let mut ggas = 1_000_000;
+    let is = 0;
 
     let input = MessageOutputCtx {
         base_asset_id,
@@ -237,12 +240,17 @@ fn test_smo(
         receipts: &mut receipts,
         balances: &mut balances,
         storage: &mut storage,
+        profiler: &mut Default::default(),
+        new_storage_cost: NewStorageCost::default(),
         current_contract: if internal {
             Some(ContractId::default())
         } else {
             None
         },
+        cgas: RegMut::new(&mut cgas),
+        ggas: RegMut::new(&mut ggas),
         fp: Reg::new(&fp),
+        is: Reg::new(&is),
         pc: RegMut::new(&mut pc),
         recipient_mem_address,
         msg_data_len,
@@ -97,7 +97,10 @@ fn test_state_read_word(
             memory: &mut memory,
             context: &context,
             profiler: &mut Profiler::default(),
-            new_storage_gas_per_byte: 1,
+            new_storage_cost: NewStorageCost {
+                per_slot: 0,
+                per_byte: 1,
+            },
             current_contract: None,
             cgas: RegMut::new(&mut cgas),
             ggas: RegMut::new(&mut ggas),
@@ -167,7 +170,10 @@ fn test_state_write_word(
             memory: &mut memory,
             context: &context,
             profiler: &mut Profiler::default(),
-            new_storage_gas_per_byte: 1,
+            new_storage_cost: NewStorageCost {
+                per_slot: 0,
+                per_byte: 1,
+            },
             current_contract: None,
             cgas: RegMut::new(&mut cgas),
             ggas: RegMut::new(&mut ggas),
@@ -183,7 +189,10 @@ fn test_state_write_word(
         storage: &mut storage,
         memory: &mut memory,
         context: &context,
-        new_storage_gas_per_byte: 1,
+        new_storage_cost: NewStorageCost {
+            per_slot: 0,
+            per_byte: 1,
+        },
         current_contract: None,
         profiler: &mut Profiler::default(),
         cgas: RegMut::new(&mut cgas),
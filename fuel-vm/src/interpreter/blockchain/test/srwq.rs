@@ -1,11 +1,18 @@
 use crate::storage::{
     ContractsState,
     ContractsStateData,
+    ContractsStateKey,
     MemoryStorage,
 };
+use alloc::borrow::Cow;
 
 use super::*;
-use fuel_storage::StorageAsMut;
+use core::cell::Cell;
+use fuel_storage::{
+    BatchValues,
+    StorageAsMut,
+    StorageInspect,
+};
 use test_case::test_case;
 
 const DEFAULT_OWNER: OwnershipRegisters = OwnershipRegisters {
@@ -193,3 +200,99 @@ fn test_state_read_qword(
     )?;
     Ok((memory, result_register != 0))
 }
+
+/// Counts the batch reads of the contract state.
+struct BatchCountingStorage {
+    storage: MemoryStorage,
+    batches: Cell<usize>,
+}
+
+impl StorageInspect<ContractsState> for BatchCountingStorage {
+    type Error = Infallible;
+
+    fn get(
+        &self,
+        key: &ContractsStateKey,
+    ) -> Result<Option<Cow<ContractsStateData>>, Infallible> {
+        StorageInspect::<ContractsState>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &ContractsStateKey) -> Result<bool, Infallible> {
+        StorageInspect::<ContractsState>::contains_key(&self.storage, key)
+    }
+}
+
+impl StorageBatchInspect<ContractsState> for BatchCountingStorage {
+    fn get_batch<'a, 'k, Iter>(
+        &'a self,
+        keys: Iter,
+    ) -> BatchValues<'a, ContractsState, Infallible>
+    where
+        Iter: IntoIterator<Item = &'k ContractsStateKey>,
+    {
+        self.batches.set(self.batches.get() + 1);
+        StorageBatchInspect::<ContractsState>::get_batch(&self.storage, keys)
+    }
+}
+
+#[test_case(1 => true; "All slots set")]
+#[test_case(3 => false; "Every third slot unset")]
+fn test_state_read_qword_in_one_batch(unset_every: u8) -> bool {
+    // Given
+    const SLOTS: u8 = 64;
+    let mut storage = MemoryStorage::default();
+    for i in (0..SLOTS).filter(|i| unset_every == 1 || i % unset_every != 0) {
+        storage
+            .storage::<ContractsState>()
+            .insert(
+                &(&ContractId::default(), &Bytes32::new(key(i))).into(),
+                &[i; 32],
+            )
+            .unwrap();
+    }
+    let expected_slots: Vec<u8> = (0..SLOTS)
+        .flat_map(|i| {
+            storage
+                .contract_state(&ContractId::default(), &Bytes32::new(key(i)))
+                .as_ref()
+                .as_ref()
+                .to_vec()
+        })
+        .collect();
+    let expected_all_set = (0..SLOTS).all(|i| {
+        StorageInspect::<ContractsState>::contains_key(
+            &storage,
+            &(&ContractId::default(), &Bytes32::new(key(i))).into(),
+        )
+        .unwrap()
+    });
+    let storage = BatchCountingStorage {
+        storage,
+        batches: Cell::new(0),
+    };
+    let mut memory = mem(&[&key(0)]);
+    let mut result_register = 0u64;
+    let mut pc = 0;
+
+    // When
+    state_read_qword(
+        &Default::default(),
+        &storage,
+        &mut memory,
+        RegMut::new(&mut pc),
+        DEFAULT_OWNER,
+        &mut result_register,
+        StateReadQWordParams {
+            destination_pointer: 32,
+            origin_key_pointer: 0,
+            num_slots: SLOTS as Word,
+        },
+    )
+    .unwrap();
+
+    // Then
+    assert_eq!(storage.batches.get(), 1);
+    assert_eq!(&memory[32..32 + 32 * SLOTS as usize], &expected_slots[..]);
+    assert_eq!(result_register != 0, expected_all_set);
+    expected_all_set
+}
@@ -243,7 +243,10 @@ fn test_state_write_qword(
         &mut storage,
         &memory,
         &mut Profiler::default(),
-        1,
+        NewStorageCost {
+            per_slot: 0,
+            per_byte: 1,
+        },
         None,
         RegMut::new(&mut cgas),
         RegMut::new(&mut ggas),
@@ -0,0 +1,50 @@
+//! The `tracing` spans of the call frames of a script.
+
+use alloc::vec::Vec;
+
+use fuel_asm::RegId;
+use tracing::Span;
+
+use super::Interpreter;
+
+/// The spans of the call frames that didn't return, the innermost last.
+///
+/// A call frame outlives the instruction opening it, so its span is kept open here
+/// instead of being entered, and is the explicit parent of the spans and the events
+/// of the frame.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CallSpans(Vec<Span>);
+
+impl CallSpans {
+    /// Closes the spans of the frames of the previous transaction.
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// The span of the innermost frame, or the current span outside of the calls.
+    pub(crate) fn innermost(&self) -> Span {
+        self.0.last().cloned().unwrap_or_else(Span::current)
+    }
+}
+
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
+    /// Closes the spans of the returned frames, and opens the span of the frame
+    /// entered by the last instruction.
+    pub(crate) fn follow_call_spans(&mut self) {
+        let depth = self.frames.len();
+        self.call_spans.0.truncate(depth);
+        if self.call_spans.0.len() == depth {
+            return
+        }
+        if let Some(frame) = self.frames.last() {
+            let span = tracing::debug_span!(
+                parent: &self.call_spans.innermost(),
+                "call",
+                contract_id = %frame.to(),
+                gas = self.registers[RegId::CGAS],
+                depth,
+            );
+            self.call_spans.0.push(span);
+        }
+    }
+}
@@ -10,30 +10,36 @@ use super::{
 use crate::{
     consts::*,
     context::Context,
+    coverage::Coverage,
     interpreter::{
+        EcalHandler,
         InterpreterParams,
         PanicContext,
     },
+    observer::ExecutionObserver,
+    receipt_sink::ReceiptSink,
     state::Debugger,
 };
 
-use alloc::vec;
+use alloc::{
+    sync::Arc,
+    vec,
+};
 
+#[cfg(feature = "memory-heat-map")]
+use crate::memory_heat_map::MemoryHeatMap;
 #[cfg(feature = "profile-any")]
 use crate::profiler::ProfileReceiver;
 
 use crate::profiler::Profiler;
 
 #[cfg(feature = "test-helpers")]
-use crate::{
-    interpreter::EcalHandler,
-    storage::MemoryStorage,
-};
+use crate::storage::MemoryStorage;
 
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
     Tx: Default,
-    Ecal: Default,
+    Ecal: EcalHandler + Default,
 {
     /// Create a new interpreter instance out of a storage implementation.
     ///
@@ -48,17 +54,28 @@ where
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
     Tx: Default,
+    Ecal: EcalHandler,
 {
     /// Create a new interpreter instance out of a storage implementation.
     ///
     /// If the provided storage implements
     /// [`crate::storage::InterpreterStorage`], the returned interpreter
     /// will provide full functionality.
+    ///
+    /// The [`EcalHandler::BASE_GAS_COST`] of the handler, if any, is registered as the
+    /// cost of `ECAL` in the gas costs of the `interpreter_params` that don't configure
+    /// one, a zero cost. A cost configured by the consensus parameters is kept.
     pub fn with_storage_and_ecal(
         storage: S,
-        interpreter_params: InterpreterParams,
+        mut interpreter_params: InterpreterParams,
         ecal_state: Ecal,
     ) -> Self {
+        if let Some(cost) = Ecal::BASE_GAS_COST {
+            if interpreter_params.gas_costs.ecal() == 0 {
+                interpreter_params.gas_costs =
+                    interpreter_params.gas_costs.with_ecal(cost);
+            }
+        }
         Self {
             registers: [0; VM_REGISTER_COUNT],
             memory: Memory::new(),
@@ -71,6 +88,11 @@ where
             context: Context::default(),
             balances: RuntimeBalances::default(),
             profiler: Profiler::default(),
+            coverage: None,
+            observer: None,
+            frame_gas: Default::default(),
+            #[cfg(feature = "tracing")]
+            call_spans: Default::default(),
             interpreter_params,
             panic_context: PanicContext::None,
             ecal_state,
@@ -88,6 +110,39 @@ impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
         self.profiler.set_receiver(alloc::boxed::Box::new(receiver));
         self
     }
+
+    /// Collects the coverage of the executed instructions in `coverage`, merged with
+    /// the instructions it already covers.
+    pub fn with_coverage(&mut self, coverage: Coverage) -> &mut Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    /// Records the memory accesses in the `heat_map`, merged with the accesses it
+    /// already counts.
+    ///
+    /// The heat map is shared with the clones of the interpreter.
+    #[cfg(feature = "memory-heat-map")]
+    pub fn with_memory_heat_map(&mut self, heat_map: MemoryHeatMap) -> &mut Self {
+        self.memory.set_heat_map(heat_map);
+        self
+    }
+
+    /// Notifies the `observer` after each executed instruction.
+    ///
+    /// The observer is shared with the clones of the interpreter.
+    pub fn with_observer(&mut self, observer: Arc<dyn ExecutionObserver>) -> &mut Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Notifies the `sink` of each receipt as soon as it is pushed.
+    ///
+    /// The sink is shared with the clones of the interpreter.
+    pub fn with_receipt_sink(&mut self, sink: Arc<dyn ReceiptSink>) -> &mut Self {
+        self.receipts.set_sink(sink);
+        self
+    }
 }
 
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
@@ -26,6 +26,7 @@ use crate::{
         RuntimeError,
     },
     interpreter::{
+        memory::ToAddr,
         receipts::ReceiptsCtx,
         InputContracts,
         PanicContext,
@@ -42,9 +43,12 @@ use fuel_asm::{
     RegisterId,
     Word,
 };
-use fuel_storage::StorageSize;
+use fuel_storage::{
+    StorageRead,
+    StorageSize,
+};
 use fuel_tx::{
-    Contract,
+    NewStorageCost,
     Output,
     Receipt,
 };
@@ -55,8 +59,6 @@ use fuel_types::{
     ContractId,
 };
 
-use alloc::borrow::Cow;
-
 #[cfg(test)]
 mod tests;
 
@@ -92,7 +94,11 @@ where
         b: Word,
         c: Word,
     ) -> IoResult<(), S::DataError> {
-        let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
+        let destination = ContractId::from(self.memory.read_bytes(a)?);
+        self.ensure_contract_writable(&destination)?;
+        self.ensure_current_contract_writable()?;
+
+        let new_storage_cost = self.gas_costs().new_storage();
         let tx_offset = self.tx_offset();
         let (
             SystemRegisters {
@@ -112,7 +118,7 @@ where
             balances: &mut self.balances,
             receipts: &mut self.receipts,
             profiler: &mut self.profiler,
-            new_storage_gas_per_byte,
+            new_storage_cost,
             tx: &mut self.tx,
             tx_offset,
             cgas,
@@ -131,8 +137,10 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
+        self.ensure_current_contract_writable()?;
+
         let tx_offset = self.tx_offset();
-        let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
+        let new_storage_cost = self.gas_costs().new_storage();
         let (
             SystemRegisters {
                 cgas,
@@ -151,7 +159,7 @@ where
             balances: &mut self.balances,
             receipts: &mut self.receipts,
             profiler: &mut self.profiler,
-            new_storage_gas_per_byte,
+            new_storage_cost,
             tx: &mut self.tx,
             tx_offset,
             cgas,
@@ -173,19 +181,6 @@ where
     }
 }
 
-pub(crate) fn contract<'s, S>(
-    storage: &'s S,
-    contract: &ContractId,
-) -> IoResult<Cow<'s, Contract>, S::DataError>
-where
-    S: InterpreterStorage,
-{
-    storage
-        .storage_contract(contract)
-        .map_err(RuntimeError::Storage)?
-        .ok_or_else(|| PanicReason::ContractNotFound.into())
-}
-
 struct ContractBalanceCtx<'vm, S, I> {
     storage: &'vm S,
     memory: &'vm mut Memory,
@@ -223,7 +218,7 @@ struct TransferCtx<'vm, S, Tx> {
     balances: &'vm mut RuntimeBalances,
     receipts: &'vm mut ReceiptsCtx,
     profiler: &'vm mut Profiler,
-    new_storage_gas_per_byte: Word,
+    new_storage_cost: NewStorageCost,
     tx: &'vm mut Tx,
     tx_offset: usize,
     cgas: RegMut<'vm, CGAS>,
@@ -294,8 +289,8 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
                 self.cgas,
                 self.ggas,
                 profiler,
-                // Overflow safety: unset_count * 32 can be at most VM_MAX_RAM
-                ((Bytes32::LEN + WORD_SIZE) as u64) * self.new_storage_gas_per_byte,
+                self.new_storage_cost
+                    .resolve(1, (Bytes32::LEN + WORD_SIZE) as Word),
             )?;
         }
 
@@ -391,6 +386,34 @@ where
         .ok_or(PanicReason::ContractNotFound)?)
 }
 
+/// Copies `len` bytes of the code of the `contract` from the `offset` into the memory at
+/// `dst_addr`, filling the bytes past the end of the code with zeros. Only the copied
+/// bytes are read from the storage.
+/// Performs overflow and memory range checks, but no ownership checks.
+pub(crate) fn copy_contract_code_zero_fill_noownerchecks<S, A, B>(
+    storage: &S,
+    memory: &mut Memory,
+    contract: &ContractId,
+    dst_addr: A,
+    offset: usize,
+    len: B,
+) -> IoResult<(), S::Error>
+where
+    S: StorageRead<ContractsRawCode> + ?Sized,
+    A: ToAddr,
+    B: ToAddr,
+{
+    let dst = memory.write_noownerchecks(dst_addr, len)?;
+    let read =
+        StorageRead::<ContractsRawCode>::read_range(storage, contract, offset, dst)
+            .map_err(RuntimeError::Storage)?
+            .ok_or(PanicReason::ContractNotFound)?;
+    if let Some(zero) = dst.get_mut(read..) {
+        zero.fill(0);
+    }
+    Ok(())
+}
+
 pub(crate) fn balance<S>(
     storage: &S,
     contract: &ContractId,
@@ -142,7 +142,10 @@ fn test_transfer(
         balances: &mut balances,
         receipts: &mut receipts,
         profiler: &mut Default::default(),
-        new_storage_gas_per_byte: 1,
+        new_storage_cost: NewStorageCost {
+            per_slot: 0,
+            per_byte: 1,
+        },
         tx: &mut tx,
         tx_offset: 0,
         cgas: RegMut::new(&mut cgas),
@@ -296,7 +299,10 @@ fn test_transfer_output(
         balances: &mut balances,
         receipts: &mut receipts,
         profiler: &mut Default::default(),
-        new_storage_gas_per_byte: 1,
+        new_storage_cost: NewStorageCost {
+            per_slot: 0,
+            per_byte: 1,
+        },
         tx: &mut tx,
         tx_offset,
         cgas: RegMut::new(&mut cgas),
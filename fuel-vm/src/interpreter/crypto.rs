@@ -19,6 +19,7 @@ use fuel_crypto::{
     Message,
     PublicKey,
     Signature,
+    SignatureMode,
 };
 use fuel_types::{
     Bytes32,
@@ -91,7 +92,8 @@ pub(crate) fn secp256k1_recover(
     let signature = Signature::from_bytes_ref(&sig);
     let message = Message::from_bytes_ref(&msg);
 
-    match signature.recover(message) {
+    // The high `s` form of the signature is accepted
+    match signature.recover_with_mode(message, SignatureMode::Lax) {
         Ok(pub_key) => {
             memory.write_bytes(owner, a, *pub_key)?;
             clear_err(err);
@@ -118,7 +120,8 @@ pub(crate) fn secp256r1_recover(
     let msg = Bytes32::from(memory.read_bytes(c)?);
     let message = Message::from_bytes_ref(&msg);
 
-    match fuel_crypto::secp256r1::recover(&sig, message) {
+    // The high `s` form of the signature is accepted
+    match fuel_crypto::secp256r1::recover_with_mode(&sig, message, SignatureMode::Lax) {
         Ok(pub_key) => {
             memory.write_bytes(owner, a, *pub_key)?;
             clear_err(err);
@@ -1,7 +1,7 @@
 #![allow(clippy::cast_possible_truncation)]
 use alloc::vec;
 
-use fuel_crypto::SecretKey;
+use fuel_crypto::test_vectors;
 use rand::{
     rngs::StdRng,
     SeedableRng,
@@ -34,7 +34,7 @@ fn test_recover_secp256k1() -> SimpleResult<()> {
     let sig_address = 0;
     let msg_address = 64;
 
-    let secret = SecretKey::try_from(&[2u8; 32][..]).unwrap();
+    let secret = test_vectors::secret_key(0);
     let public_key = PublicKey::from(&secret);
     let message = Message::new([3u8; 100]);
     let signature = Signature::sign(&secret, &message);
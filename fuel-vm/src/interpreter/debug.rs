@@ -22,6 +22,12 @@ where
         self.debugger.set_single_stepping(single_stepping)
     }
 
+    /// Set the command of the next resumption of the execution, from the current
+    /// call depth.
+    pub fn set_debug_command(&mut self, command: DebugCommand) {
+        self.debugger.set_command(command, self.frames.len())
+    }
+
     /// Clear all set breakpoints.
     pub fn clear_breakpoints(&mut self) {
         self.debugger.clear_breakpoints();
@@ -45,13 +51,138 @@ where
         self.debugger.remove_breakpoint(breakpoint)
     }
 
+    /// Set a new breakpoint for the provided location, breaking only when the
+    /// condition holds.
+    pub fn set_conditional_breakpoint(
+        &mut self,
+        breakpoint: Breakpoint,
+        condition: BreakpointCondition,
+    ) {
+        self.debugger
+            .set_conditional_breakpoint(breakpoint, condition)
+    }
+
+    /// Attach a condition to a previously set breakpoint. Return `false` if the
+    /// breakpoint isn't set.
+    pub fn attach_breakpoint_condition(
+        &mut self,
+        breakpoint: &Breakpoint,
+        condition: BreakpointCondition,
+    ) -> bool {
+        self.debugger.attach_condition(breakpoint, condition)
+    }
+
+    /// Detach the condition of a breakpoint, returning it.
+    pub fn detach_breakpoint_condition(
+        &mut self,
+        breakpoint: &Breakpoint,
+    ) -> Option<BreakpointCondition> {
+        self.debugger.detach_condition(breakpoint)
+    }
+
+    /// Number of times a breakpoint broke the execution; return `None` if the
+    /// breakpoint isn't set.
+    pub fn breakpoint_hits(&self, breakpoint: &Breakpoint) -> Option<u64> {
+        self.debugger.breakpoint_hits(breakpoint)
+    }
+
+    /// Add an expression evaluated at each pause of the execution, returning its
+    /// index in the [`Self::watch_values`].
+    pub fn add_watch(&mut self, expression: WatchExpression) -> usize {
+        self.debugger.add_watch(expression)
+    }
+
+    /// Remove the watched expression at the `index`, returning it.
+    pub fn remove_watch(&mut self, index: usize) -> Option<WatchExpression> {
+        self.debugger.remove_watch(index)
+    }
+
+    /// Remove all the watched expressions.
+    pub fn clear_watches(&mut self) {
+        self.debugger.clear_watches()
+    }
+
+    /// The values of the watched expressions at the last pause of the execution.
+    pub fn watch_values(&self) -> &[WatchValue] {
+        self.debugger.watch_values()
+    }
+
+    /// Set the `register` of the execution paused at a debug event. The reserved
+    /// registers, below [`RegId::WRITABLE`], are set only if the edit is `forced`.
+    ///
+    /// The edit is recorded in the [`Self::debug_edits`].
+    pub fn set_register(
+        &mut self,
+        register: RegId,
+        value: Word,
+        forced: bool,
+    ) -> Result<(), DebugEditError> {
+        if !self.debugger.is_suspended() {
+            return Err(DebugEditError::NotSuspended)
+        }
+        if register < RegId::WRITABLE && !forced {
+            return Err(DebugEditError::ReservedRegister(register))
+        }
+
+        self.registers[register] = value;
+        self.debugger.record_edit(DebugEdit::Register {
+            register,
+            value,
+            forced,
+        });
+        Ok(())
+    }
+
+    /// Overwrite the memory from the `address` with the `bytes` in the execution
+    /// paused at a debug event. The memory must be owned by the current context, like
+    /// for the VM, unless the edit is `unchecked`.
+    ///
+    /// The edit is recorded in the [`Self::debug_edits`].
+    pub fn write_memory(
+        &mut self,
+        address: Word,
+        bytes: &[u8],
+        unchecked: bool,
+    ) -> Result<(), DebugEditError> {
+        if !self.debugger.is_suspended() {
+            return Err(DebugEditError::NotSuspended)
+        }
+
+        let destination = if unchecked {
+            self.memory.write_noownerchecks(address, bytes.len())
+        } else {
+            let owner = self.ownership_registers();
+            self.memory.write(owner, address, bytes.len())
+        };
+        destination
+            .map_err(DebugEditError::Memory)?
+            .copy_from_slice(bytes);
+        self.debugger.record_edit(DebugEdit::Memory {
+            address,
+            bytes: bytes.to_vec(),
+            unchecked,
+        });
+        Ok(())
+    }
+
+    /// The edits of the state of the paused execution, in their order.
+    pub fn debug_edits(&self) -> &[DebugEdit] {
+        self.debugger.edits()
+    }
+
     pub(crate) fn eval_debugger_state(&mut self) -> DebugEval {
         let debugger = &mut self.debugger;
 
         let contract = self.frames.last().map(CallFrame::to);
         let pc = self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]);
 
-        debugger.eval_state(contract, pc)
+        debugger.eval_state(
+            contract,
+            pc,
+            self.frames.len(),
+            self.registers.as_ref(),
+            &self.memory,
+        )
     }
 
     pub(crate) fn debugger_set_last_state(&mut self, state: ProgramState) {
@@ -200,3 +331,205 @@ fn single_stepping() {
 
     assert_eq!(stops, vec![0, 4, 8, 4, 8, 4, 8, 4, 8, 4, 8, 12]);
 }
+
+/// Executes a script counting in `0x20` and storing the counter at `$hp` through 100
+/// iterations, and returns the indices of the counter when the breakpoints broke.
+#[cfg(test)]
+fn conditional_breakpoint_stops(
+    breakpoint: Breakpoint,
+    condition: BreakpointCondition,
+) -> (Vec<Word>, Option<u64>) {
+    use fuel_asm::op;
+    use fuel_tx::ConsensusParameters;
+
+    let mut vm = Interpreter::<_, _>::with_memory_storage();
+
+    let gas_limit = 1_000_000;
+    let height = Default::default();
+    let gas_price = 0;
+
+    let script = [
+        op::movi(0x20, 0),
+        op::movi(0x21, 100),
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::addi(0x20, 0x20, 1),
+        op::sw(RegId::HP, 0x20, 0),
+        op::jnei(0x20, 0x21, 4),
+        op::ret(0x20),
+    ]
+    .into_iter()
+    .collect();
+
+    let consensus_params = ConsensusParameters::standard();
+
+    let tx = TransactionBuilder::script(script, vec![])
+        .script_gas_limit(gas_limit)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(height, &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            gas_price,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+
+    vm.set_conditional_breakpoint(breakpoint, condition);
+
+    let mut state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+
+    let mut stops = Vec::new();
+
+    while let Some(debug) = state.debug_ref() {
+        let b = debug
+            .breakpoint()
+            .expect("State without expected breakpoint");
+
+        assert_eq!(&breakpoint, b);
+        stops.push(vm.registers()[0x20]);
+
+        state = vm.resume().expect("Failed to resume");
+    }
+
+    assert_eq!(state, ProgramState::Return(100));
+
+    (stops, vm.breakpoint_hits(&breakpoint))
+}
+
+#[test]
+fn conditional_breakpoint_register() {
+    let condition = BreakpointCondition::register(RegId::new(0x20), Comparison::Eq, 5);
+
+    let (stops, hits) = conditional_breakpoint_stops(Breakpoint::script(5), condition);
+
+    assert_eq!(stops, vec![5]);
+    assert_eq!(hits, Some(1));
+}
+
+#[test]
+fn conditional_breakpoint_memory_and_combinators() {
+    let condition = BreakpointCondition::memory(RegId::HP, 0, Comparison::Eq, 41).or(
+        !BreakpointCondition::register(RegId::new(0x20), Comparison::Lt, 98),
+    );
+
+    let (stops, hits) = conditional_breakpoint_stops(Breakpoint::script(4), condition);
+
+    assert_eq!(stops, vec![41, 98, 99]);
+    assert_eq!(hits, Some(3));
+}
+
+#[test]
+fn conditional_breakpoint_out_of_memory_never_holds() {
+    let condition = BreakpointCondition::memory(RegId::HP, Word::MAX, Comparison::Ge, 0);
+
+    let (stops, hits) = conditional_breakpoint_stops(Breakpoint::script(4), condition);
+
+    assert!(stops.is_empty());
+    assert_eq!(hits, Some(0));
+}
+
+#[test]
+fn breakpoint_conditions_attach_and_detach() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    let breakpoint = Breakpoint::script(4);
+    let condition = BreakpointCondition::register(RegId::new(0x20), Comparison::Eq, 5);
+
+    assert!(!vm.attach_breakpoint_condition(&breakpoint, condition.clone()));
+    assert_eq!(vm.breakpoint_hits(&breakpoint), None);
+
+    vm.set_breakpoint(breakpoint);
+    assert!(vm.attach_breakpoint_condition(&breakpoint, condition.clone()));
+    assert_eq!(vm.detach_breakpoint_condition(&breakpoint), Some(condition));
+    assert_eq!(vm.detach_breakpoint_condition(&breakpoint), None);
+    assert_eq!(vm.breakpoint_hits(&breakpoint), Some(0));
+}
+
+#[test]
+fn watches_are_evaluated_at_each_breakpoint() {
+    use fuel_asm::op;
+    use fuel_tx::ConsensusParameters;
+
+    let mut vm = Interpreter::<_, _>::with_memory_storage();
+
+    let gas_limit = 1_000_000;
+    let height = Default::default();
+    let gas_price = 0;
+
+    let script = [
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::movi(0x20, 3),
+        op::addi(0x20, 0x20, 4),
+        op::sw(RegId::HP, 0x20, 0),
+        op::ret(0x20),
+    ]
+    .into_iter()
+    .collect();
+
+    let consensus_params = ConsensusParameters::standard();
+
+    let tx = TransactionBuilder::script(script, vec![])
+        .script_gas_limit(gas_limit)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(height, &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            gas_price,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+
+    let sum = WatchExpression::register(RegId::new(0x20)) + 8.into();
+    let word_at_hp = WatchExpression::register(RegId::HP).word_at();
+    assert_eq!(vm.add_watch(sum), 0);
+    assert_eq!(vm.add_watch(word_at_hp), 1);
+    vm.set_breakpoint(Breakpoint::script(3));
+    vm.set_breakpoint(Breakpoint::script(5));
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+    assert_eq!(state, Breakpoint::script(3));
+    let hp = vm.registers()[RegId::HP];
+    let word = vm.memory().read_bytes(hp).map(Word::from_be_bytes).unwrap();
+    assert_eq!(vm.watch_values(), &[Ok(vm.registers()[0x20] + 8), Ok(word)]);
+    assert_eq!(vm.watch_values(), &[Ok(11), Ok(0)]);
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, Breakpoint::script(5));
+    assert_eq!(vm.watch_values(), &[Ok(15), Ok(7)]);
+}
+
+#[test]
+fn watches_report_invalid_memory_and_overflow() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+
+    let registers = [0; crate::consts::VM_REGISTER_COUNT];
+    let out_of_memory = WatchExpression::from(Word::MAX).word_at();
+    let overflow = WatchExpression::from(Word::MAX) * 2.into();
+    let comparison = WatchExpression::register(RegId::ZERO).compare(
+        Comparison::Lt,
+        WatchExpression::register(RegId::ONE) - 0.into(),
+    );
+
+    assert_eq!(
+        out_of_memory.eval(&registers, vm.memory()),
+        Err(WatchError::InvalidMemory(Word::MAX))
+    );
+    assert_eq!(
+        overflow.eval(&registers, vm.memory()),
+        Err(WatchError::Overflow)
+    );
+    assert_eq!(comparison.eval(&registers, vm.memory()), Ok(0));
+    assert_eq!(vm.add_watch(comparison), 0);
+    assert!(vm.remove_watch(0).is_some());
+    assert_eq!(vm.remove_watch(0), None);
+}
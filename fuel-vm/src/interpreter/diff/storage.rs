@@ -2,6 +2,8 @@ use core::fmt::Debug;
 use hashbrown::HashMap;
 
 use fuel_storage::{
+    BatchValues,
+    StorageBatchInspect,
     StorageRead,
     StorageSize,
     StorageWrite,
@@ -101,6 +103,11 @@ where
             balances: self.balances,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            coverage: self.coverage,
+            observer: self.observer,
+            frame_gas: self.frame_gas,
+            #[cfg(feature = "tracing")]
+            call_spans: self.call_spans,
             interpreter_params: self.interpreter_params,
             ecal_state: self.ecal_state,
         }
@@ -179,6 +186,11 @@ where
             balances: self.balances,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            coverage: self.coverage,
+            observer: self.observer,
+            frame_gas: self.frame_gas,
+            #[cfg(feature = "tracing")]
+            call_spans: self.call_spans,
             interpreter_params: self.interpreter_params,
             ecal_state: self.ecal_state,
         }
@@ -318,6 +330,20 @@ where
     }
 }
 
+impl<Type: Mappable, S> StorageBatchInspect<Type> for Record<S>
+where
+    S: StorageBatchInspect<Type>,
+    S: InterpreterStorage,
+{
+    fn get_batch<'a, 'k, Iter>(&'a self, keys: Iter) -> BatchValues<'a, Type, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k Type::Key>,
+        Type::Key: 'k,
+    {
+        <S as StorageBatchInspect<Type>>::get_batch(&self.0, keys)
+    }
+}
+
 impl<Type: Mappable, S> StorageSize<Type> for Record<S>
 where
     S: StorageSize<Type>,
@@ -350,6 +376,15 @@ where
     ) -> Result<Option<Vec<u8>>, Self::Error> {
         <S as StorageRead<Type>>::read_alloc(&self.0, key)
     }
+
+    fn read_range(
+        &self,
+        key: &<Type as Mappable>::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        <S as StorageRead<Type>>::read_range(&self.0, key, offset, buf)
+    }
 }
 
 impl<Type: StorageType, S> StorageMutate<Type> for Record<S>
@@ -405,6 +440,15 @@ where
     fn take(&mut self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
         <S as StorageWrite<Type>>::take(&mut self.0, key)
     }
+
+    fn write_range(
+        &mut self,
+        key: &Type::Key,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, Self::Error> {
+        <S as StorageWrite<Type>>::write_range(&mut self.0, key, offset, buf)
+    }
 }
 
 impl<S: ContractsAssetsStorage + InterpreterStorage> ContractsAssetsStorage
@@ -4,6 +4,8 @@ use fuel_asm::{
     PanicReason,
     RegId,
 };
+use fuel_tx::DependentCost;
+use fuel_types::Word;
 
 use crate::{
     constraints::reg_key::{
@@ -28,7 +30,14 @@ where
     /// the handler must increment PC itself.
     const INC_PC: bool = true;
 
-    /// ECAL opcode handler
+    /// The base gas cost of ECAL, charged before calling the handler. If set, it is
+    /// registered as the cost of `ECAL` in the gas costs of the interpreters created
+    /// with the handler that don't configure one, otherwise the configured cost is
+    /// kept.
+    const BASE_GAS_COST: Option<Word> = None;
+
+    /// ECAL opcode handler, charging the gas of its work with
+    /// [`Interpreter::gas_charger`].
     fn ecal<S, Tx>(
         vm: &mut Interpreter<S, Tx, Self>,
         a: RegId,
@@ -89,6 +98,11 @@ where
         }
     }
 
+    /// The handle charging the gas of an ECAL handler, see [`GasCharger`].
+    pub fn gas_charger(&mut self) -> GasCharger<'_, S, Tx, Ecal> {
+        GasCharger { vm: self }
+    }
+
     /// Read access to the ECAL state
     pub fn ecal_state(&self) -> &Ecal {
         &self.ecal_state
@@ -99,3 +113,32 @@ where
         &mut self.ecal_state
     }
 }
+
+/// Charges the gas of an ECAL handler through the same code path as the opcodes: running
+/// out of gas panics with [`PanicReason::OutOfGas`], reported by the panic receipt of
+/// the ECAL, and the charged gas is profiled like the gas of the opcodes.
+pub struct GasCharger<'vm, S, Tx, Ecal> {
+    vm: &'vm mut Interpreter<S, Tx, Ecal>,
+}
+
+impl<S, Tx, Ecal> GasCharger<'_, S, Tx, Ecal> {
+    /// Charges `gas`.
+    pub fn charge(&mut self, gas: Word) -> SimpleResult<()> {
+        self.vm.gas_charge(gas)
+    }
+
+    /// Charges the `cost` of `units` units, resolved like the dependent costs of the
+    /// opcodes.
+    pub fn charge_dependent(
+        &mut self,
+        cost: DependentCost,
+        units: Word,
+    ) -> SimpleResult<()> {
+        self.charge(cost.resolve(units))
+    }
+
+    /// The gas remaining in the current context, `$cgas`.
+    pub fn remaining(&self) -> Word {
+        self.vm.registers()[RegId::CGAS]
+    }
+}
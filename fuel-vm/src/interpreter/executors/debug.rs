@@ -5,7 +5,10 @@ use crate::{
         ExecutableTransaction,
         Interpreter,
     },
-    state::ProgramState,
+    state::{
+        DebugCommand,
+        ProgramState,
+    },
     storage::InterpreterStorage,
 };
 
@@ -39,4 +42,14 @@ where
 
         Ok(state)
     }
+
+    /// Continue the execution from a previously interrupted program flow with the
+    /// `command`.
+    pub fn resume_with(
+        &mut self,
+        command: DebugCommand,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        self.set_debug_command(command);
+        self.resume()
+    }
 }
@@ -1,5 +1,9 @@
 use crate::{
-    constraints::reg_key::ProgramRegistersSegment,
+    constraints::{
+        reg_key::ProgramRegistersSegment,
+        InstructionLocation,
+    },
+    coverage::CodeId,
     error::{
         InterpreterError,
         IoResult,
@@ -15,6 +19,7 @@ use crate::{
         ExecutableTransaction,
         Interpreter,
     },
+    observer::ExecutedStep,
     state::ExecuteState,
     storage::InterpreterStorage,
 };
@@ -22,14 +27,20 @@ use crate::{
 use fuel_asm::{
     wideint,
     Instruction,
+    Opcode,
     PanicInstruction,
     PanicReason,
     RawInstruction,
     RegId,
 };
+use fuel_crypto::Hasher;
+use fuel_tx::field::Script as _;
 use fuel_types::Word;
 
-use core::ops::Div;
+use core::{
+    cmp::Ordering,
+    ops::Div,
+};
 
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
@@ -78,8 +89,97 @@ where
             }
         }
 
-        self.instruction_inner(raw.into())
-            .map_err(|e| InterpreterError::from_runtime(e, raw.into()))
+        #[cfg(feature = "profile-gas")]
+        {
+            let location = crate::profiler::InstructionLocation::new(
+                self.contract_id(),
+                self.registers[RegId::PC] - self.registers[RegId::IS],
+            );
+            self.profiler.on_instruction(location);
+            if let Ok(instruction) = Instruction::try_from(raw.into()) {
+                self.profiler.on_opcode(instruction.opcode());
+            }
+        }
+
+        if self.coverage.is_some() {
+            self.record_coverage();
+        }
+
+        let observed = self.observer.is_some().then(|| {
+            let location = InstructionLocation::new(
+                self.contract_id(),
+                self.registers[RegId::PC] - self.registers[RegId::IS],
+            );
+            (location, self.registers)
+        });
+
+        #[cfg(feature = "trace-instructions")]
+        tracing::trace!(
+            parent: &self.call_spans.innermost(),
+            pc = self.registers[RegId::PC],
+            instruction = ?Instruction::try_from(raw.into()),
+        );
+
+        let depth = self.frames.len();
+        let result = self
+            .instruction_inner(raw.into())
+            .map_err(|e| InterpreterError::from_runtime(e, raw.into()));
+
+        match self.frames.len().cmp(&depth) {
+            Ordering::Greater => {
+                let gas = self.remaining_gas();
+                self.frame_gas
+                    .enter(self.contract_id(), self.frames.len(), gas);
+            }
+            Ordering::Less => self.frame_gas.leave(self.remaining_gas()),
+            Ordering::Equal => (),
+        }
+
+        let result = match result {
+            Ok(ExecuteState::Proceed) => self
+                .enforce_gas_allowance(raw.into())
+                .map(|()| ExecuteState::Proceed)
+                .map_err(|e| InterpreterError::from_runtime(e.into(), raw.into())),
+            result => result,
+        };
+
+        #[cfg(feature = "tracing")]
+        self.follow_call_spans();
+
+        if let (Some(observer), Some((location, before))) = (&self.observer, observed) {
+            observer.on_step(&ExecutedStep {
+                location,
+                instruction: raw.into(),
+                before: &before,
+                after: &self.registers,
+            });
+        }
+
+        result
+    }
+
+    /// Marks the current instruction in the coverage of the executing code.
+    fn record_coverage(&mut self) {
+        let contract = self.contract_id();
+        let Some(coverage) = self.coverage.as_mut() else {
+            return
+        };
+        if !coverage.is_active(contract) {
+            let (code, size) = match self.frames.last() {
+                Some(frame) => (CodeId::Contract(*frame.to()), frame.code_size()),
+                // The predicates are run by their own interpreters.
+                None if self.context.is_predicate() => return,
+                None => match self.tx.as_script() {
+                    Some(script) => (
+                        CodeId::Script(Hasher::hash(script.script())),
+                        script.script().len(),
+                    ),
+                    None => return,
+                },
+            };
+            coverage.activate(contract, code, size.div_ceil(Instruction::SIZE));
+        }
+        coverage.cover(self.registers[RegId::PC] - self.registers[RegId::IS]);
     }
 
     fn instruction_inner(
@@ -583,7 +683,7 @@ where
             Instruction::RETD(retd) => {
                 let (a, b) = retd.unpack();
                 let len = r!(b);
-                self.dependent_gas_charge(self.gas_costs().retd(), len)?;
+                self.dependent_gas_charge(Opcode::RETD, len)?;
                 return Ok(self.ret_data(r!(a), len).map(ExecuteState::ReturnData)?)
             }
 
@@ -597,7 +697,7 @@ where
 
             Instruction::SMO(smo) => {
                 let (a, b, c, d) = smo.unpack();
-                self.dependent_gas_charge(self.gas_costs().smo(), r!(c))?;
+                self.dependent_gas_charge(Opcode::SMO, r!(c))?;
                 self.message_output(r!(a), r!(b), r!(c), r!(d))?;
             }
 
@@ -670,35 +770,35 @@ where
             Instruction::MCL(mcl) => {
                 let (a, b) = mcl.unpack();
                 let len = r!(b);
-                self.dependent_gas_charge(self.gas_costs().mcl(), len)?;
+                self.dependent_gas_charge(Opcode::MCL, len)?;
                 self.memclear(r!(a), len)?;
             }
 
             Instruction::MCLI(mcli) => {
                 let (a, imm) = mcli.unpack();
                 let len = Word::from(imm);
-                self.dependent_gas_charge(self.gas_costs().mcli(), len)?;
+                self.dependent_gas_charge(Opcode::MCLI, len)?;
                 self.memclear(r!(a), len)?;
             }
 
             Instruction::MCP(mcp) => {
                 let (a, b, c) = mcp.unpack();
                 let len = r!(c);
-                self.dependent_gas_charge(self.gas_costs().mcp(), len)?;
+                self.dependent_gas_charge(Opcode::MCP, len)?;
                 self.memcopy(r!(a), r!(b), len)?;
             }
 
             Instruction::MCPI(mcpi) => {
                 let (a, b, imm) = mcpi.unpack();
                 let len = imm.into();
-                self.dependent_gas_charge(self.gas_costs().mcpi(), len)?;
+                self.dependent_gas_charge(Opcode::MCPI, len)?;
                 self.memcopy(r!(a), r!(b), len)?;
             }
 
             Instruction::MEQ(meq) => {
                 let (a, b, c, d) = meq.unpack();
                 let len = r!(d);
-                self.dependent_gas_charge(self.gas_costs().meq(), len)?;
+                self.dependent_gas_charge(Opcode::MEQ, len)?;
                 self.memeq(a.into(), r!(b), r!(c), len)?;
             }
 
@@ -782,7 +882,7 @@ where
 
             Instruction::LOGD(logd) => {
                 let (a, b, c, d) = logd.unpack();
-                self.dependent_gas_charge(self.gas_costs().logd(), r!(d))?;
+                self.dependent_gas_charge(Opcode::LOGD, r!(d))?;
                 self.log_data(r!(a), r!(b), r!(c), r!(d))?;
             }
 
@@ -794,7 +894,7 @@ where
 
             Instruction::SCWQ(scwq) => {
                 let (a, b, c) = scwq.unpack();
-                self.dependent_gas_charge(self.gas_costs().scwq(), r!(c))?;
+                self.dependent_gas_charge(Opcode::SCWQ, r!(c))?;
                 self.state_clear_qword(r!(a), b.into(), r!(c))?;
             }
 
@@ -806,7 +906,7 @@ where
 
             Instruction::SRWQ(srwq) => {
                 let (a, b, c, d) = srwq.unpack();
-                self.dependent_gas_charge(self.gas_costs().srwq(), r!(d))?;
+                self.dependent_gas_charge(Opcode::SRWQ, r!(d))?;
                 self.state_read_qword(r!(a), b.into(), r!(c), r!(d))?;
             }
 
@@ -818,7 +918,7 @@ where
 
             Instruction::SWWQ(swwq) => {
                 let (a, b, c, d) = swwq.unpack();
-                self.dependent_gas_charge(self.gas_costs().swwq(), r!(d))?;
+                self.dependent_gas_charge(Opcode::SWWQ, r!(d))?;
                 self.state_write_qword(r!(a), b.into(), r!(c), r!(d))?;
             }
 
@@ -849,14 +949,14 @@ where
             Instruction::K256(k256) => {
                 let (a, b, c) = k256.unpack();
                 let len = r!(c);
-                self.dependent_gas_charge(self.gas_costs().k256(), len)?;
+                self.dependent_gas_charge(Opcode::K256, len)?;
                 self.keccak256(r!(a), r!(b), len)?;
             }
 
             Instruction::S256(s256) => {
                 let (a, b, c) = s256.unpack();
                 let len = r!(c);
-                self.dependent_gas_charge(self.gas_costs().s256(), len)?;
+                self.dependent_gas_charge(Opcode::S256, len)?;
                 self.sha256(r!(a), r!(b), len)?;
             }
 
@@ -891,6 +991,7 @@ where
             }
 
             Instruction::ECAL(ecal) => {
+                self.gas_charge(self.gas_costs().ecal())?;
                 let (a, b, c, d) = ecal.unpack();
                 self.external_call(a, b, c, d)?;
             }
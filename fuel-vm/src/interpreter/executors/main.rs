@@ -19,11 +19,13 @@ use crate::{
         PredicateVerificationFailed,
     },
     interpreter::{
+        contract::balance_increase,
         CheckedMetadata,
         EcalHandler,
         ExecutableTransaction,
         InitialBalances,
         Interpreter,
+        Memory,
         RuntimeBalances,
     },
     predicate::RuntimePredicate,
@@ -67,6 +69,9 @@ use fuel_tx::{
     field::{
         BytecodeRoot,
         BytecodeWitnessIndex,
+        InputContract,
+        MintAmount,
+        MintAssetId,
         ReceiptsRoot,
         Salt,
         Script as ScriptField,
@@ -90,6 +95,7 @@ use fuel_tx::{
     FeeParameters,
     GasCosts,
     Input,
+    Mint,
     Receipt,
     ScriptExecutionResult,
     Upgrade,
@@ -104,15 +110,21 @@ use fuel_types::{
 };
 
 /// Predicates were checked succesfully
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct PredicatesChecked {
     gas_used: Word,
+    gas_used_by_input: Vec<(usize, Word)>,
 }
 
 impl PredicatesChecked {
     pub fn gas_used(&self) -> Word {
         self.gas_used
     }
+
+    /// The gas used by the predicate of each input, by input index.
+    pub fn gas_used_by_input(&self) -> &[(usize, Word)] {
+        &self.gas_used_by_input
+    }
 }
 
 enum PredicateRunKind<'a, Tx> {
@@ -225,6 +237,37 @@ where
         Ok(predicates_checked)
     }
 
+    /// Estimates the gas used by the predicate of the input at `index` of the
+    /// `transaction`, executed with the `memory` and the `storage` of the caller. Returns
+    /// `None` if the input doesn't have a predicate.
+    ///
+    /// The `memory` is reset before the execution, so its allocation is reused between
+    /// the estimations without leaking the state of the previous predicate.
+    #[cfg(feature = "builder")]
+    pub(crate) fn estimate_predicate<S>(
+        transaction: &Tx,
+        index: usize,
+        params: &CheckPredicateParams,
+        memory: &mut Memory,
+        storage: S,
+    ) -> Option<Result<Word, PredicateVerificationFailed>>
+    where
+        S: InterpreterStorage,
+    {
+        let predicate = RuntimePredicate::from_tx(transaction, params.tx_offset, index)?;
+        let result = Self::check_predicate(
+            transaction.clone(),
+            index,
+            PredicateAction::Estimating,
+            predicate,
+            params.clone(),
+            memory,
+            storage,
+        );
+
+        Some(result.map(|(gas_used, _)| gas_used))
+    }
+
     async fn run_predicate_async<E>(
         kind: PredicateRunKind<'_, Tx>,
         params: &CheckPredicateParams,
@@ -251,6 +294,8 @@ where
                         predicate_action,
                         predicate,
                         my_params,
+                        &mut Memory::new(),
+                        PredicateStorage,
                     )
                 });
 
@@ -282,6 +327,8 @@ where
                     predicate_action,
                     predicate,
                     params.clone(),
+                    &mut Memory::new(),
+                    PredicateStorage,
                 ));
             }
         }
@@ -289,13 +336,19 @@ where
         Self::finalize_check_predicate(kind, checks, params)
     }
 
-    fn check_predicate(
+    #[allow(clippy::too_many_arguments)]
+    fn check_predicate<S>(
         tx: Tx,
         index: usize,
         predicate_action: PredicateAction,
         predicate: RuntimePredicate,
         params: CheckPredicateParams,
-    ) -> Result<(Word, usize), PredicateVerificationFailed> {
+        memory: &mut Memory,
+        storage: S,
+    ) -> Result<(Word, usize), PredicateVerificationFailed>
+    where
+        S: InterpreterStorage,
+    {
         match &tx.inputs()[index] {
             Input::CoinPredicate(CoinPredicate {
                 owner: address,
@@ -324,9 +377,7 @@ where
         let zero_gas_price = 0;
         let interpreter_params = InterpreterParams::new(zero_gas_price, params);
 
-        let mut vm = Self::with_storage(PredicateStorage {}, interpreter_params);
-
-        let available_gas = match predicate_action {
+        let (context, available_gas) = match predicate_action {
             PredicateAction::Verifying => {
                 let context = Context::PredicateVerification { program: predicate };
                 let available_gas =
@@ -335,35 +386,52 @@ where
                     } else {
                         return Err(PredicateVerificationFailed::GasNotSpecified);
                     };
+                // Only the used gas is charged, so the declared gas is bounded like the
+                // estimation.
+                if available_gas > max_gas_per_predicate {
+                    return Err(PredicateVerificationFailed::OutOfGas);
+                }
 
-                vm.init_predicate(context, tx, available_gas)?;
-                available_gas
+                (context, available_gas)
             }
             PredicateAction::Estimating => {
                 let context = Context::PredicateEstimation { program: predicate };
                 let available_gas = core::cmp::min(max_gas_per_predicate, max_gas_per_tx);
 
-                vm.init_predicate(context, tx, available_gas)?;
-                available_gas
+                (context, available_gas)
             }
         };
 
-        let result = vm.verify_predicate();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("predicate", index, action = ?predicate_action)
+            .entered();
+
+        memory.reset();
+        let mut vm = Interpreter::<S, Tx>::with_storage(storage, interpreter_params);
+        core::mem::swap(vm.memory_mut(), memory);
+
+        let result = vm
+            .init_predicate(context, tx, available_gas)
+            .map_err(PredicateVerificationFailed::from)
+            .map(|()| vm.verify_predicate());
+        let remaining_gas = vm.remaining_gas();
+        // The memory is given back to the caller, even if the initialization failed.
+        core::mem::swap(vm.memory_mut(), memory);
+        let result = result?;
         let is_successful = matches!(result, Ok(ProgramState::Return(0x01)));
 
         let gas_used = available_gas
-            .checked_sub(vm.remaining_gas())
+            .checked_sub(remaining_gas)
             .ok_or_else(|| Bug::new(BugVariant::GlobalGasUnderflow))?;
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(gas_used, success = is_successful, "verified");
+
         if let PredicateAction::Verifying = predicate_action {
             if !is_successful {
                 result?;
                 return Err(PredicateVerificationFailed::False);
             }
-
-            if vm.remaining_gas() != 0 {
-                return Err(PredicateVerificationFailed::GasMismatch);
-            }
         }
 
         Ok((gas_used, index))
@@ -409,13 +477,21 @@ where
             );
         }
 
-        let cumulative_gas_used = checks.into_iter().try_fold(0u64, |acc, result| {
-            acc.checked_add(result.map(|(gas_used, _)| gas_used)?)
-                .ok_or(PredicateVerificationFailed::OutOfGas)
-        })?;
+        let gas_used_by_input = checks
+            .into_iter()
+            .map(|result| result.map(|(gas_used, index)| (index, gas_used)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cumulative_gas_used =
+            gas_used_by_input
+                .iter()
+                .try_fold(0u64, |acc, (_, gas_used)| {
+                    acc.checked_add(*gas_used)
+                        .ok_or(PredicateVerificationFailed::OutOfGas)
+                })?;
 
         Ok(PredicatesChecked {
             gas_used: cumulative_gas_used,
+            gas_used_by_input,
         })
     }
 }
@@ -456,7 +532,7 @@ where
         let id = if let Some(m) = metadata {
             m.body.contract_id
         } else {
-            contract.id(salt, &root, &storage_root)
+            Contract::id(salt, &root, &storage_root)
         };
 
         // Prevent redeployment of contracts
@@ -487,6 +563,33 @@ where
     }
 }
 
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
+where
+    S: InterpreterStorage,
+{
+    fn mint_inner(
+        mint: &Mint,
+        storage: &mut S,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        let contract_id = &mint.input_contract().contract_id;
+
+        if !storage
+            .storage_contract_exists(contract_id)
+            .map_err(RuntimeError::Storage)?
+        {
+            return Err(InterpreterError::Panic(PanicReason::ContractNotFound));
+        }
+
+        balance_increase(
+            storage,
+            contract_id,
+            mint.mint_asset_id(),
+            *mint.mint_amount(),
+        )?;
+        Ok(())
+    }
+}
+
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
     S: InterpreterStorage,
@@ -754,23 +857,23 @@ where
             )?;
             ProgramState::Return(1)
         } else {
-            if self.transaction().inputs().iter().any(|input| {
-                if let Input::Contract(contract) = input {
-                    !self
-                        .check_contract_exists(&contract.contract_id)
-                        .unwrap_or(false)
-                } else {
-                    false
+            for input in self.transaction().inputs() {
+                if let Input::Contract(contract) | Input::ContractReadOnly(contract) =
+                    input
+                {
+                    if !self.check_contract_exists(&contract.contract_id)? {
+                        return Err(InterpreterError::Panic(
+                            PanicReason::ContractNotInInputs,
+                        ));
+                    }
                 }
-            }) {
-                return Err(InterpreterError::Panic(PanicReason::ContractNotInInputs));
             }
 
             let gas_limit;
             let is_empty_script;
             if let Some(script) = self.transaction().as_script() {
                 let offset = (self.tx_offset() + script.script_offset()) as Word;
-                gas_limit = *script.script_gas_limit();
+                gas_limit = self.script_gas(*script.script_gas_limit());
                 is_empty_script = script.script().is_empty();
 
                 self.registers[RegId::PC] = offset;
@@ -783,6 +886,7 @@ where
 
             // `Interpreter` supports only `Create` and `Script` transactions. It is not
             // `Create` -> it is `Script`.
+            self.frame_gas.enter(None, 0, self.remaining_gas());
             let program = if !is_empty_script {
                 self.run_program()
             } else {
@@ -810,6 +914,14 @@ where
 
                 Err(e) => match e.instruction_result() {
                     Some(result) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            parent: &self.call_spans.innermost(),
+                            reason = ?result.reason(),
+                            instruction = result.instruction(),
+                            contract_id = ?self.contract_id(),
+                            "panic",
+                        );
                         self.append_panic_receipt(result);
 
                         (ScriptExecutionResult::Panic, ProgramState::Revert(0))
@@ -865,9 +977,12 @@ where
             let state = self.execute()?;
 
             if in_call {
-                // Only reverts should terminate execution from a call context
-                if let ExecuteState::Revert(r) = state {
-                    return Ok(ProgramState::Revert(r));
+                // Only reverts should terminate execution from a call context, and the
+                // debug events suspend it
+                match state {
+                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+                    ExecuteState::DebugEvent(d) => return Ok(ProgramState::RunProgram(d)),
+                    _ => (),
                 }
             } else {
                 match state {
@@ -935,9 +1050,43 @@ where
     ) -> Result<StateTransitionRef<'_, Tx>, InterpreterError<S::DataError>> {
         self.verify_ready_tx(&tx)?;
 
-        let state_result = self.init_script(tx).and_then(|_| self.run());
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "transact",
+            tx_id = tracing::field::Empty,
+            gas_limit = tracing::field::Empty,
+        )
+        .entered();
+
+        let state_result = self.init_script(tx).and_then(|_| {
+            #[cfg(feature = "tracing")]
+            span.record(
+                "tx_id",
+                tracing::field::display(
+                    fuel_tx::UniqueIdentifier::cached_id(&self.tx).unwrap_or_default(),
+                ),
+            )
+            .record("gas_limit", self.remaining_gas());
+            self.run()
+        });
         self.post_execute();
 
+        #[cfg(feature = "tracing")]
+        {
+            self.call_spans.clear();
+            match &state_result {
+                Ok(state) => tracing::debug!(
+                    ?state,
+                    gas_remaining = self.remaining_gas(),
+                    "executed",
+                ),
+                Err(InterpreterError::Storage(error)) => {
+                    tracing::error!(?error, "storage error")
+                }
+                Err(error) => tracing::debug!(?error, "failed"),
+            }
+        }
+
         #[cfg(feature = "profile-any")]
         {
             let r = match &state_result {
@@ -988,6 +1137,26 @@ where
     }
 }
 
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
+where
+    S: InterpreterStorage,
+{
+    /// Executes `Mint` transaction without initialization VM and without invalidation
+    /// of the last state of execution of the `Script` transaction.
+    ///
+    /// The minted amount is added to the balance of the contract from the
+    /// `input_contract`. Returns `Mint` transaction with all modifications after
+    /// execution.
+    pub fn execute_mint(
+        &mut self,
+        tx: Checked<Mint>,
+    ) -> Result<Mint, InterpreterError<S::DataError>> {
+        let (mint, _): (Mint, <Mint as IntoChecked>::Metadata) = tx.into();
+        Self::mint_inner(&mint, &mut self.storage)?;
+        Ok(mint)
+    }
+}
+
 impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
     S: InterpreterStorage,
@@ -188,7 +188,7 @@ fn valid_upgrade_tx() -> Checked<Upgrade> {
         input_amount,
         AssetId::BASE,
         Default::default(),
-        0,
+        WitnessIndex::new(0),
     ))
     .add_random_fee_input()
     .finalize_checked_basic(Default::default())
@@ -222,17 +222,18 @@ fn valid_upload_tx() -> Checked<Upload> {
     let subsections = UploadSubsection::split_bytecode(&vec![123; 1024], 24)
         .expect("Should split bytecode");
     let subsection = subsections[0].clone();
-    TransactionBuilder::upload(UploadBody {
+    let mut builder = TransactionBuilder::upload(UploadBody {
         root: subsection.root,
         witness_index: 0,
         subsection_index: subsection.subsection_index,
         subsections_number: subsection.subsections_number,
         proof_set: subsection.proof_set,
-    })
-    .add_witness(subsection.subsection.into())
-    .max_fee_limit(arb_max_fee)
-    .add_random_fee_input()
-    .finalize_checked_basic(Default::default())
+    });
+    builder.add_witness(subsection.subsection.into());
+    builder
+        .max_fee_limit(arb_max_fee)
+        .add_random_fee_input()
+        .finalize_checked_basic(Default::default())
 }
 
 #[test]
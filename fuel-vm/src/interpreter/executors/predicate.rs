@@ -9,7 +9,7 @@ use crate::{
         ExecuteState,
         ProgramState,
     },
-    storage::PredicateStorage,
+    storage::InterpreterStorage,
 };
 
 use fuel_asm::{
@@ -18,8 +18,9 @@ use fuel_asm::{
     Word,
 };
 
-impl<Tx, Ecal> Interpreter<PredicateStorage, Tx, Ecal>
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
 where
+    S: InterpreterStorage,
     Tx: ExecutableTransaction,
     Ecal: EcalHandler,
 {
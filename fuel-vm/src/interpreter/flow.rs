@@ -3,7 +3,10 @@ use crate::{
         Call,
         CallFrame,
     },
-    constraints::reg_key::*,
+    constraints::{
+        reg_key::*,
+        InstructionLocation,
+    },
     consts::*,
     context::Context,
     error::{
@@ -62,6 +65,7 @@ use fuel_storage::{
 };
 use fuel_tx::{
     DependentCost,
+    NewStorageCost,
     PanicReason,
     Receipt,
 };
@@ -100,7 +104,14 @@ where
             context: &mut self.context,
             current_contract,
         };
-        input.ret(a)
+        let in_call = !input.frames.is_empty();
+        let result = input.ret(a);
+        if in_call {
+            self.profiler.on_return();
+        }
+        #[cfg(feature = "memory-heat-map")]
+        self.enter_heat_map_frame();
+        result
     }
 
     pub(crate) fn ret_data(&mut self, a: Word, b: Word) -> SimpleResult<Bytes32> {
@@ -114,7 +125,14 @@ where
             context: &mut self.context,
             current_contract,
         };
-        input.ret_data(a, b)
+        let in_call = !input.frames.is_empty();
+        let result = input.ret_data(a, b);
+        if in_call {
+            self.profiler.on_return();
+        }
+        #[cfg(feature = "memory-heat-map")]
+        self.enter_heat_map_frame();
+        result
     }
 
     pub(crate) fn revert(&mut self, a: Word) -> SimpleResult<()> {
@@ -130,6 +148,34 @@ where
         )
     }
 
+    /// Unwinds the current call frame to its caller with a panic receipt of the
+    /// `result`, the callee returning `0`.
+    pub(crate) fn unwind_frame(&mut self, result: PanicInstruction) -> SimpleResult<()> {
+        let current_contract =
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
+        let receipt = Receipt::panic(
+            current_contract.unwrap_or_default(),
+            result,
+            self.registers[RegId::PC],
+            self.registers[RegId::IS],
+        );
+        self.registers[RegId::RET] = 0;
+        self.registers[RegId::RETL] = 0;
+        let input = RetCtx {
+            receipts: &mut self.receipts,
+            frames: &mut self.frames,
+            registers: &mut self.registers,
+            memory: &self.memory,
+            context: &mut self.context,
+            current_contract,
+        };
+        let result = input.return_from_context(receipt);
+        self.profiler.on_return();
+        #[cfg(feature = "memory-heat-map")]
+        self.enter_heat_map_frame();
+        result
+    }
+
     pub(crate) fn append_panic_receipt(&mut self, result: PanicInstruction) {
         let pc = self.registers[RegId::PC];
         let is = self.registers[RegId::IS];
@@ -364,13 +410,22 @@ where
             amount_of_gas_to_forward,
         };
         let gas_cost = self.gas_costs().call();
-        let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
+        let new_storage_cost = self.gas_costs().new_storage();
         // Charge only for the `base` execution.
         // We will charge for the frame size in the `prepare_call`.
         self.gas_charge(gas_cost.base())?;
+        if amount_of_coins_to_forward > 0 {
+            let callee = ContractId::new(self.memory.read_bytes(call_params_pointer)?);
+            self.ensure_contract_writable(&callee)?;
+            self.ensure_current_contract_writable()?;
+        }
         let current_contract =
             current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let input_contracts = self.tx.input_contracts().copied().collect::<Vec<_>>();
+        let location = InstructionLocation::new(
+            current_contract,
+            self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]),
+        );
 
         PrepareCallCtx {
             params,
@@ -384,13 +439,17 @@ where
                 input_contracts.iter(),
                 &mut self.panic_context,
             ),
-            new_storage_gas_per_byte,
+            new_storage_cost,
             receipts: &mut self.receipts,
             frames: &mut self.frames,
             current_contract,
             profiler: &mut self.profiler,
         }
-        .prepare_call()
+        .prepare_call()?;
+        self.profiler.on_call(location);
+        #[cfg(feature = "memory-heat-map")]
+        self.enter_heat_map_frame();
+        Ok(())
     }
 }
 
@@ -447,7 +506,7 @@ struct PrepareCallCtx<'vm, S, I> {
     context: &'vm mut Context,
     gas_cost: DependentCost,
     runtime_balances: &'vm mut RuntimeBalances,
-    new_storage_gas_per_byte: Word,
+    new_storage_cost: NewStorageCost,
     storage: &'vm mut S,
     input_contracts: InputContracts<'vm, I>,
     receipts: &'vm mut ReceiptsCtx,
@@ -535,8 +594,8 @@ where
                 self.registers.system_registers.cgas.as_mut(),
                 self.registers.system_registers.ggas.as_mut(),
                 profiler,
-                // Overflow safety: unset_count * 32 can be at most VM_MAX_RAM
-                ((Bytes32::LEN + WORD_SIZE) as u64) * self.new_storage_gas_per_byte,
+                self.new_storage_cost
+                    .resolve(1, (Bytes32::LEN + WORD_SIZE) as Word),
             )?;
         }
 
@@ -380,7 +380,10 @@ fn test_prepare_call(input: Input) -> Result<Output, RuntimeError<Infallible>> {
         runtime_balances: &mut runtime_balances,
         storage: &mut storage,
         input_contracts: InputContracts::new(input_contracts.iter(), &mut panic_context),
-        new_storage_gas_per_byte: 0,
+        new_storage_cost: NewStorageCost {
+            per_slot: 0,
+            per_byte: 0,
+        },
         receipts: &mut receipts,
         frames: &mut frames,
         current_contract,
@@ -0,0 +1,160 @@
+//! Attribution of the gas of a script to its call frames.
+
+use alloc::vec::Vec;
+
+use fuel_asm::{
+    PanicInstruction,
+    PanicReason,
+    RawInstruction,
+};
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use super::{
+    ExecutableTransaction,
+    Interpreter,
+};
+use crate::error::SimpleResult;
+
+/// The gas consumed by a call frame, or by the script at the depth `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameGas {
+    /// The called contract, `None` for the script.
+    pub contract_id: Option<ContractId>,
+    /// The number of frames below this one, `0` for the script.
+    pub depth: usize,
+    /// The gas consumed by the instructions of the frame, excluding the frames it
+    /// called. The `CALL` instruction is accounted to the caller, the `RET` to the
+    /// callee.
+    pub self_gas: Word,
+    /// The gas consumed by the frame and the frames it called.
+    pub total_gas: Word,
+}
+
+/// A frame that didn't return yet.
+#[derive(Debug, Clone, Copy)]
+struct OpenFrame {
+    /// The index of the frame in the report.
+    index: usize,
+    /// The global gas when the frame was entered.
+    entry_gas: Word,
+    /// The gas consumed by the frames it called.
+    children_gas: Word,
+}
+
+/// The frames of the current script, in the order of their calls.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FrameGasTracker {
+    frames: Vec<FrameGas>,
+    open: Vec<OpenFrame>,
+}
+
+impl FrameGasTracker {
+    /// Forgets the frames of the previous transaction.
+    pub(crate) fn clear(&mut self) {
+        self.frames.clear();
+        self.open.clear();
+    }
+
+    /// Enters a frame at the `depth` with the global `gas` remaining.
+    pub(crate) fn enter(
+        &mut self,
+        contract_id: Option<ContractId>,
+        depth: usize,
+        gas: Word,
+    ) {
+        self.open.push(OpenFrame {
+            index: self.frames.len(),
+            entry_gas: gas,
+            children_gas: 0,
+        });
+        self.frames.push(FrameGas {
+            contract_id,
+            depth,
+            self_gas: 0,
+            total_gas: 0,
+        });
+    }
+
+    /// Leaves the innermost frame with the global `gas` remaining.
+    pub(crate) fn leave(&mut self, gas: Word) {
+        let Some(frame) = self.open.pop() else { return };
+        let total_gas = frame.entry_gas.saturating_sub(gas);
+        let report = &mut self.frames[frame.index];
+        report.total_gas = total_gas;
+        report.self_gas = total_gas.saturating_sub(frame.children_gas);
+        if let Some(parent) = self.open.last_mut() {
+            parent.children_gas = parent.children_gas.saturating_add(total_gas);
+        }
+    }
+
+    /// The self gas of the innermost frame at the `depth` so far, with the global `gas`
+    /// remaining, `None` if the frames aren't tracked at this depth.
+    fn self_gas(&self, depth: usize, gas: Word) -> Option<Word> {
+        let frame = self.open.last().filter(|_| self.open.len() == depth + 1)?;
+        Some(
+            frame
+                .entry_gas
+                .saturating_sub(gas)
+                .saturating_sub(frame.children_gas),
+        )
+    }
+
+    /// The frames, with the ones that didn't return leaving with the global `gas`
+    /// remaining.
+    fn report(&self, gas: Word) -> Vec<FrameGas> {
+        let mut tracker = self.clone();
+        while !tracker.open.is_empty() {
+            tracker.leave(gas);
+        }
+        tracker.frames
+    }
+}
+
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
+    /// The gas consumed by the script of the last transaction and by each of its call
+    /// frames, in the order of the calls.
+    ///
+    /// The frames that reverted or panicked report the gas consumed until then, as do
+    /// the ones still executing after a debugger breakpoint.
+    pub fn frame_gas_report(&self) -> Vec<FrameGas> {
+        self.frame_gas.report(self.remaining_gas())
+    }
+}
+
+impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal>
+where
+    Tx: ExecutableTransaction,
+{
+    /// Unwinds the current call frame with an `OutOfGas` panic of the `instruction` if
+    /// its self gas exceeds the [`InterpreterParams::gas_allowance`] of its contract.
+    ///
+    /// [`InterpreterParams::gas_allowance`]: super::InterpreterParams::gas_allowance
+    pub(crate) fn enforce_gas_allowance(
+        &mut self,
+        instruction: RawInstruction,
+    ) -> SimpleResult<()> {
+        if self.interpreter_params.gas_allowance.is_empty() {
+            return Ok(())
+        }
+        let Some(frame) = self.frames.last() else {
+            return Ok(())
+        };
+        let Some(allowance) = self.interpreter_params.gas_allowance.get(frame.to())
+        else {
+            return Ok(())
+        };
+        let self_gas = self
+            .frame_gas
+            .self_gas(self.frames.len(), self.remaining_gas());
+        if self_gas.is_none_or(|gas| gas <= *allowance) {
+            return Ok(())
+        }
+
+        self.unwind_frame(PanicInstruction::error(PanicReason::OutOfGas, instruction))?;
+        self.frame_gas.leave(self.remaining_gas());
+        Ok(())
+    }
+}
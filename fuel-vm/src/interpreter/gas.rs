@@ -13,6 +13,7 @@ use crate::{
 };
 
 use fuel_asm::{
+    Opcode,
     PanicReason,
     RegId,
 };
@@ -31,6 +32,16 @@ impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
         self.registers[RegId::GGAS]
     }
 
+    /// The gas available to a script with the `gas_limit`, unlimited with
+    /// [`InterpreterParams::free_gas`](crate::interpreter::InterpreterParams::free_gas).
+    pub(crate) fn script_gas(&self, gas_limit: Word) -> Word {
+        if self.interpreter_params.free_gas {
+            Word::MAX
+        } else {
+            gas_limit
+        }
+    }
+
     /// Sets the amount of gas available for execution to both CGAS and GGAS.
     /// Only useful in contexts where CGAS and GGAS are the same,
     /// i.e. predicates and testing.
@@ -39,25 +50,27 @@ impl<S, Tx, Ecal> Interpreter<S, Tx, Ecal> {
         self.registers[RegId::CGAS] = gas;
     }
 
+    /// Charges the cost of the `opcode` for `units` units, resolved by
+    /// [`GasCosts::charge_for`](fuel_tx::GasCosts::charge_for) so that the charged gas
+    /// is the predicted one.
     pub(crate) fn dependent_gas_charge(
         &mut self,
-        gas_cost: DependentCost,
-        arg: Word,
+        opcode: Opcode,
+        units: Word,
     ) -> SimpleResult<()> {
-        let current_contract = self.contract_id();
-        let SystemRegisters {
-            pc, ggas, cgas, is, ..
-        } = split_registers(&mut self.registers).0;
-        let profiler = ProfileGas {
-            pc: pc.as_ref(),
-            is: is.as_ref(),
-            current_contract,
-            profiler: &mut self.profiler,
-        };
-        dependent_gas_charge(cgas, ggas, profiler, gas_cost, arg)
+        let gas = self
+            .gas_costs()
+            .charge_for(opcode, units)
+            .ok_or_else(|| Bug::new(BugVariant::MissingGasCost))?;
+        #[cfg(feature = "profile-gas")]
+        self.profiler.add_units(units);
+        self.gas_charge(gas)
     }
 
     /// Do a gas charge with the given amount, panicing when running out of gas.
+    ///
+    /// Charging more than the remaining context gas, like a dependent cost saturated at
+    /// [`Word::MAX`], consumes all of it and panics with [`PanicReason::OutOfGas`].
     pub fn gas_charge(&mut self, gas: Word) -> SimpleResult<()> {
         let current_contract = self.contract_id();
         let SystemRegisters {
@@ -81,6 +94,7 @@ pub(crate) fn dependent_gas_charge_without_base(
     gas_cost: DependentCost,
     arg: Word,
 ) -> SimpleResult<()> {
+    profiler.profile_units(arg);
     let cost =
         dependent_gas_charge_without_base_inner(cgas.as_mut(), ggas, gas_cost, arg)?;
     profiler.profile(cgas.as_ref(), cost);
@@ -97,28 +111,6 @@ fn dependent_gas_charge_without_base_inner(
     gas_charge_inner(cgas, ggas, cost).map(|_| cost)
 }
 
-pub(crate) fn dependent_gas_charge(
-    mut cgas: RegMut<CGAS>,
-    ggas: RegMut<GGAS>,
-    mut profiler: ProfileGas<'_>,
-    gas_cost: DependentCost,
-    arg: Word,
-) -> SimpleResult<()> {
-    let cost = dependent_gas_charge_inner(cgas.as_mut(), ggas, gas_cost, arg)?;
-    profiler.profile(cgas.as_ref(), cost);
-    Ok(())
-}
-
-fn dependent_gas_charge_inner(
-    cgas: RegMut<CGAS>,
-    ggas: RegMut<GGAS>,
-    gas_cost: DependentCost,
-    arg: Word,
-) -> Result<Word, PanicOrBug> {
-    let cost = gas_cost.resolve(arg);
-    gas_charge_inner(cgas, ggas, cost).map(|_| cost)
-}
-
 pub(crate) fn gas_charge(
     cgas: RegMut<CGAS>,
     ggas: RegMut<GGAS>,
@@ -181,4 +173,11 @@ impl<'a> ProfileGas<'a> {
             self.profiler.add_gas(location, gas_use);
         }
     }
+
+    /// Records the `units` of the dependent cost charged for the current instruction.
+    #[allow(unused_variables)]
+    pub(crate) fn profile_units(&mut self, units: Word) {
+        #[cfg(feature = "profile-gas")]
+        self.profiler.add_units(units);
+    }
 }
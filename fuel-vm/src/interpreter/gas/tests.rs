@@ -103,11 +103,15 @@ fn test_dependent_gas_charge(input: DepGasChargeInput) -> SimpleResult<GasCharge
     } = input;
     let mut cgas = RegMut::new(&mut cgas);
     let mut ggas = RegMut::new(&mut ggas);
-    dependent_gas_charge_inner(cgas.as_mut(), ggas.as_mut(), gas_cost, dependent_factor)
-        .map(|_| GasChargeOutput {
-            cgas: *cgas,
-            ggas: *ggas,
-        })
+    gas_charge_inner(
+        cgas.as_mut(),
+        ggas.as_mut(),
+        gas_cost.resolve(dependent_factor),
+    )
+    .map(|_| GasChargeOutput {
+        cgas: *cgas,
+        ggas: *ggas,
+    })
 }
 
 #[test_case(
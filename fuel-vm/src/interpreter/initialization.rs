@@ -13,6 +13,7 @@ use crate::{
     context::Context,
     error::InterpreterError,
     prelude::RuntimeError,
+    receipt_sink::ReceiptContext,
     storage::InterpreterStorage,
 };
 use fuel_asm::RegId;
@@ -36,11 +37,22 @@ where
     ) -> Result<(), RuntimeError<S::DataError>> {
         tx.prepare_init_execute();
         self.tx = tx;
+        let tx_id = self.transaction().id(&self.chain_id());
 
         self.initial_balances = initial_balances.clone();
 
         self.frames.clear();
+        #[cfg(feature = "memory-heat-map")]
+        self.enter_heat_map_frame();
+        self.frame_gas.clear();
         self.receipts.clear();
+        self.receipts.set_context(ReceiptContext {
+            tx_id,
+            block_height: self.context.block_height(),
+        });
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.on_transaction();
+        }
 
         // Optimized for memset
         self.registers.iter_mut().for_each(|r| *r = 0);
@@ -51,7 +63,7 @@ where
         // Set heap area
         self.registers[RegId::HP] = VM_MAX_RAM;
 
-        self.push_stack(self.transaction().id(&self.chain_id()).as_ref())?;
+        self.push_stack(tx_id.as_ref())?;
 
         let base_asset_id = self.interpreter_params.base_asset_id;
         self.push_stack(&*base_asset_id)?;
@@ -116,7 +128,7 @@ where
 
         let gas_limit = tx
             .as_script()
-            .map(|script| *script.script_gas_limit())
+            .map(|script| self.script_gas(*script.script_gas_limit()))
             .unwrap_or_default();
 
         let initial_balances = metadata.balances();
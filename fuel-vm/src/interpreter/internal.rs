@@ -2,6 +2,7 @@ use super::{
     ExecutableTransaction,
     Interpreter,
     Memory,
+    PanicContext,
     RuntimeBalances,
 };
 use crate::{
@@ -18,6 +19,7 @@ use fuel_asm::{
 };
 use fuel_tx::{
     field::Outputs,
+    Input,
     Output,
 };
 use fuel_types::{
@@ -47,6 +49,33 @@ where
         let tx_offset = self.tx_offset();
         update_memory_output(&mut self.tx, &mut self.memory, tx_offset, idx)
     }
+
+    /// Checks that the contract isn't declared as `Input::ContractReadOnly`, so its
+    /// state and balances may be mutated.
+    pub(crate) fn ensure_contract_writable(
+        &mut self,
+        contract: &ContractId,
+    ) -> SimpleResult<()> {
+        let read_only = self.tx.inputs().iter().any(|input| {
+            matches!(input, Input::ContractReadOnly(c) if &c.contract_id == contract)
+        });
+
+        if read_only {
+            self.panic_context = PanicContext::ContractId(*contract);
+            Err(PanicReason::ContractNotWritable.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Same as [`Self::ensure_contract_writable`] for the contract of the current call
+    /// frame. Does nothing in the external context.
+    pub(crate) fn ensure_current_contract_writable(&mut self) -> SimpleResult<()> {
+        match self.internal_contract() {
+            Ok(contract) => self.ensure_contract_writable(&contract),
+            Err(_) => Ok(()),
+        }
+    }
 }
 
 /// Increase the variable output with a given asset ID. Modifies both the referenced tx
@@ -37,6 +37,20 @@ use alloc::{
     vec::Vec,
 };
 
+#[cfg(feature = "memory-heat-map")]
+use crate::memory_heat_map::{
+    HeatMapFrame,
+    MemoryAccess,
+    MemoryHeatMap,
+};
+#[cfg(feature = "memory-heat-map")]
+use alloc::sync::Arc;
+#[cfg(feature = "memory-heat-map")]
+use std::sync::{
+    Mutex,
+    PoisonError,
+};
+
 #[cfg(test)]
 mod tests;
 
@@ -69,7 +83,8 @@ fn reverse_resize_at_least(vec: &mut Vec<u8>, new_len: usize) {
 }
 
 /// The memory of the VM, represented as stack and heap.
-#[derive(Debug, Clone, PartialEq, Eq, Derivative)]
+#[derive(Debug, Clone, Derivative)]
+#[derivative(PartialEq, Eq)]
 pub struct Memory {
     /// Stack. Grows upwards.
     #[derivative(Debug(format_with = "fmt_truncated_hex::<16>"))]
@@ -80,6 +95,10 @@ pub struct Memory {
     /// Lowest allowed heap address, i.e. hp register value.
     /// This is needed since we can allocate extra heap for performance reasons.
     hp: usize,
+    /// The heat map recording the accesses, shared with the clones of the memory.
+    #[cfg(feature = "memory-heat-map")]
+    #[derivative(PartialEq = "ignore")]
+    heat_map: Option<Arc<Mutex<MemoryHeatMap>>>,
 }
 
 impl Default for Memory {
@@ -95,6 +114,56 @@ impl Memory {
             stack: Vec::new(),
             heap: Vec::new(),
             hp: MEM_SIZE,
+            #[cfg(feature = "memory-heat-map")]
+            heat_map: None,
+        }
+    }
+
+    /// Clears the stack and the heap, like a new memory, keeping the allocation of the
+    /// stack for reuse.
+    pub fn reset(&mut self) {
+        self.stack.truncate(0);
+        self.heap.truncate(0);
+        self.hp = MEM_SIZE;
+    }
+
+    /// Records the accesses in the `heat_map`, see [`MemoryHeatMap`].
+    #[cfg(feature = "memory-heat-map")]
+    pub(crate) fn set_heat_map(&mut self, heat_map: MemoryHeatMap) {
+        self.heat_map = Some(Arc::new(Mutex::new(heat_map)));
+    }
+
+    /// The accesses recorded since [`Self::set_heat_map`].
+    #[cfg(feature = "memory-heat-map")]
+    pub(crate) fn heat_map(&self) -> Option<MemoryHeatMap> {
+        self.heat_map.as_ref().map(|heat_map| {
+            heat_map
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        })
+    }
+
+    /// Attributes the next accesses to the `frame`.
+    #[cfg(feature = "memory-heat-map")]
+    pub(crate) fn set_heat_map_frame(&self, frame: HeatMapFrame) {
+        if let Some(heat_map) = &self.heat_map {
+            heat_map
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .set_frame(frame);
+        }
+    }
+
+    /// Counts the access to the `range` in the heat map, if any.
+    #[cfg(feature = "memory-heat-map")]
+    #[inline(always)]
+    fn record_access(&self, access: MemoryAccess, range: &MemoryRange) {
+        if let Some(heat_map) = &self.heat_map {
+            heat_map
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .record(access, range.start(), range.len());
         }
     }
 
@@ -185,6 +254,8 @@ impl Memory {
         count: C,
     ) -> Result<&[u8], PanicReason> {
         let range = self.verify(addr, count)?;
+        #[cfg(feature = "memory-heat-map")]
+        self.record_access(MemoryAccess::Read, &range);
 
         if range.end() <= self.stack.len() {
             Ok(&self.stack[range.usizes()])
@@ -215,6 +286,8 @@ impl Memory {
         len: B,
     ) -> Result<&mut [u8], PanicReason> {
         let range = self.verify(addr, len)?;
+        #[cfg(feature = "memory-heat-map")]
+        self.record_access(MemoryAccess::Write, &range);
         if range.end() <= self.stack.len() {
             Ok(&mut self.stack[range.usizes()])
         } else if range.start() >= self.heap_offset() {
@@ -887,6 +960,7 @@ impl OwnershipRegisters {
 
 /// Attempt copy from slice to memory, filling zero bytes when exceeding slice boundaries.
 /// Performs overflow and memory range checks, but no ownership checks.
+#[cfg(test)]
 pub(crate) fn copy_from_slice_zero_fill_noownerchecks<A: ToAddr, B: ToAddr>(
     memory: &mut Memory,
     src: &[u8],
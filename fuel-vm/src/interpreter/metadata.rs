@@ -179,6 +179,10 @@ impl<Tx> GTFInput<'_, Tx> {
                 .policies()
                 .get(PolicyType::MaxFee)
                 .ok_or(PanicReason::PolicyIsNotSet)?,
+            GTFArgs::PolicyExpiration => tx
+                .policies()
+                .get(PolicyType::Expiration)
+                .ok_or(PanicReason::PolicyIsNotSet)?,
             GTFArgs::ScriptInputsCount | GTFArgs::CreateInputsCount => {
                 tx.inputs().len() as Word
             }
@@ -0,0 +1,184 @@
+//! The validated construction of the [`InterpreterParams`].
+
+use super::InterpreterParams;
+use crate::{
+    checked_transaction::CheckPredicateParams,
+    consts::{
+        MEM_SIZE,
+        VM_MAX_RAM,
+    },
+};
+
+use fuel_tx::{
+    FeeParameters,
+    GasCosts,
+};
+use fuel_types::{
+    AssetId,
+    ChainId,
+    ContractId,
+    Word,
+};
+
+/// The error of the [`InterpreterParamsBuilder::build`] of inconsistent parameters.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum InterpreterParamsError {
+    /// The transaction would start past the end of the memory.
+    #[display(fmt = "The tx offset {_0} is outside of the memory")]
+    TxOffsetOutOfMemory(usize),
+    /// A contract of the maximum size wouldn't fit in the memory.
+    #[display(fmt = "The contract max size {_0} exceeds the memory")]
+    ContractMaxSizeExceedsMemory(u64),
+    /// A message data of the maximum length wouldn't fit in the memory.
+    #[display(fmt = "The max message data length {_0} exceeds the memory")]
+    MessageDataLengthExceedsMemory(u64),
+    /// The gas price factor is zero, making any fee unpayable.
+    #[display(fmt = "The gas price factor is zero")]
+    ZeroGasPriceFactor,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InterpreterParamsError {}
+
+/// Builds [`InterpreterParams`] from the consensus parameters, with chainable setters
+/// and the validation of the parameters at [`Self::build`].
+///
+/// Deserialized parameters are validated with
+/// `InterpreterParamsBuilder::from(params).build()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterpreterParamsBuilder {
+    params: InterpreterParams,
+}
+
+impl InterpreterParamsBuilder {
+    /// The parameters of the `params`, with a zero gas price.
+    pub fn new<T: Into<CheckPredicateParams>>(params: T) -> Self {
+        let params: CheckPredicateParams = params.into();
+        Self {
+            params: InterpreterParams {
+                gas_price: 0,
+                gas_costs: params.gas_costs,
+                max_inputs: params.max_inputs,
+                contract_max_size: params.contract_max_size,
+                tx_offset: params.tx_offset,
+                max_message_data_length: params.max_message_data_length,
+                chain_id: params.chain_id,
+                fee_params: params.fee_params,
+                base_asset_id: params.base_asset_id,
+                free_gas: false,
+                gas_allowance: Default::default(),
+            },
+        }
+    }
+
+    /// Sets the gas price.
+    pub fn gas_price(&mut self, gas_price: Word) -> &mut Self {
+        self.params.gas_price = gas_price;
+        self
+    }
+
+    /// Sets the gas costs.
+    pub fn gas_costs(&mut self, gas_costs: GasCosts) -> &mut Self {
+        self.params.gas_costs = gas_costs;
+        self
+    }
+
+    /// Sets the maximum number of inputs.
+    pub fn max_inputs(&mut self, max_inputs: u16) -> &mut Self {
+        self.params.max_inputs = max_inputs;
+        self
+    }
+
+    /// Sets the maximum size of the contract in bytes.
+    pub fn contract_max_size(&mut self, contract_max_size: u64) -> &mut Self {
+        self.params.contract_max_size = contract_max_size;
+        self
+    }
+
+    /// Sets the offset of the transaction data in the memory.
+    pub fn tx_offset(&mut self, tx_offset: usize) -> &mut Self {
+        self.params.tx_offset = tx_offset;
+        self
+    }
+
+    /// Sets the maximum length of the message data.
+    pub fn max_message_data_length(&mut self, max_message_data_length: u64) -> &mut Self {
+        self.params.max_message_data_length = max_message_data_length;
+        self
+    }
+
+    /// Sets the chain ID.
+    pub fn chain_id(&mut self, chain_id: ChainId) -> &mut Self {
+        self.params.chain_id = chain_id;
+        self
+    }
+
+    /// Sets the fee parameters.
+    pub fn fee_params(&mut self, fee_params: FeeParameters) -> &mut Self {
+        self.params.fee_params = fee_params;
+        self
+    }
+
+    /// Sets the base asset ID.
+    pub fn base_asset_id(&mut self, base_asset_id: AssetId) -> &mut Self {
+        self.params.base_asset_id = base_asset_id;
+        self
+    }
+
+    /// Sets whether the scripts run with unlimited gas, see
+    /// [`InterpreterParams::free_gas`].
+    pub fn free_gas(&mut self, free_gas: bool) -> &mut Self {
+        self.params.free_gas = free_gas;
+        self
+    }
+
+    /// Sets the gas allowance of the call frames of the `contract_id`, see
+    /// [`InterpreterParams::gas_allowance`].
+    pub fn gas_allowance(&mut self, contract_id: ContractId, gas: Word) -> &mut Self {
+        self.params.gas_allowance.insert(contract_id, gas);
+        self
+    }
+
+    /// The parameters, if the transaction, the contracts and the message data fit in
+    /// the memory and the gas price factor isn't zero.
+    pub fn build(&self) -> Result<InterpreterParams, InterpreterParamsError> {
+        let params = &self.params;
+        if params.tx_offset >= MEM_SIZE {
+            return Err(InterpreterParamsError::TxOffsetOutOfMemory(
+                params.tx_offset,
+            ));
+        }
+        if params.contract_max_size > VM_MAX_RAM {
+            return Err(InterpreterParamsError::ContractMaxSizeExceedsMemory(
+                params.contract_max_size,
+            ));
+        }
+        if params.max_message_data_length > VM_MAX_RAM {
+            return Err(InterpreterParamsError::MessageDataLengthExceedsMemory(
+                params.max_message_data_length,
+            ));
+        }
+        if params.fee_params.gas_price_factor() == 0 {
+            return Err(InterpreterParamsError::ZeroGasPriceFactor);
+        }
+        Ok(self.params.clone())
+    }
+
+    /// The parameters without the validation of [`Self::build`].
+    pub(crate) fn into_unchecked(self) -> InterpreterParams {
+        self.params
+    }
+}
+
+impl From<InterpreterParams> for InterpreterParamsBuilder {
+    fn from(params: InterpreterParams) -> Self {
+        Self { params }
+    }
+}
+
+#[cfg(feature = "test-helpers")]
+impl Default for InterpreterParamsBuilder {
+    fn default() -> Self {
+        Self::new(fuel_tx::ConsensusParameters::standard())
+    }
+}
@@ -1,4 +1,7 @@
-use alloc::vec::Vec;
+use alloc::{
+    sync::Arc,
+    vec::Vec,
+};
 use core::{
     mem,
     ops::Index,
@@ -18,6 +21,10 @@ use crate::{
         Bug,
         BugVariant,
     },
+    receipt_sink::{
+        ReceiptContext,
+        ReceiptSink,
+    },
 };
 
 /// Receipts and the associated Merkle tree
@@ -25,6 +32,10 @@ use crate::{
 pub struct ReceiptsCtx {
     receipts: Vec<Receipt>,
     receipts_tree: MerkleTree,
+    /// The sink notified of each pushed receipt.
+    sink: Option<Arc<dyn ReceiptSink>>,
+    /// The transaction producing the receipts, given to the sink.
+    context: ReceiptContext,
 }
 
 impl ReceiptsCtx {
@@ -36,7 +47,7 @@ impl ReceiptsCtx {
     /// Returns a panic if the context is full.
     pub fn push(&mut self, receipt: Receipt) -> SimpleResult<()> {
         if self.receipts.len() == Self::MAX_RECEIPTS {
-            return Err(Bug::new(BugVariant::ReceiptsCtxFull).into())
+            return Err(Bug::new(BugVariant::ReceiptsCtxFull).into());
         }
 
         // Last two slots can be only used for ending the script,
@@ -49,10 +60,13 @@ impl ReceiptsCtx {
                     Receipt::ScriptResult { .. } | Receipt::Panic { .. }
                 ))
         {
-            return Err(PanicReason::TooManyReceipts.into())
+            return Err(PanicReason::TooManyReceipts.into());
         }
 
         self.receipts_tree.push(receipt.to_bytes().as_slice());
+        if let Some(sink) = &self.sink {
+            sink.on_receipt(&self.context, &receipt);
+        }
         self.receipts.push(receipt);
         Ok(())
     }
@@ -63,6 +77,16 @@ impl ReceiptsCtx {
         self.receipts.clear();
     }
 
+    /// Notifies the `sink` of the receipts pushed from now on.
+    pub(crate) fn set_sink(&mut self, sink: Arc<dyn ReceiptSink>) {
+        self.sink = Some(sink);
+    }
+
+    /// Sets the transaction producing the next receipts.
+    pub(crate) fn set_context(&mut self, context: ReceiptContext) {
+        self.context = context;
+    }
+
     /// Return how many receipts are in this context
     pub fn len(&self) -> usize {
         self.receipts.len()
@@ -223,4 +247,14 @@ mod tests {
         let expected_root = ephemeral_merkle_root(leaves);
         assert_eq!(root, expected_root)
     }
+
+    #[test]
+    fn root_matches_receipt_root() {
+        let mut ctx = ReceiptsCtx::default();
+        for receipt in iter::repeat(create_receipt()).take(5) {
+            ctx.push(receipt).expect("context not full");
+        }
+
+        assert_eq!(ctx.root(), Receipt::root(ctx.as_ref()));
+    }
 }
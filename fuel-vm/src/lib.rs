@@ -22,11 +22,28 @@ pub mod constraints;
 pub mod consts;
 pub mod context;
 mod convert;
+pub mod coverage;
 pub mod crypto;
+#[cfg(feature = "debug-server")]
+pub mod debug;
+pub mod diff;
+#[cfg(all(feature = "std", feature = "test-helpers"))]
+pub mod differential;
 pub mod error;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz_util;
+#[cfg(feature = "gas-bench")]
+pub mod gas_bench;
 pub mod interpreter;
 pub mod memory_client;
+#[cfg(feature = "memory-heat-map")]
+pub mod memory_heat_map;
+pub mod observer;
 pub mod predicate;
+pub mod receipt_sink;
+#[cfg(all(feature = "std", feature = "test-helpers"))]
+pub mod repl;
+pub mod source_map;
 pub mod state;
 pub mod storage;
 pub mod transactor;
@@ -53,6 +70,15 @@ pub mod profiler {
 
         /// Add gas to the current coverage location.
         pub fn add_gas(&mut self, _location: InstructionLocation, _gas_use: u64) {}
+
+        /// Count the execution of the instruction at the location.
+        pub fn on_instruction(&mut self, _location: InstructionLocation) {}
+
+        /// Enter the frame of the contract called at the location.
+        pub fn on_call(&mut self, _location: InstructionLocation) {}
+
+        /// Return from the frame of the current contract.
+        pub fn on_return(&mut self) {}
     }
 }
 
@@ -124,6 +150,7 @@ pub mod prelude {
             CallFrame,
         },
         context::Context,
+        coverage::Coverage,
         error::{
             Bug,
             BugVariant,
@@ -137,6 +164,10 @@ pub mod prelude {
         },
         memory_client::MemoryClient,
         predicate::RuntimePredicate,
+        source_map::{
+            SourceLocation,
+            SourceMap,
+        },
         state::{
             Debugger,
             ProgramState,
@@ -151,9 +182,20 @@ pub mod prelude {
         transactor::Transactor,
     };
 
+    #[cfg(feature = "builder")]
+    pub use crate::checked_transaction::builder::EstimatePredicatesBuilderExt;
+
     pub use crate::state::{
         Breakpoint,
+        BreakpointCondition,
+        Comparison,
+        DebugCommand,
+        DebugEdit,
+        DebugEditError,
         DebugEval,
+        WatchError,
+        WatchExpression,
+        WatchValue,
     };
 
     #[cfg(any(test, feature = "test-helpers"))]
@@ -177,12 +219,16 @@ pub mod prelude {
     pub use crate::profiler::{
         CoverageProfilingData,
         GasProfilingData,
+        GasShift,
         InstructionLocation,
+        OpcodeProfilingData,
         PerLocationIter,
         PerLocationKeys,
         PerLocationValues,
         ProfileReceiver,
         ProfilingData,
+        SensitivityReport,
         StderrReceiver,
+        TransactionProfile,
     };
 }
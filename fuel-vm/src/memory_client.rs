@@ -18,6 +18,7 @@ use fuel_tx::{
     Create,
     FeeParameters,
     GasCosts,
+    Mint,
     Receipt,
     Script,
     Upgrade,
@@ -86,19 +87,31 @@ impl<Ecal: EcalHandler> MemoryClient<Ecal> {
         &mut self,
         tx: Checked<Create>,
     ) -> Result<Create, InterpreterError<Infallible>> {
+        self.open_checkpoint();
         self.transactor.deploy(tx)
     }
 
+    /// Executes `Mint` transaction.
+    pub fn mint(
+        &mut self,
+        tx: Checked<Mint>,
+    ) -> Result<Mint, InterpreterError<Infallible>> {
+        self.open_checkpoint();
+        self.transactor.mint(tx)
+    }
+
     /// Executes `Upgrade` transaction.
     pub fn upgrade(
         &mut self,
         tx: Checked<Upgrade>,
     ) -> Result<Upgrade, InterpreterError<Infallible>> {
+        self.open_checkpoint();
         self.transactor.upgrade(tx)
     }
 
     /// Executes `Upload` transaction.
     pub fn upload(&mut self, tx: Checked<Upload>) -> Option<Upload> {
+        self.open_checkpoint();
         self.transactor.upload(tx).ok()
     }
 
@@ -107,6 +120,7 @@ impl<Ecal: EcalHandler> MemoryClient<Ecal> {
     /// Since the memory storage is `Infallible`, associatively, the memory
     /// client should also be.
     pub fn transact(&mut self, tx: Checked<Script>) -> &[Receipt] {
+        self.open_checkpoint();
         self.transactor.transact(tx);
 
         // TODO `Transactor::result` should accept error as generic so compile-time
@@ -118,6 +132,7 @@ impl<Ecal: EcalHandler> MemoryClient<Ecal> {
                 self.transactor.as_mut().revert();
             } else {
                 self.transactor.as_mut().commit();
+                self.transactor.as_mut().flatten();
             }
         } else {
             // if vm failed to execute, revert storage just in case
@@ -127,6 +142,16 @@ impl<Ecal: EcalHandler> MemoryClient<Ecal> {
         self.transactor.receipts().unwrap_or_default()
     }
 
+    /// Opens the checkpoint of the changes not transacted yet, if it isn't open. The
+    /// changes of the other transactions are kept or discarded together with the
+    /// next executed script.
+    fn open_checkpoint(&mut self) {
+        let storage = self.transactor.as_mut();
+        if storage.checkpoints() == 0 {
+            storage.begin();
+        }
+    }
+
     /// Persist the changes caused by [`Self::transact`].
     pub fn persist(&mut self) {
         self.as_mut().persist();
@@ -0,0 +1,185 @@
+//! Counts of the memory accesses of the executions, bucketed by page and call frame.
+//!
+//! A [`MemoryHeatMap`] attached with
+//! [`Interpreter::with_memory_heat_map`](crate::interpreter::Interpreter::with_memory_heat_map)
+//! records every read and write of the memory of the VM, accumulated over the executed
+//! transactions. An access spanning several pages counts once in each page, with the
+//! bytes of the page. Without the `memory-heat-map` feature, the recording is compiled
+//! out of the memory.
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+};
+use core::fmt;
+
+use fuel_types::ContractId;
+
+/// The call frame making the accesses.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HeatMapFrame {
+    /// The number of call frames, zero for the script or the predicate.
+    pub depth: usize,
+    /// The called contract, `None` for the script or the predicate.
+    pub contract: Option<ContractId>,
+}
+
+/// The accesses to a page.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageHeat {
+    /// The number of reads.
+    pub reads: u64,
+    /// The number of bytes read.
+    pub read_bytes: u64,
+    /// The number of writes.
+    pub writes: u64,
+    /// The number of bytes written.
+    pub write_bytes: u64,
+}
+
+impl PageHeat {
+    /// The number of reads and writes.
+    pub const fn accesses(&self) -> u64 {
+        self.reads.saturating_add(self.writes)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.reads = self.reads.saturating_add(other.reads);
+        self.read_bytes = self.read_bytes.saturating_add(other.read_bytes);
+        self.writes = self.writes.saturating_add(other.writes);
+        self.write_bytes = self.write_bytes.saturating_add(other.write_bytes);
+    }
+}
+
+/// The accesses of a call frame to a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeatMapBucket {
+    /// The frame making the accesses.
+    pub frame: HeatMapFrame,
+    /// The address of the first byte of the page.
+    pub page: usize,
+    /// The accesses to the page.
+    pub heat: PageHeat,
+}
+
+/// The kind of a memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MemoryAccess {
+    Read,
+    Write,
+}
+
+/// The memory accesses bucketed by page of [`Self::page_size`] bytes and by call
+/// frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryHeatMap {
+    page_size: usize,
+    frame: HeatMapFrame,
+    buckets: BTreeMap<(HeatMapFrame, usize), PageHeat>,
+}
+
+impl Default for MemoryHeatMap {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_PAGE_SIZE)
+    }
+}
+
+impl MemoryHeatMap {
+    /// The default size of a page, 16 KiB.
+    pub const DEFAULT_PAGE_SIZE: usize = 16 * 1024;
+
+    /// Buckets the accesses by pages of `page_size` bytes, at least one.
+    pub fn new(page_size: usize) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            frame: HeatMapFrame::default(),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// The size of the pages, in bytes.
+    pub const fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// The accessed pages of each frame, ordered by frame and by address.
+    pub fn buckets(&self) -> impl Iterator<Item = HeatMapBucket> + '_ {
+        self.buckets
+            .iter()
+            .map(|(&(frame, page), &heat)| HeatMapBucket { frame, page, heat })
+    }
+
+    /// The accesses of all the frames to each accessed page, by address.
+    pub fn pages(&self) -> BTreeMap<usize, PageHeat> {
+        let mut pages = BTreeMap::<usize, PageHeat>::new();
+        for ((_, page), heat) in &self.buckets {
+            pages.entry(*page).or_default().merge(heat);
+        }
+        pages
+    }
+
+    /// Forgets the recorded accesses.
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+    }
+
+    /// Writes a CSV line for each bucket, after a header line, in the order of
+    /// [`Self::buckets`]. The contract of the script is empty.
+    pub fn write_csv<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        writeln!(
+            w,
+            "depth,contract,page_start,page_end,reads,read_bytes,writes,write_bytes"
+        )?;
+        for HeatMapBucket { frame, page, heat } in self.buckets() {
+            write!(w, "{},", frame.depth)?;
+            if let Some(contract) = frame.contract {
+                write!(w, "{contract}")?;
+            }
+            writeln!(
+                w,
+                ",{page},{},{},{},{},{}",
+                page.saturating_add(self.page_size),
+                heat.reads,
+                heat.read_bytes,
+                heat.writes,
+                heat.write_bytes
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The CSV of [`Self::write_csv`].
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        self.write_csv(&mut csv)
+            .expect("Writing into a string doesn't fail");
+        csv
+    }
+
+    /// Attributes the next accesses to the `frame`.
+    pub(crate) fn set_frame(&mut self, frame: HeatMapFrame) {
+        self.frame = frame;
+    }
+
+    /// Counts an access of `len` bytes at `start`, once in each page it spans.
+    pub(crate) fn record(&mut self, access: MemoryAccess, start: usize, len: usize) {
+        let end = start.saturating_add(len);
+        let mut page = start - start % self.page_size;
+        while page < end {
+            let next = page.saturating_add(self.page_size);
+            let bytes = (end.min(next) - start.max(page)) as u64;
+            let heat = self.buckets.entry((self.frame, page)).or_default();
+            match access {
+                MemoryAccess::Read => {
+                    heat.reads = heat.reads.saturating_add(1);
+                    heat.read_bytes = heat.read_bytes.saturating_add(bytes);
+                }
+                MemoryAccess::Write => {
+                    heat.writes = heat.writes.saturating_add(1);
+                    heat.write_bytes = heat.write_bytes.saturating_add(bytes);
+                }
+            }
+            page = next;
+        }
+    }
+}
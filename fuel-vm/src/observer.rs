@@ -0,0 +1,61 @@
+//! Hook observing each instruction executed by the interpreter.
+//!
+//! An [`ExecutionObserver`] attached with
+//! [`Interpreter::with_observer`](crate::interpreter::Interpreter::with_observer) is
+//! notified after every dispatched instruction with the registers before and after
+//! it. The observer is shared by the clones of the interpreter, so it keeps its state
+//! behind its own synchronization.
+
+use core::fmt;
+
+use fuel_asm::{
+    Opcode,
+    RawInstruction,
+    RegId,
+};
+use fuel_types::Word;
+
+use crate::constraints::InstructionLocation;
+
+/// An instruction executed by the interpreter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutedStep<'a> {
+    /// The location of the instruction.
+    pub location: InstructionLocation,
+    /// The executed instruction.
+    pub instruction: RawInstruction,
+    /// The registers before the instruction.
+    pub before: &'a [Word],
+    /// The registers after the instruction, or after its panic.
+    pub after: &'a [Word],
+}
+
+impl ExecutedStep<'_> {
+    /// The opcode of the instruction, `None` for an invalid instruction.
+    pub fn opcode(&self) -> Option<Opcode> {
+        Opcode::try_from(self.instruction.to_be_bytes()[0]).ok()
+    }
+
+    /// The gas remaining in the transaction after the instruction.
+    pub fn gas(&self) -> Word {
+        self.after[RegId::GGAS.to_u8() as usize]
+    }
+
+    /// The registers changed by the instruction with their new value, except the
+    /// `$pc` and the gas registers, which change at every instruction.
+    pub fn written_registers(&self) -> impl Iterator<Item = (RegId, Word)> + '_ {
+        (0u8..)
+            .zip(self.before.iter().zip(self.after))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(register, (_, after))| (RegId::new(register), *after))
+            .filter(|(register, _)| {
+                ![RegId::PC, RegId::CGAS, RegId::GGAS].contains(register)
+            })
+    }
+}
+
+/// Observer of the instructions executed by the interpreter.
+pub trait ExecutionObserver: fmt::Debug + Send + Sync {
+    /// Called after the execution of each instruction, including the failed ones.
+    fn on_step(&self, step: &ExecutedStep<'_>);
+}
@@ -239,9 +239,9 @@ mod tests {
                 Ok(()),
             ),
             (
-                // A valid predicate, but gas amount mismatches
+                // A valid predicate, using less than the declared gas
                 predicate_input!(vec![op::ret(0x01),]),
-                Err(PredicateVerificationFailed::GasMismatch),
+                Ok(()),
             ),
             (
                 // Returning an invalid value
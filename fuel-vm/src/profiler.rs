@@ -2,6 +2,7 @@
 
 use alloc::{
     boxed::Box,
+    collections::BTreeMap,
     format,
     string::{
         String,
@@ -9,14 +10,25 @@ use alloc::{
     },
     vec::Vec,
 };
-use core::fmt;
+use core::{
+    cmp::Ordering,
+    fmt,
+};
 use hashbrown::HashMap;
 
 use dyn_clone::DynClone;
 
-use fuel_types::ContractId;
+use fuel_asm::Opcode;
+use fuel_tx::GasCosts;
+use fuel_types::{
+    ContractId,
+    Word,
+};
 
-use crate::prelude::*;
+use crate::{
+    prelude::*,
+    source_map::SourceMap,
+};
 
 pub use crate::constraints::InstructionLocation;
 
@@ -77,23 +89,6 @@ impl<'de> serde::Deserialize<'de> for InstructionLocation {
     }
 }
 
-impl InstructionLocation {
-    /// New location from context and offset
-    pub const fn new(context: Option<ContractId>, offset: u64) -> Self {
-        Self { context, offset }
-    }
-
-    /// Context, i.e. current contract
-    pub const fn context(&self) -> Option<ContractId> {
-        self.context
-    }
-
-    /// Offset from the IS register
-    pub const fn offset(&self) -> u64 {
-        self.offset
-    }
-}
-
 impl fmt::Display for InstructionLocation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -183,6 +178,11 @@ pub struct Profiler {
     receiver: Option<Box<dyn ProfileReceiver + Send + Sync>>,
     /// Collected profiling data
     data: ProfilingData,
+    /// Locations of the calls of the current call chain, from the script
+    call_stack: Vec<InstructionLocation>,
+    /// The opcode of the current instruction, with the units charged so far
+    #[cfg(feature = "profile-gas")]
+    current_opcode: Option<(Opcode, Word)>,
 }
 
 impl Profiler {
@@ -191,9 +191,17 @@ impl Profiler {
         &mut self,
         state_result: Result<&ProgramState, InterpreterError<String>>,
     ) {
+        #[cfg(feature = "profile-gas")]
+        {
+            self.end_opcode();
+            let transaction = core::mem::take(&mut self.data.current_transaction);
+            self.data.transactions.push(transaction);
+        }
         if let Some(r) = &mut self.receiver {
             r.on_transaction(state_result, &self.data);
         }
+        // The calls not returned from were ended by a revert or a panic
+        self.call_stack.clear();
     }
 
     /// Sets profiling data receiver
@@ -221,6 +229,100 @@ impl Profiler {
     /// Add gas to the current coverage location.
     pub fn add_gas(&mut self, location: InstructionLocation, gas_use: u64) {
         self.data_mut().gas_mut().add(location, gas_use);
+        #[cfg(feature = "profile-gas")]
+        {
+            let transaction = &mut self.data.current_transaction;
+            transaction.gas = transaction.gas.saturating_add(gas_use);
+            self.call_stack.push(location);
+            self.data.stacks.sample(&self.call_stack).gas += gas_use;
+            self.call_stack.pop();
+        }
+    }
+
+    /// Count the execution of the instruction at the location.
+    #[allow(unused_variables)]
+    pub fn on_instruction(&mut self, location: InstructionLocation) {
+        #[cfg(feature = "profile-gas")]
+        {
+            self.call_stack.push(location);
+            self.data.stacks.sample(&self.call_stack).instructions += 1;
+            self.call_stack.pop();
+        }
+    }
+
+    /// Enter the frame of the contract called at the location.
+    pub fn on_call(&mut self, location: InstructionLocation) {
+        self.call_stack.push(location);
+    }
+
+    /// Return from the frame of the current contract.
+    pub fn on_return(&mut self) {
+        self.call_stack.pop();
+    }
+
+    /// Folded stacks of the gas used per call chain, for `flamegraph.pl` or `inferno`
+    #[cfg(feature = "profile-gas")]
+    pub fn folded_stacks(&self) -> String {
+        self.data.stacks().folded(StackMetric::Gas, None)
+    }
+
+    /// Count the execution of the opcode of the current instruction.
+    #[cfg(feature = "profile-gas")]
+    pub fn on_opcode(&mut self, opcode: Opcode) {
+        self.end_opcode();
+        self.current_opcode = Some((opcode, 0));
+    }
+
+    /// Add the units of the dependent cost charged for the current instruction, like
+    /// the bytes copied by `MCP`.
+    #[cfg(feature = "profile-gas")]
+    pub fn add_units(&mut self, units: Word) {
+        if let Some((_, current_units)) = &mut self.current_opcode {
+            *current_units = current_units.saturating_add(units);
+        }
+    }
+
+    #[cfg(feature = "profile-gas")]
+    fn end_opcode(&mut self) {
+        if let Some((opcode, units)) = self.current_opcode.take() {
+            self.data.current_transaction.opcodes.add(opcode, units);
+        }
+    }
+
+    /// The gas of the profiled transactions under the `old` and the `new` gas costs,
+    /// computed from the executed opcodes with [`GasCosts::charge_for`] without
+    /// re-executing the transactions.
+    ///
+    /// The gas recorded for a transaction is re-priced by the difference of the costs
+    /// of its opcodes, the rest of it, like the storage of new bytes, being kept.
+    #[cfg(feature = "profile-gas")]
+    pub fn sensitivity(&self, old: &GasCosts, new: &GasCosts) -> SensitivityReport {
+        let mut opcodes = HashMap::<Opcode, GasShift>::new();
+        let transactions = self
+            .data
+            .transactions()
+            .iter()
+            .map(|transaction| {
+                let mut shift = GasShift {
+                    old: transaction.gas,
+                    new: transaction.gas,
+                };
+                for (opcode, _) in transaction.opcodes.iter() {
+                    let old_gas = transaction.opcodes.gas(old, opcode);
+                    let new_gas = transaction.opcodes.gas(new, opcode);
+                    shift.new = shift.new.saturating_sub(old_gas).saturating_add(new_gas);
+                    let total = opcodes.entry(opcode).or_default();
+                    total.old = total.old.saturating_add(old_gas);
+                    total.new = total.new.saturating_add(new_gas);
+                }
+                shift
+            })
+            .collect();
+
+        SensitivityReport {
+            transactions,
+            opcodes: opcodes.into_iter().collect(),
+        }
     }
 }
 
@@ -247,6 +349,15 @@ pub struct ProfilingData {
     coverage: CoverageProfilingData,
     #[cfg(feature = "profile-gas")]
     gas: GasProfilingData,
+    #[cfg(feature = "profile-gas")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stacks: StackProfilingData,
+    #[cfg(feature = "profile-gas")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    transactions: Vec<TransactionProfile>,
+    #[cfg(feature = "profile-gas")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    current_transaction: TransactionProfile,
 }
 
 impl ProfilingData {
@@ -256,12 +367,24 @@ impl ProfilingData {
         &self.gas
     }
 
+    /// Call chain profiling info, immutable
+    #[cfg(feature = "profile-gas")]
+    pub fn stacks(&self) -> &StackProfilingData {
+        &self.stacks
+    }
+
     /// Gas profiling info, mutable
     #[cfg(feature = "profile-gas")]
     pub fn gas_mut(&mut self) -> &mut GasProfilingData {
         &mut self.gas
     }
 
+    /// The opcodes executed by each profiled transaction
+    #[cfg(feature = "profile-gas")]
+    pub fn transactions(&self) -> &[TransactionProfile] {
+        &self.transactions
+    }
+
     /// Coverage profiling info, immutable
     #[cfg(feature = "profile-coverage")]
     pub fn coverage(&self) -> &CoverageProfilingData {
@@ -340,6 +463,61 @@ impl<'a> GasProfilingData {
     pub fn values(&'a self) -> PerLocationValues<'a, u64> {
         PerLocationValues(self.gas_use.values())
     }
+
+    /// Aggregate the gas used by the function of each location in the `source_map`
+    pub fn by_function<'m>(&self, source_map: &'m SourceMap) -> GasPerFunction<'m> {
+        let mut per_function = GasPerFunction::default();
+        for (location, gas) in self.iter() {
+            match source_map.get(location) {
+                Some(source) => {
+                    *per_function
+                        .gas_use
+                        .entry(source.function.as_str())
+                        .or_insert(0) += gas
+                }
+                None => per_function.unmapped += gas,
+            }
+        }
+        per_function
+    }
+}
+
+/// Used gas per function of a source map
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasPerFunction<'m> {
+    gas_use: BTreeMap<&'m str, u64>,
+    unmapped: u64,
+}
+
+impl<'m> GasPerFunction<'m> {
+    /// Get total gas used by the function
+    pub fn get(&self, function: &str) -> u64 {
+        self.gas_use.get(function).copied().unwrap_or(0)
+    }
+
+    /// Gas used at the locations missing from the source map
+    pub const fn unmapped(&self) -> u64 {
+        self.unmapped
+    }
+
+    /// Iterate through functions and gas values, sorted by function
+    pub fn iter(&self) -> impl Iterator<Item = (&'m str, u64)> + '_ {
+        self.gas_use.iter().map(|(function, gas)| (*function, *gas))
+    }
+}
+
+impl fmt::Display for GasPerFunction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut items: Vec<(_, _)> = self.iter().collect();
+        items.sort_by(|(a, a_gas), (b, b_gas)| b_gas.cmp(a_gas).then(a.cmp(b)));
+        for (function, gas) in items {
+            writeln!(f, "{function}: {gas}")?;
+        }
+        if self.unmapped > 0 {
+            writeln!(f, "<unknown>: {}", self.unmapped)?;
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for GasProfilingData {
@@ -352,3 +530,289 @@ impl fmt::Display for GasProfilingData {
         Ok(())
     }
 }
+
+/// Quantity counted by the folded stacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackMetric {
+    /// Gas used
+    Gas,
+    /// Executed instructions
+    Instructions,
+}
+
+/// Gas used and instructions executed at a location of a call chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StackSample {
+    /// Gas used
+    pub gas: u64,
+    /// Executed instructions
+    pub instructions: u64,
+}
+
+impl StackSample {
+    /// The counted quantity
+    pub const fn get(&self, metric: StackMetric) -> u64 {
+        match metric {
+            StackMetric::Gas => self.gas,
+            StackMetric::Instructions => self.instructions,
+        }
+    }
+}
+
+/// Samples per call chain: the locations of the calls from the script, followed by
+/// the location in the called contract
+#[derive(Debug, Clone, Default)]
+pub struct StackProfilingData {
+    samples: HashMap<Vec<InstructionLocation>, StackSample>,
+}
+
+impl StackProfilingData {
+    fn sample(&mut self, stack: &[InstructionLocation]) -> &mut StackSample {
+        if !self.samples.contains_key(stack) {
+            self.samples.insert(stack.to_vec(), StackSample::default());
+        }
+        self.samples.get_mut(stack).expect("Inserted above")
+    }
+
+    /// Get the sample of the call chain
+    pub fn get(&self, stack: &[InstructionLocation]) -> StackSample {
+        self.samples.get(stack).copied().unwrap_or_default()
+    }
+
+    /// Iterate through call chains and samples
+    pub fn iter(&self) -> impl Iterator<Item = (&[InstructionLocation], StackSample)> {
+        self.samples
+            .iter()
+            .map(|(stack, sample)| (stack.as_slice(), *sample))
+    }
+
+    /// Folded stacks, one `frame;frame;frame count` line per call chain sorted by
+    /// frames. The frames are the script and the called contracts, or their functions
+    /// at the locations in the `source_map`.
+    pub fn folded(&self, metric: StackMetric, source_map: Option<&SourceMap>) -> String {
+        let frame = |location: &InstructionLocation| match source_map
+            .and_then(|source_map| source_map.get(location))
+        {
+            Some(source) => source.function.clone(),
+            None => location
+                .context
+                .map(|contract_id| contract_id.to_string())
+                .unwrap_or_else(|| "script".to_string()),
+        };
+        let mut folded = BTreeMap::<String, u64>::new();
+        for (stack, sample) in self.iter() {
+            let frames = stack.iter().map(frame).collect::<Vec<_>>().join(";");
+            *folded.entry(frames).or_insert(0) += sample.get(metric);
+        }
+        folded
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(frames, count)| format!("{frames} {count}\n"))
+            .collect()
+    }
+}
+
+/// The opcodes executed by a transaction and the gas recorded for its instructions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionProfile {
+    gas: Word,
+    opcodes: OpcodeProfilingData,
+}
+
+impl TransactionProfile {
+    /// The gas recorded for the instructions of the transaction
+    pub const fn gas(&self) -> Word {
+        self.gas
+    }
+
+    /// The executed opcodes
+    pub const fn opcodes(&self) -> &OpcodeProfilingData {
+        &self.opcodes
+    }
+}
+
+/// Executions of each opcode, by the units of the dependent cost charged for them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpcodeProfilingData {
+    executions: HashMap<Opcode, BTreeMap<Word, u64>>,
+}
+
+impl OpcodeProfilingData {
+    /// Count an execution of the opcode charged for `units` units
+    pub fn add(&mut self, opcode: Opcode, units: Word) {
+        *self
+            .executions
+            .entry(opcode)
+            .or_default()
+            .entry(units)
+            .or_insert(0) += 1;
+    }
+
+    /// Get the number of executions of the opcode
+    pub fn count(&self, opcode: Opcode) -> u64 {
+        self.units_with_count(opcode).map(|(_, count)| count).sum()
+    }
+
+    /// Get the total units charged for the executions of the opcode, saturating at
+    /// [`Word::MAX`]
+    pub fn units(&self, opcode: Opcode) -> Word {
+        self.units_with_count(opcode)
+            .map(|(units, count)| units.saturating_mul(count))
+            .fold(0, Word::saturating_add)
+    }
+
+    /// The gas charged for the executions of the opcode with the `gas_costs`, each
+    /// execution resolving its own units like the interpreter, saturating at
+    /// [`Word::MAX`]
+    pub fn gas(&self, gas_costs: &GasCosts, opcode: Opcode) -> Word {
+        self.units_with_count(opcode)
+            .map(|(units, count)| {
+                gas_costs
+                    .charge_for(opcode, units)
+                    .unwrap_or(0)
+                    .saturating_mul(count)
+            })
+            .fold(0, Word::saturating_add)
+    }
+
+    /// Iterate through the executed opcodes and their number of executions
+    pub fn iter(&self) -> impl Iterator<Item = (Opcode, u64)> + '_ {
+        self.executions
+            .keys()
+            .map(|opcode| (*opcode, self.count(*opcode)))
+    }
+
+    fn units_with_count(&self, opcode: Opcode) -> impl Iterator<Item = (Word, u64)> + '_ {
+        self.executions
+            .get(&opcode)
+            .into_iter()
+            .flatten()
+            .map(|(units, count)| (*units, *count))
+    }
+}
+
+/// The gas under the old and the new gas costs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasShift {
+    /// Gas with the old gas costs
+    pub old: Word,
+    /// Gas with the new gas costs
+    pub new: Word,
+}
+
+impl GasShift {
+    /// The change of the gas, negative if the new gas costs are cheaper
+    pub const fn delta(&self) -> i128 {
+        self.new as i128 - self.old as i128
+    }
+
+    /// The change of the gas relative to the old gas, `None` if the old gas is zero
+    pub fn relative(&self) -> Option<f64> {
+        (self.old > 0).then(|| self.delta() as f64 / self.old as f64)
+    }
+
+    /// Compares the `|delta| / old` of the shifts, a zero old gas being the largest
+    fn cmp_relative(&self, other: &Self) -> Ordering {
+        let (delta, other_delta) =
+            (self.delta().unsigned_abs(), other.delta().unsigned_abs());
+        match (self.old, other.old) {
+            (0, 0) => delta.cmp(&other_delta),
+            (0, _) => Ordering::Greater,
+            (_, 0) => Ordering::Less,
+            (old, other_old) => {
+                (delta * other_old as u128).cmp(&(other_delta * old as u128))
+            }
+        }
+    }
+}
+
+/// The gas of the profiled transactions and of their opcodes under two gas costs,
+/// see [`Profiler::sensitivity`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SensitivityReport {
+    transactions: Vec<GasShift>,
+    opcodes: Vec<(Opcode, GasShift)>,
+}
+
+impl SensitivityReport {
+    /// The gas of each profiled transaction, in the order of their execution
+    pub fn transactions(&self) -> &[GasShift] {
+        &self.transactions
+    }
+
+    /// The gas of all the profiled transactions
+    pub fn total(&self) -> GasShift {
+        self.transactions
+            .iter()
+            .fold(GasShift::default(), |total, shift| GasShift {
+                old: total.old.saturating_add(shift.old),
+                new: total.new.saturating_add(shift.new),
+            })
+    }
+
+    /// The gas of the executions of the opcode in all the profiled transactions
+    pub fn opcode(&self, opcode: Opcode) -> GasShift {
+        self.opcodes
+            .iter()
+            .find(|(o, _)| *o == opcode)
+            .map(|(_, shift)| *shift)
+            .unwrap_or_default()
+    }
+
+    /// The opcodes whose gas changed, by largest absolute change first
+    pub fn absolute_movers(&self) -> Vec<(Opcode, GasShift)> {
+        let mut movers = self.movers();
+        movers.sort_by(|(a, a_shift), (b, b_shift)| {
+            b_shift
+                .delta()
+                .unsigned_abs()
+                .cmp(&a_shift.delta().unsigned_abs())
+                .then((*a as u8).cmp(&(*b as u8)))
+        });
+        movers
+    }
+
+    /// The opcodes whose gas changed, by largest change relative to their old gas
+    /// first
+    pub fn relative_movers(&self) -> Vec<(Opcode, GasShift)> {
+        let mut movers = self.movers();
+        movers.sort_by(|(a, a_shift), (b, b_shift)| {
+            b_shift
+                .cmp_relative(a_shift)
+                .then((*a as u8).cmp(&(*b as u8)))
+        });
+        movers
+    }
+
+    fn movers(&self) -> Vec<(Opcode, GasShift)> {
+        self.opcodes
+            .iter()
+            .filter(|(_, shift)| shift.delta() != 0)
+            .copied()
+            .collect()
+    }
+}
+
+impl fmt::Display for SensitivityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        writeln!(
+            f,
+            "total: {} -> {} ({:+})",
+            total.old,
+            total.new,
+            total.delta()
+        )?;
+        for (opcode, shift) in self.absolute_movers() {
+            writeln!(
+                f,
+                "{}: {} -> {} ({:+})",
+                opcode.mnemonic(),
+                shift.old,
+                shift.new,
+                shift.delta()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//! Hook receiving the receipts of the interpreter as they are produced.
+//!
+//! A [`ReceiptSink`] attached with
+//! [`Interpreter::with_receipt_sink`](crate::interpreter::Interpreter::with_receipt_sink)
+//! is notified of each receipt pushed by the executed transaction, before the
+//! execution returns. Like the [observer](crate::observer::ExecutionObserver), the sink
+//! is shared by the clones of the interpreter, so it keeps its state behind its own
+//! synchronization.
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use fuel_tx::Receipt;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+};
+
+#[cfg(feature = "structured-logs")]
+pub mod structured_log;
+
+#[cfg(feature = "structured-logs")]
+pub use structured_log::{
+    LogField,
+    StructuredLogSink,
+};
+
+/// The transaction producing a receipt.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReceiptContext {
+    /// The id of the executed transaction.
+    pub tx_id: Bytes32,
+    /// The height of the block of a script, `None` for a predicate.
+    pub block_height: Option<BlockHeight>,
+}
+
+/// Receiver of the receipts produced by the interpreter.
+pub trait ReceiptSink: fmt::Debug + Send + Sync {
+    /// Called after each receipt is pushed, in the order of the receipts.
+    ///
+    /// The receipts changed in place afterwards, like when a diff of the interpreter
+    /// is inverted, aren't notified again.
+    fn on_receipt(&self, context: &ReceiptContext, receipt: &Receipt);
+}
+
+impl<T> ReceiptSink for Arc<T>
+where
+    T: ReceiptSink + ?Sized,
+{
+    fn on_receipt(&self, context: &ReceiptContext, receipt: &Receipt) {
+        T::on_receipt(self, context, receipt)
+    }
+}
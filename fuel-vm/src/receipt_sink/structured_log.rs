@@ -0,0 +1,279 @@
+//! Receipt sink writing the logs and the panics as JSON lines, for a log pipeline:
+//!
+//! ```text
+//! {"tx_id":"4e1f..","block_height":7,"receipt":"log","id":null,"ra":42,..,"pc":10368}
+//! {"tx_id":"4e1f..","block_height":7,"receipt":"panic","reason":"MemoryOverflow",..}
+//! ```
+//!
+//! The ids and the data are rendered in lowercase hex and the registers as numbers.
+//! The names of the fields are configured with [`StructuredLogSink::rename`] and
+//! [`StructuredLogSink::omit`].
+
+use std::{
+    collections::BTreeMap,
+    fmt::{
+        self,
+        Write as _,
+    },
+    io::Write,
+    string::{
+        String,
+        ToString,
+    },
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Mutex,
+        PoisonError,
+    },
+    vec::Vec,
+};
+
+use fuel_tx::Receipt;
+use fuel_types::Word;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::{
+    ReceiptContext,
+    ReceiptSink,
+};
+
+/// A field of the lines written by the [`StructuredLogSink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogField {
+    /// The id of the transaction, `tx_id`.
+    TxId,
+    /// The height of the block, `null` for a predicate, `block_height`.
+    BlockHeight,
+    /// The kind of the receipt, `log`, `log_data` or `panic`, `receipt`.
+    Receipt,
+    /// The id of the contract, `null` for the script, `id`.
+    Id,
+    /// The `$ra` of a log, `ra`.
+    Ra,
+    /// The `$rb` of a log, `rb`.
+    Rb,
+    /// The `$rc` of a log, `rc`.
+    Rc,
+    /// The `$rd` of a log, `rd`.
+    Rd,
+    /// The address of the logged data, `ptr`.
+    Ptr,
+    /// The length of the logged data, before the truncation, `len`.
+    Len,
+    /// The hash of the logged data, `digest`.
+    Digest,
+    /// The logged data, truncated to the limit of the sink, `data`.
+    Data,
+    /// Whether the logged data was truncated, `truncated`.
+    Truncated,
+    /// The reason of a panic, `reason`.
+    Reason,
+    /// The contract missing in the inputs for a panic, `contract_id`.
+    ContractId,
+    /// The program counter, `pc`.
+    Pc,
+    /// The start of the executed code, `is`.
+    Is,
+}
+
+impl LogField {
+    /// The name of the field, unless renamed.
+    pub const fn default_name(&self) -> &'static str {
+        match self {
+            Self::TxId => "tx_id",
+            Self::BlockHeight => "block_height",
+            Self::Receipt => "receipt",
+            Self::Id => "id",
+            Self::Ra => "ra",
+            Self::Rb => "rb",
+            Self::Rc => "rc",
+            Self::Rd => "rd",
+            Self::Ptr => "ptr",
+            Self::Len => "len",
+            Self::Digest => "digest",
+            Self::Data => "data",
+            Self::Truncated => "truncated",
+            Self::Reason => "reason",
+            Self::ContractId => "contract_id",
+            Self::Pc => "pc",
+            Self::Is => "is",
+        }
+    }
+}
+
+/// Writes a JSON line for each `Log`, `LogData` and `Panic` receipt as soon as it is
+/// pushed, ignoring the other receipts.
+///
+/// The sink never fails the execution: a line the writer fails to write, or to flush,
+/// is dropped and counted by [`Self::dropped`].
+pub struct StructuredLogSink<W> {
+    writer: Mutex<W>,
+    names: BTreeMap<LogField, Option<String>>,
+    max_data_len: usize,
+    dropped: AtomicU64,
+}
+
+impl<W> StructuredLogSink<W> {
+    /// The default limit of the logged data written in a line, in bytes.
+    pub const DEFAULT_MAX_DATA_LEN: usize = 1024;
+
+    /// Writes the lines into the `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            names: BTreeMap::new(),
+            max_data_len: Self::DEFAULT_MAX_DATA_LEN,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Names the `field` with `name` in the lines.
+    pub fn rename(mut self, field: LogField, name: impl Into<String>) -> Self {
+        self.names.insert(field, Some(name.into()));
+        self
+    }
+
+    /// Leaves the `field` out of the lines.
+    pub fn omit(mut self, field: LogField) -> Self {
+        self.names.insert(field, None);
+        self
+    }
+
+    /// Truncates the logged data to its first `max_data_len` bytes.
+    pub fn max_data_len(mut self, max_data_len: usize) -> Self {
+        self.max_data_len = max_data_len;
+        self
+    }
+
+    /// The number of lines dropped after an error of the writer.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Consumes the sink, returning its writer.
+    pub fn into_writer(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn name(&self, field: LogField) -> Option<&str> {
+        match self.names.get(&field) {
+            Some(name) => name.as_deref(),
+            None => Some(field.default_name()),
+        }
+    }
+
+    /// The fields of the line of the `receipt`, `None` for the ignored receipts.
+    fn line(
+        &self,
+        context: &ReceiptContext,
+        receipt: &Receipt,
+    ) -> Option<Map<String, Value>> {
+        let kind = match receipt {
+            Receipt::Log { .. } => "log",
+            Receipt::LogData { .. } => "log_data",
+            Receipt::Panic { .. } => "panic",
+            _ => return None,
+        };
+
+        let mut line = Map::new();
+        let mut insert = |field: LogField, value: Value| {
+            if let Some(name) = self.name(field) {
+                line.insert(name.to_string(), value);
+            }
+        };
+        let word = |word: Option<Word>| word.map_or(Value::Null, Value::from);
+        let text = |text: Option<String>| text.map_or(Value::Null, Value::String);
+
+        insert(LogField::TxId, Value::String(context.tx_id.to_string()));
+        insert(
+            LogField::BlockHeight,
+            context
+                .block_height
+                .map_or(Value::Null, |height| Value::from(*height)),
+        );
+        insert(LogField::Receipt, Value::from(kind));
+        insert(LogField::Id, text(receipt.id().map(ToString::to_string)));
+        match receipt {
+            Receipt::Log { ra, rb, rc, rd, .. } => {
+                insert(LogField::Ra, Value::from(*ra));
+                insert(LogField::Rb, Value::from(*rb));
+                insert(LogField::Rc, Value::from(*rc));
+                insert(LogField::Rd, Value::from(*rd));
+            }
+            Receipt::LogData { ra, rb, digest, .. } => {
+                let data = receipt.data().unwrap_or_default();
+                let truncated = data.len() > self.max_data_len;
+                let data = &data[..data.len().min(self.max_data_len)];
+                insert(LogField::Ra, Value::from(*ra));
+                insert(LogField::Rb, Value::from(*rb));
+                insert(LogField::Ptr, word(receipt.ptr()));
+                insert(LogField::Len, word(receipt.len()));
+                insert(LogField::Digest, Value::String(digest.to_string()));
+                insert(LogField::Data, Value::String(hex(data)));
+                insert(LogField::Truncated, Value::Bool(truncated));
+            }
+            Receipt::Panic {
+                reason,
+                contract_id,
+                ..
+            } => {
+                insert(LogField::Reason, Value::String(reason.reason().to_string()));
+                insert(
+                    LogField::ContractId,
+                    text(contract_id.as_ref().map(ToString::to_string)),
+                );
+            }
+            _ => {}
+        }
+        insert(LogField::Pc, word(receipt.pc()));
+        insert(LogField::Is, word(receipt.is()));
+        Some(line)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+impl<W> ReceiptSink for StructuredLogSink<W>
+where
+    W: Write + Send,
+{
+    fn on_receipt(&self, context: &ReceiptContext, receipt: &Receipt) {
+        let Some(line) = self.line(context, receipt) else {
+            return;
+        };
+        let mut bytes: Vec<u8> = Value::Object(line).to_string().into_bytes();
+        bytes.push(b'\n');
+
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        if writer
+            .write_all(&bytes)
+            .and_then(|()| writer.flush())
+            .is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<W> fmt::Debug for StructuredLogSink<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuredLogSink")
+            .field("names", &self.names)
+            .field("max_data_len", &self.max_data_len)
+            .field("dropped", &self.dropped())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,346 @@
+//! Interactive session executing the instructions entered one at a time.
+//!
+//! A [`ReplSession`] keeps an interpreter running a script made of a synthetic region
+//! of [`SCRIPT_CAPACITY`] `noop`s. Each entered instruction is written at `$pc` and
+//! executed, so the registers, memory and storage carry across the instructions. An
+//! instruction that panics is reported and rolled back.
+//!
+//! The lines starting with `:` are commands:
+//!
+//! - `:regs` shows the registers.
+//! - `:mem <addr> <len>` shows the bytes of the memory.
+//! - `:receipts` shows the receipts.
+//! - `:reset` restarts the session.
+//! - `:load <file>` executes the instructions of the file.
+
+use alloc::{
+    boxed::Box,
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+use std::{
+    fs,
+    io,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    ParseError,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    ReceiptFormatter,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::IntoChecked,
+    constraints::InstructionLocation,
+    error::InterpreterError,
+    interpreter::{
+        Interpreter,
+        InterpreterParams,
+    },
+    observer::ExecutedStep,
+    state::ExecuteState,
+    storage::MemoryStorage,
+};
+
+/// The number of instructions of the synthetic script region.
+pub const SCRIPT_CAPACITY: usize = 1024;
+
+/// Failure of an input of the session.
+#[derive(Debug, derive_more::Display)]
+pub enum ReplError {
+    /// The input isn't a valid instruction.
+    #[display(fmt = "Invalid instruction: {_0}")]
+    Parse(ParseError),
+    /// The command doesn't exist.
+    #[display(fmt = "Unknown command `{_0}`")]
+    UnknownCommand(String),
+    /// The arguments of the command are invalid.
+    #[display(fmt = "Usage: {_0}")]
+    InvalidArguments(&'static str),
+    /// The memory range can't be read.
+    #[display(fmt = "Invalid memory range: {_0}")]
+    Memory(PanicReason),
+    /// `$pc` left the synthetic script region, after a jump or once the region is
+    /// full.
+    #[display(fmt = "$pc {_0:#x} is outside of the script, use `:reset`")]
+    OutOfScript(Word),
+    /// The instruction failed and was rolled back.
+    #[display(fmt = "{instruction:?} rolled back: {error}")]
+    RolledBack {
+        /// The failed instruction.
+        instruction: Instruction,
+        /// The failure of the instruction.
+        error: InterpreterError<core::convert::Infallible>,
+    },
+    /// The file can't be read.
+    #[display(fmt = "{_0}")]
+    Io(io::Error),
+    /// A line of the loaded file failed.
+    #[display(fmt = "Line {line}: {error}")]
+    Load {
+        /// The number of the line, starting from `1`.
+        line: usize,
+        /// The failure of the line.
+        error: Box<ReplError>,
+    },
+}
+
+/// The result of an input of the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplOutput {
+    /// The input is empty.
+    Nothing,
+    /// The instruction was executed.
+    Step {
+        /// The executed instruction.
+        instruction: Instruction,
+        /// The registers written by the instruction with their new value.
+        written: Vec<(RegId, Word)>,
+        /// The state of the interpreter after the instruction.
+        state: ExecuteState,
+    },
+    /// The registers, for `:regs`.
+    Registers(Vec<Word>),
+    /// The memory from the `address`, for `:mem`.
+    Memory {
+        /// The address of the first byte.
+        address: Word,
+        /// The bytes of the memory.
+        bytes: Vec<u8>,
+    },
+    /// The receipts of the session, for `:receipts`.
+    Receipts(Vec<Receipt>),
+    /// The session was restarted, for `:reset`.
+    Reset,
+    /// The instructions of the file were executed, for `:load`.
+    Loaded {
+        /// The number of executed instructions.
+        steps: usize,
+    },
+}
+
+impl fmt::Display for ReplOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nothing | Self::Reset => Ok(()),
+            Self::Step { written, state, .. } => {
+                for (register, value) in written {
+                    writeln!(f, "{register} = {value:#x}")?;
+                }
+                match state {
+                    ExecuteState::Proceed => Ok(()),
+                    state => writeln!(f, "{state:?}"),
+                }
+            }
+            Self::Registers(registers) => {
+                for (register, value) in (0u8..).zip(registers) {
+                    let register = RegId::new(register).to_string();
+                    writeln!(f, "{register:<6} {value:#018x}")?;
+                }
+                Ok(())
+            }
+            Self::Memory { address, bytes } => {
+                for (address, row) in (*address..).step_by(16).zip(bytes.chunks(16)) {
+                    write!(f, "{address:#010x} ")?;
+                    for byte in row {
+                        write!(f, " {byte:02x}")?;
+                    }
+                    writeln!(f)?;
+                }
+                Ok(())
+            }
+            Self::Receipts(receipts) => {
+                write!(f, "{}", ReceiptFormatter::new().display(receipts))
+            }
+            Self::Loaded { steps } => writeln!(f, "Executed {steps} instructions"),
+        }
+    }
+}
+
+/// Session executing the entered instructions on a persistent interpreter.
+#[derive(Debug, Clone)]
+pub struct ReplSession {
+    vm: Interpreter<MemoryStorage, Script>,
+    consensus_params: ConsensusParameters,
+    program: Vec<Instruction>,
+}
+
+impl Default for ReplSession {
+    fn default() -> Self {
+        Self::new(ConsensusParameters::standard())
+    }
+}
+
+impl ReplSession {
+    /// New session over an empty memory storage.
+    pub fn new(consensus_params: ConsensusParameters) -> Self {
+        let script = vec![op::noop(); SCRIPT_CAPACITY].into_iter().collect();
+        // Half of the maximal gas leaves room for the gas of the transaction bytes.
+        let gas_limit = consensus_params.tx_params().max_gas_per_tx() / 2;
+        let tx = TransactionBuilder::script(script, vec![])
+            .script_gas_limit(gas_limit)
+            .add_random_fee_input()
+            .finalize()
+            .into_checked(Default::default(), &consensus_params)
+            .expect("The synthetic script is valid")
+            .into_ready(
+                0,
+                consensus_params.gas_costs(),
+                consensus_params.fee_params(),
+            )
+            .expect("The synthetic script is free");
+
+        let mut vm = Interpreter::with_storage(
+            MemoryStorage::default(),
+            InterpreterParams::new(0, &consensus_params),
+        );
+        vm.init_script(tx)
+            .expect("The synthetic script is initialized");
+
+        Self {
+            vm,
+            consensus_params,
+            program: Vec::new(),
+        }
+    }
+
+    /// The interpreter of the session.
+    pub const fn interpreter(&self) -> &Interpreter<MemoryStorage, Script> {
+        &self.vm
+    }
+
+    /// The successfully executed instructions, in their order.
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    /// Restarts the session with a new interpreter.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.consensus_params.clone());
+    }
+
+    /// Writes the `instruction` at `$pc` and executes it, rolling back the interpreter
+    /// if it fails.
+    pub fn step(&mut self, instruction: Instruction) -> Result<ReplOutput, ReplError> {
+        let pc = self.vm.registers()[RegId::PC];
+        let start = self.vm.registers()[RegId::IS];
+        let end = start + (SCRIPT_CAPACITY * Instruction::SIZE) as Word;
+        if !(start..end).contains(&pc) {
+            return Err(ReplError::OutOfScript(pc))
+        }
+
+        let snapshot = self.vm.clone();
+        self.vm
+            .memory_mut()
+            .write_bytes_noownerchecks(pc, instruction.to_bytes())
+            .expect("The script is in the memory");
+        match self.vm.execute() {
+            Ok(state) => {
+                self.program.push(instruction);
+                let written = ExecutedStep {
+                    location: InstructionLocation::new(None, pc - start),
+                    instruction: instruction.into(),
+                    before: snapshot.registers(),
+                    after: self.vm.registers(),
+                }
+                .written_registers()
+                .collect();
+                Ok(ReplOutput::Step {
+                    instruction,
+                    written,
+                    state,
+                })
+            }
+            Err(error) => {
+                self.vm = snapshot;
+                Err(ReplError::RolledBack { instruction, error })
+            }
+        }
+    }
+
+    /// Evaluates a line: a command when it starts with `:`, otherwise an instruction.
+    pub fn eval(&mut self, line: &str) -> Result<ReplOutput, ReplError> {
+        let line = line.trim();
+        let Some(command) = line.strip_prefix(':') else {
+            return match line.parse() {
+                Ok(instruction) => self.step(instruction),
+                Err(ParseError::Empty) => Ok(ReplOutput::Nothing),
+                Err(error) => Err(ReplError::Parse(error)),
+            }
+        };
+
+        let mut args = command.split_whitespace();
+        match args.next().unwrap_or_default() {
+            "regs" => Ok(ReplOutput::Registers(self.vm.registers().to_vec())),
+            "mem" => {
+                const USAGE: &str = ":mem <addr> <len>";
+                let mut number = || {
+                    args.next()
+                        .and_then(parse_number)
+                        .ok_or(ReplError::InvalidArguments(USAGE))
+                };
+                let address = number()?;
+                let len = number()?;
+                let bytes = self
+                    .vm
+                    .memory()
+                    .read(address, len)
+                    .map_err(ReplError::Memory)?;
+                Ok(ReplOutput::Memory {
+                    address,
+                    bytes: bytes.to_vec(),
+                })
+            }
+            "receipts" => Ok(ReplOutput::Receipts(self.vm.receipts().to_vec())),
+            "reset" => {
+                self.reset();
+                Ok(ReplOutput::Reset)
+            }
+            "load" => {
+                let path = args
+                    .next()
+                    .ok_or(ReplError::InvalidArguments(":load <file>"))?;
+                let program = fs::read_to_string(path).map_err(ReplError::Io)?;
+                self.load(&program)
+            }
+            command => Err(ReplError::UnknownCommand(command.into())),
+        }
+    }
+
+    /// Executes the instructions of the `program`, one per line, stopping at the first
+    /// failed line.
+    pub fn load(&mut self, program: &str) -> Result<ReplOutput, ReplError> {
+        let mut steps = 0;
+        for (index, instruction) in fuel_asm::parse_lines(program) {
+            instruction
+                .map_err(ReplError::Parse)
+                .and_then(|instruction| self.step(instruction))
+                .map_err(|error| ReplError::Load {
+                    line: index + 1,
+                    error: Box::new(error),
+                })?;
+            steps += 1;
+        }
+        Ok(ReplOutput::Loaded { steps })
+    }
+}
+
+fn parse_number(token: &str) -> Option<Word> {
+    match token.strip_prefix("0x") {
+        Some(hex) => Word::from_str_radix(hex, 16).ok(),
+        None => token.parse().ok(),
+    }
+}
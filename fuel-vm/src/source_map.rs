@@ -0,0 +1,250 @@
+//! Source maps, linking the instructions of the scripts and the contracts to the
+//! locations of their source code.
+//!
+//! The interpreter never consults them during the execution: they only render the
+//! [backtraces](crate::backtrace::Backtrace::display), the gas profiles and the listings
+//! of the bytecode with the functions and the lines of the source code.
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    ops::Range,
+};
+
+use fuel_asm::Instruction;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use crate::constraints::InstructionLocation;
+
+/// A location in the source code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceLocation {
+    /// The path of the source file.
+    pub file: String,
+    /// The line in the file, from 1.
+    pub line: u32,
+    /// The column in the line, from 1.
+    pub column: u32,
+    /// The name of the function.
+    pub function: String,
+}
+
+impl SourceLocation {
+    /// New location of the `function`, at the `line` and `column` of the `file`.
+    pub fn new(
+        file: impl Into<String>,
+        line: u32,
+        column: u32,
+        function: impl Into<String>,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            line,
+            column,
+            function: function.into(),
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}:{}",
+            self.function, self.file, self.line, self.column
+        )
+    }
+}
+
+/// The error of the construction or the decoding of a [`SourceMap`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum SourceMapError {
+    /// The range of offsets has no instruction.
+    #[display(fmt = "The range of offsets {_0:?} is empty")]
+    EmptyRange(Range<Word>),
+    /// The range of offsets overlaps with the range of another entry of the context.
+    #[display(fmt = "The range of offsets {_0:?} overlaps with another entry")]
+    OverlappingRange(Range<Word>),
+    /// The bytes aren't an encoded source map.
+    #[display(fmt = "Invalid encoding of the source map")]
+    InvalidEncoding,
+}
+
+/// The location in the source code of a range of the bytecode of a script or a
+/// contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SourceMapEntry {
+    context: Option<ContractId>,
+    start: Word,
+    end: Word,
+    location: SourceLocation,
+}
+
+/// Map from the ranges of the bytecode of the scripts and the contracts to the
+/// locations of their source code, like the debug info emitted by the compilers.
+///
+/// The ranges are [`InstructionLocation`] offsets, in bytes from the start of the
+/// script or of the contract code. The script uses the `None` context.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The entries sorted by context and by offset, without overlaps in a context.
+    entries: Vec<SourceMapEntry>,
+}
+
+impl SourceMap {
+    /// New empty source map.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Maps the `range` of offsets of the bytecode of the `context` to the `location`.
+    pub fn insert(
+        &mut self,
+        context: Option<ContractId>,
+        range: Range<Word>,
+        location: SourceLocation,
+    ) -> Result<(), SourceMapError> {
+        if range.is_empty() {
+            return Err(SourceMapError::EmptyRange(range))
+        }
+        let index = self.entries.partition_point(|entry| {
+            (entry.context, entry.start) < (context, range.start)
+        });
+        let overlaps_previous = index
+            .checked_sub(1)
+            .map(|previous| &self.entries[previous])
+            .is_some_and(|previous| {
+                previous.context == context && previous.end > range.start
+            });
+        let overlaps_next = self
+            .entries
+            .get(index)
+            .is_some_and(|next| next.context == context && next.start < range.end);
+        if overlaps_previous || overlaps_next {
+            return Err(SourceMapError::OverlappingRange(range))
+        }
+        self.entries.insert(
+            index,
+            SourceMapEntry {
+                context,
+                start: range.start,
+                end: range.end,
+                location,
+            },
+        );
+        Ok(())
+    }
+
+    /// The location in the source code of the instruction at the `location`.
+    pub fn get(&self, location: &InstructionLocation) -> Option<&SourceLocation> {
+        let key = (location.context(), location.offset());
+        let index = self
+            .entries
+            .partition_point(|entry| (entry.context, entry.start) <= key);
+        let entry = &self.entries[index.checked_sub(1)?];
+        (entry.context == key.0 && key.1 < entry.end).then_some(&entry.location)
+    }
+
+    /// The number of mapped ranges.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map has no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the mapped ranges of each context and their locations, sorted by
+    /// context and by offset.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (Option<ContractId>, Range<Word>, &SourceLocation)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.context, entry.start..entry.end, &entry.location))
+    }
+
+    /// Renders the instructions of the `bytecode` of the `context` with their offsets,
+    /// grouped by function and annotated with their location in the source code.
+    pub fn listing<'a>(
+        &'a self,
+        context: Option<ContractId>,
+        bytecode: &'a [u8],
+    ) -> Listing<'a> {
+        Listing {
+            source_map: self,
+            context,
+            bytecode,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl SourceMap {
+    /// Decodes the source map encoded with [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SourceMapError> {
+        let entries: Vec<SourceMapEntry> =
+            postcard::from_bytes(bytes).map_err(|_| SourceMapError::InvalidEncoding)?;
+        let mut source_map = Self::new();
+        for entry in entries {
+            source_map.insert(entry.context, entry.start..entry.end, entry.location)?;
+        }
+        Ok(source_map)
+    }
+
+    /// Encodes the source map in a compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        postcard::to_allocvec(&self.entries).expect("Encoding to a vector can't fail")
+    }
+}
+
+/// The listing of a bytecode, rendered by [`SourceMap::listing`].
+pub struct Listing<'a> {
+    source_map: &'a SourceMap,
+    context: Option<ContractId>,
+    bytecode: &'a [u8],
+}
+
+impl fmt::Display for Listing<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut function = None;
+        for (index, bytes) in self.bytecode.chunks(Instruction::SIZE).enumerate() {
+            let offset = (index * Instruction::SIZE) as Word;
+            let location = self
+                .source_map
+                .get(&InstructionLocation::new(self.context, offset));
+            let current = location.map(|location| location.function.as_str());
+            if current != function {
+                writeln!(f, "{}:", current.unwrap_or("<unknown>"))?;
+                function = current;
+            }
+            let instruction = <[u8; Instruction::SIZE]>::try_from(bytes)
+                .ok()
+                .and_then(|bytes| Instruction::try_from(bytes).ok());
+            match instruction {
+                Some(instruction) => write!(f, "{offset:>8}: {instruction:?}")?,
+                None => write!(f, "{offset:>8}: <invalid {bytes:02x?}>")?,
+            }
+            match location {
+                Some(location) => writeln!(
+                    f,
+                    " ; {}:{}:{}",
+                    location.file, location.line, location.column
+                )?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
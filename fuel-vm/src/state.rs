@@ -14,7 +14,15 @@ mod debugger;
 
 pub use debug::{
     Breakpoint,
+    BreakpointCondition,
+    Comparison,
+    DebugCommand,
+    DebugEdit,
+    DebugEditError,
     DebugEval,
+    WatchError,
+    WatchExpression,
+    WatchValue,
 };
 
 pub use debugger::Debugger;
@@ -1,9 +1,26 @@
-use fuel_asm::Instruction;
+use alloc::{
+    boxed::Box,
+    vec::Vec,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Not,
+    Sub,
+};
+
+use fuel_asm::{
+    Instruction,
+    PanicReason,
+    RegId,
+};
 use fuel_types::{
     ContractId,
     Word,
 };
 
+use crate::interpreter::Memory;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Breakpoint description that binds a tuple `(contract, $pc)` to a debugger
@@ -54,6 +71,307 @@ impl Breakpoint {
     }
 }
 
+/// Command resuming the execution paused at a debug event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DebugCommand {
+    /// Run until the next breakpoint.
+    Continue,
+    /// Break at the next instruction, in the called contract for a `CALL`.
+    Step,
+    /// Break at the next instruction at the same call depth, executing the whole
+    /// called contract for a `CALL`.
+    StepOver,
+    /// Break at the next instruction after the current call frame returns. From the
+    /// script, run until the next breakpoint.
+    StepOut,
+}
+
+/// Comparison of a value of the interpreter with a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// The value is equal to the constant.
+    Eq,
+    /// The value is different from the constant.
+    Ne,
+    /// The value is less than the constant.
+    Lt,
+    /// The value is less than or equal to the constant.
+    Le,
+    /// The value is greater than the constant.
+    Gt,
+    /// The value is greater than or equal to the constant.
+    Ge,
+}
+
+impl Comparison {
+    /// Compare the `value` with the `constant`.
+    pub const fn compare(&self, value: Word, constant: Word) -> bool {
+        match self {
+            Self::Eq => value == constant,
+            Self::Ne => value != constant,
+            Self::Lt => value < constant,
+            Self::Le => value <= constant,
+            Self::Gt => value > constant,
+            Self::Ge => value >= constant,
+        }
+    }
+}
+
+/// Condition of a [`Breakpoint`], evaluated when the execution reaches it: the
+/// execution breaks only if the condition holds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BreakpointCondition {
+    /// Compares the value of a register.
+    Register {
+        /// The compared register.
+        register: RegId,
+        /// The comparison of the register with the `value`.
+        comparison: Comparison,
+        /// The constant compared with the register.
+        value: Word,
+    },
+    /// Compares the word of the memory at the value of a register plus an offset. The
+    /// condition doesn't hold if the word isn't in the memory.
+    Memory {
+        /// The register of the base address of the word.
+        register: RegId,
+        /// The offset of the word from the base address.
+        offset: Word,
+        /// The comparison of the word with the `value`.
+        comparison: Comparison,
+        /// The constant compared with the word.
+        value: Word,
+    },
+    /// Both conditions hold.
+    And(Box<Self>, Box<Self>),
+    /// At least one of the conditions holds.
+    Or(Box<Self>, Box<Self>),
+    /// The condition doesn't hold.
+    Not(Box<Self>),
+}
+
+impl BreakpointCondition {
+    /// Condition comparing the value of the `register` with the `value`.
+    pub const fn register(register: RegId, comparison: Comparison, value: Word) -> Self {
+        Self::Register {
+            register,
+            comparison,
+            value,
+        }
+    }
+
+    /// Condition comparing the word of the memory at the value of the `register` plus
+    /// the `offset` with the `value`.
+    pub const fn memory(
+        register: RegId,
+        offset: Word,
+        comparison: Comparison,
+        value: Word,
+    ) -> Self {
+        Self::Memory {
+            register,
+            offset,
+            comparison,
+            value,
+        }
+    }
+
+    /// Condition holding when both this condition and the `other` hold.
+    pub fn and(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    /// Condition holding when this condition or the `other` holds.
+    pub fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Evaluate the condition with the `registers` and the `memory` of the
+    /// interpreter.
+    pub fn eval(&self, registers: &[Word], memory: &Memory) -> bool {
+        match self {
+            Self::Register {
+                register,
+                comparison,
+                value,
+            } => comparison.compare(registers[*register], *value),
+            Self::Memory {
+                register,
+                offset,
+                comparison,
+                value,
+            } => registers[*register]
+                .checked_add(*offset)
+                .and_then(|address| read_word(memory, address).ok())
+                .is_some_and(|word| comparison.compare(word, *value)),
+            Self::And(a, b) => a.eval(registers, memory) && b.eval(registers, memory),
+            Self::Or(a, b) => a.eval(registers, memory) || b.eval(registers, memory),
+            Self::Not(condition) => !condition.eval(registers, memory),
+        }
+    }
+}
+
+impl Not for BreakpointCondition {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// Reads the word of the `memory` at the `address`.
+fn read_word(memory: &Memory, address: Word) -> Result<Word, WatchError> {
+    memory
+        .read_bytes(address)
+        .map(Word::from_be_bytes)
+        .map_err(|_| WatchError::InvalidMemory(address))
+}
+
+/// Expression over the registers and the memory of the interpreter, evaluated at each
+/// pause of the execution by the [`Debugger`](crate::state::Debugger).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WatchExpression {
+    /// A constant.
+    Constant(Word),
+    /// The value of a register.
+    Register(RegId),
+    /// The word of the memory at the address of the expression.
+    Memory(Box<Self>),
+    /// The sum of both expressions.
+    Add(Box<Self>, Box<Self>),
+    /// The difference of both expressions.
+    Sub(Box<Self>, Box<Self>),
+    /// The product of both expressions.
+    Mul(Box<Self>, Box<Self>),
+    /// `1` if the comparison of both expressions holds, `0` otherwise.
+    Compare(Box<Self>, Comparison, Box<Self>),
+}
+
+/// Failure of the evaluation of a [`WatchExpression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WatchError {
+    /// The word at the address isn't in the memory.
+    InvalidMemory(Word),
+    /// The arithmetic overflowed.
+    Overflow,
+}
+
+/// The value of a [`WatchExpression`].
+pub type WatchValue = Result<Word, WatchError>;
+
+impl WatchExpression {
+    /// Expression of the value of the `register`.
+    pub const fn register(register: RegId) -> Self {
+        Self::Register(register)
+    }
+
+    /// Expression of the word of the memory at the address of this expression.
+    pub fn word_at(self) -> Self {
+        Self::Memory(Box::new(self))
+    }
+
+    /// Expression comparing this expression with the `other`.
+    pub fn compare(self, comparison: Comparison, other: Self) -> Self {
+        Self::Compare(Box::new(self), comparison, Box::new(other))
+    }
+
+    /// Evaluate the expression with the `registers` and the `memory` of the
+    /// interpreter.
+    pub fn eval(&self, registers: &[Word], memory: &Memory) -> WatchValue {
+        let arithmetic = |a: &Self, b: &Self, op: fn(Word, Word) -> Option<Word>| {
+            op(a.eval(registers, memory)?, b.eval(registers, memory)?)
+                .ok_or(WatchError::Overflow)
+        };
+        match self {
+            Self::Constant(value) => Ok(*value),
+            Self::Register(register) => Ok(registers[*register]),
+            Self::Memory(address) => read_word(memory, address.eval(registers, memory)?),
+            Self::Add(a, b) => arithmetic(a, b, Word::checked_add),
+            Self::Sub(a, b) => arithmetic(a, b, Word::checked_sub),
+            Self::Mul(a, b) => arithmetic(a, b, Word::checked_mul),
+            Self::Compare(a, comparison, b) => Ok(comparison
+                .compare(a.eval(registers, memory)?, b.eval(registers, memory)?)
+                as Word),
+        }
+    }
+}
+
+impl Add for WatchExpression {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::Add(Box::new(self), Box::new(other))
+    }
+}
+
+impl Sub for WatchExpression {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::Sub(Box::new(self), Box::new(other))
+    }
+}
+
+impl Mul for WatchExpression {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::Mul(Box::new(self), Box::new(other))
+    }
+}
+
+impl From<Word> for WatchExpression {
+    fn from(value: Word) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl From<RegId> for WatchExpression {
+    fn from(register: RegId) -> Self {
+        Self::Register(register)
+    }
+}
+
+/// Edit of the state of an execution paused at a debug event, recorded by the
+/// [`Debugger`](crate::state::Debugger) to reproduce the session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DebugEdit {
+    /// A register was set.
+    Register {
+        /// The set register.
+        register: RegId,
+        /// The new value of the register.
+        value: Word,
+        /// Whether the register is reserved, i.e. below [`RegId::WRITABLE`].
+        forced: bool,
+    },
+    /// Bytes of the memory were overwritten.
+    Memory {
+        /// The address of the first written byte.
+        address: Word,
+        /// The written bytes.
+        bytes: Vec<u8>,
+        /// Whether the ownership of the memory wasn't checked.
+        unchecked: bool,
+    },
+}
+
+/// Failure of an edit of the state of the execution by the debugger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum DebugEditError {
+    /// The execution isn't paused at a debug event.
+    #[display(fmt = "The execution isn't paused at a debug event")]
+    NotSuspended,
+    /// The register is reserved and the edit isn't forced.
+    #[display(fmt = "The register {_0} is reserved")]
+    ReservedRegister(RegId),
+    /// The memory can't be written, like by the VM.
+    #[display(fmt = "The memory can't be written: {_0:?}")]
+    Memory(PanicReason),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// State evaluation of the interpreter that will describe if a program should
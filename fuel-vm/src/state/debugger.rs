@@ -1,18 +1,51 @@
-use crate::state::{
-    Breakpoint,
-    DebugEval,
-    ProgramState,
+use crate::{
+    interpreter::Memory,
+    state::{
+        Breakpoint,
+        BreakpointCondition,
+        DebugCommand,
+        DebugEdit,
+        DebugEval,
+        ProgramState,
+        WatchExpression,
+        WatchValue,
+    },
 };
 
+use alloc::vec::Vec;
+
 use fuel_types::{
     ContractId,
     Word,
 };
 
-use hashbrown::{
-    HashMap,
-    HashSet,
-};
+use hashbrown::HashMap;
+
+/// The condition and the hit counter of a breakpoint.
+#[derive(Debug, Default, Clone)]
+struct BreakpointState {
+    condition: Option<BreakpointCondition>,
+    hits: u64,
+}
+
+/// The instructions breaking the execution after a step command, like temporary
+/// breakpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepTarget {
+    /// Any instruction.
+    Next,
+    /// The instructions at most at the call depth.
+    MaxDepth(usize),
+}
+
+impl StepTarget {
+    const fn matches(&self, depth: usize) -> bool {
+        match self {
+            Self::Next => true,
+            Self::MaxDepth(max_depth) => depth <= *max_depth,
+        }
+    }
+}
 
 /// Debugger implementation for the VM.
 #[derive(Debug, Default, Clone)]
@@ -21,8 +54,12 @@ pub struct Debugger {
     is_active: bool,
     /// Single-stepping mode triggers a breakpoint after each instruction
     single_stepping: bool,
-    breakpoints: HashMap<ContractId, HashSet<Word>>,
+    breakpoints: HashMap<ContractId, HashMap<Word, BreakpointState>>,
+    step: Option<StepTarget>,
     last_state: Option<ProgramState>,
+    watches: Vec<WatchExpression>,
+    watch_values: Vec<WatchValue>,
+    edits: Vec<DebugEdit>,
 }
 
 impl Debugger {
@@ -42,28 +79,47 @@ impl Debugger {
         self.single_stepping = single_stepping;
     }
 
+    /// Set the command of the next resumption of the execution, paused at the call
+    /// `depth`, i.e. the number of call frames.
+    ///
+    /// The step commands break once, at the next instruction they target or at the
+    /// first breakpoint before it.
+    pub fn set_command(&mut self, command: DebugCommand, depth: usize) {
+        self.is_active = true;
+        self.step = match command {
+            DebugCommand::Continue => None,
+            DebugCommand::Step => Some(StepTarget::Next),
+            DebugCommand::StepOver => Some(StepTarget::MaxDepth(depth)),
+            DebugCommand::StepOut => depth.checked_sub(1).map(StepTarget::MaxDepth),
+        };
+    }
+
     /// Set a new breakpoint in the provided location.
     pub fn clear_breakpoints(&mut self) {
         self.breakpoints.clear();
     }
 
     /// Set a new breakpoint in the provided location.
+    ///
+    /// The condition and the hit counter of an existing breakpoint are kept.
     pub fn set_breakpoint(&mut self, breakpoint: Breakpoint) {
         self.is_active = true;
-        let contract = *breakpoint.contract();
-        let pc = breakpoint.pc();
-
         self.breakpoints
-            .get_mut(&contract)
-            .map(|set| set.insert(pc))
-            .map(|_| ())
-            .unwrap_or_else(|| {
-                let mut set = HashSet::new();
-
-                set.insert(pc);
+            .entry(*breakpoint.contract())
+            .or_default()
+            .entry(breakpoint.pc())
+            .or_default();
+    }
 
-                self.breakpoints.insert(contract, set);
-            });
+    /// Set a new breakpoint in the provided location, breaking only when the
+    /// condition holds.
+    pub fn set_conditional_breakpoint(
+        &mut self,
+        breakpoint: Breakpoint,
+        condition: BreakpointCondition,
+    ) {
+        self.set_breakpoint(breakpoint);
+        self.attach_condition(&breakpoint, condition);
     }
 
     /// Remove a breakpoint, if existent.
@@ -71,33 +127,175 @@ impl Debugger {
         self.is_active = true;
         self.breakpoints
             .get_mut(breakpoint.contract())
-            .map(|set| set.remove(&breakpoint.pc()));
+            .map(|breakpoints| breakpoints.remove(&breakpoint.pc()));
+    }
+
+    /// Attach a condition to an existing breakpoint, replacing its previous
+    /// condition. Return `false` if the breakpoint isn't set.
+    pub fn attach_condition(
+        &mut self,
+        breakpoint: &Breakpoint,
+        condition: BreakpointCondition,
+    ) -> bool {
+        self.breakpoint_mut(breakpoint)
+            .map(|state| state.condition = Some(condition))
+            .is_some()
+    }
+
+    /// Detach the condition of a breakpoint, returning it. The breakpoint breaks
+    /// unconditionally afterwards.
+    pub fn detach_condition(
+        &mut self,
+        breakpoint: &Breakpoint,
+    ) -> Option<BreakpointCondition> {
+        self.breakpoint_mut(breakpoint)
+            .and_then(|state| state.condition.take())
+    }
+
+    /// Condition of a breakpoint, if any.
+    pub fn breakpoint_condition(
+        &self,
+        breakpoint: &Breakpoint,
+    ) -> Option<&BreakpointCondition> {
+        self.breakpoint(breakpoint)
+            .and_then(|state| state.condition.as_ref())
+    }
+
+    /// Number of times a breakpoint broke the execution; return `None` if the
+    /// breakpoint isn't set.
+    pub fn breakpoint_hits(&self, breakpoint: &Breakpoint) -> Option<u64> {
+        self.breakpoint(breakpoint).map(|state| state.hits)
+    }
+
+    fn breakpoint(&self, breakpoint: &Breakpoint) -> Option<&BreakpointState> {
+        self.breakpoints
+            .get(breakpoint.contract())
+            .and_then(|breakpoints| breakpoints.get(&breakpoint.pc()))
+    }
+
+    fn breakpoint_mut(
+        &mut self,
+        breakpoint: &Breakpoint,
+    ) -> Option<&mut BreakpointState> {
+        self.breakpoints
+            .get_mut(breakpoint.contract())
+            .and_then(|breakpoints| breakpoints.get_mut(&breakpoint.pc()))
+    }
+
+    /// Add an expression evaluated at each pause of the execution, returning its
+    /// index in the [`Self::watch_values`].
+    pub fn add_watch(&mut self, expression: WatchExpression) -> usize {
+        self.is_active = true;
+        self.watches.push(expression);
+        self.watches.len() - 1
+    }
+
+    /// Remove the expression at the `index`, returning it. The indices of the
+    /// following expressions are shifted down.
+    pub fn remove_watch(&mut self, index: usize) -> Option<WatchExpression> {
+        (index < self.watches.len()).then(|| self.watches.remove(index))
+    }
+
+    /// Remove all the watched expressions.
+    pub fn clear_watches(&mut self) {
+        self.watches.clear();
+        self.watch_values.clear();
+    }
+
+    /// The watched expressions.
+    pub fn watches(&self) -> &[WatchExpression] {
+        &self.watches
+    }
+
+    /// The values of the watched expressions at the last pause of the execution, in
+    /// the order of the expressions.
+    pub fn watch_values(&self) -> &[WatchValue] {
+        &self.watch_values
+    }
+
+    /// Returns `true` if the execution is paused at a debug event.
+    pub fn is_suspended(&self) -> bool {
+        self.last_state
+            .as_ref()
+            .is_some_and(|state| state.is_debug())
+    }
+
+    /// Record an edit of the state of the paused execution.
+    pub fn record_edit(&mut self, edit: DebugEdit) {
+        self.edits.push(edit);
+    }
+
+    /// The edits of the state of the execution, in their order.
+    pub fn edits(&self) -> &[DebugEdit] {
+        &self.edits
     }
 
     /// Evaluate the current state of the interpreter whether or not a
     /// breakpoint was reached.
-    pub fn eval_state(&mut self, contract: Option<&ContractId>, pc: Word) -> DebugEval {
+    ///
+    /// The conditions of the breakpoints are evaluated with the `registers` and the
+    /// `memory`, only when the execution reaches them, and the step commands with the
+    /// call `depth`. The watched expressions are evaluated when the execution breaks.
+    pub fn eval_state(
+        &mut self,
+        contract: Option<&ContractId>,
+        pc: Word,
+        depth: usize,
+        registers: &[Word],
+        memory: &Memory,
+    ) -> DebugEval {
         // Default contract address maps to unset contract target
         let contract = contract.copied().unwrap_or_default();
         let last_state = self.last_state.take();
 
         let current = Breakpoint::raw(contract, pc);
 
+        if matches!(last_state, Some(s) if s == current) {
+            return DebugEval::Continue
+        }
+
         if self.single_stepping {
-            return match last_state {
-                Some(s) if s == current => DebugEval::Continue,
-                _ => current.into(),
-            }
+            return self.pause(current, registers, memory)
         }
 
-        self.breakpoints
-            .get(&contract)
-            .and_then(|set| set.get(&pc))
-            .map(|_| match last_state {
-                Some(s) if s == current => DebugEval::Continue,
-                _ => current.into(),
-            })
-            .unwrap_or_default()
+        if self.step.is_some_and(|step| step.matches(depth)) {
+            self.step = None;
+            return self.pause(current, registers, memory)
+        }
+
+        let Some(state) = self
+            .breakpoints
+            .get_mut(&contract)
+            .and_then(|breakpoints| breakpoints.get_mut(&pc))
+        else {
+            return DebugEval::Continue
+        };
+
+        if state
+            .condition
+            .as_ref()
+            .is_some_and(|condition| !condition.eval(registers, memory))
+        {
+            return DebugEval::Continue
+        }
+
+        state.hits = state.hits.saturating_add(1);
+        self.step = None;
+        self.pause(current, registers, memory)
+    }
+
+    fn pause(
+        &mut self,
+        breakpoint: Breakpoint,
+        registers: &[Word],
+        memory: &Memory,
+    ) -> DebugEval {
+        self.watch_values = self
+            .watches
+            .iter()
+            .map(|watch| watch.eval(registers, memory))
+            .collect();
+        breakpoint.into()
     }
 
     /// Overwrite the last known state of the VM.
@@ -7,12 +7,25 @@ use fuel_types::{
     ContractId,
 };
 
+mod cached;
+mod column;
 mod contracts_assets;
 mod contracts_state;
+mod instrumented;
 mod interpreter;
+#[cfg(feature = "serde")]
+mod kv;
 mod memory;
+mod overlay;
 pub(crate) mod predicate;
 
+pub use cached::{
+    CacheCapacity,
+    CacheCounters,
+    CacheMetrics,
+    CachedStorage,
+};
+pub use column::Column;
 pub use contracts_assets::{
     ContractsAssetKey,
     ContractsAssets,
@@ -22,11 +35,43 @@ pub use contracts_state::{
     ContractsStateData,
     ContractsStateKey,
 };
+pub use instrumented::{
+    InstrumentedStorage,
+    OperationMetrics,
+    StorageMetrics,
+};
+pub(crate) use interpreter::{
+    contract_state_batch,
+    contract_state_keys,
+};
 pub use interpreter::{
     ContractsAssetsStorage,
     InterpreterStorage,
 };
-pub use memory::MemoryStorage;
+#[cfg(feature = "serde")]
+pub use kv::{
+    KeyValueStore,
+    KvBatch,
+    KvEntries,
+    KvStoreAdapter,
+    KvStoreError,
+    KvTransaction,
+    PersistentStorage,
+    PersistentStore,
+};
+#[cfg(feature = "redb")]
+pub use kv::RedbStore;
+pub use memory::{
+    DiffEntry,
+    MemoryStorage,
+    StorageDiff,
+    StorageSnapshot,
+    StorageSnapshotV1,
+};
+pub use overlay::{
+    OverlayStorage,
+    StorageChanges,
+};
 pub use predicate::PredicateStorage;
 
 #[cfg(feature = "alloc")]
@@ -0,0 +1,602 @@
+//! Read-through cache of the hot entries of a storage.
+
+use crate::storage::{
+    contract_state_keys,
+    memory::{
+        last_key_of_range,
+        read_range_from,
+    },
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateData,
+    ContractsStateKey,
+    InterpreterStorage,
+    UploadedBytecodes,
+};
+
+use fuel_storage::{
+    BatchValues,
+    Mappable,
+    StorageBatchInspect,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+};
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    ops::RangeInclusive,
+};
+
+/// The hits and the misses of a cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCounters {
+    /// The number of reads served by the cache.
+    pub hits: u64,
+    /// The number of reads served by the wrapped storage.
+    pub misses: u64,
+}
+
+/// The counters of the caches of a [`CachedStorage`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// The counters of the state slots cache.
+    pub state: CacheCounters,
+    /// The counters of the contracts code cache.
+    pub code: CacheCounters,
+    /// The counters of the balances cache.
+    pub balances: CacheCounters,
+}
+
+/// The number of entries kept by each cache of a [`CachedStorage`]. A zero capacity
+/// disables the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCapacity {
+    /// The number of state slots.
+    pub state: usize,
+    /// The number of contracts code.
+    pub code: usize,
+    /// The number of balances.
+    pub balances: usize,
+}
+
+impl Default for CacheCapacity {
+    fn default() -> Self {
+        Self {
+            state: 4096,
+            code: 64,
+            balances: 1024,
+        }
+    }
+}
+
+/// Cache evicting the least recently used key when it is full.
+///
+/// The keys absent from the storage are cached as `None`.
+#[derive(Debug, Clone)]
+struct Lru<K, V> {
+    capacity: usize,
+    /// The cached values with the tick of their last use.
+    entries: BTreeMap<K, (Option<V>, u64)>,
+    /// The cached keys by the tick of their last use.
+    uses: BTreeMap<u64, K>,
+    tick: u64,
+    counters: CacheCounters,
+}
+
+impl<K: Ord + Clone, V> Lru<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: BTreeMap::new(),
+            uses: BTreeMap::new(),
+            tick: 0,
+            counters: Default::default(),
+        }
+    }
+
+    /// The cached value of the `key`, counting a hit or a miss.
+    fn get(&mut self, key: &K) -> Option<Option<&V>> {
+        self.tick = self.tick.wrapping_add(1);
+        match self.entries.get_mut(key) {
+            Some((value, used)) => {
+                self.uses.remove(used);
+                self.uses.insert(self.tick, key.clone());
+                *used = self.tick;
+                self.counters.hits = self.counters.hits.saturating_add(1);
+                Some(value.as_ref())
+            }
+            None => {
+                self.counters.misses = self.counters.misses.saturating_add(1);
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: K, value: Option<V>) {
+        if self.capacity == 0 {
+            return
+        }
+        self.tick = self.tick.wrapping_add(1);
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            self.uses.remove(&used);
+        }
+        self.uses.insert(self.tick, key);
+        while self.entries.len() > self.capacity {
+            let Some((_, evicted)) = self.uses.pop_first() else {
+                break
+            };
+            self.entries.remove(&evicted);
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some((_, used)) = self.entries.remove(key) {
+            self.uses.remove(&used);
+        }
+    }
+
+    fn remove_range(&mut self, range: RangeInclusive<K>) {
+        let keys = self
+            .entries
+            .range(range)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        keys.iter().for_each(|key| self.remove(key));
+    }
+}
+
+/// Storage decorator caching the state slots, the code and the balances of the
+/// contracts read from the wrapped storage, in least recently used caches.
+///
+/// The reads are served by the caches and load the missing entries from the wrapped
+/// storage, including the absent ones. The writes go through to the wrapped storage
+/// and update the caches. The changes made directly to the wrapped storage must be
+/// followed by [`invalidate_contract`](Self::invalidate_contract).
+#[derive(Debug, Clone)]
+pub struct CachedStorage<S> {
+    storage: S,
+    state: RefCell<Lru<ContractsStateKey, ContractsStateData>>,
+    code: RefCell<Lru<ContractId, Contract>>,
+    balances: RefCell<Lru<ContractsAssetKey, Word>>,
+}
+
+impl<S> CachedStorage<S> {
+    /// Wraps the `storage` with empty caches of the `capacity`.
+    pub fn new(storage: S, capacity: CacheCapacity) -> Self {
+        Self {
+            storage,
+            state: RefCell::new(Lru::new(capacity.state)),
+            code: RefCell::new(Lru::new(capacity.code)),
+            balances: RefCell::new(Lru::new(capacity.balances)),
+        }
+    }
+
+    /// The hits and the misses of the caches so far.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            state: self.state.borrow().counters,
+            code: self.code.borrow().counters,
+            balances: self.balances.borrow().counters,
+        }
+    }
+
+    /// Zeroes the hits and the misses of the caches.
+    pub fn reset_metrics(&mut self) {
+        self.state.get_mut().counters = Default::default();
+        self.code.get_mut().counters = Default::default();
+        self.balances.get_mut().counters = Default::default();
+    }
+
+    /// Drops the cached code, state slots and balances of the `contract`, so the next
+    /// reads see the changes made to it directly in the wrapped storage.
+    pub fn invalidate_contract(&mut self, contract: &ContractId) {
+        self.code.get_mut().remove(contract);
+        self.state.get_mut().remove_range(
+            ContractsStateKey::new(contract, &Bytes32::zeroed())
+                ..=ContractsStateKey::new(contract, &Bytes32::new([u8::MAX; 32])),
+        );
+        self.balances.get_mut().remove_range(
+            ContractsAssetKey::new(contract, &[0; 32].into())
+                ..=ContractsAssetKey::new(contract, &[u8::MAX; 32].into()),
+        );
+    }
+
+    /// The wrapped storage.
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// The wrapped storage, mutably. The changes made directly to it aren't seen
+    /// through the caches until the contract is
+    /// [invalidated](Self::invalidate_contract).
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Consumes the decorator and returns the wrapped storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+/// Applies `f` to the cached value of the `key`, loading it with `load` on a miss.
+fn read_through<K, V, E, R>(
+    cache: &RefCell<Lru<K, V>>,
+    key: &K,
+    load: impl FnOnce() -> Result<Option<V>, E>,
+    f: impl FnOnce(Option<&V>) -> R,
+) -> Result<R, E>
+where
+    K: Ord + Clone,
+{
+    let mut cache = cache.borrow_mut();
+    if let Some(value) = cache.get(key) {
+        return Ok(f(value))
+    }
+    let value = load()?;
+    let result = f(value.as_ref());
+    cache.insert(key.clone(), value);
+    Ok(result)
+}
+
+/// Applies the `write` to the wrapped storage and caches the `value` of the `key`
+/// after it. The key is dropped from the cache if the write fails.
+fn write_through<K, V, E, R>(
+    cache: &mut RefCell<Lru<K, V>>,
+    key: &K,
+    value: Option<V>,
+    write: impl FnOnce() -> Result<R, E>,
+) -> Result<R, E>
+where
+    K: Ord + Clone,
+{
+    let cache = cache.get_mut();
+    cache.remove(key);
+    let result = write()?;
+    cache.insert(key.clone(), value);
+    Ok(result)
+}
+
+macro_rules! cached_table {
+    ($table:ident, $cache:ident) => {
+        impl<S: InterpreterStorage> StorageInspect<$table> for CachedStorage<S> {
+            type Error = S::DataError;
+
+            fn get(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Cow<'_, <$table as Mappable>::OwnedValue>>, Self::Error>
+            {
+                read_through(
+                    &self.$cache,
+                    key,
+                    || {
+                        StorageInspect::<$table>::get(&self.storage, key)
+                            .map(|value| value.map(Cow::into_owned))
+                    },
+                    |value| value.cloned().map(Cow::Owned),
+                )
+            }
+
+            fn contains_key(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<bool, Self::Error> {
+                Ok(StorageInspect::<$table>::get(self, key)?.is_some())
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageMutate<$table> for CachedStorage<S> {
+            fn insert(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                value: &<$table as Mappable>::Value,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                let storage = &mut self.storage;
+                write_through(
+                    &mut self.$cache,
+                    key,
+                    Some(value.to_owned().into()),
+                    || StorageMutate::<$table>::insert(storage, key, value),
+                )
+            }
+
+            fn remove(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                let storage = &mut self.storage;
+                write_through(&mut self.$cache, key, None, || {
+                    StorageMutate::<$table>::remove(storage, key)
+                })
+            }
+        }
+    };
+}
+
+macro_rules! cached_bytes_table {
+    ($table:ident, $cache:ident) => {
+        cached_table!($table, $cache);
+
+        impl<S: InterpreterStorage> StorageSize<$table> for CachedStorage<S> {
+            fn size_of_value(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<usize>, Self::Error> {
+                Ok(StorageInspect::<$table>::get(self, key)?
+                    .map(|value| value.as_ref().as_ref().len()))
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageRead<$table> for CachedStorage<S> {
+            fn read(
+                &self,
+                key: &<$table as Mappable>::Key,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                StorageRead::<$table>::read_range(self, key, 0, buf)
+            }
+
+            fn read_alloc(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                Ok(StorageInspect::<$table>::get(self, key)?
+                    .map(|value| value.as_ref().as_ref().to_vec()))
+            }
+
+            fn read_range(
+                &self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                read_through(
+                    &self.$cache,
+                    key,
+                    || {
+                        StorageInspect::<$table>::get(&self.storage, key)
+                            .map(|value| value.map(Cow::into_owned))
+                    },
+                    |value| {
+                        value.map(|value| read_range_from(value.as_ref(), offset, buf))
+                    },
+                )
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageWrite<$table> for CachedStorage<S> {
+            fn write(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                let storage = &mut self.storage;
+                write_through(&mut self.$cache, key, Some(buf.to_vec().into()), || {
+                    StorageWrite::<$table>::write(storage, key, buf)
+                })
+            }
+
+            fn replace(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+                let storage = &mut self.storage;
+                write_through(&mut self.$cache, key, Some(buf.to_vec().into()), || {
+                    StorageWrite::<$table>::replace(storage, key, buf)
+                })
+            }
+
+            fn take(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                let storage = &mut self.storage;
+                write_through(&mut self.$cache, key, None, || {
+                    StorageWrite::<$table>::take(storage, key)
+                })
+            }
+
+            fn write_range(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                // The value after the write isn't known without reading it, so the key is
+                // loaded again by the next read.
+                self.$cache.get_mut().remove(key);
+                StorageWrite::<$table>::write_range(&mut self.storage, key, offset, buf)
+            }
+        }
+    };
+}
+
+cached_bytes_table!(ContractsRawCode, code);
+cached_bytes_table!(ContractsState, state);
+cached_table!(ContractsAssets, balances);
+
+impl<S: InterpreterStorage> StorageInspect<UploadedBytecodes> for CachedStorage<S> {
+    type Error = S::DataError;
+
+    fn get(
+        &self,
+        key: &Bytes32,
+    ) -> Result<Option<Cow<'_, <UploadedBytecodes as Mappable>::OwnedValue>>, Self::Error>
+    {
+        StorageInspect::<UploadedBytecodes>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &Bytes32) -> Result<bool, Self::Error> {
+        StorageInspect::<UploadedBytecodes>::contains_key(&self.storage, key)
+    }
+}
+
+impl<S: InterpreterStorage> StorageMutate<UploadedBytecodes> for CachedStorage<S> {
+    fn insert(
+        &mut self,
+        key: &Bytes32,
+        value: &<UploadedBytecodes as Mappable>::Value,
+    ) -> Result<Option<<UploadedBytecodes as Mappable>::OwnedValue>, Self::Error> {
+        StorageMutate::<UploadedBytecodes>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Bytes32,
+    ) -> Result<Option<<UploadedBytecodes as Mappable>::OwnedValue>, Self::Error> {
+        StorageMutate::<UploadedBytecodes>::remove(&mut self.storage, key)
+    }
+}
+
+impl<S: InterpreterStorage> StorageBatchInspect<ContractsState> for CachedStorage<S> {
+    fn get_batch<'a, 'k, Iter>(
+        &'a self,
+        keys: Iter,
+    ) -> BatchValues<'a, ContractsState, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k ContractsStateKey>,
+    {
+        let mut cache = self.state.borrow_mut();
+        let cached = keys
+            .into_iter()
+            .map(|key| (key, cache.get(key).map(|value| value.cloned())))
+            .collect::<Vec<_>>();
+        // The missing keys are loaded with one batch of the wrapped storage.
+        let mut loaded = StorageBatchInspect::<ContractsState>::get_batch(
+            &self.storage,
+            cached
+                .iter()
+                .filter(|(_, value)| value.is_none())
+                .map(|(key, _)| *key),
+        )
+        .into_iter();
+        cached
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Some(value) => value,
+                    None => {
+                        let value = loaded
+                            .next()
+                            .expect("The wrapped storage returns a value for each key")?
+                            .map(Cow::into_owned);
+                        cache.insert(*key, value.clone());
+                        value
+                    }
+                };
+                Ok(value.map(Cow::Owned))
+            })
+            .collect()
+    }
+}
+
+impl<S: InterpreterStorage> ContractsAssetsStorage for CachedStorage<S> {}
+
+impl<S: InterpreterStorage> InterpreterStorage for CachedStorage<S> {
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        self.storage.consensus_parameters_version()
+    }
+
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        self.storage.state_transition_version()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        self.storage
+            .set_consensus_parameters(version, consensus_parameters)
+    }
+
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        self.storage.set_state_transition_bytecode(version, hash)
+    }
+
+    fn contract_state_insert_range<'a, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let values = values.collect::<Vec<_>>();
+        let keys =
+            contract_state_keys(contract, start_key, values.len()).collect::<Vec<_>>();
+        let cache = self.state.get_mut();
+        keys.iter().for_each(|key| cache.remove(key));
+        let unset_count = self.storage.contract_state_insert_range(
+            contract,
+            start_key,
+            values.iter().copied(),
+        )?;
+        keys.into_iter()
+            .zip(values)
+            .for_each(|(key, value)| cache.insert(key, Some(value.into())));
+        Ok(unset_count)
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        if let Some((last_key, _)) = last_key_of_range(start_key, range) {
+            self.state.get_mut().remove_range(
+                ContractsStateKey::new(contract, start_key)
+                    ..=ContractsStateKey::new(contract, &last_key),
+            );
+        }
+        self.storage
+            .contract_state_remove_range(contract, start_key, range)
+    }
+}
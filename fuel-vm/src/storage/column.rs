@@ -0,0 +1,101 @@
+//! The columns of the tables used by the interpreter.
+
+use crate::storage::{
+    ContractsAssets,
+    ContractsRawCode,
+    ContractsState,
+    UploadedBytecodes,
+};
+
+use fuel_storage::{
+    StorageColumns,
+    TableColumn,
+};
+
+/// The columns of a key-value store holding the tables used by the interpreter.
+///
+/// The tables keyed by a version or a block height use the big-endian bytes of the
+/// number as the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum Column {
+    /// The [`ContractsRawCode`] table.
+    ContractsRawCode = 0,
+    /// The [`ContractsState`] table.
+    ContractsState = 1,
+    /// The [`ContractsAssets`] table.
+    ContractsAssets = 2,
+    /// The [`UploadedBytecodes`] table.
+    UploadedBytecodes = 3,
+    /// The consensus parameters by version.
+    ConsensusParametersVersions = 4,
+    /// The root of the state transition bytecode by version.
+    StateTransitionBytecodeVersions = 5,
+    /// The timestamp of the blocks by height.
+    BlockTimestamps = 6,
+    /// The hash of the blocks by height.
+    BlockHashes = 7,
+    /// The metadata of the current block, keyed by the name of the value: the
+    /// `block_height`, the `coinbase`, the `consensus_parameters_version` and the
+    /// `state_transition_version`.
+    Metadata = 8,
+    /// The nodes of the sparse Merkle trees of the [`ContractsState`], encoded by
+    /// [`encode_node`](fuel_merkle::sparse::encode_node) and keyed by the contract id
+    /// followed by the hash of the node.
+    ContractsStateMerkleData = 9,
+    /// The root of the sparse Merkle tree of the [`ContractsState`] of each contract,
+    /// keyed by the contract id.
+    ContractsStateMerkleMetadata = 10,
+}
+
+impl StorageColumns for Column {
+    const ALL: &'static [Self] = &[
+        Self::ContractsRawCode,
+        Self::ContractsState,
+        Self::ContractsAssets,
+        Self::UploadedBytecodes,
+        Self::ConsensusParametersVersions,
+        Self::StateTransitionBytecodeVersions,
+        Self::BlockTimestamps,
+        Self::BlockHashes,
+        Self::Metadata,
+        Self::ContractsStateMerkleData,
+        Self::ContractsStateMerkleMetadata,
+    ];
+
+    fn id(&self) -> u32 {
+        *self as u32
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ContractsRawCode => "ContractsRawCode",
+            Self::ContractsState => "ContractsState",
+            Self::ContractsAssets => "ContractsAssets",
+            Self::UploadedBytecodes => "UploadedBytecodes",
+            Self::ConsensusParametersVersions => "ConsensusParametersVersions",
+            Self::StateTransitionBytecodeVersions => "StateTransitionBytecodeVersions",
+            Self::BlockTimestamps => "BlockTimestamps",
+            Self::BlockHashes => "BlockHashes",
+            Self::Metadata => "Metadata",
+            Self::ContractsStateMerkleData => "ContractsStateMerkleData",
+            Self::ContractsStateMerkleMetadata => "ContractsStateMerkleMetadata",
+        }
+    }
+}
+
+macro_rules! table_column {
+    ($table:ident) => {
+        impl TableColumn for $table {
+            type Column = Column;
+
+            const COLUMN: Column = Column::$table;
+        }
+    };
+}
+
+table_column!(ContractsRawCode);
+table_column!(ContractsState);
+table_column!(ContractsAssets);
+table_column!(UploadedBytecodes);
@@ -0,0 +1,602 @@
+//! Storage decorator collecting the metrics of the storage calls.
+
+use crate::storage::{
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateData,
+    ContractsStateKey,
+    InterpreterStorage,
+    UploadedBytecode,
+    UploadedBytecodes,
+};
+
+use fuel_storage::{
+    BatchValues,
+    Mappable,
+    StorageBatchInspect,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+};
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+/// The metrics of one storage operation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// The number of calls.
+    pub calls: u64,
+    /// The number of bytes of the keys passed to the calls.
+    pub key_bytes: u64,
+    /// The number of bytes of the values read or written by the calls.
+    pub value_bytes: u64,
+    /// The total time spent in the calls.
+    #[cfg(feature = "std")]
+    pub elapsed: std::time::Duration,
+}
+
+/// The metrics collected by an [`InstrumentedStorage`], by operation.
+///
+/// The operations are named after the table and the method of the storage traits, like
+/// `"ContractsState::get"`, or after the method of the [`InterpreterStorage`], like
+/// `"block_height"`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageMetrics {
+    /// The metrics of the operations called at least once.
+    pub operations: BTreeMap<&'static str, OperationMetrics>,
+}
+
+impl StorageMetrics {
+    /// The metrics of the `operation`, zeroed if it was never called.
+    pub fn operation(&self, operation: &str) -> OperationMetrics {
+        self.operations.get(operation).copied().unwrap_or_default()
+    }
+
+    /// The metrics summed over all the operations.
+    pub fn total(&self) -> OperationMetrics {
+        self.operations
+            .values()
+            .fold(OperationMetrics::default(), |total, metrics| {
+                OperationMetrics {
+                    calls: total.calls.saturating_add(metrics.calls),
+                    key_bytes: total.key_bytes.saturating_add(metrics.key_bytes),
+                    value_bytes: total.value_bytes.saturating_add(metrics.value_bytes),
+                    #[cfg(feature = "std")]
+                    elapsed: total.elapsed.saturating_add(metrics.elapsed),
+                }
+            })
+    }
+}
+
+/// Storage decorator counting the calls, the bytes of the keys and the values and,
+/// with the `std` feature, the time of each operation of the wrapped storage.
+///
+/// With the `tracing` feature, every call also emits a `trace` event with the
+/// `fuel_vm::storage` target.
+#[derive(Debug, Default, Clone)]
+pub struct InstrumentedStorage<S> {
+    storage: S,
+    metrics: RefCell<StorageMetrics>,
+}
+
+impl<S> InstrumentedStorage<S> {
+    /// Wraps the `storage` without any collected metrics.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            metrics: Default::default(),
+        }
+    }
+
+    /// A snapshot of the metrics collected so far.
+    pub fn metrics(&self) -> StorageMetrics {
+        self.metrics.borrow().clone()
+    }
+
+    /// Zeroes the collected metrics.
+    pub fn reset(&mut self) {
+        self.metrics = Default::default();
+    }
+
+    /// The wrapped storage.
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// The wrapped storage, mutably. The calls made directly to it aren't recorded.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Consumes the decorator and returns the wrapped storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+/// Records the `call` of the `operation` into the `metrics`, with the bytes of the
+/// values returned by `value_bytes`.
+fn record<R>(
+    metrics: &RefCell<StorageMetrics>,
+    operation: &'static str,
+    key_bytes: usize,
+    call: impl FnOnce() -> R,
+    value_bytes: impl FnOnce(&R) -> usize,
+) -> R {
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+    let result = call();
+    #[cfg(feature = "std")]
+    let elapsed = start.elapsed();
+    let value_bytes = value_bytes(&result);
+
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        target: "fuel_vm::storage",
+        operation,
+        key_bytes,
+        value_bytes,
+        "storage call"
+    );
+
+    let mut metrics = metrics.borrow_mut();
+    let entry = metrics.operations.entry(operation).or_default();
+    entry.calls = entry.calls.saturating_add(1);
+    entry.key_bytes = entry.key_bytes.saturating_add(key_bytes as u64);
+    entry.value_bytes = entry.value_bytes.saturating_add(value_bytes as u64);
+    #[cfg(feature = "std")]
+    {
+        entry.elapsed = entry.elapsed.saturating_add(elapsed);
+    }
+    result
+}
+
+/// The number of bytes of a value of a table.
+trait ValueBytes {
+    fn value_bytes(&self) -> usize;
+}
+
+impl ValueBytes for [u8] {
+    fn value_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ValueBytes for Contract {
+    fn value_bytes(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+impl ValueBytes for ContractsStateData {
+    fn value_bytes(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+impl ValueBytes for Word {
+    fn value_bytes(&self) -> usize {
+        core::mem::size_of::<Word>()
+    }
+}
+
+impl ValueBytes for UploadedBytecode {
+    fn value_bytes(&self) -> usize {
+        match self {
+            UploadedBytecode::Uncompleted { bytecode, .. } => bytecode.len(),
+            UploadedBytecode::Completed(bytecode) => bytecode.len(),
+        }
+    }
+}
+
+fn found_bytes<V: ValueBytes + ?Sized, T: core::ops::Deref<Target = V>, E>(
+    result: &Result<Option<T>, E>,
+) -> usize {
+    match result {
+        Ok(Some(value)) => value.value_bytes(),
+        _ => 0,
+    }
+}
+
+fn option_bytes<E>(result: &Result<Option<usize>, E>) -> usize {
+    match result {
+        Ok(Some(len)) => *len,
+        _ => 0,
+    }
+}
+
+fn owned_bytes<E>(result: &Result<Option<Vec<u8>>, E>) -> usize {
+    match result {
+        Ok(Some(value)) => value.len(),
+        _ => 0,
+    }
+}
+
+macro_rules! instrumented_table {
+    ($table:ident) => {
+        impl<S: InterpreterStorage> StorageInspect<$table> for InstrumentedStorage<S> {
+            type Error = S::DataError;
+
+            fn get(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Cow<'_, <$table as Mappable>::OwnedValue>>, Self::Error>
+            {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::get"),
+                    key.as_ref().len(),
+                    || StorageInspect::<$table>::get(&self.storage, key),
+                    found_bytes,
+                )
+            }
+
+            fn contains_key(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<bool, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::contains_key"),
+                    key.as_ref().len(),
+                    || StorageInspect::<$table>::contains_key(&self.storage, key),
+                    |_| 0,
+                )
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageMutate<$table> for InstrumentedStorage<S> {
+            fn insert(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                value: &<$table as Mappable>::Value,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::insert"),
+                    key.as_ref().len(),
+                    || StorageMutate::<$table>::insert(&mut self.storage, key, value),
+                    |_| value.value_bytes(),
+                )
+            }
+
+            fn remove(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::remove"),
+                    key.as_ref().len(),
+                    || StorageMutate::<$table>::remove(&mut self.storage, key),
+                    |_| 0,
+                )
+            }
+        }
+    };
+}
+
+macro_rules! instrumented_bytes_table {
+    ($table:ident) => {
+        instrumented_table!($table);
+
+        impl<S: InterpreterStorage> StorageSize<$table> for InstrumentedStorage<S> {
+            fn size_of_value(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<usize>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::size_of_value"),
+                    key.as_ref().len(),
+                    || StorageSize::<$table>::size_of_value(&self.storage, key),
+                    |_| 0,
+                )
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageRead<$table> for InstrumentedStorage<S> {
+            fn read(
+                &self,
+                key: &<$table as Mappable>::Key,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::read"),
+                    key.as_ref().len(),
+                    || StorageRead::<$table>::read(&self.storage, key, buf),
+                    option_bytes,
+                )
+            }
+
+            fn read_alloc(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::read_alloc"),
+                    key.as_ref().len(),
+                    || StorageRead::<$table>::read_alloc(&self.storage, key),
+                    owned_bytes,
+                )
+            }
+
+            fn read_range(
+                &self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::read_range"),
+                    key.as_ref().len(),
+                    || StorageRead::<$table>::read_range(&self.storage, key, offset, buf),
+                    option_bytes,
+                )
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageWrite<$table> for InstrumentedStorage<S> {
+            fn write(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::write"),
+                    key.as_ref().len(),
+                    || StorageWrite::<$table>::write(&mut self.storage, key, buf),
+                    |_| buf.len(),
+                )
+            }
+
+            fn replace(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::replace"),
+                    key.as_ref().len(),
+                    || StorageWrite::<$table>::replace(&mut self.storage, key, buf),
+                    |_| buf.len(),
+                )
+            }
+
+            fn take(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::take"),
+                    key.as_ref().len(),
+                    || StorageWrite::<$table>::take(&mut self.storage, key),
+                    owned_bytes,
+                )
+            }
+
+            fn write_range(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                record(
+                    &self.metrics,
+                    concat!(stringify!($table), "::write_range"),
+                    key.as_ref().len(),
+                    || {
+                        StorageWrite::<$table>::write_range(
+                            &mut self.storage,
+                            key,
+                            offset,
+                            buf,
+                        )
+                    },
+                    |_| buf.len(),
+                )
+            }
+        }
+    };
+}
+
+instrumented_bytes_table!(ContractsRawCode);
+instrumented_bytes_table!(ContractsState);
+instrumented_table!(ContractsAssets);
+instrumented_table!(UploadedBytecodes);
+
+impl<S: InterpreterStorage> StorageBatchInspect<ContractsState>
+    for InstrumentedStorage<S>
+{
+    fn get_batch<'a, 'k, Iter>(
+        &'a self,
+        keys: Iter,
+    ) -> BatchValues<'a, ContractsState, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k ContractsStateKey>,
+    {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        let key_bytes = keys.iter().map(|key| key.as_ref().len()).sum();
+        record(
+            &self.metrics,
+            "ContractsState::get_batch",
+            key_bytes,
+            || StorageBatchInspect::<ContractsState>::get_batch(&self.storage, keys),
+            |values| values.iter().map(found_bytes).sum(),
+        )
+    }
+}
+
+impl<S: InterpreterStorage> ContractsAssetsStorage for InstrumentedStorage<S> {}
+
+impl<S: InterpreterStorage> InterpreterStorage for InstrumentedStorage<S> {
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        record(
+            &self.metrics,
+            "block_height",
+            0,
+            || self.storage.block_height(),
+            |_| 0,
+        )
+    }
+
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        record(
+            &self.metrics,
+            "consensus_parameters_version",
+            0,
+            || self.storage.consensus_parameters_version(),
+            |_| 0,
+        )
+    }
+
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        record(
+            &self.metrics,
+            "state_transition_version",
+            0,
+            || self.storage.state_transition_version(),
+            |_| 0,
+        )
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        record(
+            &self.metrics,
+            "timestamp",
+            0,
+            || self.storage.timestamp(height),
+            |_| 0,
+        )
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        record(
+            &self.metrics,
+            "block_hash",
+            0,
+            || self.storage.block_hash(block_height),
+            |_| 0,
+        )
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        record(
+            &self.metrics,
+            "coinbase",
+            0,
+            || self.storage.coinbase(),
+            |_| 0,
+        )
+    }
+
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        record(
+            &self.metrics,
+            "set_consensus_parameters",
+            0,
+            || {
+                self.storage
+                    .set_consensus_parameters(version, consensus_parameters)
+            },
+            |_| 0,
+        )
+    }
+
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        record(
+            &self.metrics,
+            "set_state_transition_bytecode",
+            0,
+            || self.storage.set_state_transition_bytecode(version, hash),
+            |_| 0,
+        )
+    }
+
+    fn contract_state_insert_range<'a, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let mut value_bytes = 0usize;
+        let values = values.inspect(|value| {
+            value_bytes = value_bytes.saturating_add(value.len());
+        });
+        let result = record(
+            &self.metrics,
+            "contract_state_insert_range",
+            ContractsStateKey::LEN,
+            || {
+                self.storage
+                    .contract_state_insert_range(contract, start_key, values)
+            },
+            |_| 0,
+        );
+        if let Some(entry) = self
+            .metrics
+            .get_mut()
+            .operations
+            .get_mut("contract_state_insert_range")
+        {
+            entry.value_bytes = entry.value_bytes.saturating_add(value_bytes as u64);
+        }
+        result
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        record(
+            &self.metrics,
+            "contract_state_remove_range",
+            ContractsStateKey::LEN,
+            || {
+                self.storage
+                    .contract_state_remove_range(contract, start_key, range)
+            },
+            |_| 0,
+        )
+    }
+}
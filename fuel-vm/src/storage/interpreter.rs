@@ -2,6 +2,7 @@
 
 use fuel_storage::{
     StorageAsRef,
+    StorageBatchInspect,
     StorageInspect,
     StorageMutate,
     StorageRead,
@@ -31,6 +32,7 @@ use crate::{
         ContractsRawCode,
         ContractsState,
         ContractsStateData,
+        ContractsStateKey,
         UploadedBytecode,
         UploadedBytecodes,
     },
@@ -53,6 +55,7 @@ pub trait InterpreterStorage:
     + StorageWrite<ContractsState, Error = Self::DataError>
     + StorageSize<ContractsState, Error = Self::DataError>
     + StorageRead<ContractsState, Error = Self::DataError>
+    + StorageBatchInspect<ContractsState, Error = Self::DataError>
     + StorageMutate<UploadedBytecodes, Error = Self::DataError>
     + ContractsAssetsStorage<Error = Self::DataError>
 {
@@ -220,13 +223,18 @@ pub trait InterpreterStorage:
 
     /// Fetch a range of values from a key-value mapping in a contract storage.
     /// Returns the full range requested using optional values in case
-    /// a requested slot is unset.  
+    /// a requested slot is unset.
+    ///
+    /// The default implementation fetches the range with one
+    /// [`StorageBatchInspect::get_batch`] call.
     fn contract_state_range(
         &self,
         id: &ContractId,
         start_key: &Bytes32,
         range: usize,
-    ) -> Result<Vec<Option<Cow<ContractsStateData>>>, Self::DataError>;
+    ) -> Result<Vec<Option<Cow<ContractsStateData>>>, Self::DataError> {
+        contract_state_batch(self, id, start_key, range)
+    }
 
     /// Insert a range of key-value mappings into contract storage.
     /// Returns the number of keys that were previously unset but are now set.
@@ -249,6 +257,56 @@ pub trait InterpreterStorage:
     ) -> Result<Option<()>, Self::DataError>;
 }
 
+/// The keys of the `range` consecutive slots of the `contract` state from the
+/// `start_key`. The keys stop at the last key of the state.
+pub(crate) fn contract_state_keys<'a>(
+    contract: &'a ContractId,
+    start_key: &Bytes32,
+    range: usize,
+) -> impl Iterator<Item = ContractsStateKey> + 'a {
+    core::iter::successors(Some(**start_key), |n| {
+        let mut n = *n;
+        if add_one(&mut n) {
+            None
+        } else {
+            Some(n)
+        }
+    })
+    .take(range)
+    .map(move |key| (contract, &Bytes32::from(key)).into())
+}
+
+/// Fetches the `range` slots of the `contract` state from the `start_key` with one
+/// [`StorageBatchInspect::get_batch`] call.
+pub(crate) fn contract_state_batch<'a, S>(
+    storage: &'a S,
+    contract: &ContractId,
+    start_key: &Bytes32,
+    range: usize,
+) -> Result<Vec<Option<Cow<'a, ContractsStateData>>>, S::Error>
+where
+    S: StorageBatchInspect<ContractsState> + ?Sized,
+{
+    let mut keys = Vec::with_capacity(range);
+    keys.extend(contract_state_keys(contract, start_key, range));
+    StorageBatchInspect::<ContractsState>::get_batch(storage, keys.iter())
+        .into_iter()
+        .collect()
+}
+
+fn add_one(a: &mut [u8; 32]) -> bool {
+    let right = u128::from_be_bytes(a[16..].try_into().unwrap());
+    let (right, of) = right.overflowing_add(1);
+    a[16..].copy_from_slice(&right.to_be_bytes()[..]);
+    if of {
+        let left = u128::from_be_bytes(a[..16].try_into().unwrap());
+        let (left, of) = left.overflowing_add(1);
+        a[..16].copy_from_slice(&left.to_be_bytes()[..]);
+        return of
+    }
+    false
+}
+
 /// Storage operations for contract assets.
 pub trait ContractsAssetsStorage: StorageMutate<ContractsAssets> {
     /// Fetch the balance of an asset ID in a contract storage.
@@ -0,0 +1,509 @@
+//! Interpreter storage over a generic key-value store.
+
+use crate::{
+    prelude::{
+        InterpreterError,
+        RuntimeError,
+    },
+    storage::{
+        contract_state_keys,
+        Column,
+        ContractsAssets,
+        ContractsAssetsStorage,
+        ContractsRawCode,
+        ContractsState,
+        InterpreterStorage,
+        UploadedBytecodes,
+    },
+};
+
+use fuel_storage::{
+    Mappable,
+    StorageBatchInspect,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+    TableColumn,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use alloc::{
+    borrow::Cow,
+    vec::Vec,
+};
+
+mod persistent;
+#[cfg(feature = "redb")]
+mod redb;
+
+pub use persistent::{
+    KvBatch,
+    KvEntries,
+    KvTransaction,
+    PersistentStorage,
+    PersistentStore,
+};
+#[cfg(feature = "redb")]
+pub use self::redb::RedbStore;
+
+/// Key-value store with the [`Column`]s of the tables used by the interpreter.
+pub trait KeyValueStore {
+    /// The error of the store.
+    type Error: core::fmt::Debug;
+
+    /// Returns the value of the `key` in the `column`.
+    fn get(&self, column: Column, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Sets the value of the `key` in the `column`.
+    fn put(
+        &mut self,
+        column: Column,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Removes the `key` from the `column`.
+    fn delete(&mut self, column: Column, key: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// The error of a [`KvStoreAdapter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KvStoreError<E> {
+    /// The key-value store failed.
+    Store(E),
+    /// A key of the column isn't a valid encoding of the keys of the column.
+    InvalidKey {
+        /// The column of the key.
+        column: Column,
+    },
+    /// A value of the column isn't a valid encoding of the values of the column.
+    InvalidValue {
+        /// The column of the value.
+        column: Column,
+    },
+    /// The store has no timestamp or hash of the block at the height.
+    MissingBlock(BlockHeight),
+}
+
+impl<E> From<KvStoreError<E>> for InterpreterError<KvStoreError<E>> {
+    fn from(val: KvStoreError<E>) -> Self {
+        let rt: RuntimeError<KvStoreError<E>> = val.into();
+        rt.into()
+    }
+}
+
+impl<E> From<KvStoreError<E>> for RuntimeError<KvStoreError<E>> {
+    fn from(val: KvStoreError<E>) -> Self {
+        RuntimeError::Storage(val)
+    }
+}
+
+const BLOCK_HEIGHT: &[u8] = b"block_height";
+const COINBASE: &[u8] = b"coinbase";
+const CONSENSUS_PARAMETERS_VERSION: &[u8] = b"consensus_parameters_version";
+const STATE_TRANSITION_VERSION: &[u8] = b"state_transition_version";
+
+/// The encoding of the values of a table in its column.
+trait KvTable: TableColumn<Column = Column> {
+    fn encode(value: &Self::Value) -> Cow<'_, [u8]>;
+
+    fn decode(bytes: Vec<u8>) -> Option<Self::OwnedValue>;
+}
+
+impl KvTable for ContractsRawCode {
+    fn encode(value: &[u8]) -> Cow<'_, [u8]> {
+        Cow::Borrowed(value)
+    }
+
+    fn decode(bytes: Vec<u8>) -> Option<Self::OwnedValue> {
+        Some(bytes.into())
+    }
+}
+
+impl KvTable for ContractsState {
+    fn encode(value: &[u8]) -> Cow<'_, [u8]> {
+        Cow::Borrowed(value)
+    }
+
+    fn decode(bytes: Vec<u8>) -> Option<Self::OwnedValue> {
+        Some(bytes.into())
+    }
+}
+
+impl KvTable for ContractsAssets {
+    fn encode(value: &Word) -> Cow<'_, [u8]> {
+        Cow::Owned(value.to_be_bytes().to_vec())
+    }
+
+    fn decode(bytes: Vec<u8>) -> Option<Word> {
+        Some(Word::from_be_bytes(bytes.as_slice().try_into().ok()?))
+    }
+}
+
+impl KvTable for UploadedBytecodes {
+    fn encode(value: &Self::Value) -> Cow<'_, [u8]> {
+        Cow::Owned(postcard::to_allocvec(value).expect("The bytecode is serializable"))
+    }
+
+    fn decode(bytes: Vec<u8>) -> Option<Self::OwnedValue> {
+        postcard::from_bytes(&bytes).ok()
+    }
+}
+
+/// Interpreter storage over the get, put and delete of a [`KeyValueStore`].
+///
+/// Each table is stored in its [`Column`], keyed by the bytes of its key. The code and
+/// the state are stored as raw bytes, the balances and the numbers as big-endian
+/// bytes, and the other values are encoded with `postcard`. The metadata missing from
+/// the store are the ones of the genesis block: the block height, the coinbase and the
+/// versions are zero.
+#[derive(Debug, Clone, Default)]
+pub struct KvStoreAdapter<K> {
+    store: K,
+}
+
+impl<K> KvStoreAdapter<K> {
+    /// Create a new interpreter storage over the `store`.
+    pub fn new(store: K) -> Self {
+        Self { store }
+    }
+
+    /// The key-value store.
+    pub fn store(&self) -> &K {
+        &self.store
+    }
+
+    /// The mutable key-value store.
+    pub fn store_mut(&mut self) -> &mut K {
+        &mut self.store
+    }
+
+    /// Consumes the adapter and returns the key-value store.
+    pub fn into_store(self) -> K {
+        self.store
+    }
+}
+
+impl<K: KeyValueStore> KvStoreAdapter<K> {
+    /// Set the current block height.
+    pub fn set_block_height(
+        &mut self,
+        block_height: BlockHeight,
+    ) -> Result<(), K::Error> {
+        self.store
+            .put(Column::Metadata, BLOCK_HEIGHT, &block_height.to_be_bytes())
+    }
+
+    /// Set the coinbase address.
+    pub fn set_coinbase(&mut self, coinbase: &ContractId) -> Result<(), K::Error> {
+        self.store
+            .put(Column::Metadata, COINBASE, coinbase.as_ref())
+    }
+
+    /// Set the current version of the consensus parameters.
+    pub fn set_consensus_parameters_version(
+        &mut self,
+        version: u32,
+    ) -> Result<(), K::Error> {
+        self.store.put(
+            Column::Metadata,
+            CONSENSUS_PARAMETERS_VERSION,
+            &version.to_be_bytes(),
+        )
+    }
+
+    /// Set the current version of the state transition function.
+    pub fn set_state_transition_version(&mut self, version: u32) -> Result<(), K::Error> {
+        self.store.put(
+            Column::Metadata,
+            STATE_TRANSITION_VERSION,
+            &version.to_be_bytes(),
+        )
+    }
+
+    /// Set the timestamp and the hash of the block at the `height`.
+    pub fn set_block(
+        &mut self,
+        height: BlockHeight,
+        timestamp: Word,
+        hash: &Bytes32,
+    ) -> Result<(), K::Error> {
+        let key = height.to_be_bytes();
+        self.store
+            .put(Column::BlockTimestamps, &key, &timestamp.to_be_bytes())?;
+        self.store.put(Column::BlockHashes, &key, hash.as_ref())
+    }
+
+    fn get_value(
+        &self,
+        column: Column,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>, KvStoreError<K::Error>> {
+        self.store.get(column, key).map_err(KvStoreError::Store)
+    }
+
+    fn get_array<const N: usize>(
+        &self,
+        column: Column,
+        key: &[u8],
+    ) -> Result<Option<[u8; N]>, KvStoreError<K::Error>> {
+        self.get_value(column, key)?
+            .map(|bytes| {
+                bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| KvStoreError::InvalidValue { column })
+            })
+            .transpose()
+    }
+
+    fn get_version(&self, key: &[u8]) -> Result<u32, KvStoreError<K::Error>> {
+        Ok(self
+            .get_array(Column::Metadata, key)?
+            .map(u32::from_be_bytes)
+            .unwrap_or_default())
+    }
+}
+
+impl<K, T> StorageInspect<T> for KvStoreAdapter<K>
+where
+    K: KeyValueStore,
+    T: KvTable,
+    T::Key: AsRef<[u8]>,
+{
+    type Error = KvStoreError<K::Error>;
+
+    fn get(&self, key: &T::Key) -> Result<Option<Cow<'_, T::OwnedValue>>, Self::Error> {
+        self.get_value(T::COLUMN, key.as_ref())?
+            .map(|bytes| {
+                T::decode(bytes)
+                    .map(Cow::Owned)
+                    .ok_or(KvStoreError::InvalidValue { column: T::COLUMN })
+            })
+            .transpose()
+    }
+
+    fn contains_key(&self, key: &T::Key) -> Result<bool, Self::Error> {
+        Ok(self.get_value(T::COLUMN, key.as_ref())?.is_some())
+    }
+}
+
+impl<K, T> StorageMutate<T> for KvStoreAdapter<K>
+where
+    K: KeyValueStore,
+    T: KvTable,
+    T::Key: AsRef<[u8]>,
+{
+    fn insert(
+        &mut self,
+        key: &T::Key,
+        value: &T::Value,
+    ) -> Result<Option<T::OwnedValue>, Self::Error> {
+        let previous = StorageInspect::<T>::get(self, key)?.map(Cow::into_owned);
+        self.store
+            .put(T::COLUMN, key.as_ref(), &T::encode(value))
+            .map_err(KvStoreError::Store)?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &T::Key) -> Result<Option<T::OwnedValue>, Self::Error> {
+        let previous = StorageInspect::<T>::get(self, key)?.map(Cow::into_owned);
+        self.store
+            .delete(T::COLUMN, key.as_ref())
+            .map_err(KvStoreError::Store)?;
+        Ok(previous)
+    }
+}
+
+macro_rules! kv_bytes_table {
+    ($table:ident) => {
+        impl<K: KeyValueStore> StorageSize<$table> for KvStoreAdapter<K> {
+            fn size_of_value(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<usize>, Self::Error> {
+                Ok(self
+                    .get_value(Column::$table, key.as_ref())?
+                    .map(|bytes| bytes.len()))
+            }
+        }
+
+        impl<K: KeyValueStore> StorageRead<$table> for KvStoreAdapter<K> {
+            fn read(
+                &self,
+                key: &<$table as Mappable>::Key,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                Ok(self.get_value(Column::$table, key.as_ref())?.map(|bytes| {
+                    let len = buf.len().min(bytes.len());
+                    buf[..len].copy_from_slice(&bytes[..len]);
+                    len
+                }))
+            }
+
+            fn read_alloc(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                self.get_value(Column::$table, key.as_ref())
+            }
+        }
+
+        impl<K: KeyValueStore> StorageWrite<$table> for KvStoreAdapter<K> {
+            fn write(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                self.store
+                    .put(Column::$table, key.as_ref(), buf)
+                    .map_err(KvStoreError::Store)?;
+                Ok(buf.len())
+            }
+
+            fn replace(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+                let previous = StorageRead::<$table>::read_alloc(self, key)?;
+                let size = StorageWrite::<$table>::write(self, key, buf)?;
+                Ok((size, previous))
+            }
+
+            fn take(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                let previous = StorageRead::<$table>::read_alloc(self, key)?;
+                self.store
+                    .delete(Column::$table, key.as_ref())
+                    .map_err(KvStoreError::Store)?;
+                Ok(previous)
+            }
+        }
+    };
+}
+
+kv_bytes_table!(ContractsRawCode);
+kv_bytes_table!(ContractsState);
+
+impl<K: KeyValueStore> StorageBatchInspect<ContractsState> for KvStoreAdapter<K> {}
+
+impl<K: KeyValueStore> ContractsAssetsStorage for KvStoreAdapter<K> {}
+
+impl<K: KeyValueStore> InterpreterStorage for KvStoreAdapter<K> {
+    type DataError = KvStoreError<K::Error>;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        Ok(self
+            .get_array(Column::Metadata, BLOCK_HEIGHT)?
+            .map(BlockHeight::from)
+            .unwrap_or_default())
+    }
+
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        self.get_version(CONSENSUS_PARAMETERS_VERSION)
+    }
+
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        self.get_version(STATE_TRANSITION_VERSION)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.get_array(Column::BlockTimestamps, &height.to_be_bytes())?
+            .map(Word::from_be_bytes)
+            .ok_or(KvStoreError::MissingBlock(height))
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.get_array(Column::BlockHashes, &block_height.to_be_bytes())?
+            .map(Bytes32::from)
+            .ok_or(KvStoreError::MissingBlock(block_height))
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        Ok(self
+            .get_array(Column::Metadata, COINBASE)?
+            .map(ContractId::from)
+            .unwrap_or_default())
+    }
+
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        let column = Column::ConsensusParametersVersions;
+        let key = version.to_be_bytes();
+        let previous = self
+            .get_value(column, &key)?
+            .map(|bytes| {
+                postcard::from_bytes(&bytes)
+                    .map_err(|_| KvStoreError::InvalidValue { column })
+            })
+            .transpose()?;
+        let value = postcard::to_allocvec(consensus_parameters)
+            .expect("The consensus parameters are serializable");
+        self.store
+            .put(column, &key, &value)
+            .map_err(KvStoreError::Store)?;
+        Ok(previous)
+    }
+
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        let column = Column::StateTransitionBytecodeVersions;
+        let key = version.to_be_bytes();
+        let previous = self.get_array(column, &key)?.map(Bytes32::from);
+        self.store
+            .put(column, &key, hash.as_ref())
+            .map_err(KvStoreError::Store)?;
+        Ok(previous)
+    }
+
+    fn contract_state_insert_range<'a, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let mut unset_count = 0;
+        for (key, value) in
+            contract_state_keys(contract, start_key, usize::MAX).zip(values)
+        {
+            if !StorageInspect::<ContractsState>::contains_key(self, &key)? {
+                unset_count += 1;
+            }
+            StorageWrite::<ContractsState>::write(self, &key, value)?;
+        }
+        Ok(unset_count)
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        let mut all_set_key = true;
+        for key in contract_state_keys(contract, start_key, range) {
+            all_set_key &= StorageWrite::<ContractsState>::take(self, &key)?.is_some();
+        }
+        Ok(all_set_key.then_some(()))
+    }
+}
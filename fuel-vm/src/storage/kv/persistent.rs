@@ -0,0 +1,514 @@
+//! Atomic commits and snapshots of the interpreter storage over a key-value store.
+
+use super::{
+    KeyValueStore,
+    KvStoreAdapter,
+    KvStoreError,
+    KvTable,
+};
+use crate::storage::{
+    Column,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    StorageSnapshot,
+    StorageSnapshotV1,
+    UploadedBytecodes,
+};
+
+use fuel_merkle::sparse::{
+    empty_sum,
+    in_memory::NodesTable,
+    MerkleStorageAdapter,
+    MerkleStorageAdapterError,
+    MerkleTree,
+    MerkleTreeError,
+    MerkleTreeKey,
+};
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+};
+use fuel_types::{
+    Bytes32,
+    ContractId,
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::ops::Deref;
+
+/// The changes of a batch of writes: the new value of each key of each column, `None`
+/// for the removed keys.
+pub type KvBatch = BTreeMap<(Column, Vec<u8>), Option<Vec<u8>>>;
+
+/// The keys and the values of the entries of a column, sorted by key.
+pub type KvEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// The new values of the leaves of a state tree, `None` for the removed leaves.
+type StateChanges = Vec<(MerkleTreeKey, Option<Vec<u8>>)>;
+
+/// Key-value store persisting the batches of writes atomically, like the write batches
+/// of RocksDB.
+pub trait PersistentStore: KeyValueStore {
+    /// Applies all the changes of the `batch`, or none of them if it fails.
+    fn write_batch(&mut self, batch: KvBatch) -> Result<(), Self::Error>;
+
+    /// The keys and the values of the `column` whose key starts with the `prefix`,
+    /// sorted by key.
+    fn entries(&self, column: Column, prefix: &[u8]) -> Result<KvEntries, Self::Error>;
+}
+
+/// Key-value store buffering the writes to the wrapped store, until they are
+/// [committed](Self::commit) with one atomic batch.
+///
+/// The reads see the buffered writes. The writes dropped without a commit, like the
+/// ones of a process killed in the middle of a transaction, never reach the store.
+///
+/// The store keeps a sparse Merkle tree of the state of each contract, in the columns
+/// [`Column::ContractsStateMerkleData`] and [`Column::ContractsStateMerkleMetadata`].
+/// The commits update the trees of the changed states in the same batch, through the
+/// [`MerkleStorageAdapter`], so the roots are never out of sync with the states.
+#[derive(Debug, Clone, Default)]
+pub struct KvTransaction<K> {
+    store: K,
+    batch: KvBatch,
+}
+
+impl<K> KvTransaction<K> {
+    /// Starts buffering the writes to the `store`.
+    pub fn new(store: K) -> Self {
+        Self {
+            store,
+            batch: KvBatch::new(),
+        }
+    }
+
+    /// The writes not committed yet.
+    pub fn batch(&self) -> &KvBatch {
+        &self.batch
+    }
+
+    /// Drops the writes not committed yet.
+    pub fn rollback(&mut self) {
+        self.batch.clear();
+    }
+
+    /// The wrapped store, without the writes not committed yet.
+    pub fn store(&self) -> &K {
+        &self.store
+    }
+
+    /// The mutable wrapped store. Its writes bypass the buffered ones.
+    pub fn store_mut(&mut self) -> &mut K {
+        &mut self.store
+    }
+
+    /// Consumes the transaction, dropping the writes not committed yet, and returns
+    /// the wrapped store.
+    pub fn into_store(self) -> K {
+        self.store
+    }
+}
+
+impl<K: KeyValueStore> KvTransaction<K> {
+    /// The root of the state of the `contract`, with the writes not committed yet.
+    pub fn state_root(
+        &self,
+        contract: &ContractId,
+    ) -> Result<MerkleRoot, KvStoreError<K::Error>> {
+        let root = self.committed_state_root(contract)?;
+        let changes = self.state_changes(contract)?;
+        if changes.is_empty() {
+            return Ok(root)
+        }
+        let mut nodes = MerkleStorageAdapter::new(StateNodes {
+            transaction: self,
+            contract: *contract,
+        });
+        update_state_tree(&mut nodes, &root, changes)
+    }
+
+    /// The root of the state tree of the `contract` in the store.
+    fn committed_state_root(
+        &self,
+        contract: &ContractId,
+    ) -> Result<MerkleRoot, KvStoreError<K::Error>> {
+        let column = Column::ContractsStateMerkleMetadata;
+        self.store
+            .get(column, contract.as_ref())
+            .map_err(KvStoreError::Store)?
+            .map_or(Ok(*empty_sum()), |root| {
+                root.as_slice()
+                    .try_into()
+                    .map_err(|_| KvStoreError::InvalidValue { column })
+            })
+    }
+
+    /// The writes of the state of the `contract` not committed yet, keyed by the
+    /// leaves of its tree.
+    fn state_changes(
+        &self,
+        contract: &ContractId,
+    ) -> Result<StateChanges, KvStoreError<K::Error>> {
+        let column = Column::ContractsState;
+        self.batch
+            .range((column, contract.to_vec())..)
+            .take_while(|((pending_column, key), _)| {
+                *pending_column == column && key.starts_with(contract.as_ref())
+            })
+            .map(|((_, key), value)| {
+                let key = ContractsStateKey::from_slice(key)
+                    .map_err(|_| KvStoreError::InvalidKey { column })?;
+                Ok((MerkleTreeKey::new(*key.state_key()), value.clone()))
+            })
+            .collect()
+    }
+}
+
+impl<K: PersistentStore> KvTransaction<K> {
+    /// Writes the buffered writes to the store with one atomic batch, with the nodes
+    /// and the roots of the trees of the changed states. The writes are kept buffered
+    /// if the store fails.
+    pub fn commit(&mut self) -> Result<(), KvStoreError<K::Error>> {
+        if self.batch.is_empty() {
+            return Ok(())
+        }
+        let buffered = self.batch.clone();
+        let result = self.update_state_trees().and_then(|()| {
+            let batch = core::mem::take(&mut self.batch);
+            self.store.write_batch(batch).map_err(KvStoreError::Store)
+        });
+        if result.is_err() {
+            self.batch = buffered;
+        }
+        result
+    }
+
+    /// Adds the nodes and the roots of the trees of the changed states to the batch.
+    fn update_state_trees(&mut self) -> Result<(), KvStoreError<K::Error>> {
+        let column = Column::ContractsState;
+        let contracts = self
+            .batch
+            .range((column, Vec::new())..)
+            .take_while(|((pending_column, _), _)| *pending_column == column)
+            .filter_map(|((_, key), _)| {
+                ContractId::try_from(key.get(..ContractId::LEN)?).ok()
+            })
+            .collect::<BTreeSet<_>>();
+
+        for contract in contracts {
+            let root = self.committed_state_root(&contract)?;
+            let changes = self.state_changes(&contract)?;
+            let mut nodes = MerkleStorageAdapter::new(StateNodes {
+                transaction: &mut *self,
+                contract,
+            });
+            let root = update_state_tree(&mut nodes, &root, changes)?;
+            nodes.commit().map_err(KvStoreError::Store)?;
+
+            let column = Column::ContractsStateMerkleMetadata;
+            if root == *empty_sum() {
+                self.delete(column, contract.as_ref())
+            } else {
+                self.put(column, contract.as_ref(), &root)
+            }
+            .map_err(KvStoreError::Store)?;
+        }
+        Ok(())
+    }
+}
+
+/// The table of the nodes of a state tree, encoded by
+/// [`encode_node`](fuel_merkle::sparse::encode_node) and keyed by their hash.
+struct StateNodesTable;
+
+impl Mappable for StateNodesTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = MerkleRoot;
+    type OwnedValue = Vec<u8>;
+    type Value = [u8];
+}
+
+/// The nodes of the state tree of the `contract`, in the
+/// [`Column::ContractsStateMerkleData`] of the `transaction`.
+struct StateNodes<T> {
+    transaction: T,
+    contract: ContractId,
+}
+
+impl<T> StateNodes<T> {
+    fn key(&self, hash: &MerkleRoot) -> Vec<u8> {
+        [self.contract.as_ref(), hash.as_ref()].concat()
+    }
+}
+
+impl<K, T> StorageInspect<StateNodesTable> for StateNodes<T>
+where
+    K: KeyValueStore,
+    T: Deref<Target = KvTransaction<K>>,
+{
+    type Error = K::Error;
+
+    fn get(&self, hash: &MerkleRoot) -> Result<Option<Cow<'_, Vec<u8>>>, K::Error> {
+        Ok(self
+            .transaction
+            .get(Column::ContractsStateMerkleData, &self.key(hash))?
+            .map(Cow::Owned))
+    }
+
+    fn contains_key(&self, hash: &MerkleRoot) -> Result<bool, K::Error> {
+        Ok(StorageInspect::<StateNodesTable>::get(self, hash)?.is_some())
+    }
+}
+
+impl<K: KeyValueStore> StorageMutate<StateNodesTable>
+    for StateNodes<&mut KvTransaction<K>>
+{
+    fn insert(
+        &mut self,
+        hash: &MerkleRoot,
+        node: &[u8],
+    ) -> Result<Option<Vec<u8>>, K::Error> {
+        let previous =
+            StorageInspect::<StateNodesTable>::get(self, hash)?.map(Cow::into_owned);
+        let key = self.key(hash);
+        self.transaction
+            .put(Column::ContractsStateMerkleData, &key, node)?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, hash: &MerkleRoot) -> Result<Option<Vec<u8>>, K::Error> {
+        let previous =
+            StorageInspect::<StateNodesTable>::get(self, hash)?.map(Cow::into_owned);
+        let key = self.key(hash);
+        self.transaction
+            .delete(Column::ContractsStateMerkleData, &key)?;
+        Ok(previous)
+    }
+}
+
+/// Applies the `changes` to the state tree of the `root` over the `nodes`, and
+/// returns the new root. The written nodes stay in the `nodes` until they are
+/// committed.
+fn update_state_tree<T, E>(
+    nodes: &mut MerkleStorageAdapter<StateNodes<T>, StateNodesTable>,
+    root: &MerkleRoot,
+    changes: StateChanges,
+) -> Result<MerkleRoot, KvStoreError<E>>
+where
+    StateNodes<T>: StorageInspect<StateNodesTable, Error = E>,
+{
+    let state_tree_error = |error| match error {
+        MerkleTreeError::StorageError(MerkleStorageAdapterError::StorageError(error)) => {
+            KvStoreError::Store(error)
+        }
+        _ => KvStoreError::InvalidValue {
+            column: Column::ContractsStateMerkleData,
+        },
+    };
+    MerkleTree::<NodesTable, _>::load(nodes, root)
+        .map_err(state_tree_error)?
+        .update_batch(changes)
+        .map_err(state_tree_error)
+}
+
+impl<K: KeyValueStore> KeyValueStore for KvTransaction<K> {
+    type Error = K::Error;
+
+    fn get(&self, column: Column, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.batch.get(&(column, key.to_vec())) {
+            Some(value) => Ok(value.clone()),
+            None => self.store.get(column, key),
+        }
+    }
+
+    fn put(
+        &mut self,
+        column: Column,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.batch
+            .insert((column, key.to_vec()), Some(value.to_vec()));
+        Ok(())
+    }
+
+    fn delete(&mut self, column: Column, key: &[u8]) -> Result<(), Self::Error> {
+        self.batch.insert((column, key.to_vec()), None);
+        Ok(())
+    }
+}
+
+impl<K: PersistentStore> PersistentStore for KvTransaction<K> {
+    fn write_batch(&mut self, batch: KvBatch) -> Result<(), Self::Error> {
+        self.batch.extend(batch);
+        Ok(())
+    }
+
+    fn entries(&self, column: Column, prefix: &[u8]) -> Result<KvEntries, Self::Error> {
+        let mut entries = self
+            .store
+            .entries(column, prefix)?
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        let pending = self.batch.range((column, prefix.to_vec())..).take_while(
+            |((pending_column, key), _)| {
+                *pending_column == column && key.starts_with(prefix)
+            },
+        );
+        for ((_, key), value) in pending {
+            match value {
+                Some(value) => entries.insert(key.clone(), value.clone()),
+                None => entries.remove(key),
+            };
+        }
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Interpreter storage over a [`PersistentStore`], committing the writes of each
+/// transaction atomically with [`KvTransaction::commit`].
+pub type PersistentStorage<K> = KvStoreAdapter<KvTransaction<K>>;
+
+impl<K: KeyValueStore> KvStoreAdapter<K> {
+    /// Writes the tables and the metadata of the `snapshot` of a
+    /// [`MemoryStorage`](crate::storage::MemoryStorage) to the store. The snapshot has
+    /// no blocks, so their timestamps and hashes are set with
+    /// [`set_block`](Self::set_block).
+    pub fn import_snapshot(
+        &mut self,
+        snapshot: &StorageSnapshot,
+    ) -> Result<(), KvStoreError<K::Error>> {
+        let StorageSnapshot::V1(snapshot) = snapshot;
+        for (id, code) in &snapshot.contracts {
+            StorageMutate::<ContractsRawCode>::insert(self, id, code.as_ref())?;
+        }
+        for (key, balance) in &snapshot.balances {
+            StorageMutate::<ContractsAssets>::insert(self, key, balance)?;
+        }
+        for (key, value) in &snapshot.contract_state {
+            StorageMutate::<ContractsState>::insert(self, key, value.as_ref())?;
+        }
+        for (root, bytecode) in &snapshot.state_transition_bytecodes {
+            StorageMutate::<UploadedBytecodes>::insert(self, root, bytecode)?;
+        }
+        for (version, parameters) in &snapshot.consensus_parameters_versions {
+            self.set_consensus_parameters(*version, parameters)?;
+        }
+        for (version, root) in &snapshot.state_transition_bytecodes_versions {
+            self.set_state_transition_bytecode(*version, root)?;
+        }
+        self.set_block_height(snapshot.block_height)
+            .map_err(KvStoreError::Store)?;
+        self.set_coinbase(&snapshot.coinbase)
+            .map_err(KvStoreError::Store)?;
+        self.set_consensus_parameters_version(snapshot.consensus_parameters_version)
+            .map_err(KvStoreError::Store)?;
+        self.set_state_transition_version(snapshot.state_transition_version)
+            .map_err(KvStoreError::Store)
+    }
+}
+
+impl<K: PersistentStore> KvStoreAdapter<K> {
+    /// Reads the tables and the metadata of the store into the snapshot of a
+    /// [`MemoryStorage`](crate::storage::MemoryStorage).
+    pub fn export_snapshot(&self) -> Result<StorageSnapshot, KvStoreError<K::Error>> {
+        Ok(StorageSnapshot::V1(StorageSnapshotV1 {
+            block_height: self.block_height()?,
+            coinbase: self.coinbase()?,
+            consensus_parameters_version: self.consensus_parameters_version()?,
+            state_transition_version: self.state_transition_version()?,
+            contracts: self
+                .table::<ContractsRawCode, _>(|key| ContractId::try_from(key).ok())?,
+            balances: self.table::<ContractsAssets, _>(|key| {
+                ContractsAssetKey::from_slice(key).ok()
+            })?,
+            contract_state: self.table::<ContractsState, _>(|key| {
+                ContractsStateKey::from_slice(key).ok()
+            })?,
+            consensus_parameters_versions: self.column(
+                Column::ConsensusParametersVersions,
+                version,
+                |value| postcard::from_bytes(&value).ok(),
+            )?,
+            state_transition_bytecodes: self
+                .table::<UploadedBytecodes, _>(|key| Bytes32::try_from(key).ok())?,
+            state_transition_bytecodes_versions: self.column(
+                Column::StateTransitionBytecodeVersions,
+                version,
+                |value| Bytes32::try_from(value.as_slice()).ok(),
+            )?,
+        }))
+    }
+
+    /// The entries of the `column`, decoded with `key` and `value`.
+    fn column<Key: Ord, Value>(
+        &self,
+        column: Column,
+        key: impl Fn(&[u8]) -> Option<Key>,
+        value: impl Fn(Vec<u8>) -> Option<Value>,
+    ) -> Result<BTreeMap<Key, Value>, KvStoreError<K::Error>> {
+        self.store
+            .entries(column, &[])
+            .map_err(KvStoreError::Store)?
+            .into_iter()
+            .map(|(k, v)| {
+                let k = key(&k).ok_or(KvStoreError::InvalidKey { column })?;
+                let v = value(v).ok_or(KvStoreError::InvalidValue { column })?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+
+    fn table<T: KvTable, Key: Ord>(
+        &self,
+        key: impl Fn(&[u8]) -> Option<Key>,
+    ) -> Result<BTreeMap<Key, T::OwnedValue>, KvStoreError<K::Error>> {
+        self.column(T::COLUMN, key, T::decode)
+    }
+}
+
+fn version(key: &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(key.try_into().ok()?))
+}
+
+impl<K: KeyValueStore> MerkleRootStorage<ContractId, ContractsState>
+    for KvStoreAdapter<KvTransaction<K>>
+{
+    fn root(&self, contract: &ContractId) -> Result<MerkleRoot, Self::Error> {
+        self.store().state_root(contract)
+    }
+}
+
+impl<K> KvStoreAdapter<KvTransaction<K>> {
+    /// Consumes the storage, dropping the writes not committed yet, and returns the
+    /// wrapped store.
+    pub fn into_persistent_store(self) -> K {
+        self.into_store().into_store()
+    }
+}
+
+impl<K: PersistentStore> KvStoreAdapter<KvTransaction<K>> {
+    /// Writes the writes since the last commit to the store with one atomic batch.
+    pub fn commit(&mut self) -> Result<(), KvStoreError<K::Error>> {
+        self.store_mut().commit()
+    }
+
+    /// Drops the writes since the last commit.
+    pub fn rollback(&mut self) {
+        self.store_mut().rollback()
+    }
+}
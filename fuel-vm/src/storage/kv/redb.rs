@@ -0,0 +1,124 @@
+//! Persistent key-value store over a `redb` database.
+
+use super::{
+    KeyValueStore,
+    KvBatch,
+    KvEntries,
+    PersistentStore,
+};
+use crate::storage::Column;
+
+use fuel_storage::StorageColumns;
+use redb::{
+    backends::InMemoryBackend,
+    Builder,
+    Database,
+    TableDefinition,
+};
+
+use std::{
+    path::Path,
+    sync::Arc,
+};
+
+/// The table of the `column`, named after it.
+fn table(column: Column) -> TableDefinition<'static, &'static [u8], &'static [u8]> {
+    TableDefinition::new(column.name())
+}
+
+/// [`PersistentStore`] over a `redb` database, with a table per [`Column`].
+///
+/// Each batch is written by one write transaction, which `redb` commits atomically
+/// and durably: a process killed in the middle of a batch leaves the database with all
+/// the previous batches and none of the writes of the interrupted one. The clones of
+/// the store share its database.
+#[derive(Debug, Clone)]
+pub struct RedbStore {
+    database: Arc<Database>,
+}
+
+impl RedbStore {
+    /// Opens the database at the `path`, creating it and the tables of the columns if
+    /// needed. A database that wasn't closed, like the one of a killed process, is
+    /// repaired to its last commit.
+    #[allow(clippy::result_large_err)]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, redb::Error> {
+        Self::with_tables(Database::create(path)?)
+    }
+
+    /// Creates a database kept in memory, with the tables of the columns.
+    #[allow(clippy::result_large_err)]
+    pub fn in_memory() -> Result<Self, redb::Error> {
+        Self::with_tables(Builder::new().create_with_backend(InMemoryBackend::new())?)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn with_tables(database: Database) -> Result<Self, redb::Error> {
+        let transaction = database.begin_write()?;
+        for column in Column::ALL {
+            transaction.open_table(table(*column))?;
+        }
+        transaction.commit()?;
+        Ok(Self {
+            database: Arc::new(database),
+        })
+    }
+}
+
+impl KeyValueStore for RedbStore {
+    type Error = redb::Error;
+
+    fn get(&self, column: Column, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let transaction = self.database.begin_read()?;
+        let table = transaction.open_table(table(column))?;
+        Ok(table.get(key)?.map(|value| value.value().to_vec()))
+    }
+
+    fn put(
+        &mut self,
+        column: Column,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        let change = ((column, key.to_vec()), Some(value.to_vec()));
+        self.write_batch(KvBatch::from([change]))
+    }
+
+    fn delete(&mut self, column: Column, key: &[u8]) -> Result<(), Self::Error> {
+        self.write_batch(KvBatch::from([((column, key.to_vec()), None)]))
+    }
+}
+
+impl PersistentStore for RedbStore {
+    fn write_batch(&mut self, batch: KvBatch) -> Result<(), Self::Error> {
+        let transaction = self.database.begin_write()?;
+        for column in Column::ALL {
+            let mut table = transaction.open_table(table(*column))?;
+            let changes = batch
+                .range((*column, Vec::new())..)
+                .take_while(|((batch_column, _), _)| batch_column == column);
+            for ((_, key), value) in changes {
+                match value {
+                    Some(value) => table.insert(key.as_slice(), value.as_slice())?,
+                    None => table.remove(key.as_slice())?,
+                };
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn entries(&self, column: Column, prefix: &[u8]) -> Result<KvEntries, Self::Error> {
+        let transaction = self.database.begin_read()?;
+        let table = transaction.open_table(table(column))?;
+        let mut entries = Vec::new();
+        for entry in table.range(prefix..)? {
+            let (key, value) = entry?;
+            if !key.value().starts_with(prefix) {
+                break
+            }
+            entries.push((key.value().to_vec(), value.value().to_vec()));
+        }
+        Ok(entries)
+    }
+}
@@ -11,10 +11,20 @@ use crate::storage::{
 };
 
 use fuel_crypto::Hasher;
+use fuel_merkle::sparse::{
+    in_memory::MerkleTree,
+    MerkleTreeKey,
+};
 use fuel_storage::{
+    BoxedIter,
     Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
     StorageAsRef,
+    StorageBatchInspect,
+    StorageColumns,
     StorageInspect,
+    StorageIter,
     StorageMutate,
     StorageRead,
     StorageSize,
@@ -34,42 +44,113 @@ use tai64::Tai64;
 
 use alloc::{
     borrow::Cow,
-    collections::BTreeMap,
+    boxed::Box,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     vec::Vec,
 };
-use core::convert::Infallible;
+use core::{
+    convert::Infallible,
+    ops::RangeInclusive,
+};
+
+use super::{
+    column::Column,
+    interpreter::{
+        contract_state_keys,
+        ContractsAssetsStorage,
+    },
+    overlay::contract_state_root,
+};
+
+mod overlay;
+mod state_roots;
 
-use super::interpreter::ContractsAssetsStorage;
+use overlay::{
+    Layers,
+    OverlayMap,
+};
+use state_roots::StateRoots;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone)]
 struct MemoryStorageInner {
-    contracts: BTreeMap<ContractId, Contract>,
-    balances: BTreeMap<ContractsAssetKey, Word>,
-    contract_state: BTreeMap<ContractsStateKey, ContractsStateData>,
+    contracts: OverlayMap<ContractId, Contract>,
+    balances: OverlayMap<ContractsAssetKey, Word>,
+    contract_state: OverlayMap<ContractsStateKey, ContractsStateData>,
     /// Mapping from consensus parameters version to consensus parameters.
-    consensus_parameters_versions: BTreeMap<u32, ConsensusParameters>,
+    consensus_parameters_versions: OverlayMap<u32, ConsensusParameters>,
     /// Mapping from state transition bytecode root to bytecode.
-    state_transition_bytecodes: BTreeMap<Bytes32, UploadedBytecode>,
+    state_transition_bytecodes: OverlayMap<Bytes32, UploadedBytecode>,
     /// Mapping from state transition bytecode version to hash.
-    state_transition_bytecodes_versions: BTreeMap<u32, Bytes32>,
+    state_transition_bytecodes_versions: OverlayMap<u32, Bytes32>,
+}
+
+impl MemoryStorageInner {
+    /// The table of the `column`, or `None` for the columns of the block metadata,
+    /// which aren't versioned by the checkpoints, and of the state trees, which the
+    /// memory storage doesn't keep.
+    fn table(&mut self, column: Column) -> Option<&mut dyn Layers> {
+        match column {
+            Column::ContractsRawCode => Some(&mut self.contracts),
+            Column::ContractsState => Some(&mut self.contract_state),
+            Column::ContractsAssets => Some(&mut self.balances),
+            Column::UploadedBytecodes => Some(&mut self.state_transition_bytecodes),
+            Column::ConsensusParametersVersions => {
+                Some(&mut self.consensus_parameters_versions)
+            }
+            Column::StateTransitionBytecodeVersions => {
+                Some(&mut self.state_transition_bytecodes_versions)
+            }
+            Column::BlockTimestamps
+            | Column::BlockHashes
+            | Column::Metadata
+            | Column::ContractsStateMerkleData
+            | Column::ContractsStateMerkleMetadata => None,
+        }
+    }
+
+    /// Applies `f` to the tables, in the order of their columns.
+    fn for_each_table(&mut self, mut f: impl FnMut(&mut dyn Layers)) {
+        for &column in Column::ALL {
+            if let Some(table) = self.table(column) {
+                f(table);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 /// In-memory storage implementation for the interpreter.
 ///
-/// It tracks 3 states:
+/// The storage is a persisted state with a stack of overlay layers of changes on top
+/// of it, read from the top layer down to the persisted state:
+///
+/// - [`begin`](Self::begin) opens a checkpoint with a new layer, and the checkpoints
+///   nest: a block builder can open a checkpoint for the block and one for each of its
+///   transactions.
+/// - [`commit`](Self::commit) closes the last checkpoint and keeps its changes in the
+///   enclosing checkpoint, or in the transacted state above the persisted state for the
+///   outermost checkpoint. [`revert`](Self::revert) closes it and discards its changes.
+/// - [`persist`](Self::persist) moves the transacted state to the persisted state, and
+///   [`rollback`](Self::rollback) discards it.
 ///
-/// - memory: the transactions will be applied to this state.
-/// - transacted: will receive the committed `memory` state.
-/// - persisted: will receive the persisted `transacted` state.
+/// The changes outside any checkpoint are applied to the transacted state, or to the
+/// persisted state if there is no transacted change.
 pub struct MemoryStorage {
     block_height: BlockHeight,
     coinbase: ContractId,
     consensus_parameters_version: u32,
     state_transition_version: u32,
-    memory: MemoryStorageInner,
-    transacted: MemoryStorageInner,
-    persisted: MemoryStorageInner,
+    state: MemoryStorageInner,
+    /// The index of the first layer of each open checkpoint, from the outermost one.
+    /// The layers below the first checkpoint are the transacted state.
+    checkpoints: Vec<usize>,
+    /// The number of layers of the tables.
+    layers: usize,
+    /// The cached trees of the state of the contracts.
+    state_roots: StateRoots,
 }
 
 impl MemoryStorage {
@@ -90,17 +171,33 @@ impl MemoryStorage {
             coinbase,
             consensus_parameters_version,
             state_transition_version,
-            memory: Default::default(),
-            transacted: Default::default(),
-            persisted: Default::default(),
+            state: Default::default(),
+            checkpoints: Vec::new(),
+            layers: 0,
+            state_roots: Default::default(),
         }
     }
 
-    /// Iterate over all contract state in storage
+    /// Iterate over all contract state in storage, in the order of the keys. Two
+    /// storages with the same state iterate over it in the same order, whatever the
+    /// order of the writes.
     pub fn all_contract_state(
         &self,
     ) -> impl Iterator<Item = (&ContractsStateKey, &ContractsStateData)> {
-        self.memory.contract_state.iter()
+        self.state.contract_state.iter()
+    }
+
+    /// Iterate over the state of the `contract`, in the order of the keys.
+    pub fn contract_state_entries(
+        &self,
+        contract: &ContractId,
+    ) -> impl Iterator<Item = (Bytes32, Cow<'_, ContractsStateData>)> + '_ {
+        self.storage::<ContractsState>()
+            .iter_prefix(contract.as_ref())
+            .map(|entry| {
+                let (key, value) = entry.expect("Infallible");
+                (*key.state_key(), value)
+            })
     }
 
     /// Fetch a mapping from the contract state.
@@ -115,26 +212,92 @@ impl MemoryStorage {
             .unwrap_or(Cow::Owned(ContractsStateData::default()))
     }
 
-    /// Set the transacted state to the memory state.
+    /// Opens a nested checkpoint. The changes until the matching
+    /// [`commit`](Self::commit) or [`revert`](Self::revert) are kept or discarded
+    /// together.
+    pub fn begin(&mut self) {
+        self.checkpoints.push(self.layers);
+        self.state.for_each_table(|table| table.push_layer());
+        self.layers = self.layers.saturating_add(1);
+    }
+
+    /// Closes the last open checkpoint and keeps its changes, in the enclosing
+    /// checkpoint or in the transacted state. Does nothing if no checkpoint is open.
     pub fn commit(&mut self) {
-        self.transacted = self.memory.clone();
+        self.checkpoints.pop();
     }
 
-    /// Revert the memory state to the transacted state.
+    /// Closes the last open checkpoint and discards its changes, including the changes
+    /// of the checkpoints committed inside it. Does nothing if no checkpoint is open.
     pub fn revert(&mut self) {
-        self.memory = self.transacted.clone();
+        if let Some(start) = self.checkpoints.pop() {
+            self.truncate_layers(start);
+        }
     }
 
-    /// Revert the memory and transacted changes to the persisted state.
+    /// The number of open checkpoints.
+    pub fn checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Collapses the layers of the committed checkpoints into the layer of their
+    /// enclosing checkpoint, or of the transacted state, so the reads traverse one
+    /// layer per open checkpoint. The state doesn't change.
+    pub fn flatten(&mut self) {
+        let mut end = self.layers;
+        for &start in self.checkpoints.iter().rev() {
+            self.state
+                .for_each_table(|table| table.merge_layers(start, end));
+            end = start;
+        }
+        self.state
+            .for_each_table(|table| table.merge_layers(0, end));
+
+        let transacted = usize::from(end > 0);
+        self.checkpoints
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, start)| *start = i.saturating_add(transacted));
+        self.layers = self.checkpoints.len().saturating_add(transacted);
+    }
+
+    /// Reverts the open checkpoints and the transacted state to the persisted state.
     pub fn rollback(&mut self) {
-        self.memory = self.persisted.clone();
-        self.transacted = self.persisted.clone();
+        self.checkpoints.clear();
+        self.truncate_layers(0);
     }
 
-    /// Persist the changes from transacted to memory+persisted state.
+    /// Reverts the open checkpoints and persists the transacted state.
     pub fn persist(&mut self) {
-        self.memory = self.transacted.clone();
-        self.persisted = self.transacted.clone();
+        if let Some(&start) = self.checkpoints.first() {
+            self.truncate_layers(start);
+        }
+        self.checkpoints.clear();
+        self.state.for_each_table(|table| table.merge_into_base());
+        self.layers = 0;
+    }
+
+    fn truncate_layers(&mut self, len: usize) {
+        for key in self.state.contract_state.keys_above(len) {
+            self.state_roots.touch(key);
+        }
+        self.state
+            .for_each_table(|table| table.truncate_layers(len));
+        self.layers = len;
+    }
+
+    fn insert_slot(
+        &mut self,
+        key: ContractsStateKey,
+        value: ContractsStateData,
+    ) -> Option<ContractsStateData> {
+        self.state_roots.touch(&key);
+        self.state.contract_state.insert(key, value)
+    }
+
+    fn remove_slot(&mut self, key: &ContractsStateKey) -> Option<ContractsStateData> {
+        self.state_roots.touch(key);
+        self.state.contract_state.remove(key)
     }
 
     #[cfg(feature = "test-helpers")]
@@ -159,27 +322,28 @@ impl MemoryStorage {
     }
 
     #[cfg(feature = "test-helpers")]
-    /// Returns mutable reference to the consensus parameters versions table.
+    /// Returns mutable reference to the persisted consensus parameters versions table.
     pub fn consensus_parameters_versions_mut(
         &mut self,
     ) -> &mut BTreeMap<u32, ConsensusParameters> {
-        &mut self.memory.consensus_parameters_versions
+        self.state.consensus_parameters_versions.base_mut()
     }
 
     #[cfg(feature = "test-helpers")]
-    /// Returns mutable reference to the state transition bytecodes table.
+    /// Returns mutable reference to the persisted state transition bytecodes table.
     pub fn state_transition_bytecodes_mut(
         &mut self,
     ) -> &mut BTreeMap<Bytes32, UploadedBytecode> {
-        &mut self.memory.state_transition_bytecodes
+        self.state.state_transition_bytecodes.base_mut()
     }
 
     #[cfg(feature = "test-helpers")]
-    /// Returns mutable reference to the state transition bytecodes versions table.
+    /// Returns mutable reference to the persisted state transition bytecodes versions
+    /// table.
     pub fn state_transition_bytecodes_versions_mut(
         &mut self,
     ) -> &mut BTreeMap<u32, Bytes32> {
-        &mut self.memory.state_transition_bytecodes_versions
+        self.state.state_transition_bytecodes_versions.base_mut()
     }
 }
 
@@ -192,15 +356,282 @@ impl Default for MemoryStorage {
     }
 }
 
+/// The snapshot of the state of a [`MemoryStorage`], created by
+/// [`MemoryStorage::snapshot`] and restored by [`MemoryStorage::from_snapshot`].
+///
+/// The snapshot is serialized with the version of its format, so the snapshots of the
+/// previous versions can still be loaded. The tables are sorted maps, so two snapshots
+/// of the same state serialize to the same bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageSnapshot {
+    /// The first version of the snapshot.
+    V1(StorageSnapshotV1),
+}
+
+/// The first version of the [`StorageSnapshot`], with every table of the
+/// [`MemoryStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageSnapshotV1 {
+    /// The block height of the chain.
+    pub block_height: BlockHeight,
+    /// The coinbase contract.
+    pub coinbase: ContractId,
+    /// The current consensus parameters version.
+    pub consensus_parameters_version: u32,
+    /// The current state transition version.
+    pub state_transition_version: u32,
+    /// The bytecode of the contracts.
+    pub contracts: BTreeMap<ContractId, Contract>,
+    /// The balances of the contracts.
+    pub balances: BTreeMap<ContractsAssetKey, Word>,
+    /// The state slots of the contracts.
+    pub contract_state: BTreeMap<ContractsStateKey, ContractsStateData>,
+    /// Mapping from consensus parameters version to consensus parameters.
+    pub consensus_parameters_versions: BTreeMap<u32, ConsensusParameters>,
+    /// Mapping from state transition bytecode root to bytecode.
+    pub state_transition_bytecodes: BTreeMap<Bytes32, UploadedBytecode>,
+    /// Mapping from state transition bytecode version to hash.
+    pub state_transition_bytecodes_versions: BTreeMap<u32, Bytes32>,
+}
+
+impl MemoryStorage {
+    /// Takes the snapshot of the state of the storage, including the changes of the
+    /// open checkpoints.
+    pub fn snapshot(&self) -> StorageSnapshot {
+        fn table<K: Ord + Clone, V: Clone>(table: &OverlayMap<K, V>) -> BTreeMap<K, V> {
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        }
+
+        let MemoryStorageInner {
+            contracts,
+            balances,
+            contract_state,
+            consensus_parameters_versions,
+            state_transition_bytecodes,
+            state_transition_bytecodes_versions,
+        } = &self.state;
+
+        StorageSnapshot::V1(StorageSnapshotV1 {
+            block_height: self.block_height,
+            coinbase: self.coinbase,
+            consensus_parameters_version: self.consensus_parameters_version,
+            state_transition_version: self.state_transition_version,
+            contracts: table(contracts),
+            balances: table(balances),
+            contract_state: table(contract_state),
+            consensus_parameters_versions: table(consensus_parameters_versions),
+            state_transition_bytecodes: table(state_transition_bytecodes),
+            state_transition_bytecodes_versions: table(
+                state_transition_bytecodes_versions,
+            ),
+        })
+    }
+
+    /// Creates a storage with the state of the `snapshot` as its persisted state.
+    pub fn from_snapshot(snapshot: StorageSnapshot) -> Self {
+        let StorageSnapshot::V1(StorageSnapshotV1 {
+            block_height,
+            coinbase,
+            consensus_parameters_version,
+            state_transition_version,
+            contracts,
+            balances,
+            contract_state,
+            consensus_parameters_versions,
+            state_transition_bytecodes,
+            state_transition_bytecodes_versions,
+        }) = snapshot;
+
+        let state = MemoryStorageInner {
+            contracts: contracts.into(),
+            balances: balances.into(),
+            contract_state: contract_state.into(),
+            consensus_parameters_versions: consensus_parameters_versions.into(),
+            state_transition_bytecodes: state_transition_bytecodes.into(),
+            state_transition_bytecodes_versions: state_transition_bytecodes_versions
+                .into(),
+        };
+
+        Self {
+            block_height,
+            coinbase,
+            consensus_parameters_version,
+            state_transition_version,
+            state,
+            checkpoints: Vec::new(),
+            layers: 0,
+            state_roots: Default::default(),
+        }
+    }
+
+    /// Saves the [snapshot](Self::snapshot) of the storage to the file at the `path`,
+    /// encoded with `postcard`.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn save_to<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let bytes = postcard::to_allocvec(&self.snapshot()).map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, error.to_string())
+        })?;
+        std::fs::write(path, bytes)
+    }
+
+    /// Loads the storage from the snapshot saved to the file at the `path` by
+    /// [`save_to`](Self::save_to).
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let snapshot = postcard::from_bytes(&bytes).map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, error.to_string())
+        })?;
+        Ok(Self::from_snapshot(snapshot))
+    }
+}
+
+/// The changed entry of a table between two storages: the value in the first storage
+/// and the value in the second one, `None` for an absent key.
+pub type DiffEntry<V> = (Option<V>, Option<V>);
+
+/// The entries of the tables that differ between two [`MemoryStorage`]s, returned by
+/// [`MemoryStorage::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageDiff {
+    /// The changed bytecode of the contracts.
+    pub contracts: BTreeMap<ContractId, DiffEntry<Contract>>,
+    /// The changed balances of the contracts.
+    pub balances: BTreeMap<ContractsAssetKey, DiffEntry<Word>>,
+    /// The changed state slots of the contracts.
+    pub contract_state: BTreeMap<ContractsStateKey, DiffEntry<ContractsStateData>>,
+    /// The changed consensus parameters by version.
+    pub consensus_parameters_versions: BTreeMap<u32, DiffEntry<ConsensusParameters>>,
+    /// The changed state transition bytecodes by root.
+    pub state_transition_bytecodes: BTreeMap<Bytes32, DiffEntry<UploadedBytecode>>,
+    /// The changed state transition bytecode roots by version.
+    pub state_transition_bytecodes_versions: BTreeMap<u32, DiffEntry<Bytes32>>,
+}
+
+impl StorageDiff {
+    /// Returns `true` if the storages have the same entries.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+            && self.balances.is_empty()
+            && self.contract_state.is_empty()
+            && self.consensus_parameters_versions.is_empty()
+            && self.state_transition_bytecodes.is_empty()
+            && self.state_transition_bytecodes_versions.is_empty()
+    }
+}
+
+impl MemoryStorage {
+    /// The commitment to the state and the balances of all the contracts.
+    ///
+    /// It is the root of the sparse Merkle tree with a leaf for each contract with code,
+    /// state or balances, keyed by the contract id. The leaf is the root of the state of
+    /// the contract, like [`Contract::initial_state_root`], followed by the root of the
+    /// sparse Merkle tree of its balances keyed by the asset id, with the big-endian
+    /// bytes of the balances as the values.
+    pub fn global_state_root(&self) -> Bytes32 {
+        let contracts = self
+            .state
+            .contracts
+            .iter()
+            .map(|(id, _)| *id)
+            .chain(
+                self.state
+                    .balances
+                    .iter()
+                    .map(|(key, _)| *key.contract_id()),
+            )
+            .chain(
+                self.state
+                    .contract_state
+                    .iter()
+                    .map(|(key, _)| *key.contract_id()),
+            )
+            .collect::<BTreeSet<_>>();
+        let leaves = contracts
+            .iter()
+            .map(|contract| {
+                let state_root = contract_state_root(self, contract).expect("Infallible");
+                let balances = self
+                    .storage::<ContractsAssets>()
+                    .iter_prefix(contract.as_ref())
+                    .map(|entry| {
+                        let (key, balance) = entry.expect("Infallible");
+                        (MerkleTreeKey::new(key.asset_id()), balance.to_be_bytes())
+                    })
+                    .collect::<Vec<_>>();
+                let balances_root = MerkleTree::root_from_set(balances.into_iter());
+                let mut leaf = [0u8; 64];
+                leaf[..32].copy_from_slice(&state_root);
+                leaf[32..].copy_from_slice(&balances_root);
+                (MerkleTreeKey::new(contract), leaf)
+            })
+            .collect::<Vec<_>>();
+        MerkleTree::root_from_set(leaves.into_iter()).into()
+    }
+
+    /// The entries of the tables that differ from the ones of the `other` storage,
+    /// including the changes of the open checkpoints of both.
+    pub fn diff(&self, other: &Self) -> StorageDiff {
+        fn table<K, V>(
+            left: &OverlayMap<K, V>,
+            right: &OverlayMap<K, V>,
+        ) -> BTreeMap<K, DiffEntry<V>>
+        where
+            K: Ord + Clone,
+            V: Clone + PartialEq,
+        {
+            let mut diff = BTreeMap::new();
+            for (key, value) in left.iter() {
+                if right.get(key) != Some(value) {
+                    diff.insert(
+                        key.clone(),
+                        (Some(value.clone()), right.get(key).cloned()),
+                    );
+                }
+            }
+            for (key, value) in right.iter() {
+                if !left.contains_key(key) {
+                    diff.insert(key.clone(), (None, Some(value.clone())));
+                }
+            }
+            diff
+        }
+
+        let (left, right) = (&self.state, &other.state);
+        StorageDiff {
+            contracts: table(&left.contracts, &right.contracts),
+            balances: table(&left.balances, &right.balances),
+            contract_state: table(&left.contract_state, &right.contract_state),
+            consensus_parameters_versions: table(
+                &left.consensus_parameters_versions,
+                &right.consensus_parameters_versions,
+            ),
+            state_transition_bytecodes: table(
+                &left.state_transition_bytecodes,
+                &right.state_transition_bytecodes,
+            ),
+            state_transition_bytecodes_versions: table(
+                &left.state_transition_bytecodes_versions,
+                &right.state_transition_bytecodes_versions,
+            ),
+        }
+    }
+}
+
 impl StorageInspect<ContractsRawCode> for MemoryStorage {
     type Error = Infallible;
 
     fn get(&self, key: &ContractId) -> Result<Option<Cow<'_, Contract>>, Infallible> {
-        Ok(self.memory.contracts.get(key).map(Cow::Borrowed))
+        Ok(self.state.contracts.get(key).map(Cow::Borrowed))
     }
 
     fn contains_key(&self, key: &ContractId) -> Result<bool, Infallible> {
-        Ok(self.memory.contracts.contains_key(key))
+        Ok(self.state.contracts.contains_key(key))
     }
 }
 
@@ -210,18 +641,18 @@ impl StorageMutate<ContractsRawCode> for MemoryStorage {
         key: &ContractId,
         value: &[u8],
     ) -> Result<Option<Contract>, Infallible> {
-        Ok(self.memory.contracts.insert(*key, value.into()))
+        Ok(self.state.contracts.insert(*key, value.into()))
     }
 
     fn remove(&mut self, key: &ContractId) -> Result<Option<Contract>, Infallible> {
-        Ok(self.memory.contracts.remove(key))
+        Ok(self.state.contracts.remove(key))
     }
 }
 
 impl StorageWrite<ContractsRawCode> for MemoryStorage {
     fn write(&mut self, key: &ContractId, buf: &[u8]) -> Result<usize, Infallible> {
         let size = buf.len();
-        self.memory.contracts.insert(*key, Contract::from(buf));
+        self.state.contracts.insert(*key, Contract::from(buf));
         Ok(size)
     }
 
@@ -232,7 +663,7 @@ impl StorageWrite<ContractsRawCode> for MemoryStorage {
     ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
         let size = buf.len();
         let prev = self
-            .memory
+            .state
             .contracts
             .insert(*key, Contract::from(buf))
             .map(Into::into);
@@ -240,14 +671,31 @@ impl StorageWrite<ContractsRawCode> for MemoryStorage {
     }
 
     fn take(&mut self, key: &ContractId) -> Result<Option<Vec<u8>>, Self::Error> {
-        let prev = self.memory.contracts.remove(key).map(Into::into);
+        let prev = self.state.contracts.remove(key).map(Into::into);
         Ok(prev)
     }
+
+    fn write_range(
+        &mut self,
+        key: &ContractId,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, Self::Error> {
+        let mut code = self
+            .state
+            .contracts
+            .get(key)
+            .map(|c| c.as_ref().to_vec())
+            .unwrap_or_default();
+        write_range_into(&mut code, offset, buf);
+        self.state.contracts.insert(*key, code.into());
+        Ok(buf.len())
+    }
 }
 
 impl StorageSize<ContractsRawCode> for MemoryStorage {
     fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Infallible> {
-        Ok(self.memory.contracts.get(key).map(|c| c.as_ref().len()))
+        Ok(self.state.contracts.get(key).map(|c| c.as_ref().len()))
     }
 }
 
@@ -257,7 +705,7 @@ impl StorageRead<ContractsRawCode> for MemoryStorage {
         key: &ContractId,
         buf: &mut [u8],
     ) -> Result<Option<usize>, Self::Error> {
-        Ok(self.memory.contracts.get(key).map(|c| {
+        Ok(self.state.contracts.get(key).map(|c| {
             let len = buf.len().min(c.as_ref().len());
             buf.copy_from_slice(&c.as_ref()[..len]);
             len
@@ -265,7 +713,35 @@ impl StorageRead<ContractsRawCode> for MemoryStorage {
     }
 
     fn read_alloc(&self, key: &ContractId) -> Result<Option<Vec<u8>>, Self::Error> {
-        Ok(self.memory.contracts.get(key).map(|c| c.as_ref().to_vec()))
+        Ok(self.state.contracts.get(key).map(|c| c.as_ref().to_vec()))
+    }
+
+    fn read_range(
+        &self,
+        key: &ContractId,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        Ok(self
+            .state
+            .contracts
+            .get(key)
+            .map(|c| read_range_from(c.as_ref(), offset, buf)))
+    }
+}
+
+impl StorageIter<ContractsRawCode> for MemoryStorage {
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, ContractsRawCode, Infallible> {
+        let entries = prefix_range(prefix)
+            .into_iter()
+            .flat_map(|range| {
+                let (start, end) = range.into_inner();
+                self.state
+                    .contracts
+                    .range(ContractId::from(start)..=ContractId::from(end))
+            })
+            .map(|(key, value)| Ok((*key, Cow::Borrowed(value))));
+        Box::new(entries)
     }
 }
 
@@ -277,7 +753,7 @@ impl StorageInspect<UploadedBytecodes> for MemoryStorage {
         key: &<UploadedBytecodes as Mappable>::Key,
     ) -> Result<Option<Cow<'_, UploadedBytecode>>, Infallible> {
         Ok(self
-            .memory
+            .state
             .state_transition_bytecodes
             .get(key)
             .map(Cow::Borrowed))
@@ -287,7 +763,7 @@ impl StorageInspect<UploadedBytecodes> for MemoryStorage {
         &self,
         key: &<UploadedBytecodes as Mappable>::Key,
     ) -> Result<bool, Infallible> {
-        Ok(self.memory.state_transition_bytecodes.contains_key(key))
+        Ok(self.state.state_transition_bytecodes.contains_key(key))
     }
 }
 
@@ -298,7 +774,7 @@ impl StorageMutate<UploadedBytecodes> for MemoryStorage {
         value: &<UploadedBytecodes as Mappable>::Value,
     ) -> Result<Option<UploadedBytecode>, Infallible> {
         Ok(self
-            .memory
+            .state
             .state_transition_bytecodes
             .insert(*key, value.clone()))
     }
@@ -307,7 +783,7 @@ impl StorageMutate<UploadedBytecodes> for MemoryStorage {
         &mut self,
         key: &<UploadedBytecodes as Mappable>::Key,
     ) -> Result<Option<UploadedBytecode>, Infallible> {
-        Ok(self.memory.state_transition_bytecodes.remove(key))
+        Ok(self.state.state_transition_bytecodes.remove(key))
     }
 }
 
@@ -318,14 +794,14 @@ impl StorageInspect<ContractsAssets> for MemoryStorage {
         &self,
         key: &<ContractsAssets as Mappable>::Key,
     ) -> Result<Option<Cow<'_, Word>>, Infallible> {
-        Ok(self.memory.balances.get(key).map(Cow::Borrowed))
+        Ok(self.state.balances.get(key).map(Cow::Borrowed))
     }
 
     fn contains_key(
         &self,
         key: &<ContractsAssets as Mappable>::Key,
     ) -> Result<bool, Infallible> {
-        Ok(self.memory.balances.contains_key(key))
+        Ok(self.state.balances.contains_key(key))
     }
 }
 
@@ -335,14 +811,30 @@ impl StorageMutate<ContractsAssets> for MemoryStorage {
         key: &<ContractsAssets as Mappable>::Key,
         value: &Word,
     ) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.insert(*key, *value))
+        Ok(self.state.balances.insert(*key, *value))
     }
 
     fn remove(
         &mut self,
         key: &<ContractsAssets as Mappable>::Key,
     ) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.remove(key))
+        Ok(self.state.balances.remove(key))
+    }
+}
+
+impl StorageIter<ContractsAssets> for MemoryStorage {
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, ContractsAssets, Infallible> {
+        let entries = prefix_range(prefix)
+            .into_iter()
+            .flat_map(|range| {
+                let (start, end) = range.into_inner();
+                self.state.balances.range(
+                    ContractsAssetKey::from_array(start)
+                        ..=ContractsAssetKey::from_array(end),
+                )
+            })
+            .map(|(key, value)| Ok((*key, Cow::Borrowed(value))));
+        Box::new(entries)
     }
 }
 
@@ -354,14 +846,14 @@ impl StorageInspect<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<Cow<'_, <ContractsState as Mappable>::OwnedValue>>, Infallible>
     {
-        Ok(self.memory.contract_state.get(key).map(Cow::Borrowed))
+        Ok(self.state.contract_state.get(key).map(Cow::Borrowed))
     }
 
     fn contains_key(
         &self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<bool, Infallible> {
-        Ok(self.memory.contract_state.contains_key(key))
+        Ok(self.state.contract_state.contains_key(key))
     }
 }
 
@@ -371,14 +863,14 @@ impl StorageMutate<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
         value: &<ContractsState as Mappable>::Value,
     ) -> Result<Option<<ContractsState as Mappable>::OwnedValue>, Infallible> {
-        Ok(self.memory.contract_state.insert(*key, value.into()))
+        Ok(self.insert_slot(*key, value.into()))
     }
 
     fn remove(
         &mut self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<ContractsStateData>, Infallible> {
-        Ok(self.memory.contract_state.remove(key))
+        Ok(self.remove_slot(key))
     }
 }
 
@@ -389,9 +881,7 @@ impl StorageWrite<ContractsState> for MemoryStorage {
         buf: &[u8],
     ) -> Result<usize, Infallible> {
         let size = buf.len();
-        self.memory
-            .contract_state
-            .insert(*key, ContractsStateData::from(buf));
+        self.insert_slot(*key, ContractsStateData::from(buf));
         Ok(size)
     }
 
@@ -405,9 +895,7 @@ impl StorageWrite<ContractsState> for MemoryStorage {
     {
         let size = buf.len();
         let prev = self
-            .memory
-            .contract_state
-            .insert(*key, ContractsStateData::from(buf))
+            .insert_slot(*key, ContractsStateData::from(buf))
             .map(Into::into);
         Ok((size, prev))
     }
@@ -416,9 +904,26 @@ impl StorageWrite<ContractsState> for MemoryStorage {
         &mut self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
-        let prev = self.memory.contract_state.remove(key).map(Into::into);
+        let prev = self.remove_slot(key).map(Into::into);
         Ok(prev)
     }
+
+    fn write_range(
+        &mut self,
+        key: &<ContractsState as Mappable>::Key,
+        offset: usize,
+        buf: &[u8],
+    ) -> Result<usize, Self::Error> {
+        let mut data = self
+            .state
+            .contract_state
+            .get(key)
+            .map(|data| data.as_ref().to_vec())
+            .unwrap_or_default();
+        write_range_into(&mut data, offset, buf);
+        self.insert_slot(*key, data.into());
+        Ok(buf.len())
+    }
 }
 
 impl StorageSize<ContractsState> for MemoryStorage {
@@ -426,11 +931,7 @@ impl StorageSize<ContractsState> for MemoryStorage {
         &self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<usize>, Infallible> {
-        Ok(self
-            .memory
-            .contract_state
-            .get(key)
-            .map(|c| c.as_ref().len()))
+        Ok(self.state.contract_state.get(key).map(|c| c.as_ref().len()))
     }
 }
 
@@ -440,7 +941,7 @@ impl StorageRead<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
         buf: &mut [u8],
     ) -> Result<Option<usize>, Self::Error> {
-        Ok(self.memory.contract_state.get(key).map(|data| {
+        Ok(self.state.contract_state.get(key).map(|data| {
             let len = buf.len().min(data.as_ref().len());
             buf.copy_from_slice(&data.as_ref()[..len]);
             len
@@ -452,13 +953,61 @@ impl StorageRead<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
         Ok(self
-            .memory
+            .state
             .contract_state
             .get(key)
             .map(|c| c.as_ref().to_vec()))
     }
+
+    fn read_range(
+        &self,
+        key: &<ContractsState as Mappable>::Key,
+        offset: usize,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        Ok(self
+            .state
+            .contract_state
+            .get(key)
+            .map(|data| read_range_from(data.as_ref(), offset, buf)))
+    }
+}
+
+impl StorageIter<ContractsState> for MemoryStorage {
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, ContractsState, Infallible> {
+        let entries = prefix_range(prefix)
+            .into_iter()
+            .flat_map(|range| {
+                let (start, end) = range.into_inner();
+                self.state.contract_state.range(
+                    ContractsStateKey::from_array(start)
+                        ..=ContractsStateKey::from_array(end),
+                )
+            })
+            .map(|(key, value)| Ok((*key, Cow::Borrowed(value))));
+        Box::new(entries)
+    }
+}
+
+impl MerkleRootStorage<ContractId, ContractsState> for MemoryStorage {
+    /// The root of the state of the contract, computed like
+    /// [`Contract::initial_state_root`]. The tree of the state is cached by the first
+    /// call and updated with the slots changed since the previous call.
+    fn root(&self, contract: &ContractId) -> Result<MerkleRoot, Infallible> {
+        let slots = self.state.contract_state.range(
+            ContractsStateKey::new(contract, &Bytes32::zeroed())
+                ..=ContractsStateKey::new(contract, &Bytes32::new([u8::MAX; 32])),
+        );
+        Ok(self.state_roots.root(
+            contract,
+            |key| self.state.contract_state.get(&(contract, key).into()),
+            slots.map(|(key, value)| (*key.state_key(), value)),
+        ))
+    }
 }
 
+impl StorageBatchInspect<ContractsState> for MemoryStorage {}
+
 impl ContractsAssetsStorage for MemoryStorage {}
 
 impl InterpreterStorage for MemoryStorage {
@@ -497,7 +1046,7 @@ impl InterpreterStorage for MemoryStorage {
         consensus_parameters: &ConsensusParameters,
     ) -> Result<Option<ConsensusParameters>, Self::DataError> {
         Ok(self
-            .memory
+            .state
             .consensus_parameters_versions
             .insert(version, consensus_parameters.clone()))
     }
@@ -508,48 +1057,11 @@ impl InterpreterStorage for MemoryStorage {
         bytecode: &Bytes32,
     ) -> Result<Option<Bytes32>, Self::DataError> {
         Ok(self
-            .memory
+            .state
             .state_transition_bytecodes_versions
             .insert(version, *bytecode))
     }
 
-    fn contract_state_range(
-        &self,
-        id: &ContractId,
-        start_key: &Bytes32,
-        range: usize,
-    ) -> Result<Vec<Option<Cow<ContractsStateData>>>, Self::DataError> {
-        let start: ContractsStateKey = (id, start_key).into();
-        let end: ContractsStateKey = (id, &Bytes32::new([u8::MAX; 32])).into();
-        let mut iter = self.memory.contract_state.range(start..end);
-
-        let mut next_item = iter.next();
-        Ok(core::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .map(|next_key: [u8; 32]| match next_item.take() {
-            Some((k, v)) => match next_key.cmp(k.state_key()) {
-                core::cmp::Ordering::Less => {
-                    next_item = Some((k, v));
-                    None
-                }
-                core::cmp::Ordering::Equal => {
-                    next_item = iter.next();
-                    Some(Cow::Borrowed(v))
-                }
-                core::cmp::Ordering::Greater => None,
-            },
-            None => None,
-        })
-        .take(range)
-        .collect())
-    }
-
     fn contract_state_insert_range<'a, I>(
         &mut self,
         contract: &ContractId,
@@ -562,23 +1074,15 @@ impl InterpreterStorage for MemoryStorage {
         let storage: &mut dyn StorageWrite<ContractsState, Error = Self::DataError> =
             self;
         let mut unset_count = 0;
-        core::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .zip(values)
-        .try_for_each(|(key, value)| {
-            let key: ContractsStateKey = (contract, &Bytes32::from(key)).into();
-            if !storage.contains_key(&key)? {
-                unset_count += 1;
-            }
-            storage.write(&key, value)?;
-            Ok::<_, Self::DataError>(())
-        })?;
+        contract_state_keys(contract, start_key, usize::MAX)
+            .zip(values)
+            .try_for_each(|(key, value)| {
+                if !storage.contains_key(&key)? {
+                    unset_count += 1;
+                }
+                storage.write(&key, value)?;
+                Ok::<_, Self::DataError>(())
+            })?;
         Ok(unset_count)
     }
 
@@ -588,40 +1092,78 @@ impl InterpreterStorage for MemoryStorage {
         start_key: &Bytes32,
         range: usize,
     ) -> Result<Option<()>, Self::DataError> {
-        let mut all_set_key = true;
-        let mut values: hashbrown::HashSet<_> =
-            core::iter::successors(Some(**start_key), |n| {
-                let mut n = *n;
-                if add_one(&mut n) {
-                    None
-                } else {
-                    Some(n)
-                }
-            })
-            .take(range)
-            .collect();
-        self.memory.contract_state.retain(|key, _| {
-            let c = key.contract_id();
-            let k = key.state_key();
-            let r = values.remove(&**k);
-            all_set_key &= c == contract && r;
-            c != contract || !r
+        let Some((last_key, len)) = last_key_of_range(start_key, range) else {
+            return Ok(Some(()))
+        };
+        // Iterates over the set keys of the range instead of probing each key, so the
+        // cost doesn't depend on the length of the range.
+        let start: ContractsStateKey = (contract, start_key).into();
+        let end: ContractsStateKey = (contract, &last_key).into();
+        let keys = self
+            .state
+            .contract_state
+            .range(start..=end)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        keys.iter().for_each(|key| {
+            self.remove_slot(key);
         });
-        Ok((all_set_key && values.is_empty()).then_some(()))
+        Ok((keys.len() == len).then_some(()))
     }
 }
 
-fn add_one(a: &mut [u8; 32]) -> bool {
-    let right = u128::from_be_bytes(a[16..].try_into().unwrap());
-    let (right, of) = right.overflowing_add(1);
-    a[16..].copy_from_slice(&right.to_be_bytes()[..]);
-    if of {
-        let left = u128::from_be_bytes(a[..16].try_into().unwrap());
-        let (left, of) = left.overflowing_add(1);
-        a[..16].copy_from_slice(&left.to_be_bytes()[..]);
-        return of
+/// Copies the bytes of the `value` from the `offset` into the `buf`, returning the
+/// number of bytes copied.
+pub(super) fn read_range_from(value: &[u8], offset: usize, buf: &mut [u8]) -> usize {
+    let value = value.get(offset..).unwrap_or_default();
+    let len = buf.len().min(value.len());
+    buf[..len].copy_from_slice(&value[..len]);
+    len
+}
+
+/// Writes the `buf` into the `value` from the `offset`, extending the `value` with zeros
+/// if it ends before.
+fn write_range_into(value: &mut Vec<u8>, offset: usize, buf: &[u8]) {
+    let end = offset.saturating_add(buf.len());
+    if value.len() < end {
+        value.resize(end, 0);
+    }
+    value[offset..end].copy_from_slice(buf);
+}
+
+/// The range of the `N`-byte keys starting with the `prefix`, or `None` if the prefix
+/// is longer than the keys.
+fn prefix_range<const N: usize>(prefix: &[u8]) -> Option<RangeInclusive<[u8; N]>> {
+    let mut start = [0; N];
+    let mut end = [u8::MAX; N];
+    start.get_mut(..prefix.len())?.copy_from_slice(prefix);
+    end.get_mut(..prefix.len())?.copy_from_slice(prefix);
+    Some(start..=end)
+}
+
+/// The last key and the number of keys of the `range` consecutive keys from the
+/// `start_key`, stopping at the last key of the state. `None` for the empty range.
+pub(super) fn last_key_of_range(
+    start_key: &Bytes32,
+    range: usize,
+) -> Option<(Bytes32, usize)> {
+    let offset = u128::try_from(range.checked_sub(1)?).ok()?;
+    let high = u128::from_be_bytes(start_key[..16].try_into().unwrap());
+    let low = u128::from_be_bytes(start_key[16..].try_into().unwrap());
+    let (low, carry) = low.overflowing_add(offset);
+    let mut last_key = [0; 32];
+    match high.checked_add(u128::from(carry)) {
+        Some(high) => {
+            last_key[..16].copy_from_slice(&high.to_be_bytes());
+            last_key[16..].copy_from_slice(&low.to_be_bytes());
+            Some((last_key.into(), range))
+        }
+        None => {
+            // The `low + 1` keys after the last key of the state are out of the range.
+            let overflow = usize::try_from(low).ok()?.checked_add(1)?;
+            Some((Bytes32::new([u8::MAX; 32]), range.checked_sub(overflow)?))
+        }
     }
-    false
 }
 
 #[cfg(test)]
@@ -651,7 +1193,7 @@ mod tests {
     ) -> Vec<Option<ContractsStateData>> {
         let mut mem = MemoryStorage::default();
         for k in store {
-            mem.memory.contract_state.insert(
+            mem.state.contract_state.insert(
                 (&ContractId::default(), &(**k).into()).into(),
                 Default::default(),
             );
@@ -0,0 +1,176 @@
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::ops::RangeBounds;
+
+/// A sorted map with a stack of overlay layers on top of a base map.
+///
+/// The writes go to the top layer, or to the base map if there is no layer, and the
+/// reads traverse the layers from the top down to the base map.
+#[derive(Debug, Clone)]
+pub(super) struct OverlayMap<K, V> {
+    base: BTreeMap<K, V>,
+    /// The layers, from the bottom to the top. `None` removes the key from the lower
+    /// layers and the base map.
+    layers: Vec<BTreeMap<K, Option<V>>>,
+}
+
+impl<K, V> Default for OverlayMap<K, V> {
+    fn default() -> Self {
+        Self {
+            base: BTreeMap::new(),
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for OverlayMap<K, V> {
+    fn from(base: BTreeMap<K, V>) -> Self {
+        Self {
+            base,
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl<K, V> OverlayMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    pub fn get(&self, key: &K) -> Option<&V> {
+        Self::get_below(&self.base, &self.layers, key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.write(key, Some(value))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.write(key.clone(), None)
+    }
+
+    /// Iterates over the entries of the map in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.range(..)
+    }
+
+    /// Iterates over the entries of the map in the `range` of keys, in key order.
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = (&K, &V)>
+    where
+        R: RangeBounds<K> + Clone,
+    {
+        let mut entries = self
+            .base
+            .range(range.clone())
+            .map(|(key, value)| (key, Some(value)))
+            .collect::<BTreeMap<_, _>>();
+        for layer in self.layers.iter() {
+            entries.extend(
+                layer
+                    .range(range.clone())
+                    .map(|(key, value)| (key, value.as_ref())),
+            );
+        }
+        entries
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+    }
+
+    /// The keys written in the layers above the first `len` layers.
+    pub fn keys_above(&self, len: usize) -> impl Iterator<Item = &K> {
+        self.layers.iter().skip(len).flat_map(BTreeMap::keys)
+    }
+
+    /// The base map, under the changes of the layers.
+    #[cfg(feature = "test-helpers")]
+    pub fn base_mut(&mut self) -> &mut BTreeMap<K, V> {
+        &mut self.base
+    }
+
+    fn get_below<'a>(
+        base: &'a BTreeMap<K, V>,
+        layers: &'a [BTreeMap<K, Option<V>>],
+        key: &K,
+    ) -> Option<&'a V> {
+        for layer in layers.iter().rev() {
+            if let Some(value) = layer.get(key) {
+                return value.as_ref()
+            }
+        }
+        base.get(key)
+    }
+
+    fn write(&mut self, key: K, value: Option<V>) -> Option<V> {
+        let Some((top, below)) = self.layers.split_last_mut() else {
+            return match value {
+                Some(value) => self.base.insert(key, value),
+                None => self.base.remove(&key),
+            }
+        };
+        let previous = match top.get(&key) {
+            Some(previous) => previous.clone(),
+            None => Self::get_below(&self.base, below, &key).cloned(),
+        };
+        top.insert(key, value);
+        previous
+    }
+}
+
+/// The operations on the layers of an [`OverlayMap`], applied to all the tables of the
+/// storage together.
+pub(super) trait Layers {
+    /// Pushes an empty layer on top of the stack.
+    fn push_layer(&mut self);
+
+    /// Discards the layers above the first `len` layers.
+    fn truncate_layers(&mut self, len: usize);
+
+    /// Merges the layers in `start..end` into the layer at `start`.
+    fn merge_layers(&mut self, start: usize, end: usize);
+
+    /// Merges all the layers into the base map.
+    fn merge_into_base(&mut self);
+}
+
+impl<K, V> Layers for OverlayMap<K, V>
+where
+    K: Ord,
+{
+    fn push_layer(&mut self) {
+        self.layers.push(BTreeMap::new());
+    }
+
+    fn truncate_layers(&mut self, len: usize) {
+        self.layers.truncate(len);
+    }
+
+    fn merge_layers(&mut self, start: usize, end: usize) {
+        if end <= start.saturating_add(1) {
+            return
+        }
+        let merged = self
+            .layers
+            .drain(start.saturating_add(1)..end)
+            .collect::<Vec<_>>();
+        if let Some(layer) = self.layers.get_mut(start) {
+            merged.into_iter().for_each(|changes| layer.extend(changes));
+        }
+    }
+
+    fn merge_into_base(&mut self) {
+        for layer in self.layers.drain(..) {
+            for (key, value) in layer {
+                match value {
+                    Some(value) => self.base.insert(key, value),
+                    None => self.base.remove(&key),
+                };
+            }
+        }
+    }
+}
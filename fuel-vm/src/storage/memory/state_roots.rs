@@ -0,0 +1,94 @@
+use crate::storage::{
+    ContractsStateData,
+    ContractsStateKey,
+};
+
+use fuel_merkle::sparse::{
+    in_memory::MerkleTree,
+    MerkleTreeKey,
+};
+use fuel_storage::MerkleRoot;
+use fuel_types::{
+    Bytes32,
+    ContractId,
+};
+
+use alloc::{
+    collections::{
+        btree_map::Entry,
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    mem,
+};
+
+/// The sparse Merkle trees of the state of the contracts whose root was queried, kept
+/// up to date incrementally.
+///
+/// The writes only mark the changed slots of the cached trees, and the next query of
+/// the root of a contract applies the current values of its changed slots to its tree
+/// with one batch update. The cache isn't cloned: the clone rebuilds the trees on
+/// demand.
+#[derive(Debug, Default)]
+pub(super) struct StateRoots(RefCell<BTreeMap<ContractId, StateTree>>);
+
+#[derive(Debug)]
+struct StateTree {
+    tree: MerkleTree,
+    /// The slots changed since the last update of the tree.
+    changed: BTreeSet<Bytes32>,
+}
+
+impl Clone for StateRoots {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl StateRoots {
+    /// Marks the slot of the `key` as changed.
+    pub fn touch(&mut self, key: &ContractsStateKey) {
+        if let Some(state) = self.0.get_mut().get_mut(key.contract_id()) {
+            state.changed.insert(*key.state_key());
+        }
+    }
+
+    /// The root of the state of the `contract`. The `slot` returns the current value of
+    /// a slot, and the `slots` all the slots of the contract, to build its tree on the
+    /// first query.
+    pub fn root<'a, S, I>(&self, contract: &ContractId, slot: S, slots: I) -> MerkleRoot
+    where
+        S: Fn(&Bytes32) -> Option<&'a ContractsStateData>,
+        I: Iterator<Item = (Bytes32, &'a ContractsStateData)>,
+    {
+        let mut trees = self.0.borrow_mut();
+        match trees.entry(*contract) {
+            Entry::Occupied(entry) => {
+                let state = entry.into_mut();
+                if !state.changed.is_empty() {
+                    let updates = mem::take(&mut state.changed)
+                        .into_iter()
+                        .map(|key| (MerkleTreeKey::new(key), slot(&key)))
+                        .collect::<Vec<_>>();
+                    state.tree.update_batch(updates);
+                }
+                state.tree.root()
+            }
+            Entry::Vacant(entry) => {
+                let tree = MerkleTree::from_set(
+                    slots.map(|(key, value)| (MerkleTreeKey::new(key), value)),
+                );
+                let root = tree.root();
+                entry.insert(StateTree {
+                    tree,
+                    changed: BTreeSet::new(),
+                });
+                root
+            }
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! Copy-on-write storage for the dry runs.
+
+use crate::storage::{
+    contract_state_keys,
+    memory::read_range_from,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateData,
+    ContractsStateKey,
+    InterpreterStorage,
+    UploadedBytecode,
+    UploadedBytecodes,
+};
+
+use fuel_merkle::sparse::{
+    in_memory::MerkleTree,
+    MerkleTreeKey,
+};
+use fuel_storage::{
+    BatchValues,
+    BoxedIter,
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageBatchInspect,
+    StorageInspect,
+    StorageIter,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+};
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use alloc::{
+    borrow::{
+        Cow,
+        ToOwned,
+    },
+    boxed::Box,
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+/// The changes buffered by an [`OverlayStorage`]. `None` removes the key from the
+/// base storage.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageChanges {
+    /// The changes of the contracts code.
+    pub contracts: BTreeMap<ContractId, Option<Contract>>,
+    /// The changes of the contracts balances.
+    pub balances: BTreeMap<ContractsAssetKey, Option<Word>>,
+    /// The changes of the contracts state.
+    pub contract_state: BTreeMap<ContractsStateKey, Option<ContractsStateData>>,
+    /// The changes of the uploaded bytecodes.
+    pub uploaded_bytecodes: BTreeMap<Bytes32, Option<UploadedBytecode>>,
+    /// The consensus parameters set by version.
+    pub consensus_parameters_versions: BTreeMap<u32, ConsensusParameters>,
+    /// The state transition bytecodes set by version.
+    pub state_transition_bytecodes_versions: BTreeMap<u32, Bytes32>,
+}
+
+impl StorageChanges {
+    /// Returns `true` if there is no change.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Copy-on-write storage for the dry runs against the live state.
+///
+/// The reads go through to the `base` storage, borrowed immutably, and the writes are
+/// buffered in memory, so the transactions executed over the overlay never modify the
+/// base storage. [`into_changes`](Self::into_changes) returns the buffered changes.
+///
+/// The base storage doesn't expose the consensus parameters and the state transition
+/// bytecodes by version, so setting them returns only the previous values set over the
+/// overlay.
+#[derive(Debug, Clone)]
+pub struct OverlayStorage<'a, S> {
+    base: &'a S,
+    changes: StorageChanges,
+}
+
+impl<'a, S> OverlayStorage<'a, S> {
+    /// Create a new overlay without changes over the `base` storage.
+    pub fn new(base: &'a S) -> Self {
+        Self {
+            base,
+            changes: Default::default(),
+        }
+    }
+
+    /// The base storage.
+    pub fn base(&self) -> &'a S {
+        self.base
+    }
+
+    /// The buffered changes.
+    pub fn changes(&self) -> &StorageChanges {
+        &self.changes
+    }
+
+    /// Consumes the overlay and returns the buffered changes.
+    pub fn into_changes(self) -> StorageChanges {
+        self.changes
+    }
+
+    /// Discards the buffered changes, so the reads see the base storage again.
+    pub fn discard(&mut self) {
+        self.changes = Default::default();
+    }
+}
+
+macro_rules! overlay_table {
+    ($table:ty, $changes:ident) => {
+        impl<S: InterpreterStorage> StorageInspect<$table> for OverlayStorage<'_, S> {
+            type Error = S::DataError;
+
+            fn get(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Cow<'_, <$table as Mappable>::OwnedValue>>, Self::Error>
+            {
+                match self.changes.$changes.get(key) {
+                    Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+                    None => StorageInspect::<$table>::get(self.base, key),
+                }
+            }
+
+            fn contains_key(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<bool, Self::Error> {
+                match self.changes.$changes.get(key) {
+                    Some(value) => Ok(value.is_some()),
+                    None => StorageInspect::<$table>::contains_key(self.base, key),
+                }
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageMutate<$table> for OverlayStorage<'_, S> {
+            fn insert(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                value: &<$table as Mappable>::Value,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                let previous =
+                    StorageInspect::<$table>::get(self, key)?.map(Cow::into_owned);
+                self.changes
+                    .$changes
+                    .insert(*key, Some(value.to_owned().into()));
+                Ok(previous)
+            }
+
+            fn remove(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                let previous =
+                    StorageInspect::<$table>::get(self, key)?.map(Cow::into_owned);
+                self.changes.$changes.insert(*key, None);
+                Ok(previous)
+            }
+        }
+    };
+}
+
+macro_rules! overlay_bytes_table {
+    ($table:ty, $changes:ident) => {
+        overlay_table!($table, $changes);
+
+        impl<S: InterpreterStorage> StorageSize<$table> for OverlayStorage<'_, S> {
+            fn size_of_value(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<usize>, Self::Error> {
+                match self.changes.$changes.get(key) {
+                    Some(value) => Ok(value.as_ref().map(|value| value.as_ref().len())),
+                    None => StorageSize::<$table>::size_of_value(self.base, key),
+                }
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageRead<$table> for OverlayStorage<'_, S> {
+            fn read(
+                &self,
+                key: &<$table as Mappable>::Key,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                match self.changes.$changes.get(key) {
+                    Some(value) => Ok(value.as_ref().map(|value| {
+                        let value = value.as_ref();
+                        let len = buf.len().min(value.len());
+                        buf[..len].copy_from_slice(&value[..len]);
+                        len
+                    })),
+                    None => StorageRead::<$table>::read(self.base, key, buf),
+                }
+            }
+
+            fn read_alloc(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                match self.changes.$changes.get(key) {
+                    Some(value) => {
+                        Ok(value.as_ref().map(|value| value.as_ref().to_vec()))
+                    }
+                    None => StorageRead::<$table>::read_alloc(self.base, key),
+                }
+            }
+
+            fn read_range(
+                &self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                match self.changes.$changes.get(key) {
+                    Some(value) => Ok(value
+                        .as_ref()
+                        .map(|value| read_range_from(value.as_ref(), offset, buf))),
+                    None => {
+                        StorageRead::<$table>::read_range(self.base, key, offset, buf)
+                    }
+                }
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageWrite<$table> for OverlayStorage<'_, S> {
+            fn write(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                self.changes
+                    .$changes
+                    .insert(*key, Some(buf.to_vec().into()));
+                Ok(buf.len())
+            }
+
+            fn replace(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+                let previous = StorageRead::<$table>::read_alloc(self, key)?;
+                let size = StorageWrite::<$table>::write(self, key, buf)?;
+                Ok((size, previous))
+            }
+
+            fn take(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                let previous = StorageRead::<$table>::read_alloc(self, key)?;
+                self.changes.$changes.insert(*key, None);
+                Ok(previous)
+            }
+        }
+    };
+}
+
+overlay_bytes_table!(ContractsRawCode, contracts);
+overlay_bytes_table!(ContractsState, contract_state);
+overlay_table!(ContractsAssets, balances);
+overlay_table!(UploadedBytecodes, uploaded_bytecodes);
+
+impl<S: InterpreterStorage> StorageBatchInspect<ContractsState>
+    for OverlayStorage<'_, S>
+{
+    fn get_batch<'b, 'k, Iter>(
+        &'b self,
+        keys: Iter,
+    ) -> BatchValues<'b, ContractsState, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k ContractsStateKey>,
+    {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        // The keys not changed by the overlay are read with one batch of the base.
+        let mut base = StorageBatchInspect::<ContractsState>::get_batch(
+            self.base,
+            keys.iter()
+                .copied()
+                .filter(|key| !self.changes.contract_state.contains_key(key)),
+        )
+        .into_iter();
+        keys.into_iter()
+            .map(|key| match self.changes.contract_state.get(key) {
+                Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+                None => base
+                    .next()
+                    .expect("The base storage returns a value for each key"),
+            })
+            .collect()
+    }
+}
+
+impl<S> StorageIter<ContractsState> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage + StorageIter<ContractsState>,
+{
+    fn iter_prefix(&self, prefix: &[u8]) -> BoxedIter<'_, ContractsState, Self::Error> {
+        let mut entries = BTreeMap::new();
+        for entry in StorageIter::<ContractsState>::iter_prefix(self.base, prefix) {
+            match entry {
+                Ok((key, value)) => entries.insert(key, Some(value)),
+                Err(err) => return Box::new(core::iter::once(Err(err))),
+            };
+        }
+        self.changes
+            .contract_state
+            .iter()
+            .filter(|(key, _)| key.as_ref().starts_with(prefix))
+            .for_each(|(key, value)| {
+                entries.insert(*key, value.as_ref().map(Cow::Borrowed));
+            });
+        Box::new(
+            entries
+                .into_iter()
+                .filter_map(|(key, value)| Some(Ok((key, value?)))),
+        )
+    }
+}
+
+impl<S> MerkleRootStorage<ContractId, ContractsState> for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage + StorageIter<ContractsState>,
+{
+    /// The root of the state of the contract with the buffered changes, computed like
+    /// [`Contract::initial_state_root`].
+    fn root(&self, contract: &ContractId) -> Result<MerkleRoot, Self::Error> {
+        contract_state_root(self, contract)
+    }
+}
+
+/// The root of the sparse Merkle tree of the state of the `contract`, computed like
+/// [`Contract::initial_state_root`] over the slots of the contract in the `storage`.
+pub(crate) fn contract_state_root<S>(
+    storage: &S,
+    contract: &ContractId,
+) -> Result<MerkleRoot, S::Error>
+where
+    S: StorageIter<ContractsState> + ?Sized,
+{
+    let slots = StorageIter::<ContractsState>::iter_prefix(storage, contract.as_ref())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MerkleTree::root_from_set(slots.iter().map(
+        |(key, value)| (MerkleTreeKey::new(key.state_key()), value.as_ref()),
+    )))
+}
+
+impl<S: InterpreterStorage> ContractsAssetsStorage for OverlayStorage<'_, S> {}
+
+impl<S: InterpreterStorage> InterpreterStorage for OverlayStorage<'_, S> {
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.base.block_height()
+    }
+
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        self.base.consensus_parameters_version()
+    }
+
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        self.base.state_transition_version()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.base.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.base.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.base.coinbase()
+    }
+
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        Ok(self
+            .changes
+            .consensus_parameters_versions
+            .insert(version, consensus_parameters.clone()))
+    }
+
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        Ok(self
+            .changes
+            .state_transition_bytecodes_versions
+            .insert(version, *hash))
+    }
+
+    fn contract_state_insert_range<'b, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'b [u8]>,
+    {
+        let mut unset_count = 0;
+        for (key, value) in
+            contract_state_keys(contract, start_key, usize::MAX).zip(values)
+        {
+            if !StorageInspect::<ContractsState>::contains_key(self, &key)? {
+                unset_count += 1;
+            }
+            self.changes
+                .contract_state
+                .insert(key, Some(value.to_vec().into()));
+        }
+        Ok(unset_count)
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        let mut all_set_key = true;
+        for key in contract_state_keys(contract, start_key, range) {
+            all_set_key &= StorageInspect::<ContractsState>::contains_key(self, &key)?;
+            self.changes.contract_state.insert(key, None);
+        }
+        Ok(all_set_key.then_some(()))
+    }
+}
@@ -14,6 +14,7 @@ use crate::{
 use fuel_asm::Word;
 use fuel_storage::{
     Mappable,
+    StorageBatchInspect,
     StorageInspect,
     StorageMutate,
     StorageRead,
@@ -75,6 +76,8 @@ impl<Type: Mappable> StorageInspect<Type> for PredicateStorage {
     }
 }
 
+impl<Type: Mappable> StorageBatchInspect<Type> for PredicateStorage {}
+
 impl<Type: Mappable> StorageMutate<Type> for PredicateStorage {
     fn insert(
         &mut self,
@@ -0,0 +1,66 @@
+#![cfg(feature = "arbitrary")]
+#![allow(non_snake_case)]
+
+use crate::prelude::*;
+use ::arbitrary::Unstructured;
+use fuel_tx::{
+    arbitrary_checkable,
+    ConsensusParameters,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+const SAMPLES: usize = 200;
+
+#[test]
+fn arbitrary_checkable__mostly_passes_checks() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    // Given
+    let txs: Vec<Script> = (0..SAMPLES)
+        .map(|_| {
+            let data: Vec<u8> = (0..512).map(|_| rng.gen::<u8>()).collect();
+            arbitrary_checkable(&mut Unstructured::new(&data))
+                .expect("Should produce a transaction")
+        })
+        .collect();
+
+    // When
+    let checked = txs
+        .into_iter()
+        .filter(|tx| tx.clone().into_checked(Default::default(), &params).is_ok())
+        .count();
+
+    // Then
+    assert!(
+        checked * 2 > SAMPLES,
+        "Only {checked} of {SAMPLES} transactions passed the checks"
+    );
+}
+
+#[test]
+fn arbitrary_checkable__executes_checked_transactions() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    for _ in 0..SAMPLES {
+        // Given
+        let data: Vec<u8> = (0..512).map(|_| rng.gen::<u8>()).collect();
+        let tx = arbitrary_checkable(&mut Unstructured::new(&data))
+            .expect("Should produce a transaction");
+        let Ok(checked) = tx.into_checked(Default::default(), &params) else {
+            continue
+        };
+
+        // When
+        let mut client = MemoryClient::default();
+        let result = client.transact(checked);
+
+        // Then
+        assert!(!result.is_empty(), "The execution should produce receipts");
+    }
+}
@@ -1,13 +1,22 @@
-use alloc::vec;
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+};
 
 use crate::{
+    backtrace::BacktraceCall,
     consts::*,
     prelude::*,
+    script_with_data_offset,
 };
 use fuel_asm::{
     op,
     RegId,
 };
+use fuel_types::canonical::Serialize;
 
 #[test]
 fn backtrace() {
@@ -91,3 +100,99 @@ fn backtrace() {
         .to();
     assert_eq!(id, &contract_call);
 }
+
+#[test]
+fn backtrace__captures_the_call_chain_two_calls_deep() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let asset_id = *test_context.get_base_asset_id();
+    let inner = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x20, 1),
+                op::div(0x20, 0x20, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let outer = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x12, 4),
+                op::call(0x11, 0x12, 0x13, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::addi(0x11, 0x10, Call::LEN as Immediate12),
+            op::addi(0x13, 0x11, Call::LEN as Immediate12),
+            op::movi(0x12, 10),
+            op::call(0x10, 0x12, 0x13, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(outer, 1, 2)
+        .to_bytes()
+        .into_iter()
+        .chain(Call::new(inner, 0xabc, 0xdef).to_bytes())
+        .chain(asset_id.iter().copied())
+        .collect();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(outer)
+        .contract_input(inner)
+        .coin_input(asset_id, 1000)
+        .fee_input()
+        .contract_output(&outer)
+        .contract_output(&inner)
+        .change_output(asset_id)
+        .build();
+
+    // When
+    let (_, backtrace) = test_context
+        .execute_tx_with_backtrace(tx, 0)
+        .expect("Should execute tx");
+
+    // Then
+    let backtrace = backtrace.expect("Expected a backtrace for the panic");
+    assert_eq!(
+        backtrace.call_chain(),
+        &[
+            BacktraceCall {
+                contract: outer,
+                asset_id,
+                amount: 10,
+                a: 1,
+                b: 2,
+            },
+            BacktraceCall {
+                contract: inner,
+                asset_id,
+                amount: 4,
+                a: 0xabc,
+                b: 0xdef,
+            },
+        ]
+    );
+    assert_eq!(
+        backtrace.to_string(),
+        format!(
+            "Panic\n   \
+             0: contract {inner} at offset 4\n      \
+             call of {inner} with a 2748, b 3567, forwarding 4 of {asset_id}\n   \
+             1: contract {outer} at offset 4\n      \
+             call of {outer} with a 1, b 2, forwarding 10 of {asset_id}\n   \
+             2: script at offset 16\n"
+        )
+    );
+}
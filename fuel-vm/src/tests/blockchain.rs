@@ -67,8 +67,7 @@ fn deploy_contract(
 ) {
     let code_root = Contract::root_from_code(contract.as_ref());
     let state_root = Contract::initial_state_root(storage_slots.iter());
-    let contract_id =
-        Contract::from(contract.as_ref()).id(&salt, &code_root, &state_root);
+    let contract_id = Contract::id(&salt, &code_root, &state_root);
 
     let tx_params = TxParameters::default();
     let height = Default::default();
@@ -585,7 +584,7 @@ fn ldc__load_len_of_target_contract<'a>(
     let contract = Contract::from(target_contract_witness.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_id = contract.id(&salt, &contract_root, &state_root);
+    let contract_id = Contract::id(&salt, &contract_root, &state_root);
 
     let input0 = Input::contract(rng.gen(), rng.gen(), rng.gen(), rng.gen(), contract_id);
     let output0 = Output::contract_created(contract_id, state_root);
@@ -728,7 +727,7 @@ fn ldc_reason_helper(cmd: Vec<Instruction>, expected_reason: PanicReason) {
     let contract = Contract::from(program.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_id = contract.id(&salt, &contract_root, &state_root);
+    let contract_id = Contract::id(&salt, &contract_root, &state_root);
 
     let output0 = Output::contract_created(contract_id, state_root);
 
@@ -1141,8 +1140,7 @@ fn code_root_a_plus_32_overflow() {
     let storage_slots = vec![];
 
     let state_root = Contract::initial_state_root(storage_slots.iter());
-    let contract_id =
-        Contract::from(contract.as_ref()).id(&salt, &code_root, &state_root);
+    let contract_id = Contract::id(&salt, &code_root, &state_root);
 
     deploy_contract(&mut client, contract, salt, storage_slots);
 
@@ -1187,8 +1185,7 @@ fn code_root_a_over_max_ram() {
     let storage_slots = vec![];
 
     let state_root = Contract::initial_state_root(storage_slots.iter());
-    let contract_id =
-        Contract::from(contract.as_ref()).id(&salt, &code_root, &state_root);
+    let contract_id = Contract::id(&salt, &code_root, &state_root);
 
     deploy_contract(&mut client, contract, salt, storage_slots);
 
@@ -1746,7 +1743,13 @@ fn smo_instruction_works() {
             .max_fee_limit(max_fee);
         // add inputs
         for (amount, data) in inputs {
-            tx.add_unsigned_message_input(secret, sender, rng.gen(), amount, data);
+            tx.add_unsigned_message_input(
+                secret.clone(),
+                sender,
+                rng.gen(),
+                amount,
+                data,
+            );
         }
         tx.add_unsigned_coin_input(secret, rng.gen(), max_fee, AssetId::BASE, rng.gen());
         let tx = tx
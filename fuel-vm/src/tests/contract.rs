@@ -1,7 +1,14 @@
+#![allow(non_snake_case)]
+
 use crate::{
+    checked_transaction::CheckError,
+    interpreter::InterpreterParams,
     prelude::*,
     script_with_data_offset,
-    util::test_helpers::TestBuilder,
+    util::test_helpers::{
+        TestBuilder,
+        TestStorage,
+    },
 };
 use alloc::{
     vec,
@@ -14,6 +21,7 @@ use fuel_asm::{
 use fuel_tx::{
     policies::Policies,
     ConsensusParameters,
+    ReceiptsExt,
     Witness,
 };
 use fuel_types::canonical::Serialize;
@@ -23,10 +31,18 @@ use rand::{
     SeedableRng,
 };
 
-#[test]
-fn prevent_contract_id_redeployment() {
+use super::test_helpers::{
+    kv_transaction_storage,
+    redb_storage,
+};
+
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn prevent_contract_id_redeployment(#[case] storage: impl TestStorage) {
     let mut rng = StdRng::seed_from_u64(2322u64);
-    let mut client = MemoryClient::default();
+    let mut txtor = Transactor::<_, Script>::new(storage, InterpreterParams::default());
 
     let input_amount = 1000;
     let spend_amount = 600;
@@ -43,7 +59,7 @@ fn prevent_contract_id_redeployment() {
     let contract = Contract::from(program.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_undefined = contract.id(&salt, &contract_root, &state_root);
+    let contract_undefined = Contract::id(&salt, &contract_root, &state_root);
 
     let output = Output::contract_created(contract_undefined, state_root);
 
@@ -78,21 +94,23 @@ fn prevent_contract_id_redeployment() {
         .expect("failed to generate checked tx");
 
     // deploy contract
-    client
+    txtor
         .deploy(create.clone())
         .expect("First create should be executed");
-    let mut txtor: Transactor<_, _> = client.into();
     // second deployment should fail
     let result = txtor.deploy(create).unwrap_err();
-    assert_eq!(
+    assert!(matches!(
         result,
         InterpreterError::Panic(PanicReason::ContractIdAlreadyDeployed)
-    );
+    ));
 }
 
-#[test]
-fn mint_burn() {
-    let mut test_context = TestBuilder::new(2322u64);
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn mint_burn(#[case] storage: impl TestStorage) {
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
 
     let mut balance = 1000;
     let gas_limit = 1_000_000;
@@ -259,9 +277,12 @@ fn mint_burn() {
     assert_eq!(0, storage_balance);
 }
 
-#[test]
-fn mint_consumes_gas_for_new_assets() {
-    let mut test_context = TestBuilder::new(2322u64);
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn mint_consumes_gas_for_new_assets(#[case] storage: impl TestStorage) {
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
 
     let balance = 1000;
     let gas_limit = 1_000_000;
@@ -324,15 +345,20 @@ fn mint_consumes_gas_for_new_assets() {
     assert!(new_asset > existing_asset);
 }
 
-#[test]
-fn call_increases_contract_asset_balance_and_balance_register() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn call_increases_contract_asset_balance_and_balance_register(
+    #[case] storage: impl TestStorage,
+) {
     let rng = &mut StdRng::seed_from_u64(2322u64);
 
     let gas_limit = 1_000_000;
     let asset_id: AssetId = rng.gen();
     let call_amount = 500u64;
 
-    let mut test_context = TestBuilder::new(2322u64);
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
     let contract_id = test_context
         .setup_contract(vec![op::ret(RegId::BAL)], None, None)
         .contract_id;
@@ -389,8 +415,13 @@ fn call_increases_contract_asset_balance_and_balance_register() {
     assert_eq!(transfer_tx.receipts()[1].val().unwrap(), call_amount);
 }
 
-#[test]
-fn call_decreases_internal_balance_and_increases_destination_contract_balance() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn call_decreases_internal_balance_and_increases_destination_contract_balance(
+    #[case] storage: impl TestStorage,
+) {
     let rng = &mut StdRng::seed_from_u64(2322u64);
 
     let gas_limit = 1_000_000;
@@ -398,7 +429,7 @@ fn call_decreases_internal_balance_and_increases_destination_contract_balance()
     let call_amount = 500;
     let initial_internal_balance = 1_000_000;
 
-    let mut test_context = TestBuilder::new(2322u64);
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
     let dest_contract_id = test_context
         .setup_contract(
             vec![
@@ -486,8 +517,12 @@ fn call_decreases_internal_balance_and_increases_destination_contract_balance()
     assert_eq!(transfer_tx.receipts()[2].val().unwrap(), call_amount);
 }
 
-#[test]
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
 fn internal_transfer_reduces_source_contract_balance_and_increases_destination_contract_balance(
+    #[case] storage: impl TestStorage,
 ) {
     let rng = &mut StdRng::seed_from_u64(2322u64);
 
@@ -496,7 +531,7 @@ fn internal_transfer_reduces_source_contract_balance_and_increases_destination_c
     let transfer_amount = 500;
     let initial_internal_balance = 1_000_000;
 
-    let mut test_context = TestBuilder::new(2322u64);
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
     let dest_contract_id = test_context.setup_contract(vec![], None, None).contract_id;
 
     let program = vec![
@@ -568,8 +603,13 @@ fn internal_transfer_reduces_source_contract_balance_and_increases_destination_c
     assert_eq!(source_balance, initial_internal_balance - transfer_amount);
 }
 
-#[test]
-fn internal_transfer_cant_exceed_more_than_source_contract_balance() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn internal_transfer_cant_exceed_more_than_source_contract_balance(
+    #[case] storage: impl TestStorage,
+) {
     let rng = &mut StdRng::seed_from_u64(2322u64);
 
     let gas_limit = 1_000_000;
@@ -578,7 +618,7 @@ fn internal_transfer_cant_exceed_more_than_source_contract_balance() {
     // set initial internal balance to < transfer amount
     let initial_internal_balance = 100;
 
-    let mut test_context = TestBuilder::new(2322u64);
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
     let dest_contract_id = test_context.setup_contract(vec![], None, None).contract_id;
 
     let program = vec![
@@ -649,3 +689,350 @@ fn internal_transfer_cant_exceed_more_than_source_contract_balance() {
         test_context.get_contract_balance(&sender_contract_id, &asset_id);
     assert_eq!(source_balance, initial_internal_balance);
 }
+
+fn deploy_with_predicted_id<S: InterpreterStorage>(
+    txtor: &mut Transactor<S, Script>,
+    code: &[u8],
+    salt: Salt,
+    storage_slots: Vec<StorageSlot>,
+) -> Result<ContractId, CheckError> {
+    let code_root = Contract::root_from_code(code);
+    let state_root = Contract::initial_state_root(storage_slots.iter());
+    let contract_id = Contract::id(&salt, &code_root, &state_root);
+
+    let create = TransactionBuilder::create(code.to_vec().into(), salt, storage_slots)
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize()
+        .into_checked(Default::default(), &ConsensusParameters::standard())?;
+
+    txtor
+        .deploy(create)
+        .expect("The contract should be deployed");
+
+    Ok(contract_id)
+}
+
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn deployed_contract_is_stored_under_predicted_id(#[case] storage: impl TestStorage) {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut txtor = Transactor::<_, Script>::new(storage, InterpreterParams::default());
+
+    let code: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let salt: Salt = rng.gen();
+    let mut storage_slots: Vec<StorageSlot> = (0..4)
+        .map(|_| StorageSlot::new(rng.gen(), rng.gen()))
+        .collect();
+    storage_slots.sort();
+
+    let contract_id =
+        deploy_with_predicted_id(&mut txtor, &code, salt, storage_slots.clone())
+            .expect("The slots are valid");
+
+    let storage = txtor.interpreter().as_ref();
+    assert_eq!(
+        storage
+            .storage_contract(&contract_id)
+            .unwrap()
+            .expect("The contract is deployed")
+            .as_ref()
+            .as_ref(),
+        code.as_slice()
+    );
+    for slot in &storage_slots {
+        let value = storage
+            .contract_state(&contract_id, slot.key())
+            .unwrap()
+            .expect("The slot is stored");
+        assert_eq!(value.as_ref().as_ref(), slot.value().as_ref());
+    }
+    assert_eq!(
+        Contract::checked_initial_state_root(&storage_slots),
+        Ok(Contract::initial_state_root(storage_slots.iter()))
+    );
+}
+
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn deployed_contract_without_state_is_stored_under_predicted_id(
+    #[case] storage: impl TestStorage,
+) {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut txtor = Transactor::<_, Script>::new(storage, InterpreterParams::default());
+
+    let code: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let salt: Salt = rng.gen();
+
+    let contract_id = deploy_with_predicted_id(&mut txtor, &code, salt, vec![])
+        .expect("The slots are valid");
+
+    let expected_id = Contract::id(
+        &salt,
+        &Contract::root_from_code(&code),
+        &Contract::default_state_root(),
+    );
+    assert_eq!(contract_id, expected_id);
+    assert!(txtor
+        .interpreter()
+        .as_ref()
+        .storage_contract_exists(&expected_id)
+        .unwrap());
+}
+
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn deploying_contract_with_duplicated_slots_is_rejected(
+    #[case] storage: impl TestStorage,
+) {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut txtor = Transactor::<_, Script>::new(storage, InterpreterParams::default());
+
+    let code: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let salt: Salt = rng.gen();
+    let key: Bytes32 = rng.gen();
+    let storage_slots = vec![
+        StorageSlot::new(key, rng.gen()),
+        StorageSlot::new(key, rng.gen()),
+    ];
+
+    assert_eq!(
+        Contract::checked_initial_state_root(&storage_slots),
+        Err(ValidityError::TransactionCreateStorageSlotOrder)
+    );
+
+    let result = deploy_with_predicted_id(&mut txtor, &code, salt, storage_slots);
+
+    assert_eq!(
+        result,
+        Err(CheckError::Validity(
+            ValidityError::TransactionCreateStorageSlotOrder
+        ))
+    );
+}
+
+/// Starts a script calling the read-only `contract_id` and forwarding the `amount` of
+/// the base asset.
+fn call_read_only_contract_script<S: TestStorage>(
+    test_context: &mut TestBuilder<S>,
+    contract_id: ContractId,
+    amount: Word,
+) -> &mut TestBuilder<S> {
+    let asset_id = *test_context.get_base_asset_id();
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::addi(0x11, 0x10, Call::LEN as Immediate12),
+            op::movi(0x12, u32::try_from(amount).unwrap()),
+            op::call(0x10, 0x12, 0x11, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data: Vec<u8> = Call::new(contract_id, 0, 0)
+        .to_bytes()
+        .into_iter()
+        .chain(asset_id.iter().copied())
+        .collect();
+
+    test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input_read_only(contract_id)
+        .coin_input(asset_id, 1000)
+        .fee_input()
+        .change_output(asset_id)
+}
+
+fn call_read_only_contract<S: TestStorage>(
+    test_context: &mut TestBuilder<S>,
+    contract_id: ContractId,
+    amount: Word,
+) -> Vec<Receipt> {
+    call_read_only_contract_script(test_context, contract_id, amount)
+        .execute()
+        .receipts()
+        .to_vec()
+}
+
+#[test]
+fn call__read_only_contract_can_read_its_state() {
+    let mut test_context = TestBuilder::new(2322u64);
+    // Reads the word stored under the key `[1, 0, ..., 0]` and logs it.
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 1),
+        op::sb(RegId::HP, 0x11, 0),
+        op::srw(0x12, 0x13, RegId::HP),
+        op::log(0x12, 0x13, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let mut key = Bytes32::zeroed();
+    key[0] = 1;
+    let initial_state = vec![StorageSlot::new(key, Bytes32::new([2; 32]))];
+    let contract_id = test_context
+        .setup_contract(program, None, Some(initial_state))
+        .contract_id;
+
+    // When
+    let snapshot = call_read_only_contract_script(&mut test_context, contract_id, 0)
+        .execute_snapshot();
+
+    // Then
+    let tx_id = *snapshot.tx_id();
+    assert_eq!(
+        snapshot.redact(tx_id, "[tx id]").to_string(),
+        READ_ONLY_CONTRACT_READS_ITS_STATE
+    );
+}
+
+const READ_ONLY_CONTRACT_READS_ITS_STATE: &str = r#"state: Some(Return(1))
+gas_used: Some(238)
+tx_id: [tx id]
+utxo_ids:
+  00000000000000000000000000000000000000000000000000000000000000000000
+  7c2a39fc3a2f2cb17cbcd4de5262d2915958c3d158bf455248c5666edb600ed41c6d
+  c49d65de61cf04588a764b557d25cc6c6b4bc0d7429227e2a21e61c213b3a3e28212
+outputs:
+  Change { to: 1236b7b72d76ffae1d854ef4d1cfd1c779926185a4a0e6eac51e8f2dc7a272bd, amount: 4294968295, asset_id: 0000000000000000000000000000000000000000000000000000000000000000 }
+registers:
+  $one = 0x1
+  $pc = 0x2894
+  $ssp = 0x2bc0
+  $sp = 0x2bc0
+  $hp = 0x3ffffe0
+  $ggas = 0xf4152
+  $cgas = 0xf4152
+  $is = 0x2880
+  $ret = 0x1
+  $r16 = 0x2898
+  $r17 = 0x28c8
+receipts:
+[0] Call
+    id       0000000000000000000000000000000000000000000000000000000000000000
+    to       b235cf32621b36e2b8dfa32befea09efa7206bec79a75bcc40252f3bf6cedce4
+    amount   0
+    asset_id 0000000000000000000000000000000000000000000000000000000000000000
+    gas      999813
+    param1   0
+    param2   0
+    pc       11800
+    is       11800
+[1] Log
+    id b235cf32621b36e2b8dfa32befea09efa7206bec79a75bcc40252f3bf6cedce4
+    ra 144680345676153346
+    rb 1
+    rc 0
+    rd 0
+    pc 11820
+    is 11800
+[2] Return
+    id  b235cf32621b36e2b8dfa32befea09efa7206bec79a75bcc40252f3bf6cedce4
+    val 1
+    pc  11824
+    is  11800
+[3] Return
+    id  0000000000000000000000000000000000000000000000000000000000000000
+    val 1
+    pc  10384
+    is  10368
+[4] ScriptResult
+    result   Success
+    gas_used 238
+storage:
+  balance b235cf32621b36e2b8dfa32befea09efa7206bec79a75bcc40252f3bf6cedce4 0000000000000000000000000000000000000000000000000000000000000000: - -> 0
+"#;
+
+#[test]
+fn call__read_only_contract_panics_on_state_write() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let program = vec![op::sww(RegId::ZERO, 0x10, RegId::ONE), op::ret(RegId::ONE)];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    // When
+    let snapshot = call_read_only_contract_script(&mut test_context, contract_id, 0)
+        .execute_snapshot();
+
+    // Then
+    let tx_id = *snapshot.tx_id();
+    assert_eq!(
+        snapshot.redact(tx_id, "[tx id]").to_string(),
+        READ_ONLY_CONTRACT_PANICS_ON_STATE_WRITE
+    );
+}
+
+const READ_ONLY_CONTRACT_PANICS_ON_STATE_WRITE: &str = r#"state: Some(Revert(0))
+gas_used: Some(254)
+tx_id: [tx id]
+utxo_ids:
+  00000000000000000000000000000000000000000000000000000000000000000000
+  7c2a39fc3a2f2cb17cbcd4de5262d2915958c3d158bf455248c5666edb600ed41c6d
+  c49d65de61cf04588a764b557d25cc6c6b4bc0d7429227e2a21e61c213b3a3e28212
+outputs:
+  Change { to: 1236b7b72d76ffae1d854ef4d1cfd1c779926185a4a0e6eac51e8f2dc7a272bd, amount: 4294968295, asset_id: 0000000000000000000000000000000000000000000000000000000000000000 }
+registers:
+  $one = 0x1
+  $pc = 0x2e18
+  $ssp = 0x2e20
+  $sp = 0x2e20
+  $fp = 0x2bc0
+  $hp = 0x4000000
+  $ggas = 0xf4142
+  $cgas = 0xf4142
+  $is = 0x2e18
+  $r16 = 0x2898
+  $r17 = 0x28c8
+receipts:
+[0] Call
+    id       0000000000000000000000000000000000000000000000000000000000000000
+    to       10a6312ee3c2940f7519ff1bb3619d018798100f03c38af9400e2517e89a1094
+    amount   0
+    asset_id 0000000000000000000000000000000000000000000000000000000000000000
+    gas      999813
+    param1   0
+    param2   0
+    pc       11800
+    is       11800
+[1] Panic
+    id          10a6312ee3c2940f7519ff1bb3619d018798100f03c38af9400e2517e89a1094
+    reason      ContractNotWritable: The contract is a read-only input of the transaction and its state or balances can't be modified.
+    instruction 0x3a010040 (SWW)
+    pc          11800
+    is          11800
+    contract_id 10a6312ee3c2940f7519ff1bb3619d018798100f03c38af9400e2517e89a1094
+[2] ScriptResult
+    result   Panic
+    gas_used 254
+storage:
+  balance 10a6312ee3c2940f7519ff1bb3619d018798100f03c38af9400e2517e89a1094 0000000000000000000000000000000000000000000000000000000000000000: - -> 0
+"#;
+
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn call__forwarding_coins_to_read_only_contract_panics(
+    #[case] storage: impl TestStorage,
+) {
+    let mut test_context = TestBuilder::with_storage(2322u64, storage);
+    let program = vec![op::ret(RegId::ONE)];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    // When
+    let receipts = call_read_only_contract(&mut test_context, contract_id, 100);
+
+    // Then
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::ContractNotWritable)
+    );
+    assert!(receipts.calls().is_empty());
+}
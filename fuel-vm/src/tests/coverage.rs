@@ -0,0 +1,253 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Ready,
+    coverage::{
+        CodeId,
+        CoverageError,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+/// Returns `1` when the first word of the script data is zero, and `2` otherwise.
+fn branching_script() -> Vec<u8> {
+    vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::lw(0x11, 0x10, 0),
+        op::jnzi(0x11, 5),
+        op::movi(0x12, 1),
+        op::ret(0x12),
+        op::movi(0x12, 2),
+        op::ret(0x12),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn ready(script: Vec<u8>, input: Word) -> Ready<Script> {
+    let consensus_params = ConsensusParameters::standard();
+    TransactionBuilder::script(script, input.to_be_bytes().to_vec())
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap()
+}
+
+fn script_id(script: &[u8]) -> CodeId {
+    CodeId::Script(Hasher::hash(script))
+}
+
+#[test]
+fn coverage__leaves_the_untaken_branch_uncovered() {
+    // Given
+    let script = branching_script();
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_coverage(Coverage::new());
+
+    // When
+    let state = *vm.transact(ready(script.clone(), 0)).unwrap().state();
+
+    // Then
+    assert_eq!(state, ProgramState::Return(1));
+    let coverage = vm.coverage().unwrap();
+    let script = coverage.get(&script_id(&script)).unwrap();
+    assert_eq!(coverage.len(), 1);
+    assert_eq!(script.instructions(), 7);
+    assert_eq!(script.covered().collect::<Vec<_>>(), vec![0, 4, 8, 12, 16]);
+    assert_eq!(script.uncovered().collect::<Vec<_>>(), vec![20, 24]);
+    assert!(!script.is_fully_covered());
+}
+
+#[test]
+fn coverage__merges_the_branches_of_the_transactions() {
+    // Given
+    let script = branching_script();
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_coverage(Coverage::new());
+    let first = *vm.transact(ready(script.clone(), 0)).unwrap().state();
+
+    // When
+    let second = *vm.transact(ready(script.clone(), 1)).unwrap().state();
+
+    // Then
+    assert_eq!(first, ProgramState::Return(1));
+    assert_eq!(second, ProgramState::Return(2));
+    let coverage = vm.coverage().unwrap();
+    assert_eq!(coverage.len(), 1);
+    assert!(coverage
+        .get(&script_id(&script))
+        .unwrap()
+        .is_fully_covered());
+}
+
+#[test]
+fn merge__matches_the_coverage_collected_across_the_transactions() {
+    // Given
+    let script = branching_script();
+    let collect = |inputs: &[Word]| {
+        let mut vm = Interpreter::<_, Script>::with_memory_storage();
+        vm.with_coverage(Coverage::new());
+        for input in inputs {
+            vm.transact(ready(script.clone(), *input)).unwrap();
+        }
+        vm.take_coverage().unwrap()
+    };
+    let mut merged = collect(&[0]);
+
+    // When
+    merged.merge(&collect(&[1]));
+
+    // Then
+    assert_eq!(merged, collect(&[0, 1]));
+    assert!(merged.get(&script_id(&script)).unwrap().is_fully_covered());
+}
+
+#[test]
+fn coverage__has_a_bitmap_per_called_contract() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract = test_context
+        .setup_contract(
+            vec![op::jnzi(0x20, 2), op::ret(RegId::ONE), op::ret(RegId::ZERO)],
+            None,
+            None,
+        )
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let consensus_params = ConsensusParameters::standard();
+    let tx = test_context
+        .start_script(script.clone(), Call::new(contract, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract)
+        .fee_input()
+        .contract_output(&contract)
+        .build()
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, &consensus_params),
+    );
+    vm.with_coverage(Coverage::new());
+
+    // When
+    vm.transact(tx).unwrap();
+
+    // Then
+    let coverage = vm.coverage().unwrap();
+    let contract = coverage.get(&CodeId::Contract(contract)).unwrap();
+    assert_eq!(contract.instructions(), 3);
+    assert_eq!(contract.uncovered().collect::<Vec<_>>(), vec![8]);
+    let script: Vec<u8> = script.into_iter().collect();
+    assert!(coverage
+        .get(&script_id(&script))
+        .unwrap()
+        .is_fully_covered());
+}
+
+#[test]
+fn from_bytes__decodes_the_encoded_coverage() {
+    // Given
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_coverage(Coverage::new());
+    vm.transact(ready(branching_script(), 0)).unwrap();
+    let coverage = vm.take_coverage().unwrap();
+    let bytes = coverage.to_bytes();
+
+    // When
+    let decoded = Coverage::from_bytes(&bytes);
+
+    // Then
+    assert_eq!(decoded, Ok(coverage));
+    assert_eq!(
+        Coverage::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(CoverageError::InvalidEncoding)
+    );
+    assert_eq!(
+        Coverage::from_bytes(b"FVMD"),
+        Err(CoverageError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn lcov__renders_the_offsets_without_source_map() {
+    // Given
+    let script = branching_script();
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_coverage(Coverage::new());
+    vm.transact(ready(script.clone(), 1)).unwrap();
+
+    // When
+    let lcov = vm.coverage().unwrap().lcov(None).to_string();
+
+    // Then
+    let hash = Hasher::hash(&script);
+    assert_eq!(
+        lcov,
+        format!(
+            "SF:script {hash}\nDA:0,1\nDA:4,1\nDA:8,1\nDA:12,0\nDA:16,0\nDA:20,1\n\
+             DA:24,1\nLF:7\nLH:5\nend_of_record\n"
+        )
+    );
+}
+
+#[test]
+fn lcov__renders_the_lines_of_the_source_map() {
+    // Given
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_coverage(Coverage::new());
+    vm.transact(ready(branching_script(), 0)).unwrap();
+    let mut source_map = SourceMap::new();
+    source_map
+        .insert(None, 0..12, SourceLocation::new("main.sw", 2, 5, "main"))
+        .unwrap();
+    source_map
+        .insert(None, 12..20, SourceLocation::new("main.sw", 3, 9, "main"))
+        .unwrap();
+    source_map
+        .insert(None, 20..28, SourceLocation::new("main.sw", 5, 9, "main"))
+        .unwrap();
+
+    // When
+    let lcov = vm.coverage().unwrap().lcov(Some(&source_map)).to_string();
+
+    // Then
+    assert_eq!(
+        lcov,
+        "SF:main.sw\nDA:2,1\nDA:3,1\nDA:5,0\nLF:3\nLH:2\nend_of_record\n"
+    );
+}
@@ -20,6 +20,7 @@ use fuel_tx::{
 use fuel_types::ChainId;
 use rand::{
     rngs::StdRng,
+    Rng,
     SeedableRng,
 };
 use sha3::{
@@ -154,6 +155,32 @@ fn ecrecover_tx_id() {
     assert!(success);
 }
 
+#[test]
+fn seeded_keys_sign_coin_inputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let mut client = MemoryClient::default();
+    let mut builder = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    );
+    builder.script_gas_limit(1_000_000);
+    for secret in fuel_crypto::test_vectors::keyring::<3>() {
+        builder.add_unsigned_coin_input(
+            secret,
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            rng.gen(),
+        );
+    }
+
+    let tx = builder.finalize_checked(Default::default());
+    let receipts = client.transact(tx);
+
+    assert_success(receipts);
+}
+
 #[cfg(feature = "std")]
 #[tokio::test]
 async fn recover_tx_id_predicate() {
@@ -0,0 +1,197 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Ready,
+    consts::VM_MAX_RAM,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+
+/// Branches on `0x10` to log `1` or `2`, with a breakpoint before the branch.
+fn branching() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 0),
+        op::jnzi(0x10, 5),
+        op::movi(0x20, 1),
+        op::log(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+        op::movi(0x20, 2),
+        op::log(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+const BRANCH: Word = 1;
+
+/// Allocates a word of the heap and logs it, with a breakpoint before the load.
+fn logging_the_heap() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::lw(0x20, RegId::HP, 0),
+        op::log(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+const LOAD: Word = 2;
+
+fn ready(script: Vec<Instruction>) -> Ready<Script> {
+    let consensus_params = ConsensusParameters::standard();
+    TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap()
+}
+
+/// Executes the `script` up to the breakpoint at the `pc`.
+fn paused_at(script: Vec<Instruction>, pc: Word) -> Interpreter<MemoryStorage, Script> {
+    let mut vm = Interpreter::with_memory_storage();
+    vm.set_breakpoint(Breakpoint::script(pc));
+    let state = vm
+        .transact(ready(script))
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+    assert_eq!(state, Breakpoint::script(pc));
+    vm
+}
+
+fn logs(vm: &Interpreter<MemoryStorage, Script>) -> Vec<Word> {
+    vm.receipts()
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log { ra, .. } => Some(*ra),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn set_register__takes_the_other_branch_after_the_breakpoint() {
+    // Given
+    let mut vm = paused_at(branching(), BRANCH);
+
+    // When
+    vm.set_register(RegId::new(0x10), 1, false).unwrap();
+    let state = vm.resume().expect("Failed to resume");
+
+    // Then
+    assert_eq!(state, ProgramState::Return(1));
+    assert_eq!(logs(&vm), vec![2]);
+    assert_eq!(
+        vm.debug_edits(),
+        &[DebugEdit::Register {
+            register: RegId::new(0x10),
+            value: 1,
+            forced: false,
+        }]
+    );
+}
+
+#[test]
+fn set_register__without_edit_takes_the_first_branch() {
+    let mut vm = paused_at(branching(), BRANCH);
+
+    vm.resume().expect("Failed to resume");
+
+    assert_eq!(logs(&vm), vec![1]);
+    assert!(vm.debug_edits().is_empty());
+}
+
+#[test]
+fn set_register__rejects_the_reserved_registers_unless_forced() {
+    // Given
+    let mut vm = paused_at(branching(), BRANCH);
+
+    // When
+    let rejected = vm.set_register(RegId::HP, 0, false);
+    let forced = vm.set_register(RegId::ERR, 7, true);
+
+    // Then
+    assert_eq!(rejected, Err(DebugEditError::ReservedRegister(RegId::HP)));
+    assert_eq!(forced, Ok(()));
+    assert_eq!(vm.registers()[RegId::ERR], 7);
+    assert_eq!(vm.debug_edits().len(), 1);
+}
+
+#[test]
+fn write_memory__is_read_by_the_resumed_program() {
+    // Given
+    let mut vm = paused_at(logging_the_heap(), LOAD);
+    let hp = vm.registers()[RegId::HP];
+
+    // When
+    vm.write_memory(hp, &42u64.to_be_bytes(), false).unwrap();
+    vm.resume().expect("Failed to resume");
+
+    // Then
+    assert_eq!(logs(&vm), vec![42]);
+    assert_eq!(
+        vm.debug_edits(),
+        &[DebugEdit::Memory {
+            address: hp,
+            bytes: 42u64.to_be_bytes().to_vec(),
+            unchecked: false,
+        }]
+    );
+}
+
+#[test]
+fn write_memory__checks_the_ownership_unless_unchecked() {
+    // Given
+    let mut vm = paused_at(logging_the_heap(), LOAD);
+
+    // When
+    let rejected = vm.write_memory(0, &[1, 2, 3], false);
+    let unchecked = vm.write_memory(0, &[1, 2, 3], true);
+
+    // Then
+    assert_eq!(
+        rejected,
+        Err(DebugEditError::Memory(PanicReason::MemoryOwnership))
+    );
+    assert_eq!(unchecked, Ok(()));
+    assert_eq!(vm.memory().read(0, 3).unwrap(), &[1, 2, 3]);
+    assert_eq!(
+        vm.write_memory(VM_MAX_RAM, &[1], true),
+        Err(DebugEditError::Memory(PanicReason::MemoryOverflow))
+    );
+}
+
+#[test]
+fn edits__are_rejected_when_the_execution_isnt_paused() {
+    // Given
+    let mut vm = paused_at(branching(), BRANCH);
+    vm.resume().expect("Failed to resume");
+
+    // When
+    let register = vm.set_register(RegId::new(0x10), 1, false);
+    let memory = vm.write_memory(0, &[1], true);
+
+    // Then
+    assert_eq!(register, Err(DebugEditError::NotSuspended));
+    assert_eq!(memory, Err(DebugEditError::NotSuspended));
+    let mut idle = Interpreter::<_, Script>::with_memory_storage();
+    assert_eq!(
+        idle.set_register(RegId::new(0x10), 1, false),
+        Err(DebugEditError::NotSuspended)
+    );
+}
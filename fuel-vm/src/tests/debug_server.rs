@@ -0,0 +1,213 @@
+#![cfg(feature = "debug-server")]
+#![allow(non_snake_case)]
+
+use crate::{
+    debug::server::{
+        DebugRequest,
+        DebugResponse,
+        DebugServer,
+    },
+    prelude::*,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Write,
+    },
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+};
+
+const STACK_EXTEND_AMOUNT: Word = 100;
+
+/// The program of the `dynamic_call_frame_ops` test: logs the stack pointer, extends
+/// the stack, logs it again, shrinks the stack and returns the stack pointer.
+fn dynamic_call_frame_ops() -> Vec<Instruction> {
+    vec![
+        op::log(RegId::SP, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::movi(0x10, STACK_EXTEND_AMOUNT as Immediate18),
+        op::cfe(0x10),
+        op::log(RegId::SP, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::movi(0x11, 50),
+        op::cfs(0x11),
+        op::ret(RegId::SP),
+    ]
+}
+
+fn server() -> DebugServer<MemoryStorage, Script> {
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(
+        dynamic_call_frame_ops().into_iter().collect(),
+        vec![],
+    )
+    .script_gas_limit(1_000_000)
+    .add_random_fee_input()
+    .finalize()
+    .into_checked(Default::default(), &consensus_params)
+    .expect("failed to generate checked tx")
+    .into_ready(
+        0,
+        consensus_params.gas_costs(),
+        consensus_params.fee_params(),
+    )
+    .unwrap();
+
+    DebugServer::new(Interpreter::with_memory_storage(), tx)
+}
+
+/// Client scripting a session over the JSON-over-TCP protocol.
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(address: std::net::SocketAddr) -> Self {
+        let writer = TcpStream::connect(address).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Self { reader, writer }
+    }
+
+    fn send(&mut self, request: &str) -> DebugResponse {
+        writeln!(self.writer, "{request}").unwrap();
+        let mut line = std::string::String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+#[test]
+fn session__pauses_at_the_breakpoint_and_completes_after_continue() {
+    // Given
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let handle = server().spawn(listener);
+    let mut client = Client::connect(address);
+    let script = ContractId::zeroed();
+
+    // When
+    let set = client.send(&format!(
+        r#"{{"command":"set_breakpoint","contract":"{script}","pc":3}}"#
+    ));
+    let paused = client.send(r#"{"command":"continue"}"#);
+    let registers = client.send(r#"{"command":"read_registers"}"#);
+    let receipts_so_far = client.send(r#"{"command":"receipts"}"#);
+    let terminated = client.send(r#"{"command":"continue"}"#);
+    let receipts = client.send(r#"{"command":"receipts"}"#);
+    let finished = client.send(r#"{"command":"continue"}"#);
+    drop(client);
+
+    // Then
+    assert_eq!(set, DebugResponse::Ok);
+    assert_eq!(
+        paused,
+        DebugResponse::Paused {
+            contract: script,
+            pc: 3,
+            watches: vec![],
+        }
+    );
+    let DebugResponse::Registers { registers } = registers else {
+        panic!("Expected the registers, got {registers:?}")
+    };
+    let sp = registers[RegId::SP.to_u8() as usize];
+    let DebugResponse::Receipts {
+        receipts: receipts_so_far,
+    } = receipts_so_far
+    else {
+        panic!("Expected the receipts, got {receipts_so_far:?}")
+    };
+    let Some(Receipt::Log { ra: initial_sp, .. }) = receipts_so_far.first() else {
+        panic!("Expected the first log, got {receipts_so_far:?}")
+    };
+    assert_eq!(sp, initial_sp + STACK_EXTEND_AMOUNT);
+    assert_eq!(
+        terminated,
+        DebugResponse::Terminated {
+            state: ProgramState::Return(sp - 50)
+        }
+    );
+    let DebugResponse::Receipts { receipts } = receipts else {
+        panic!("Expected the receipts, got {receipts:?}")
+    };
+    let logs = receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log { ra, .. } => Some(*ra),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(logs, vec![*initial_sp, sp]);
+    assert!(receipts.iter().any(
+        |receipt| matches!(receipt, Receipt::Return { val, .. } if *val == sp - 50)
+    ));
+    assert!(matches!(finished, DebugResponse::Error { .. }));
+    let server = handle.join().unwrap().unwrap();
+    assert_eq!(server.interpreter().receipts(), receipts.as_slice());
+}
+
+#[test]
+fn step__pauses_after_each_instruction() {
+    // Given
+    let mut server = server();
+
+    // When
+    let steps = (0..3)
+        .map(|_| server.handle(DebugRequest::Step))
+        .collect::<Vec<_>>();
+
+    // Then
+    let script = ContractId::zeroed();
+    assert_eq!(
+        steps,
+        (0..3)
+            .map(|pc| DebugResponse::Paused {
+                contract: script,
+                pc,
+                watches: vec![],
+            })
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        server.handle(DebugRequest::CallFrames),
+        DebugResponse::CallFrames { frames: vec![] }
+    );
+    let DebugResponse::Memory { bytes } = server.handle(DebugRequest::ReadMemory {
+        start: server.interpreter().registers()[RegId::IS],
+        len: Instruction::SIZE as Word,
+    }) else {
+        panic!("Expected the memory")
+    };
+    assert_eq!(
+        bytes,
+        op::log(RegId::SP, RegId::ZERO, RegId::ZERO, RegId::ZERO).to_bytes()
+    );
+}
+
+#[test]
+fn serve__answers_an_invalid_request_with_an_error() {
+    // Given
+    let mut server = server();
+    let mut output = Vec::new();
+
+    // When
+    server
+        .serve(&b"{\"command\":\"jump\"}\n"[..], &mut output)
+        .unwrap();
+
+    // Then
+    let response: DebugResponse = serde_json::from_slice(&output).unwrap();
+    assert!(matches!(response, DebugResponse::Error { .. }));
+}
@@ -0,0 +1,225 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Ready,
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+};
+use fuel_asm::{
+    op,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+/// The index of the `CALL` of the script, and of the instruction after it.
+const SCRIPT_CALL: Word = 2;
+/// The index of the `CALL` of the outer contract.
+const OUTER_CALL: Word = 1;
+
+struct Setup {
+    vm: Interpreter<MemoryStorage, Script>,
+    tx: Ready<Script>,
+    outer: ContractId,
+    inner: ContractId,
+}
+
+/// Deploys the `inner` contract and an outer contract calling it, and builds an
+/// interpreter and a script calling the outer contract.
+fn setup(inner: Vec<Instruction>) -> Setup {
+    let mut test_context = TestBuilder::new(2322u64);
+    let inner = test_context.setup_contract(inner, None, None).contract_id;
+    let outer = test_context
+        .setup_contract(
+            vec![
+                op::noop(),
+                op::call(0x11, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::noop(),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::movi(0x11, data_offset as Immediate18 + Call::LEN as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::noop(),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = [outer, inner]
+        .iter()
+        .flat_map(|contract| Call::new(*contract, 0, 0).to_bytes())
+        .collect();
+    let consensus_params = ConsensusParameters::standard();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(100_000)
+        .contract_input(outer)
+        .contract_input(inner)
+        .fee_input()
+        .contract_output(&outer)
+        .contract_output(&inner)
+        .build()
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+    let vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, &consensus_params),
+    );
+    Setup {
+        vm,
+        tx,
+        outer,
+        inner,
+    }
+}
+
+fn returning_contract() -> Vec<Instruction> {
+    vec![
+        op::noop(),
+        op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+fn breakpoint(state: &ProgramState) -> Breakpoint {
+    *state
+        .debug_ref()
+        .and_then(DebugEval::breakpoint)
+        .expect("Expected a breakpoint")
+}
+
+#[test]
+fn step_over__executes_the_whole_called_contract() {
+    // Given
+    let Setup { mut vm, tx, .. } = setup(returning_contract());
+    vm.set_breakpoint(Breakpoint::script(SCRIPT_CALL));
+    let state = ProgramState::from(vm.transact(tx).unwrap());
+    assert_eq!(breakpoint(&state), Breakpoint::script(SCRIPT_CALL));
+
+    // When
+    let state = vm.resume_with(DebugCommand::StepOver).unwrap();
+
+    // Then
+    assert_eq!(breakpoint(&state), Breakpoint::script(SCRIPT_CALL + 1));
+    let calls = vm
+        .receipts()
+        .iter()
+        .filter(|receipt| matches!(receipt, Receipt::Call { .. }))
+        .count();
+    let returns = vm
+        .receipts()
+        .iter()
+        .filter(|receipt| matches!(receipt, Receipt::Return { .. }))
+        .count();
+    assert_eq!(calls, 2);
+    assert_eq!(returns, 2);
+    assert!(vm
+        .receipts()
+        .iter()
+        .any(|receipt| matches!(receipt, Receipt::Log { ra: 1, .. })));
+}
+
+#[test]
+fn step_over__behaves_like_a_step_without_call() {
+    // Given
+    let Setup { mut vm, tx, .. } = setup(returning_contract());
+    vm.set_breakpoint(Breakpoint::script(0));
+    let state = ProgramState::from(vm.transact(tx).unwrap());
+    assert_eq!(breakpoint(&state), Breakpoint::script(0));
+
+    // When
+    let state = vm.resume_with(DebugCommand::StepOver).unwrap();
+
+    // Then
+    assert_eq!(breakpoint(&state), Breakpoint::script(1));
+}
+
+#[test]
+fn step_over__stops_at_a_breakpoint_in_the_called_contract() {
+    // Given
+    let Setup {
+        mut vm, tx, inner, ..
+    } = setup(returning_contract());
+    vm.set_breakpoint(Breakpoint::script(SCRIPT_CALL));
+    let state = ProgramState::from(vm.transact(tx).unwrap());
+    assert_eq!(breakpoint(&state), Breakpoint::script(SCRIPT_CALL));
+    vm.set_breakpoint(Breakpoint::new(inner, 1));
+
+    // When
+    let in_callee = vm.resume_with(DebugCommand::StepOver).unwrap();
+    let after = vm.resume_with(DebugCommand::Continue).unwrap();
+
+    // Then
+    assert_eq!(breakpoint(&in_callee), Breakpoint::new(inner, 1));
+    assert_eq!(after, ProgramState::Return(1));
+}
+
+#[test]
+fn step_out__returns_from_each_nested_call() {
+    // Given
+    let Setup {
+        mut vm,
+        tx,
+        outer,
+        inner,
+    } = setup(returning_contract());
+    vm.set_single_stepping(true);
+    let mut state = ProgramState::from(vm.transact(tx).unwrap());
+    while vm.call_stack().len() < 2 {
+        state = vm.resume().unwrap();
+        assert!(state.is_debug());
+    }
+    vm.set_single_stepping(false);
+
+    // When
+    let in_outer = vm.resume_with(DebugCommand::StepOut).unwrap();
+    let outer_depth = vm.call_stack().len();
+    let in_script = vm.resume_with(DebugCommand::StepOut).unwrap();
+    let script_depth = vm.call_stack().len();
+    let finished = vm.resume_with(DebugCommand::StepOut).unwrap();
+
+    // Then
+    assert_eq!(breakpoint(&state), Breakpoint::new(inner, 0));
+    assert_eq!(
+        breakpoint(&in_outer),
+        Breakpoint::new(outer, OUTER_CALL + 1)
+    );
+    assert_eq!(outer_depth, 1);
+    assert_eq!(breakpoint(&in_script), Breakpoint::script(SCRIPT_CALL + 1));
+    assert_eq!(script_depth, 0);
+    assert_eq!(finished, ProgramState::Return(1));
+}
+
+#[test]
+fn step_over__surfaces_the_exhaustion_of_the_gas_in_the_called_contract() {
+    // Given
+    let Setup { mut vm, tx, .. } = setup(vec![op::noop(), op::ji(0)]);
+    vm.set_breakpoint(Breakpoint::script(SCRIPT_CALL));
+    let state = ProgramState::from(vm.transact(tx).unwrap());
+    assert_eq!(breakpoint(&state), Breakpoint::script(SCRIPT_CALL));
+
+    // When
+    let result = vm.resume_with(DebugCommand::StepOver);
+
+    // Then
+    let error = result.expect_err("Expected the contract to run out of gas");
+    assert_eq!(error.panic_reason(), Some(PanicReason::OutOfGas));
+}
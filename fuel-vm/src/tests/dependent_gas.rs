@@ -0,0 +1,97 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::prelude::*;
+use fuel_asm::{
+    op,
+    Instruction,
+    Opcode,
+    RegId,
+};
+
+const LENGTHS: [Word; 5] = [0, 1, 1000, 10 * 1024, 2 * 1024 * 1024];
+
+/// The gas used by a script allocating `2 * len + 32` bytes of heap, with the length
+/// in `0x10`, the first half of the heap in `$hp` and the second in `0x11`, and
+/// executing the `instruction`.
+fn gas_used(instruction: Instruction, len: Word) -> Word {
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::lw(0x10, 0x10, 0),
+        op::add(0x12, 0x10, 0x10),
+        op::addi(0x12, 0x12, 32),
+        op::aloc(0x12),
+        op::add(0x11, RegId::HP, 0x10),
+        instruction,
+        op::ret(RegId::ONE),
+    ];
+    let result = TestBuilder::new(2322u64)
+        .start_script(script, len.to_be_bytes().to_vec())
+        .script_gas_limit(10_000_000)
+        .fee_input()
+        .execute();
+    assert!(
+        matches!(result.state(), ProgramState::Return(1)),
+        "{:?}",
+        result.receipts()
+    );
+    result
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap()
+}
+
+/// Asserts the gas used by the `instruction`, compared with a `NOOP`, is the predicted
+/// one for each of the [`LENGTHS`].
+fn assert_charges_the_predicted_gas(instruction: Instruction) {
+    let gas_costs = GasCosts::default();
+    let opcode = instruction.opcode();
+    let noop = gas_costs.charge_for(Opcode::NOOP, 0).unwrap();
+    let charged = LENGTHS
+        .iter()
+        .map(|len| gas_used(instruction, *len) - gas_used(op::noop(), *len) + noop)
+        .collect::<Vec<_>>();
+    let predicted = LENGTHS
+        .iter()
+        .map(|len| gas_costs.charge_for(opcode, *len).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(charged, predicted, "{opcode:?}");
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_mcp() {
+    assert_charges_the_predicted_gas(op::mcp(RegId::HP, 0x11, 0x10));
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_mcl() {
+    assert_charges_the_predicted_gas(op::mcl(RegId::HP, 0x10));
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_meq() {
+    assert_charges_the_predicted_gas(op::meq(0x13, RegId::HP, 0x11, 0x10));
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_s256() {
+    assert_charges_the_predicted_gas(op::s256(0x11, RegId::HP, 0x10));
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_k256() {
+    assert_charges_the_predicted_gas(op::k256(0x11, RegId::HP, 0x10));
+}
+
+#[test]
+fn charge_for__predicts_the_gas_of_logd() {
+    assert_charges_the_predicted_gas(op::logd(RegId::ZERO, RegId::ZERO, RegId::HP, 0x10));
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    differential::{
+        Corpus,
+        CorpusCase,
+        DifferentialHarness,
+        ExecutionOutcome,
+        InterpreterExecutor,
+        Mismatch,
+        ReferenceExecutor,
+    },
+    prelude::*,
+};
+use fuel_asm::op;
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV1,
+    ConsensusParameters,
+    GasCosts,
+};
+
+fn case(name: &str, script: Vec<Instruction>) -> CorpusCase {
+    let transaction = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into();
+    CorpusCase {
+        name: name.to_string(),
+        storage: MemoryStorage::default().snapshot(),
+        transaction,
+    }
+}
+
+fn corpus() -> Corpus {
+    [
+        case(
+            "add",
+            vec![op::movi(0x10, 3), op::addi(0x11, 0x10, 1), op::ret(0x11)],
+        ),
+        case(
+            "log",
+            vec![op::movi(0x10, 7), op::log(0x10, 0, 0, 0), op::ret(0x10)],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn executor(name: &str, gas_costs: GasCosts) -> InterpreterExecutor {
+    InterpreterExecutor::new(name, ConsensusParameters::standard())
+        .with_gas_costs(gas_costs)
+}
+
+fn expensive_addi() -> GasCosts {
+    GasCosts::new(
+        GasCostsValuesV1 {
+            addi: 6,
+            ..GasCostsValuesV1::unit()
+        }
+        .into(),
+    )
+}
+
+#[test]
+fn run__matches_the_same_interpreter() {
+    // Given
+    let harness = DifferentialHarness::new(
+        executor("current", GasCosts::unit()),
+        executor("reference", GasCosts::unit()),
+    );
+
+    // When
+    let report = harness.run(&corpus());
+
+    // Then
+    assert!(report.is_match(), "{report}");
+    assert_eq!(report.cases.len(), 2);
+    assert_eq!(
+        report.to_string(),
+        "0 of 2 cases differ between `current` and `reference`\n"
+    );
+}
+
+#[test]
+fn run__reports_the_cases_executing_an_instruction_with_other_costs() {
+    // Given
+    let harness = DifferentialHarness::new(
+        executor("current", GasCosts::unit()),
+        executor("reference", expensive_addi()),
+    );
+
+    // When
+    let report = harness.run(&corpus());
+
+    // Then
+    let mismatched: Vec<_> = report.mismatched().collect();
+    assert_eq!(mismatched.len(), 1);
+    assert_eq!(mismatched[0].name, "add");
+    let mismatches = &mismatched[0].mismatches;
+    let [Mismatch::Receipts(_, _), Mismatch::Gas(current, reference), Mismatch::Steps(steps)] =
+        mismatches.as_slice()
+    else {
+        panic!("Expected the receipts, gas and steps mismatches, got {mismatches:?}")
+    };
+    assert_eq!(current - reference, 5);
+    assert_eq!(steps.index(), Some(1));
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "1 of 2 cases differ between `current` and `reference`\n\
+             add:\n    \
+                 receipts: 2 != 2 receipts, differing from the receipt 1\n    \
+                 gas remaining: {current} != {reference}\n    \
+                 steps: diverging from the step 1\n"
+        )
+    );
+}
+
+/// Reference rejecting every transaction.
+struct Rejecting;
+
+impl ReferenceExecutor for Rejecting {
+    fn name(&self) -> &str {
+        "rejecting"
+    }
+
+    fn execute(&self, storage: MemoryStorage, _: &Transaction) -> ExecutionOutcome {
+        ExecutionOutcome::rejected(storage, "unsupported")
+    }
+}
+
+#[test]
+fn run__reports_the_different_results() {
+    // Given
+    let harness =
+        DifferentialHarness::new(executor("current", GasCosts::unit()), Rejecting);
+    let corpus: Corpus = corpus().cases()[1..].iter().cloned().collect();
+
+    // When
+    let report = harness.run(&corpus);
+
+    // Then
+    let mismatches = &report.cases[0].mismatches;
+    assert_eq!(
+        mismatches[0],
+        Mismatch::Result(Ok(ProgramState::Return(7)), Err("unsupported".to_string()))
+    );
+    assert!(
+        matches!(&mismatches[1], Mismatch::Receipts(current, reference) if current.len() == 3 && reference.is_empty())
+    );
+}
+
+#[test]
+fn load_dir__loads_the_saved_cases() {
+    // Given
+    let directory =
+        std::env::temp_dir().join(format!("differential-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let corpus = corpus();
+    for case in corpus.cases().iter().rev() {
+        case.save_to(&directory).unwrap();
+    }
+
+    // When
+    let loaded = Corpus::load_dir(&directory);
+
+    // Then
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(loaded.unwrap(), corpus);
+}
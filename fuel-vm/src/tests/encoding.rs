@@ -115,7 +115,7 @@ fn input() {
             Word::MAX,
             [0xcc; 32].into(),
             TxPointer::new(0x3802.into(), 0x28),
-            0xff,
+            WitnessIndex::new(0xff),
         ),
         Input::coin_predicate(
             UtxoId::new([0xaa; 32].into(), 0),
@@ -142,7 +142,7 @@ fn input() {
             [0xbb; 32].into(),
             Word::MAX,
             [0xcc; 32].into(),
-            0xff,
+            WitnessIndex::new(0xff),
         ),
         Input::message_coin_predicate(
             [0xaa; 32].into(),
@@ -167,7 +167,7 @@ fn input() {
             [0xbb; 32].into(),
             Word::MAX,
             [0xcc; 32].into(),
-            0xff,
+            WitnessIndex::new(0xff),
             vec![0xdd; 50],
         ),
         Input::message_data_predicate(
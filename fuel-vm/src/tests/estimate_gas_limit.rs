@@ -0,0 +1,148 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    transactor::GasEstimate,
+    util::test_helpers::TestBuilder,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+
+const GAS_LIMIT: Word = 1_000_000;
+
+fn script_tx(script: Vec<Instruction>, gas_limit: Word) -> Checked<Script> {
+    TestBuilder::new(2322u64)
+        .start_script(script, vec![])
+        .script_gas_limit(gas_limit)
+        .fee_input()
+        .build()
+}
+
+fn transactor() -> Transactor<MemoryStorage, Script> {
+    Transactor::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+}
+
+fn gas_used(
+    transactor: &mut Transactor<MemoryStorage, Script>,
+    tx: Checked<Script>,
+) -> Word {
+    transactor
+        .transact(tx)
+        .receipts()
+        .and_then(|receipts| receipts.iter().find_map(Receipt::gas_used))
+        .expect("The script result is missing")
+}
+
+/// Allocates a byte of the heap for each 16 units of the remaining gas, then clears
+/// the bytes one at a time.
+fn allocating_its_gas() -> Vec<Instruction> {
+    vec![
+        op::divi(0x10, RegId::GGAS, 16),
+        op::aloc(0x10),
+        op::move_(0x11, RegId::HP),
+        op::sb(0x11, RegId::ZERO, 0),
+        op::addi(0x11, 0x11, 1),
+        op::subi(0x10, 0x10, 1),
+        op::jnzb(0x10, RegId::ZERO, 2),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn estimate_gas_limit__finds_the_gas_used_by_a_plain_script() {
+    // Given
+    let script = vec![
+        op::movi(0x10, 50),
+        op::subi(0x10, 0x10, 1),
+        op::jnzb(0x10, RegId::ZERO, 0),
+        op::ret(RegId::ONE),
+    ];
+    let template = script_tx(script, GAS_LIMIT);
+    let mut transactor = transactor();
+
+    // When
+    let estimate = transactor.estimate_gas_limit(&template, 0).unwrap();
+
+    // Then
+    let used = gas_used(&mut transactor, template);
+    let Some(GasEstimate {
+        gas_limit,
+        gas_used,
+        ambiguous: false,
+        ..
+    }) = estimate
+    else {
+        panic!("Expected an estimate, got {estimate:?}")
+    };
+    assert_eq!(gas_limit, used);
+    assert_eq!(gas_used, used);
+}
+
+#[test]
+fn estimate_gas_limit__finds_the_limit_of_a_script_allocating_its_gas() {
+    // Given
+    let script = allocating_its_gas();
+    let template = script_tx(script.clone(), GAS_LIMIT);
+    let mut transactor = transactor();
+
+    // When
+    let estimate = transactor
+        .estimate_gas_limit(&template, 0)
+        .unwrap()
+        .expect("The script succeeds with the gas limit of the template");
+
+    // Then
+    let used_with_the_template = gas_used(&mut transactor, template);
+    assert!(!estimate.ambiguous);
+    assert!(
+        estimate.gas_limit < used_with_the_template / 2,
+        "{estimate:?}"
+    );
+    assert!(estimate.gas_used <= estimate.gas_limit);
+    let below = script_tx(script, estimate.gas_limit - 1);
+    assert_eq!(transactor.estimate_gas_limit(&below, 0).unwrap(), None);
+}
+
+#[test]
+fn estimate_gas_limit__stops_at_the_tolerance() {
+    // Given
+    let script = allocating_its_gas();
+    let template = script_tx(script.clone(), GAS_LIMIT);
+    let transactor = transactor();
+    let exact = transactor
+        .estimate_gas_limit(&template, 0)
+        .unwrap()
+        .unwrap();
+
+    // When
+    let estimate = transactor
+        .estimate_gas_limit(&template, 1_000)
+        .unwrap()
+        .unwrap();
+
+    // Then
+    assert!(estimate.iterations < exact.iterations);
+    assert!((exact.gas_limit..=exact.gas_limit + 1_000).contains(&estimate.gas_limit));
+}
+
+#[test]
+fn estimate_gas_limit__returns_none_for_a_failing_script() {
+    let template = script_tx(vec![op::rvrt(RegId::ONE)], GAS_LIMIT);
+
+    let estimate = transactor().estimate_gas_limit(&template, 0).unwrap();
+
+    assert_eq!(estimate, None);
+}
@@ -0,0 +1,194 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    diff::{
+        compare,
+        try_compare,
+        Divergence,
+        ExecutionTrace,
+        TraceReader,
+        TraceRecorder,
+        TraceWriter,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV1,
+    ConsensusParameters,
+    GasCosts,
+};
+
+/// The index of the first `ADDI` of the script.
+const FIRST_ADDI: usize = 1;
+
+fn script() -> Vec<u8> {
+    vec![
+        op::movi(0x10, 3),
+        op::addi(0x11, 0x10, 1),
+        op::muli(0x12, 0x11, 2),
+        op::addi(0x12, 0x12, 1),
+        op::ret(0x12),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn interpreter(gas_costs: GasCosts) -> Interpreter<MemoryStorage, Script> {
+    let mut params = InterpreterParams::new(0, ConsensusParameters::standard());
+    params.gas_costs = gas_costs;
+    Interpreter::with_storage(MemoryStorage::default(), params)
+}
+
+fn tx() -> crate::checked_transaction::Ready<Script> {
+    let consensus_params = ConsensusParameters::standard();
+    TransactionBuilder::script(script(), vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap()
+}
+
+fn record(gas_costs: GasCosts) -> ExecutionTrace {
+    let recorder = Arc::new(TraceRecorder::new());
+    let mut vm = interpreter(gas_costs);
+    vm.with_observer(recorder.clone());
+    vm.transact(tx()).unwrap();
+    recorder.take()
+}
+
+fn expensive_addi() -> GasCosts {
+    GasCosts::new(
+        GasCostsValuesV1 {
+            addi: 6,
+            ..GasCostsValuesV1::unit()
+        }
+        .into(),
+    )
+}
+
+#[test]
+fn compare__reports_the_first_instruction_with_a_different_cost() {
+    // Given
+    let cheap = record(GasCosts::unit());
+    let expensive = record(expensive_addi());
+
+    // When
+    let divergence = compare(cheap, expensive);
+
+    // Then
+    let Divergence::Step {
+        index,
+        a: Some(a),
+        b: Some(b),
+    } = divergence
+    else {
+        panic!("Expected a divergence of both runs, got {divergence:?}")
+    };
+    assert_eq!(index, FIRST_ADDI);
+    assert_eq!(a.opcode(), Some(Opcode::ADDI));
+    assert_eq!(a.location, b.location);
+    assert_eq!(a.written, vec![(RegId::new(0x11), 4)]);
+    assert_eq!(a.written, b.written);
+    assert_eq!(a.gas - b.gas, 5);
+}
+
+#[test]
+fn compare__finds_identical_runs() {
+    // Given
+    let first = record(GasCosts::unit());
+    let second = record(GasCosts::unit());
+
+    // When
+    let divergence = compare(first, second);
+
+    // Then
+    assert_eq!(divergence, Divergence::Identical { steps: 5 });
+}
+
+#[test]
+fn compare__reports_the_end_of_the_shorter_run() {
+    // Given
+    let full = record(GasCosts::unit());
+    let truncated = full.steps()[..3].iter().cloned().collect();
+
+    // When
+    let divergence = compare(full.clone(), truncated);
+
+    // Then
+    assert_eq!(
+        divergence,
+        Divergence::Step {
+            index: 3,
+            a: Some(full.steps()[3].clone()),
+            b: None,
+        }
+    );
+}
+
+#[test]
+fn try_compare__reads_the_traces_streamed_by_the_writers() {
+    // Given
+    let stream = |gas_costs| {
+        let writer = Arc::new(TraceWriter::new(Vec::new()).unwrap());
+        let mut vm = interpreter(gas_costs);
+        vm.with_observer(writer.clone());
+        vm.transact(tx()).unwrap();
+        drop(vm);
+        writer.flush().unwrap();
+        Arc::into_inner(writer).unwrap().into_inner()
+    };
+    let cheap = stream(GasCosts::unit());
+    let expensive = stream(expensive_addi());
+
+    // When
+    let divergence = try_compare(
+        TraceReader::new(cheap.as_slice()).unwrap(),
+        TraceReader::new(expensive.as_slice()).unwrap(),
+    )
+    .unwrap();
+
+    // Then
+    assert_eq!(
+        ExecutionTrace::read_from(cheap.as_slice()).unwrap(),
+        record(GasCosts::unit())
+    );
+    assert_eq!(
+        divergence,
+        compare(record(GasCosts::unit()), record(expensive_addi()))
+    );
+    assert_eq!(divergence.index(), Some(FIRST_ADDI));
+}
+
+#[test]
+fn read_from__rejects_a_truncated_trace() {
+    // Given
+    let mut bytes = Vec::new();
+    record(GasCosts::unit()).write_to(&mut bytes).unwrap();
+
+    // When
+    let truncated = ExecutionTrace::read_from(&bytes[..bytes.len() - 1]);
+    let invalid = TraceReader::new(&b"FVMC\x01"[..]);
+
+    // Then
+    assert!(truncated.is_err());
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,142 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+    util::test_helpers::ExecutionSnapshot,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::Inputs,
+    ConsensusParameters,
+};
+use fuel_types::canonical::Serialize;
+
+/// Executes a script logging a constant, paying the fee with a coin of the `seed`, and
+/// returns the snapshot with and without the redaction of the transaction id and the
+/// UTXO ids.
+fn snapshots(seed: u64) -> (String, String) {
+    let mut test_context = TestBuilder::new(seed);
+    let base_asset_id = *test_context.get_base_asset_id();
+    let tx = test_context
+        .start_script(
+            vec![
+                op::movi(0x10, 42),
+                op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .coin_input(base_asset_id, 1_000_000)
+        .build();
+    let utxo_ids = tx
+        .transaction()
+        .inputs()
+        .iter()
+        .filter_map(|input| input.utxo_id().map(ToString::to_string))
+        .collect::<Vec<_>>();
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    );
+    transactor.transact(tx);
+
+    let snapshot = ExecutionSnapshot::capture(&transactor);
+    let unredacted = snapshot.to_string();
+    let tx_id = *snapshot.tx_id();
+    let redacted = snapshot
+        .redact(tx_id, "[tx id]")
+        .redact_with(|text| {
+            utxo_ids.iter().fold(text.to_string(), |text, utxo_id| {
+                text.replace(utxo_id, "[utxo id]")
+            })
+        })
+        .to_string();
+    (unredacted, redacted)
+}
+
+#[test]
+fn redaction__keeps_the_snapshots_with_different_fee_inputs_equal() {
+    // When
+    let (first, first_redacted) = snapshots(1);
+    let (second, second_redacted) = snapshots(2);
+
+    // Then
+    assert_ne!(first, second);
+    assert_eq!(first_redacted, second_redacted);
+    assert!(first_redacted.contains("tx_id: [tx id]\n"));
+    assert!(first_redacted.contains("  [utxo id]\n"));
+    assert!(first_redacted.contains("    ra 42\n"));
+}
+
+#[test]
+fn capture__is_deterministic() {
+    let (first, _) = snapshots(1);
+    let (second, _) = snapshots(1);
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn capture__renders_the_storage_changes_since_the_baseline() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::sww(RegId::HP, 0x11, RegId::ONE),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+
+    // When
+    let snapshot = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .execute_snapshot()
+        .to_string();
+
+    // Then
+    let storage = snapshot.split_once("storage:\n").unwrap().1;
+    let state = storage
+        .lines()
+        .filter(|line| line.starts_with("  state "))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        state,
+        vec![format!(
+            "  state {contract_id} {}: - -> {:016x}{}",
+            Bytes32::zeroed(),
+            1,
+            "00".repeat(24)
+        )]
+    );
+}
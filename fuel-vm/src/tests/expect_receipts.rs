@@ -0,0 +1,108 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    expect_receipts,
+    prelude::*,
+    util::test_helpers::{
+        ExpectedReceipts,
+        ReceiptPattern,
+    },
+};
+use fuel_types::ContractId;
+
+const CONTRACT: ContractId = ContractId::new([1; 32]);
+
+fn receipts() -> Vec<Receipt> {
+    vec![
+        Receipt::log(CONTRACT, 5, 1, 0, 0, 0, 0),
+        Receipt::return_data(CONTRACT, 0, 0, 0, vec![7; 32]),
+        Receipt::ret(CONTRACT, 1, 0, 0),
+        Receipt::script_result(ScriptExecutionResult::Success, 100),
+    ]
+}
+
+#[test]
+fn expect_receipts__matches_the_fields_and_the_wildcards() {
+    let receipts = receipts();
+    let logged = 5;
+
+    expect_receipts!(
+        receipts,
+        [
+            log(ra == logged, rb > 0),
+            return_data(data.len() == 32, id == CONTRACT),
+            _,
+            script_result(Success),
+        ]
+    );
+    expect_receipts!(receipts, [log(), .., script_result(Success)]);
+    expect_receipts!(
+        receipts,
+        [.., ret(val == 1), .., script_result(Success), ..]
+    );
+}
+
+#[test]
+fn check__marks_the_first_receipt_not_matching() {
+    // Given
+    let receipts = receipts();
+    let expected = ExpectedReceipts::new()
+        .then(ReceiptPattern::new("log", "ra == 5", |receipt| {
+            receipt.ra() == Some(5)
+        }))
+        .any_number()
+        .then(ReceiptPattern::new("panic", "OutOfGas", |_| true))
+        .then(ReceiptPattern::new("script_result", "Panic", |_| true));
+
+    // When
+    let mismatch = expected.check(&receipts).unwrap_err();
+
+    // Then
+    assert_eq!(
+        mismatch.to_string(),
+        format!(
+            "The receipts don't match the expected sequence\n\
+             expected:\n    \
+                 log(ra == 5)\n    \
+                 ..\n  \
+               > panic(OutOfGas)\n    \
+                 script_result(Panic)\n\
+             actual:\n    \
+                 [0] {:?}\n  \
+               > [1] {:?}\n    \
+                 [2] {:?}\n    \
+                 [3] {:?}\n",
+            receipts[0], receipts[1], receipts[2], receipts[3]
+        )
+    );
+}
+
+#[test]
+fn check__marks_the_unexpected_receipts_and_the_missing_ones() {
+    // Given
+    let receipts = receipts();
+    let too_short = ExpectedReceipts::new().any().any();
+    let too_long = ExpectedReceipts::new().any_number().any().any();
+
+    // When
+    let unexpected = too_short.check(&receipts[..3]).unwrap_err().to_string();
+    let missing = too_long.check(&receipts[..1]).unwrap_err().to_string();
+
+    // Then
+    assert!(unexpected.contains("  > (end of the expected receipts)\n"));
+    assert!(unexpected.contains(&format!("  > [2] {:?}\n", receipts[2])));
+    assert!(missing.contains("    ..\n    _\n  > _\n"));
+    assert!(missing.ends_with("  > (end of the receipts)\n"));
+}
+
+#[test]
+#[should_panic(expected = "  > ret(val == 2)\n")]
+fn expect_receipts__panics_with_the_diff_of_the_sequences() {
+    expect_receipts!(receipts(), [log(), _, ret(val == 2), ..]);
+}
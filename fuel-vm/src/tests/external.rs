@@ -1,24 +1,47 @@
-use alloc::vec;
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
 use fuel_asm::{
     op,
     GTFArgs,
+    Instruction,
+    Opcode,
+    PanicReason,
     RegId,
     Word,
 };
+use fuel_crypto::SecretKey;
 use fuel_tx::{
     ConsensusParameters,
+    DependentCost,
     Finalizable,
     Receipt,
     Script,
-    ScriptExecutionResult,
     TransactionBuilder,
 };
-use fuel_vm::prelude::{
-    Interpreter,
-    IntoChecked,
-    MemoryClient,
+use fuel_types::{
+    Bytes32,
+    Bytes64,
+};
+use fuel_vm::{
+    expect_receipts,
+    interpreter::InterpreterParams,
+    prelude::{
+        Interpreter,
+        IntoChecked,
+        MemoryClient,
+    },
+    storage::MemoryStorage,
 };
 use itertools::Itertools;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
 
 /// An ECAL opcode handler function, which charges for `noop` and does nothing.
 #[derive(Debug, Default, Clone, Copy)]
@@ -60,10 +83,7 @@ fn noop_ecal() {
     client.transact(tx);
     let receipts = client.receipts().expect("Expected receipts");
 
-    let Receipt::ScriptResult { result, .. } = receipts.last().unwrap() else {
-        panic!("Expected a result receipt");
-    };
-    assert_eq!(*result, ScriptExecutionResult::Success);
+    expect_receipts!(receipts, [.., script_result(Success)]);
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -143,3 +163,251 @@ fn provide_ecal_fn() {
     assert_eq!(*ra, 2 + 3 + 4 + 5);
     assert_eq!(*rb, 2 * 3 * 4 * 5);
 }
+
+/// An example of ECAL handler verifying BIP340 Schnorr signatures, for the hosts that
+/// need them before a dedicated opcode exists.
+///
+/// `a` receives `0` if the signature is valid and `1` otherwise, `b` points to the
+/// 32 bytes x-only public key, `c` to the 64 bytes signature and `d` to the 32 bytes
+/// message. It charges the cost of `eck1`, the `schnorr` bench of `fuel-crypto` compares
+/// both verifications to price a dedicated opcode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SchnorrVerifyEcal;
+
+impl ::fuel_vm::interpreter::EcalHandler for SchnorrVerifyEcal {
+    fn ecal<S, Tx>(
+        vm: &mut ::fuel_vm::prelude::Interpreter<S, Tx, Self>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> ::fuel_vm::error::SimpleResult<()> {
+        vm.gas_charge(vm.gas_costs().eck1())?;
+
+        let public_key = vm.memory().read_bytes(vm.registers()[b])?;
+        let signature = vm.memory().read_bytes(vm.registers()[c])?;
+        let message = vm.memory().read(vm.registers()[d], 32)?;
+
+        let result = fuel_crypto::schnorr::verify(
+            &Bytes32::new(public_key),
+            &Bytes64::new(signature),
+            message,
+        );
+        vm.registers_mut()[a] = Word::from(result.is_err());
+
+        Ok(())
+    }
+}
+
+fn schnorr_ecal_result(
+    public_key: Bytes32,
+    signature: Bytes64,
+    message: Bytes32,
+) -> Word {
+    let vm: Interpreter<_, Script, SchnorrVerifyEcal> =
+        Interpreter::with_memory_storage();
+
+    let script_data = [&public_key[..], &signature[..], &message[..]].concat();
+    let script = vec![
+        op::gtf_args(0x10, 0x00, GTFArgs::ScriptData),
+        op::addi(0x11, 0x10, Bytes32::LEN as u16),
+        op::addi(0x12, 0x11, Bytes64::LEN as u16),
+        op::ecal(0x20, 0x10, 0x11, 0x12),
+        op::log(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut client = MemoryClient::from_txtor(vm.into());
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate a checked tx");
+    client.transact(tx);
+    let receipts = client.receipts().expect("Expected receipts");
+
+    let Receipt::Log { ra, .. } = receipts.first().unwrap() else {
+        panic!("Expected a log receipt");
+    };
+    *ra
+}
+
+#[test]
+fn schnorr_verify_ecal() {
+    let rng = &mut StdRng::seed_from_u64(2322);
+    let secret = SecretKey::random(rng);
+    let message: Bytes32 = rng.gen();
+    let public_key = fuel_crypto::schnorr::public_key(&secret);
+    let signature = fuel_crypto::schnorr::sign(&secret, &*message, &rng.gen())
+        .expect("Failed to sign");
+
+    assert_eq!(schnorr_ecal_result(public_key, signature, message), 0);
+
+    let mut tampered = signature;
+    tampered[63] ^= 1;
+    assert_eq!(schnorr_ecal_result(public_key, tampered, message), 1);
+    assert_eq!(schnorr_ecal_result(public_key, signature, rng.gen()), 1);
+}
+
+const MEMCPY_BASE_GAS: Word = 5;
+const MEMCPY_GAS_PER_BYTE: Word = 2;
+
+/// An ECAL handler copying `c` bytes from the address in `b` to the address in `a`,
+/// charged [`MEMCPY_GAS_PER_BYTE`] per byte on top of its base cost, and storing the
+/// remaining gas in `d`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MemcpyEcal;
+
+impl ::fuel_vm::interpreter::EcalHandler for MemcpyEcal {
+    const BASE_GAS_COST: Option<Word> = Some(MEMCPY_BASE_GAS);
+
+    fn ecal<S, Tx>(
+        vm: &mut ::fuel_vm::prelude::Interpreter<S, Tx, Self>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> ::fuel_vm::error::SimpleResult<()> {
+        let [dst, src, len] = [a, b, c].map(|reg| vm.registers()[reg]);
+
+        let mut gas = vm.gas_charger();
+        gas.charge_dependent(
+            DependentCost::from_gas_per_unit(0, MEMCPY_GAS_PER_BYTE),
+            len,
+        )?;
+        let remaining = gas.remaining();
+
+        vm.memory_mut().memcopy_noownerchecks(dst, src, len)?;
+        vm.registers_mut()[d] = remaining;
+
+        Ok(())
+    }
+}
+
+/// Copies the script data to the heap with the [`MemcpyEcal`], with the `gas_limit`,
+/// and logs the remaining gas and the copied bytes.
+fn memcpy_ecal_receipts(data: &[u8], gas_limit: Word) -> Vec<Receipt> {
+    let vm: Interpreter<_, Script, MemcpyEcal> = Interpreter::with_memory_storage();
+
+    let script = vec![
+        op::movi(0x10, data.len() as u32),
+        op::aloc(0x10),
+        op::gtf_args(0x11, 0x00, GTFArgs::ScriptData),
+        op::ecal(RegId::HP, 0x11, 0x10, 0x12),
+        op::log(0x12, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::logd(RegId::ZERO, RegId::ZERO, RegId::HP, 0x10),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut client = MemoryClient::from_txtor(vm.into());
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(script, data.to_vec())
+        .script_gas_limit(gas_limit)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate a checked tx");
+    client.transact(tx);
+    client.receipts().expect("Expected receipts").to_vec()
+}
+
+/// The gas remaining after the ECAL, logged by [`memcpy_ecal_receipts`].
+fn remaining_after_ecal(receipts: &[Receipt]) -> Word {
+    let Receipt::Log { ra, .. } = receipts.first().unwrap() else {
+        panic!("Expected a log receipt: {receipts:?}");
+    };
+    *ra
+}
+
+/// The opcode of the instruction panicking with [`PanicReason::OutOfGas`].
+fn out_of_gas_opcode(receipts: &[Receipt]) -> Opcode {
+    let Some(Receipt::Panic { reason, .. }) = receipts
+        .iter()
+        .find(|receipt| matches!(receipt, Receipt::Panic { .. }))
+    else {
+        panic!("Expected a panic receipt: {receipts:?}");
+    };
+    assert_eq!(*reason.reason(), PanicReason::OutOfGas);
+    Instruction::try_from(*reason.instruction())
+        .expect("The instruction is valid")
+        .opcode()
+}
+
+#[test]
+fn memcpy_ecal__registers_its_base_cost() {
+    let vm: Interpreter<_, Script, MemcpyEcal> = Interpreter::with_memory_storage();
+
+    assert_eq!(vm.gas_costs().ecal(), MEMCPY_BASE_GAS);
+}
+
+#[test]
+fn memcpy_ecal__keeps_the_configured_base_cost() {
+    // Given
+    let mut consensus_params = ConsensusParameters::standard();
+    consensus_params.set_gas_costs(consensus_params.gas_costs().with_ecal(7));
+    let interpreter_params = InterpreterParams::new(0, &consensus_params);
+
+    // When
+    let vm: Interpreter<_, Script, MemcpyEcal> = Interpreter::with_storage_and_ecal(
+        MemoryStorage::default(),
+        interpreter_params,
+        MemcpyEcal,
+    );
+
+    // Then
+    assert_eq!(vm.gas_costs().ecal(), 7);
+}
+
+#[test]
+fn memcpy_ecal__charges_the_base_cost_and_each_copied_byte() {
+    // Given
+    let rng = &mut StdRng::seed_from_u64(2322);
+    let data: Bytes64 = rng.gen();
+    let gas_limit = 1_000_000;
+
+    // When
+    let receipts = memcpy_ecal_receipts(&data[..], gas_limit);
+    let without_data = memcpy_ecal_receipts(&[], gas_limit);
+
+    // Then
+    let Receipt::LogData {
+        data: Some(copied), ..
+    } = &receipts[1]
+    else {
+        panic!("Expected a log data receipt: {receipts:?}");
+    };
+    assert_eq!(copied.as_slice(), &data[..]);
+    assert_eq!(
+        remaining_after_ecal(&without_data) - remaining_after_ecal(&receipts),
+        data.len() as Word * MEMCPY_GAS_PER_BYTE
+    );
+}
+
+#[test]
+fn memcpy_ecal__runs_out_of_gas_at_the_boundary_of_its_charge() {
+    // Given
+    let rng = &mut StdRng::seed_from_u64(2322);
+    let data: Bytes64 = rng.gen();
+    let gas_limit = 1_000_000;
+    let needed =
+        gas_limit - remaining_after_ecal(&memcpy_ecal_receipts(&data[..], gas_limit));
+
+    // When
+    let enough = memcpy_ecal_receipts(&data[..], needed);
+    let short = memcpy_ecal_receipts(&data[..], needed - 1);
+
+    // Then
+    assert_eq!(out_of_gas_opcode(&enough), Opcode::LOG);
+    assert_eq!(out_of_gas_opcode(&short), Opcode::ECAL);
+    expect_receipts!(short, [.., panic(OutOfGas), script_result(Panic)]);
+    let gas_used = short.iter().find_map(Receipt::gas_used).unwrap();
+    assert_eq!(gas_used, needed - 1);
+}
@@ -0,0 +1,486 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use crate::{
+    checked_transaction::{
+        CheckError,
+        EstimatePredicates,
+        PredicateGas,
+        Ready,
+    },
+    interpreter::{
+        InterpreterParams,
+        Memory,
+    },
+    prelude::*,
+};
+use core::iter;
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::{
+        Inputs,
+        Outputs,
+    },
+    ConsensusParameters,
+    FeeParameters,
+    TransactionBuilder,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use test_case::test_case;
+
+const GAS_PRICE: Word = 7;
+const INPUT_AMOUNT: Word = 1_000_000_000;
+const MAX_FEE_LIMIT: Word = 100_000_000;
+
+fn consensus_params() -> ConsensusParameters {
+    let mut params = ConsensusParameters::standard();
+    params.set_fee_params(FeeParameters::default().with_gas_price_factor(92));
+    params
+}
+
+fn script_with_size(noops: usize) -> Vec<u8> {
+    iter::repeat(op::noop())
+        .take(noops)
+        .chain(iter::once(op::ret(RegId::ONE)))
+        .collect()
+}
+
+fn estimate_and_execute(tx: Script, params: &ConsensusParameters) {
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+
+    let ready = tx
+        .into_checked(Default::default(), params)
+        .expect("Should check the transaction")
+        .into_ready(GAS_PRICE, params.gas_costs(), params.fee_params())
+        .expect("Should cover the max fee");
+
+    let (gas_used, change) = execute(ready, params);
+
+    let (final_fee, refund) = fee
+        .fee_after_execution(gas_used)
+        .expect("Should compute the final fee");
+
+    assert_eq!(change, INPUT_AMOUNT - MAX_FEE_LIMIT + refund);
+    assert_eq!(final_fee + refund, MAX_FEE_LIMIT);
+    assert!(final_fee <= fee.max_fee());
+}
+
+/// Executes the `ready` transaction, returning the gas used by the script and the
+/// amount of the change output.
+fn execute(ready: Ready<Script>, params: &ConsensusParameters) -> (Word, Word) {
+    let mut transactor = Transactor::<_, _>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(GAS_PRICE, params),
+    );
+    let state = transactor
+        .transact_ready_tx(ready)
+        .to_owned_state_transition()
+        .expect("Should execute the transaction");
+    let gas_used = state
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .expect("Should have the script result");
+    let change = state
+        .tx()
+        .outputs()
+        .iter()
+        .find_map(|output| match output {
+            Output::Change { amount, .. } => Some(*amount),
+            _ => None,
+        })
+        .expect("Should have the change output");
+
+    (gas_used, change)
+}
+
+#[test_case(0; "empty script")]
+#[test_case(10; "small script")]
+#[test_case(1_000; "large script")]
+fn fee_estimation__matches_execution_for_script(noops: usize) {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = consensus_params();
+    let gas_limit = 100_000;
+
+    let tx = TransactionBuilder::script(script_with_size(noops), vec![])
+        .with_params(params.clone())
+        .script_gas_limit(gas_limit)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            INPUT_AMOUNT,
+            *params.base_asset_id(),
+            Default::default(),
+        )
+        .add_output(Output::change(rng.gen(), 0, *params.base_asset_id()))
+        .finalize();
+
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+    assert_eq!(fee.script_gas_limit(), gas_limit);
+    assert_eq!(fee.predicate_gas(), 0);
+    assert_eq!(fee.inputs_gas(), params.gas_costs().ecr1());
+
+    estimate_and_execute(tx, &params);
+}
+
+#[test]
+fn fee_estimation__matches_execution_for_predicate_input() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = consensus_params();
+
+    let predicate: Vec<u8> = iter::repeat(op::noop())
+        .take(100)
+        .chain(iter::once(op::ret(RegId::ONE)))
+        .collect();
+    let owner = Input::predicate_owner(&predicate);
+
+    let mut tx = TransactionBuilder::script(script_with_size(10), vec![])
+        .with_params(params.clone())
+        .script_gas_limit(10_000)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            INPUT_AMOUNT,
+            *params.base_asset_id(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .add_output(Output::change(owner, 0, *params.base_asset_id()))
+        .finalize();
+    tx.estimate_predicates(&params.clone().into())
+        .expect("Should estimate the predicate");
+
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+    assert!(fee.predicate_gas() > 0);
+    assert_eq!(
+        fee.predicate_gas(),
+        tx.inputs()[0].predicate_gas_used().unwrap()
+    );
+
+    estimate_and_execute(tx, &params);
+}
+
+fn witness_heavy_tx(rng: &mut StdRng, params: &ConsensusParameters) -> Script {
+    let mut builder = TransactionBuilder::script(script_with_size(10), vec![]);
+    builder
+        .with_params(params.clone())
+        .script_gas_limit(10_000)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            INPUT_AMOUNT,
+            *params.base_asset_id(),
+            Default::default(),
+        )
+        .add_output(Output::change(rng.gen(), 0, *params.base_asset_id()));
+    builder.add_witness(vec![0; 10_000].into());
+    builder.finalize()
+}
+
+#[test]
+fn fee_estimation__da_gas_per_byte_defaults_to_gas_per_byte() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = consensus_params();
+    let fee_params = params.fee_params();
+    let tx = witness_heavy_tx(rng, &params);
+
+    // When
+    let default_fee =
+        TransactionFee::checked_from_tx(params.gas_costs(), fee_params, &tx, GAS_PRICE)
+            .expect("Should estimate the fee");
+    let explicit_fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        &fee_params.with_da_gas_per_byte(fee_params.gas_per_byte()),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+
+    // Then
+    assert_eq!(default_fee, explicit_fee);
+    assert_eq!(
+        default_fee.bytes_gas(),
+        tx.metered_bytes_size() as Word * fee_params.gas_per_byte()
+    );
+}
+
+#[test]
+fn fee_estimation__da_gas_per_byte_prices_witness_bytes() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = consensus_params();
+    let mut da_params = params.clone();
+    da_params.set_fee_params(
+        params
+            .fee_params()
+            .with_da_gas_per_byte(4 * params.fee_params().gas_per_byte()),
+    );
+    let tx = witness_heavy_tx(rng, &params);
+
+    // When
+    let default_fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+    let da_fee = TransactionFee::checked_from_tx(
+        da_params.gas_costs(),
+        da_params.fee_params(),
+        &tx,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+
+    // Then
+    assert!(default_fee.da_bytes_gas() > default_fee.execution_bytes_gas());
+    assert_eq!(da_fee.da_bytes_gas(), 4 * default_fee.da_bytes_gas());
+    assert_eq!(
+        da_fee.execution_bytes_gas(),
+        default_fee.execution_bytes_gas()
+    );
+    assert_eq!(
+        da_fee.min_gas() - default_fee.min_gas(),
+        3 * default_fee.da_bytes_gas()
+    );
+    assert!(da_fee.max_fee() > default_fee.max_fee());
+    estimate_and_execute(tx, &da_params);
+}
+
+/// Checks that the transaction built with the max fee limit equal to the `max_gas` is
+/// executed, and that with one unit less it fails the max fee check. The gas price and
+/// the gas price factor are `1`, so the max fee is equal to the max gas.
+fn assert_max_gas_is_exact<F>(build: F)
+where
+    F: Fn(Word, &ConsensusParameters) -> Script,
+{
+    const GAS_PRICE: Word = 1;
+    let mut params = ConsensusParameters::standard();
+    params.set_fee_params(FeeParameters::default().with_gas_price_factor(1));
+    let max_gas = fuel_tx::fee::max_gas(
+        &build(1, &params),
+        params.gas_costs(),
+        params.fee_params(),
+    );
+
+    // When
+    let exact = build(max_gas, &params)
+        .into_checked(Default::default(), &params)
+        .expect("Should check the transaction")
+        .into_ready(GAS_PRICE, params.gas_costs(), params.fee_params());
+    let insufficient = build(max_gas - 1, &params)
+        .into_checked(Default::default(), &params)
+        .expect("Should check the transaction")
+        .into_ready(GAS_PRICE, params.gas_costs(), params.fee_params());
+
+    // Then
+    let ready = exact.expect("The max gas should cover the max fee");
+    let mut transactor = Transactor::<_, _>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(GAS_PRICE, &params),
+    );
+    let state = transactor
+        .transact_ready_tx(ready)
+        .to_owned_state_transition()
+        .expect("Should execute the transaction");
+    assert!(!state.should_revert());
+    assert_eq!(
+        insufficient.unwrap_err(),
+        CheckError::InsufficientMaxFee {
+            max_fee_from_policies: max_gas - 1,
+            max_fee_from_gas_price: max_gas,
+        }
+    );
+}
+
+#[test]
+fn max_gas__is_the_exact_max_fee_for_signed_script() {
+    assert_max_gas_is_exact(|max_fee_limit, params| {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        TransactionBuilder::script(script_with_size(10), vec![])
+            .with_params(params.clone())
+            .script_gas_limit(10_000)
+            .max_fee_limit(max_fee_limit)
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                INPUT_AMOUNT,
+                *params.base_asset_id(),
+                Default::default(),
+            )
+            .add_output(Output::change(rng.gen(), 0, *params.base_asset_id()))
+            .finalize()
+    });
+}
+
+#[test]
+fn max_gas__is_the_exact_max_fee_for_script_with_predicate() {
+    assert_max_gas_is_exact(|max_fee_limit, params| {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let predicate = script_with_size(100);
+        let owner = Input::predicate_owner(&predicate);
+        let mut tx = TransactionBuilder::script(script_with_size(10), vec![])
+            .with_params(params.clone())
+            .script_gas_limit(10_000)
+            .max_fee_limit(max_fee_limit)
+            .add_input(Input::coin_predicate(
+                rng.gen(),
+                owner,
+                INPUT_AMOUNT,
+                *params.base_asset_id(),
+                Default::default(),
+                0,
+                predicate,
+                vec![],
+            ))
+            .add_output(Output::change(owner, 0, *params.base_asset_id()))
+            .finalize();
+        tx.estimate_predicates(&params.clone().into())
+            .expect("Should estimate the predicate");
+        tx
+    });
+}
+
+/// The script spending the coin of a predicate running 100 `noop`s, declaring the
+/// `predicate_gas_used` returned by `declared` from the estimated gas, and the estimated
+/// gas.
+fn script_with_declared_predicate_gas<F>(
+    params: &ConsensusParameters,
+    declared: F,
+) -> (Script, Word)
+where
+    F: FnOnce(Word) -> Word,
+{
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let predicate = script_with_size(100);
+    let owner = Input::predicate_owner(&predicate);
+    let mut builder = TransactionBuilder::script(script_with_size(10), vec![]);
+    builder
+        .with_params(params.clone())
+        .script_gas_limit(10_000)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            INPUT_AMOUNT,
+            *params.base_asset_id(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .add_output(Output::change(owner, 0, *params.base_asset_id()));
+    builder
+        .estimate_predicates(
+            &params.clone().into(),
+            &mut Memory::new(),
+            &mut PredicateStorage,
+        )
+        .expect("Should estimate the predicate");
+    let estimated = builder.inputs()[0]
+        .predicate_gas_used()
+        .expect("The input has a predicate");
+    builder.set_predicate_gas_used(0, declared(estimated));
+
+    (builder.finalize(), estimated)
+}
+
+#[test]
+fn refund__the_unused_declared_predicate_gas_is_refunded_to_the_change() {
+    let params = consensus_params();
+    let (exact, estimated) = script_with_declared_predicate_gas(&params, |gas| gas);
+    let (generous, _) = script_with_declared_predicate_gas(&params, |gas| 10 * gas);
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &generous,
+        GAS_PRICE,
+    )
+    .expect("Should estimate the fee");
+
+    // When
+    let check = |tx: Script| {
+        let checked = tx
+            .into_checked(Default::default(), &params)
+            .expect("Should check the transaction");
+        let predicate_gas = checked.metadata().predicate_gas.clone();
+        let ready = checked
+            .into_ready(GAS_PRICE, params.gas_costs(), params.fee_params())
+            .expect("Should cover the max fee");
+        (predicate_gas, execute(ready, &params))
+    };
+    let (_, (_, exact_change)) = check(exact);
+    let (predicate_gas, (gas_used, generous_change)) = check(generous);
+
+    // Then
+    assert_eq!(
+        predicate_gas[..],
+        [PredicateGas {
+            input_index: 0,
+            declared: 10 * estimated,
+            used: Some(estimated),
+        }]
+    );
+    assert_eq!(predicate_gas.charged(), estimated);
+    let (final_fee, refund) = fee
+        .fee_after_execution_with_predicate_gas(gas_used, estimated)
+        .expect("Should compute the final fee");
+    let (_, refund_of_declared_gas) = fee
+        .fee_after_execution(gas_used)
+        .expect("Should compute the final fee");
+    assert_eq!(generous_change, INPUT_AMOUNT - MAX_FEE_LIMIT + refund);
+    assert_eq!(final_fee + refund, MAX_FEE_LIMIT);
+    assert!(refund > refund_of_declared_gas);
+    assert_eq!(
+        generous_change, exact_change,
+        "Only the gas used by the predicate is charged"
+    );
+}
+
+#[test]
+fn check_predicates__a_predicate_exceeding_its_declared_gas_fails() {
+    let params = consensus_params();
+    let (tx, _) = script_with_declared_predicate_gas(&params, |gas| gas - 1);
+
+    // When
+    let result = tx.into_checked(Default::default(), &params);
+
+    // Then
+    assert!(
+        matches!(result, Err(CheckError::PredicateVerificationFailed(_))),
+        "{result:?}"
+    );
+}
@@ -6,6 +6,7 @@ use alloc::{
 
 use crate::{
     consts::*,
+    expect_receipts,
     prelude::*,
     script_with_data_offset,
     util::test_helpers::TestBuilder,
@@ -302,25 +303,11 @@ fn repeated_nested_calls() {
         .fee_input()
         .contract_output(&contract_id)
         .execute();
-    let receipts = result.receipts();
-
-    if let Receipt::ScriptResult { result, .. } = receipts[receipts.len() - 1] {
-        if result != ScriptExecutionResult::Panic {
-            panic!("Expected vm panic, got {result:?} instead");
-        }
-    } else {
-        unreachable!("No script result");
-    }
 
-    if let Receipt::Panic { reason: pr, .. } = receipts[receipts.len() - 2] {
-        assert_eq!(
-            *pr.reason(),
-            PanicReason::OutOfGas,
-            "Panic reason differs for the expected reason"
-        );
-    } else {
-        unreachable!("No script receipt for a paniced tx");
-    }
+    expect_receipts!(
+        result.receipts(),
+        [.., panic(OutOfGas), script_result(Panic)]
+    );
 }
 
 #[test]
@@ -0,0 +1,144 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    interpreter::{
+        FrameGas,
+        InterpreterParams,
+    },
+    prelude::*,
+    util::test_helpers::TestBuilder,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+const SCRIPT_LOOPS: u16 = 200;
+const A_LOOPS: u16 = 400;
+const B_LOOPS: u16 = 100;
+/// The gas of the instructions around the loops of a frame, like the calls.
+const TOLERANCE: Word = 250;
+
+/// Burns the gas of `loops` iterations of a `noop`.
+fn burn(loops: u16) -> Vec<Instruction> {
+    vec![
+        op::movi(0x11, loops.into()),
+        op::noop(),
+        op::subi(0x11, 0x11, 1),
+        op::jnzb(0x11, RegId::ZERO, 1),
+    ]
+}
+
+fn loop_gas(gas_costs: &GasCosts, loops: u16) -> Word {
+    gas_costs.movi()
+        + Word::from(loops) * (gas_costs.noop() + gas_costs.subi() + gas_costs.jnzb())
+}
+
+fn assert_burned(frame: &FrameGas, expected: Word) {
+    assert!(
+        (expected..expected + TOLERANCE).contains(&frame.self_gas),
+        "Expected about {expected} gas for {frame:?}"
+    );
+}
+
+/// Runs the script calling the contract A, calling the contract B, ending with the
+/// `last` instruction of B.
+fn run(last: Instruction) -> (Vec<FrameGas>, Vec<Receipt>, GasCosts, [ContractId; 2]) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let mut b_code = burn(B_LOOPS);
+    b_code.push(last);
+    let b = test_context.setup_contract(b_code, None, None).contract_id;
+    let call_len = Call::new(b, 0, 0).to_bytes().len();
+    let mut a_code = burn(A_LOOPS);
+    a_code.extend([
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::addi(0x10, 0x10, call_len as Immediate12),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ]);
+    let a = test_context.setup_contract(a_code, None, None).contract_id;
+    let mut script = burn(SCRIPT_LOOPS);
+    script.extend([
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ]);
+    let mut script_data = Call::new(a, 0, 0).to_bytes();
+    script_data.extend(Call::new(b, 0, 0).to_bytes());
+
+    let consensus_params = ConsensusParameters::standard();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(a)
+        .contract_input(b)
+        .fee_input()
+        .contract_output(&a)
+        .contract_output(&b)
+        .build();
+    let mut transactor = Transactor::<_, Script>::new(
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, &consensus_params),
+    );
+    transactor.transact(tx);
+
+    (
+        transactor.frame_gas_report(),
+        transactor.interpreter().receipts().to_vec(),
+        transactor.gas_costs().clone(),
+        [a, b],
+    )
+}
+
+fn gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("The script result is missing")
+}
+
+#[test]
+fn frame_gas_report__splits_the_gas_of_the_nested_calls() {
+    // When
+    let (frames, receipts, gas_costs, [a, b]) = run(op::ret(RegId::ONE));
+
+    // Then
+    let [script, in_a, in_b] = frames.as_slice() else {
+        panic!("Expected three frames, got {frames:?}")
+    };
+    assert_eq!((script.contract_id, script.depth), (None, 0));
+    assert_eq!((in_a.contract_id, in_a.depth), (Some(a), 1));
+    assert_eq!((in_b.contract_id, in_b.depth), (Some(b), 2));
+    assert_burned(script, loop_gas(&gas_costs, SCRIPT_LOOPS));
+    assert_burned(in_a, loop_gas(&gas_costs, A_LOOPS));
+    assert_burned(in_b, loop_gas(&gas_costs, B_LOOPS));
+    assert_eq!(in_b.total_gas, in_b.self_gas);
+    assert_eq!(in_a.total_gas, in_a.self_gas + in_b.total_gas);
+    assert_eq!(script.total_gas, script.self_gas + in_a.total_gas);
+    assert_eq!(script.total_gas, gas_used(&receipts));
+}
+
+#[test]
+fn frame_gas_report__reports_the_gas_of_the_reverted_frames() {
+    // When
+    let (frames, receipts, gas_costs, _) = run(op::rvrt(RegId::ONE));
+
+    // Then
+    let [script, in_a, in_b] = frames.as_slice() else {
+        panic!("Expected three frames, got {frames:?}")
+    };
+    assert!(receipts
+        .iter()
+        .any(|receipt| matches!(receipt, Receipt::Revert { .. })));
+    assert_burned(in_b, loop_gas(&gas_costs, B_LOOPS));
+    assert_eq!(in_a.total_gas, in_a.self_gas + in_b.total_gas);
+    assert_eq!(script.total_gas, script.self_gas + in_a.total_gas);
+    assert_eq!(script.total_gas, gas_used(&receipts));
+}
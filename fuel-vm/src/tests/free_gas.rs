@@ -0,0 +1,200 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::{
+        CheckError,
+        Checks,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_crypto::SecretKey;
+use fuel_tx::{
+    field::Outputs,
+    ConsensusParameters,
+    TransactionBuilder,
+    ValidityError,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+const GAS_LIMIT: Word = 1_000;
+const MAX_FEE_LIMIT: Word = 1_000;
+const LOOPS: u32 = 100_000;
+
+/// A script looping [`LOOPS`] times, far beyond the [`GAS_LIMIT`], with a zero-value
+/// coin input and a change output.
+fn looping_tx(amount: Word) -> Script {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+    let script = vec![
+        op::movi(0x10, LOOPS),
+        op::subi(0x10, 0x10, 1),
+        op::jnzb(0x10, RegId::ZERO, 0),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect::<Vec<u8>>();
+
+    TransactionBuilder::script(script, vec![])
+        .script_gas_limit(GAS_LIMIT)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            amount,
+            *params.base_asset_id(),
+            Default::default(),
+        )
+        .add_output(Output::change(rng.gen(), 0, *params.base_asset_id()))
+        .with_params(params)
+        .finalize()
+}
+
+fn gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("The script result is missing")
+}
+
+#[test]
+fn into_checked_free_gas__skips_the_fee_checks() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let tx = looping_tx(0);
+
+    // When
+    let err = tx
+        .clone()
+        .into_checked(Default::default(), &params)
+        .expect_err("The inputs don't cover the max fee");
+    let checked = tx
+        .into_checked_free_gas(Default::default(), &params)
+        .expect("The fees aren't checked");
+
+    // Then
+    assert_eq!(
+        err,
+        CheckError::Validity(ValidityError::InsufficientFeeAmount {
+            expected: MAX_FEE_LIMIT,
+            provided: 0,
+        })
+    );
+    assert_eq!(
+        checked.checks(),
+        &(Checks::Basic | Checks::Signatures | Checks::Predicates)
+    );
+}
+
+#[test]
+fn free_gas__runs_a_loop_exhausting_the_gas_limit() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let checked = looping_tx(0)
+        .into_checked_free_gas(Default::default(), &params)
+        .unwrap();
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::free_gas(&params),
+    );
+
+    // When
+    let state = transactor
+        .transact(checked)
+        .to_owned_state_transition()
+        .expect("The free gas transaction is executed");
+
+    // Then
+    assert_eq!(state.state(), &ProgramState::Return(1));
+    assert!(gas_used(state.receipts()) > GAS_LIMIT);
+    assert_eq!(
+        state.tx().outputs()[0].amount(),
+        Some(0),
+        "The max fee that wasn't deducted isn't refunded"
+    );
+}
+
+#[test]
+fn free_gas__refunds_the_deducted_max_fee() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let checked = looping_tx(MAX_FEE_LIMIT)
+        .into_checked(Default::default(), &params)
+        .unwrap();
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::free_gas(&params),
+    );
+
+    // When
+    let state = transactor
+        .transact(checked)
+        .to_owned_state_transition()
+        .unwrap();
+
+    // Then
+    assert_eq!(state.state(), &ProgramState::Return(1));
+    assert_eq!(state.tx().outputs()[0].amount(), Some(MAX_FEE_LIMIT));
+}
+
+#[test]
+fn free_gas__the_same_loop_runs_out_of_gas_with_the_gas_limit() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let checked = looping_tx(MAX_FEE_LIMIT)
+        .into_checked(Default::default(), &params)
+        .unwrap();
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, &params),
+    );
+
+    // When
+    let receipts = transactor.transact(checked).receipts().unwrap().to_vec();
+
+    // Then
+    assert!(receipts.iter().any(|receipt| matches!(
+        receipt,
+        Receipt::Panic { reason, .. } if *reason.reason() == PanicReason::OutOfGas
+    )));
+    assert_eq!(gas_used(&receipts), GAS_LIMIT);
+}
+
+#[test]
+fn transactor__rejects_a_free_gas_tx_without_the_free_gas_params() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let checked = looping_tx(0)
+        .into_checked_free_gas(Default::default(), &params)
+        .unwrap();
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, &params),
+    );
+
+    // When
+    transactor.transact(checked);
+
+    // Then
+    let err = transactor.result().expect_err("The fees aren't checked");
+    assert!(matches!(
+        err,
+        InterpreterError::CheckError(CheckError::InsufficientChecks {
+            required,
+            performed,
+        }) if required.contains(Checks::Fees) && !performed.contains(Checks::Fees)
+    ));
+}
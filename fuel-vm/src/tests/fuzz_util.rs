@@ -0,0 +1,131 @@
+#![cfg(feature = "fuzzing")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    fuzz_util::{
+        execute_reproducer,
+        make_reproducer,
+        minimize_script,
+        ReproResult,
+        ReproducerGuard,
+    },
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+
+fn reproducer(script: &[Instruction]) -> Vec<u8> {
+    let tx = TransactionBuilder::script(script.iter().copied().collect(), vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into();
+    make_reproducer(
+        &ConsensusParameters::standard(),
+        &MemoryStorage::default(),
+        &tx,
+        0,
+    )
+}
+
+fn dividing_by_zero() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 1),
+        op::div(0x11, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn execute_reproducer__reproduces_the_panic() {
+    // Given
+    let reproducer = reproducer(&dividing_by_zero());
+
+    // When
+    let first = execute_reproducer(&reproducer);
+    let second = execute_reproducer(&reproducer);
+
+    // Then
+    assert_eq!(first.panic_reason(), Some(PanicReason::ArithmeticError));
+    assert!(matches!(
+        first,
+        ReproResult::Executed {
+            state: ProgramState::Revert(_),
+            ..
+        }
+    ));
+    assert_eq!(first, second);
+}
+
+#[test]
+fn execute_reproducer__rejects_an_invalid_blob() {
+    let result = execute_reproducer(&[0xff; 3]);
+
+    assert!(matches!(result, ReproResult::Invalid(_)));
+    assert!(result.receipts().is_empty());
+}
+
+#[test]
+fn minimize_script__finds_the_shortest_failing_prefix() {
+    // Given
+    let mut script = vec![op::noop(); 5];
+    script.extend(dividing_by_zero());
+    script.extend([op::noop(); 20]);
+    let mut executions = 0;
+
+    // When
+    let minimized = minimize_script(&script, |script| {
+        executions += 1;
+        execute_reproducer(&reproducer(script)).panic_reason()
+            == Some(PanicReason::ArithmeticError)
+    });
+
+    // Then
+    let mut expected = vec![op::noop(); 5];
+    expected.extend(&dividing_by_zero()[..2]);
+    assert_eq!(minimized, Some(expected));
+    assert!(executions <= 7, "{executions} executions");
+}
+
+#[test]
+fn minimize_script__keeps_a_successful_script() {
+    let minimized = minimize_script(&[op::ret(RegId::ONE)], |script| {
+        execute_reproducer(&reproducer(script))
+            .panic_reason()
+            .is_some()
+    });
+
+    assert_eq!(minimized, None);
+}
+
+#[test]
+fn reproducer_guard__keeps_the_reproducer_of_a_panic() {
+    // Given
+    let directory = std::env::temp_dir();
+    let panicking = directory.join(format!("panicking-{}.repro", std::process::id()));
+    let succeeding = directory.join(format!("succeeding-{}.repro", std::process::id()));
+    let reproducer = reproducer(&dividing_by_zero());
+
+    // When
+    let result = std::panic::catch_unwind(|| {
+        let _guard = ReproducerGuard::new(&panicking, &reproducer).unwrap();
+        panic!("The fuzz target panicked")
+    });
+    drop(ReproducerGuard::new(&succeeding, &reproducer).unwrap());
+
+    // Then
+    assert!(result.is_err());
+    let saved = std::fs::read(&panicking).unwrap();
+    std::fs::remove_file(&panicking).unwrap();
+    assert_eq!(saved, reproducer);
+    assert!(!succeeding.exists());
+}
@@ -0,0 +1,180 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    interpreter::{
+        FrameGas,
+        InterpreterParamsBuilder,
+    },
+    prelude::*,
+    util::test_helpers::TestBuilder,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+const GAS_LIMIT: Word = 1_000_000;
+const B_LOOPS: u16 = 10_000;
+const B_ALLOWANCE: Word = 1_000;
+/// The value logged by A after the call to B.
+const AFTER_CALL: u16 = 42;
+
+struct Execution {
+    receipts: Vec<Receipt>,
+    state: ProgramState,
+    frames: Vec<FrameGas>,
+}
+
+/// Runs the script calling the contract A, calling the contract B looping
+/// [`B_LOOPS`] times, and then logging [`AFTER_CALL`] and the `$ret` of B, with the
+/// gas `allowance` of A and B.
+fn run(
+    allowance: impl FnOnce(&mut InterpreterParamsBuilder, [ContractId; 2]),
+) -> (Execution, [ContractId; 2]) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let b_code = vec![
+        op::movi(0x11, B_LOOPS.into()),
+        op::noop(),
+        op::subi(0x11, 0x11, 1),
+        op::jnzb(0x11, RegId::ZERO, 1),
+        op::ret(RegId::ONE),
+    ];
+    let b = test_context.setup_contract(b_code, None, None).contract_id;
+    let call_len = Call::new(b, 0, 0).to_bytes().len();
+    let a_code = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::addi(0x10, 0x10, call_len as Immediate12),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::movi(0x12, AFTER_CALL.into()),
+        op::log(0x12, RegId::RET, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let a = test_context.setup_contract(a_code, None, None).contract_id;
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let mut script_data = Call::new(a, 0, 0).to_bytes();
+    script_data.extend(Call::new(b, 0, 0).to_bytes());
+
+    let consensus_params = ConsensusParameters::standard();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(GAS_LIMIT)
+        .contract_input(a)
+        .contract_input(b)
+        .fee_input()
+        .contract_output(&a)
+        .contract_output(&b)
+        .build();
+    let mut params = InterpreterParamsBuilder::new(&consensus_params);
+    allowance(&mut params, [a, b]);
+    let mut transactor = Transactor::<_, Script>::new(
+        test_context.get_storage().clone(),
+        params.build().unwrap(),
+    );
+    transactor.transact(tx);
+
+    let execution = Execution {
+        receipts: transactor.interpreter().receipts().to_vec(),
+        state: *transactor.state_transition().unwrap().state(),
+        frames: transactor.frame_gas_report(),
+    };
+    (execution, [a, b])
+}
+
+fn gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("The script result is missing")
+}
+
+fn panicked_contract(receipts: &[Receipt]) -> Option<ContractId> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Panic { id, reason, .. }
+            if *reason.reason() == PanicReason::OutOfGas =>
+        {
+            Some(*id)
+        }
+        _ => None,
+    })
+}
+
+/// The `ra` and `rb` of the log of A after the call of B.
+fn logged_after_call(receipts: &[Receipt], a: ContractId) -> Option<(Word, Word)> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Log { id, ra, rb, .. } if *id == a => Some((*ra, *rb)),
+        _ => None,
+    })
+}
+
+#[test]
+fn gas_allowance__the_callee_exceeding_it_panics_and_the_caller_continues() {
+    // When
+    let (execution, [a, b]) = run(|params, [_, b]| {
+        params.gas_allowance(b, B_ALLOWANCE);
+    });
+
+    // Then
+    assert_eq!(execution.state, ProgramState::Return(1));
+    assert_eq!(panicked_contract(&execution.receipts), Some(b));
+    assert_eq!(
+        logged_after_call(&execution.receipts, a),
+        Some((AFTER_CALL.into(), 0)),
+        "A runs after the panic of B, which returns 0"
+    );
+    assert!(gas_used(&execution.receipts) < GAS_LIMIT);
+
+    let in_b = execution
+        .frames
+        .iter()
+        .find(|frame| frame.contract_id == Some(b))
+        .unwrap();
+    assert!(in_b.self_gas > B_ALLOWANCE, "{in_b:?}");
+    assert!(in_b.self_gas <= B_ALLOWANCE + 10, "{in_b:?}");
+}
+
+#[test]
+fn gas_allowance__the_callee_within_it_returns() {
+    // When
+    let (execution, [a, _]) = run(|params, [_, b]| {
+        params.gas_allowance(b, GAS_LIMIT);
+    });
+
+    // Then
+    assert_eq!(execution.state, ProgramState::Return(1));
+    assert_eq!(panicked_contract(&execution.receipts), None);
+    assert_eq!(
+        logged_after_call(&execution.receipts, a),
+        Some((AFTER_CALL.into(), 1))
+    );
+}
+
+#[test]
+fn gas_allowance__the_gas_of_the_callee_isnt_counted_against_the_caller() {
+    // When
+    let (without, _) = run(|_, _| {});
+    let (with_caller_allowance, [a, _]) = run(|params, [a, _]| {
+        params.gas_allowance(a, B_ALLOWANCE);
+    });
+
+    // Then
+    assert_eq!(panicked_contract(&with_caller_allowance.receipts), None);
+    assert_eq!(
+        gas_used(&without.receipts),
+        gas_used(&with_caller_allowance.receipts)
+    );
+    assert_eq!(
+        logged_after_call(&with_caller_allowance.receipts, a),
+        Some((AFTER_CALL.into(), 1))
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "gas-bench")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    string::ToString,
+    vec,
+};
+
+use crate::{
+    gas_bench::{
+        GasBench,
+        GasBenchConfig,
+        GasBenchEntry,
+        GasBenchReport,
+    },
+    prelude::*,
+};
+use fuel_asm::Opcode;
+use fuel_tx::GasCostValue;
+
+/// A sweep small enough for the CI, with meaningless costs.
+fn tiny_config() -> GasBenchConfig {
+    GasBenchConfig {
+        iterations: 2,
+        unroll: 2,
+        samples: 1,
+        sizes: vec![0, 64, 256],
+        ..Default::default()
+    }
+}
+
+fn entry(report: &GasBenchReport, opcode: Opcode) -> &GasBenchEntry {
+    report
+        .entries()
+        .iter()
+        .find(|entry| entry.opcode == opcode)
+        .unwrap()
+}
+
+#[test]
+fn gas_bench__measures_the_fixed_and_the_dependent_opcodes() {
+    // Given
+    let config = tiny_config().with_opcodes([Opcode::MUL, Opcode::MCP]);
+
+    // When
+    let report = GasBench::new(config).run();
+
+    // Then
+    assert_eq!(
+        entry(&report, Opcode::ADD).measured,
+        Some(GasCostValue::Fixed(1))
+    );
+    let Some(GasCostValue::Fixed(mul)) = entry(&report, Opcode::MUL).measured else {
+        panic!("{:?}", entry(&report, Opcode::MUL))
+    };
+    let Some(GasCostValue::Dependent(mcp)) = entry(&report, Opcode::MCP).measured else {
+        panic!("{:?}", entry(&report, Opcode::MCP))
+    };
+    assert_eq!(entry(&report, Opcode::DIV).measured, None);
+
+    let defaults = GasCostsValues::default();
+    let values = report.values();
+    assert_eq!(values.add(), 1);
+    assert_eq!(values.mul(), mul);
+    assert_eq!(values.mcp(), mcp);
+    assert_eq!(values.div(), defaults.div());
+    assert_eq!(values.jmp(), defaults.jmp());
+
+    let (loaded, warnings) = GasCosts::from_reader(report.to_json().as_bytes()).unwrap();
+    assert_eq!(*loaded, *values);
+    assert_eq!(warnings, vec![]);
+
+    let table = report.to_string();
+    let mul_line = table.lines().find(|line| line.starts_with("mul ")).unwrap();
+    assert!(mul_line.ends_with('%'), "{mul_line}");
+    let div_line = table.lines().find(|line| line.starts_with("div ")).unwrap();
+    assert!(div_line.ends_with(" -"), "{div_line}");
+}
+
+#[test]
+fn gas_bench__executes_the_script_of_each_benchmarked_opcode() {
+    // When
+    let report = GasBench::new(tiny_config()).run();
+
+    // Then
+    let measured = report
+        .entries()
+        .iter()
+        .filter(|entry| entry.measured.is_some())
+        .map(|entry| entry.key.as_str())
+        .collect::<alloc::vec::Vec<_>>();
+    assert_eq!(measured.len(), 45, "{measured:?}");
+    assert!(measured.contains(&"eck1"));
+    assert!(measured.contains(&"k256"));
+}
@@ -0,0 +1,153 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::CheckError,
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV1,
+    ConsensusParameters,
+    FeeParameters,
+    GasCosts,
+    TransactionBuilder,
+    ValidityError,
+};
+
+const GAS_LIMIT: Word = 1_000;
+const MAX_FEE_LIMIT: Word = 1_000;
+
+fn script_tx(
+    script: Vec<Instruction>,
+    gas_limit: Word,
+    params: &ConsensusParameters,
+) -> Script {
+    TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .script_gas_limit(gas_limit)
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_random_fee_input()
+        .with_params(params.clone())
+        .finalize()
+}
+
+fn into_ready(
+    tx: Script,
+    gas_price: Word,
+    params: &ConsensusParameters,
+) -> Result<crate::checked_transaction::Ready<Script>, CheckError> {
+    tx.into_checked(Default::default(), params)
+        .expect("The transaction is valid")
+        .into_ready(gas_price, params.gas_costs(), params.fee_params())
+}
+
+#[test]
+fn into_ready__max_gas_price_with_max_gas_limit_is_a_balance_overflow() {
+    // Given
+    let mut params = ConsensusParameters::standard();
+    params.set_tx_params(params.tx_params().with_max_gas_per_tx(Word::MAX));
+    params.set_fee_params(params.fee_params().with_gas_price_factor(1));
+    let tx = script_tx(vec![op::ret(RegId::ONE)], Word::MAX, &params);
+
+    // When
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        Word::MAX,
+    );
+    let err = into_ready(tx, Word::MAX, &params).expect_err("The fee overflows");
+
+    // Then
+    assert_eq!(fee, None);
+    assert_eq!(err, CheckError::Validity(ValidityError::BalanceOverflow));
+}
+
+#[test]
+fn into_ready__zero_gas_price_factor_is_a_balance_overflow() {
+    // Given
+    let mut params = ConsensusParameters::standard();
+    params.set_fee_params(FeeParameters::default().with_gas_price_factor(0));
+    let tx = script_tx(vec![op::ret(RegId::ONE)], GAS_LIMIT, &params);
+
+    // When
+    let err = into_ready(tx, 1, &params).expect_err("The fee is unpayable");
+
+    // Then
+    assert_eq!(err, CheckError::Validity(ValidityError::BalanceOverflow));
+}
+
+#[test]
+fn fee_after_execution__refunds_nothing_when_the_fee_exceeds_the_max_fee_limit() {
+    // Given
+    let params = ConsensusParameters::standard();
+    let gas_price = 1;
+    let tx = script_tx(vec![op::ret(RegId::ONE)], GAS_LIMIT, &params);
+    let fee = TransactionFee::checked_from_tx(
+        params.gas_costs(),
+        params.fee_params(),
+        &tx,
+        gas_price,
+    )
+    .unwrap();
+
+    // When
+    let within_limit = fee.fee_after_execution(GAS_LIMIT);
+    let beyond_limit = fee.fee_after_execution(Word::MAX);
+    let refund = tx.refund_fee(
+        params.gas_costs(),
+        params.fee_params(),
+        Word::MAX,
+        gas_price,
+    );
+
+    // Then
+    let (final_fee, refund_within_limit) = within_limit.expect("The fee is covered");
+    assert_eq!(final_fee + refund_within_limit, MAX_FEE_LIMIT);
+    assert_eq!(beyond_limit, None);
+    assert_eq!(refund, None);
+}
+
+#[test]
+fn gas_charge__a_dependent_cost_overflowing_u64_runs_out_of_gas() {
+    // Given
+    let mut params = ConsensusParameters::standard();
+    params.set_gas_costs(GasCosts::new(
+        GasCostsValuesV1 {
+            mcl: DependentCost::from_gas_per_unit(1, Word::MAX),
+            ..GasCostsValuesV1::unit()
+        }
+        .into(),
+    ));
+    let script = vec![
+        op::movi(0x10, 2),
+        op::aloc(0x10),
+        op::mcl(RegId::HP, 0x10),
+        op::ret(RegId::ONE),
+    ];
+    let ready = into_ready(script_tx(script, GAS_LIMIT, &params), 0, &params).unwrap();
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, &params),
+    );
+
+    // When
+    let state = vm.transact(ready).expect("The panic isn't an error");
+
+    // Then
+    let receipts = state.receipts();
+    assert!(receipts.iter().any(|receipt| matches!(
+        receipt,
+        Receipt::Panic { reason, .. } if *reason.reason() == PanicReason::OutOfGas
+    )));
+    let gas_used = receipts.iter().find_map(Receipt::gas_used).unwrap();
+    assert_eq!(gas_used, GAS_LIMIT);
+}
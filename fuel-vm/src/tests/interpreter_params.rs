@@ -0,0 +1,177 @@
+#![allow(non_snake_case)]
+
+use alloc::vec;
+
+use crate::{
+    consts::{
+        MEM_SIZE,
+        VM_MAX_RAM,
+    },
+    interpreter::{
+        InterpreterParams,
+        InterpreterParamsBuilder,
+        InterpreterParamsError,
+    },
+    prelude::*,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    FeeParameters,
+};
+
+#[test]
+fn build__the_default_builder_matches_the_standard_parameters() {
+    // When
+    let params = InterpreterParamsBuilder::default().build();
+
+    // Then
+    assert_eq!(
+        params,
+        Ok(InterpreterParams::new(0, ConsensusParameters::standard()))
+    );
+    assert_eq!(params, Ok(InterpreterParams::default()));
+}
+
+#[test]
+fn build__applies_the_chained_setters() {
+    // Given
+    let mut builder = InterpreterParamsBuilder::new(ConsensusParameters::standard());
+
+    // When
+    let params = builder
+        .gas_price(7)
+        .max_inputs(3)
+        .chain_id(9.into())
+        .free_gas(true)
+        .build()
+        .unwrap();
+
+    // Then
+    assert_eq!(params.gas_price, 7);
+    assert_eq!(params.max_inputs, 3);
+    assert_eq!(params.chain_id, 9.into());
+    assert!(params.free_gas);
+}
+
+#[test]
+fn build__rejects_a_tx_offset_outside_of_the_memory() {
+    // When
+    let err = InterpreterParamsBuilder::default()
+        .tx_offset(MEM_SIZE)
+        .build()
+        .expect_err("The transaction doesn't fit in the memory");
+
+    // Then
+    assert_eq!(err, InterpreterParamsError::TxOffsetOutOfMemory(MEM_SIZE));
+}
+
+#[test]
+fn build__rejects_sizes_exceeding_the_memory() {
+    // When
+    let contract = InterpreterParamsBuilder::default()
+        .contract_max_size(VM_MAX_RAM + 1)
+        .build();
+    let message = InterpreterParamsBuilder::default()
+        .max_message_data_length(VM_MAX_RAM + 1)
+        .build();
+
+    // Then
+    assert_eq!(
+        contract,
+        Err(InterpreterParamsError::ContractMaxSizeExceedsMemory(
+            VM_MAX_RAM + 1
+        ))
+    );
+    assert_eq!(
+        message,
+        Err(InterpreterParamsError::MessageDataLengthExceedsMemory(
+            VM_MAX_RAM + 1
+        ))
+    );
+}
+
+#[test]
+fn build__rejects_a_zero_gas_price_factor() {
+    // When
+    let err = InterpreterParamsBuilder::default()
+        .fee_params(FeeParameters::default().with_gas_price_factor(0))
+        .build()
+        .expect_err("The fees are unpayable");
+
+    // Then
+    assert_eq!(err, InterpreterParamsError::ZeroGasPriceFactor);
+    assert_eq!(err.to_string(), "The gas price factor is zero");
+}
+
+#[cfg(feature = "serde")]
+fn custom_params() -> InterpreterParams {
+    InterpreterParamsBuilder::default()
+        .gas_price(3)
+        .gas_costs(GasCosts::unit())
+        .fee_params(FeeParameters::default().with_gas_price_factor(2))
+        .gas_allowance([7; 32].into(), 1_000)
+        .build()
+        .unwrap()
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde__round_trips_the_parameters() {
+    // Given
+    let params = custom_params();
+
+    // When
+    let json = serde_json::to_string(&params).unwrap();
+    let deserialized: InterpreterParams = serde_json::from_str(&json).unwrap();
+
+    // Then
+    assert_eq!(deserialized, params);
+    assert_eq!(
+        InterpreterParamsBuilder::from(deserialized).build(),
+        Ok(params)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn transactor__with_deserialized_params_behaves_like_with_params_built_in_code() {
+    use fuel_asm::{
+        op,
+        RegId,
+    };
+
+    // Given
+    let params = custom_params();
+    let json = serde_json::to_vec(&params).unwrap();
+    let deserialized: InterpreterParams = serde_json::from_slice(&json).unwrap();
+    let consensus_params = ConsensusParameters::standard();
+    let script = vec![
+        op::movi(0x10, 5),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .script_gas_limit(1_000)
+        .max_fee_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .unwrap();
+    let execute = |params: InterpreterParams| {
+        let mut transactor =
+            Transactor::<_, Script>::new(MemoryStorage::default(), params);
+        transactor
+            .transact(tx.clone())
+            .to_owned_state_transition()
+            .unwrap()
+    };
+
+    // When
+    let in_code = execute(params);
+    let from_json = execute(deserialized);
+
+    // Then
+    assert_eq!(in_code.state(), &ProgramState::Return(1));
+    assert_eq!(in_code.receipts(), from_json.receipts());
+    assert_eq!(in_code.tx(), from_json.tx());
+}
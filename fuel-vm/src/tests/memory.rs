@@ -1,29 +1,35 @@
 #![cfg(feature = "std")]
 
 use fuel_asm::PanicReason;
-use test_case::test_case;
+
+use crate::{
+    consts::VM_MAX_RAM,
+    expect_receipts,
+    interpreter::InterpreterParams,
+    prelude::*,
+};
 
 use fuel_asm::{
     op,
     RegId,
 };
-use fuel_tx::Receipt;
-use fuel_vm::{
-    consts::VM_MAX_RAM,
-    interpreter::InterpreterParams,
-    prelude::*,
+use fuel_tx::{
+    Receipt,
+    ReceiptsExt,
 };
 
 use super::test_helpers::{
     assert_panics,
-    run_script,
+    kv_transaction_storage,
+    redb_storage,
     set_full_word,
 };
 use fuel_tx::ConsensusParameters;
 
-fn setup(program: Vec<Instruction>) -> Transactor<MemoryStorage, Script> {
-    let storage = MemoryStorage::default();
-
+fn setup<S: InterpreterStorage>(
+    storage: S,
+    program: Vec<Instruction>,
+) -> Transactor<S, Script> {
     let gas_price = 0;
     let gas_limit = 1_000_000;
     let maturity = Default::default();
@@ -48,8 +54,11 @@ fn setup(program: Vec<Instruction>) -> Transactor<MemoryStorage, Script> {
     vm
 }
 
-#[test]
-fn test_lw() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_lw(#[case] storage: impl InterpreterStorage) {
     let ops = vec![
         op::movi(0x10, 8),
         op::aloc(0x10),
@@ -58,14 +67,17 @@ fn test_lw() {
         op::lw(0x13, 0x10, 0),
         op::ret(RegId::ONE),
     ];
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
     let result = vm.registers()[0x13_usize];
     assert_eq!(1, result);
 }
 
-#[test]
-fn test_lw_unaglined() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_lw_unaglined(#[case] storage: impl InterpreterStorage) {
     let ops = vec![
         op::movi(0x10, 9),
         op::aloc(0x10),
@@ -74,14 +86,17 @@ fn test_lw_unaglined() {
         op::lw(0x13, 0x10, 0),
         op::ret(RegId::ONE),
     ];
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
     let result = vm.registers()[0x13_usize];
     assert_eq!(1, result);
 }
 
-#[test]
-fn test_lb() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_lb(#[case] storage: impl InterpreterStorage) {
     let ops = vec![
         op::movi(0x10, 8),
         op::aloc(0x10),
@@ -90,14 +105,17 @@ fn test_lb() {
         op::lb(0x13, 0x10, 0),
         op::ret(RegId::ONE),
     ];
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
     let result = vm.registers()[0x13_usize] as u8;
     assert_eq!(1, result);
 }
 
-#[test]
-fn test_aloc_sb_lb_last_byte_of_memory() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_aloc_sb_lb_last_byte_of_memory(#[case] storage: impl InterpreterStorage) {
     let ops = vec![
         op::move_(0x20, RegId::HP),
         op::movi(0x10, 1),
@@ -107,8 +125,8 @@ fn test_aloc_sb_lb_last_byte_of_memory() {
         op::lb(0x13, RegId::HP, 0),
         op::ret(RegId::ONE),
     ];
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
     let r1 = vm.registers()[0x20_usize];
     let r2 = vm.registers()[0x21_usize];
     assert_eq!(r1 - 1, r2);
@@ -116,11 +134,17 @@ fn test_aloc_sb_lb_last_byte_of_memory() {
     assert_eq!(1, result);
 }
 
-#[test_case(1, false)]
-#[test_case(2, false)]
-#[test_case(1, true)]
-#[test_case(2, true)]
-fn test_stack_and_heap_cannot_overlap(offset: u64, cause_error: bool) {
+#[rstest::rstest]
+#[case(1, false)]
+#[case(2, false)]
+#[case(1, true)]
+#[case(2, true)]
+fn test_stack_and_heap_cannot_overlap(
+    #[case] offset: u64,
+    #[case] cause_error: bool,
+    #[values(MemoryStorage::default(), kv_transaction_storage(), redb_storage())]
+    storage: impl InterpreterStorage,
+) {
     // First, allocate almost all memory to heap, and then allocate the remaining
     // memory on the stack. If cause_error is set, then attempts to allocate one
     // byte too much here, causing a memory overflow error.
@@ -140,27 +164,26 @@ fn test_stack_and_heap_cannot_overlap(offset: u64, cause_error: bool) {
         op::ret(RegId::ONE),
     ]);
 
-    let vm = setup(ops);
+    let vm = setup(storage, ops);
 
-    let mut receipts = vm.receipts().unwrap().to_vec();
+    let receipts = vm.receipts().unwrap();
 
     if cause_error {
-        let _ = receipts.pop().unwrap(); // Script result unneeded, the panic receipt below is enough
-        if let Receipt::Panic { reason, .. } = receipts.pop().unwrap() {
-            assert!(matches!(reason.reason(), PanicReason::MemoryGrowthOverlap));
-        } else {
-            panic!("Expected tx panic when cause_error is set");
-        }
-    } else if let Receipt::ScriptResult { result, .. } = receipts.pop().unwrap() {
-        assert!(matches!(result, ScriptExecutionResult::Success));
+        expect_receipts!(
+            receipts,
+            [.., panic(MemoryGrowthOverlap), script_result(Panic)]
+        );
     } else {
-        panic!("Expected tx success when cause_error is not set");
+        expect_receipts!(receipts, [.., script_result(Success)]);
     }
 }
 
 /// tests for cfe & cfs
-#[test]
-fn dynamic_call_frame_ops() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn dynamic_call_frame_ops(#[case] storage: impl InterpreterStorage) {
     const STACK_EXTEND_AMOUNT: u32 = 100u32;
     const STACK_SHRINK_AMOUNT: u32 = 50u32;
     let ops = vec![
@@ -180,7 +203,7 @@ fn dynamic_call_frame_ops() {
         op::ret(RegId::SP),
     ];
 
-    let vm = setup(ops);
+    let vm = setup(storage, ops);
 
     let receipts = vm.receipts().unwrap().to_vec();
     // gather values of sp from the test
@@ -209,8 +232,13 @@ fn dynamic_call_frame_ops() {
     );
 }
 
-#[test]
-fn dynamic_call_frame_ops_bug_missing_ssp_check() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn dynamic_call_frame_ops_bug_missing_ssp_check(
+    #[case] storage: impl InterpreterStorage,
+) {
     let ops = vec![
         op::cfs(RegId::SP),
         op::slli(0x10, RegId::ONE, 26),
@@ -218,8 +246,9 @@ fn dynamic_call_frame_ops_bug_missing_ssp_check() {
         op::sw(RegId::ZERO, 0x10, 0),
         op::ret(RegId::ONE),
     ];
-    let receipts = run_script(ops);
-    assert_panics(&receipts, PanicReason::MemoryOverflow);
+    let vm = setup(storage, ops);
+    let receipts = vm.receipts().unwrap();
+    assert_panics(receipts, PanicReason::MemoryOverflow);
 }
 
 #[rstest::rstest]
@@ -227,6 +256,8 @@ fn test_mcl_and_mcli(
     #[values(0, 1, 7, 8, 9, 255, 256, 257)] count: u32,
     #[values(true, false)] half: bool, // Clear only first count/2 bytes
     #[values(true, false)] mcli: bool, // Test mcli instead of mcl
+    #[values(MemoryStorage::default(), kv_transaction_storage(), redb_storage())]
+    storage: impl InterpreterStorage,
 ) {
     // Allocate count + 1 bytes of memory, so we can check that the last byte is not
     // cleared
@@ -254,8 +285,8 @@ fn test_mcl_and_mcli(
     ops.push(op::logd(0, 0, RegId::HP, 0x10));
     ops.push(op::ret(RegId::ONE));
 
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
 
     if let Some(Receipt::LogData { data, .. }) = vm.receipts().first() {
         let data = data.as_ref().unwrap();
@@ -277,6 +308,8 @@ fn test_mcl_and_mcli(
 fn test_mcp_and_mcpi(
     #[values(0, 1, 7, 8, 9, 255, 256, 257)] count: u32,
     #[values(true, false)] mcpi: bool, // Test mcpi instead of mcp
+    #[values(MemoryStorage::default(), kv_transaction_storage(), redb_storage())]
+    storage: impl InterpreterStorage,
 ) {
     // Allocate (count + 1) * 2 bytes of memory, so we can check that the last byte is not
     // copied
@@ -308,8 +341,8 @@ fn test_mcp_and_mcpi(
     ops.push(op::logd(0, 0, RegId::HP, 0x10));
     ops.push(op::ret(RegId::ONE));
 
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
 
     if let Some(Receipt::LogData { data, .. }) = vm.receipts().first() {
         let data = data.as_ref().unwrap();
@@ -323,8 +356,11 @@ fn test_mcp_and_mcpi(
     }
 }
 
-#[test]
-fn test_meq() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_meq(#[case] storage: impl InterpreterStorage) {
     let ops = vec![
         op::movi(0x20, 16),
         op::aloc(0x20),
@@ -338,8 +374,8 @@ fn test_meq() {
         op::log(0x20, 0x21, 0x22, 0x23),
         op::ret(RegId::ONE),
     ];
-    let vm = setup(ops);
-    let vm: &Interpreter<MemoryStorage, Script> = vm.as_ref();
+    let vm = setup(storage, ops);
+    let vm: &Interpreter<_, Script> = vm.as_ref();
     if let Some(Receipt::Log { ra, rb, rc, rd, .. }) = vm.receipts().first() {
         assert_eq!(*ra, 1);
         assert_eq!(*rb, 1);
@@ -350,35 +386,48 @@ fn test_meq() {
     }
 }
 
-#[test]
-fn test_heap_not_executable() {
-    let receipts = run_script(vec![
-        op::movi(0x10, 16),
-        op::aloc(0x10),
-        op::sub(0x10, RegId::HP, RegId::IS),
-        op::divi(0x10, 0x10, 4),
-        op::jmp(0x10),
-        op::ret(RegId::ONE),
-    ]);
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_heap_not_executable(#[case] storage: impl InterpreterStorage) {
+    let vm = setup(
+        storage,
+        vec![
+            op::movi(0x10, 16),
+            op::aloc(0x10),
+            op::sub(0x10, RegId::HP, RegId::IS),
+            op::divi(0x10, 0x10, 4),
+            op::jmp(0x10),
+            op::ret(RegId::ONE),
+        ],
+    );
+    let receipts = vm.receipts().unwrap();
 
-    if let Some(Receipt::Panic { reason, .. }) = receipts.first() {
-        assert!(matches!(reason.reason(), PanicReason::MemoryNotExecutable));
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::MemoryNotExecutable)
+    );
 }
 
-#[test]
-fn test_shrunk_stack_remains_readable() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_shrunk_stack_remains_readable(#[case] storage: impl InterpreterStorage) {
     let nonce = 12345;
-    let receipts = run_script(vec![
-        op::movi(0x21, nonce),
-        op::cfei(8),
-        op::sw(RegId::SSP, 0x21, 0),
-        op::cfsi(8),
-        op::lw(0x20, RegId::SSP, 0),
-        op::ret(0x20),
-    ]);
+    let vm = setup(
+        storage,
+        vec![
+            op::movi(0x21, nonce),
+            op::cfei(8),
+            op::sw(RegId::SSP, 0x21, 0),
+            op::cfsi(8),
+            op::lw(0x20, RegId::SSP, 0),
+            op::ret(0x20),
+        ],
+    );
+    let receipts = vm.receipts().unwrap();
 
     if let Some(Receipt::Return { val, .. }) = receipts.first() {
         assert_eq!(*val, nonce as u64);
@@ -387,18 +436,25 @@ fn test_shrunk_stack_remains_readable() {
     }
 }
 
-#[test]
-fn test_stack_extension_doesnt_zero_memory() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_stack_extension_doesnt_zero_memory(#[case] storage: impl InterpreterStorage) {
     let canary = 12345;
-    let receipts = run_script(vec![
-        op::movi(0x21, canary),
-        op::cfei(8),
-        op::sw(RegId::SSP, 0x21, 0),
-        op::cfsi(8),
-        op::cfei(8),
-        op::lw(0x20, RegId::SSP, 0),
-        op::ret(0x20),
-    ]);
+    let vm = setup(
+        storage,
+        vec![
+            op::movi(0x21, canary),
+            op::cfei(8),
+            op::sw(RegId::SSP, 0x21, 0),
+            op::cfsi(8),
+            op::cfei(8),
+            op::lw(0x20, RegId::SSP, 0),
+            op::ret(0x20),
+        ],
+    );
+    let receipts = vm.receipts().unwrap();
 
     if let Some(Receipt::Return { val, .. }) = receipts.first() {
         assert_eq!(*val, canary as u64);
@@ -407,26 +463,32 @@ fn test_stack_extension_doesnt_zero_memory() {
     }
 }
 
-#[test]
-fn test_shrunk_stack_is_not_writable() {
-    let receipts = run_script(vec![
-        op::movi(0x21, 12345),
-        op::cfei(8),
-        op::sw(RegId::SSP, 0x21, 0),
-        op::cfsi(8),
-        op::sw(RegId::SSP, 0x21, 0),
-        op::ret(0x20),
-    ]);
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_shrunk_stack_is_not_writable(#[case] storage: impl InterpreterStorage) {
+    let vm = setup(
+        storage,
+        vec![
+            op::movi(0x21, 12345),
+            op::cfei(8),
+            op::sw(RegId::SSP, 0x21, 0),
+            op::cfsi(8),
+            op::sw(RegId::SSP, 0x21, 0),
+            op::ret(0x20),
+        ],
+    );
+    let receipts = vm.receipts().unwrap();
 
-    if let Some(Receipt::Panic { reason, .. }) = receipts.first() {
-        assert!(matches!(reason.reason(), PanicReason::MemoryOwnership));
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(receipts.panic_reason(), Some(PanicReason::MemoryOwnership));
 }
 
-#[test]
-fn test_heap_allocation_zeroes_memory() {
+#[rstest::rstest]
+#[case::memory(MemoryStorage::default())]
+#[case::kv_transaction(kv_transaction_storage())]
+#[case::redb(redb_storage())]
+fn test_heap_allocation_zeroes_memory(#[case] storage: impl InterpreterStorage) {
     let canary = 12345;
     let mut script = set_full_word(0x20, VM_MAX_RAM);
     script.extend(vec![
@@ -446,7 +508,8 @@ fn test_heap_allocation_zeroes_memory() {
         op::lw(0x24, 0x21, 0),
         op::ret(0x24),
     ]);
-    let receipts = run_script(script);
+    let vm = setup(storage, script);
+    let receipts = vm.receipts().unwrap();
     if let Some(Receipt::Return { val, .. }) = receipts.first() {
         assert_eq!(*val, 0u64);
     } else {
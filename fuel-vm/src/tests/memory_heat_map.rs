@@ -0,0 +1,180 @@
+#![cfg(feature = "memory-heat-map")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Checked,
+    consts::VM_MAX_RAM,
+    interpreter::InterpreterParams,
+    memory_heat_map::{
+        HeatMapFrame,
+        MemoryAccess,
+        MemoryHeatMap,
+    },
+    prelude::*,
+    util::test_helpers::TestBuilder,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+const PAGE_SIZE: usize = MemoryHeatMap::DEFAULT_PAGE_SIZE;
+#[allow(clippy::cast_possible_truncation)]
+const TOP_PAGE: usize = VM_MAX_RAM as usize - PAGE_SIZE;
+const LOW_PAGE: usize = TOP_PAGE - 3 * PAGE_SIZE;
+/// Four pages.
+const HEAP_SIZE: Immediate18 = 0x10000;
+
+fn heat_map_of(
+    storage: MemoryStorage,
+    tx: Checked<Script>,
+) -> (ProgramState, MemoryHeatMap) {
+    let consensus_params = ConsensusParameters::standard();
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        storage,
+        InterpreterParams::new(0, &consensus_params),
+    );
+    vm.with_memory_heat_map(MemoryHeatMap::new(PAGE_SIZE));
+    let ready = tx
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+    let state = *vm.transact(ready).unwrap().state();
+    (state, vm.memory_heat_map().unwrap())
+}
+
+/// Allocates four pages of heap, writes a word three times at the bottom of the heap
+/// and reads the last word of the memory twice.
+fn touching_two_distant_heap_pages() -> Checked<Script> {
+    TestBuilder::new(2322u64)
+        .start_script(
+            vec![
+                op::movi(0x10, HEAP_SIZE),
+                op::aloc(0x10),
+                op::sw(RegId::HP, RegId::ONE, 0),
+                op::sw(RegId::HP, RegId::ONE, 0),
+                op::sw(RegId::HP, RegId::ONE, 0),
+                op::add(0x11, RegId::HP, 0x10),
+                op::subi(0x11, 0x11, 8),
+                op::lw(0x12, 0x11, 0),
+                op::lw(0x12, 0x11, 0),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build()
+}
+
+#[test]
+fn memory_heat_map__counts_the_accesses_of_the_hot_pages() {
+    // When
+    let (state, heat_map) =
+        heat_map_of(MemoryStorage::default(), touching_two_distant_heap_pages());
+
+    // Then
+    assert_eq!(state, ProgramState::Return(1));
+    let pages = heat_map.pages();
+    assert_eq!(
+        pages.keys().copied().collect::<Vec<_>>(),
+        vec![0, LOW_PAGE, TOP_PAGE]
+    );
+    let (low, top) = (pages[&LOW_PAGE], pages[&TOP_PAGE]);
+    assert_eq!((low.reads, low.writes, low.write_bytes), (0, 3, 24));
+    assert_eq!((top.reads, top.read_bytes, top.writes), (2, 16, 0));
+    // The stack holds the transaction, with the script fetched by instruction
+    let stack = pages[&0];
+    assert!(stack.reads >= 10, "{stack:?}");
+    assert!(stack.write_bytes >= 32, "{stack:?}");
+    assert!(heat_map
+        .buckets()
+        .all(|bucket| bucket.frame == HeatMapFrame::default()));
+}
+
+#[test]
+fn memory_heat_map__counts_an_access_in_each_page_it_spans() {
+    // Given
+    let mut heat_map = MemoryHeatMap::new(16);
+
+    // When
+    heat_map.record(MemoryAccess::Write, 8, 32);
+
+    // Then
+    let writes = heat_map
+        .pages()
+        .into_iter()
+        .map(|(page, heat)| (page, heat.writes, heat.write_bytes))
+        .collect::<Vec<_>>();
+    assert_eq!(writes, vec![(0, 1, 8), (16, 1, 16), (32, 1, 8)]);
+}
+
+#[test]
+fn memory_heat_map__attributes_the_accesses_to_the_call_frames() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, 8),
+                op::aloc(0x10),
+                op::sw(RegId::HP, RegId::ONE, 0),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let tx = test_context
+        .start_script(
+            vec![
+                op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+                op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            Call::new(contract_id, 0, 0).to_bytes(),
+        )
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    // When
+    let (_, heat_map) = heat_map_of(test_context.get_storage().clone(), tx);
+
+    // Then
+    let contract = HeatMapFrame {
+        depth: 1,
+        contract: Some(contract_id),
+    };
+    let heap = heat_map
+        .buckets()
+        .find(|bucket| bucket.frame == contract && bucket.page == TOP_PAGE)
+        .unwrap();
+    assert_eq!((heap.heat.writes, heap.heat.write_bytes), (1, 8));
+    assert!(heat_map
+        .buckets()
+        .any(|bucket| bucket.frame == contract && bucket.page == 0));
+    let csv = heat_map.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("depth,contract,page_start,page_end,reads,read_bytes,writes,write_bytes")
+    );
+    assert!(lines.next().unwrap().starts_with("0,,0,16384,"));
+    assert!(csv.contains(&alloc::format!(
+        "\n1,{contract_id},{TOP_PAGE},{},0,0,1,8\n",
+        TOP_PAGE + PAGE_SIZE
+    )));
+}
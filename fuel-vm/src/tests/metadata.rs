@@ -82,7 +82,7 @@ fn metadata() {
     let contract = Contract::from(program.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_metadata = contract.id(&salt, &contract_root, &state_root);
+    let contract_metadata = Contract::id(&salt, &contract_root, &state_root);
     let output = Output::contract_created(contract_metadata, state_root);
 
     let tx = TransactionBuilder::create(program, salt, vec![])
@@ -131,7 +131,7 @@ fn metadata() {
     let contract = Contract::from(program.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_call = contract.id(&salt, &contract_root, &state_root);
+    let contract_call = Contract::id(&salt, &contract_root, &state_root);
 
     let output = Output::contract_created(contract_call, state_root);
 
@@ -356,6 +356,7 @@ fn get_transaction_fields() {
     let tip = 4321;
     let gas_limit = 10_000_000;
     let maturity = 50.into();
+    let expiration: BlockHeight = 150.into();
     let height = 122.into();
     let input = 10_000_000;
 
@@ -366,8 +367,7 @@ fn get_transaction_fields() {
     let code_root = Contract::root_from_code(contract.as_ref());
     let storage_slots = vec![];
     let state_root = Contract::initial_state_root(storage_slots.iter());
-    let contract_id =
-        Contract::from(contract.as_ref()).id(&salt, &code_root, &state_root);
+    let contract_id = Contract::id(&salt, &code_root, &state_root);
 
     let tx = TransactionBuilder::create(contract, salt, storage_slots)
         .add_output(Output::contract_created(contract_id, state_root))
@@ -427,7 +427,8 @@ fn get_transaction_fields() {
     let asset = rng.gen();
     let asset_amt = 27;
 
-    let tx = TransactionBuilder::script(vec![], vec![])
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
         .maturity(maturity)
         .with_gas_costs(gas_costs)
         .script_gas_limit(gas_limit)
@@ -451,8 +452,9 @@ fn get_transaction_fields() {
             contract_input_index,
             rng.gen(),
             state_root,
-        ))
-        .add_witness(Witness::from(b"some-data".to_vec()))
+        ));
+    builder.add_witness(Witness::from(b"some-data".to_vec()));
+    let tx = builder
         .add_unsigned_message_input(
             SecretKey::random(rng),
             rng.gen(),
@@ -596,6 +598,12 @@ fn get_transaction_fields() {
         op::eq(0x10, 0x10, 0x11),
         op::and(0x20, 0x20, 0x10),
 
+        op::movi(0x19, 0x00),
+        op::movi(0x11, *expiration as Immediate18),
+        op::gtf_args(0x10, 0x19, GTFArgs::PolicyExpiration),
+        op::eq(0x10, 0x10, 0x11),
+        op::and(0x20, 0x20, 0x10),
+
         op::movi(0x19, 0x00),
         op::movi(0x11, witness_limit as Immediate18),
         op::gtf_args(0x10, 0x19, GTFArgs::PolicyWitnessLimit),
@@ -931,6 +939,7 @@ fn get_transaction_fields() {
     let tx = builder
         .tip(tip)
         .maturity(maturity)
+        .expiration(expiration)
         .script_gas_limit(gas_limit)
         .witness_limit(witness_limit)
         .max_fee_limit(max_fee_limit)
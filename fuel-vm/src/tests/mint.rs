@@ -0,0 +1,168 @@
+use crate::{
+    checked_transaction::{
+        CheckError,
+        Checked,
+        IntoChecked,
+    },
+    error::InterpreterError,
+    memory_client::MemoryClient,
+    storage::ContractsAssetsStorage,
+};
+use fuel_asm::{
+    op,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    input,
+    output,
+    ConsensusParameters,
+    Contract,
+    Finalizable,
+    Mint,
+    Output,
+    TransactionBuilder,
+    ValidityError,
+};
+use fuel_types::{
+    BlockHeight,
+    ContractId,
+    Word,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+const HEIGHT: BlockHeight = BlockHeight::new(10);
+
+fn deploy_contract(client: &mut MemoryClient, rng: &mut StdRng) -> ContractId {
+    let code: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let salt = rng.gen();
+    let state_root = Contract::default_state_root();
+    let contract_id = Contract::id(&salt, &Contract::root_from_code(&code), &state_root);
+
+    let create = TransactionBuilder::create(code.into(), salt, vec![])
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize()
+        .into_checked(HEIGHT, &ConsensusParameters::standard())
+        .expect("The `Create` should be valid");
+    client
+        .deploy(create)
+        .expect("The contract should be deployed");
+
+    contract_id
+}
+
+fn mint(
+    contract_id: ContractId,
+    output_index: u16,
+    amount: Word,
+) -> Result<Checked<Mint>, CheckError> {
+    let params = ConsensusParameters::standard();
+    let input_contract = input::contract::Contract {
+        contract_id,
+        ..Default::default()
+    };
+    let output_contract = output::contract::Contract {
+        input_index: output_index,
+        ..Default::default()
+    };
+
+    TransactionBuilder::mint(
+        HEIGHT,
+        0,
+        input_contract,
+        output_contract,
+        amount,
+        *params.base_asset_id(),
+        0,
+    )
+    .finalize()
+    .into_checked(HEIGHT, &params)
+}
+
+#[test]
+fn mint_increases_contract_balance() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut client = MemoryClient::default();
+    let contract_id = deploy_contract(&mut client, rng);
+    let asset_id = *ConsensusParameters::standard().base_asset_id();
+
+    // When
+    client
+        .mint(mint(contract_id, 0, 1000).expect("The `Mint` should be valid"))
+        .expect("The `Mint` should be executed");
+    client
+        .mint(mint(contract_id, 0, 500).expect("The `Mint` should be valid"))
+        .expect("The `Mint` should be executed");
+
+    // Then
+    let balance = client
+        .as_ref()
+        .contract_asset_id_balance(&contract_id, &asset_id)
+        .unwrap();
+    assert_eq!(balance, Some(1500));
+}
+
+#[test]
+fn mint_fails_for_unknown_contract() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut client = MemoryClient::default();
+
+    // When
+    let result =
+        client.mint(mint(rng.gen(), 0, 1000).expect("The `Mint` should be valid"));
+
+    // Then
+    assert_eq!(
+        result,
+        Err(InterpreterError::Panic(PanicReason::ContractNotFound))
+    );
+}
+
+#[test]
+fn mint_fails_when_contract_balance_overflows() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut client = MemoryClient::default();
+    let contract_id = deploy_contract(&mut client, rng);
+    let asset_id = *ConsensusParameters::standard().base_asset_id();
+    client
+        .as_mut()
+        .contract_asset_id_balance_insert(&contract_id, &asset_id, Word::MAX)
+        .unwrap();
+
+    // When
+    let result =
+        client.mint(mint(contract_id, 0, 1).expect("The `Mint` should be valid"));
+
+    // Then
+    assert_eq!(
+        result,
+        Err(InterpreterError::Panic(PanicReason::BalanceOverflow))
+    );
+    let balance = client
+        .as_ref()
+        .contract_asset_id_balance(&contract_id, &asset_id)
+        .unwrap();
+    assert_eq!(balance, Some(Word::MAX));
+}
+
+#[test]
+fn mint_with_wrong_output_index_fails_checks() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // When
+    let result = mint(rng.gen(), 1, 1000);
+
+    // Then
+    assert_eq!(
+        result.unwrap_err(),
+        CheckError::Validity(ValidityError::TransactionMintIncorrectOutputIndex)
+    );
+}
@@ -1,33 +1,75 @@
 #![allow(clippy::cast_possible_truncation)]
+use criterion as _;
 use futures as _;
+use tempfile as _;
 use tokio as _;
 use tokio_rayon as _;
+use tracing_subscriber as _;
 
 mod test_helpers;
 
 mod alu;
+mod arbitrary;
 mod backtrace;
 mod blockchain;
 mod cgas;
 mod code_coverage;
 mod contract;
+mod coverage;
 mod crypto;
+mod debug_edits;
+mod debug_server;
+mod debug_steps;
+mod dependent_gas;
+mod differential;
 mod encoding;
+mod estimate_gas_limit;
+mod execution_diff;
+mod execution_snapshot;
+mod expect_receipts;
 mod external;
+mod fee;
 mod flow;
+mod frame_gas;
+mod free_gas;
+mod fuzz_util;
+mod gas_allowance;
+mod gas_bench;
 mod gas_factor;
+mod gas_overflow;
+mod interpreter_params;
 mod jump_absolute;
 mod jump_relative;
 mod limits;
 mod log;
 mod memory;
+mod memory_heat_map;
 mod metadata;
+mod mint;
+mod multisig;
 mod outputs;
 mod predicate;
 mod profile_gas;
+mod profile_sensitivity;
+mod profile_stacks;
 mod receipts;
+mod repl;
+mod script_data;
 mod serde_profile;
+mod source_map;
 mod spec;
+mod storage_cache;
+mod storage_checkpoints;
+mod storage_diff;
+mod storage_faults;
+mod storage_gas;
+mod storage_iter;
+mod storage_kv;
+mod storage_metrics;
+mod storage_overlay;
+mod storage_snapshot;
+mod storage_state_roots;
+mod structured_logs;
 mod upgrade;
 mod upload;
 mod validation;
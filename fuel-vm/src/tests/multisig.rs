@@ -0,0 +1,208 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use crate::{
+    checked_transaction::{
+        CheckError,
+        Checked,
+        EstimatePredicates,
+    },
+    error::PredicateVerificationFailed,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    GMArgs,
+    GTFArgs,
+    RegId,
+};
+use fuel_crypto::Message;
+use fuel_tx::{
+    field::{
+        Inputs,
+        Witnesses,
+    },
+    ConsensusParameters,
+    TransactionBuilder,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+/// The number of instructions in the [`multisig_predicate`] before the public keys.
+const PREDICATE_INSTRUCTIONS: usize = 30;
+
+/// Returns the predicate that verifies that at least `threshold` of `keys` signed
+/// the transaction id.
+///
+/// The predicate data contains the index of the witness with the signatures encoded by
+/// `fuel_tx::multisig::encode`. The signatures are expected in the order of `keys`, and
+/// each key is counted at most once.
+fn multisig_predicate(threshold: u16, keys: &[PublicKey]) -> Vec<u8> {
+    let keys_len = (keys.len() * PublicKey::LEN) as u16;
+    let code_len = (PREDICATE_INSTRUCTIONS * Instruction::SIZE) as u16;
+
+    #[rustfmt::skip]
+    let code = vec![
+        // 0x1a is the index of the witness with signatures from the predicate data
+        op::gm_args(0x18, GMArgs::GetVerifyingPredicate),
+        op::gtf_args(0x1a, 0x18, GTFArgs::InputCoinPredicateData),
+        op::lw(0x1a, 0x1a, 0),
+        // 0x10 is the current signature, 0x12 is the end of signatures
+        op::gtf_args(0x10, 0x1a, GTFArgs::WitnessData),
+        op::gtf_args(0x11, 0x1a, GTFArgs::WitnessDataLength),
+        op::add(0x12, 0x10, 0x11),
+        // 0x16 is the recovered public key
+        op::movi(0x17, PublicKey::LEN as u32),
+        op::aloc(0x17),
+        op::move_(0x16, RegId::HP),
+        // 0x13 is the current public key, 0x14 is the end of public keys
+        op::addi(0x13, RegId::IS, code_len),
+        op::addi(0x14, 0x13, keys_len),
+        // 0x15 is the number of valid signatures, 0x19 is the tx id
+        op::movi(0x15, 0),
+        op::movi(0x19, 0),
+        // 13: the loop over signatures
+        op::eq(0x18, 0x10, 0x12),
+        op::jnzi(0x18, 25),
+        op::eck1(0x16, 0x10, 0x19),
+        op::add(0x10, 0x10, 0x17),
+        // 17: the loop over remaining public keys, fails if there is no match
+        op::eq(0x18, 0x13, 0x14),
+        op::jnzi(0x18, 29),
+        op::meq(0x18, 0x16, 0x13, 0x17),
+        op::add(0x13, 0x13, 0x17),
+        op::jnzi(0x18, 23),
+        op::ji(17),
+        // 23: the signature matches the public key
+        op::addi(0x15, 0x15, 1),
+        op::ji(13),
+        // 25: succeeds if `threshold` signatures are valid
+        op::movi(0x18, threshold as u32),
+        op::gt(0x18, 0x18, 0x15),
+        op::eq(0x18, 0x18, RegId::ZERO),
+        op::ret(0x18),
+        // 29: the signature doesn't match any public key
+        op::ret(RegId::ZERO),
+    ];
+    assert_eq!(code.len(), PREDICATE_INSTRUCTIONS);
+
+    code.into_iter()
+        .flat_map(|op| u32::from(op).to_be_bytes())
+        .chain(keys.iter().flat_map(|key| key.as_ref().iter().copied()))
+        .collect()
+}
+
+/// Builds the transaction spending the coin of the 2-of-3 multisig predicate, signed
+/// by `signers`, along with `signed_inputs` coins owned by random keys.
+fn transact_multisig(
+    rng: &mut StdRng,
+    signers: &[usize],
+    signed_inputs: usize,
+) -> Result<Checked<Script>, CheckError> {
+    let params = ConsensusParameters::standard();
+    let chain_id = params.chain_id();
+    let secrets: Vec<_> = (0..3).map(|_| SecretKey::random(rng)).collect();
+    let keys: Vec<_> = secrets.iter().map(SecretKey::public_key).collect();
+    let predicate = multisig_predicate(2, &keys);
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.script_gas_limit(1_000_000);
+    (0..signed_inputs).for_each(|_| {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            Default::default(),
+        );
+    });
+    let witness_index = builder.witnesses().len() as Word;
+    builder.add_input(Input::coin_predicate(
+        rng.gen(),
+        Input::predicate_owner(&predicate),
+        1_000,
+        rng.gen(),
+        Default::default(),
+        0,
+        predicate,
+        witness_index.to_be_bytes().to_vec(),
+    ));
+
+    let tx_id = builder.finalize_without_signature().id(&chain_id);
+    let message = Message::from_bytes(*tx_id);
+    let signatures: Vec<_> = signers
+        .iter()
+        .map(|signer| Signature::sign(&secrets[*signer], &message))
+        .collect();
+    builder.add_multisig_witness(&signatures);
+
+    let mut tx = builder.finalize();
+    assert_eq!(tx.witnesses().len(), signed_inputs + 1);
+    tx.estimate_predicates(&params.clone().into())?;
+    tx.into_checked(Default::default(), &params)
+}
+
+#[test]
+fn multisig_predicate__two_of_three_signatures_pass() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let result = transact_multisig(rng, &[0, 2], 0);
+
+    result.expect("The predicate should accept 2 of 3 signatures");
+}
+
+#[test]
+fn multisig_predicate__three_of_three_signatures_pass() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let result = transact_multisig(rng, &[0, 1, 2], 0);
+
+    result.expect("The predicate should accept 3 of 3 signatures");
+}
+
+#[test]
+fn multisig_predicate__one_of_three_signatures_fails() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let result = transact_multisig(rng, &[1], 0);
+
+    assert!(matches!(
+        result,
+        Err(CheckError::PredicateVerificationFailed(
+            PredicateVerificationFailed::Panic(PanicReason::PredicateReturnedNonOne)
+        ))
+    ));
+}
+
+#[test]
+fn multisig_predicate__repeated_signature_fails() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let result = transact_multisig(rng, &[1, 1], 0);
+
+    assert!(matches!(
+        result,
+        Err(CheckError::PredicateVerificationFailed(
+            PredicateVerificationFailed::Panic(PanicReason::PredicateReturnedNonOne)
+        ))
+    ));
+}
+
+#[test]
+fn multisig_predicate__works_with_signed_coin_inputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let checked = transact_multisig(rng, &[1, 2], 2)
+        .expect("The predicate and signed inputs should be valid");
+
+    let witness_indices: Vec<_> = checked
+        .transaction()
+        .inputs()
+        .iter()
+        .filter_map(Input::witness_index)
+        .collect();
+    assert_eq!(witness_indices, vec![0, 1]);
+}
@@ -22,6 +22,7 @@ use fuel_asm::{
 use fuel_tx::{
     policies::Policies,
     ConsensusParameters,
+    ReceiptsExt,
     Witness,
 };
 use fuel_types::canonical::Serialize;
@@ -129,7 +130,7 @@ fn correct_change_is_provided_for_coin_outputs_create() {
     let contract = Contract::from(program.as_ref());
     let contract_root = contract.root();
     let state_root = Contract::default_state_root();
-    let contract_undefined = contract.id(&salt, &contract_root, &state_root);
+    let contract_undefined = Contract::id(&salt, &contract_root, &state_root);
 
     let output = Output::contract_created(contract_undefined, state_root);
 
@@ -181,6 +182,50 @@ fn correct_change_is_provided_for_coin_outputs_create() {
     assert_eq!(change, input_amount - spend_amount);
 }
 
+#[test]
+fn balanced_change_is_provided_for_each_asset() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let owner: Address = rng.gen();
+    let recipient: Address = rng.gen();
+    let partly_spent: AssetId = rng.gen();
+    let fully_spent: AssetId = rng.gen();
+    let input_amount = 1000;
+    let spend_amount = 600;
+
+    let script = core::iter::once(op::ret(RegId::ONE)).collect();
+    let mut builder = TransactionBuilder::script(script, vec![]);
+    builder
+        .script_gas_limit(100)
+        .max_fee_limit(10)
+        .add_output(Output::coin(recipient, spend_amount, partly_spent))
+        .add_output(Output::coin(recipient, input_amount, fully_spent));
+    for asset_id in [AssetId::BASE, partly_spent, fully_spent] {
+        builder.add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            input_amount,
+            asset_id,
+            Default::default(),
+        );
+    }
+
+    let mut context = TestBuilder::new(2322u64);
+    let tx = builder
+        .finalize_checked_balanced(context.get_block_height(), owner)
+        .expect("The transaction should be balanced");
+    let state = context.execute_tx(tx).expect("Script should be executed");
+    let outputs = state.tx().outputs().to_vec();
+
+    assert_eq!(find_change(outputs.clone(), AssetId::BASE), input_amount);
+    assert_eq!(
+        find_change(outputs.clone(), partly_spent),
+        input_amount - spend_amount
+    );
+    assert!(!outputs.iter().any(|output| {
+        matches!(output, Output::Change { asset_id, .. } if asset_id == &fully_spent)
+    }));
+}
+
 #[test]
 fn change_is_reduced_by_external_transfer() {
     let mut rng = StdRng::seed_from_u64(2322u64);
@@ -343,11 +388,10 @@ fn zero_amount_transfer_reverts() {
 
     let receipts = result.receipts();
 
-    if let Some(Receipt::Panic { reason, .. }) = receipts.first() {
-        assert_eq!(reason.reason(), &PanicReason::TransferZeroCoins);
-    } else {
-        panic!("Expected a panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::TransferZeroCoins)
+    );
 }
 
 #[test]
@@ -397,11 +441,10 @@ fn zero_amount_transfer_out_reverts() {
 
     let receipts = result.receipts();
 
-    if let Some(Receipt::Panic { reason, .. }) = receipts.first() {
-        assert_eq!(reason.reason(), &PanicReason::TransferZeroCoins);
-    } else {
-        panic!("Expected a panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::TransferZeroCoins)
+    );
 }
 
 #[test]
@@ -17,6 +17,7 @@ use tokio_rayon::AsyncRayonHandle;
 
 use crate::{
     error::PredicateVerificationFailed,
+    interpreter::Memory,
     prelude::*,
 };
 
@@ -28,7 +29,10 @@ use crate::checked_transaction::{
     ParallelExecutor,
 };
 use core::iter;
-use fuel_tx::ConsensusParameters;
+use fuel_tx::{
+    field::Inputs,
+    ConsensusParameters,
+};
 
 pub struct TokioWithRayon;
 
@@ -456,7 +460,7 @@ async fn gas_used_by_predicates_not_causes_out_of_gas_during_script() {
 }
 
 #[tokio::test]
-async fn gas_used_by_predicates_more_than_limit() {
+async fn gas_used_by_predicates_less_than_declared_is_charged() {
     let rng = &mut StdRng::seed_from_u64(2322u64);
 
     let params = CheckPredicateParams::default();
@@ -539,25 +543,170 @@ async fn gas_used_by_predicates_more_than_limit() {
     builder.add_input(input);
 
     // parallel version
-    {
-        let tx_with_predicate = builder
-            .clone()
-            .finalize_checked_basic(Default::default())
-            .check_predicates_async::<TokioWithRayon>(&params)
-            .await;
-
-        assert!(matches!(
-            tx_with_predicate.unwrap_err(),
-            CheckError::PredicateVerificationFailed(_)
-        ));
-    }
+    let parallel_tx_with_predicate = builder
+        .clone()
+        .finalize_checked_basic(Default::default())
+        .check_predicates_async::<TokioWithRayon>(&params)
+        .await
+        .expect("The predicate may use less than its declared gas");
 
     let tx_with_predicate = builder
         .finalize_checked_basic(Default::default())
-        .check_predicates(&params);
+        .check_predicates(&params)
+        .expect("The predicate may use less than its declared gas");
+
+    let predicate_gas = &tx_with_predicate.metadata().predicate_gas;
+    assert_eq!(predicate_gas[0].declared, gas_limit + 1);
+    assert!(predicate_gas.charged() < gas_limit);
+    assert_eq!(
+        &parallel_tx_with_predicate.metadata().predicate_gas,
+        predicate_gas
+    );
+}
+
+fn predicate_input(rng: &mut StdRng, predicate: Vec<Instruction>) -> Input {
+    let predicate: Vec<u8> = predicate
+        .into_iter()
+        .flat_map(|op| u32::from(op).to_be_bytes())
+        .collect();
+    let owner = Input::predicate_owner(&predicate);
 
-    assert!(matches!(
-        tx_with_predicate.unwrap_err(),
-        CheckError::PredicateVerificationFailed(_)
+    Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1_000,
+        rng.gen(),
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    )
+}
+
+#[test]
+fn builder_estimate_predicates_produces_checkable_tx() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // Given
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.script_gas_limit(1_000_000);
+    builder.add_input(predicate_input(rng, vec![op::ret(RegId::ONE)]));
+    builder.add_unsigned_coin_input(
+        SecretKey::random(rng),
+        rng.gen(),
+        1_000,
+        rng.gen(),
+        rng.gen(),
+    );
+    builder.add_input(predicate_input(
+        rng,
+        vec![
+            op::addi(0x20, 0x20, 1),
+            op::addi(0x20, 0x20, 1),
+            op::ret(RegId::ONE),
+        ],
     ));
+    let params = CheckPredicateParams::from(builder.get_params());
+
+    // When
+    let tx = builder
+        .with_estimated_predicates(&params, &mut Memory::new(), &mut PredicateStorage)
+        .expect("Should estimate predicates")
+        .finalize();
+
+    // Then
+    let gas_used: Vec<_> = tx.inputs().iter().map(Input::predicate_gas_used).collect();
+    assert!(
+        matches!(gas_used[..], [Some(first), None, Some(second)] if first > 0 && second > first)
+    );
+    tx.into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("The estimated transaction should pass the checks");
+}
+
+#[test]
+fn builder_estimate_predicates_overwrites_previous_estimation() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // Given
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.script_gas_limit(1_000_000);
+    builder.add_input(predicate_input(rng, vec![op::ret(RegId::ONE)]));
+    let params = CheckPredicateParams::from(builder.get_params());
+    let mut memory = Memory::new();
+    builder
+        .estimate_predicates(&params, &mut memory, &mut PredicateStorage)
+        .unwrap();
+    let estimated = builder.inputs()[0].predicate_gas_used();
+    builder.set_predicate_gas_used(0, 1);
+
+    // When
+    builder
+        .estimate_predicates(&params, &mut memory, &mut PredicateStorage)
+        .unwrap();
+
+    // Then
+    assert_eq!(builder.inputs()[0].predicate_gas_used(), estimated);
+}
+
+#[test]
+fn builder_estimate_predicates_reports_index_of_failed_input() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // Given
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.script_gas_limit(1_000_000);
+    builder.add_input(predicate_input(rng, vec![op::ret(RegId::ONE)]));
+    let mut invalid_owner = predicate_input(rng, vec![op::ret(RegId::ONE)]);
+    if let Input::CoinPredicate(coin) = &mut invalid_owner {
+        coin.owner = rng.gen();
+    }
+    builder.add_input(invalid_owner);
+    let params = CheckPredicateParams::from(builder.get_params());
+
+    // When
+    let result =
+        builder.estimate_predicates(&params, &mut Memory::new(), &mut PredicateStorage);
+
+    // Then
+    assert_eq!(
+        result,
+        Err(CheckError::PredicateEstimationFailed {
+            index: 1,
+            error: PredicateVerificationFailed::InvalidOwner,
+        })
+    );
+    assert_eq!(builder.inputs()[0].predicate_gas_used(), Some(0));
+}
+
+#[test]
+fn builder_estimate_predicates_uses_the_reset_memory_of_the_caller() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // Given
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.script_gas_limit(1_000_000);
+    builder.add_input(predicate_input(rng, vec![op::ret(RegId::ONE)]));
+    let params = CheckPredicateParams::from(builder.get_params());
+    let mut fresh = builder.clone();
+    fresh
+        .estimate_predicates(&params, &mut Memory::new(), &mut PredicateStorage)
+        .unwrap();
+    let dirty_len = 0x10_000;
+    let mut memory = Memory::new();
+    memory.grow_stack(dirty_len).unwrap();
+    memory.write_noownerchecks(0, dirty_len).unwrap().fill(0xff);
+
+    // When
+    builder
+        .estimate_predicates(&params, &mut memory, &mut PredicateStorage)
+        .unwrap();
+
+    // Then
+    assert_eq!(
+        builder.inputs()[0].predicate_gas_used(),
+        fresh.inputs()[0].predicate_gas_used()
+    );
+    let stack = memory.stack_raw();
+    assert!(!stack.is_empty() && stack.len() < dirty_len as usize);
+    assert!(stack.iter().any(|byte| *byte != 0xff));
 }
@@ -0,0 +1,170 @@
+#![cfg(feature = "profile-gas")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+use fuel_asm::{
+    op,
+    Opcode,
+    RegId,
+};
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV2,
+    ConsensusParameters,
+    GasCostsValues,
+};
+
+/// The lengths copied by the `MCP`s of the script.
+const LENGTHS: [u32; 3] = [3_000, 50_000, 100_000];
+
+/// Copies each of the [`LENGTHS`] on the heap, computing the source address with an
+/// `ADD`.
+fn script() -> Vec<u8> {
+    let max_len = *LENGTHS.iter().max().unwrap();
+    let mut script = vec![op::movi(0x10, 2 * max_len), op::aloc(0x10)];
+    for len in LENGTHS {
+        script.extend([
+            op::movi(0x11, len),
+            op::add(0x12, RegId::HP, 0x11),
+            op::mcp(RegId::HP, 0x12, 0x11),
+        ]);
+    }
+    script.push(op::ret(RegId::ONE));
+    script.into_iter().collect()
+}
+
+fn default_values() -> GasCostsValuesV2 {
+    let GasCostsValues::V2(values) = GasCostsValues::default() else {
+        unreachable!("The default gas costs are the latest version");
+    };
+    values
+}
+
+fn double_slope(cost: DependentCost) -> DependentCost {
+    match cost {
+        DependentCost::LightOperation {
+            base,
+            units_per_gas,
+        } => DependentCost::from_units_per_gas(base, units_per_gas / 2),
+        DependentCost::HeavyOperation { base, gas_per_unit } => {
+            DependentCost::from_gas_per_unit(base, 2 * gas_per_unit)
+        }
+    }
+}
+
+/// Executes the script with the `gas_costs`, returning the gas used by the script and
+/// the profiler.
+fn execute(gas_costs: &GasCosts) -> (Word, Profiler) {
+    let mut params = ConsensusParameters::standard();
+    params.set_gas_costs(gas_costs.clone());
+    let tx = TransactionBuilder::script(script(), vec![])
+        .script_gas_limit(1_000_000)
+        .max_fee_limit(1_000_000)
+        .add_random_fee_input()
+        .with_params(params.clone())
+        .finalize()
+        .into_checked(Default::default(), &params)
+        .expect("The transaction is valid");
+    let mut transactor = Transactor::<_, Script>::new(
+        MemoryStorage::default(),
+        InterpreterParams::new(0, &params),
+    );
+    let receipts = transactor.transact(tx).receipts().unwrap().to_vec();
+    let gas_used = receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("The script result is missing");
+    (gas_used, transactor.interpreter().profiler().clone())
+}
+
+#[test]
+fn sensitivity__predicts_the_gas_of_a_re_execution_with_a_doubled_mcp_slope() {
+    // Given
+    let old = GasCosts::default();
+    let values = default_values();
+    let new = GasCosts::new(
+        GasCostsValuesV2 {
+            mcp: double_slope(values.mcp),
+            ..values
+        }
+        .into(),
+    );
+    let (old_gas_used, profiler) = execute(&old);
+
+    // When
+    let report = profiler.sensitivity(&old, &new);
+
+    // Then
+    let (new_gas_used, _) = execute(&new);
+    assert!(new_gas_used > old_gas_used);
+    assert_eq!(
+        report.transactions(),
+        [GasShift {
+            old: old_gas_used,
+            new: new_gas_used,
+        }]
+    );
+    assert_eq!(
+        report.total().delta(),
+        new_gas_used as i128 - old_gas_used as i128
+    );
+    let movers = report.absolute_movers();
+    assert_eq!(movers.len(), 1);
+    assert_eq!(movers[0].0, Opcode::MCP);
+
+    let opcodes = profiler.data().transactions()[0].opcodes();
+    assert_eq!(opcodes.count(Opcode::MCP), LENGTHS.len() as u64);
+    assert_eq!(
+        opcodes.units(Opcode::MCP),
+        LENGTHS.iter().map(|len| Word::from(*len)).sum::<Word>()
+    );
+    assert_eq!(opcodes.units(Opcode::ADD), 0);
+}
+
+#[test]
+fn sensitivity__lists_the_absolute_and_the_relative_movers() {
+    // Given
+    let old = GasCosts::default();
+    let values = default_values();
+    let new = GasCosts::new(
+        GasCostsValuesV2 {
+            add: 2 * values.add,
+            mcp: double_slope(values.mcp),
+            ..values
+        }
+        .into(),
+    );
+    let (_, profiler) = execute(&old);
+
+    // When
+    let report = profiler.sensitivity(&old, &new);
+
+    // Then
+    let add = report.opcode(Opcode::ADD);
+    let mcp = report.opcode(Opcode::MCP);
+    assert_eq!(add.relative(), Some(1.0));
+    assert!(mcp.delta() > add.delta());
+    assert!(mcp.relative().unwrap() < 1.0);
+    let opcodes = |movers: Vec<(Opcode, GasShift)>| {
+        movers
+            .into_iter()
+            .map(|(opcode, _)| opcode)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        opcodes(report.absolute_movers()),
+        vec![Opcode::MCP, Opcode::ADD]
+    );
+    assert_eq!(
+        opcodes(report.relative_movers()),
+        vec![Opcode::ADD, Opcode::MCP]
+    );
+    assert_eq!(report.opcode(Opcode::RET).delta(), 0);
+}
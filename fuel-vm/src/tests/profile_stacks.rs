@@ -0,0 +1,265 @@
+#![cfg(feature = "profile-gas")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    profiler::StackMetric,
+    script_with_data_offset,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+
+const NOOPS: usize = 5;
+
+/// Builds a script calling the first of the `contracts` with the `Call` of the second
+/// in the register `0x11`, and the register `0x20` set to `counter`.
+fn call_script(
+    test_context: &mut TestBuilder,
+    contracts: &[ContractId],
+    counter: u16,
+) -> Checked<Script> {
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x20, counter.into()),
+            op::movi(0x10, data_offset as Immediate18),
+            op::movi(0x11, data_offset as Immediate18 + Call::LEN as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = contracts
+        .iter()
+        .flat_map(|contract| Call::new(*contract, 0, 0).to_bytes())
+        .collect();
+    let mut builder = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000);
+    for contract in contracts {
+        builder = builder.contract_input(*contract);
+    }
+    builder = builder.fee_input();
+    for contract in contracts {
+        builder = builder.contract_output(contract);
+    }
+    builder.build()
+}
+
+fn transactor(test_context: &TestBuilder) -> Transactor<MemoryStorage, Script> {
+    Transactor::new(
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+}
+
+fn script_gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .expect("Expected the script result")
+}
+
+/// The `frames;count` lines of the folded stacks, by frames.
+fn lines(folded: &str) -> Vec<(&str, Word)> {
+    folded
+        .lines()
+        .map(|line| {
+            let (frames, count) = line.rsplit_once(' ').expect("Expected a count");
+            (frames, count.parse().expect("Expected a number"))
+        })
+        .collect()
+}
+
+#[test]
+fn folded_stacks__has_the_stack_of_the_nested_calls() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let inner = test_context
+        .setup_contract(
+            [op::noop(); NOOPS]
+                .into_iter()
+                .chain([op::ret(RegId::ONE)])
+                .collect::<Vec<_>>(),
+            None,
+            None,
+        )
+        .contract_id;
+    let outer = test_context
+        .setup_contract(
+            vec![
+                op::call(0x11, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let tx = call_script(&mut test_context, &[outer, inner], 0);
+    let mut transactor = transactor(&test_context);
+
+    // When
+    let receipts = transactor.transact(tx).receipts().unwrap().to_vec();
+    let profiler = transactor.interpreter().profiler();
+    let gas = profiler.folded_stacks();
+    let instructions = profiler
+        .data()
+        .stacks()
+        .folded(StackMetric::Instructions, None);
+
+    // Then
+    let inner_stack = format!("script;{outer};{inner}");
+    let gas_costs = ConsensusParameters::standard().gas_costs().clone();
+    let inner_gas = gas_costs.noop() * NOOPS as Word + gas_costs.ret();
+    assert!(
+        lines(&gas).contains(&(inner_stack.as_str(), inner_gas)),
+        "{gas}"
+    );
+    assert_eq!(
+        lines(&instructions),
+        vec![
+            ("script", 5),
+            (format!("script;{outer}").as_str(), 2),
+            (inner_stack.as_str(), NOOPS as Word + 1),
+        ]
+    );
+    let total_gas: Word = lines(&gas).iter().map(|(_, gas)| gas).sum();
+    assert!(total_gas <= script_gas_used(&receipts), "{gas}");
+}
+
+#[test]
+fn folded_stacks__nests_the_recursive_calls() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    // Calls itself through its own id at `$fp`, until the counter reaches zero.
+    let recursive = test_context
+        .setup_contract(
+            vec![
+                op::jnzi(0x20, 2),
+                op::ret(RegId::ONE),
+                op::subi(0x20, 0x20, 1),
+                op::call(RegId::FP, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let tx = call_script(&mut test_context, &[recursive], 2);
+    let mut transactor = transactor(&test_context);
+
+    // When
+    transactor.transact(tx);
+    let instructions = transactor
+        .interpreter()
+        .profiler()
+        .data()
+        .stacks()
+        .folded(StackMetric::Instructions, None);
+
+    // Then
+    assert_eq!(
+        lines(&instructions),
+        vec![
+            ("script", 5),
+            (format!("script;{recursive}").as_str(), 4),
+            (format!("script;{recursive};{recursive}").as_str(), 4),
+            (
+                format!("script;{recursive};{recursive};{recursive}").as_str(),
+                2
+            ),
+        ]
+    );
+}
+
+#[test]
+fn folded_stacks__unwinds_the_calls_of_a_reverted_transaction() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let reverting = test_context
+        .setup_contract(vec![op::rvrt(RegId::ONE)], None, None)
+        .contract_id;
+    let returning = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let reverted = call_script(&mut test_context, &[reverting], 0);
+    let returned = call_script(&mut test_context, &[returning], 0);
+    let mut transactor = transactor(&test_context);
+
+    // When
+    transactor.transact(reverted);
+    transactor.transact(returned);
+    let instructions = transactor
+        .interpreter()
+        .profiler()
+        .data()
+        .stacks()
+        .folded(StackMetric::Instructions, None);
+
+    // Then
+    let returning_stack = format!("script;{returning}");
+    let reverting_stack = format!("script;{reverting}");
+    let mut expected = vec![
+        ("script", 9),
+        (returning_stack.as_str(), 1),
+        (reverting_stack.as_str(), 1),
+    ];
+    expected.sort();
+    assert_eq!(lines(&instructions), expected);
+}
+
+#[test]
+fn folded__names_the_frames_with_the_source_map() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract = test_context
+        .setup_contract(vec![op::noop(), op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let tx = call_script(&mut test_context, &[contract], 0);
+    let mut transactor = transactor(&test_context);
+    let mut source_map = SourceMap::new();
+    source_map
+        .insert(None, 0..20, SourceLocation::new("script.sw", 1, 1, "main"))
+        .unwrap();
+    source_map
+        .insert(
+            Some(contract),
+            0..4,
+            SourceLocation::new("contract.sw", 1, 1, "work"),
+        )
+        .unwrap();
+
+    // When
+    transactor.transact(tx);
+    let instructions = transactor
+        .interpreter()
+        .profiler()
+        .data()
+        .stacks()
+        .folded(StackMetric::Instructions, Some(&source_map));
+
+    // Then
+    assert_eq!(
+        lines(&instructions),
+        vec![
+            ("main", 5),
+            (format!("main;{contract}").as_str(), 1),
+            ("main;work", 1),
+        ]
+    );
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    vec,
+};
+
+use crate::{
+    prelude::*,
+    repl::{
+        ReplError,
+        ReplOutput,
+        ReplSession,
+    },
+    state::ExecuteState,
+};
+use fuel_asm::{
+    op,
+    ParseError,
+    RegId,
+};
+
+#[test]
+fn eval__keeps_the_memory_across_the_instructions() {
+    // Given
+    let mut session = ReplSession::default();
+    session.eval("movi $r16 8").unwrap();
+    session.eval("aloc $r16").unwrap();
+    session.eval("sw $hp $one 0").unwrap();
+    let hp = session.interpreter().registers()[RegId::HP];
+
+    // When
+    let memory = session.eval(&format!(":mem {hp:#x} 8")).unwrap();
+
+    // Then
+    assert_eq!(
+        memory,
+        ReplOutput::Memory {
+            address: hp,
+            bytes: vec![0, 0, 0, 0, 0, 0, 0, 1],
+        }
+    );
+    assert_eq!(
+        session.program(),
+        [
+            op::movi(0x10, 8),
+            op::aloc(0x10),
+            op::sw(RegId::HP, RegId::ONE, 0)
+        ]
+    );
+}
+
+#[test]
+fn eval__reports_the_written_registers() {
+    // Given
+    let mut session = ReplSession::default();
+
+    // When
+    let output = session.eval("addi $r16 $zero 42").unwrap();
+
+    // Then
+    assert_eq!(
+        output,
+        ReplOutput::Step {
+            instruction: op::addi(0x10, RegId::ZERO, 42),
+            written: vec![(RegId::new(0x10), 42)],
+            state: ExecuteState::Proceed,
+        }
+    );
+    assert_eq!(output.to_string(), "$r16 = 0x2a\n");
+}
+
+#[test]
+fn eval__rolls_back_the_panicking_instruction() {
+    // Given
+    let mut session = ReplSession::default();
+    session.eval("movi $r16 1").unwrap();
+    let registers = session.interpreter().registers().to_vec();
+
+    // When
+    let result = session.eval("div $r17 $r16 $zero");
+
+    // Then
+    let Err(ReplError::RolledBack { instruction, error }) = result else {
+        panic!("Expected a rolled back instruction, got {result:?}")
+    };
+    assert_eq!(instruction, op::div(0x11, 0x10, RegId::ZERO));
+    assert_eq!(error.panic_reason(), Some(PanicReason::ArithmeticError));
+    assert_eq!(session.interpreter().registers(), registers.as_slice());
+    assert_eq!(session.program(), [op::movi(0x10, 1)]);
+    session.eval("div $r17 $r16 $r16").unwrap();
+    assert_eq!(session.interpreter().registers()[0x11], 1);
+}
+
+#[test]
+fn eval__shows_the_receipts_of_the_session() {
+    // Given
+    let mut session = ReplSession::default();
+    session.eval("movi $r16 7").unwrap();
+    session.eval("log $r16 $zero $zero $zero").unwrap();
+
+    // When
+    let receipts = session.eval(":receipts").unwrap();
+
+    // Then
+    let ReplOutput::Receipts(receipts) = receipts else {
+        panic!("Expected the receipts, got {receipts:?}")
+    };
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].ra(), Some(7));
+}
+
+#[test]
+fn eval__resets_the_interpreter() {
+    // Given
+    let mut session = ReplSession::default();
+    let registers = session.interpreter().registers().to_vec();
+    session.eval("movi $r16 1").unwrap();
+
+    // When
+    let output = session.eval(":reset").unwrap();
+
+    // Then
+    assert_eq!(output, ReplOutput::Reset);
+    assert_eq!(session.interpreter().registers(), registers.as_slice());
+    assert!(session.program().is_empty());
+}
+
+#[test]
+fn eval__loads_the_instructions_of_the_file() {
+    // Given
+    let path = std::env::temp_dir().join(format!("repl-{}.asm", std::process::id()));
+    std::fs::write(&path, "movi $r16 2 // two\n\nmuli $r16 $r16 3\n").unwrap();
+    let mut session = ReplSession::default();
+
+    // When
+    let output = session.eval(&format!(":load {}", path.display()));
+
+    // Then
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.unwrap(), ReplOutput::Loaded { steps: 2 });
+    assert_eq!(session.interpreter().registers()[0x10], 6);
+}
+
+#[test]
+fn load__reports_the_failed_line() {
+    // Given
+    let mut session = ReplSession::default();
+
+    // When
+    let result = session.load("movi $r16 2\nmovi $r17\nmovi $r18 3");
+
+    // Then
+    let Err(ReplError::Load { line, error }) = result else {
+        panic!("Expected a failed line, got {result:?}")
+    };
+    assert_eq!(line, 2);
+    assert!(matches!(
+        *error,
+        ReplError::Parse(ParseError::MissingOperand)
+    ));
+    assert_eq!(session.program(), [op::movi(0x10, 2)]);
+}
+
+#[test]
+fn eval__rejects_the_invalid_commands() {
+    // Given
+    let mut session = ReplSession::default();
+
+    // When
+    let unknown = session.eval(":foo");
+    let invalid = session.eval(":mem 0x10");
+    let out_of_bounds = session.eval(&format!(":mem {} 1", u64::MAX));
+    let empty = session.eval("   ");
+
+    // Then
+    assert!(
+        matches!(unknown, Err(ReplError::UnknownCommand(command)) if command == "foo")
+    );
+    assert!(matches!(invalid, Err(ReplError::InvalidArguments(_))));
+    assert!(matches!(out_of_bounds, Err(ReplError::Memory(_))));
+    assert_eq!(empty.unwrap(), ReplOutput::Nothing);
+}
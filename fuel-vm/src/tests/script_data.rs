@@ -0,0 +1,92 @@
+use alloc::vec::Vec;
+
+use crate::prelude::*;
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_tx::script_data::{
+    Builder,
+    Reader,
+    SymbolTable,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+/// Returns the instructions loading the VM address of the symbol `name` into `ra`.
+fn load_symbol(symbols: &SymbolTable, name: &str, ra: u8) -> [Instruction; 2] {
+    let symbol = symbols.get(name).expect("The symbol is recorded");
+    let offset = u16::try_from(symbol.offset).expect("The offset fits into `u16`");
+    op::script_data_address(ra, offset)
+}
+
+#[test]
+fn script_reads_fields_of_call_payload() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let contract_id: Bytes32 = rng.gen();
+    let recipient: Address = rng.gen();
+    let amount: Word = rng.gen();
+    let arguments: Vec<u8> = (0..37).map(|_| rng.gen::<u8>()).collect();
+
+    // Given
+    let mut builder = Builder::new();
+    builder
+        .bytes32("contract_id", &contract_id)
+        .word("amount", amount)
+        .bytes("arguments", &arguments)
+        .address("recipient", &recipient);
+    let (script_data, symbols) = builder.finalize();
+
+    let mut reader = Reader::new(&script_data);
+    assert_eq!(reader.bytes32(), Ok(contract_id));
+    assert_eq!(reader.word(), Ok(amount));
+    assert_eq!(reader.bytes(), Ok(arguments.as_slice()));
+    assert_eq!(reader.address(), Ok(recipient));
+
+    // 0x10 is the address of the current field, 0x11 is its length
+    let script = [
+        load_symbol(&symbols, "contract_id", 0x10).to_vec(),
+        vec![
+            op::movi(0x11, Bytes32::LEN as u32),
+            op::logd(RegId::ZERO, RegId::ZERO, 0x10, 0x11),
+        ],
+        load_symbol(&symbols, "amount", 0x10).to_vec(),
+        vec![
+            op::lw(0x12, 0x10, 0),
+            op::log(0x12, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        ],
+        // The length prefix is the word right before the bytes
+        load_symbol(&symbols, "arguments", 0x10).to_vec(),
+        vec![
+            op::subi(0x12, 0x10, 8),
+            op::lw(0x11, 0x12, 0),
+            op::logd(RegId::ZERO, RegId::ZERO, 0x10, 0x11),
+        ],
+        load_symbol(&symbols, "recipient", 0x10).to_vec(),
+        vec![
+            op::movi(0x11, Address::LEN as u32),
+            op::logd(RegId::ZERO, RegId::ZERO, 0x10, 0x11),
+            op::ret(RegId::ONE),
+        ],
+    ]
+    .concat();
+
+    // When
+    let result = TestBuilder::new(2322u64)
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .execute();
+
+    // Then
+    let receipts = result.receipts();
+    assert_eq!(receipts[0].data(), Some(contract_id.as_ref()));
+    assert_eq!(receipts[1].ra(), Some(amount));
+    assert_eq!(receipts[2].data(), Some(arguments.as_slice()));
+    assert_eq!(receipts[3].data(), Some(recipient.as_ref()));
+    assert!(matches!(receipts[4], Receipt::Return { val: 1, .. }));
+}
@@ -0,0 +1,242 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+};
+
+use crate::{
+    constraints::InstructionLocation,
+    prelude::*,
+    script_with_data_offset,
+    source_map::SourceMapError,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+fn contract_map(contract: ContractId) -> SourceMap {
+    let mut source_map = SourceMap::new();
+    source_map
+        .insert(None, 0..12, SourceLocation::new("script.sw", 3, 9, "main"))
+        .unwrap();
+    source_map
+        .insert(
+            Some(contract),
+            0..8,
+            SourceLocation::new("contract.sw", 10, 5, "setup"),
+        )
+        .unwrap();
+    source_map
+        .insert(
+            Some(contract),
+            8..12,
+            SourceLocation::new("contract.sw", 14, 5, "divide"),
+        )
+        .unwrap();
+    source_map
+}
+
+#[test]
+fn backtrace__display_shows_the_mapped_functions_and_lines() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract = vec![
+        op::movi(0x10, 1),
+        op::noop(),
+        op::div(0x10, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    let (_, backtrace) = test_context
+        .execute_tx_with_backtrace(tx, 0)
+        .expect("Should execute tx");
+    let backtrace = backtrace.expect("Expected a backtrace for the panic");
+    let source_map = contract_map(contract_id);
+
+    // When
+    let rendered = backtrace.display(&source_map).to_string();
+
+    // Then
+    assert_eq!(
+        backtrace.locations(),
+        vec![
+            InstructionLocation::new(Some(contract_id), 8),
+            InstructionLocation::new(None, 4),
+        ]
+    );
+    let asset_id = backtrace.call_chain()[0].asset_id;
+    assert_eq!(
+        rendered,
+        format!(
+            "Panic\n   0: divide at contract.sw:14:5\n      \
+             call of {contract_id} with a 0, b 0, forwarding 0 of {asset_id}\n   \
+             1: main at script.sw:3:9\n"
+        )
+    );
+}
+
+#[test]
+fn backtrace__display_shows_the_offsets_missing_from_the_map() {
+    // Given
+    let script = vec![op::noop(), op::div(0x10, RegId::ONE, RegId::ZERO)];
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+    let (_, backtrace) = test_context
+        .execute_tx_with_backtrace(tx, 0)
+        .expect("Should execute tx");
+    let backtrace = backtrace.expect("Expected a backtrace for the panic");
+
+    // When
+    let rendered = backtrace.display(&SourceMap::new()).to_string();
+
+    // Then
+    assert_eq!(rendered, "Panic\n   0: script at offset 4\n");
+}
+
+#[test]
+fn get__finds_the_range_of_the_context_containing_the_offset() {
+    // Given
+    let contract = ContractId::new([1; 32]);
+    let source_map = contract_map(contract);
+
+    // When
+    let function = |context, offset| {
+        source_map
+            .get(&InstructionLocation::new(context, offset))
+            .map(|location| location.function.as_str())
+    };
+
+    // Then
+    assert_eq!(function(None, 0), Some("main"));
+    assert_eq!(function(None, 11), Some("main"));
+    assert_eq!(function(None, 12), None);
+    assert_eq!(function(Some(contract), 7), Some("setup"));
+    assert_eq!(function(Some(contract), 8), Some("divide"));
+    assert_eq!(function(Some(contract), 12), None);
+    assert_eq!(function(Some(ContractId::new([2; 32])), 0), None);
+}
+
+#[test]
+fn insert__rejects_empty_and_overlapping_ranges() {
+    // Given
+    let contract = ContractId::new([1; 32]);
+    let mut source_map = contract_map(contract);
+    let location = SourceLocation::new("contract.sw", 1, 1, "other");
+
+    // When
+    let empty = source_map.insert(None, 12..12, location.clone());
+    let overlapping_previous = source_map.insert(Some(contract), 4..9, location.clone());
+    let overlapping_next = source_map.insert(None, 12..16, location.clone());
+    let other_context = source_map.insert(Some(ContractId::new([2; 32])), 0..8, location);
+
+    // Then
+    assert_eq!(empty, Err(SourceMapError::EmptyRange(12..12)));
+    assert_eq!(
+        overlapping_previous,
+        Err(SourceMapError::OverlappingRange(4..9))
+    );
+    assert_eq!(overlapping_next, Ok(()));
+    assert_eq!(other_context, Ok(()));
+    assert_eq!(source_map.len(), 5);
+}
+
+#[test]
+fn listing__groups_the_instructions_by_function() {
+    // Given
+    let contract = ContractId::new([1; 32]);
+    let source_map = contract_map(contract);
+    let bytecode: Vec<u8> = [
+        op::movi(0x10, 1),
+        op::noop(),
+        op::div(0x10, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+
+    // When
+    let listing = source_map.listing(Some(contract), &bytecode).to_string();
+
+    // Then
+    let lines = listing.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 7, "{listing}");
+    assert_eq!(lines[0], "setup:");
+    assert!(lines[1].starts_with("       0: "), "{listing}");
+    assert!(lines[1].ends_with(" ; contract.sw:10:5"), "{listing}");
+    assert_eq!(lines[3], "divide:");
+    assert!(lines[4].ends_with(" ; contract.sw:14:5"), "{listing}");
+    assert_eq!(lines[5], "<unknown>:");
+    assert!(lines[6].starts_with("      12: "), "{listing}");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn from_bytes__decodes_the_encoded_map() {
+    // Given
+    let source_map = contract_map(ContractId::new([1; 32]));
+
+    // When
+    let decoded = SourceMap::from_bytes(&source_map.to_bytes());
+
+    // Then
+    assert_eq!(decoded, Ok(source_map));
+    assert_eq!(
+        SourceMap::from_bytes(&[0xff]),
+        Err(SourceMapError::InvalidEncoding)
+    );
+}
+
+#[cfg(feature = "profile-any")]
+#[test]
+fn by_function__aggregates_the_gas_of_the_profile() {
+    // Given
+    let contract = ContractId::new([1; 32]);
+    let source_map = contract_map(contract);
+    let mut gas = crate::profiler::GasProfilingData::default();
+    gas.add(InstructionLocation::new(Some(contract), 0), 2);
+    gas.add(InstructionLocation::new(Some(contract), 4), 3);
+    gas.add(InstructionLocation::new(Some(contract), 8), 7);
+    gas.add(InstructionLocation::new(None, 4), 1);
+    gas.add(InstructionLocation::new(None, 16), 4);
+
+    // When
+    let per_function = gas.by_function(&source_map);
+
+    // Then
+    assert_eq!(
+        per_function.iter().collect::<Vec<_>>(),
+        vec![("divide", 7), ("main", 1), ("setup", 5)]
+    );
+    assert_eq!(per_function.unmapped(), 4);
+    assert_eq!(
+        per_function.to_string(),
+        "divide: 7\nsetup: 5\nmain: 1\n<unknown>: 4\n"
+    );
+}
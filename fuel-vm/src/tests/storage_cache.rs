@@ -0,0 +1,200 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+    storage::{
+        CacheCapacity,
+        CacheCounters,
+        CachedStorage,
+        ContractsState,
+        ContractsStateKey,
+    },
+    util::test_helpers::TestBuilder,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_storage::{
+    StorageAsMut,
+    StorageAsRef,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+    ReceiptsExt,
+};
+use fuel_types::canonical::Serialize;
+
+fn slot(key: u8) -> ContractsStateKey {
+    (&ContractId::new([1; 32]), &Bytes32::new([key; 32])).into()
+}
+
+fn read_slot<S: InterpreterStorage>(storage: &S, key: u8) -> Option<Vec<u8>> {
+    storage
+        .storage::<ContractsState>()
+        .get(&slot(key))
+        .unwrap()
+        .map(|value| value.into_owned().0)
+}
+
+/// Increments the word stored under the key `[1, 0, ..., 0]` and logs the new value.
+fn counter_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 1),
+        op::sb(RegId::HP, 0x11, 0),
+        op::srw(0x12, 0x13, RegId::HP),
+        op::addi(0x12, 0x12, 1),
+        op::sww(RegId::HP, 0x13, 0x12),
+        op::log(0x12, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Deploys the [`counter_contract`] and builds a script calling it.
+fn setup() -> (MemoryStorage, Checked<Script>) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(counter_contract(), None, None)
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    (test_context.get_storage().clone(), tx)
+}
+
+/// Executes the `tx` `count` times one after the other over the `storage`.
+fn transact_all<S>(storage: S, tx: &Checked<Script>, count: usize) -> (S, Vec<Receipt>)
+where
+    S: InterpreterStorage + Clone,
+{
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    let mut receipts = Vec::new();
+    for _ in 0..count {
+        let tx_receipts = transactor.transact(tx.clone()).receipts().unwrap().to_vec();
+        assert_eq!(tx_receipts.panic_reason(), None);
+        receipts.extend(tx_receipts);
+    }
+    (AsRef::<S>::as_ref(&transactor).clone(), receipts)
+}
+
+#[test]
+fn invalidate_contract__exposes_the_changes_made_to_the_inner_storage() {
+    // Given
+    let mut storage = CachedStorage::new(MemoryStorage::default(), Default::default());
+    storage
+        .storage_as_mut::<ContractsState>()
+        .insert(&slot(1), &[1; 32])
+        .unwrap();
+    assert_eq!(read_slot(&storage, 1), Some(vec![1; 32]));
+    storage
+        .inner_mut()
+        .storage_as_mut::<ContractsState>()
+        .insert(&slot(1), &[2; 32])
+        .unwrap();
+    assert_eq!(read_slot(&storage, 1), Some(vec![1; 32]));
+
+    // When
+    storage.invalidate_contract(&ContractId::new([1; 32]));
+
+    // Then
+    assert_eq!(read_slot(&storage, 1), Some(vec![2; 32]));
+}
+
+#[test]
+fn get__counts_the_hits_and_the_misses_of_present_and_absent_slots() {
+    // Given
+    let mut inner = MemoryStorage::default();
+    inner
+        .storage_as_mut::<ContractsState>()
+        .insert(&slot(1), &[1; 32])
+        .unwrap();
+    let mut storage = CachedStorage::new(inner, Default::default());
+
+    // When
+    for _ in 0..3 {
+        read_slot(&storage, 1);
+        read_slot(&storage, 2);
+    }
+
+    // Then
+    assert_eq!(
+        storage.metrics().state,
+        CacheCounters { hits: 4, misses: 2 }
+    );
+    storage.reset_metrics();
+    assert_eq!(storage.metrics(), Default::default());
+}
+
+#[test]
+fn get__reloads_the_least_recently_used_slot_once_evicted() {
+    // Given
+    let capacity = CacheCapacity {
+        state: 2,
+        ..Default::default()
+    };
+    let storage = CachedStorage::new(MemoryStorage::default(), capacity);
+    read_slot(&storage, 1);
+    read_slot(&storage, 2);
+    read_slot(&storage, 1);
+
+    // When
+    read_slot(&storage, 3);
+
+    // Then
+    let misses = storage.metrics().state.misses;
+    read_slot(&storage, 1);
+    assert_eq!(storage.metrics().state.misses, misses);
+    read_slot(&storage, 2);
+    assert_eq!(storage.metrics().state.misses, misses + 1);
+}
+
+#[test]
+fn transact__gives_the_same_receipts_and_state_with_and_without_the_cache() {
+    // Given
+    let (storage, tx) = setup();
+    let cached = CachedStorage::new(storage.clone(), Default::default());
+
+    // When
+    let (storage, receipts) = transact_all(storage, &tx, 3);
+    let (cached, cached_receipts) = transact_all(cached, &tx, 3);
+
+    // Then
+    assert_eq!(receipts, cached_receipts);
+    assert_eq!(
+        receipts
+            .iter()
+            .filter_map(|receipt| receipt.ra())
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert!(storage.diff(cached.inner()).is_empty());
+    let metrics = cached.metrics();
+    assert!(metrics.code.hits > 0, "{metrics:?}");
+    assert!(metrics.state.hits > 0, "{metrics:?}");
+}
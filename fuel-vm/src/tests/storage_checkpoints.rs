@@ -0,0 +1,162 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    prelude::*,
+    storage::ContractsState,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_storage::{
+    StorageAsMut,
+    StorageAsRef,
+};
+use fuel_tx::{
+    Contract,
+    StorageSlot,
+};
+
+const CONTRACT: ContractId = ContractId::new([1; 32]);
+
+fn write(storage: &mut MemoryStorage, key: u8, value: u8) {
+    storage
+        .storage::<ContractsState>()
+        .insert(&(&CONTRACT, &Bytes32::new([key; 32])).into(), &[value; 32])
+        .unwrap();
+}
+
+fn remove(storage: &mut MemoryStorage, key: u8) {
+    storage
+        .storage::<ContractsState>()
+        .remove(&(&CONTRACT, &Bytes32::new([key; 32])).into())
+        .unwrap();
+}
+
+fn read(storage: &MemoryStorage, key: u8) -> Option<Vec<u8>> {
+    storage
+        .storage::<ContractsState>()
+        .get(&(&CONTRACT, &Bytes32::new([key; 32])).into())
+        .unwrap()
+        .map(|value| value.as_ref().as_ref().to_vec())
+}
+
+fn state_root(storage: &MemoryStorage) -> Bytes32 {
+    let slots = storage
+        .all_contract_state()
+        .filter(|(key, _)| key.contract_id() == &CONTRACT)
+        .map(|(key, value)| {
+            StorageSlot::new(*key.state_key(), Bytes32::try_from(value.as_ref()).unwrap())
+        })
+        .collect::<Vec<_>>();
+    Contract::initial_state_root(slots.iter())
+}
+
+#[test]
+fn revert__inside_a_committed_checkpoint_discards_only_the_inner_changes() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    write(&mut storage, 1, 1);
+    storage.begin();
+    write(&mut storage, 2, 2);
+    storage.begin();
+    write(&mut storage, 1, 3);
+    remove(&mut storage, 2);
+    write(&mut storage, 3, 3);
+
+    // When
+    storage.revert();
+    storage.commit();
+
+    // Then
+    assert_eq!(storage.checkpoints(), 0);
+    assert_eq!(read(&storage, 1), Some(vec![1; 32]));
+    assert_eq!(read(&storage, 2), Some(vec![2; 32]));
+    assert_eq!(read(&storage, 3), None);
+}
+
+#[test]
+fn revert__of_a_checkpoint_discards_the_changes_committed_inside_it() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    write(&mut storage, 1, 1);
+    storage.begin();
+    write(&mut storage, 2, 2);
+    storage.begin();
+    write(&mut storage, 1, 3);
+    remove(&mut storage, 2);
+    storage.commit();
+    assert_eq!(read(&storage, 1), Some(vec![3; 32]));
+    assert_eq!(read(&storage, 2), None);
+
+    // When
+    storage.revert();
+
+    // Then
+    assert_eq!(storage.checkpoints(), 0);
+    assert_eq!(read(&storage, 1), Some(vec![1; 32]));
+    assert_eq!(read(&storage, 2), None);
+}
+
+#[test]
+fn flatten__keeps_the_contract_state_root() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    let mut expected = MemoryStorage::default();
+    for key in 0..8 {
+        write(&mut storage, key, key);
+        write(&mut expected, key, key);
+    }
+    storage.persist();
+    storage.begin();
+    for key in 0..4 {
+        storage.begin();
+        write(&mut storage, key, 0xff);
+        write(&mut expected, key, 0xff);
+        storage.begin();
+        remove(&mut storage, key.saturating_add(4));
+        remove(&mut expected, key.saturating_add(4));
+        storage.commit();
+        storage.commit();
+    }
+    storage.begin();
+    write(&mut storage, 9, 9);
+    write(&mut expected, 9, 9);
+    let root = state_root(&storage);
+
+    // When
+    storage.flatten();
+
+    // Then
+    assert_eq!(storage.checkpoints(), 2);
+    assert_eq!(root, state_root(&expected));
+    assert_eq!(state_root(&storage), root);
+    storage.revert();
+    remove(&mut expected, 9);
+    assert_eq!(state_root(&storage), state_root(&expected));
+    storage.commit();
+    storage.persist();
+    assert_eq!(state_root(&storage), state_root(&expected));
+    assert_eq!(storage.snapshot(), expected.snapshot());
+}
+
+#[test]
+fn rollback__discards_the_transacted_changes() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    write(&mut storage, 1, 1);
+    storage.persist();
+    storage.begin();
+    write(&mut storage, 1, 2);
+    storage.commit();
+    storage.begin();
+    write(&mut storage, 2, 2);
+
+    // When
+    storage.rollback();
+
+    // Then
+    assert_eq!(storage.checkpoints(), 0);
+    assert_eq!(read(&storage, 1), Some(vec![1; 32]));
+    assert_eq!(read(&storage, 2), None);
+}
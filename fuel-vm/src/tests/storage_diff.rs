@@ -0,0 +1,154 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    prelude::*,
+    storage::{
+        ContractsAssets,
+        ContractsRawCode,
+        ContractsState,
+    },
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_storage::StorageAsMut;
+
+const CONTRACTS: [ContractId; 3] = [
+    ContractId::new([1; 32]),
+    ContractId::new([2; 32]),
+    ContractId::new([3; 32]),
+];
+
+fn write_state(storage: &mut MemoryStorage, contract: &ContractId, key: u8, value: u8) {
+    storage
+        .storage_as_mut::<ContractsState>()
+        .insert(&(contract, &Bytes32::new([key; 32])).into(), &[value; 32])
+        .unwrap();
+}
+
+fn write_balance(
+    storage: &mut MemoryStorage,
+    contract: &ContractId,
+    asset: u8,
+    value: Word,
+) {
+    storage
+        .storage_as_mut::<ContractsAssets>()
+        .insert(&(contract, &AssetId::new([asset; 32])).into(), &value)
+        .unwrap();
+}
+
+/// The code, the state and the balances of the contracts, written in the `contracts`
+/// order.
+fn storage_with(contracts: impl Iterator<Item = ContractId>) -> MemoryStorage {
+    let mut storage = MemoryStorage::default();
+    for contract in contracts {
+        storage
+            .storage_as_mut::<ContractsRawCode>()
+            .insert(&contract, &[contract[0]; 8])
+            .unwrap();
+        for key in 0..4 {
+            write_state(&mut storage, &contract, key, contract[0].wrapping_add(key));
+        }
+        write_balance(&mut storage, &contract, 0xaa, 100);
+    }
+    storage
+}
+
+#[test]
+fn global_state_root__is_the_same_for_the_same_state_written_in_different_orders() {
+    // Given
+    let storage = storage_with(CONTRACTS.into_iter());
+    let mut reversed = MemoryStorage::default();
+    reversed.begin();
+    for contract in CONTRACTS.iter().rev() {
+        write_balance(&mut reversed, contract, 0xaa, 100);
+        for key in (0..4).rev() {
+            write_state(&mut reversed, contract, key, contract[0].wrapping_add(key));
+        }
+        reversed
+            .storage_as_mut::<ContractsRawCode>()
+            .insert(contract, &[contract[0]; 8])
+            .unwrap();
+    }
+
+    // When
+    let root = storage.global_state_root();
+    let reversed_root = reversed.global_state_root();
+    let diff = storage.diff(&reversed);
+
+    // Then
+    assert_eq!(root, reversed_root);
+    assert!(diff.is_empty(), "{diff:?}");
+    assert_eq!(
+        storage.all_contract_state().collect::<Vec<_>>(),
+        reversed.all_contract_state().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn diff__pinpoints_the_changed_slot() {
+    // Given
+    let storage = storage_with(CONTRACTS.into_iter());
+    let mut changed = storage.clone();
+    write_state(&mut changed, &CONTRACTS[1], 2, 0xff);
+
+    // When
+    let diff = changed.diff(&storage);
+
+    // Then
+    let key = (&CONTRACTS[1], &Bytes32::new([2; 32])).into();
+    assert_eq!(
+        diff.contract_state.into_iter().collect::<Vec<_>>(),
+        vec![(key, (Some(vec![0xff; 32].into()), Some(vec![4; 32].into())))]
+    );
+    assert!(diff.contracts.is_empty());
+    assert!(diff.balances.is_empty());
+    assert_ne!(changed.global_state_root(), storage.global_state_root());
+}
+
+#[test]
+fn diff__reports_the_keys_missing_from_either_storage() {
+    // Given
+    let storage = storage_with(CONTRACTS[..2].iter().copied());
+    let mut changed = storage.clone();
+    changed
+        .storage_as_mut::<ContractsState>()
+        .remove(&(&CONTRACTS[0], &Bytes32::new([0; 32])).into())
+        .unwrap();
+    write_balance(&mut changed, &CONTRACTS[1], 0xbb, 1);
+
+    // When
+    let diff = storage.diff(&changed);
+
+    // Then
+    assert_eq!(
+        diff.contract_state.into_values().collect::<Vec<_>>(),
+        vec![(Some(vec![1; 32].into()), None)]
+    );
+    assert_eq!(
+        diff.balances.into_iter().collect::<Vec<_>>(),
+        vec![(
+            (&CONTRACTS[1], &AssetId::new([0xbb; 32])).into(),
+            (None, Some(1))
+        )]
+    );
+}
+
+#[test]
+fn global_state_root__commits_to_the_balances() {
+    // Given
+    let storage = storage_with(CONTRACTS.into_iter());
+    let mut changed = storage.clone();
+    write_balance(&mut changed, &CONTRACTS[2], 0xaa, 101);
+
+    // When
+    let root = storage.global_state_root();
+    let changed_root = changed.global_state_root();
+
+    // Then
+    assert_ne!(root, changed_root);
+    write_balance(&mut changed, &CONTRACTS[2], 0xaa, 100);
+    assert_eq!(changed.global_state_root(), root);
+}
@@ -0,0 +1,161 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::deploy_read_then_write_contract;
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::StorageSnapshot,
+    util::test_helpers::{
+        Fault,
+        FaultyStorage,
+        FaultyStorageError,
+    },
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::convert::Infallible;
+use fuel_tx::{
+    ConsensusParameters,
+    ReceiptsExt,
+};
+
+type FaultResult = Result<Vec<Word>, InterpreterError<FaultyStorageError<Infallible>>>;
+
+struct Outcome {
+    result: FaultResult,
+    calls: usize,
+    snapshot: StorageSnapshot,
+}
+
+/// Transacts the `tx` inside a checkpoint of the `storage`, committed if the
+/// transaction succeeds and reverted otherwise.
+fn transact(storage: MemoryStorage, fault: Fault, tx: Checked<Script>) -> Outcome {
+    let consensus_params = ConsensusParameters::standard();
+    let mut storage = FaultyStorage::new(storage, fault);
+    storage.inner_mut().begin();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    transactor.transact(tx);
+    let result = match transactor.result() {
+        Ok(state) => {
+            assert_eq!(state.receipts().panic_reason(), None);
+            Ok(state.receipts().logs().iter().map(|log| log.ra).collect())
+        }
+        Err(InterpreterError::Storage(err)) => {
+            Err(InterpreterError::Storage(err.clone()))
+        }
+        Err(err) => panic!("Expected a storage error, got {err:?}"),
+    };
+    let storage = AsMut::<FaultyStorage<MemoryStorage>>::as_mut(&mut transactor);
+    let calls = storage.calls();
+    let storage = storage.inner_mut();
+    if result.is_ok() {
+        storage.commit();
+    } else {
+        storage.revert();
+    }
+    Outcome {
+        result,
+        calls,
+        snapshot: storage.snapshot(),
+    }
+}
+
+#[test]
+fn transact__without_faults_writes_the_state() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let snapshot = storage.snapshot();
+
+    // When
+    let outcome = transact(storage, Fault::Never, tx);
+
+    // Then
+    assert_eq!(outcome.result, Ok(vec![0]));
+    assert!(outcome.calls > 0);
+    assert_ne!(outcome.snapshot, snapshot);
+}
+
+#[test]
+fn transact__with_a_fault_at_each_call_fails_with_a_storage_error_and_writes_nothing() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let snapshot = storage.snapshot();
+    let calls = transact(storage.clone(), Fault::Never, tx.clone()).calls;
+
+    for call in 0..calls {
+        // When
+        let outcome = transact(storage.clone(), Fault::OnCall(call), tx.clone());
+
+        // Then
+        match outcome.result {
+            Err(InterpreterError::Storage(FaultyStorageError::Injected {
+                call: failed,
+                ..
+            })) => assert_eq!(failed, call),
+            result => panic!("Expected the call {call} to fail, got {result:?}"),
+        }
+        assert_eq!(
+            outcome.snapshot, snapshot,
+            "The call {call} wrote the state"
+        );
+    }
+}
+
+#[test]
+fn transact__with_a_failing_operation_fails_with_a_storage_error_and_writes_nothing() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let snapshot = storage.snapshot();
+    let fault = Fault::OnOperation("ContractsState::replace");
+
+    // When
+    let outcome = transact(storage, fault, tx);
+
+    // Then
+    match outcome.result {
+        Err(InterpreterError::Storage(FaultyStorageError::Injected {
+            operation,
+            ..
+        })) => assert_eq!(operation, "ContractsState::replace"),
+        result => panic!("Expected the state write to fail, got {result:?}"),
+    }
+    assert_eq!(outcome.snapshot, snapshot);
+}
+
+#[test]
+fn transact__with_random_faults_is_all_or_nothing() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let snapshot = storage.snapshot();
+    let expected = transact(storage.clone(), Fault::Never, tx.clone()).snapshot;
+
+    for seed in 0..32 {
+        let fault = Fault::Random {
+            seed,
+            probability: 0.05,
+        };
+
+        // When
+        let outcome = transact(storage.clone(), fault, tx.clone());
+
+        // Then
+        match outcome.result {
+            Ok(logs) => {
+                assert_eq!(logs, vec![0]);
+                assert_eq!(outcome.snapshot, expected);
+            }
+            Err(InterpreterError::Storage(FaultyStorageError::Injected { .. })) => {
+                assert_eq!(outcome.snapshot, snapshot);
+            }
+            Err(err) => panic!("Expected a storage error, got {err:?}"),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    prelude::*,
+    script_with_data_offset,
+    util::test_helpers::TestBuilder,
+};
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV2,
+    Chargeable,
+    ConsensusParameters,
+    GasCostsValues,
+};
+use fuel_types::{
+    canonical::Serialize,
+    Bytes32,
+    Immediate18,
+};
+
+/// The number of slots created by each path.
+const SLOTS: u8 = 10;
+const PER_SLOT: Word = 1_000;
+const PER_BYTE: Word = 10;
+/// The length of the data of the message sent by `SMO`.
+const DATA_LEN: Word = 100;
+
+fn gas_costs(per_slot: Word, per_byte: Word) -> GasCosts {
+    let GasCostsValues::V2(values) = GasCostsValues::default() else {
+        unreachable!("The default gas costs are the latest version");
+    };
+    GasCosts::new(
+        GasCostsValuesV2 {
+            new_storage_per_slot: per_slot,
+            new_storage_per_byte: per_byte,
+            ..values
+        }
+        .into(),
+    )
+}
+
+/// The gas charged for creating `slots` new storage slots.
+fn storage_gas(slots: Word) -> Word {
+    slots * (PER_SLOT + StorageSlot::SLOT_SIZE as Word * PER_BYTE)
+}
+
+fn key(index: u8) -> Bytes32 {
+    let mut key = Bytes32::zeroed();
+    key[Bytes32::LEN - 1] = index;
+    key
+}
+
+/// The `body` of a contract logging the gas it uses and the value of `0x13`, storing
+/// the number of slots created.
+fn measured(body: Vec<Instruction>) -> Vec<Instruction> {
+    let mut program = vec![op::move_(0x20, RegId::GGAS)];
+    program.extend(body);
+    program.extend([
+        op::sub(0x21, 0x20, RegId::GGAS),
+        op::log(0x21, 0x13, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]);
+    program
+}
+
+/// Writes the slots of the keys `0..SLOTS` with a `SWW` each.
+fn sww_program() -> Vec<Instruction> {
+    let mut body = vec![op::movi(0x10, Bytes32::LEN as Immediate18), op::aloc(0x10)];
+    for _ in 0..SLOTS {
+        body.extend([
+            op::sww(RegId::HP, 0x11, RegId::ONE),
+            op::add(0x13, 0x13, 0x11),
+            op::lw(0x12, RegId::HP, 3),
+            op::addi(0x12, 0x12, 1),
+            op::sw(RegId::HP, 0x12, 3),
+        ]);
+    }
+    measured(body)
+}
+
+/// Writes the slots of the keys `0..SLOTS` with one `SWWQ`.
+fn swwq_program() -> Vec<Instruction> {
+    measured(vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::move_(0x11, RegId::HP),
+        op::movi(0x10, (SLOTS as usize * Bytes32::LEN) as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x12, SLOTS as Immediate18),
+        op::swwq(0x11, 0x13, RegId::HP, 0x12),
+    ])
+}
+
+/// Calls the contract of the `program` deployed with the `initial_state`, returning
+/// the gas used by the contract and the number of slots it created.
+fn call(
+    program: Vec<Instruction>,
+    initial_state: Option<Vec<StorageSlot>>,
+    gas_costs: GasCosts,
+) -> (Word, Word) {
+    let mut test_context = TestBuilder::new(2322u64);
+    test_context.with_gas_costs(gas_costs);
+    let contract_id = test_context
+        .setup_contract(program, None, initial_state)
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    let result = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .execute();
+    assert!(matches!(result.state(), ProgramState::Return(1)));
+    result
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::Log { ra, rb, .. } => Some((*ra, *rb)),
+            _ => None,
+        })
+        .expect("The log of the contract is missing")
+}
+
+/// The gas the `program` uses more when the new storage is charged, and the number of
+/// slots it created.
+fn execution_storage_gas(
+    program: Vec<Instruction>,
+    initial_state: Option<Vec<StorageSlot>>,
+) -> (Word, Word) {
+    let (free, _) = call(program.clone(), initial_state.clone(), gas_costs(0, 0));
+    let (charged, created) = call(program, initial_state, gas_costs(PER_SLOT, PER_BYTE));
+    (charged - free, created)
+}
+
+/// A `Create` with the slots of the keys `0..SLOTS`.
+fn create_tx() -> Create {
+    let slots = (0..SLOTS)
+        .map(|index| StorageSlot::new(key(index), Bytes32::zeroed()))
+        .collect();
+    TransactionBuilder::create(
+        vec![op::ret(RegId::ONE)]
+            .into_iter()
+            .collect::<Vec<u8>>()
+            .into(),
+        Default::default(),
+        slots,
+    )
+    .add_random_fee_input()
+    .finalize()
+}
+
+/// The min gas the [`create_tx`] requires more when the new storage is charged.
+fn create_storage_gas() -> Word {
+    let tx = create_tx();
+    let fee_params = *ConsensusParameters::standard().fee_params();
+    tx.min_gas(&gas_costs(PER_SLOT, PER_BYTE), &fee_params)
+        - tx.min_gas(&gas_costs(0, 0), &fee_params)
+}
+
+#[test]
+fn new_storage__costs_the_same_through_sww_swwq_and_create() {
+    // When
+    let sww = execution_storage_gas(sww_program(), None);
+    let swwq = execution_storage_gas(swwq_program(), None);
+    let create = create_storage_gas();
+
+    // Then
+    let expected = storage_gas(SLOTS as Word);
+    assert_eq!(sww, (expected, SLOTS as Word));
+    assert_eq!(swwq, (expected, SLOTS as Word));
+    assert_eq!(create, expected);
+}
+
+#[test]
+fn swwq__charges_only_the_newly_created_slots() {
+    // Given
+    let existing = (0..SLOTS)
+        .step_by(2)
+        .map(|index| StorageSlot::new(key(index), Bytes32::zeroed()))
+        .collect::<Vec<_>>();
+    let created = SLOTS as Word - existing.len() as Word;
+
+    // When
+    let swwq = execution_storage_gas(swwq_program(), Some(existing));
+
+    // Then
+    assert_eq!(swwq, (storage_gas(created), created));
+}
+
+/// The gas used by a script sending a message of `DATA_LEN` bytes of data.
+fn smo_gas_used(gas_costs: GasCosts) -> Word {
+    let script = vec![
+        op::movi(0x10, DATA_LEN as Immediate18),
+        op::aloc(0x10),
+        op::smo(RegId::HP, RegId::HP, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let mut test_context = TestBuilder::new(2322u64);
+    let result = test_context
+        .with_gas_costs(gas_costs)
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .execute();
+    assert!(matches!(result.state(), ProgramState::Return(1)));
+    result
+        .receipts()
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("The script result is missing")
+}
+
+#[test]
+fn smo__charges_the_data_retained_in_the_outbox() {
+    // When
+    let free = smo_gas_used(gas_costs(0, 0));
+    let charged = smo_gas_used(gas_costs(PER_SLOT, PER_BYTE));
+
+    // Then
+    assert_eq!(charged - free, PER_SLOT + DATA_LEN * PER_BYTE);
+}
+
+#[test]
+fn smo__charges_no_new_storage_without_a_per_slot_cost() {
+    // When
+    let free = smo_gas_used(gas_costs(0, 0));
+    let per_byte_only = smo_gas_used(gas_costs(0, PER_BYTE));
+    let default = smo_gas_used(GasCosts::default());
+
+    // Then
+    assert_eq!(GasCosts::default().new_storage_per_byte(), 1);
+    assert_eq!(per_byte_only, free);
+    assert_eq!(default, free);
+}
+
+#[test]
+fn create__checked_fee_has_the_min_gas_of_the_initial_slots() {
+    // Given
+    let tx = create_tx();
+    let gas_costs = gas_costs(PER_SLOT, PER_BYTE);
+    let mut consensus_params = ConsensusParameters::standard();
+    consensus_params.set_gas_costs(gas_costs.clone());
+    let fee_params = consensus_params.fee_params();
+
+    // When
+    let fee = TransactionFee::checked_from_tx(&gas_costs, fee_params, &tx, 1)
+        .expect("The fee doesn't overflow");
+    let checked = tx
+        .clone()
+        .into_checked_basic(Default::default(), &consensus_params)
+        .expect("The transaction is valid");
+
+    // Then
+    assert_eq!(fee.min_gas(), tx.min_gas(&gas_costs, fee_params));
+    assert_eq!(fee.min_gas(), checked.metadata().min_gas);
+    assert_eq!(fee.max_gas(), checked.metadata().max_gas);
+    assert_eq!(
+        fee.min_gas() - tx.min_gas(&GasCosts::default(), fee_params),
+        storage_gas(SLOTS as Word)
+    );
+}
+
+#[test]
+fn create__charges_no_new_storage_without_a_per_slot_cost() {
+    // Given
+    let tx = create_tx();
+    let fee_params = *ConsensusParameters::standard().fee_params();
+
+    // When
+    let free = tx.min_gas(&gas_costs(0, 0), &fee_params);
+    let per_byte_only = tx.min_gas(&gas_costs(0, PER_BYTE), &fee_params);
+    let default = tx.min_gas(&GasCosts::default(), &fee_params);
+
+    // Then
+    assert_eq!(per_byte_only, free);
+    assert_eq!(default, free);
+}
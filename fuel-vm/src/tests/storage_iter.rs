@@ -0,0 +1,216 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    prelude::*,
+    storage::{
+        ContractsAssets,
+        ContractsRawCode,
+        ContractsState,
+        ContractsStateKey,
+    },
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_storage::{
+    StorageAsMut,
+    StorageAsRef,
+};
+
+const CONTRACT: ContractId = ContractId::new([0x11; 32]);
+
+/// Shares all but the last byte with the [`CONTRACT`].
+const NEIGHBOUR: ContractId = {
+    let mut id = [0x11; 32];
+    id[31] = 0x12;
+    ContractId::new(id)
+};
+
+fn slot_key(i: u16) -> Bytes32 {
+    let mut key = [0; 32];
+    key[30..].copy_from_slice(&i.to_be_bytes());
+    key.into()
+}
+
+fn write(storage: &mut MemoryStorage, contract: &ContractId, key: &Bytes32, value: u8) {
+    storage
+        .storage_as_mut::<ContractsState>()
+        .insert(&(contract, key).into(), &[value; 32])
+        .unwrap();
+}
+
+fn storage_with_interleaved_slots(slots: u16) -> MemoryStorage {
+    let mut storage = MemoryStorage::default();
+    for i in (0..slots).rev() {
+        write(&mut storage, &NEIGHBOUR, &slot_key(i), 2);
+        write(&mut storage, &CONTRACT, &slot_key(i), 1);
+    }
+    storage
+}
+
+#[test]
+fn contract_state_entries__returns_only_the_slots_of_the_contract_in_order() {
+    // Given
+    let storage = storage_with_interleaved_slots(1000);
+
+    // When
+    let entries = storage
+        .contract_state_entries(&CONTRACT)
+        .collect::<Vec<_>>();
+
+    // Then
+    assert_eq!(entries.len(), 1000);
+    for (i, (key, value)) in entries.iter().enumerate() {
+        assert_eq!(key, &slot_key(i as u16));
+        assert_eq!(value.as_ref().as_ref(), &[1; 32]);
+    }
+}
+
+#[test]
+fn iter_prefix__of_a_shared_prefix_returns_the_slots_of_both_contracts_in_order() {
+    // Given
+    let storage = storage_with_interleaved_slots(1000);
+
+    // When
+    let keys = storage
+        .storage::<ContractsState>()
+        .iter_prefix(&CONTRACT[..31])
+        .map(|entry| entry.unwrap().0)
+        .collect::<Vec<_>>();
+
+    // Then
+    let expected = [CONTRACT, NEIGHBOUR]
+        .iter()
+        .flat_map(|contract| {
+            (0..1000).map(|i| ContractsStateKey::new(contract, &slot_key(i)))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn iter_prefix__sees_the_changes_of_the_open_checkpoints() {
+    // Given
+    let mut storage = storage_with_interleaved_slots(4);
+    storage.begin();
+    write(&mut storage, &CONTRACT, &slot_key(10), 3);
+    storage
+        .storage_as_mut::<ContractsState>()
+        .remove(&(&CONTRACT, &slot_key(0)).into())
+        .unwrap();
+
+    // When
+    let entries = storage
+        .contract_state_entries(&CONTRACT)
+        .map(|(key, value)| (key, value.into_owned()))
+        .collect::<Vec<_>>();
+
+    // Then
+    assert_eq!(
+        entries,
+        vec![
+            (slot_key(1), [1; 32][..].into()),
+            (slot_key(2), [1; 32][..].into()),
+            (slot_key(3), [1; 32][..].into()),
+            (slot_key(10), [3; 32][..].into()),
+        ]
+    );
+}
+
+#[test]
+fn iter_prefix__returns_the_balances_and_the_code_of_the_contract() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    for contract in [NEIGHBOUR, CONTRACT] {
+        for asset in [3u8, 1, 2] {
+            storage
+                .storage_as_mut::<ContractsAssets>()
+                .insert(&(&contract, &AssetId::new([asset; 32])).into(), &10)
+                .unwrap();
+        }
+        storage
+            .storage_as_mut::<ContractsRawCode>()
+            .insert(&contract, &[contract[31]; 8])
+            .unwrap();
+    }
+
+    // When
+    let assets = storage
+        .storage::<ContractsAssets>()
+        .iter_prefix(CONTRACT.as_ref())
+        .map(|entry| *entry.unwrap().0.asset_id())
+        .collect::<Vec<_>>();
+    let code = storage
+        .storage::<ContractsRawCode>()
+        .iter_prefix(&CONTRACT[..31])
+        .map(|entry| entry.unwrap())
+        .map(|(id, code)| (id, code.as_ref().as_ref().to_vec()))
+        .collect::<Vec<_>>();
+
+    // Then
+    assert_eq!(
+        assets,
+        vec![
+            AssetId::new([1; 32]),
+            AssetId::new([2; 32]),
+            AssetId::new([3; 32])
+        ]
+    );
+    assert_eq!(
+        code,
+        vec![(CONTRACT, vec![0x11; 8]), (NEIGHBOUR, vec![0x12; 8])]
+    );
+}
+
+#[test]
+fn iter_prefix__is_empty_for_a_prefix_longer_than_the_keys() {
+    // Given
+    let storage = storage_with_interleaved_slots(4);
+
+    // When
+    let entries = storage
+        .storage::<ContractsRawCode>()
+        .iter_prefix(&[0x11; 33])
+        .count();
+
+    // Then
+    assert_eq!(entries, 0);
+}
+
+#[test]
+fn contract_state_remove_range__clears_the_set_slots_of_a_large_range() {
+    // Given
+    let mut storage = storage_with_interleaved_slots(1000);
+    let start_key = slot_key(10);
+
+    // When
+    let result = storage.contract_state_remove_range(&CONTRACT, &start_key, 1 << 40);
+
+    // Then
+    assert_eq!(result, Ok(None));
+    let keys = storage
+        .contract_state_entries(&CONTRACT)
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(keys, (0..10).map(slot_key).collect::<Vec<_>>());
+    assert_eq!(storage.contract_state_entries(&NEIGHBOUR).count(), 1000);
+}
+
+#[test]
+fn contract_state_remove_range__stops_at_the_last_key() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    let mut last_keys = [[u8::MAX; 32]; 2];
+    last_keys[0][31] = 0xfe;
+    for key in last_keys {
+        write(&mut storage, &CONTRACT, &key.into(), 1);
+    }
+
+    // When
+    let result = storage.contract_state_remove_range(&CONTRACT, &last_keys[0].into(), 5);
+
+    // Then
+    assert_eq!(result, Ok(Some(())));
+    assert_eq!(storage.contract_state_entries(&CONTRACT).count(), 0);
+}
@@ -0,0 +1,619 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+    storage::{
+        Column,
+        ContractsState,
+        KeyValueStore,
+        KvBatch,
+        KvEntries,
+        KvStoreAdapter,
+        KvStoreError,
+        KvTransaction,
+        PersistentStorage,
+        PersistentStore,
+        StorageSnapshot,
+    },
+    util::test_helpers::TestBuilder,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_merkle::{
+    common::StorageMap,
+    sparse::{
+        in_memory::NodesTable,
+        MerkleTree,
+        MerkleTreeKey,
+    },
+};
+use fuel_storage::{
+    StorageAsRef,
+    StorageColumns,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+};
+use fuel_types::canonical::Serialize;
+
+use super::test_helpers::BTreeMapStore;
+
+#[cfg(feature = "redb")]
+use crate::storage::RedbStore;
+#[cfg(feature = "redb")]
+use std::path::Path;
+
+/// Store crashing in the middle of its next batch of writes, which it then doesn't
+/// apply.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct CrashingStore {
+    store: BTreeMapStore,
+    crash: bool,
+}
+
+impl KeyValueStore for CrashingStore {
+    type Error = &'static str;
+
+    fn get(&self, column: Column, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.store.get(column, key).unwrap())
+    }
+
+    fn put(
+        &mut self,
+        column: Column,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.store.put(column, key, value).unwrap();
+        Ok(())
+    }
+
+    fn delete(&mut self, column: Column, key: &[u8]) -> Result<(), Self::Error> {
+        self.store.delete(column, key).unwrap();
+        Ok(())
+    }
+}
+
+impl PersistentStore for CrashingStore {
+    fn write_batch(&mut self, batch: KvBatch) -> Result<(), Self::Error> {
+        if self.crash {
+            self.crash = false;
+            return Err("crashed")
+        }
+        self.store.write_batch(batch).unwrap();
+        Ok(())
+    }
+
+    fn entries(&self, column: Column, prefix: &[u8]) -> Result<KvEntries, Self::Error> {
+        Ok(self.store.entries(column, prefix).unwrap())
+    }
+}
+
+type KvStorage = KvStoreAdapter<BTreeMapStore>;
+
+/// Writes the state of the `storage` and its blocks up to the current one to the `kv`.
+fn import<K: KeyValueStore>(kv: &mut KvStoreAdapter<K>, storage: &MemoryStorage) {
+    kv.import_snapshot(&storage.snapshot()).unwrap();
+    for height in 0..=*storage.block_height().unwrap() {
+        let height = height.into();
+        kv.set_block(
+            height,
+            storage.timestamp(height).unwrap(),
+            &storage.block_hash(height).unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+/// The key-value storage with the state of the `storage` and its blocks up to the
+/// current one.
+fn kv_storage<K: KeyValueStore + Default>(storage: &MemoryStorage) -> KvStoreAdapter<K> {
+    let mut kv = KvStoreAdapter::<K>::default();
+    import(&mut kv, storage);
+    kv
+}
+
+/// The persistent store with the committed state of the `storage`.
+fn committed_store<K>(storage: &MemoryStorage) -> K
+where
+    K: PersistentStore + Default,
+{
+    let mut kv: PersistentStorage<K> = kv_storage(storage);
+    kv.commit().unwrap();
+    kv.into_persistent_store()
+}
+
+/// Checks the roots of the states of the contracts of the `storage` in the `kv`.
+fn assert_same_state_roots<K: KeyValueStore>(
+    kv: &PersistentStorage<K>,
+    storage: &MemoryStorage,
+) {
+    let StorageSnapshot::V1(snapshot) = storage.snapshot();
+    for contract in snapshot.contracts.keys() {
+        assert_eq!(
+            kv.storage::<ContractsState>().root(contract).unwrap(),
+            storage.storage::<ContractsState>().root(contract).unwrap()
+        );
+    }
+}
+
+/// Deploys the `contract` with the `balance` of the base asset, and returns the
+/// storage with the contract and a script calling it.
+fn setup(contract: Vec<Instruction>, balance: Word) -> (MemoryStorage, Checked<Script>) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let base_asset_id = *test_context.get_base_asset_id();
+    let contract_id = test_context
+        .setup_contract(contract, Some((base_asset_id, balance)), None)
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    (test_context.get_storage().clone(), tx)
+}
+
+fn transact_in<S: InterpreterStorage>(
+    storage: &mut S,
+    tx: Checked<Script>,
+) -> Vec<Receipt> {
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    transactor.transact(tx).receipts().unwrap().to_vec()
+}
+
+fn transact<S: InterpreterStorage>(
+    mut storage: S,
+    tx: Checked<Script>,
+) -> (S, Vec<Receipt>) {
+    let receipts = transact_in(&mut storage, tx);
+    (storage, receipts)
+}
+
+/// The name of the database file of the `redb` stores in their directory.
+#[cfg(feature = "redb")]
+const REDB_FILE: &str = "storage.redb";
+
+/// The persistent storage over a new `redb` database in the `dir`, with the
+/// committed state of the `storage`.
+#[cfg(feature = "redb")]
+fn redb_storage(storage: &MemoryStorage, dir: &Path) -> PersistentStorage<RedbStore> {
+    let mut kv = reopen_redb_storage(dir);
+    import(&mut kv, storage);
+    kv.commit().unwrap();
+    kv
+}
+
+/// The persistent storage over the `redb` database in the `dir`.
+#[cfg(feature = "redb")]
+fn reopen_redb_storage(dir: &Path) -> PersistentStorage<RedbStore> {
+    let store = RedbStore::open(dir.join(REDB_FILE)).unwrap();
+    PersistentStorage::new(KvTransaction::new(store))
+}
+
+/// Calls the `contract` over the memory storage and over the key-value storage with
+/// the same state, and checks both have the same receipts and the same state after.
+///
+/// With the `redb` feature, the contract is also called over a `redb` database, whose
+/// committed state and roots are checked the same way.
+fn assert_same_execution(contract: Vec<Instruction>, balance: Word) -> Vec<Receipt> {
+    // Given
+    let (storage, tx) = setup(contract, balance);
+    let kv: KvStorage = kv_storage(&storage);
+    #[cfg(feature = "redb")]
+    let (redb_dir, redb_tx, mut redb) = {
+        let dir = tempfile::tempdir().unwrap();
+        let redb = redb_storage(&storage, dir.path());
+        (dir, tx.clone(), redb)
+    };
+
+    // When
+    let (storage, receipts) = transact(storage, tx.clone());
+    let (kv, kv_receipts) = transact(kv, tx);
+
+    // Then
+    assert_eq!(kv_receipts, receipts);
+    assert_eq!(kv.export_snapshot().unwrap(), storage.snapshot());
+    assert_eq!(
+        kv.into_store(),
+        kv_storage::<BTreeMapStore>(&storage).into_store()
+    );
+    #[cfg(feature = "redb")]
+    {
+        let redb_receipts = transact_in(&mut redb, redb_tx);
+        redb.commit().unwrap();
+        drop(redb.into_persistent_store());
+        let redb = reopen_redb_storage(redb_dir.path());
+        assert_eq!(redb_receipts, receipts);
+        assert_eq!(redb.export_snapshot().unwrap(), storage.snapshot());
+        assert_same_state_roots(&redb, &storage);
+    }
+    receipts
+}
+
+#[test]
+fn columns__are_listed_in_the_order_of_their_ids_with_distinct_names() {
+    let ids = Column::ALL.iter().map(Column::id).collect::<Vec<_>>();
+    let mut names = Column::ALL.iter().map(Column::name).collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    assert_eq!(ids, (0..Column::ALL.len() as u32).collect::<Vec<_>>());
+    assert_eq!(names.len(), Column::ALL.len());
+}
+
+#[test]
+fn transact__reads_and_writes_the_state_like_the_memory_storage() {
+    let contract = vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::sb(RegId::HP, RegId::ONE, 0),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::log(0x11, 0x12, RegId::ZERO, RegId::ZERO),
+        op::movi(0x11, 7),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = assert_same_execution(contract, 0);
+
+    assert!(matches!(receipts[1], Receipt::Log { ra: 0, rb: 0, .. }));
+}
+
+#[test]
+fn transact__writes_reads_and_clears_ranges_of_slots_like_the_memory_storage() {
+    let contract = vec![
+        // The key and the two slots of values.
+        op::movi(0x10, 96),
+        op::aloc(0x10),
+        op::movi(0x11, 2),
+        op::movi(0x12, 0xff),
+        op::sb(RegId::HP, 0x12, 32),
+        op::sb(RegId::HP, 0x12, 95),
+        op::addi(0x13, RegId::HP, 32),
+        op::swwq(RegId::HP, 0x14, 0x13, 0x11),
+        op::srwq(0x13, 0x15, RegId::HP, 0x11),
+        op::movi(0x16, 64),
+        op::logd(0x14, 0x15, 0x13, 0x16),
+        op::scwq(RegId::HP, 0x17, 0x11),
+        op::srwq(0x13, 0x15, RegId::HP, 0x11),
+        op::log(0x14, 0x15, 0x17, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = assert_same_execution(contract, 0);
+
+    assert!(matches!(receipts[1], Receipt::LogData { ra: 2, rb: 1, .. }));
+    assert!(matches!(
+        receipts[2],
+        Receipt::Log {
+            ra: 2,
+            rb: 0,
+            rc: 1,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn transact__mints_and_burns_like_the_memory_storage() {
+    let contract = vec![
+        op::movi(0x10, 100),
+        op::mint(0x10, RegId::ZERO),
+        op::movi(0x10, 30),
+        op::burn(0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = assert_same_execution(contract, 1_000);
+
+    assert!(matches!(receipts[1], Receipt::Mint { val: 100, .. }));
+    assert!(matches!(receipts[2], Receipt::Burn { val: 30, .. }));
+}
+
+#[test]
+fn transact__reads_the_blocks_and_the_code_like_the_memory_storage() {
+    let contract = vec![
+        op::bhei(0x10),
+        op::subi(0x11, 0x10, 1),
+        op::time(0x12, 0x11),
+        op::movi(0x13, 32),
+        op::aloc(0x13),
+        op::bhsh(RegId::HP, 0x11),
+        op::logd(0x10, 0x12, RegId::HP, 0x13),
+        op::movi(0x14, 16),
+        op::aloc(0x14),
+        op::addi(
+            0x15,
+            RegId::FP,
+            CallFrame::contract_id_offset() as Immediate12,
+        ),
+        op::ccp(RegId::HP, 0x15, RegId::ZERO, 0x14),
+        op::logd(RegId::ZERO, RegId::ZERO, RegId::HP, 0x14),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = assert_same_execution(contract, 0);
+
+    assert!(matches!(receipts[1], Receipt::LogData { len: 32, .. }));
+    assert!(matches!(receipts[2], Receipt::LogData { len: 16, .. }));
+}
+
+#[test]
+fn transact__fails_with_a_missing_block() {
+    // Given
+    let contract = vec![op::bhei(0x10), op::time(0x11, 0x10), op::ret(RegId::ONE)];
+    let (storage, tx) = setup(contract, 0);
+    let block_height = storage.block_height().unwrap();
+    let mut kv: KvStorage = kv_storage(&storage);
+    kv.store_mut()
+        .delete(Column::BlockTimestamps, &block_height.to_be_bytes())
+        .unwrap();
+
+    // When
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(kv, InterpreterParams::new(0, &consensus_params));
+    transactor.transact(tx);
+
+    // Then
+    assert!(matches!(
+        transactor.error(),
+        Some(InterpreterError::Storage(KvStoreError::MissingBlock(height)))
+            if *height == block_height
+    ));
+}
+
+/// Increments the word stored under the key `[1, 0, ..., 0]`.
+fn counter_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::sb(RegId::HP, RegId::ONE, 0),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::addi(0x11, 0x11, 1),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn persistent_storage__drops_the_writes_of_a_transaction_not_committed() {
+    // Given
+    let (storage, tx) = setup(counter_contract(), 0);
+    let mut kv: PersistentStorage<BTreeMapStore> = kv_storage(&storage);
+    kv.commit().unwrap();
+    let committed = kv.store().store().clone();
+
+    // When
+    let (kv, _) = transact(kv, tx);
+
+    // Then
+    assert!(!kv.store().batch().is_empty());
+    assert_eq!(kv.into_persistent_store(), committed);
+}
+
+#[test]
+fn persistent_storage__commits_the_writes_of_each_transaction_atomically() {
+    // Given
+    let (storage, tx) = setup(counter_contract(), 0);
+    let mut kv: PersistentStorage<CrashingStore> = kv_storage(&storage);
+    kv.commit().unwrap();
+    let committed = kv.store().store().clone();
+    let (mut kv, _) = transact(kv, tx.clone());
+    let (storage, _) = transact(storage, tx);
+    let buffered = kv.store().batch().clone();
+
+    // When
+    kv.store_mut().store_mut().crash = true;
+    let crashed = kv.commit();
+
+    // Then
+    assert_eq!(crashed, Err(KvStoreError::Store("crashed")));
+    assert_eq!(kv.store().store(), &committed);
+    assert_eq!(kv.store().batch(), &buffered);
+    kv.commit().unwrap();
+    assert!(kv.store().batch().is_empty());
+    assert_eq!(
+        kv.into_persistent_store().store,
+        committed_store::<BTreeMapStore>(&storage)
+    );
+}
+
+#[test]
+fn root__of_the_state_is_the_one_of_the_memory_storage() {
+    // Given
+    let (storage, tx) = setup(counter_contract(), 0);
+    let kv: PersistentStorage<BTreeMapStore> = kv_storage(&storage);
+    let (storage, _) = transact(storage, tx.clone());
+
+    // When
+    let (kv, _) = transact(kv, tx);
+
+    // Then
+    assert_same_state_roots(&kv, &storage);
+}
+
+/// Writes the words from 1 to 20 to the slots from the key `[0, ..., 0]`, and clears
+/// the first 10 of them.
+fn slots_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 21 * 32),
+        op::aloc(0x10),
+        op::movi(0x11, 20),
+        op::movi(0x12, 1),
+        op::movi(0x16, 21),
+        op::addi(0x13, RegId::HP, 32 + 24),
+        op::sw(0x13, 0x12, 0),
+        op::addi(0x12, 0x12, 1),
+        op::addi(0x13, 0x13, 32),
+        op::jneb(0x12, 0x16, RegId::ZERO, 2),
+        op::addi(0x13, RegId::HP, 32),
+        op::swwq(RegId::HP, 0x15, 0x13, 0x11),
+        op::movi(0x11, 10),
+        op::scwq(RegId::HP, 0x15, 0x11),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn transact__writes_and_clears_the_slots_of_a_tree_like_the_memory_storage() {
+    let receipts = assert_same_execution(slots_contract(), 0);
+
+    assert!(matches!(receipts[1], Receipt::Return { val: 1, .. }));
+}
+
+#[test]
+fn commit__keeps_the_nodes_of_the_state_trees_in_the_store() {
+    // Given
+    let (storage, tx) = setup(slots_contract(), 0);
+    let kv: PersistentStorage<BTreeMapStore> = kv_storage(&storage);
+    let (storage, _) = transact(storage, tx.clone());
+    let (mut kv, _) = transact(kv, tx);
+
+    // When
+    kv.commit().unwrap();
+
+    // Then
+    let store = kv.into_persistent_store();
+    let slots = store.entries(Column::ContractsState, &[]).unwrap();
+    let nodes = store
+        .entries(Column::ContractsStateMerkleData, &[])
+        .unwrap();
+    let roots = store
+        .entries(Column::ContractsStateMerkleMetadata, &[])
+        .unwrap();
+    let tree = MerkleTree::<NodesTable, _>::from_set(
+        StorageMap::<NodesTable>::new(),
+        slots
+            .iter()
+            .map(|(key, value)| (MerkleTreeKey::new(&key[ContractId::LEN..]), value)),
+    )
+    .unwrap();
+    assert_eq!(slots.len(), 10);
+    assert_eq!(nodes.len(), tree.storage().len());
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].1, tree.root());
+    let kv = PersistentStorage::new(KvTransaction::new(store));
+    assert_same_state_roots(&kv, &storage);
+}
+
+/// Increments the word stored under the key `[0, ..., 0]`, and writes it to the 256
+/// slots from this key, so the slots of a batch written in part differ.
+#[cfg(feature = "redb")]
+fn slots_counter_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, (1 + 256) * 32),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::addi(0x11, 0x11, 1),
+        op::movi(0x12, 256),
+        op::addi(0x13, RegId::HP, 32),
+        op::movi(0x14, 0),
+        op::sw(0x13, 0x11, 0),
+        op::addi(0x13, 0x13, 32),
+        op::addi(0x14, 0x14, 1),
+        op::jneb(0x14, 0x12, RegId::ZERO, 2),
+        op::addi(0x13, RegId::HP, 32),
+        op::swwq(RegId::HP, 0x15, 0x13, 0x12),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// The path of the `redb` database of the process committing the transactions of the
+/// crash-recovery test until it's killed.
+#[cfg(feature = "redb")]
+const REDB_CRASH_DIR: &str = "FUEL_VM_REDB_CRASH_DIR";
+
+#[cfg(feature = "redb")]
+#[test]
+fn redb_storage__recovers_the_last_commit_after_a_kill_in_the_middle_of_a_batch() {
+    use std::{
+        io::{
+            BufRead,
+            BufReader,
+            Write,
+        },
+        process::{
+            Command,
+            Stdio,
+        },
+    };
+
+    let (storage, tx) = setup(slots_counter_contract(), 0);
+
+    // The killed process commits the transactions until it's killed, printing a line
+    // before each commit.
+    if let Some(dir) = std::env::var_os(REDB_CRASH_DIR) {
+        let mut kv = reopen_redb_storage(Path::new(&dir));
+        let mut stdout = std::io::stdout();
+        loop {
+            transact_in(&mut kv, tx.clone());
+            writeln!(stdout, "committing").unwrap();
+            kv.commit().unwrap();
+        }
+    }
+
+    // Given
+    let dir = tempfile::tempdir().unwrap();
+    drop(redb_storage(&storage, dir.path()).into_persistent_store());
+    let (_, module) = module_path!().split_once("::").unwrap();
+    let test = format!(
+        "{module}::redb_storage__recovers_the_last_commit_after_a_kill_in_the_middle_of_a_batch"
+    );
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", &test, "--nocapture"])
+        .env(REDB_CRASH_DIR, dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let commits = 5;
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    for _ in 0..commits {
+        // The first line follows the name of the test printed by the test harness.
+        while !lines.next().unwrap().unwrap().ends_with("committing") {}
+    }
+
+    // When
+    // The process is writing the batch of the last transaction.
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    // Then
+    let kv = reopen_redb_storage(dir.path());
+    let snapshot = kv.export_snapshot().unwrap();
+    let StorageSnapshot::V1(state) = &snapshot;
+    let counter = state.contract_state.values().next().unwrap().as_ref();
+    let transactions = Word::from_be_bytes(counter[..8].try_into().unwrap());
+    assert!(transactions == commits - 1 || transactions == commits);
+    let mut storage = storage;
+    for _ in 0..transactions {
+        transact_in(&mut storage, tx.clone());
+    }
+    assert_eq!(snapshot, storage.snapshot());
+    assert_same_state_roots(&kv, &storage);
+}
@@ -0,0 +1,97 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{
+    deploy_read_then_write_contract,
+    read_then_write_contract,
+};
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        ContractsStateKey,
+        InstrumentedStorage,
+        StorageMetrics,
+    },
+};
+use fuel_tx::{
+    ConsensusParameters,
+    ReceiptsExt,
+};
+
+fn transact(
+    storage: InstrumentedStorage<MemoryStorage>,
+    tx: Checked<Script>,
+) -> InstrumentedStorage<MemoryStorage> {
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    let receipts = transactor.transact(tx).receipts().unwrap();
+    assert_eq!(receipts.panic_reason(), None);
+    AsRef::<InstrumentedStorage<MemoryStorage>>::as_ref(&transactor).clone()
+}
+
+#[test]
+fn metrics__counts_the_code_reads_and_the_state_reads_and_writes_of_a_call() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let code_len = read_then_write_contract().len() * Instruction::SIZE;
+
+    // When
+    let metrics = transact(InstrumentedStorage::new(storage), tx).metrics();
+
+    // Then
+    let code_reads = metrics.operation("ContractsRawCode::read");
+    assert_eq!(code_reads.calls, 1);
+    assert_eq!(code_reads.key_bytes, ContractId::LEN as u64);
+    assert_eq!(code_reads.value_bytes, code_len as u64);
+    assert_eq!(
+        metrics.operation("ContractsRawCode::size_of_value").calls,
+        1
+    );
+    let srw = metrics.operation("ContractsState::get");
+    assert_eq!(srw.calls, 1);
+    assert_eq!(srw.key_bytes, ContractsStateKey::LEN as u64);
+    assert_eq!(srw.value_bytes, 0);
+    let sww = metrics.operation("ContractsState::replace");
+    assert_eq!(sww.calls, 1);
+    assert_eq!(sww.key_bytes, ContractsStateKey::LEN as u64);
+    assert_eq!(sww.value_bytes, Bytes32::LEN as u64);
+    assert_eq!(
+        metrics.operation("ContractsState::insert"),
+        Default::default()
+    );
+}
+
+#[test]
+fn metrics__counts_the_bytes_of_the_values_read_from_the_state() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let storage = transact(InstrumentedStorage::new(storage), tx.clone()).into_inner();
+
+    // When
+    let metrics = transact(InstrumentedStorage::new(storage), tx).metrics();
+
+    // Then
+    let srw = metrics.operation("ContractsState::get");
+    assert_eq!(srw.calls, 1);
+    assert_eq!(srw.value_bytes, Bytes32::LEN as u64);
+}
+
+#[test]
+fn reset__zeroes_the_metrics() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let storage = test_context.get_storage().clone();
+    let mut storage = transact(InstrumentedStorage::new(storage), tx);
+    assert!(storage.metrics().total().calls > 0);
+
+    // When
+    storage.reset();
+
+    // Then
+    assert_eq!(storage.metrics(), StorageMetrics::default());
+    assert_eq!(storage.metrics().total(), Default::default());
+}
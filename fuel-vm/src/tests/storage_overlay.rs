@@ -0,0 +1,153 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::deploy_read_then_write_contract;
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        ContractsState,
+        ContractsStateKey,
+        OverlayStorage,
+    },
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    ReceiptsExt,
+};
+
+fn state_key() -> Bytes32 {
+    let mut key = [0; 32];
+    key[0] = 1;
+    key.into()
+}
+
+fn dry_run<S>(storage: S, tx: Checked<Script>) -> (S, Vec<Word>)
+where
+    S: InterpreterStorage + Clone,
+{
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    let receipts = transactor.transact(tx).receipts().unwrap().to_vec();
+    assert_eq!(receipts.panic_reason(), None);
+    let logs = receipts.logs().iter().map(|log| log.ra).collect();
+    (AsRef::<S>::as_ref(&transactor).clone(), logs)
+}
+
+#[test]
+fn transact__over_an_overlay_leaves_the_base_storage_untouched() {
+    // Given
+    let (test_context, contract_id, tx) = deploy_read_then_write_contract();
+    let base = test_context.get_storage();
+    let snapshot = base.snapshot();
+
+    // When
+    let (overlay, logs) = dry_run(OverlayStorage::new(base), tx);
+
+    // Then
+    assert_eq!(logs, vec![0]);
+    assert_eq!(base.snapshot(), snapshot);
+    let changes = overlay.into_changes();
+    let mut expected = [0; 32];
+    expected[..8].copy_from_slice(&7u64.to_be_bytes());
+    assert_eq!(
+        changes
+            .contract_state
+            .get(&(&contract_id, &state_key()).into()),
+        Some(&Some(expected[..].into()))
+    );
+}
+
+#[test]
+fn transact__over_a_fresh_overlay_does_not_see_the_previous_dry_runs() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let base = test_context.get_storage();
+    let (first, logs) = dry_run(OverlayStorage::new(base), tx.clone());
+    assert_eq!(logs, vec![0]);
+    assert!(!first.changes().is_empty());
+
+    // When
+    let (second, logs) = dry_run(OverlayStorage::new(base), tx);
+
+    // Then
+    assert_eq!(logs, vec![0]);
+    assert_eq!(first.changes(), second.changes());
+}
+
+#[test]
+fn discard__drops_the_buffered_writes() {
+    // Given
+    let (test_context, _, tx) = deploy_read_then_write_contract();
+    let (mut overlay, _) =
+        dry_run(OverlayStorage::new(test_context.get_storage()), tx.clone());
+
+    // When
+    overlay.discard();
+
+    // Then
+    assert!(overlay.changes().is_empty());
+    let (_, logs) = dry_run(overlay, tx);
+    assert_eq!(logs, vec![0]);
+}
+
+#[test]
+fn root__of_the_overlay_matches_the_root_after_applying_the_writes() {
+    // Given
+    let (test_context, contract_id, tx) = deploy_read_then_write_contract();
+    let base = test_context.get_storage();
+    let base_root = base.root(&contract_id).unwrap();
+    let (overlay, _) = dry_run(OverlayStorage::new(base), tx.clone());
+    let (applied, _) = dry_run(base.clone(), tx);
+
+    // When
+    let root = overlay.root(&contract_id).unwrap();
+
+    // Then
+    assert_ne!(root, base_root);
+    assert_eq!(root, applied.root(&contract_id).unwrap());
+    assert_eq!(base.root(&contract_id).unwrap(), base_root);
+}
+
+#[test]
+fn get_batch__reads_the_buffered_writes_over_the_base_storage() {
+    // Given
+    let contract_id = ContractId::new([1; 32]);
+    let keys = [1u8, 2, 3]
+        .map(|key| ContractsStateKey::new(&contract_id, &Bytes32::new([key; 32])));
+    let mut base = MemoryStorage::default();
+    for key in &keys[..2] {
+        base.storage_as_mut::<ContractsState>()
+            .insert(key, &[1; 32])
+            .unwrap();
+    }
+    let mut overlay = OverlayStorage::new(&base);
+    overlay
+        .storage_as_mut::<ContractsState>()
+        .remove(&keys[0])
+        .unwrap();
+    overlay
+        .storage_as_mut::<ContractsState>()
+        .insert(&keys[2], &[2; 32])
+        .unwrap();
+
+    // When
+    let values = overlay
+        .storage_as_ref::<ContractsState>()
+        .get_batch(keys.iter())
+        .into_iter()
+        .map(|value| value.unwrap().map(|value| value.as_ref().as_ref().to_vec()))
+        .collect::<Vec<_>>();
+
+    // Then
+    assert_eq!(values, vec![None, Some(vec![1; 32]), Some(vec![2; 32])]);
+    assert!(base
+        .storage_as_ref::<ContractsState>()
+        .contains_key(&keys[0])
+        .unwrap());
+}
@@ -0,0 +1,132 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{
+    call_contract_script,
+    read_then_write_contract,
+};
+use crate::{
+    prelude::*,
+    storage::{
+        ContractsAssetsStorage,
+        StorageSnapshot,
+    },
+    util::test_helpers::TestBuilder,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use fuel_tx::ReceiptsExt;
+
+fn call_contract(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+) -> Vec<Receipt> {
+    let tx = call_contract_script(test_context, contract_id);
+    test_context
+        .execute_tx(tx)
+        .expect("expected successful vm execution")
+        .receipts()
+        .to_vec()
+}
+
+fn logged_words(receipts: &[Receipt]) -> Vec<Word> {
+    receipts.logs().iter().map(|log| log.ra).collect()
+}
+
+fn snapshot_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "fuel-vm-storage-snapshot-{}-{name}",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn load_from__restores_the_state_written_by_a_transaction() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let asset_id = AssetId::new([3; 32]);
+    let contract_id = test_context
+        .setup_contract(read_then_write_contract(), Some((asset_id, 100)), None)
+        .contract_id;
+    let receipts = call_contract(&mut test_context, contract_id);
+    assert_eq!(receipts.panic_reason(), None);
+    assert_eq!(logged_words(&receipts), vec![0]);
+    let path = snapshot_path("restore");
+    test_context.get_storage().save_to(&path).unwrap();
+
+    // When
+    let storage = MemoryStorage::load_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Then
+    assert_eq!(storage.snapshot(), test_context.get_storage().snapshot());
+    assert_eq!(
+        storage
+            .contract_asset_id_balance(&contract_id, &asset_id)
+            .unwrap(),
+        Some(100)
+    );
+    let mut restored_context = TestBuilder::new(2322u64);
+    TestBuilder::storage(&mut restored_context, storage);
+    let receipts = call_contract(&mut restored_context, contract_id);
+    assert_eq!(receipts.panic_reason(), None);
+    assert_eq!(logged_words(&receipts), vec![7]);
+}
+
+#[test]
+fn save_to__writes_the_same_bytes_for_the_same_state() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    for _ in 0..3 {
+        test_context.setup_contract(read_then_write_contract(), None, None);
+    }
+    let storage = test_context.get_storage();
+    let restored = MemoryStorage::from_snapshot(storage.snapshot());
+    let first_path = snapshot_path("first");
+    let second_path = snapshot_path("second");
+
+    // When
+    storage.save_to(&first_path).unwrap();
+    restored.save_to(&second_path).unwrap();
+
+    // Then
+    let first = std::fs::read(&first_path).unwrap();
+    let second = std::fs::read(&second_path).unwrap();
+    std::fs::remove_file(&first_path).unwrap();
+    std::fs::remove_file(&second_path).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn load_from__fails_for_a_corrupted_snapshot() {
+    // Given
+    let path = snapshot_path("corrupted");
+    std::fs::write(&path, [0xff; 4]).unwrap();
+
+    // When
+    let result = MemoryStorage::load_from(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    // Then
+    let error = result.expect_err("The snapshot is corrupted");
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn from_snapshot__sets_the_versions_and_the_block_height() {
+    // Given
+    let storage =
+        MemoryStorage::new_with_versions(5.into(), ContractId::new([1; 32]), 2, 3);
+    let StorageSnapshot::V1(snapshot) = storage.snapshot();
+
+    // When
+    let restored = MemoryStorage::from_snapshot(StorageSnapshot::V1(snapshot.clone()));
+
+    // Then
+    assert_eq!(snapshot.block_height, 5.into());
+    assert_eq!(snapshot.coinbase, ContractId::new([1; 32]));
+    assert_eq!(restored.block_height().unwrap(), 5.into());
+    assert_eq!(restored.consensus_parameters_version().unwrap(), 2);
+    assert_eq!(restored.state_transition_version().unwrap(), 3);
+}
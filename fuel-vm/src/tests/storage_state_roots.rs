@@ -0,0 +1,141 @@
+#![allow(non_snake_case)]
+
+use crate::{
+    prelude::*,
+    storage::ContractsState,
+};
+use alloc::vec::Vec;
+use fuel_storage::{
+    StorageAsMut,
+    StorageAsRef,
+};
+use fuel_tx::{
+    Contract,
+    StorageSlot,
+};
+use quickcheck_macros::quickcheck;
+
+const CONTRACTS: [ContractId; 2] = [ContractId::new([1; 32]), ContractId::new([2; 32])];
+
+fn slot_key(key: u8) -> Bytes32 {
+    let mut slot = [0; 32];
+    slot[31] = key;
+    slot.into()
+}
+
+/// The root of the state of the `contract` computed from scratch.
+fn full_root(storage: &MemoryStorage, contract: &ContractId) -> Bytes32 {
+    let slots = storage
+        .contract_state_entries(contract)
+        .map(|(key, value)| {
+            StorageSlot::new(key, Bytes32::try_from(value.as_ref().as_ref()).unwrap())
+        })
+        .collect::<Vec<_>>();
+    Contract::initial_state_root(slots.iter())
+}
+
+fn incremental_root(storage: &MemoryStorage, contract: &ContractId) -> Bytes32 {
+    storage
+        .storage::<ContractsState>()
+        .root(contract)
+        .unwrap()
+        .into()
+}
+
+/// Applies the operation encoded by the `op` to the `storage`.
+fn apply(storage: &mut MemoryStorage, (kind, contract, key, value): (u8, bool, u8, u8)) {
+    let contract = &CONTRACTS[usize::from(contract)];
+    let key = slot_key(key % 16);
+    match kind % 8 {
+        0 | 1 => {
+            storage
+                .storage_as_mut::<ContractsState>()
+                .insert(&(contract, &key).into(), &[value; 32])
+                .unwrap();
+        }
+        2 => {
+            storage
+                .storage_as_mut::<ContractsState>()
+                .remove(&(contract, &key).into())
+                .unwrap();
+        }
+        3 => {
+            storage
+                .contract_state_remove_range(contract, &key, usize::from(value % 4))
+                .unwrap();
+        }
+        4 => {
+            let values = [[value; 32]; 3];
+            storage
+                .contract_state_insert_range(
+                    contract,
+                    &key,
+                    values.iter().map(|value| value.as_slice()),
+                )
+                .unwrap();
+        }
+        5 => storage.begin(),
+        6 => storage.commit(),
+        _ => storage.revert(),
+    }
+}
+
+#[quickcheck]
+fn root__matches_the_full_recomputation_after_random_writes_and_deletes(
+    ops: Vec<(u8, bool, u8, u8)>,
+) -> bool {
+    let mut storage = MemoryStorage::default();
+    ops.into_iter().enumerate().all(|(i, op)| {
+        apply(&mut storage, op);
+        // Queries the roots only every few operations, so several changes are
+        // applied to the cached trees at once.
+        i % 3 != 0
+            || CONTRACTS.iter().all(|contract| {
+                incremental_root(&storage, contract) == full_root(&storage, contract)
+            })
+    }) && CONTRACTS.iter().all(|contract| {
+        incremental_root(&storage, contract) == full_root(&storage, contract)
+    })
+}
+
+#[quickcheck]
+fn root__matches_the_full_recomputation_after_a_rollback_or_a_clone(
+    ops: Vec<(u8, bool, u8, u8)>,
+    rollback: bool,
+) -> bool {
+    let mut storage = MemoryStorage::default();
+    let (before, after) = ops.split_at(ops.len() / 2);
+    before.iter().for_each(|op| apply(&mut storage, *op));
+    storage.persist();
+    CONTRACTS.iter().for_each(|contract| {
+        incremental_root(&storage, contract);
+    });
+    after.iter().for_each(|op| apply(&mut storage, *op));
+    if rollback {
+        storage.rollback();
+    }
+    let clone = storage.clone();
+
+    CONTRACTS.iter().all(|contract| {
+        let root = full_root(&storage, contract);
+        incremental_root(&storage, contract) == root
+            && incremental_root(&clone, contract) == root
+    })
+}
+
+#[test]
+fn root__of_a_contract_without_state_is_the_empty_root() {
+    // Given
+    let mut storage = MemoryStorage::default();
+    apply(&mut storage, (0, false, 1, 1));
+    incremental_root(&storage, &CONTRACTS[0]);
+
+    // When
+    apply(&mut storage, (2, false, 1, 0));
+
+    // Then
+    assert_eq!(
+        incremental_root(&storage, &CONTRACTS[0]),
+        Contract::default_state_root()
+    );
+}
@@ -0,0 +1,260 @@
+#![cfg(feature = "structured-logs")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use std::{
+    io,
+    sync::Mutex,
+};
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    receipt_sink::{
+        LogField,
+        StructuredLogSink,
+    },
+};
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::BlockHeight;
+use serde_json::{
+    json,
+    Value,
+};
+
+const BLOCK_HEIGHT: u32 = 7;
+
+/// Logs `42`, logs the first 32 bytes of the memory, the transaction id, and panics
+/// dividing by zero.
+fn logging_and_panicking() -> Checked<Script> {
+    TestBuilder::new(2322u64)
+        .start_script(
+            vec![
+                op::movi(0x10, 42),
+                op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::movi(0x11, 32),
+                op::logd(RegId::ZERO, RegId::ZERO, RegId::ZERO, 0x11),
+                op::div(0x10, 0x10, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build()
+}
+
+fn transactor() -> Transactor<MemoryStorage, Script> {
+    Transactor::new(
+        MemoryStorage::new(BlockHeight::new(BLOCK_HEIGHT), Default::default()),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+}
+
+/// A writer shared with the test.
+#[derive(Debug, Default, Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn lines(&self) -> Vec<Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A writer failing the first `failures` writes, like a full pipe.
+#[derive(Debug)]
+struct Congested {
+    failures: usize,
+    written: Vec<u8>,
+}
+
+impl io::Write for Congested {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(io::ErrorKind::WouldBlock.into())
+        }
+        self.written.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn structured_log_sink__writes_the_logs_and_the_panic() {
+    // Given
+    let tx = logging_and_panicking();
+    let tx_id = tx.id().to_string();
+    let buffer = SharedBuffer::default();
+    let mut transactor = transactor();
+    transactor.with_receipt_sink(StructuredLogSink::new(buffer.clone()));
+
+    // When
+    transactor.transact(tx);
+
+    // Then
+    let receipts = transactor.receipts().unwrap();
+    let pc = |index: usize| receipts[index].pc().unwrap();
+    let is = receipts[0].is().unwrap();
+    let digest = receipts[1].digest().unwrap().to_string();
+    assert_eq!(
+        buffer.lines(),
+        vec![
+            json!({
+                "tx_id": tx_id,
+                "block_height": BLOCK_HEIGHT,
+                "receipt": "log",
+                "id": null,
+                "ra": 42,
+                "rb": 0,
+                "rc": 0,
+                "rd": 0,
+                "pc": pc(0),
+                "is": is,
+            }),
+            json!({
+                "tx_id": tx_id,
+                "block_height": BLOCK_HEIGHT,
+                "receipt": "log_data",
+                "id": null,
+                "ra": 0,
+                "rb": 0,
+                "ptr": 0,
+                "len": 32,
+                "digest": digest,
+                "data": tx_id,
+                "truncated": false,
+                "pc": pc(1),
+                "is": is,
+            }),
+            json!({
+                "tx_id": tx_id,
+                "block_height": BLOCK_HEIGHT,
+                "receipt": "panic",
+                "id": null,
+                "reason": "ArithmeticError",
+                "contract_id": null,
+                "pc": pc(2),
+                "is": is,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn structured_log_sink__maps_the_fields_and_truncates_the_data() {
+    // Given
+    let tx = logging_and_panicking();
+    let tx_id = tx.id().to_string();
+    let buffer = SharedBuffer::default();
+    let mut transactor = transactor();
+    transactor.with_receipt_sink(
+        StructuredLogSink::new(buffer.clone())
+            .rename(LogField::TxId, "transaction")
+            .rename(LogField::Receipt, "kind")
+            .omit(LogField::BlockHeight)
+            .omit(LogField::Pc)
+            .omit(LogField::Is)
+            .max_data_len(4),
+    );
+
+    // When
+    transactor.transact(tx);
+
+    // Then
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 3);
+    assert!(lines
+        .iter()
+        .all(|line| line["transaction"] == tx_id.as_str()
+            && line.get("tx_id").is_none()
+            && line.get("block_height").is_none()
+            && line.get("pc").is_none()));
+    assert_eq!(lines[1]["kind"], "log_data");
+    assert_eq!(lines[1]["len"], 32);
+    assert_eq!(lines[1]["data"], tx_id.get(..8).expect("The id is in hex"));
+    assert_eq!(lines[1]["truncated"], true);
+}
+
+#[test]
+fn structured_log_sink__drops_and_counts_the_lines_failing_to_write() {
+    // Given
+    let sink = Arc::new(StructuredLogSink::new(Congested {
+        failures: 2,
+        written: Vec::new(),
+    }));
+    let mut transactor = transactor();
+    transactor.with_receipt_sink(sink.clone());
+
+    // When
+    transactor.transact(logging_and_panicking());
+
+    // Then
+    assert!(transactor.error().is_none());
+    assert_eq!(transactor.receipts().unwrap().len(), 4);
+    assert_eq!(sink.dropped(), 2);
+    drop(transactor);
+    let written = Arc::into_inner(sink).unwrap().into_writer().written;
+    let lines = String::from_utf8(written).unwrap();
+    let line: Value = serde_json::from_str(lines.trim_end()).unwrap();
+    assert_eq!(line["receipt"], "panic");
+}
+
+#[test]
+fn structured_log_sink__writes_the_receipts_before_the_end_of_the_execution() {
+    // Given
+    let buffer = SharedBuffer::default();
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        MemoryStorage::new(BlockHeight::new(BLOCK_HEIGHT), Default::default()),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    );
+    vm.with_receipt_sink(Arc::new(StructuredLogSink::new(buffer.clone())));
+    vm.set_breakpoint(Breakpoint::script(2));
+    let consensus_params = ConsensusParameters::standard();
+    let ready = logging_and_panicking()
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+
+    // When
+    let state = vm.transact(ready).map(ProgramState::from).unwrap();
+
+    // Then
+    assert_eq!(state, Breakpoint::script(2));
+    let lines = buffer.lines();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["ra"], 42);
+    assert_eq!(lines[0]["block_height"], BLOCK_HEIGHT);
+}
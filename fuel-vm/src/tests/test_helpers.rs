@@ -1,16 +1,36 @@
 use alloc::{
+    collections::BTreeMap,
     vec,
     vec::Vec,
 };
+use core::convert::Infallible;
 
 use fuel_asm::{
     op,
     Instruction,
+    RegId,
 };
 use fuel_crypto::SecretKey;
 use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
 use fuel_vm::prelude::*;
 
+use crate::{
+    checked_transaction::Checked,
+    script_with_data_offset,
+    storage::{
+        Column,
+        KeyValueStore,
+        KvBatch,
+        KvEntries,
+        KvTransaction,
+        PersistentStorage,
+        PersistentStore,
+        RedbStore,
+    },
+    util::test_helpers::TestBuilder,
+};
+
 /// Set a register `r` to a Word-sized number value using left-shifts
 pub fn set_full_word(r: RegisterId, v: Word) -> Vec<Instruction> {
     let r = u8::try_from(r).unwrap();
@@ -87,3 +107,113 @@ pub fn assert_panics(receipts: &[Receipt], reason: PanicReason) {
         unreachable!("No script receipt for a paniced tx");
     }
 }
+
+/// Logs the word stored under the key `[1, 0, ..., 0]` and then stores `7` under it.
+pub fn read_then_write_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 1),
+        op::sb(RegId::HP, 0x11, 0),
+        op::srw(0x12, 0x13, RegId::HP),
+        op::log(0x12, 0x13, RegId::ZERO, RegId::ZERO),
+        op::movi(0x14, 7),
+        op::sww(RegId::HP, 0x15, 0x14),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Builds a script calling the `contract_id` with all of its gas.
+pub fn call_contract_script(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+) -> Checked<Script> {
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+}
+
+/// Deploys the [`read_then_write_contract`] and builds a script calling it.
+pub fn deploy_read_then_write_contract() -> (TestBuilder, ContractId, Checked<Script>) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(read_then_write_contract(), None, None)
+        .contract_id;
+    let tx = call_contract_script(&mut test_context, contract_id);
+    (test_context, contract_id, tx)
+}
+
+/// Key-value store keeping the columns in one map.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BTreeMapStore(BTreeMap<(Column, Vec<u8>), Vec<u8>>);
+
+impl KeyValueStore for BTreeMapStore {
+    type Error = Infallible;
+
+    fn get(&self, column: Column, key: &[u8]) -> Result<Option<Vec<u8>>, Infallible> {
+        Ok(self.0.get(&(column, key.to_vec())).cloned())
+    }
+
+    fn put(
+        &mut self,
+        column: Column,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), Infallible> {
+        self.0.insert((column, key.to_vec()), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&mut self, column: Column, key: &[u8]) -> Result<(), Infallible> {
+        self.0.remove(&(column, key.to_vec()));
+        Ok(())
+    }
+}
+
+impl PersistentStore for BTreeMapStore {
+    fn write_batch(&mut self, batch: KvBatch) -> Result<(), Infallible> {
+        for (key, value) in batch {
+            match value {
+                Some(value) => self.0.insert(key, value),
+                None => self.0.remove(&key),
+            };
+        }
+        Ok(())
+    }
+
+    fn entries(&self, column: Column, prefix: &[u8]) -> Result<KvEntries, Infallible> {
+        Ok(self
+            .0
+            .range((column, prefix.to_vec())..)
+            .take_while(|((entry_column, key), _)| {
+                *entry_column == column && key.starts_with(prefix)
+            })
+            .map(|((_, key), value)| (key.clone(), value.clone()))
+            .collect())
+    }
+}
+
+/// The persistent storage over a new [`BTreeMapStore`].
+pub fn kv_transaction_storage() -> PersistentStorage<BTreeMapStore> {
+    PersistentStorage::default()
+}
+
+/// The persistent storage over a new `redb` database kept in memory.
+pub fn redb_storage() -> PersistentStorage<RedbStore> {
+    let store = RedbStore::in_memory().unwrap();
+    PersistentStorage::new(KvTransaction::new(store))
+}
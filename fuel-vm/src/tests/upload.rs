@@ -8,6 +8,7 @@ use fuel_asm::{
     op,
     PanicReason,
 };
+use fuel_merkle::binary::root_calculator::MerkleRootCalculator;
 use fuel_tx::{
     field::Outputs,
     policies::Policies,
@@ -19,7 +20,10 @@ use fuel_tx::{
     UploadSubsection,
     ValidityError,
 };
-use fuel_types::AssetId;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+};
 
 use crate::{
     checked_transaction::Ready,
@@ -132,6 +136,42 @@ fn transact__uploads_bytecode_with_several_subsections() {
     );
 }
 
+#[test]
+fn transact__uploads_bytecode_with_three_subsections_under_merkle_root_of_subsections() {
+    let mut client = Interpreter::<_, Upload>::with_memory_storage();
+
+    // Given
+    let bytecode: Vec<u8> = (0..BYTECODE_SIZE).map(|i| i as u8).collect();
+    let subsection_size = BYTECODE_SIZE.div_ceil(3);
+    let subsections =
+        UploadSubsection::split_bytecode(&bytecode, subsection_size).unwrap();
+    assert_eq!(subsections.len(), 3);
+    let mut calculator = MerkleRootCalculator::new();
+    bytecode
+        .chunks(subsection_size)
+        .for_each(|chunk| calculator.push(chunk));
+    let root: Bytes32 = calculator.root().into();
+
+    // When
+    for subsection in subsections {
+        assert_eq!(subsection.root, root);
+        let tx = valid_transaction_from_subsection(subsection);
+        let _ = client.transact(tx).expect("Failed to transact");
+    }
+
+    // Then
+    assert_eq!(
+        client
+            .as_ref()
+            .storage_as_ref::<UploadedBytecodes>()
+            .get(&root)
+            .unwrap()
+            .unwrap()
+            .into_owned(),
+        UploadedBytecode::Completed(bytecode)
+    );
+}
+
 #[test]
 fn transact__uploads_bytecode_with_half_of_subsections() {
     let mut client = Interpreter::<_, Upload>::with_memory_storage();
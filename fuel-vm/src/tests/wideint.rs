@@ -20,7 +20,10 @@ use fuel_asm::{
     PanicReason,
     RegId,
 };
-use fuel_tx::Receipt;
+use fuel_tx::{
+    Receipt,
+    ReceiptsExt,
+};
 
 use super::test_helpers::{
     assert_panics,
@@ -233,11 +236,10 @@ fn incr_overflow_u128() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticOverflow);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::ArithmeticOverflow)
+    );
 }
 
 #[test]
@@ -260,11 +262,10 @@ fn incr_overflow_u256() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticOverflow);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::ArithmeticOverflow)
+    );
 }
 
 #[test]
@@ -349,11 +350,10 @@ fn multiply_overflow_u128() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticOverflow);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::ArithmeticOverflow)
+    );
 }
 
 #[test]
@@ -374,11 +374,10 @@ fn multiply_overflow_u256() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticOverflow);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(
+        receipts.panic_reason(),
+        Some(PanicReason::ArithmeticOverflow)
+    );
 }
 
 #[test]
@@ -722,11 +721,7 @@ fn divide_by_zero_u128() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticError);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(receipts.panic_reason(), Some(PanicReason::ArithmeticError));
 }
 
 #[test]
@@ -744,11 +739,7 @@ fn divide_by_zero_u256() {
 
     let receipts = run_script(ops);
 
-    if let Receipt::Panic { reason, .. } = receipts.first().unwrap() {
-        assert_eq!(*reason.reason(), PanicReason::ArithmeticError);
-    } else {
-        panic!("Expected panic receipt");
-    }
+    assert_eq!(receipts.panic_reason(), Some(PanicReason::ArithmeticError));
 }
 
 #[test]
@@ -3,7 +3,9 @@
 use crate::{
     backtrace::Backtrace,
     checked_transaction::{
+        CheckError,
         Checked,
+        Checks,
         IntoChecked,
     },
     error::InterpreterError,
@@ -11,14 +13,19 @@ use crate::{
         CheckedMetadata,
         EcalHandler,
         ExecutableTransaction,
+        FrameGas,
         Interpreter,
     },
+    receipt_sink::ReceiptSink,
     state::{
         ProgramState,
         StateTransition,
         StateTransitionRef,
     },
-    storage::InterpreterStorage,
+    storage::{
+        InterpreterStorage,
+        OverlayStorage,
+    },
 };
 
 use crate::{
@@ -28,15 +35,25 @@ use crate::{
         NotSupportedEcal,
     },
 };
+use alloc::{
+    sync::Arc,
+    vec::Vec,
+};
 use fuel_tx::{
+    field::ScriptGasLimit,
+    Cacheable,
+    Chargeable,
     Create,
     FeeParameters,
     GasCosts,
+    Mint,
     Receipt,
     Script,
+    ScriptExecutionResult,
     Upgrade,
     Upload,
 };
+use fuel_types::Word;
 
 #[derive(Debug)]
 /// State machine to execute transactions and provide runtime entities on
@@ -53,6 +70,7 @@ where
     interpreter: Interpreter<S, Tx, Ecal>,
     program_state: Option<ProgramState>,
     error: Option<InterpreterError<S::DataError>>,
+    required_checks: Checks,
 }
 
 impl<S, Tx, Ecal> Transactor<S, Tx, Ecal>
@@ -70,9 +88,63 @@ where
             ),
             program_state: None,
             error: None,
+            required_checks: Checks::Basic,
         }
     }
 }
+
+impl<S, Tx, Ecal> Transactor<S, Tx, Ecal>
+where
+    S: InterpreterStorage,
+{
+    /// The checks the transactions must pass to be executed.
+    pub fn required_checks(&self) -> &Checks {
+        &self.required_checks
+    }
+
+    /// Sets the checks the transactions must pass to be executed. Transactions without
+    /// them are rejected with [`CheckError::InsufficientChecks`]. By default, only
+    /// [`Checks::Basic`] is required. [`Checks::Fees`] is always required, unless the
+    /// interpreter runs with [`InterpreterParams::free_gas`].
+    pub fn set_required_checks(&mut self, checks: Checks) -> &mut Self {
+        self.required_checks = checks;
+        self
+    }
+
+    /// Notifies the `sink` of each receipt of the executed transactions as soon as
+    /// it is pushed, see [`Interpreter::with_receipt_sink`].
+    pub fn with_receipt_sink<R>(&mut self, sink: R) -> &mut Self
+    where
+        R: ReceiptSink + 'static,
+    {
+        self.interpreter.with_receipt_sink(Arc::new(sink));
+        self
+    }
+
+    /// Fails if the `performed` checks don't contain the required ones, including the
+    /// fees outside of the zero-gas execution.
+    fn ensure_required_checks(
+        &self,
+        performed: &Checks,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        let mut required = self.required_checks;
+        if !self.interpreter.interpreter_params().free_gas {
+            required |= Checks::Fees;
+        }
+
+        if performed.contains(required) {
+            Ok(())
+        } else {
+            Err(InterpreterError::CheckError(
+                CheckError::InsufficientChecks {
+                    required,
+                    performed: *performed,
+                },
+            ))
+        }
+    }
+}
+
 impl<'a, S, Tx, Ecal> Transactor<S, Tx, Ecal>
 where
     S: InterpreterStorage,
@@ -196,6 +268,145 @@ where
             .copied()
             .map(|result| Backtrace::from_vm_error(&self.interpreter, result))
     }
+
+    /// The gas consumed by the script of the last transaction and by each of its call
+    /// frames, see [`Interpreter::frame_gas_report`].
+    pub fn frame_gas_report(&self) -> Vec<FrameGas> {
+        self.interpreter.frame_gas_report()
+    }
+}
+
+/// The maximal number of executions of [`Transactor::estimate_gas_limit`].
+const GAS_ESTIMATE_MAX_ITERATIONS: usize = 32;
+
+/// The gas limit found by [`Transactor::estimate_gas_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GasEstimate {
+    /// The lowest gas limit found with which the script succeeds.
+    pub gas_limit: Word,
+    /// The gas used by the script with the `gas_limit`.
+    pub gas_used: Word,
+    /// The number of executions of the script.
+    pub iterations: usize,
+    /// Whether the search stopped after the maximal number of executions before
+    /// reaching the tolerance, e.g. because the success of the script isn't monotonic
+    /// in its gas limit. The `gas_limit` still succeeds but may not be the lowest.
+    pub ambiguous: bool,
+}
+
+impl<S, Ecal> Transactor<S, Script, Ecal>
+where
+    S: InterpreterStorage,
+    Ecal: EcalHandler,
+{
+    /// Estimates the lowest `script_gas_limit` of the `template` with which its script
+    /// succeeds, within the `tolerance`, returning `None` if it doesn't succeed with
+    /// the gas limit of the `template`.
+    ///
+    /// The script is executed with the gas limits found by a binary search below the
+    /// limit of the `template`, over an [`OverlayStorage`] of the storage of the
+    /// transactor, so the storage isn't modified. It differs from the gas used by a
+    /// single execution for the scripts depending on their gas, e.g. reading `$ggas`.
+    ///
+    /// The checks of the `template` are kept for the other gas limits without being
+    /// performed again, though the signatures commit to the gas limit.
+    pub fn estimate_gas_limit(
+        &self,
+        template: &Checked<Script>,
+        tolerance: Word,
+    ) -> Result<Option<GasEstimate>, InterpreterError<S::DataError>> {
+        let mut high = *template.transaction().script_gas_limit();
+        let Some(mut gas_used) = self.dry_run(template, high)? else {
+            return Ok(None);
+        };
+        // The highest gas limit known to fail.
+        let mut low: Option<Word> = None;
+        let mut iterations = 1;
+
+        let converged = |low: Option<Word>, high: Word| match low {
+            Some(low) => high - low <= tolerance.max(1),
+            None => high == 0,
+        };
+        while !converged(low, high) {
+            if iterations == GAS_ESTIMATE_MAX_ITERATIONS {
+                return Ok(Some(GasEstimate {
+                    gas_limit: high,
+                    gas_used,
+                    iterations,
+                    ambiguous: true,
+                }));
+            }
+            // The gas used is the best guess, unless it is above the middle.
+            let floor = low.unwrap_or(0);
+            let middle = floor + (high - floor) / 2;
+            let limit = if !matches!(low, Some(low) if gas_used <= low) {
+                gas_used.min(middle)
+            } else {
+                middle
+            };
+            iterations += 1;
+            match self.dry_run(template, limit)? {
+                Some(used) => {
+                    high = limit;
+                    gas_used = used;
+                }
+                None => low = Some(limit),
+            }
+        }
+
+        Ok(Some(GasEstimate {
+            gas_limit: high,
+            gas_used,
+            iterations,
+            ambiguous: false,
+        }))
+    }
+
+    /// Executes the `template` with the `gas_limit` over an overlay of the storage,
+    /// returning the gas used if the script succeeds.
+    fn dry_run(
+        &self,
+        template: &Checked<Script>,
+        gas_limit: Word,
+    ) -> Result<Option<Word>, InterpreterError<S::DataError>> {
+        let params = self.interpreter.interpreter_params();
+        let (mut tx, mut metadata, checks) = template.clone().into_parts();
+        *tx.script_gas_limit_mut() = gas_limit;
+        tx.precompute(&params.chain_id)
+            .map_err(|error| InterpreterError::CheckError(error.into()))?;
+        metadata.min_gas = tx.min_gas(&params.gas_costs, &params.fee_params);
+        metadata.max_gas = tx.max_gas(&params.gas_costs, &params.fee_params);
+        let checked = Checked::from_parts_unchecked(tx, metadata, checks);
+
+        let interpreter = Interpreter::<_, Script, _>::with_storage_and_ecal(
+            OverlayStorage::new(self.interpreter.as_ref()),
+            params.clone(),
+            self.interpreter.ecal_state().clone(),
+        );
+        let mut transactor = Transactor::from(interpreter);
+        transactor.set_required_checks(self.required_checks);
+        transactor.transact(checked);
+        if let Some(error) = transactor.error.take() {
+            return match error {
+                // The smaller gas limit is rejected like a failed script.
+                InterpreterError::CheckError(_) => Ok(None),
+                error => Err(error),
+            };
+        }
+
+        let result = transactor
+            .interpreter
+            .receipts()
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { result, gas_used } => Some((*result, *gas_used)),
+                _ => None,
+            });
+        Ok(match result {
+            Some((ScriptExecutionResult::Success, gas_used)) => Some(gas_used),
+            _ => None,
+        })
+    }
 }
 
 impl<S, Tx, Ecal> Transactor<S, Tx, Ecal>
@@ -223,9 +434,19 @@ where
         &mut self,
         ready_tx: Ready<Create>,
     ) -> Result<Create, InterpreterError<S::DataError>> {
+        self.ensure_required_checks(ready_tx.checks())?;
         self.interpreter.deploy(ready_tx)
     }
 
+    /// Executes `Mint` checked transactions.
+    pub fn mint(
+        &mut self,
+        checked: Checked<Mint>,
+    ) -> Result<Mint, InterpreterError<S::DataError>> {
+        self.ensure_required_checks(checked.checks())?;
+        self.interpreter.execute_mint(checked)
+    }
+
     /// Executes `Upgrade` checked transactions.
     pub fn upgrade(
         &mut self,
@@ -247,6 +468,7 @@ where
         &mut self,
         ready_tx: Ready<Upgrade>,
     ) -> Result<Upgrade, InterpreterError<S::DataError>> {
+        self.ensure_required_checks(ready_tx.checks())?;
         self.interpreter.upgrade(ready_tx)
     }
 
@@ -271,6 +493,7 @@ where
         &mut self,
         ready_tx: Ready<Upload>,
     ) -> Result<Upload, InterpreterError<S::DataError>> {
+        self.ensure_required_checks(ready_tx.checks())?;
         self.interpreter.upload(ready_tx)
     }
 }
@@ -299,6 +522,10 @@ where
 
     /// Transact a `Ready` transaction directly instead of letting `Transactor` construct
     pub fn transact_ready_tx(&mut self, ready_tx: Ready<Tx>) -> &mut Self {
+        if let Err(e) = self.ensure_required_checks(ready_tx.checks()) {
+            return self.handle_error(e);
+        }
+
         match self.interpreter.transact(ready_tx) {
             Ok(s) => {
                 self.program_state.replace(s.into());
@@ -325,11 +552,13 @@ where
     fn from(interpreter: Interpreter<S, Tx, Ecal>) -> Self {
         let program_state = None;
         let error = None;
+        let required_checks = Checks::Basic;
 
         Self {
             interpreter,
             program_state,
             error,
+            required_checks,
         }
     }
 }
@@ -78,11 +78,40 @@ macro_rules! script_with_data_offset {
     }};
 }
 
+#[cfg(feature = "random")]
+#[cfg(any(test, feature = "test-helpers"))]
+mod execution_snapshot;
+#[cfg(feature = "random")]
+#[cfg(any(test, feature = "test-helpers"))]
+mod expect_receipts;
+#[cfg(feature = "random")]
+#[cfg(any(test, feature = "test-helpers"))]
+mod faulty_storage;
+
 #[allow(missing_docs)]
 #[cfg(feature = "random")]
 #[cfg(any(test, feature = "test-helpers"))]
 /// Testing utilities
 pub mod test_helpers {
+    pub use super::{
+        execution_snapshot::{
+            ExecutionSnapshot,
+            Redaction,
+        },
+        expect_receipts::{
+            receipt_kind,
+            ExpectedReceipt,
+            ExpectedReceipts,
+            ReceiptPattern,
+            ReceiptsMismatch,
+        },
+        faulty_storage::{
+            Fault,
+            FaultyStorage,
+            FaultyStorageError,
+        },
+    };
+    pub use crate::expect_receipts;
     use alloc::{
         vec,
         vec::Vec,
@@ -97,11 +126,16 @@ pub mod test_helpers {
         memory_client::MemoryClient,
         state::StateTransition,
         storage::{
-            ContractsAssetsStorage,
+            InterpreterStorage,
             MemoryStorage,
         },
         transactor::Transactor,
     };
+    #[cfg(feature = "serde")]
+    use crate::storage::{
+        KeyValueStore,
+        KvStoreAdapter,
+    };
     use anyhow::anyhow;
 
     use crate::{
@@ -173,19 +207,104 @@ pub mod test_helpers {
         pub salt: Salt,
     }
 
-    pub struct TestBuilder {
+    /// Storage the [`TestBuilder`] can execute the transactions over. It is cloned
+    /// before each execution, and the clone of a reverted one is dropped.
+    pub trait TestStorage: InterpreterStorage + Clone {
+        /// Set the current block height.
+        fn set_block_height(&mut self, block_height: BlockHeight);
+    }
+
+    impl TestStorage for MemoryStorage {
+        fn set_block_height(&mut self, block_height: BlockHeight) {
+            MemoryStorage::set_block_height(self, block_height)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<K> TestStorage for KvStoreAdapter<K>
+    where
+        K: KeyValueStore + Clone,
+        K::Error: core::fmt::Debug,
+    {
+        fn set_block_height(&mut self, block_height: BlockHeight) {
+            KvStoreAdapter::set_block_height(self, block_height)
+                .expect("failed to set the block height");
+        }
+    }
+
+    pub struct TestBuilder<S = MemoryStorage> {
         rng: StdRng,
         gas_price: Word,
         max_fee_limit: Word,
         script_gas_limit: Word,
         builder: TransactionBuilder<Script>,
-        storage: MemoryStorage,
+        storage: S,
         block_height: BlockHeight,
         consensus_params: ConsensusParameters,
     }
 
     impl TestBuilder {
         pub fn new(seed: u64) -> Self {
+            Self::with_storage(seed, MemoryStorage::default())
+        }
+
+        pub fn build_get_balance_tx(
+            contract_id: &ContractId,
+            asset_id: &AssetId,
+            tx_offset: usize,
+        ) -> Checked<Script> {
+            let (script, _) = script_with_data_offset!(
+                data_offset,
+                vec![
+                    op::movi(0x11, data_offset),
+                    op::addi(
+                        0x12,
+                        0x11,
+                        Immediate12::try_from(AssetId::LEN)
+                            .expect("`AssetId::LEN` is 32 bytes")
+                    ),
+                    op::bal(0x10, 0x11, 0x12),
+                    op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                    op::ret(RegId::ONE),
+                ],
+                tx_offset
+            );
+
+            let script_data: Vec<u8> = [asset_id.as_ref(), contract_id.as_ref()]
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect();
+
+            TestBuilder::new(2322u64)
+                .start_script(script, script_data)
+                .gas_price(0)
+                .script_gas_limit(1_000_000)
+                .contract_input(*contract_id)
+                .fee_input()
+                .contract_output(contract_id)
+                .build()
+        }
+
+        /// Build test tx, execute it and capture its end state, with the storage
+        /// changes of the execution
+        pub fn execute_snapshot<'a>(&mut self) -> ExecutionSnapshot<'a> {
+            let tx = self.build();
+            let baseline = self.storage.clone();
+            let interpreter_params =
+                InterpreterParams::new(self.gas_price, &self.consensus_params);
+            let mut transactor =
+                Transactor::<_, _>::new(self.storage.clone(), interpreter_params);
+
+            self.execute_tx_inner(&mut transactor, tx)
+                .expect("expected successful vm execution");
+            ExecutionSnapshot::capture(&transactor).since(&baseline)
+        }
+    }
+
+    impl<S: TestStorage> TestBuilder<S> {
+        /// Create a new builder executing the transactions over the `storage`.
+        pub fn with_storage(seed: u64, storage: S) -> Self {
             let bytecode = core::iter::once(op::ret(RegId::ONE)).collect();
             TestBuilder {
                 rng: StdRng::seed_from_u64(seed),
@@ -193,7 +312,7 @@ pub mod test_helpers {
                 max_fee_limit: 0,
                 script_gas_limit: 100,
                 builder: TransactionBuilder::script(bytecode, vec![]),
-                storage: MemoryStorage::default(),
+                storage,
                 block_height: Default::default(),
                 consensus_params: ConsensusParameters::standard(),
             }
@@ -214,45 +333,41 @@ pub mod test_helpers {
             self
         }
 
-        pub fn gas_price(&mut self, price: Word) -> &mut TestBuilder {
+        pub fn gas_price(&mut self, price: Word) -> &mut Self {
             self.gas_price = price;
             self
         }
 
-        pub fn max_fee_limit(&mut self, max_fee_limit: Word) -> &mut TestBuilder {
+        pub fn max_fee_limit(&mut self, max_fee_limit: Word) -> &mut Self {
             self.max_fee_limit = max_fee_limit;
             self
         }
 
-        pub fn script_gas_limit(&mut self, limit: Word) -> &mut TestBuilder {
+        pub fn script_gas_limit(&mut self, limit: Word) -> &mut Self {
             self.builder.script_gas_limit(limit);
             self.script_gas_limit = limit;
             self
         }
 
-        pub fn change_output(&mut self, asset_id: AssetId) -> &mut TestBuilder {
+        pub fn change_output(&mut self, asset_id: AssetId) -> &mut Self {
             self.builder
                 .add_output(Output::change(self.rng.gen(), 0, asset_id));
             self
         }
 
-        pub fn coin_output(
-            &mut self,
-            asset_id: AssetId,
-            amount: Word,
-        ) -> &mut TestBuilder {
+        pub fn coin_output(&mut self, asset_id: AssetId, amount: Word) -> &mut Self {
             self.builder
                 .add_output(Output::coin(self.rng.gen(), amount, asset_id));
             self
         }
 
-        pub fn variable_output(&mut self, asset_id: AssetId) -> &mut TestBuilder {
+        pub fn variable_output(&mut self, asset_id: AssetId) -> &mut Self {
             self.builder
                 .add_output(Output::variable(Address::zeroed(), 0, asset_id));
             self
         }
 
-        pub fn contract_output(&mut self, id: &ContractId) -> &mut TestBuilder {
+        pub fn contract_output(&mut self, id: &ContractId) -> &mut Self {
             let input_idx = self
                 .builder
                 .inputs()
@@ -269,11 +384,7 @@ pub mod test_helpers {
             self
         }
 
-        pub fn coin_input(
-            &mut self,
-            asset_id: AssetId,
-            amount: Word,
-        ) -> &mut TestBuilder {
+        pub fn coin_input(&mut self, asset_id: AssetId, amount: Word) -> &mut Self {
             self.builder.add_unsigned_coin_input(
                 fuel_crypto::SecretKey::random(&mut self.rng),
                 self.rng.gen(),
@@ -284,12 +395,12 @@ pub mod test_helpers {
             self
         }
 
-        pub fn fee_input(&mut self) -> &mut TestBuilder {
+        pub fn fee_input(&mut self) -> &mut Self {
             self.builder.add_random_fee_input();
             self
         }
 
-        pub fn contract_input(&mut self, contract_id: ContractId) -> &mut TestBuilder {
+        pub fn contract_input(&mut self, contract_id: ContractId) -> &mut Self {
             self.builder.add_input(Input::contract(
                 self.rng.gen(),
                 self.rng.gen(),
@@ -300,27 +411,43 @@ pub mod test_helpers {
             self
         }
 
-        pub fn witness(&mut self, witness: Witness) -> &mut TestBuilder {
+        pub fn contract_input_read_only(&mut self, contract_id: ContractId) -> &mut Self {
+            self.builder.add_input(Input::contract_read_only(
+                self.rng.gen(),
+                self.rng.gen(),
+                self.rng.gen(),
+                self.rng.gen(),
+                contract_id,
+            ));
+            self
+        }
+
+        pub fn witness(&mut self, witness: Witness) -> &mut Self {
             self.builder.add_witness(witness);
             self
         }
 
-        pub fn storage(&mut self, storage: MemoryStorage) -> &mut TestBuilder {
+        pub fn storage(&mut self, storage: S) -> &mut Self {
             self.storage = storage;
             self
         }
 
-        pub fn block_height(&mut self, block_height: BlockHeight) -> &mut TestBuilder {
+        pub fn block_height(&mut self, block_height: BlockHeight) -> &mut Self {
             self.block_height = block_height;
             self
         }
 
-        pub fn with_fee_params(&mut self, fee_params: FeeParameters) -> &mut TestBuilder {
+        pub fn with_fee_params(&mut self, fee_params: FeeParameters) -> &mut Self {
             self.consensus_params.set_fee_params(fee_params);
             self
         }
 
-        pub fn base_asset_id(&mut self, base_asset_id: AssetId) -> &mut TestBuilder {
+        pub fn with_gas_costs(&mut self, gas_costs: GasCosts) -> &mut Self {
+            self.consensus_params.set_gas_costs(gas_costs);
+            self
+        }
+
+        pub fn base_asset_id(&mut self, base_asset_id: AssetId) -> &mut Self {
             self.consensus_params.set_base_asset_id(base_asset_id);
             self
         }
@@ -335,6 +462,7 @@ pub mod test_helpers {
             self.builder.with_script_params(*self.get_script_params());
             self.builder.with_fee_params(*self.get_fee_params());
             self.builder.with_base_asset_id(*self.get_base_asset_id());
+            self.builder.with_gas_costs(self.get_gas_costs().clone());
             self.builder.finalize_checked(self.block_height)
         }
 
@@ -378,44 +506,6 @@ pub mod test_helpers {
             self.consensus_params.gas_costs()
         }
 
-        pub fn build_get_balance_tx(
-            contract_id: &ContractId,
-            asset_id: &AssetId,
-            tx_offset: usize,
-        ) -> Checked<Script> {
-            let (script, _) = script_with_data_offset!(
-                data_offset,
-                vec![
-                    op::movi(0x11, data_offset),
-                    op::addi(
-                        0x12,
-                        0x11,
-                        Immediate12::try_from(AssetId::LEN)
-                            .expect("`AssetId::LEN` is 32 bytes")
-                    ),
-                    op::bal(0x10, 0x11, 0x12),
-                    op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
-                    op::ret(RegId::ONE),
-                ],
-                tx_offset
-            );
-
-            let script_data: Vec<u8> = [asset_id.as_ref(), contract_id.as_ref()]
-                .into_iter()
-                .flatten()
-                .copied()
-                .collect();
-
-            TestBuilder::new(2322u64)
-                .start_script(script, script_data)
-                .gas_price(0)
-                .script_gas_limit(1_000_000)
-                .contract_input(*contract_id)
-                .fee_input()
-                .contract_output(contract_id)
-                .build()
-        }
-
         pub fn setup_contract(
             &mut self,
             contract: Vec<Instruction>,
@@ -437,7 +527,7 @@ pub mod test_helpers {
             let storage_root = Contract::initial_state_root(storage_slots.iter());
             let contract = Contract::from(program.as_ref());
             let contract_root = contract.root();
-            let contract_id = contract.id(&salt, &contract_root, &storage_root);
+            let contract_id = Contract::id(&salt, &contract_root, &storage_root);
 
             let tx = TransactionBuilder::create(program, salt, storage_slots)
                 .max_fee_limit(self.max_fee_limit)
@@ -469,7 +559,7 @@ pub mod test_helpers {
 
         fn execute_tx_inner<Tx, Ecal>(
             &mut self,
-            transactor: &mut Transactor<MemoryStorage, Tx, Ecal>,
+            transactor: &mut Transactor<S, Tx, Ecal>,
             checked: Checked<Tx>,
         ) -> anyhow::Result<StateTransition<Tx>>
         where
@@ -565,7 +655,7 @@ pub mod test_helpers {
                 .expect("expected successful vm execution")
         }
 
-        pub fn get_storage(&self) -> &MemoryStorage {
+        pub fn get_storage(&self) -> &S {
             &self.storage
         }
 
@@ -628,8 +718,7 @@ pub mod test_helpers {
         let code_root = Contract::root_from_code(contract.as_ref());
         let storage_slots = vec![];
         let state_root = Contract::initial_state_root(storage_slots.iter());
-        let contract_id =
-            Contract::from(contract.as_ref()).id(&salt, &code_root, &state_root);
+        let contract_id = Contract::id(&salt, &code_root, &state_root);
 
         let contract_deployer = TransactionBuilder::create(contract, salt, storage_slots)
             .max_fee_limit(zero_fee_limit)
@@ -0,0 +1,242 @@
+//! Canonical text of the end state of an execution, for snapshot tests:
+//!
+//! ```rust,ignore
+//! let snapshot = ExecutionSnapshot::capture(&transactor)
+//!     .since(&storage_before)
+//!     .redact(tx_id, "[tx id]");
+//! assert_eq!(snapshot.to_string(), EXPECTED);
+//! ```
+
+use alloc::{
+    boxed::Box,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use core::fmt::{
+    self,
+    Write as _,
+};
+
+use fuel_asm::RegId;
+use fuel_tx::{
+    Output,
+    Receipt,
+    ReceiptFormatter,
+};
+use fuel_types::{
+    Bytes32,
+    Word,
+};
+
+use crate::{
+    consts::VM_REGISTER_COUNT,
+    interpreter::{
+        EcalHandler,
+        ExecutableTransaction,
+    },
+    state::ProgramState,
+    storage::{
+        DiffEntry,
+        MemoryStorage,
+    },
+    transactor::Transactor,
+};
+
+/// Rewrites the text of a snapshot, e.g. replacing the values varying between the
+/// runs by placeholders.
+pub type Redaction<'a> = dyn Fn(&str) -> String + 'a;
+
+/// The state, the gas used, the transaction id, the UTXO ids of the inputs, the
+/// outputs, the non-zero registers, the receipts and the storage changes of an
+/// execution, rendered by the `Display` implementation.
+///
+/// The rendering is deterministic: the bytes are rendered in lowercase hex, the
+/// storage changes are sorted by key and the receipts are rendered in full by the
+/// [`ReceiptFormatter`]. The [`Self::redact`]ions apply to the whole rendering.
+pub struct ExecutionSnapshot<'a> {
+    state: Option<ProgramState>,
+    gas_used: Option<Word>,
+    tx_id: Bytes32,
+    utxo_ids: Vec<String>,
+    outputs: Vec<Output>,
+    registers: [Word; VM_REGISTER_COUNT],
+    receipts: Vec<Receipt>,
+    storage: MemoryStorage,
+    baseline: MemoryStorage,
+    redactions: Vec<Box<Redaction<'a>>>,
+}
+
+impl<'a> ExecutionSnapshot<'a> {
+    /// Captures the end state of the last execution of the `transactor`.
+    ///
+    /// The storage changes are the entries of its storage, unless the storage before
+    /// the execution is given with [`Self::since`].
+    pub fn capture<Tx, Ecal>(transactor: &Transactor<MemoryStorage, Tx, Ecal>) -> Self
+    where
+        Tx: ExecutableTransaction,
+        Ecal: EcalHandler,
+    {
+        let interpreter = transactor.interpreter();
+        let tx = interpreter.transaction();
+        let mut registers = [0; VM_REGISTER_COUNT];
+        registers.copy_from_slice(interpreter.registers());
+        Self {
+            state: transactor.state_transition().map(|state| *state.state()),
+            gas_used: interpreter.receipts().iter().find_map(Receipt::gas_used),
+            tx_id: tx.id(&interpreter.interpreter_params().chain_id),
+            utxo_ids: tx
+                .inputs()
+                .iter()
+                .filter_map(|input| input.utxo_id())
+                .map(ToString::to_string)
+                .collect(),
+            outputs: tx.outputs().clone(),
+            registers,
+            receipts: interpreter.receipts().to_vec(),
+            storage: AsRef::<MemoryStorage>::as_ref(transactor).clone(),
+            baseline: MemoryStorage::default(),
+            redactions: Vec::new(),
+        }
+    }
+
+    /// Renders the changes of the storage since the `baseline`, the storage before
+    /// the execution.
+    pub fn since(mut self, baseline: &MemoryStorage) -> Self {
+        self.baseline = baseline.clone();
+        self
+    }
+
+    /// Replaces each occurrence of the rendering of the `value`, like the transaction
+    /// id or a UTXO id, by the `placeholder`.
+    pub fn redact(self, value: impl fmt::Display, placeholder: &'a str) -> Self {
+        let value = value.to_string();
+        self.redact_with(move |text| text.replace(&value, placeholder))
+    }
+
+    /// Rewrites the rendering with the `redaction`, after the previous ones.
+    pub fn redact_with<F>(mut self, redaction: F) -> Self
+    where
+        F: Fn(&str) -> String + 'a,
+    {
+        self.redactions.push(Box::new(redaction));
+        self
+    }
+
+    /// The id of the executed transaction.
+    pub fn tx_id(&self) -> &Bytes32 {
+        &self.tx_id
+    }
+
+    fn write_unredacted(&self, f: &mut String) -> fmt::Result {
+        writeln!(f, "state: {:?}", self.state)?;
+        writeln!(f, "gas_used: {:?}", self.gas_used)?;
+        writeln!(f, "tx_id: {}", self.tx_id)?;
+        writeln!(f, "utxo_ids:")?;
+        for utxo_id in &self.utxo_ids {
+            writeln!(f, "  {utxo_id}")?;
+        }
+        writeln!(f, "outputs:")?;
+        for output in &self.outputs {
+            writeln!(f, "  {output:?}")?;
+        }
+        writeln!(f, "registers:")?;
+        for (register, value) in (0u8..).zip(self.registers.iter()) {
+            if *value != 0 {
+                writeln!(f, "  {} = {value:#x}", RegId::new(register))?;
+            }
+        }
+        writeln!(f, "receipts:")?;
+        ReceiptFormatter::new()
+            .full_data(true)
+            .write(f, &self.receipts)?;
+        writeln!(f, "storage:")?;
+        self.write_storage(f)
+    }
+
+    fn write_storage(&self, f: &mut String) -> fmt::Result {
+        fn entry<V>(
+            f: &mut String,
+            key: fmt::Arguments,
+            (before, after): &DiffEntry<V>,
+            value: impl Fn(&V) -> String,
+        ) -> fmt::Result {
+            let value = |entry: &Option<V>| entry.as_ref().map_or("-".into(), &value);
+            writeln!(f, "  {key}: {} -> {}", value(before), value(after))
+        }
+
+        let diff = self.baseline.diff(&self.storage);
+        for (contract, change) in &diff.contracts {
+            entry(f, format_args!("code {contract}"), change, |code| {
+                hex(code.as_ref())
+            })?;
+        }
+        for (key, change) in &diff.balances {
+            let (contract, asset) = (key.contract_id(), key.asset_id());
+            entry(
+                f,
+                format_args!("balance {contract} {asset}"),
+                change,
+                |balance| balance.to_string(),
+            )?;
+        }
+        for (key, change) in &diff.contract_state {
+            let (contract, slot) = (key.contract_id(), key.state_key());
+            entry(f, format_args!("state {contract} {slot}"), change, |data| {
+                hex(&data.0)
+            })?;
+        }
+        for (version, change) in &diff.consensus_parameters_versions {
+            entry(
+                f,
+                format_args!("consensus_parameters {version}"),
+                change,
+                |parameters| alloc::format!("{parameters:?}"),
+            )?;
+        }
+        for (root, change) in &diff.state_transition_bytecodes {
+            entry(
+                f,
+                format_args!("state_transition_bytecode {root}"),
+                change,
+                |bytecode| alloc::format!("{bytecode:?}"),
+            )?;
+        }
+        for (version, change) in &diff.state_transition_bytecodes_versions {
+            entry(
+                f,
+                format_args!("state_transition_bytecode_version {version}"),
+                change,
+                |root| root.to_string(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+impl fmt::Display for ExecutionSnapshot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut text = String::new();
+        self.write_unredacted(&mut text)?;
+        let text = self
+            .redactions
+            .iter()
+            .fold(text, |text, redaction| redaction(&text));
+        f.write_str(&text)
+    }
+}
+
+impl fmt::Debug for ExecutionSnapshot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,318 @@
+//! Assertions on the sequence of receipts of an execution, declared with the
+//! [`expect_receipts!`](crate::expect_receipts) macro:
+//!
+//! ```rust,ignore
+//! expect_receipts!(receipts, [
+//!     call(to == contract_id),
+//!     ..,
+//!     return_data(data.len() == 32),
+//!     script_result(Success),
+//! ]);
+//! ```
+
+use alloc::{
+    boxed::Box,
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::fmt::{
+    self,
+    Write as _,
+};
+
+use fuel_tx::Receipt;
+
+/// The name of the kind of the `receipt` in the
+/// [`expect_receipts!`](crate::expect_receipts) patterns, `ret` for a `Return`.
+pub fn receipt_kind(receipt: &Receipt) -> &'static str {
+    match receipt {
+        Receipt::Call { .. } => "call",
+        Receipt::Return { .. } => "ret",
+        Receipt::ReturnData { .. } => "return_data",
+        Receipt::Panic { .. } => "panic",
+        Receipt::Revert { .. } => "revert",
+        Receipt::Log { .. } => "log",
+        Receipt::LogData { .. } => "log_data",
+        Receipt::Transfer { .. } => "transfer",
+        Receipt::TransferOut { .. } => "transfer_out",
+        Receipt::ScriptResult { .. } => "script_result",
+        Receipt::MessageOut { .. } => "message_out",
+        Receipt::Mint { .. } => "mint",
+        Receipt::Burn { .. } => "burn",
+        #[allow(unreachable_patterns)]
+        _ => "unknown",
+    }
+}
+
+/// Partial match of a receipt of a kind.
+pub struct ReceiptPattern<'a> {
+    kind: &'static str,
+    description: String,
+    predicate: Box<dyn Fn(&Receipt) -> bool + 'a>,
+}
+
+impl<'a> ReceiptPattern<'a> {
+    /// New pattern matching the receipts of the `kind`, named like by [`receipt_kind`],
+    /// for which the `predicate` holds.
+    pub fn new<F>(
+        kind: &'static str,
+        description: impl Into<String>,
+        predicate: F,
+    ) -> Self
+    where
+        F: Fn(&Receipt) -> bool + 'a,
+    {
+        Self {
+            kind,
+            description: description.into(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Whether the `receipt` matches the pattern.
+    pub fn matches(&self, receipt: &Receipt) -> bool {
+        receipt_kind(receipt) == self.kind && (self.predicate)(receipt)
+    }
+}
+
+impl fmt::Debug for ReceiptPattern<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.kind, self.description)
+    }
+}
+
+/// An item of the [`ExpectedReceipts`].
+#[derive(Debug)]
+pub enum ExpectedReceipt<'a> {
+    /// A receipt matching the pattern.
+    Receipt(ReceiptPattern<'a>),
+    /// Any single receipt, `_` in the macro.
+    Any,
+    /// Any number of receipts, including none, `..` in the macro.
+    AnyNumber,
+}
+
+impl fmt::Display for ExpectedReceipt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Receipt(pattern) => write!(f, "{pattern:?}"),
+            Self::Any => f.write_str("_"),
+            Self::AnyNumber => f.write_str(".."),
+        }
+    }
+}
+
+/// The expected sequence of receipts.
+#[derive(Debug, Default)]
+pub struct ExpectedReceipts<'a> {
+    items: Vec<ExpectedReceipt<'a>>,
+}
+
+/// The receipts don't match the [`ExpectedReceipts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptsMismatch {
+    message: String,
+}
+
+impl fmt::Display for ReceiptsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl<'a> ExpectedReceipts<'a> {
+    /// New empty sequence, matching no receipt.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects a receipt matching the `pattern`.
+    pub fn then(mut self, pattern: ReceiptPattern<'a>) -> Self {
+        self.items.push(ExpectedReceipt::Receipt(pattern));
+        self
+    }
+
+    /// Expects any single receipt.
+    pub fn any(mut self) -> Self {
+        self.items.push(ExpectedReceipt::Any);
+        self
+    }
+
+    /// Expects any number of receipts, including none.
+    pub fn any_number(mut self) -> Self {
+        self.items.push(ExpectedReceipt::AnyNumber);
+        self
+    }
+
+    /// Checks that the `receipts` match the sequence.
+    pub fn check(&self, receipts: &[Receipt]) -> Result<(), ReceiptsMismatch> {
+        let mut matcher = Matcher {
+            items: &self.items,
+            receipts,
+            failed: vec![vec![false; receipts.len() + 1]; self.items.len() + 1],
+            furthest: (0, 0),
+        };
+        if matcher.matches(0, 0, true) {
+            return Ok(())
+        }
+        let (item, receipt) = matcher.furthest;
+        Err(ReceiptsMismatch {
+            message: self.describe(receipts, item, receipt),
+        })
+    }
+
+    /// Panics with the diff of the sequences if the `receipts` don't match.
+    #[track_caller]
+    pub fn assert(&self, receipts: &[Receipt]) {
+        if let Err(mismatch) = self.check(receipts) {
+            panic!("{mismatch}")
+        }
+    }
+
+    fn describe(&self, receipts: &[Receipt], item: usize, receipt: usize) -> String {
+        let marker = |marked| if marked { "  > " } else { "    " };
+        let mut message =
+            String::from("The receipts don't match the expected sequence\n");
+        message.push_str("expected:\n");
+        for (index, expected) in self.items.iter().enumerate() {
+            let _ = writeln!(message, "{}{expected}", marker(index == item));
+        }
+        if item == self.items.len() {
+            let _ = writeln!(message, "{}(end of the expected receipts)", marker(true));
+        }
+        message.push_str("actual:\n");
+        for (index, actual) in receipts.iter().enumerate() {
+            let _ = writeln!(message, "{}[{index}] {actual:?}", marker(index == receipt));
+        }
+        if receipt == receipts.len() {
+            let _ = writeln!(message, "{}(end of the receipts)", marker(true));
+        }
+        message
+    }
+}
+
+/// Backtracking match of the items against the receipts.
+struct Matcher<'e, 'a> {
+    items: &'e [ExpectedReceipt<'a>],
+    receipts: &'e [Receipt],
+    /// The positions already known to fail.
+    failed: Vec<Vec<bool>>,
+    /// The furthest item with its receipt reached without skipping receipts with a
+    /// `..`, reported as the mismatch.
+    furthest: (usize, usize),
+}
+
+impl Matcher<'_, '_> {
+    /// Whether the receipts from the `receipt` match the items from the `item`, where
+    /// `matched` tells if the previous receipts were all matched by explicit items.
+    fn matches(&mut self, item: usize, receipt: usize, matched: bool) -> bool {
+        if matched && (receipt, item) > (self.furthest.1, self.furthest.0) {
+            self.furthest = (item, receipt);
+        }
+        if self.failed[item][receipt] {
+            return false
+        }
+        let next = self.receipts.get(receipt);
+        let matches = match (self.items.get(item), next) {
+            (None, next) => next.is_none(),
+            (Some(ExpectedReceipt::AnyNumber), next) => {
+                self.matches(item + 1, receipt, matched)
+                    || (next.is_some() && self.matches(item, receipt + 1, false))
+            }
+            (Some(_), None) => false,
+            (Some(ExpectedReceipt::Any), Some(_)) => {
+                self.matches(item + 1, receipt + 1, matched)
+            }
+            (Some(ExpectedReceipt::Receipt(pattern)), Some(next)) => {
+                pattern.matches(next) && self.matches(item + 1, receipt + 1, matched)
+            }
+        };
+        self.failed[item][receipt] = !matches;
+        matches
+    }
+}
+
+/// Asserts that the receipts match the expected sequence, panicking with the diff of
+/// the sequences otherwise.
+///
+/// Each item is `_` for any receipt, `..` for any number of receipts,
+/// `script_result(<ScriptExecutionResult variant>)`, `panic(<PanicReason variant>)` or
+/// a kind of receipt named like by
+/// [`receipt_kind`](crate::util::test_helpers::receipt_kind) with comma-separated
+/// comparisons of its fields, e.g. `log(ra == 5, rb > 1)`. A field is read with the
+/// accessor of the same name of the receipt.
+#[macro_export]
+macro_rules! expect_receipts {
+    ($receipts:expr, [$($items:tt)*]) => {
+        $crate::expect_receipts!(
+            @items $crate::util::test_helpers::ExpectedReceipts::new(); $($items)*
+        )
+        .assert(&$receipts)
+    };
+
+    (@items $expected:expr;) => { $expected };
+    (@items $expected:expr; .. $(, $($rest:tt)*)?) => {
+        $crate::expect_receipts!(@items $expected.any_number(); $($($rest)*)?)
+    };
+    (@items $expected:expr; _ $(, $($rest:tt)*)?) => {
+        $crate::expect_receipts!(@items $expected.any(); $($($rest)*)?)
+    };
+    (@items $expected:expr; $kind:ident ($($pattern:tt)*) $(, $($rest:tt)*)?) => {
+        $crate::expect_receipts!(
+            @items $expected.then($crate::expect_receipts!(@pattern $kind($($pattern)*)));
+            $($($rest)*)?
+        )
+    };
+
+    (@pattern script_result($result:ident)) => {
+        $crate::util::test_helpers::ReceiptPattern::new(
+            "script_result",
+            stringify!($result),
+            |receipt| matches!(
+                receipt.result(),
+                Some($crate::fuel_tx::ScriptExecutionResult::$result)
+            ),
+        )
+    };
+    (@pattern panic($reason:ident)) => {
+        $crate::util::test_helpers::ReceiptPattern::new(
+            "panic",
+            stringify!($reason),
+            |receipt| matches!(
+                receipt.reason().map(|reason| *reason.reason()),
+                Some($crate::fuel_asm::PanicReason::$reason)
+            ),
+        )
+    };
+    (@pattern $kind:ident($($conditions:tt)*)) => {
+        $crate::util::test_helpers::ReceiptPattern::new(
+            stringify!($kind),
+            stringify!($($conditions)*),
+            |receipt| $crate::expect_receipts!(@conditions receipt [] $($conditions)*),
+        )
+    };
+
+    // Splits the comma-separated conditions.
+    (@conditions $receipt:ident [$($condition:tt)*]) => {
+        $crate::expect_receipts!(@condition $receipt $($condition)*)
+    };
+    (@conditions $receipt:ident [$($condition:tt)*] , $($rest:tt)*) => {
+        $crate::expect_receipts!(@condition $receipt $($condition)*)
+            && $crate::expect_receipts!(@conditions $receipt [] $($rest)*)
+    };
+    (@conditions $receipt:ident [$($condition:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::expect_receipts!(@conditions $receipt [$($condition)* $next] $($rest)*)
+    };
+
+    (@condition $receipt:ident) => {{
+        let _ = $receipt;
+        true
+    }};
+    (@condition $receipt:ident $field:ident $($comparison:tt)*) => {
+        match $receipt.$field().map(|value| value.to_owned()) {
+            Some($field) => $field $($comparison)*,
+            None => false,
+        }
+    };
+}
@@ -0,0 +1,429 @@
+//! Storage wrapper injecting failures into the calls of the wrapped storage.
+
+use crate::{
+    prelude::{
+        InterpreterError,
+        RuntimeError,
+    },
+    storage::{
+        ContractsAssets,
+        ContractsAssetsStorage,
+        ContractsRawCode,
+        ContractsState,
+        ContractsStateKey,
+        InterpreterStorage,
+        UploadedBytecodes,
+    },
+};
+
+use fuel_storage::{
+    BatchValues,
+    Mappable,
+    StorageBatchInspect,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+use rand::{
+    prelude::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use alloc::{
+    borrow::Cow,
+    vec::Vec,
+};
+use core::cell::{
+    Cell,
+    RefCell,
+};
+
+/// The calls of the storage failed by a [`FaultyStorage`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    /// No call fails.
+    Never,
+    /// The call with the index, counting from zero, fails.
+    OnCall(usize),
+    /// All the calls of the operation fail, e.g. `"ContractsState::insert"` or
+    /// `"block_height"`.
+    OnOperation(&'static str),
+    /// Each call fails with the `probability`, drawn from a random generator seeded
+    /// with the `seed`.
+    Random {
+        /// The seed of the random generator.
+        seed: u64,
+        /// The probability of a call to fail.
+        probability: f64,
+    },
+}
+
+/// The error of a [`FaultyStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaultyStorageError<E> {
+    /// The call failed because of the [`Fault`].
+    Injected {
+        /// The index of the failed call.
+        call: usize,
+        /// The operation of the failed call.
+        operation: &'static str,
+    },
+    /// The wrapped storage failed.
+    Storage(E),
+}
+
+impl<E> From<FaultyStorageError<E>> for InterpreterError<FaultyStorageError<E>> {
+    fn from(val: FaultyStorageError<E>) -> Self {
+        let rt: RuntimeError<FaultyStorageError<E>> = val.into();
+        rt.into()
+    }
+}
+
+impl<E> From<FaultyStorageError<E>> for RuntimeError<FaultyStorageError<E>> {
+    fn from(val: FaultyStorageError<E>) -> Self {
+        RuntimeError::Storage(val)
+    }
+}
+
+/// Storage failing the calls to the wrapped storage selected by the [`Fault`].
+///
+/// Every call of the storage traits used by the interpreter counts as one call,
+/// including the failed ones. The failed calls don't reach the wrapped storage.
+#[derive(Debug)]
+pub struct FaultyStorage<S> {
+    storage: S,
+    fault: Fault,
+    calls: Cell<usize>,
+    rng: RefCell<StdRng>,
+}
+
+impl<S> FaultyStorage<S> {
+    /// Wraps the `storage`, failing the calls selected by the `fault`.
+    pub fn new(storage: S, fault: Fault) -> Self {
+        let seed = match fault {
+            Fault::Random { seed, .. } => seed,
+            _ => 0,
+        };
+        Self {
+            storage,
+            fault,
+            calls: Cell::new(0),
+            rng: RefCell::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// The number of calls made to the storage so far.
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    /// The wrapped storage.
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    /// The wrapped storage, mutably. The calls made directly to it aren't counted.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Consumes the wrapper and returns the wrapped storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    /// Counts the call of the `operation` and fails it if the [`Fault`] selects it.
+    fn call<E>(&self, operation: &'static str) -> Result<(), FaultyStorageError<E>> {
+        match self.injected(operation) {
+            Some(call) => Err(FaultyStorageError::Injected { call, operation }),
+            None => Ok(()),
+        }
+    }
+
+    /// Counts the call of the `operation` and returns its index if the [`Fault`]
+    /// selects it.
+    fn injected(&self, operation: &'static str) -> Option<usize> {
+        let call = self.calls.get();
+        self.calls.set(call.saturating_add(1));
+        let fails = match self.fault {
+            Fault::Never => false,
+            Fault::OnCall(index) => index == call,
+            Fault::OnOperation(failed) => failed == operation,
+            Fault::Random { probability, .. } => {
+                self.rng.borrow_mut().gen_bool(probability)
+            }
+        };
+        fails.then_some(call)
+    }
+}
+
+macro_rules! faulty_table {
+    ($table:ident) => {
+        impl<S: InterpreterStorage> StorageInspect<$table> for FaultyStorage<S> {
+            type Error = FaultyStorageError<S::DataError>;
+
+            fn get(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Cow<'_, <$table as Mappable>::OwnedValue>>, Self::Error>
+            {
+                self.call(concat!(stringify!($table), "::get"))?;
+                StorageInspect::<$table>::get(&self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn contains_key(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<bool, Self::Error> {
+                self.call(concat!(stringify!($table), "::contains_key"))?;
+                StorageInspect::<$table>::contains_key(&self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageMutate<$table> for FaultyStorage<S> {
+            fn insert(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                value: &<$table as Mappable>::Value,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                self.call(concat!(stringify!($table), "::insert"))?;
+                StorageMutate::<$table>::insert(&mut self.storage, key, value)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn remove(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                self.call(concat!(stringify!($table), "::remove"))?;
+                StorageMutate::<$table>::remove(&mut self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+        }
+    };
+}
+
+macro_rules! faulty_bytes_table {
+    ($table:ident) => {
+        faulty_table!($table);
+
+        impl<S: InterpreterStorage> StorageSize<$table> for FaultyStorage<S> {
+            fn size_of_value(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<usize>, Self::Error> {
+                self.call(concat!(stringify!($table), "::size_of_value"))?;
+                StorageSize::<$table>::size_of_value(&self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageRead<$table> for FaultyStorage<S> {
+            fn read(
+                &self,
+                key: &<$table as Mappable>::Key,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                self.call(concat!(stringify!($table), "::read"))?;
+                StorageRead::<$table>::read(&self.storage, key, buf)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn read_alloc(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                self.call(concat!(stringify!($table), "::read_alloc"))?;
+                StorageRead::<$table>::read_alloc(&self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn read_range(
+                &self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &mut [u8],
+            ) -> Result<Option<usize>, Self::Error> {
+                self.call(concat!(stringify!($table), "::read_range"))?;
+                StorageRead::<$table>::read_range(&self.storage, key, offset, buf)
+                    .map_err(FaultyStorageError::Storage)
+            }
+        }
+
+        impl<S: InterpreterStorage> StorageWrite<$table> for FaultyStorage<S> {
+            fn write(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                self.call(concat!(stringify!($table), "::write"))?;
+                StorageWrite::<$table>::write(&mut self.storage, key, buf)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn replace(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                buf: &[u8],
+            ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+                self.call(concat!(stringify!($table), "::replace"))?;
+                StorageWrite::<$table>::replace(&mut self.storage, key, buf)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn take(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Vec<u8>>, Self::Error> {
+                self.call(concat!(stringify!($table), "::take"))?;
+                StorageWrite::<$table>::take(&mut self.storage, key)
+                    .map_err(FaultyStorageError::Storage)
+            }
+
+            fn write_range(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                offset: usize,
+                buf: &[u8],
+            ) -> Result<usize, Self::Error> {
+                self.call(concat!(stringify!($table), "::write_range"))?;
+                StorageWrite::<$table>::write_range(&mut self.storage, key, offset, buf)
+                    .map_err(FaultyStorageError::Storage)
+            }
+        }
+    };
+}
+
+faulty_bytes_table!(ContractsRawCode);
+faulty_bytes_table!(ContractsState);
+faulty_table!(ContractsAssets);
+faulty_table!(UploadedBytecodes);
+
+impl<S: InterpreterStorage> StorageBatchInspect<ContractsState> for FaultyStorage<S> {
+    fn get_batch<'a, 'k, Iter>(
+        &'a self,
+        keys: Iter,
+    ) -> BatchValues<'a, ContractsState, Self::Error>
+    where
+        Iter: IntoIterator<Item = &'k ContractsStateKey>,
+    {
+        let operation = "ContractsState::get_batch";
+        if let Some(call) = self.injected(operation) {
+            return keys
+                .into_iter()
+                .map(|_| Err(FaultyStorageError::Injected { call, operation }))
+                .collect();
+        }
+        StorageBatchInspect::<ContractsState>::get_batch(&self.storage, keys)
+            .into_iter()
+            .map(|value| value.map_err(FaultyStorageError::Storage))
+            .collect()
+    }
+}
+
+impl<S: InterpreterStorage> ContractsAssetsStorage for FaultyStorage<S> {}
+
+impl<S: InterpreterStorage> InterpreterStorage for FaultyStorage<S> {
+    type DataError = FaultyStorageError<S::DataError>;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.call("block_height")?;
+        self.storage
+            .block_height()
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        self.call("consensus_parameters_version")?;
+        self.storage
+            .consensus_parameters_version()
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        self.call("state_transition_version")?;
+        self.storage
+            .state_transition_version()
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.call("timestamp")?;
+        self.storage
+            .timestamp(height)
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.call("block_hash")?;
+        self.storage
+            .block_hash(block_height)
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.call("coinbase")?;
+        self.storage.coinbase().map_err(FaultyStorageError::Storage)
+    }
+
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        self.call("set_consensus_parameters")?;
+        self.storage
+            .set_consensus_parameters(version, consensus_parameters)
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        self.call("set_state_transition_bytecode")?;
+        self.storage
+            .set_state_transition_bytecode(version, hash)
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn contract_state_insert_range<'a, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        self.call("contract_state_insert_range")?;
+        self.storage
+            .contract_state_insert_range(contract, start_key, values)
+            .map_err(FaultyStorageError::Storage)
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        self.call("contract_state_remove_range")?;
+        self.storage
+            .contract_state_remove_range(contract, start_key, range)
+            .map_err(FaultyStorageError::Storage)
+    }
+}
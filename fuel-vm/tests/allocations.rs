@@ -0,0 +1,139 @@
+//! Counts the heap allocations of the execution of the contract calls.
+//!
+//! The test binary has its own global allocator, counting the allocations of each
+//! thread, so it is kept out of the unit tests of the crate.
+
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+    StorageSlot,
+};
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    script_with_data_offset,
+    util::test_helpers::TestBuilder,
+};
+use std::{
+    alloc::{
+        GlobalAlloc,
+        Layout,
+        System,
+    },
+    cell::Cell,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+// SAFETY: Forwards to the system allocator, only counting the allocations.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The number of allocations of the current thread made by `f`.
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+const CALLS: usize = 10;
+const MAX_SLOTS: u8 = 64;
+
+/// Deploys a contract reading its `slots` slots from the key `[0; 32]`, and builds a
+/// script calling it [`CALLS`] times.
+fn setup(slots: u8) -> (MemoryStorage, Checked<Script>) {
+    // The heap has the same size for any number of slots.
+    let len = Bytes32::LEN as u32 * u32::from(MAX_SLOTS);
+    let contract = vec![
+        op::movi(0x10, len + Bytes32::LEN as u32),
+        op::aloc(0x10),
+        op::movi(0x11, slots.into()),
+        op::addi(0x12, RegId::HP, Bytes32::LEN as u16),
+        op::srwq(0x12, 0x13, RegId::HP, 0x11),
+        op::ret(RegId::ONE),
+    ];
+    let state = (0..slots)
+        .map(|slot| {
+            let mut key = Bytes32::zeroed();
+            key[31] = slot;
+            StorageSlot::new(key, Bytes32::new([slot; 32]))
+        })
+        .collect();
+
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(contract, None, Some(state))
+        .contract_id;
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        [op::movi(0x10, data_offset as Immediate18)]
+            .into_iter()
+            .chain([op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS); CALLS])
+            .chain([op::ret(RegId::ONE)])
+            .collect::<Vec<Instruction>>(),
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = Call::new(contract_id, 0, 0).to_bytes();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(10_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    (test_context.get_storage().clone(), tx)
+}
+
+/// The receipts of the `tx` and the allocations of its execution.
+fn transact(storage: MemoryStorage, tx: Checked<Script>) -> (Vec<Receipt>, u64) {
+    let consensus_params = ConsensusParameters::standard();
+    let mut transactor =
+        Transactor::<_, _>::new(storage, InterpreterParams::new(0, &consensus_params));
+    let (receipts, count) =
+        allocations(|| transactor.transact(tx).receipts().unwrap().to_vec());
+    assert_eq!(receipts.len(), CALLS * 2 + 2, "{receipts:?}");
+    (receipts, count)
+}
+
+#[test]
+fn srwq__allocates_the_same_for_any_number_of_slots() {
+    // Given
+    let (one_slot, one_slot_tx) = setup(1);
+    let (many_slots, many_slots_tx) = setup(MAX_SLOTS);
+
+    // When
+    let (_, one_slot_allocations) = transact(one_slot, one_slot_tx);
+    let (_, many_slots_allocations) = transact(many_slots, many_slots_tx);
+
+    // Then
+    assert_eq!(many_slots_allocations, one_slot_allocations);
+}
@@ -0,0 +1,342 @@
+//! Checks the `tracing` spans and events of the execution.
+//!
+//! The spans are collected by a layer of the `tracing-subscriber` registry, set as the
+//! default subscriber of the thread executing the transaction.
+
+#![cfg(feature = "tracing")]
+#![allow(non_snake_case)]
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::{
+        CheckPredicateParams,
+        CheckPredicates,
+        EstimatePredicates,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+    util::test_helpers::TestBuilder,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use tracing::{
+    field::{
+        Field,
+        Visit,
+    },
+    span,
+    Event,
+    Level,
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{
+        Context,
+        SubscriberExt,
+    },
+    registry::LookupSpan,
+    Layer,
+};
+
+type Fields = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RecordedSpan {
+    name: &'static str,
+    /// The index of the parent span in the recorded spans.
+    parent: Option<usize>,
+    fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RecordedEvent {
+    level: Level,
+    /// The index of the parent span in the recorded spans.
+    parent: Option<usize>,
+    fields: Fields,
+}
+
+#[derive(Debug, Default)]
+struct Recorded {
+    spans: Vec<RecordedSpan>,
+    events: Vec<RecordedEvent>,
+}
+
+/// The index of a span in the recorded spans, stored in its extensions.
+struct SpanIndex(usize);
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Records the spans with their parent, and the events with their span.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Recorded>>);
+
+impl Recorder {
+    fn take(&self) -> Recorded {
+        core::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl<S> Layer<S> for Recorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &span::Attributes<'_>,
+        id: &span::Id,
+        ctx: Context<'_, S>,
+    ) {
+        let span = ctx.span(id).expect("The span is open");
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanIndex>().map(|i| i.0));
+        let mut fields = Fields::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let mut recorded = self.0.lock().unwrap();
+        span.extensions_mut()
+            .insert(SpanIndex(recorded.spans.len()));
+        recorded.spans.push(RecordedSpan {
+            name: attrs.metadata().name(),
+            parent,
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("The span is open");
+        let extensions = span.extensions();
+        let index = extensions
+            .get::<SpanIndex>()
+            .expect("The span is recorded")
+            .0;
+        values.record(&mut FieldVisitor(
+            &mut self.0.lock().unwrap().spans[index].fields,
+        ));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let parent = ctx
+            .event_span(event)
+            .and_then(|span| span.extensions().get::<SpanIndex>().map(|i| i.0));
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().events.push(RecordedEvent {
+            level: *event.metadata().level(),
+            parent,
+            fields,
+        });
+    }
+}
+
+/// Runs `f` with the recorder as the default subscriber, returning the recorded spans
+/// and events.
+fn record<R>(f: impl FnOnce() -> R) -> (R, Recorded) {
+    let recorder = Recorder::default();
+    let subscriber = tracing_subscriber::registry().with(recorder.clone());
+    let result = tracing::subscriber::with_default(subscriber, f);
+    (result, recorder.take())
+}
+
+#[test]
+fn transact__opens_the_spans_of_the_nested_calls_inside_the_transaction() {
+    // Given
+    let mut test_context = TestBuilder::new(2322u64);
+    let inner = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let call_len = Call::new(inner, 0, 0).to_bytes().len();
+    let outer = test_context
+        .setup_contract(
+            vec![
+                op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+                op::addi(0x10, 0x10, call_len as Immediate12),
+                op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let mut script_data = Call::new(outer, 0, 0).to_bytes();
+    script_data.extend(Call::new(inner, 0, 0).to_bytes());
+    let gas_limit = 1_000_000;
+    let consensus_params = ConsensusParameters::standard();
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(gas_limit)
+        .contract_input(outer)
+        .contract_input(inner)
+        .fee_input()
+        .contract_output(&outer)
+        .contract_output(&inner)
+        .build();
+    let tx_id = tx.id();
+    let tx = tx
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, &consensus_params),
+    );
+
+    // When
+    let (_, recorded) = record(|| {
+        vm.transact(tx).unwrap();
+    });
+
+    // Then
+    let spans = recorded.spans;
+    assert_eq!(
+        spans
+            .iter()
+            .map(|span| (span.name, span.parent))
+            .collect::<Vec<_>>(),
+        [("transact", None), ("call", Some(0)), ("call", Some(1))]
+    );
+    assert_eq!(spans[0].fields["tx_id"], tx_id.to_string());
+    assert_eq!(spans[0].fields["gas_limit"], gas_limit.to_string());
+    assert_eq!(spans[1].fields["contract_id"], outer.to_string());
+    assert_eq!(spans[1].fields["depth"], "1");
+    assert_eq!(spans[2].fields["contract_id"], inner.to_string());
+    assert_eq!(spans[2].fields["depth"], "2");
+    let outer_gas: Word = spans[1].fields["gas"].parse().unwrap();
+    let inner_gas: Word = spans[2].fields["gas"].parse().unwrap();
+    assert!(0 < inner_gas && inner_gas < outer_gas && outer_gas < gas_limit);
+    let executed = recorded
+        .events
+        .iter()
+        .find(|event| event.fields.get("message").map(String::as_str) == Some("executed"))
+        .expect("The end of the execution is recorded");
+    assert_eq!(executed.parent, Some(0));
+    assert_eq!(executed.fields["state"], "Return(1)");
+}
+
+#[test]
+fn transact__records_the_panic_of_the_script_in_the_transaction() {
+    // Given
+    let consensus_params = ConsensusParameters::standard();
+    let script = vec![op::div(0x10, RegId::ONE, RegId::ZERO), op::ret(RegId::ONE)];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate checked tx")
+        .into_ready(
+            0,
+            consensus_params.gas_costs(),
+            consensus_params.fee_params(),
+        )
+        .unwrap();
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+
+    // When
+    let (_, recorded) = record(|| {
+        vm.transact(tx).unwrap();
+    });
+
+    // Then
+    let panics: Vec<_> = recorded
+        .events
+        .iter()
+        .filter(|event| event.fields.get("message").map(String::as_str) == Some("panic"))
+        .collect();
+    assert_eq!(panics.len(), 1);
+    assert_eq!(panics[0].level, Level::WARN);
+    assert_eq!(panics[0].parent, Some(0));
+    assert_eq!(panics[0].fields["reason"], "ArithmeticError");
+    assert_eq!(panics[0].fields["contract_id"], "None");
+    let traced: Vec<_> = recorded
+        .events
+        .iter()
+        .filter(|event| event.level == Level::TRACE)
+        .collect();
+    if cfg!(feature = "trace-instructions") {
+        assert_eq!(traced.len(), 1);
+        assert_eq!(traced[0].parent, Some(0));
+    } else {
+        assert!(traced.is_empty());
+    }
+}
+
+#[test]
+fn check_predicates__opens_a_span_per_predicate_input() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    // Given
+    let predicate: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let owner = Input::predicate_owner(&predicate);
+    let mut tx = TransactionBuilder::script(vec![], vec![])
+        .script_gas_limit(1_000_000)
+        .add_random_fee_input()
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            1_000,
+            rng.gen(),
+            rng.gen(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize();
+    let consensus_params = ConsensusParameters::standard();
+    let params = CheckPredicateParams::from(&consensus_params);
+    tx.estimate_predicates(&params).unwrap();
+    let checked = tx
+        .into_checked_basic(Default::default(), &consensus_params)
+        .unwrap();
+
+    // When
+    let (result, recorded) = record(|| checked.check_predicates(&params));
+
+    // Then
+    result.expect("The predicate is valid");
+    assert_eq!(recorded.spans.len(), 1);
+    let span = &recorded.spans[0];
+    assert_eq!(span.name, "predicate");
+    assert_eq!(span.parent, None);
+    assert_eq!(span.fields["index"], "1");
+    assert_eq!(span.fields["action"], "Verifying");
+    let verified = recorded
+        .events
+        .iter()
+        .find(|event| event.fields.get("message").map(String::as_str) == Some("verified"))
+        .expect("The verification is recorded");
+    assert_eq!(verified.parent, Some(0));
+    assert_eq!(verified.fields["success"], "true");
+}